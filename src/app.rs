@@ -1,27 +1,106 @@
+use std::collections::HashMap;
+use std::process::ExitStatus;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use color_eyre::Result;
 use color_eyre::eyre::eyre;
-use tracing::{debug, error, warn};
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::widgets::{Block, Paragraph};
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tracing::{debug, error, info, warn};
 
-use crate::cli::Args;
-use crate::commands::Command;
-use crate::config::{AppConfig, GlobalAction, KeyResolver, save_last_context, save_theme};
-use crate::context::{CloudContext, ContextSelectorView, load_contexts};
-use crate::registry::{ServiceId, ServiceRegistry};
-use crate::service::{Service, ServiceMsg, ServiceSelectorView};
-use crate::theme::{ThemeEvent, ThemeInfo, ThemeSelectorView};
-use crate::tui::{Event, Tui};
+use crate::cli::{Args, CliCommand};
+use crate::commands::{
+    CheckUpdateCmd,
+    Command,
+    CopyToClipboardCmd,
+    LoadHierarchyCmd,
+    ShellCmd,
+    ValidateContextCmd,
+    WarmUpCmd,
+    shell,
+};
+use crate::config::conflicts::find_conflicts;
+use crate::config::{
+    self,
+    ActionContext,
+    ActionMode,
+    AppConfig,
+    AutoSave,
+    CommandRecord,
+    GlobalAction,
+    KeyResolver,
+    LowBandwidth,
+    Workspace,
+    available_profiles,
+    load_with_profile,
+    save_profile,
+};
+use crate::context::{
+    AuthMethod,
+    CloudContext,
+    ContextEvent,
+    ContextHealth,
+    ContextSelectorView,
+    GcpContext,
+    load_contexts,
+    save_contexts,
+};
+use crate::error::Error;
+use crate::i18n::{self, Locale, t, t_args};
+use crate::middleware::{Metrics, Middleware, MiddlewareStack};
+use crate::provider::gcp::{HierarchyEvent, HierarchyNode, ProjectBrowserView};
+use crate::registry::{ResourceLink, ServiceId, ServiceRegistry, WarmClient};
+use crate::service::{Service, ServiceMsg, ServiceSelectorView, ServiceStatus};
+use crate::startup::StartupProfile;
+use crate::theme::{ThemeEvent, ThemeInfo, ThemeSelectorView, no_color_requested, theme_from_name};
+use crate::tui::{DrawTimer, Event, Tui};
+use crate::ui::widgets::set_reduced_motion;
 use crate::ui::{
-    CommandId, CommandPanel, Component, ErrorDialog, ErrorDialogEvent, EventResult, HelpEvent,
-    HelpOverlay, KeybindingSection, Screen, StatusBar, Toast, ToastManager, ToastType,
+    AccountEvent,
+    AccountSelectorView,
+    BookmarkEvent,
+    BookmarksView,
+    CommandHistoryView,
+    CommandId,
+    CommandPanel,
+    Component,
+    ErrorDialog,
+    ErrorDialogEvent,
+    EventResult,
+    FocusManager,
+    HelpEvent,
+    HelpOverlay,
+    HistoryEvent,
+    Keybinding,
+    KeybindingSection,
+    OUTPUT_PANE_HEIGHT,
+    OutputPane,
+    OutputPaneEvent,
+    ProfileEvent,
+    ProfileSelectorView,
+    QuitDialog,
+    QuitEvent,
+    ReleaseNotesEvent,
+    ReleaseNotesOverlay,
+    Screen,
+    StatsEvent,
+    StatsOverlay,
+    StatusBar,
+    Toast,
+    ToastManager,
+    ToastType,
+    WorkspaceEvent,
+    WorkspacesView,
 };
-use crate::{Theme, context};
+use crate::{Theme, changelog, clipboard, context, cost, time};
+
+/// Frames per second, and fewer when rendering for a slow connection.
+const FRAME_RATE: f64 = 60.0;
+const LOW_BANDWIDTH_FRAME_RATE: f64 = 10.0;
 
 #[derive(Debug, Clone)]
 pub enum AppMessage {
@@ -29,28 +108,95 @@ pub enum AppMessage {
     Render,
     Resize(u16, u16),
     Suspend,
-    Resume,
     Quit,
-    ClearScreen,
 
-    DisplayError(String),
+    DisplayError(Error),
     DisplayHelp,
     DisplayThemeSelector,
+    DisplayProfileSelector,
+    DisplayAccountSelector,
+    DisplayStats,
+    DisplayHistory,
+    /// Run a command from the history again.
+    RetryCommand(CommandRecord),
+    DisplayBookmarks,
+    /// Bookmark the service and resource on display.
+    AddBookmark,
+    DeleteBookmark(ResourceLink),
+    DisplayWorkspaces,
+    /// Save the open services as the named workspace.
+    SaveWorkspace(String),
+    RestoreWorkspace(Workspace),
+    DeleteWorkspace(String),
+    DisplayReleaseNotes,
     ClosePopup,
 
     CommandCompleted {
         id: CommandId,
         success: bool,
     },
+    /// A running command, e.g. a [`StreamCmd`], queued messages for the
+    /// service that should not wait until it completes.
+    ///
+    /// [`StreamCmd`]: crate::commands::StreamCmd
+    CommandEmitted,
     ToggleCommandStatus,
     ShowToast {
         message: String,
         toast_type: ToastType,
     },
+    /// Append a command's output to the output pane.
+    AppendOutput {
+        title: String,
+        output: String,
+        success: bool,
+    },
+    ToggleOutput,
+    /// Run a shell command with the TUI suspended.
+    RunForeground {
+        command: String,
+        wait: bool,
+    },
+
+    /// A background client initialization started by a context switch finished.
+    ServiceWarmedUp {
+        context: String,
+        service: ServiceId,
+        result: Result<WarmClient, String>,
+        elapsed: Duration,
+    },
+
+    /// A background check of a context's credentials and project finished.
+    ContextValidated {
+        context: String,
+        health: ContextHealth,
+    },
+
+    /// A release newer than this build was published.
+    UpdateAvailable {
+        version: String,
+        notes: String,
+    },
+
+    /// Open the project browser with the credentials of a context.
+    BrowseProjects(Option<CloudContext>),
+    /// A level of the project browser was listed.
+    HierarchyLoaded {
+        parent: Option<String>,
+        result: Result<Vec<HierarchyNode>, Error>,
+    },
 
     SelectContext(CloudContext),
     SelectService(ServiceId),
+    /// Switch to a resource of another service, see [`ResourceLink`].
+    OpenLink(ResourceLink),
     SelectTheme(ThemeInfo),
+    /// Switch to a config profile; `None` is the base config.
+    SelectProfile(Option<String>),
+    /// Use the active context with another gcloud account.
+    SelectAccount(String),
+    /// Swap the active service with the one used before it.
+    SwitchPrevious,
     GoBack,
 }
 
@@ -58,16 +204,40 @@ pub enum AppMessage {
 enum AppState {
     /// Selecting a cloud context (GCP project, AWS account, etc.)
     SelectingContext(ContextSelectorView),
+    /// Picking a project from the organizations and folders above it
+    BrowsingProjects(ProjectBrowserView),
     /// Selecting a service within the chosen context
     SelectingService(ServiceSelectorView),
     /// Using an active cloud service
     ActiveService(Box<dyn Service>),
 }
 
+/// A service left with its view stack intact, to switch back to.
+struct ParkedService {
+    context: CloudContext,
+    service_id: ServiceId,
+    service: Box<dyn Service>,
+}
+
+/// Panes that take turns at receiving keys while the output pane is open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pane {
+    View,
+    Output,
+}
+
 enum ActivePopup {
     Help(HelpOverlay),
     ThemeSelector(ThemeSelectorView),
+    ProfileSelector(ProfileSelectorView),
+    AccountSelector(AccountSelectorView),
+    Stats(StatsOverlay),
+    History(Box<CommandHistoryView>),
+    Bookmarks(Box<BookmarksView>),
+    Workspaces(Box<WorkspacesView>),
+    ReleaseNotes(ReleaseNotesOverlay),
     Error(ErrorDialog),
+    Quit(QuitDialog),
 }
 
 pub struct App {
@@ -76,44 +246,198 @@ pub struct App {
     popup: Option<ActivePopup>,
     status_bar: StatusBar,
     command_tracker: CommandPanel,
+    output_pane: OutputPane,
+    focus: FocusManager<Pane>,
     toast_manager: ToastManager,
     should_quit: bool,
     should_suspend: bool,
     active_context: Option<CloudContext>,
+    /// Service shown in [`AppState::ActiveService`].
+    active_service: Option<ServiceId>,
+    /// The service used before the active one, see [`Self::switch_previous`].
+    previous: Option<ParkedService>,
+    /// Bookmarked links, newest first, see [`ResourceLink`].
+    bookmarks: Vec<String>,
+    workspaces: Vec<Workspace>,
+    /// Last context, services and theme, saved once they stop changing.
+    autosave: AutoSave,
+    /// Name of the context the service statuses below belong to.
+    warm_context: Option<String>,
+    service_status: HashMap<ServiceId, ServiceStatus>,
+    /// Last known health of each context, by name.
+    context_health: HashMap<String, ContextHealth>,
     registry: Arc<ServiceRegistry>,
     msg_tx: UnboundedSender<AppMessage>,
     msg_rx: UnboundedReceiver<AppMessage>,
     config: Arc<AppConfig>,
     resolver: Arc<KeyResolver>,
+    /// Config profile applied over the base config, if any.
+    active_profile: Option<String>,
     pending_service: Option<String>,
+    /// Running with demo contexts from `--demo`.
+    demo: bool,
+    profile: StartupProfile,
+    /// Commands spawned before the first frame; `None` once it has rendered.
+    deferred_commands: Option<Vec<Box<dyn Command>>>,
+    middleware: MiddlewareStack,
+    /// Collected for the stats overlay, unless disabled in the config.
+    metrics: Option<Arc<Metrics>>,
+    /// Version and notes of a newer release, once the update check found one.
+    available_update: Option<(String, String)>,
+    /// Whether rendering is cheap for a slow connection; `Auto` while
+    /// watching how long drawing takes.
+    low_bandwidth: LowBandwidth,
+    draw_timer: DrawTimer,
 }
 
 impl App {
+    /// Create the app without touching the network.
+    ///
+    /// Commands queued before the first frame is drawn (e.g. client
+    /// initialization for a service passed on the command line) are held
+    /// back until [`run`](Self::run) has rendered it.
     pub fn new(
         registry: ServiceRegistry,
+        contexts: Vec<CloudContext>,
         config: Arc<AppConfig>,
+        active_profile: Option<String>,
         resolver: Arc<KeyResolver>,
         theme: Theme,
-    ) -> Result<Self> {
+        profile: StartupProfile,
+    ) -> Self {
         let (msg_tx, msg_rx) = mpsc::unbounded_channel();
+        let mut status_bar = StatusBar::new(config.status_bar.clone(), resolver.clone());
+        status_bar.set_profile(active_profile.clone());
+        let contexts = contexts
+            .into_iter()
+            .filter(|c| config.shows_context(c.name()))
+            .collect();
+        let (middleware, metrics) = MiddlewareStack::from_config(&config.diagnostics);
+        let state = config::load_state();
+        let command_tracker =
+            CommandPanel::new().with_history(state.command_history, config.history.size);
+        let low_bandwidth = config.display.low_bandwidth;
+        let theme = if low_bandwidth == LowBandwidth::On {
+            theme.with_plain_borders()
+        } else {
+            theme
+        };
 
-        Ok(Self {
-            state: AppState::SelectingContext(ContextSelectorView::new(resolver.clone())?),
+        let mut app = Self {
+            state: AppState::SelectingContext(ContextSelectorView::with_contexts(
+                Vec::new(),
+                &HashMap::new(),
+                resolver.clone(),
+            )),
             theme,
             popup: None,
-            status_bar: StatusBar::new(resolver.clone()),
-            command_tracker: CommandPanel::new(),
+            status_bar,
+            command_tracker,
+            output_pane: OutputPane::new(resolver.clone()),
+            focus: FocusManager::new(vec![Pane::View, Pane::Output]),
             toast_manager: ToastManager::new(),
             should_quit: false,
             should_suspend: false,
             active_context: None,
+            active_service: None,
+            previous: None,
+            bookmarks: state.bookmarks,
+            workspaces: state.workspaces,
+            autosave: AutoSave::default(),
+            warm_context: None,
+            service_status: HashMap::new(),
+            context_health: HashMap::new(),
             registry: Arc::new(registry),
             msg_tx,
             msg_rx,
             config,
             resolver,
+            active_profile,
             pending_service: None,
-        })
+            demo: false,
+            profile,
+            deferred_commands: Some(Vec::new()),
+            middleware,
+            metrics,
+            available_update: None,
+            low_bandwidth,
+            draw_timer: DrawTimer::default(),
+        };
+        app.go_to_filtered_context_selection(contexts);
+        app.check_key_conflicts();
+        app
+    }
+
+    /// Warn about keys that trigger another action than configured.
+    fn check_key_conflicts(&mut self) {
+        let conflicts = find_conflicts(&self.resolver.keybindings);
+        if conflicts.is_empty() {
+            return;
+        }
+        let lines: Vec<String> = conflicts.iter().map(ToString::to_string).collect();
+        for line in &lines {
+            warn!("Key conflict in {line}");
+        }
+        let message = format!(
+            "{}\n\n{}",
+            t("Some keys are bound to several actions, only the first one runs:"),
+            lines.join("\n")
+        );
+        self.popup = Some(ActivePopup::Error(
+            ErrorDialog::new(message, self.resolver.clone()).with_title("Key conflicts"),
+        ));
+    }
+
+    /// Show what changed since the version that ran last, once after an
+    /// upgrade. Key conflicts found at startup are shown instead.
+    pub fn show_whats_new(&mut self) {
+        let Some(notes) = changelog::take_upgrade_notes() else {
+            return;
+        };
+        if self.config.display.whats_new && self.popup.is_none() {
+            self.popup = Some(ActivePopup::ReleaseNotes(ReleaseNotesOverlay::new(
+                t("What's New"),
+                notes,
+                self.resolver.clone(),
+            )));
+        }
+    }
+
+    /// Look for a newer release in the background, if enabled.
+    pub fn check_for_update(&mut self) {
+        if self.config.updates.check && !self.demo {
+            self.spawn_commands(vec![Box::new(CheckUpdateCmd)]);
+        }
+    }
+
+    fn handle_update_available(&mut self, version: String, notes: String) {
+        let message = t_args(
+            "lazycloud {release} is available, press {shortcut} for its notes",
+            &[
+                ("release", &version),
+                (
+                    "shortcut",
+                    &self.resolver.display_global(GlobalAction::ReleaseNotes),
+                ),
+            ],
+        );
+        self.toast_manager
+            .show(Toast::info(message).with_duration(Duration::from_secs(15)));
+        self.available_update = Some((version, notes));
+    }
+
+    /// Notes of the available update, or the changelog of this build.
+    fn open_release_notes(&mut self) {
+        self.toast_manager.clear();
+        let (title, notes) = match &self.available_update {
+            Some((version, notes)) => (format!("lazycloud {version}"), notes.clone()),
+            None => (t("What's New").to_string(), changelog::notes()),
+        };
+        self.popup = Some(ActivePopup::ReleaseNotes(ReleaseNotesOverlay::new(
+            title,
+            notes,
+            self.resolver.clone(),
+        )));
     }
 
     /// Initialize app state based on CLI args.
@@ -121,10 +445,40 @@ impl App {
     /// - Both context and service provided: go directly to service
     /// - Only context provided: go to service selection
     /// - Only service provided: use last context if compatible, else show filtered context selector
-    /// - Neither provided: normal flow (context selection)
+    /// - Neither provided: normal flow (context selection), or where the
+    ///   last session ended if `session.restore` is enabled
     ///
+    /// A workspace given with `--workspace` is restored instead. A link given
+    /// to `lazycloud open` is opened in the context it names, or
+    /// else the one given with `-c` or the last one.
     pub fn apply_cli_args(&mut self, args: &Args) -> Result<()> {
-        let contexts = load_contexts();
+        self.demo = args.demo;
+        if no_color_requested(args.no_color) {
+            self.theme = Theme::no_color();
+        }
+        let contexts = self.known_contexts();
+
+        if let Some(name) = &args.workspace {
+            let workspace = self
+                .workspaces
+                .iter()
+                .find(|workspace| &workspace.name == name)
+                .cloned()
+                .ok_or_else(|| eyre!("No workspace named '{name}'"))?;
+            return self.restore_workspace(&workspace);
+        }
+
+        if let Some(CliCommand::Open { link }) = &args.command {
+            let link: ResourceLink = link.parse()?;
+            let ctx_name = link
+                .context
+                .clone()
+                .or_else(|| args.context.clone())
+                .or_else(|| config::load_state().last_context)
+                .ok_or_else(|| eyre!("No context to open {link} in, pass one with -c"))?;
+            let context = context::find_by_name(&contexts, &ctx_name)?;
+            return self.start_link(&context, &link);
+        }
 
         match (&args.context, &args.service) {
             (Some(ctx_name), Some(svc_name)) => {
@@ -142,9 +496,10 @@ impl App {
                 let provider = self.registry.find_provider_by_name(svc_name)?;
 
                 // Try last context if compatible
-                if let Some(ctx_name) = &self.config.last_context
+                if let Some(ctx_name) = &config::load_state().last_context
                     && let Ok(context) = context::find_by_name(&contexts, ctx_name)
                     && context.provider() == provider
+                    && self.config.shows_context(ctx_name)
                 {
                     let service_id = self.registry.find_service_by_name(&context, svc_name)?;
                     self.start_service(&context, &service_id);
@@ -154,7 +509,7 @@ impl App {
                 // Last context incompatible or missing: show filtered context selector
                 let filtered: Vec<_> = contexts
                     .into_iter()
-                    .filter(|c| c.provider() == provider)
+                    .filter(|c| c.provider() == provider && self.config.shows_context(c.name()))
                     .collect();
 
                 if filtered.is_empty() {
@@ -165,32 +520,290 @@ impl App {
                 self.go_to_filtered_context_selection(filtered);
             }
 
-            (None, None) => {}
+            (None, None) => {
+                if self.config.session.restore
+                    && !self.demo
+                    && let Some(last) = config::load_state().last_location
+                    && let Err(err) = self.restore_location(&contexts, &last)
+                {
+                    warn!("Failed to restore {last}: {err}");
+                }
+            }
         }
         Ok(())
     }
 
+    /// Reopen `last`, the link to where the last session ended.
+    fn restore_location(&mut self, contexts: &[CloudContext], last: &str) -> Result<()> {
+        let link: ResourceLink = last.parse()?;
+        let ctx_name = link
+            .context
+            .as_deref()
+            .ok_or_else(|| eyre!("The link names no context"))?;
+        let context = context::find_by_name(contexts, ctx_name)?;
+        self.start_link(&context, &link)
+    }
+
+    /// Start the service of `link` in `context`, showing its resource.
+    fn start_link(&mut self, context: &CloudContext, link: &ResourceLink) -> Result<()> {
+        self.ensure_available(context, &link.service)?;
+        self.start_service_at(context, &link.service, link.resource.as_deref());
+        Ok(())
+    }
+
+    fn ensure_available(&self, context: &CloudContext, service_id: &ServiceId) -> Result<()> {
+        if self.registry.is_available(service_id, context) {
+            return Ok(());
+        }
+        Err(eyre!(
+            "{service_id} is not available in '{}'",
+            context.name()
+        ))
+    }
+
     fn start_service(&mut self, context: &CloudContext, service_id: &ServiceId) {
+        self.start_service_at(context, service_id, None);
+    }
+
+    /// Start a service and have it show `resource` once loaded.
+    fn start_service_at(
+        &mut self,
+        context: &CloudContext,
+        service_id: &ServiceId,
+        resource: Option<&str>,
+    ) {
+        // A fresh copy replaces the one kept for switching back
+        if let Some(mut previous) = self.previous.take_if(|previous| {
+            previous.context.name() == context.name() && &previous.service_id == service_id
+        }) {
+            previous.service.destroy();
+        }
         self.active_context = Some(context.clone());
+        self.active_service = Some(service_id.clone());
         self.status_bar.set_active_context(context.clone());
         if let Some(provider) = self.registry.get(service_id) {
-            let service = provider.create_service(context, self.resolver.clone());
-            self.go_to_active_service(service);
+            let service = provider.create_service(
+                context,
+                self.config.clone(),
+                self.resolver.clone(),
+                self.warm_client(service_id),
+            );
+            self.go_to_active_service(service, resource);
+        }
+    }
+
+    /// Initialize clients for all services of a context in parallel.
+    ///
+    /// Services that are already warming up or ready for this context are
+    /// skipped, so returning to the selector only retries failed ones.
+    fn warm_up_services(&mut self, context: &CloudContext) {
+        if self.warm_context.as_deref() != Some(context.name()) {
+            self.warm_context = Some(context.name().to_string());
+            self.service_status.clear();
+        }
+
+        let mut commands: Vec<Box<dyn Command>> = Vec::new();
+        for provider in self.registry.available_services(context) {
+            let id = provider.service_id();
+            if matches!(
+                self.service_status.get(&id),
+                Some(ServiceStatus::Warming | ServiceStatus::Ready(_))
+            ) {
+                continue;
+            }
+            if let Some(future) = provider.warm_up(context) {
+                self.service_status
+                    .insert(id.clone(), ServiceStatus::Warming);
+                commands.push(Box::new(WarmUpCmd::new(
+                    context.name(),
+                    id,
+                    provider.display_name(),
+                    future,
+                )));
+            }
+        }
+        self.spawn_commands(commands);
+    }
+
+    fn warm_client(&self, id: &ServiceId) -> Option<WarmClient> {
+        match self.service_status.get(id) {
+            Some(ServiceStatus::Ready(client)) => Some(client.clone()),
+            _ => None,
+        }
+    }
+
+    fn handle_service_warmed_up(
+        &mut self,
+        context: &str,
+        service: &ServiceId,
+        result: Result<WarmClient, String>,
+        elapsed: Duration,
+    ) {
+        if let Some(provider) = self.registry.get(service) {
+            let phase = format!("client init ({})", provider.display_name());
+            self.profile.record(phase, elapsed);
         }
+        // Ignore results for a context the user has since switched away from
+        if self.warm_context.as_deref() != Some(context) {
+            return;
+        }
+        let status = match result {
+            Ok(client) => ServiceStatus::Ready(client),
+            Err(err) => ServiceStatus::Failed(err),
+        };
+        if let AppState::SelectingService(selector) = &mut self.state {
+            selector.set_status(service, &status);
+        }
+        self.service_status.insert(service.clone(), status);
     }
 
     fn go_to_filtered_context_selection(&mut self, contexts: Vec<CloudContext>) {
+        self.validate_contexts(&contexts);
         self.state = AppState::SelectingContext(ContextSelectorView::with_contexts(
             contexts,
+            &self.context_health,
+            self.resolver.clone(),
+        ));
+    }
+
+    /// Check the credentials and project of `contexts` in the background.
+    ///
+    /// Contexts that are being checked or were found healthy are skipped, so
+    /// returning to the selector only checks the others again.
+    fn validate_contexts(&mut self, contexts: &[CloudContext]) {
+        let mut commands: Vec<Box<dyn Command>> = Vec::new();
+        for context in contexts {
+            if matches!(
+                self.context_health.get(context.name()),
+                Some(ContextHealth::Checking | ContextHealth::Healthy)
+            ) {
+                continue;
+            }
+            self.context_health
+                .insert(context.name().to_string(), ContextHealth::Checking);
+            commands.push(Box::new(ValidateContextCmd::new(context.clone())));
+        }
+        self.spawn_commands(commands);
+    }
+
+    fn select_context(&mut self, context: &CloudContext) {
+        if let Some(ContextHealth::CredentialsExpired(_)) = self.context_health.get(context.name())
+        {
+            self.toast_manager.show(Toast::warning(t_args(
+                "The login for '{name}' has expired, run `gcloud auth application-default login`",
+                &[("name", context.name())],
+            )));
+        }
+
+        // Check for pending service from CLI args
+        if let Some(svc_name) = self.pending_service.take()
+            && let Ok(service_id) = self.registry.find_service_by_name(context, &svc_name)
+        {
+            self.start_service(context, &service_id);
+            return;
+        }
+
+        self.go_to_service_selection(context);
+    }
+
+    /// Browse projects with the credentials of `context`, or of the first
+    /// known context without one.
+    fn open_project_browser(&mut self, context: Option<CloudContext>) {
+        let base = context
+            .or_else(|| self.known_contexts().into_iter().next())
+            .map_or_else(
+                || GcpContext {
+                    display_name: String::new(),
+                    project_id: String::new(),
+                    account: String::new(),
+                    region: None,
+                    zone: None,
+                    auth: AuthMethod::ApplicationDefault,
+                    accounts: Vec::new(),
+                    projects: Vec::new(),
+                },
+                |CloudContext::Gcp(ctx)| ctx,
+            );
+        self.state = AppState::BrowsingProjects(ProjectBrowserView::new(
+            base.clone(),
             self.resolver.clone(),
         ));
+        self.spawn_commands(vec![Box::new(LoadHierarchyCmd::new(base, None))]);
+    }
+
+    fn handle_hierarchy_loaded(
+        &mut self,
+        parent: Option<&str>,
+        result: Result<Vec<HierarchyNode>, Error>,
+    ) {
+        let AppState::BrowsingProjects(browser) = &mut self.state else {
+            return;
+        };
+        match result {
+            Ok(nodes) => browser.set_children(parent, nodes),
+            Err(err) => {
+                browser.set_children(parent, Vec::new());
+                self.display_error(&err);
+            }
+        }
+    }
+
+    /// Use the project picked in the browser as the current context.
+    ///
+    /// A saved context for the project is reused, otherwise the new one is
+    /// added to the saved contexts.
+    fn activate_project(&mut self, context: GcpContext) {
+        let mut contexts = self.known_contexts();
+        let existing = contexts.iter().find(|known| match known {
+            CloudContext::Gcp(known) => known.project_id == context.project_id,
+        });
+        let context = if let Some(existing) = existing {
+            existing.clone()
+        } else {
+            let context = CloudContext::Gcp(context);
+            contexts.push(context.clone());
+            if !self.demo
+                && let Err(err) = save_contexts(&contexts)
+            {
+                warn!("Failed to save contexts: {err}");
+            }
+            context
+        };
+        self.select_context(&context);
+    }
+
+    fn handle_context_validated(&mut self, context: String, health: ContextHealth) {
+        if let AppState::SelectingContext(selector) = &mut self.state {
+            selector.set_health(&context, &health);
+        }
+        self.context_health.insert(context, health);
     }
 
     // App is single-threaded; making dyn Service Send would cascade through the entire trait hierarchy
     #[allow(clippy::future_not_send)]
     pub async fn run(&mut self) -> Result<()> {
-        let mut tui = Tui::new(60.0, 4.0)?;
+        let frame_rate = if self.low_bandwidth == LowBandwidth::On {
+            LOW_BANDWIDTH_FRAME_RATE
+        } else {
+            FRAME_RATE
+        };
+        let mut tui = Tui::new(frame_rate, 4.0)?;
         tui.enter()?;
+        let result = self.event_loop(&mut tui).await;
+
+        // Restore the terminal and save state even if the loop failed
+        tui.exit()?;
+        self.save_state();
+        if self.profile.is_enabled() {
+            eprint!("{}", self.profile.report());
+        }
+        result
+    }
+
+    #[allow(clippy::future_not_send)]
+    async fn event_loop(&mut self, tui: &mut Tui) -> Result<()> {
+        self.render(tui)?;
+        self.on_first_frame();
 
         loop {
             tokio::select! {
@@ -200,37 +813,105 @@ impl App {
                     }
                 }
                 Some(message) = self.msg_rx.recv() => {
-                    self.handle_message(&mut tui, message)?;
+                    self.handle_message(tui, message)?;
                 }
             }
 
             if self.should_suspend {
+                // Commands keep their state while stopped and carry on after
                 tui.suspend()?;
-                self.msg_tx.send(AppMessage::Resume)?;
-                self.msg_tx.send(AppMessage::ClearScreen)?;
-                tui.enter()?;
+                self.should_suspend = false;
+                tui.resume()?;
+                self.render(tui)?;
             } else if self.should_quit {
-                break;
+                return Ok(());
             }
         }
+    }
 
-        tui.exit()?;
-        Ok(())
+    /// Write what outlives the session to disk before exiting.
+    fn save_state(&mut self) {
+        if let Err(err) = self.autosave.flush() {
+            warn!("Failed to save settings: {err}");
+        }
+        let mut history = self.command_tracker.history();
+        history.truncate(self.config.history.size);
+        if let Err(err) = config::save_command_history(&history) {
+            warn!("Failed to save command history: {err}");
+        }
+        if !self.demo {
+            let location = self.current_link().map(|link| link.to_string());
+            if let Err(err) = config::save_last_location(location.as_deref()) {
+                warn!("Failed to save the last location: {err}");
+            }
+        }
+    }
+
+    /// Quit, or wait in a dialog while commands that change something still
+    /// run. Asking again while waiting quits right away.
+    fn request_quit(&mut self) {
+        let pending = self.command_tracker.mutating();
+        let losses = self.unsaved_work();
+        if (pending.is_empty() && losses.is_empty())
+            || matches!(self.popup, Some(ActivePopup::Quit(_)))
+        {
+            self.should_quit = true;
+        } else {
+            self.popup = Some(ActivePopup::Quit(QuitDialog::new(
+                pending,
+                losses,
+                self.resolver.clone(),
+            )));
+        }
+    }
+
+    /// Work the active and the parked service lose on quitting.
+    fn unsaved_work(&self) -> Vec<String> {
+        let active = match &self.state {
+            AppState::ActiveService(service) => service.unsaved_work(),
+            _ => Vec::new(),
+        };
+        let parked = self
+            .previous
+            .iter()
+            .flat_map(|parked| parked.service.unsaved_work());
+        active.into_iter().chain(parked).collect()
+    }
+
+    /// Release commands that were held back until the UI was on screen.
+    fn on_first_frame(&mut self) {
+        self.profile.record("first frame", self.profile.elapsed());
+        if let Some(commands) = self.deferred_commands.take() {
+            self.spawn_commands(commands);
+        }
     }
 
     /// Spawn commands and signal when complete.
     fn spawn_commands(&mut self, commands: Vec<Box<dyn Command>>) {
+        if let Some(deferred) = &mut self.deferred_commands {
+            deferred.extend(commands);
+            return;
+        }
         for cmd in commands {
-            let id = self.command_tracker.start(cmd.name());
+            let name = cmd.name();
+            let service = self
+                .active_service
+                .as_ref()
+                .and_then(|id| self.registry.get(id))
+                .map(|provider| provider.display_name().to_string());
+            let id = self.command_tracker.start(cmd.as_ref(), service);
             let msg_tx = self.msg_tx.clone();
+            let middleware = self.middleware.clone();
             tokio::spawn(async move {
+                let started = Instant::now();
                 let success = match cmd.execute(msg_tx.clone()).await {
                     Ok(()) => true,
                     Err(e) => {
-                        let _ = msg_tx.send(AppMessage::DisplayError(e.to_string()));
+                        let _ = msg_tx.send(AppMessage::DisplayError(Error::from_report(&e)));
                         false
                     }
                 };
+                middleware.on_command(&name, started.elapsed(), success);
                 // Signal that a command completed - service should process messages
                 let _ = msg_tx.send(AppMessage::CommandCompleted { id, success });
             });
@@ -247,39 +928,244 @@ impl App {
                 let _ = self.msg_tx.send(AppMessage::GoBack);
             }
             Err(err) => {
-                let _ = self.msg_tx.send(AppMessage::DisplayError(err.to_string()));
+                let _ = self
+                    .msg_tx
+                    .send(AppMessage::DisplayError(Error::from_report(&err)));
             }
         }
     }
 
+    /// Show a failure in the way its kind calls for.
+    fn display_error(&mut self, err: &Error) {
+        error!("Error: {err:?}");
+        // Transient failures only need a nudge to try again
+        if err.is_retryable() {
+            let hint = err.hint().map(t).unwrap_or_default();
+            self.toast_manager
+                .show(Toast::error(format!("{}. {hint}", t(err.title()))));
+        } else {
+            self.popup = Some(ActivePopup::Error(ErrorDialog::from_error(
+                err,
+                self.resolver.clone(),
+            )));
+        }
+    }
+
     /// Transition to context selection.
     fn go_to_context_selection(&mut self) {
         self.active_context = None;
         self.status_bar.clear_context();
-        self.state =
-            AppState::SelectingContext(ContextSelectorView::new(self.resolver.clone()).unwrap());
+        let contexts = self
+            .discover_contexts()
+            .into_iter()
+            .filter(|c| self.config.shows_context(c.name()))
+            .collect();
+        self.go_to_filtered_context_selection(contexts);
+    }
+
+    /// Switch themes, unless colors were turned off for the session.
+    const fn set_theme(&mut self, theme: Theme) {
+        if self.theme.is_monochrome() {
+            return;
+        }
+        self.theme = if matches!(self.low_bandwidth, LowBandwidth::On) {
+            theme.with_plain_borders()
+        } else {
+            theme
+        };
+    }
+
+    /// Render cheaply from now on, after drawing turned out to be slow.
+    fn enable_low_bandwidth(&mut self, tui: &mut Tui) {
+        info!("Drawing is consistently slow, switching to low-bandwidth rendering");
+        self.low_bandwidth = LowBandwidth::On;
+        self.theme = self.theme.with_plain_borders();
+        set_reduced_motion(true);
+        tui.set_frame_rate(LOW_BANDWIDTH_FRAME_RATE);
+        self.toast_manager.show(Toast::info(t(
+            "Drawing is slow, switched to low-bandwidth rendering",
+        )));
+    }
+
+    /// Saved contexts, or the demo ones in demo mode.
+    fn known_contexts(&self) -> Vec<CloudContext> {
+        if self.demo {
+            context::demo_contexts()
+        } else {
+            load_contexts()
+        }
+    }
+
+    /// Saved contexts merged with newly discovered ones.
+    fn discover_contexts(&self) -> Vec<CloudContext> {
+        if self.demo {
+            return context::demo_contexts();
+        }
+        context::reconcile_contexts().unwrap_or_else(|err| {
+            warn!("Failed to discover contexts: {err}");
+            load_contexts()
+        })
+    }
+
+    fn open_profile_selector(&mut self) {
+        self.popup = Some(ActivePopup::ProfileSelector(ProfileSelectorView::new(
+            available_profiles(),
+            self.active_profile.as_deref(),
+            self.resolver.clone(),
+        )));
+    }
+
+    fn open_account_selector(&mut self) {
+        let accounts = self
+            .active_context
+            .as_ref()
+            .map(CloudContext::accounts)
+            .unwrap_or_default();
+        if accounts.len() < 2 {
+            self.toast_manager
+                .show(Toast::info(t("No other accounts for this context")));
+            return;
+        }
+        self.popup = Some(ActivePopup::AccountSelector(AccountSelectorView::new(
+            accounts,
+            self.resolver.clone(),
+        )));
+    }
+
+    /// Use the active context with another account.
+    ///
+    /// The active service rebuilds its clients and keeps its views if it can,
+    /// otherwise it is started again.
+    fn switch_account(&mut self, account: &str) {
+        self.popup = None;
+        let Some(context) = self
+            .active_context
+            .as_ref()
+            .map(|c| c.with_account(account))
+        else {
+            return;
+        };
+        self.active_context = Some(context.clone());
+        self.status_bar.set_active_context(context.clone());
+        // Warmed up clients belong to the previous account
+        self.warm_context = None;
+        self.service_status.clear();
+
+        match &mut self.state {
+            AppState::ActiveService(service) => {
+                if service.switch_context(&context) {
+                    let result = service.update();
+                    self.process_update_result(result);
+                } else if let Some(service_id) = self.active_service.clone() {
+                    service.destroy();
+                    self.start_service(&context, &service_id);
+                }
+            }
+            AppState::SelectingService(_) => self.go_to_service_selection(&context),
+            AppState::SelectingContext(_) | AppState::BrowsingProjects(_) => {}
+        }
+        self.toast_manager.show(Toast::info(t_args(
+            "Switched to account '{email}'",
+            &[("email", account)],
+        )));
+    }
+
+    /// Reload config, keybindings and theme for another profile.
+    ///
+    /// Services hold on to the config they were created with, so the active
+    /// one is closed and the user starts again from context selection.
+    fn switch_profile(&mut self, name: Option<String>) {
+        self.popup = None;
+        // Write a theme picked in the old profile before reading the new one
+        if let Err(err) = self.autosave.flush() {
+            warn!("Failed to save settings: {err}");
+        }
+        let config = match load_with_profile(name.as_deref()) {
+            Ok(config) => config,
+            Err(err) => {
+                self.popup = Some(ActivePopup::Error(ErrorDialog::new(
+                    err.to_string(),
+                    self.resolver.clone(),
+                )));
+                return;
+            }
+        };
+        if let Err(err) = save_profile(name.as_deref()) {
+            warn!("Failed to persist profile: {err}");
+        }
+
+        let keybindings = self.registry.keybindings(&config.keybindings);
+        self.resolver = Arc::new(KeyResolver::new(Arc::new(keybindings)));
+        self.set_theme(theme_from_name(&config.theme.name));
+        i18n::set_locale(config.locale.unwrap_or_else(Locale::from_env));
+        if let Err(err) = time::set_timezone(&config.display.timezone) {
+            self.toast_manager.show(Toast::warning(err.to_string()));
+        }
+        cost::set_config(&config.costs);
+        clipboard::set_backend(config.clipboard);
+        self.status_bar = StatusBar::new(config.status_bar.clone(), self.resolver.clone());
+        self.config = Arc::new(config);
+        self.status_bar.set_profile(name.clone());
+        self.output_pane.set_resolver(self.resolver.clone());
+        self.check_key_conflicts();
+
+        if let AppState::ActiveService(service) = &mut self.state {
+            service.destroy();
+        }
+        if let Some(mut previous) = self.previous.take() {
+            previous.service.destroy();
+        }
+        self.warm_context = None;
+        self.service_status.clear();
+        self.go_to_context_selection();
+
+        let label = name.as_deref().unwrap_or("default");
+        self.toast_manager.show(Toast::info(t_args(
+            "Switched to profile '{profile}'",
+            &[("profile", label)],
+        )));
+        self.active_profile = name;
     }
 
     /// Transition to service selection.
     fn go_to_service_selection(&mut self, context: &CloudContext) {
         self.active_context = Some(context.clone());
         self.status_bar.set_active_context(context.clone());
+        self.warm_up_services(context);
+        let last_used = config::load_state().last_services.remove(context.name());
         self.state = AppState::SelectingService(ServiceSelectorView::new(
             &self.registry,
             context,
+            &self.service_status,
+            last_used.as_deref(),
             self.resolver.clone(),
         ));
     }
 
     /// Transition to active service.
-    fn go_to_active_service(&mut self, mut service: Box<dyn Service>) {
-        // Save last context for -s flag
-        if let Some(ctx) = &self.active_context {
-            let _ = save_last_context(ctx.name());
+    fn go_to_active_service(&mut self, mut service: Box<dyn Service>, resource: Option<&str>) {
+        // Save last context for -s flag, and the service to select next time
+        if !self.demo
+            && let Some(ctx) = &self.active_context
+        {
+            self.autosave.set_last_context(ctx.name());
+            if let Some(service) = &self.active_service {
+                self.autosave
+                    .set_last_service(ctx.name(), &service.to_string());
+            }
         }
 
         // Initialize the service (queues startup message)
+        service.set_middleware(self.middleware.clone());
         service.init();
+        if let Some(resource) = resource
+            && !service.open_resource(resource)
+        {
+            self.toast_manager.show(Toast::warning(t_args(
+                "'{link}' can't be opened in this service",
+                &[("link", resource)],
+            )));
+        }
         self.state = AppState::ActiveService(service);
 
         // Immediately process the startup message
@@ -293,11 +1179,16 @@ impl App {
     fn go_back(&mut self) {
         match &mut self.state {
             AppState::SelectingContext(_) => {}
-            AppState::SelectingService(_) => {
+            AppState::BrowsingProjects(_) | AppState::SelectingService(_) => {
                 self.go_to_context_selection();
             }
             AppState::ActiveService(service) => {
-                service.destroy();
+                // The service keeps running while parked, until replaced
+                let losses = service.unsaved_work();
+                if !losses.is_empty() {
+                    self.toast_manager.show(Toast::warning(losses.join(", ")));
+                }
+                self.park_active_service();
                 if let Some(ref ctx) = self.active_context.clone() {
                     self.go_to_service_selection(ctx);
                 } else {
@@ -307,25 +1198,326 @@ impl App {
         }
     }
 
-    fn open_help_overlay(&mut self) {
-        let local = match &self.state {
-            AppState::ActiveService(service) => service.keybindings(),
-            _ => vec![],
+    /// Keep the active service alive to switch back to, replacing the one
+    /// kept before.
+    fn park_active_service(&mut self) {
+        if !matches!(self.state, AppState::ActiveService(_)) {
+            return;
+        }
+        let placeholder = AppState::SelectingContext(ContextSelectorView::with_contexts(
+            Vec::new(),
+            &HashMap::new(),
+            self.resolver.clone(),
+        ));
+        let AppState::ActiveService(mut service) = std::mem::replace(&mut self.state, placeholder)
+        else {
+            return;
+        };
+        let (Some(context), Some(service_id)) =
+            (self.active_context.clone(), self.active_service.take())
+        else {
+            service.destroy();
+            return;
+        };
+        let parked = ParkedService {
+            context,
+            service_id,
+            service,
+        };
+        if let Some(mut replaced) = self.previous.replace(parked) {
+            let losses = replaced.service.unsaved_work();
+            if !losses.is_empty() {
+                self.toast_manager.show(Toast::warning(losses.join(", ")));
+            }
+            replaced.service.destroy();
+        }
+    }
+
+    /// Switch to what `link` points at, in the active context unless it
+    /// names another, keeping the current service to switch back to.
+    fn open_link(&mut self, link: &ResourceLink) {
+        let context = match &link.context {
+            Some(name) => context::find_by_name(&self.known_contexts(), name),
+            None => self
+                .active_context
+                .clone()
+                .ok_or_else(|| eyre!("No context to open {link} in")),
+        };
+        let result = context.and_then(|context| {
+            self.ensure_available(&context, &link.service)?;
+            self.park_active_service();
+            self.start_link(&context, link)
+        });
+        if let Err(err) = result {
+            self.toast_manager.show(Toast::warning(err.to_string()));
+        }
+    }
+
+    /// Link to the service and resource on display, if a service is open.
+    fn current_link(&self) -> Option<ResourceLink> {
+        let (Some(context), Some(service_id), AppState::ActiveService(service)) =
+            (&self.active_context, &self.active_service, &self.state)
+        else {
+            return None;
+        };
+        let link = ResourceLink::service(context.name(), service_id.clone());
+        Some(ResourceLink {
+            resource: service.current_resource(),
+            ..link
+        })
+    }
+
+    /// Bookmark the service and resource on display.
+    fn add_bookmark(&mut self) {
+        let Some(link) = self.current_link() else {
+            self.toast_manager
+                .show(Toast::info(t("Open a service to bookmark it")));
+            return;
+        };
+        let link = link.to_string();
+        if self.bookmarks.contains(&link) {
+            self.toast_manager
+                .show(Toast::info(t("Already bookmarked")));
+            return;
+        }
+        self.bookmarks.insert(0, link.clone());
+        self.save_bookmarks();
+        self.toast_manager.show(Toast::success(t_args(
+            "Bookmarked {bookmark}",
+            &[("bookmark", &link)],
+        )));
+    }
+
+    fn delete_bookmark(&mut self, link: &ResourceLink) {
+        let link = link.to_string();
+        self.bookmarks.retain(|bookmark| *bookmark != link);
+        self.save_bookmarks();
+    }
+
+    fn save_bookmarks(&self) {
+        if self.demo {
+            return;
+        }
+        if let Err(err) = config::save_bookmarks(&self.bookmarks) {
+            warn!("Failed to save bookmarks: {err}");
+        }
+    }
+
+    fn open_workspaces(&mut self) {
+        self.popup = Some(ActivePopup::Workspaces(Box::new(WorkspacesView::new(
+            self.workspaces.clone(),
+            self.resolver.clone(),
+        ))));
+    }
+
+    /// Save the open service, the one kept for switching back and the
+    /// output pane as the workspace `name`, replacing one of that name.
+    fn save_workspace(&mut self, name: String) {
+        let Some(location) = self.current_link() else {
+            self.toast_manager
+                .show(Toast::info(t("Open a service to save a workspace")));
+            return;
         };
+        let previous = self.previous.as_ref().map(|parked| ResourceLink {
+            resource: parked.service.current_resource(),
+            ..ResourceLink::service(parked.context.name(), parked.service_id.clone())
+        });
+        let workspace = Workspace {
+            name,
+            location: location.to_string(),
+            previous: previous.map(|link| link.to_string()),
+            output: self.output_pane.is_expanded(),
+        };
+        self.toast_manager.show(Toast::success(t_args(
+            "Saved workspace '{title}'",
+            &[("title", &workspace.name)],
+        )));
+        self.workspaces.retain(|saved| saved.name != workspace.name);
+        self.workspaces.push(workspace);
+        self.workspaces.sort_by(|a, b| a.name.cmp(&b.name));
+        self.save_workspaces();
+    }
+
+    /// Open the services of `workspace`, the previous one first so it is
+    /// kept for switching back.
+    fn restore_workspace(&mut self, workspace: &Workspace) -> Result<()> {
+        let contexts = self.known_contexts();
+        let targets = workspace
+            .previous
+            .iter()
+            .chain([&workspace.location])
+            .map(|link| {
+                let link: ResourceLink = link.parse()?;
+                let name = link
+                    .context
+                    .as_deref()
+                    .ok_or_else(|| eyre!("The link {link} names no context"))?;
+                let context = context::find_by_name(&contexts, name)?;
+                self.ensure_available(&context, &link.service)?;
+                Ok((context, link))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        for (context, link) in &targets {
+            self.park_active_service();
+            self.start_link(context, link)?;
+        }
+        if self.output_pane.is_expanded() != workspace.output {
+            self.toggle_output_pane();
+        }
+        Ok(())
+    }
+
+    fn save_workspaces(&self) {
+        if self.demo {
+            return;
+        }
+        if let Err(err) = config::save_workspaces(&self.workspaces) {
+            warn!("Failed to save workspaces: {err}");
+        }
+    }
+
+    fn open_bookmarks(&mut self) {
+        let links = self
+            .bookmarks
+            .iter()
+            .filter_map(|bookmark| bookmark.parse().ok())
+            .collect();
+        self.popup = Some(ActivePopup::Bookmarks(Box::new(BookmarksView::new(
+            links,
+            self.resolver.clone(),
+        ))));
+    }
+
+    /// Swap the active service with the previous one, both keeping their
+    /// view stacks. From a selector, return to the previous service.
+    fn switch_previous(&mut self) {
+        let Some(previous) = self.previous.take() else {
+            self.toast_manager
+                .show(Toast::info(t("No previous service to switch to")));
+            return;
+        };
+        self.park_active_service();
+
+        if !self.demo {
+            self.autosave.set_last_context(previous.context.name());
+        }
+        self.status_bar.set_active_context(previous.context.clone());
+        self.active_context = Some(previous.context);
+        self.active_service = Some(previous.service_id);
+        self.state = AppState::ActiveService(previous.service);
+
+        // Catch up on results that arrived while it was in the background
+        if let AppState::ActiveService(service) = &mut self.state {
+            let result = service.update();
+            self.process_update_result(result);
+        }
+    }
+
+    /// Keys of the current view.
+    fn local_keybindings(&self) -> Vec<Keybinding> {
+        match &self.state {
+            AppState::SelectingContext(selector) => selector.keybindings(),
+            AppState::BrowsingProjects(browser) => browser.keybindings(),
+            AppState::SelectingService(_) => vec![],
+            AppState::ActiveService(service) => service.keybindings(),
+        }
+    }
+
+    /// Keys hinted in the status bar, led by whatever has focus.
+    fn status_keybindings(&self) -> Vec<Keybinding> {
+        match &self.popup {
+            Some(ActivePopup::History(history)) => return history.keybindings(),
+            Some(ActivePopup::Bookmarks(bookmarks)) => return bookmarks.keybindings(),
+            Some(ActivePopup::Workspaces(workspaces)) => return workspaces.keybindings(),
+            _ => {}
+        }
+        let mut keybindings = self.local_keybindings();
+        if self.output_pane.is_expanded() {
+            let switch = Keybinding::hint(
+                self.resolver.display_global(GlobalAction::FocusNext),
+                "Switch pane",
+            );
+            if self.focus.is_focused(Pane::Output) {
+                let mut pane = self.output_pane.keybindings();
+                pane.push(switch);
+                pane.append(&mut keybindings);
+                keybindings = pane;
+            } else {
+                keybindings.insert(0, switch);
+            }
+        }
+        keybindings
+    }
+
+    fn open_help_overlay(&mut self) {
+        let local = self.local_keybindings();
         let local_title = match &self.state {
             AppState::ActiveService(service) => service
                 .breadcrumbs()
                 .last()
                 .cloned()
-                .unwrap_or_else(|| "Current View".to_string()),
+                .unwrap_or_else(|| t("Current View").to_string()),
             _ => "Navigation".to_string(),
         };
-        self.popup = Some(ActivePopup::Help(HelpOverlay::with_sections(vec![
+        let ctx = self.action_context();
+        let custom: Vec<Keybinding> = self
+            .config
+            .actions
+            .iter()
+            .filter(|a| a.in_scope(&ctx))
+            .map(|a| Keybinding::new(a.key.display(), &a.name))
+            .collect();
+
+        let output = if self.output_pane.is_expanded() {
+            self.output_pane.keybindings()
+        } else {
+            vec![]
+        };
+
+        let sections = vec![
             KeybindingSection::new(&local_title, local),
+            KeybindingSection::new("Output", output),
+            KeybindingSection::new("Navigation", self.status_bar.navigation_keybindings()),
             KeybindingSection::new("Global", self.status_bar.global_keybindings()),
-        ])));
+            KeybindingSection::new("Custom Actions", custom),
+        ];
+        self.popup = Some(ActivePopup::Help(HelpOverlay::with_sections(
+            sections,
+            self.resolver.clone(),
+        )));
+    }
+
+    fn open_stats_overlay(&mut self) {
+        let Some(metrics) = &self.metrics else {
+            self.toast_manager.show(Toast::info(t(
+                "Metrics are disabled, enable diagnostics.metrics in the config.",
+            )));
+            return;
+        };
+        self.popup = Some(ActivePopup::Stats(StatsOverlay::new(
+            metrics.snapshot(),
+            self.resolver.clone(),
+        )));
+    }
+
+    fn open_history(&mut self) {
+        self.popup = Some(ActivePopup::History(Box::new(CommandHistoryView::new(
+            self.command_tracker.history(),
+            self.resolver.clone(),
+        ))));
+    }
+
+    fn retry_command(&mut self, record: &CommandRecord) {
+        let Some(cmd) = self.command_tracker.retry(record) else {
+            self.toast_manager
+                .show(Toast::info(t("This command can't be run again.")));
+            return;
+        };
+        self.popup = None;
+        self.spawn_commands(vec![cmd]);
     }
 
+    #[allow(clippy::too_many_lines)]
     fn handle_popup_event(&mut self, key: crossterm::event::KeyEvent) -> Result<()> {
         let Some(ref mut popup) = self.popup else {
             return Ok(());
@@ -348,6 +1540,79 @@ impl App {
                 }
                 _ => {}
             },
+            ActivePopup::ProfileSelector(selector) => match selector.handle_key(key) {
+                Ok(EventResult::Event(ProfileEvent::Selected(name))) => {
+                    self.msg_tx.send(AppMessage::SelectProfile(name))?;
+                }
+                Ok(EventResult::Event(ProfileEvent::Cancelled)) => {
+                    self.msg_tx.send(AppMessage::ClosePopup)?;
+                }
+                _ => {}
+            },
+            ActivePopup::AccountSelector(selector) => match selector.handle_key(key) {
+                Ok(EventResult::Event(AccountEvent::Selected(account))) => {
+                    self.msg_tx.send(AppMessage::SelectAccount(account))?;
+                }
+                Ok(EventResult::Event(AccountEvent::Cancelled)) => {
+                    self.msg_tx.send(AppMessage::ClosePopup)?;
+                }
+                _ => {}
+            },
+            ActivePopup::Stats(stats) => {
+                if matches!(
+                    stats.handle_key(key),
+                    Ok(EventResult::Event(StatsEvent::Close))
+                ) {
+                    self.msg_tx.send(AppMessage::ClosePopup)?;
+                }
+            }
+            ActivePopup::History(history) => match history.handle_key(key) {
+                Ok(EventResult::Event(HistoryEvent::Retry(record))) => {
+                    self.msg_tx.send(AppMessage::RetryCommand(record))?;
+                }
+                Ok(EventResult::Event(HistoryEvent::Close)) => {
+                    self.msg_tx.send(AppMessage::ClosePopup)?;
+                }
+                _ => {}
+            },
+            ActivePopup::Bookmarks(bookmarks) => match bookmarks.handle_key(key) {
+                Ok(EventResult::Event(BookmarkEvent::Open(link))) => {
+                    self.msg_tx.send(AppMessage::ClosePopup)?;
+                    self.msg_tx.send(AppMessage::OpenLink(link))?;
+                }
+                Ok(EventResult::Event(BookmarkEvent::Delete(link))) => {
+                    self.msg_tx.send(AppMessage::DeleteBookmark(link))?;
+                }
+                Ok(EventResult::Event(BookmarkEvent::Close)) => {
+                    self.msg_tx.send(AppMessage::ClosePopup)?;
+                }
+                _ => {}
+            },
+            ActivePopup::Workspaces(workspaces) => match workspaces.handle_key(key) {
+                Ok(EventResult::Event(WorkspaceEvent::Restore(workspace))) => {
+                    self.msg_tx.send(AppMessage::ClosePopup)?;
+                    self.msg_tx.send(AppMessage::RestoreWorkspace(workspace))?;
+                }
+                Ok(EventResult::Event(WorkspaceEvent::Save(name))) => {
+                    self.msg_tx.send(AppMessage::ClosePopup)?;
+                    self.msg_tx.send(AppMessage::SaveWorkspace(name))?;
+                }
+                Ok(EventResult::Event(WorkspaceEvent::Delete(name))) => {
+                    self.msg_tx.send(AppMessage::DeleteWorkspace(name))?;
+                }
+                Ok(EventResult::Event(WorkspaceEvent::Close)) => {
+                    self.msg_tx.send(AppMessage::ClosePopup)?;
+                }
+                _ => {}
+            },
+            ActivePopup::ReleaseNotes(notes) => {
+                if matches!(
+                    notes.handle_key(key),
+                    Ok(EventResult::Event(ReleaseNotesEvent::Close))
+                ) {
+                    self.msg_tx.send(AppMessage::ClosePopup)?;
+                }
+            }
             ActivePopup::Error(dialog) => {
                 if matches!(
                     dialog.handle_key(key),
@@ -356,13 +1621,145 @@ impl App {
                     self.msg_tx.send(AppMessage::ClosePopup)?;
                 }
             }
+            ActivePopup::Quit(dialog) => match dialog.handle_key(key) {
+                Ok(EventResult::Event(QuitEvent::QuitNow)) => self.should_quit = true,
+                Ok(EventResult::Event(QuitEvent::Cancelled)) => {
+                    self.msg_tx.send(AppMessage::ClosePopup)?;
+                }
+                _ => {}
+            },
         }
         Ok(())
     }
 
+    /// Open or close the output pane, focusing it when it opens.
+    fn toggle_output_pane(&mut self) {
+        self.output_pane.toggle_expanded();
+        let pane = if self.output_pane.is_expanded() {
+            Pane::Output
+        } else {
+            Pane::View
+        };
+        self.set_focus(pane);
+    }
+
+    fn set_focus(&mut self, pane: Pane) {
+        self.focus.focus(pane);
+        self.output_pane
+            .set_focused(self.focus.is_focused(Pane::Output));
+    }
+
+    /// Route a key to the output pane while it is open.
+    ///
+    /// Returns `true` if the pane consumed the key.
+    fn handle_output_pane_key(&mut self, key: crossterm::event::KeyEvent) -> Result<bool> {
+        match self.output_pane.handle_key(key)? {
+            EventResult::Event(OutputPaneEvent::Copy(text)) => {
+                self.spawn_commands(vec![Box::new(CopyToClipboardCmd::new(text, "output"))]);
+            }
+            EventResult::Event(OutputPaneEvent::Close) => self.toggle_output_pane(),
+            EventResult::Consumed => {}
+            EventResult::Ignored => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Placeholder values for custom actions, from the active service and context.
+    fn action_context(&self) -> ActionContext {
+        let mut ctx = match &self.state {
+            AppState::ActiveService(service) => service.action_context(),
+            _ => ActionContext::default(),
+        };
+        if let Some(context) = &self.active_context {
+            ctx = ctx.with_var("context", context.name());
+            match context {
+                CloudContext::Gcp(gcp) => {
+                    ctx = ctx
+                        .with_var("project", &gcp.project_id)
+                        .with_var("account", &gcp.account);
+                }
+            }
+        }
+        ctx
+    }
+
+    /// Run the first custom action bound to `key` in the current scope.
+    ///
+    /// Returns `true` if an action matched.
+    fn handle_custom_action(&mut self, key: &crossterm::event::KeyEvent) -> Result<bool> {
+        let ctx = self.action_context();
+        let prefix = self.resolver.pending();
+        let Some(action) = self
+            .config
+            .actions
+            .iter()
+            .find(|a| a.matches(prefix.as_ref(), key, &ctx))
+        else {
+            return Ok(false);
+        };
+
+        let command = match action.render_command(&ctx) {
+            Ok(command) => command,
+            Err(err) => {
+                self.msg_tx
+                    .send(AppMessage::DisplayError(Error::from_report(&err)))?;
+                return Ok(true);
+            }
+        };
+        debug!("Running custom action '{}': {command}", action.name);
+
+        match action.mode {
+            ActionMode::Foreground => {
+                self.msg_tx.send(AppMessage::RunForeground {
+                    command,
+                    wait: action.wait,
+                })?;
+            }
+            ActionMode::Background => {
+                let cmd = ShellCmd::new(action.name.clone(), command);
+                self.spawn_commands(vec![Box::new(cmd)]);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Run a foreground command and record a failed exit in the output pane.
+    fn handle_foreground(&mut self, tui: &mut Tui, command: &str, wait: bool) -> Result<()> {
+        match Self::run_foreground(tui, command, wait) {
+            Ok(status) if !status.success() => {
+                self.output_pane
+                    .push(command, &format!("exited with {status}"), false);
+            }
+            Ok(_) => {}
+            Err(err) => self
+                .msg_tx
+                .send(AppMessage::DisplayError(Error::from_report(&err)))?,
+        }
+        self.render(tui)
+    }
+
+    /// Hand the terminal to `command` and restore the TUI once it exits,
+    /// after Enter if `wait` is set.
+    fn run_foreground(tui: &mut Tui, command: &str, wait: bool) -> Result<ExitStatus> {
+        tui.exit()?;
+        let status = std::process::Command::new(shell())
+            .arg("-c")
+            .arg(command)
+            .status();
+        if wait {
+            println!("\nPress Enter to return to lazycloud");
+            let _ = std::io::stdin().read_line(&mut String::new());
+        }
+        tui.enter()?;
+        tui.clear()?;
+
+        Ok(status?)
+    }
+
     fn handle_global_event(&self, event: &Event) -> Result<()> {
         match event {
             Event::Quit => self.msg_tx.send(AppMessage::Quit)?,
+            Event::Suspend => self.msg_tx.send(AppMessage::Suspend)?,
             Event::Render => self.msg_tx.send(AppMessage::Render)?,
             Event::Resize(width, height) => {
                 self.msg_tx.send(AppMessage::Resize(*width, *height))?;
@@ -374,11 +1771,40 @@ impl App {
                     self.msg_tx.send(AppMessage::DisplayHelp)?;
                 } else if self.resolver.matches_global(key, GlobalAction::Theme) {
                     self.msg_tx.send(AppMessage::DisplayThemeSelector)?;
+                } else if self.resolver.matches_global(key, GlobalAction::Profile) {
+                    self.msg_tx.send(AppMessage::DisplayProfileSelector)?;
+                } else if self.resolver.matches_global(key, GlobalAction::Account) {
+                    self.msg_tx.send(AppMessage::DisplayAccountSelector)?;
                 } else if self
                     .resolver
                     .matches_global(key, GlobalAction::CommandsToggle)
                 {
                     self.msg_tx.send(AppMessage::ToggleCommandStatus)?;
+                } else if self
+                    .resolver
+                    .matches_global(key, GlobalAction::OutputToggle)
+                {
+                    self.msg_tx.send(AppMessage::ToggleOutput)?;
+                } else if self.resolver.matches_global(key, GlobalAction::Stats) {
+                    self.msg_tx.send(AppMessage::DisplayStats)?;
+                } else if self.resolver.matches_global(key, GlobalAction::History) {
+                    self.msg_tx.send(AppMessage::DisplayHistory)?;
+                } else if self.resolver.matches_global(key, GlobalAction::Bookmarks) {
+                    self.msg_tx.send(AppMessage::DisplayBookmarks)?;
+                } else if self.resolver.matches_global(key, GlobalAction::Bookmark) {
+                    self.msg_tx.send(AppMessage::AddBookmark)?;
+                } else if self.resolver.matches_global(key, GlobalAction::Workspaces) {
+                    self.msg_tx.send(AppMessage::DisplayWorkspaces)?;
+                } else if self
+                    .resolver
+                    .matches_global(key, GlobalAction::ReleaseNotes)
+                {
+                    self.msg_tx.send(AppMessage::DisplayReleaseNotes)?;
+                } else if self
+                    .resolver
+                    .matches_global(key, GlobalAction::SwitchPrevious)
+                {
+                    self.msg_tx.send(AppMessage::SwitchPrevious)?;
                 } else if self.resolver.matches_global(key, GlobalAction::Back) {
                     self.msg_tx.send(AppMessage::GoBack)?;
                 }
@@ -389,10 +1815,38 @@ impl App {
     }
 
     fn handle_event(&mut self, event: &Event) -> Result<()> {
+        // A pending key sequence ends with the next key, matched or not
+        let completes_chord = matches!(event, Event::Key(_)) && self.resolver.pending().is_some();
+        let result = self.route_event(event);
+        if completes_chord {
+            self.resolver.clear_pending();
+        }
+        result
+    }
+
+    /// Whether `key` begins a built-in or custom key sequence.
+    fn starts_chord(&self, key: &crossterm::event::KeyEvent) -> bool {
+        self.resolver.pending().is_none()
+            && (self.resolver.starts_chord(key)
+                || self.config.actions.iter().any(|a| a.key.starts_chord(key)))
+    }
+
+    fn route_event(&mut self, event: &Event) -> Result<()> {
+        // Settings are saved on time even while a popup is open
+        if matches!(event, Event::Tick)
+            && let Err(err) = self.autosave.tick()
+        {
+            warn!("Failed to save settings: {err}");
+        }
+
         // Popup intercepts all key events when visible
         if self.popup.is_some() {
-            if let Event::Key(key) = event {
-                self.handle_popup_event(*key)?;
+            match event {
+                Event::Key(key) => self.handle_popup_event(*key)?,
+                // Ctrl+C, Ctrl+Z and SIGTERM are not held up by a popup
+                Event::Quit => self.msg_tx.send(AppMessage::Quit)?,
+                Event::Suspend => self.msg_tx.send(AppMessage::Suspend)?,
+                _ => {}
             }
             return Ok(());
         }
@@ -403,19 +1857,37 @@ impl App {
             self.toast_manager.handle_tick();
             if let AppState::ActiveService(service) = &mut self.state {
                 service.handle_tick();
+                // Let services act on timers without waiting for input
+                let result = service.update();
+                self.process_update_result(result);
             }
             return Ok(());
         }
 
+        // The open output pane gets keys first while it has focus
+        if self.output_pane.is_expanded()
+            && self.focus.is_focused(Pane::Output)
+            && let Event::Key(key) = event
+            && self.handle_output_pane_key(*key)?
+        {
+            return Ok(());
+        }
+
         // Route input event based on current state
         let handled = match &mut self.state {
             AppState::SelectingContext(selector) => {
-                if let Event::Key(key) = event {
+                if let Event::Paste(text) = event {
+                    selector.handle_paste(text).is_ok_and(|r| r.is_consumed())
+                } else if let Event::Key(key) = event {
                     match selector.handle_key(*key) {
-                        Ok(EventResult::Event(context)) => {
+                        Ok(EventResult::Event(ContextEvent::Selected(context))) => {
                             self.msg_tx.send(AppMessage::SelectContext(context))?;
                             return Ok(());
                         }
+                        Ok(EventResult::Event(ContextEvent::Browse(context))) => {
+                            self.msg_tx.send(AppMessage::BrowseProjects(context))?;
+                            return Ok(());
+                        }
                         Ok(EventResult::Consumed) => true,
                         Ok(EventResult::Ignored) | Err(_) => false,
                     }
@@ -423,8 +1895,11 @@ impl App {
                     false
                 }
             }
+            AppState::BrowsingProjects(_) => self.route_browser_event(event),
             AppState::SelectingService(selector) => {
-                if let Event::Key(key) = event {
+                if let Event::Paste(text) = event {
+                    selector.handle_paste(text).is_ok_and(|r| r.is_consumed())
+                } else if let Event::Key(key) = event {
                     match selector.handle_key(*key) {
                         Ok(EventResult::Event(service_id)) => {
                             self.msg_tx.send(AppMessage::SelectService(service_id))?;
@@ -438,30 +1913,74 @@ impl App {
                 }
             }
             AppState::ActiveService(service) => {
-                if let Event::Key(key) = event {
-                    let result = service.handle_key(*key);
-                    if result.is_consumed() {
-                        let msg = service.update();
-                        self.process_update_result(msg);
-                    }
-                    result.is_consumed()
-                } else {
-                    false
+                let result = match event {
+                    Event::Key(key) => service.handle_key(*key),
+                    Event::Paste(text) => service.handle_paste(text),
+                    _ => EventResult::Ignored,
+                };
+                if result.is_consumed() {
+                    let msg = service.update();
+                    self.process_update_result(msg);
                 }
+                result.is_consumed()
             }
         };
 
         if !handled {
+            if let Event::Key(key) = event {
+                // Views that don't cycle focus themselves hand it to the output pane
+                if self.output_pane.is_expanded() && self.focus.handle_key(key, &self.resolver) {
+                    self.set_focus(self.focus.focused());
+                    return Ok(());
+                }
+                if self.handle_custom_action(key)? {
+                    return Ok(());
+                }
+                // Nothing wants the key on its own; wait for the rest of a sequence
+                if self.starts_chord(key) {
+                    self.resolver.set_pending(*key);
+                    return Ok(());
+                }
+            }
             self.handle_global_event(event)?;
         }
 
         Ok(())
     }
 
+    /// Whether the project browser handled `event`.
+    fn route_browser_event(&mut self, event: &Event) -> bool {
+        let AppState::BrowsingProjects(browser) = &mut self.state else {
+            return false;
+        };
+        let result = match event {
+            Event::Key(key) => browser.handle_key(*key),
+            Event::Paste(text) => browser.handle_paste(text),
+            _ => return false,
+        };
+        match result {
+            Ok(EventResult::Event(HierarchyEvent::Load(parent))) => {
+                let command = LoadHierarchyCmd::new(browser.base().clone(), parent);
+                self.spawn_commands(vec![Box::new(command)]);
+                true
+            }
+            Ok(EventResult::Event(HierarchyEvent::Activate(context))) => {
+                self.activate_project(context);
+                true
+            }
+            Ok(EventResult::Consumed) => true,
+            Ok(EventResult::Ignored) | Err(_) => false,
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
     fn handle_message(&mut self, tui: &mut Tui, msg: AppMessage) -> Result<()> {
         if !matches!(
             msg,
-            AppMessage::Tick | AppMessage::Render | AppMessage::CommandCompleted { .. }
+            AppMessage::Tick
+                | AppMessage::Render
+                | AppMessage::CommandCompleted { .. }
+                | AppMessage::CommandEmitted
         ) {
             debug!("Handling message: {msg:?}");
         }
@@ -470,42 +1989,63 @@ impl App {
             AppMessage::Tick => {
                 // Handled in handle_event
             }
-            AppMessage::Quit => self.should_quit = true,
+            AppMessage::Quit => self.request_quit(),
             AppMessage::Suspend => self.should_suspend = true,
-            AppMessage::Resume => self.should_suspend = false,
-            AppMessage::ClearScreen => tui.clear()?,
             AppMessage::Resize(width, height) => {
                 tui.resize(Rect::new(0, 0, width, height))?;
                 self.render(tui)?;
             }
             AppMessage::Render => self.render(tui)?,
-            AppMessage::DisplayError(err) => {
-                error!("Error: {err}");
-                self.popup = Some(ActivePopup::Error(ErrorDialog::new(
-                    err,
-                    self.resolver.clone(),
-                )));
-            }
+            AppMessage::DisplayError(err) => self.display_error(&err),
             AppMessage::DisplayHelp => self.open_help_overlay(),
             AppMessage::DisplayThemeSelector => {
                 self.popup = Some(ActivePopup::ThemeSelector(ThemeSelectorView::new(
                     self.resolver.clone(),
                 )));
             }
+            AppMessage::DisplayProfileSelector => self.open_profile_selector(),
+            AppMessage::DisplayAccountSelector => self.open_account_selector(),
+            AppMessage::DisplayStats => self.open_stats_overlay(),
+            AppMessage::DisplayHistory => self.open_history(),
+            AppMessage::DisplayBookmarks => self.open_bookmarks(),
+            AppMessage::AddBookmark => self.add_bookmark(),
+            AppMessage::DeleteBookmark(link) => self.delete_bookmark(&link),
+            AppMessage::DisplayWorkspaces => self.open_workspaces(),
+            AppMessage::SaveWorkspace(name) => self.save_workspace(name),
+            AppMessage::RestoreWorkspace(workspace) => {
+                if let Err(err) = self.restore_workspace(&workspace) {
+                    self.toast_manager.show(Toast::warning(err.to_string()));
+                }
+            }
+            AppMessage::DeleteWorkspace(name) => {
+                self.workspaces.retain(|workspace| workspace.name != name);
+                self.save_workspaces();
+            }
+            AppMessage::RetryCommand(record) => self.retry_command(&record),
+            AppMessage::DisplayReleaseNotes => self.open_release_notes(),
+            AppMessage::UpdateAvailable { version, notes } => {
+                self.handle_update_available(version, notes);
+            }
             AppMessage::ClosePopup => {
                 self.popup = None;
             }
             AppMessage::SelectTheme(theme_info) => {
-                // Persist theme to config file
-                if let Err(e) = save_theme(theme_info.name) {
-                    warn!("Failed to persist theme: {e}");
-                }
-                self.theme = theme_info.theme;
+                // Persisted to the config file once it stops changing
+                self.autosave
+                    .set_theme(theme_info.name, self.active_profile.as_deref());
+                self.set_theme(theme_info.theme);
                 self.popup = None;
             }
+            AppMessage::SelectProfile(name) => self.switch_profile(name),
+            AppMessage::SelectAccount(account) => self.switch_account(&account),
             AppMessage::CommandCompleted { id, success } => {
                 // Mark commands as complete in tracker
                 self.command_tracker.complete(id, success);
+                if let Some(ActivePopup::Quit(dialog)) = &mut self.popup {
+                    let pending = self.command_tracker.mutating();
+                    self.should_quit = pending.is_empty() && !dialog.has_losses();
+                    dialog.set_pending(pending);
+                }
                 // A command finished, tell service to process its messages
                 if let AppState::ActiveService(service) = &mut self.state {
                     let result = service.update();
@@ -514,6 +2054,12 @@ impl App {
                 // Render after commands completion
                 self.render(tui)?;
             }
+            AppMessage::CommandEmitted => {
+                if let AppState::ActiveService(service) = &mut self.state {
+                    let result = service.update();
+                    self.process_update_result(result);
+                }
+            }
             AppMessage::ToggleCommandStatus => {
                 self.command_tracker.toggle_expanded();
             }
@@ -524,28 +2070,55 @@ impl App {
                 let toast = match toast_type {
                     ToastType::Success => Toast::success(message),
                     ToastType::Info => Toast::info(message),
+                    ToastType::Warning => Toast::warning(message),
+                    ToastType::Error => Toast::error(message),
                 };
                 self.toast_manager.show(toast);
             }
-            AppMessage::SelectContext(context) => {
-                // Check for pending service from CLI args
-                if let Some(svc_name) = self.pending_service.take()
-                    && let Ok(service_id) = self.registry.find_service_by_name(&context, &svc_name)
-                {
-                    self.start_service(&context, &service_id);
-                    return Ok(());
+            AppMessage::AppendOutput {
+                title,
+                output,
+                success,
+            } => {
+                self.output_pane.push(&title, &output, success);
+                if !self.output_pane.is_expanded() {
+                    self.toast_manager.show(Toast::info(t_args(
+                        "Output of '{source}' is in the output pane, {toggle} shows it",
+                        &[
+                            ("source", &title),
+                            (
+                                "toggle",
+                                &self.resolver.display_global(GlobalAction::OutputToggle),
+                            ),
+                        ],
+                    )));
                 }
-
-                self.go_to_service_selection(&context);
             }
+            AppMessage::ToggleOutput => self.toggle_output_pane(),
+            AppMessage::RunForeground { command, wait } => {
+                self.handle_foreground(tui, &command, wait)?;
+            }
+            AppMessage::ServiceWarmedUp {
+                context,
+                service,
+                result,
+                elapsed,
+            } => self.handle_service_warmed_up(&context, &service, result, elapsed),
+            AppMessage::ContextValidated { context, health } => {
+                self.handle_context_validated(context, health);
+            }
+            AppMessage::BrowseProjects(context) => self.open_project_browser(context),
+            AppMessage::HierarchyLoaded { parent, result } => {
+                self.handle_hierarchy_loaded(parent.as_deref(), result);
+            }
+            AppMessage::SelectContext(context) => self.select_context(&context),
             AppMessage::SelectService(service_id) => {
-                if let Some(ctx) = &self.active_context
-                    && let Some(provider) = self.registry.get(&service_id)
-                {
-                    let service = provider.create_service(ctx, self.resolver.clone());
-                    self.go_to_active_service(service);
+                if let Some(ctx) = self.active_context.clone() {
+                    self.start_service(&ctx, &service_id);
                 }
             }
+            AppMessage::OpenLink(link) => self.open_link(&link),
+            AppMessage::SwitchPrevious => self.switch_previous(),
             AppMessage::GoBack => {
                 self.go_back();
             }
@@ -554,7 +2127,12 @@ impl App {
         Ok(())
     }
 
+    #[allow(clippy::too_many_lines)]
     fn render(&mut self, tui: &mut Tui) -> Result<()> {
+        if self.config.terminal_title.enabled {
+            tui.set_title(&self.window_title())?;
+        }
+        let started = Instant::now();
         tui.draw(|frame| {
             // Fill background with theme base color
             frame.render_widget(
@@ -563,15 +2141,12 @@ impl App {
             );
 
             // Get keybindings for status bar
-            let local_keybindings = match &self.state {
-                AppState::ActiveService(service) => service.keybindings(),
-                _ => vec![],
-            };
+            let local_keybindings = self.status_keybindings();
 
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([
-                    Constraint::Length(9), // Status bar (logo + keybindings + context)
+                    Constraint::Length(self.status_bar.height(frame.area().height)),
                     Constraint::Min(0),    // Main content
                     Constraint::Length(1), // Breadcrumbs
                 ])
@@ -585,16 +2160,30 @@ impl App {
                 &local_keybindings,
             );
 
+            // Split off the output pane below the main content when open
+            let main_area = if self.output_pane.is_expanded() {
+                let [main, pane] =
+                    Layout::vertical([Constraint::Min(0), Constraint::Length(OUTPUT_PANE_HEIGHT)])
+                        .areas(chunks[1]);
+                self.output_pane.render(frame, pane, &self.theme);
+                main
+            } else {
+                chunks[1]
+            };
+
             // Render current state
             match &mut self.state {
                 AppState::SelectingContext(selector) => {
-                    selector.render(frame, chunks[1], &self.theme);
+                    selector.render(frame, main_area, &self.theme);
+                }
+                AppState::BrowsingProjects(browser) => {
+                    browser.render(frame, main_area, &self.theme);
                 }
                 AppState::SelectingService(selector) => {
-                    selector.render(frame, chunks[1], &self.theme);
+                    selector.render(frame, main_area, &self.theme);
                 }
                 AppState::ActiveService(service) => {
-                    service.render(frame, chunks[1], &self.theme);
+                    service.render(frame, main_area, &self.theme);
                 }
             }
 
@@ -636,26 +2225,81 @@ impl App {
                     ActivePopup::ThemeSelector(selector) => {
                         selector.render(frame, frame.area(), &self.theme);
                     }
+                    ActivePopup::ProfileSelector(selector) => {
+                        selector.render(frame, frame.area(), &self.theme);
+                    }
+                    ActivePopup::AccountSelector(selector) => {
+                        selector.render(frame, frame.area(), &self.theme);
+                    }
+                    ActivePopup::Stats(stats) => {
+                        stats.render(frame, frame.area(), &self.theme);
+                    }
+                    ActivePopup::History(history) => {
+                        // Over the content only, so the bars around it aren't
+                        // cleared and sent again
+                        let area = if self.low_bandwidth == LowBandwidth::On {
+                            chunks[1]
+                        } else {
+                            frame.area()
+                        };
+                        history.render(frame, area, &self.theme);
+                    }
+                    ActivePopup::Bookmarks(bookmarks) => {
+                        let area = if self.low_bandwidth == LowBandwidth::On {
+                            chunks[1]
+                        } else {
+                            frame.area()
+                        };
+                        bookmarks.render(frame, area, &self.theme);
+                    }
+                    ActivePopup::Workspaces(workspaces) => {
+                        let area = if self.low_bandwidth == LowBandwidth::On {
+                            chunks[1]
+                        } else {
+                            frame.area()
+                        };
+                        workspaces.render(frame, area, &self.theme);
+                    }
+                    ActivePopup::ReleaseNotes(notes) => {
+                        notes.render(frame, frame.area(), &self.theme);
+                    }
                     ActivePopup::Error(dialog) => {
                         dialog.render(frame, frame.area(), &self.theme);
                     }
+                    ActivePopup::Quit(dialog) => {
+                        dialog.render(frame, frame.area(), &self.theme);
+                    }
                 }
             }
         })?;
+
+        if self.low_bandwidth == LowBandwidth::Auto && self.draw_timer.record(started.elapsed()) {
+            self.enable_low_bandwidth(tui);
+        }
         Ok(())
     }
 
+    fn window_title(&self) -> String {
+        let mut parts = vec!["lazycloud".to_string()];
+        if let Some(ctx) = &self.active_context {
+            parts.push(ctx.name().to_string());
+        }
+        parts.push(self.build_breadcrumbs().join(" > "));
+        parts.join(" — ")
+    }
+
     fn build_breadcrumbs(&self) -> Vec<String> {
         match &self.state {
             AppState::SelectingContext(_) => {
-                vec!["Select Context".to_string()]
+                vec![t("Select Context").to_string()]
             }
+            AppState::BrowsingProjects(browser) => browser.breadcrumbs(),
             AppState::SelectingService(_) => {
                 let mut bc = vec![];
                 if let Some(ctx) = &self.active_context {
                     bc.push(ctx.provider().display_name().to_string());
                 }
-                bc.push("Select Service".to_string());
+                bc.push(t("Select Service").to_string());
                 bc
             }
             AppState::ActiveService(service) => {
@@ -669,3 +2313,79 @@ impl App {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent};
+
+    use super::*;
+    use crate::provider;
+
+    fn demo_app() -> App {
+        let config = Arc::new(AppConfig::default());
+        let mut registry = ServiceRegistry::new();
+        provider::register_all(&mut registry, true);
+        let keybindings = registry.keybindings(&config.keybindings);
+        let resolver = Arc::new(KeyResolver::new(Arc::new(keybindings)));
+        let mut app = App::new(
+            registry,
+            context::demo_contexts(),
+            config,
+            None,
+            resolver,
+            Theme::default(),
+            StartupProfile::new(false),
+        );
+        app.demo = true;
+        app.popup = None;
+        app.deferred_commands = None;
+        app
+    }
+
+    /// Handle what the app sent itself until it goes quiet, as the run loop
+    /// would.
+    #[allow(clippy::future_not_send)]
+    async fn settle(app: &mut App) {
+        let quiet = Duration::from_millis(200);
+        while let Ok(Some(msg)) = tokio::time::timeout(quiet, app.msg_rx.recv()).await {
+            match msg {
+                AppMessage::CommandCompleted { .. } | AppMessage::CommandEmitted => {
+                    if let AppState::ActiveService(service) = &mut app.state {
+                        let result = service.update();
+                        app.process_update_result(result);
+                    }
+                }
+                AppMessage::GoBack => app.go_back(),
+                _ => {}
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_service_left_with_back_can_be_switched_back_to() {
+        let mut app = demo_app();
+        let context = context::demo_contexts().remove(0);
+        let service_id = ServiceId::gcp("secret-manager");
+        app.active_context = Some(context.clone());
+        app.start_service(&context, &service_id);
+        settle(&mut app).await;
+        let AppState::ActiveService(service) = &app.state else {
+            panic!("service did not start");
+        };
+        let breadcrumbs = service.breadcrumbs();
+
+        app.handle_event(&Event::Key(KeyEvent::from(KeyCode::Esc)))
+            .unwrap();
+        settle(&mut app).await;
+        assert!(matches!(app.state, AppState::SelectingService(_)));
+
+        app.switch_previous();
+        app.handle_event(&Event::Tick).unwrap();
+        settle(&mut app).await;
+        let AppState::ActiveService(service) = &app.state else {
+            panic!("switched back service closed again");
+        };
+        assert_eq!(service.breadcrumbs(), breadcrumbs);
+        assert_eq!(app.active_service, Some(service_id));
+    }
+}
@@ -0,0 +1,119 @@
+//! The changelog shipped with the binary.
+//!
+//! After an upgrade the releases since the last version that ran are shown
+//! once, unless `display.whats_new` is off. The version that last ran is kept
+//! in the state file.
+
+use tracing::warn;
+
+use crate::config::{load_state, save_last_version};
+
+const CHANGELOG: &str = include_str!("../CHANGELOG.md");
+
+/// Version of this build.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Notes of every release in the changelog, newest first.
+pub fn notes() -> String {
+    join(releases(CHANGELOG))
+}
+
+/// Notes of the releases above `version` in the changelog, newest first.
+pub fn notes_since(version: &str) -> String {
+    notes_in_since(CHANGELOG, version)
+}
+
+/// Notes of the releases in `changelog` above `version`.
+///
+/// Without a heading for `version`, e.g. after a version the changelog no
+/// longer lists, only the unreleased changes are returned, if any, instead of
+/// every release.
+fn notes_in_since(changelog: &str, version: &str) -> String {
+    let heading = format!("## [{version}]");
+    if changelog.lines().any(|line| line.starts_with(&heading)) {
+        return join(releases(changelog).take_while(|line| !line.starts_with(&heading)));
+    }
+    let mut unreleased = releases(changelog).skip_while(|line| !line.starts_with(UNRELEASED));
+    let Some(first) = unreleased.next() else {
+        return String::new();
+    };
+    join(std::iter::once(first).chain(unreleased.take_while(|line| !line.starts_with("## "))))
+}
+
+/// Heading of the changes since the latest release.
+const UNRELEASED: &str = "## [Unreleased]";
+
+fn join<'a>(lines: impl Iterator<Item = &'a str>) -> String {
+    lines.collect::<Vec<_>>().join("\n").trim().to_string()
+}
+
+/// Lines of `changelog` from the first release on.
+///
+/// Each release starts at a `## [version]` heading, as git-cliff writes it;
+/// everything before the first one is the file's preamble and left out.
+fn releases(changelog: &str) -> impl Iterator<Item = &str> {
+    changelog
+        .lines()
+        .skip_while(|line| !line.starts_with("## "))
+}
+
+/// Record that this version ran and return the notes since the previous one,
+/// if it was an older version. A first run has nothing to catch up on.
+pub fn take_upgrade_notes() -> Option<String> {
+    let previous = load_state().last_version;
+    if previous.as_deref() == Some(VERSION) {
+        return None;
+    }
+    if let Err(err) = save_last_version(VERSION) {
+        warn!("Failed to remember the version: {err}");
+    }
+    let notes = notes_since(&previous?);
+    (!notes.is_empty()).then_some(notes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = "# Changelog
+
+## [Unreleased]
+
+### Features
+
+- Report stale secrets
+
+## [0.2.0] - 2026-09-01
+
+### Bug Fixes
+
+- Keep the selection on refresh
+
+## [0.1.0] - 2026-08-01
+
+### Features
+
+- Browse secrets
+";
+
+    #[test]
+    fn test_notes_since_stops_at_version() {
+        let notes = notes_in_since(FIXTURE, "0.1.0");
+        assert!(notes.starts_with("## [Unreleased]"));
+        assert!(notes.contains("## [0.2.0]"));
+        assert!(!notes.contains("## [0.1.0]"));
+    }
+
+    #[test]
+    fn test_notes_since_unknown_version_are_unreleased_only() {
+        let notes = notes_in_since(FIXTURE, "0.0.9");
+        assert!(notes.starts_with("## [Unreleased]"));
+        assert!(notes.ends_with("- Report stale secrets"));
+
+        let released = FIXTURE.replace(
+            "## [Unreleased]\n\n### Features\n\n- Report stale secrets\n\n",
+            "",
+        );
+        assert_eq!(notes_in_since(&released, "0.0.9"), "");
+    }
+}
@@ -1,4 +1,8 @@
-use clap::Parser;
+use clap::builder::PossibleValuesParser;
+use clap::{Parser, Subcommand};
+use clap_complete::engine::ArgValueCompleter;
+
+use crate::completions::{self, complete_contexts, complete_profiles, complete_services};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -8,10 +12,51 @@ use clap::Parser;
 )]
 pub struct Args {
     /// Context name (e.g., "default", "prod")
-    #[arg(short, long)]
+    #[arg(short, long, add = ArgValueCompleter::new(complete_contexts))]
     pub context: Option<String>,
 
     /// Service name (e.g., "secret-manager")
-    #[arg(short, long)]
+    #[arg(short, long, add = ArgValueCompleter::new(complete_services))]
     pub service: Option<String>,
+
+    /// Saved workspace to restore (e.g., "oncall")
+    #[arg(short, long, conflicts_with_all = ["context", "service"])]
+    pub workspace: Option<String>,
+
+    /// Config profile to apply over config.toml (e.g., "work")
+    #[arg(short, long, add = ArgValueCompleter::new(complete_profiles))]
+    pub profile: Option<String>,
+
+    /// Explore the UI with built-in demo data instead of cloud credentials
+    #[arg(long)]
+    pub demo: bool,
+
+    /// Render without colors and with ASCII symbols only (also set by `NO_COLOR`)
+    #[arg(long)]
+    pub no_color: bool,
+
+    /// Print how long each startup phase took on exit
+    #[arg(long)]
+    pub profile_startup: bool,
+
+    #[command(subcommand)]
+    pub command: Option<CliCommand>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CliCommand {
+    /// Open a link, e.g. `lazycloud://gcp/prod/secret-manager/db-password`
+    ///
+    /// Links without a context, such as `gcp:secret-manager/db-password`,
+    /// open in the context given with -c or the last one.
+    Open { link: String },
+    /// Print the resolved config, state, cache and log paths
+    Paths,
+    /// Print the man page in roff format, e.g. to `lazycloud.1`
+    Man,
+    /// Print the script that enables shell completion, e.g. for `~/.bashrc`
+    Completions {
+        #[arg(value_parser = PossibleValuesParser::new(completions::shells()))]
+        shell: String,
+    },
 }
@@ -0,0 +1,263 @@
+//! Copying text to the clipboard through the backend that works where
+//! lazycloud runs.
+//!
+//! arboard talks to the system clipboard directly, but loses the selection on
+//! X11 and Wayland once it is dropped, so `wl-copy` and `xclip` are preferred
+//! there. Over SSH the local clipboard is out of reach and the text is handed
+//! to the terminal with an OSC 52 escape sequence instead. The `clipboard`
+//! setting picks a backend explicitly; `auto` chooses one from the session.
+
+use std::env;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::{LazyLock, PoisonError, RwLock};
+
+use arboard::Clipboard;
+use color_eyre::Result;
+use color_eyre::eyre::{bail, eyre};
+use serde::{Deserialize, Serialize};
+
+/// The `clipboard` setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ClipboardBackend {
+    /// Chosen from the session, see [`provider`].
+    #[default]
+    Auto,
+    /// The system clipboard through arboard.
+    System,
+    /// `wl-copy` on Wayland, `xclip` otherwise.
+    Command,
+    /// The terminal, with an OSC 52 escape sequence.
+    Osc52,
+    /// Nothing is copied.
+    None,
+}
+
+/// A way of putting text on the clipboard.
+pub trait ClipboardProvider: Send {
+    /// Name shown when copying fails.
+    fn name(&self) -> &'static str;
+
+    fn copy(&self, text: &str) -> Result<()>;
+
+    /// Why copying does nothing, for backends that cannot copy.
+    fn warning(&self) -> Option<&'static str> {
+        None
+    }
+}
+
+/// The system clipboard.
+pub struct ArboardProvider;
+
+impl ClipboardProvider for ArboardProvider {
+    fn name(&self) -> &'static str {
+        "system clipboard"
+    }
+
+    fn copy(&self, text: &str) -> Result<()> {
+        Clipboard::new()?.set_text(text)?;
+        Ok(())
+    }
+}
+
+/// A program reading the text from stdin, such as `wl-copy`.
+pub struct CommandProvider {
+    program: &'static str,
+    args: &'static [&'static str],
+}
+
+impl CommandProvider {
+    pub const WL_COPY: Self = Self {
+        program: "wl-copy",
+        args: &[],
+    };
+    pub const XCLIP: Self = Self {
+        program: "xclip",
+        args: &["-selection", "clipboard"],
+    };
+}
+
+impl ClipboardProvider for CommandProvider {
+    fn name(&self) -> &'static str {
+        self.program
+    }
+
+    fn copy(&self, text: &str) -> Result<()> {
+        let mut child = Command::new(self.program)
+            .args(self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?;
+        child
+            .stdin
+            .take()
+            .ok_or_else(|| eyre!("No stdin for {}", self.program))?
+            .write_all(text.as_bytes())?;
+        let status = child.wait()?;
+        if !status.success() {
+            bail!("{} exited with {status}", self.program);
+        }
+        Ok(())
+    }
+}
+
+/// The terminal's clipboard, which reaches the local machine over SSH.
+pub struct Osc52Provider;
+
+impl ClipboardProvider for Osc52Provider {
+    fn name(&self) -> &'static str {
+        "OSC 52"
+    }
+
+    fn copy(&self, text: &str) -> Result<()> {
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(osc52_sequence(text, env::var_os("TMUX").is_some()).as_bytes())?;
+        stdout.flush()?;
+        Ok(())
+    }
+}
+
+/// Copies nothing, for sessions without any clipboard.
+pub struct NoopProvider;
+
+impl ClipboardProvider for NoopProvider {
+    fn name(&self) -> &'static str {
+        "none"
+    }
+
+    fn copy(&self, _text: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn warning(&self) -> Option<&'static str> {
+        Some("Clipboard is disabled, nothing was copied")
+    }
+}
+
+static BACKEND: LazyLock<RwLock<ClipboardBackend>> = LazyLock::new(RwLock::default);
+
+/// Use `backend` for copies from now on.
+pub fn set_backend(backend: ClipboardBackend) {
+    *BACKEND.write().unwrap_or_else(PoisonError::into_inner) = backend;
+}
+
+/// The provider for the configured backend.
+///
+/// `auto` uses OSC 52 over SSH, `wl-copy` or `xclip` when the display server
+/// has one installed, and the system clipboard otherwise.
+pub fn provider() -> Box<dyn ClipboardProvider> {
+    let backend = *BACKEND.read().unwrap_or_else(PoisonError::into_inner);
+    let wayland = env::var_os("WAYLAND_DISPLAY").is_some();
+    match backend {
+        ClipboardBackend::System => Box::new(ArboardProvider),
+        ClipboardBackend::Command if wayland => Box::new(CommandProvider::WL_COPY),
+        ClipboardBackend::Command => Box::new(CommandProvider::XCLIP),
+        ClipboardBackend::Osc52 => Box::new(Osc52Provider),
+        ClipboardBackend::None => Box::new(NoopProvider),
+        ClipboardBackend::Auto => {
+            if env::var_os("SSH_CONNECTION").is_some() || env::var_os("SSH_TTY").is_some() {
+                Box::new(Osc52Provider)
+            } else if wayland && on_path("wl-copy") {
+                Box::new(CommandProvider::WL_COPY)
+            } else if env::var_os("DISPLAY").is_some() && on_path("xclip") {
+                Box::new(CommandProvider::XCLIP)
+            } else {
+                Box::new(ArboardProvider)
+            }
+        }
+    }
+}
+
+fn on_path(program: &str) -> bool {
+    env::var_os("PATH").is_some_and(|paths| {
+        env::split_paths(&paths).any(|dir| Path::new(&dir).join(program).is_file())
+    })
+}
+
+/// The escape sequence setting the clipboard to `text`, passed through tmux
+/// when running inside it.
+fn osc52_sequence(text: &str, tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+    if tmux {
+        format!("\x1bPtmux;\x1b{sequence}\x1b\\")
+    } else {
+        sequence
+    }
+}
+
+/// Standard base64 with padding.
+pub fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(char::from(ALPHABET[(n >> (18 - 6 * i)) as usize & 0x3f]));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Bytes of standard base64 `text`, `None` if it is not valid base64.
+pub fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    let text = text.trim_end_matches('=');
+    let mut decoded = Vec::with_capacity(text.len() * 3 / 4);
+    let mut bits = 0u32;
+    let mut count = 0;
+    for byte in text.bytes() {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        // Only the bits not yet written out are kept
+        bits = ((bits << 6) | u32::from(value)) & 0x3fff;
+        count += 6;
+        if count >= 8 {
+            count -= 8;
+            decoded.push((bits >> count).to_be_bytes()[3]);
+        }
+    }
+    Some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(base64_decode("Zm8=").as_deref(), Some(&b"fo"[..]));
+        assert_eq!(base64_decode("Zm9vYmFy").as_deref(), Some(&b"foobar"[..]));
+        assert_eq!(base64_decode("Zm9v!"), None);
+    }
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(osc52_sequence("hi", false), "\x1b]52;c;aGk=\x07");
+        assert_eq!(
+            osc52_sequence("hi", true),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+    }
+}
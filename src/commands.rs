@@ -4,13 +4,36 @@
 //! Services return commands, and the App spawns them with automatic
 //! completion detection and status tracking.
 
+mod check_update;
 mod clipboard;
+mod export;
+mod load_hierarchy;
+mod open_link;
+mod open_url;
+mod progress;
+mod shell;
+mod stream;
+mod validate_context;
+mod warm_up;
+mod workflow;
 
-use crate::app::AppMessage;
 use async_trait::async_trait;
+pub use check_update::CheckUpdateCmd;
 pub use clipboard::CopyToClipboardCmd;
 use color_eyre::Result;
+pub use export::{ExportRow, ExportTableCmd, TableExport};
+pub use load_hierarchy::LoadHierarchyCmd;
+pub use open_link::OpenLinkCmd;
+pub use open_url::OpenUrlCmd;
+pub use progress::Progress;
+pub use shell::{ShellCmd, shell};
+pub use stream::StreamCmd;
 use tokio::sync::mpsc::UnboundedSender;
+pub use validate_context::ValidateContextCmd;
+pub use warm_up::WarmUpCmd;
+pub use workflow::{StepStatus, StepTracker, TrackedStep, Workflow, WorkflowStep};
+
+use crate::app::AppMessage;
 
 /// Async commands that perform side effects.
 ///
@@ -22,6 +45,33 @@ pub trait Command: Send + 'static {
     /// Include context like secret names, version IDs, etc.
     fn name(&self) -> String;
 
+    /// The resource acted on, e.g. a secret name, for the command history.
+    fn resource(&self) -> Option<String> {
+        None
+    }
+
+    /// Whether the command changes something, in the cloud or on disk.
+    /// Quitting waits for these to finish unless told not to.
+    fn mutates(&self) -> bool {
+        false
+    }
+
+    /// Progress of the individual steps, for commands made of several.
+    fn steps(&self) -> Option<StepTracker> {
+        None
+    }
+
+    /// Items done so far, for commands working through a batch.
+    fn progress(&self) -> Option<Progress> {
+        None
+    }
+
+    /// A fresh copy to run again from the command history, for commands
+    /// that can be re-created.
+    fn retry(&self) -> Option<Box<dyn Command>> {
+        None
+    }
+
     /// Execute the commands.
     async fn execute(self: Box<Self>, action_tx: UnboundedSender<AppMessage>) -> Result<()>;
 }
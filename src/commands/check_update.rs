@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use color_eyre::Result;
+use serde::Deserialize;
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::debug;
+
+use crate::app::AppMessage;
+use crate::changelog::VERSION;
+use crate::commands::Command;
+
+const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/jorgeparavicini/lazycloud/releases/latest";
+
+#[derive(Deserialize)]
+struct Release {
+    tag_name: String,
+    #[serde(default)]
+    body: Option<String>,
+}
+
+/// Asks GitHub for the latest release and reports it when it is newer than
+/// this build. Failures are only logged, the check is best effort.
+pub struct CheckUpdateCmd;
+
+#[async_trait]
+impl Command for CheckUpdateCmd {
+    fn name(&self) -> String {
+        "Checking for updates".to_string()
+    }
+
+    async fn execute(self: Box<Self>, action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let response = reqwest::Client::new()
+            .get(LATEST_RELEASE_URL)
+            .header(reqwest::header::USER_AGENT, format!("lazycloud/{VERSION}"))
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status);
+        let release: Release = match response {
+            Ok(response) => response.json().await?,
+            Err(err) => {
+                debug!("Update check failed: {err}");
+                return Ok(());
+            }
+        };
+
+        let version = release.tag_name.trim_start_matches('v').to_string();
+        if is_newer(&version, VERSION) {
+            action_tx.send(AppMessage::UpdateAvailable {
+                version,
+                notes: release.body.unwrap_or_default(),
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether `version` comes after `current`, comparing `major.minor.patch`.
+/// Pre-releases and unparseable versions never count as newer.
+fn is_newer(version: &str, current: &str) -> bool {
+    let parse = |version: &str| -> Option<Vec<u64>> {
+        version.split('.').map(|part| part.parse().ok()).collect()
+    };
+    match (parse(version), parse(current)) {
+        (Some(version), Some(current)) => version > current,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_newer() {
+        assert!(is_newer("0.2.0", "0.1.0"));
+        assert!(is_newer("0.10.0", "0.9.3"));
+        assert!(!is_newer("0.1.0", "0.1.0"));
+        assert!(!is_newer("0.0.9", "0.1.0"));
+        assert!(!is_newer("0.2.0-rc.1", "0.1.0"));
+    }
+}
@@ -1,14 +1,15 @@
-use arboard::Clipboard;
 use async_trait::async_trait;
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use tokio::sync::mpsc::UnboundedSender;
 
 use crate::app::AppMessage;
+use crate::clipboard;
 use crate::commands::Command;
 use crate::ui::ToastType;
 
-use color_eyre::Result;
-use tokio::sync::mpsc::UnboundedSender;
-
-/// Copies a string to the system clipboard and shows a success toast notification.
+/// Copies a string with the configured [`clipboard`] backend and shows a
+/// success toast notification.
 pub struct CopyToClipboardCmd {
     text: String,
     toast_message: String,
@@ -30,8 +31,20 @@ impl Command for CopyToClipboardCmd {
     }
 
     async fn execute(self: Box<Self>, action_tx: UnboundedSender<AppMessage>) -> Result<()> {
-        let mut clipboard = Clipboard::new()?;
-        clipboard.set_text(self.text)?;
+        let provider = clipboard::provider();
+        if let Some(warning) = provider.warning() {
+            action_tx.send(AppMessage::ShowToast {
+                message: warning.to_string(),
+                toast_type: ToastType::Warning,
+            })?;
+            return Ok(());
+        }
+        let name = provider.name();
+        // Clipboard programs may take a moment, keep them off the runtime
+        let text = self.text;
+        tokio::task::spawn_blocking(move || provider.copy(&text))
+            .await?
+            .map_err(|err| eyre!("Copying with {name} failed: {err}"))?;
         action_tx.send(AppMessage::ShowToast {
             message: format!("Copied {}", self.toast_message),
             toast_type: ToastType::Success,
@@ -0,0 +1,171 @@
+use std::fs;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use color_eyre::Result;
+use serde_json::{Map, Value};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::app::AppMessage;
+use crate::commands::Command;
+use crate::ui::{Table, TableRow, ToastType};
+
+/// A table row that can be written to a file, one value per column.
+pub trait ExportRow: TableRow {
+    /// Plain text of each column, in the order of [`TableRow::columns`].
+    fn export_values(&self) -> Vec<String>;
+}
+
+/// The rows a table shows, captured for [`ExportTableCmd`].
+#[derive(Debug, Clone)]
+pub struct TableExport {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl TableExport {
+    /// The rows passing the current search and filters, in display order.
+    pub fn of<T: ExportRow + Clone>(table: &Table<T>) -> Self {
+        Self {
+            headers: table
+                .column_defs()
+                .iter()
+                .map(|column| column.header.to_string())
+                .collect(),
+            rows: table
+                .filtered_items()
+                .map(ExportRow::export_values)
+                .collect(),
+        }
+    }
+
+    const fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// An array with an object per row, keyed by column header.
+    fn to_json(&self) -> Result<String> {
+        let rows: Vec<Value> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let object: Map<String, Value> = self
+                    .headers
+                    .iter()
+                    .cloned()
+                    .zip(row.iter().cloned().map(Value::String))
+                    .collect();
+                Value::Object(object)
+            })
+            .collect();
+        Ok(serde_json::to_string_pretty(&rows)? + "\n")
+    }
+
+    /// A header line followed by a line per row, quoted as in RFC 4180.
+    fn to_csv(&self) -> String {
+        std::iter::once(&self.headers)
+            .chain(&self.rows)
+            .map(|fields| {
+                let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+                fields.join(",") + "\r\n"
+            })
+            .collect()
+    }
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Writes a [`TableExport`] to a file, as CSV for a `.csv` path and as JSON
+/// otherwise, and shows a toast once done.
+#[derive(Clone)]
+pub struct ExportTableCmd {
+    export: TableExport,
+    path: PathBuf,
+}
+
+impl ExportTableCmd {
+    #[must_use]
+    pub const fn new(export: TableExport, path: PathBuf) -> Self {
+        Self { export, path }
+    }
+
+    fn is_csv(&self) -> bool {
+        self.path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+    }
+}
+
+#[async_trait]
+impl Command for ExportTableCmd {
+    fn name(&self) -> String {
+        format!(
+            "Exporting {} rows to {}",
+            self.export.len(),
+            self.path.display()
+        )
+    }
+
+    fn mutates(&self) -> bool {
+        true
+    }
+
+    fn retry(&self) -> Option<Box<dyn Command>> {
+        Some(Box::new(self.clone()))
+    }
+
+    async fn execute(self: Box<Self>, action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let contents = if self.is_csv() {
+            self.export.to_csv()
+        } else {
+            self.export.to_json()?
+        };
+        fs::write(&self.path, contents)?;
+
+        action_tx.send(AppMessage::ShowToast {
+            message: format!(
+                "Exported {} rows to {}",
+                self.export.len(),
+                self.path.display()
+            ),
+            toast_type: ToastType::Success,
+        })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn export() -> TableExport {
+        TableExport {
+            headers: vec!["Key".to_string(), "Value".to_string()],
+            rows: vec![
+                vec!["env".to_string(), "prod".to_string()],
+                vec!["note".to_string(), "a, \"b\"".to_string()],
+            ],
+        }
+    }
+
+    #[test]
+    fn test_csv_quotes_fields() {
+        assert_eq!(
+            export().to_csv(),
+            "Key,Value\r\nenv,prod\r\nnote,\"a, \"\"b\"\"\"\r\n"
+        );
+    }
+
+    #[test]
+    fn test_json_keys_rows_by_header() {
+        let json: Value = serde_json::from_str(&export().to_json().unwrap()).unwrap();
+        assert_eq!(json[0]["Key"], "env");
+        assert_eq!(json[1]["Value"], "a, \"b\"");
+    }
+}
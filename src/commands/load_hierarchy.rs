@@ -0,0 +1,46 @@
+use async_trait::async_trait;
+use color_eyre::Result;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::app::AppMessage;
+use crate::commands::Command;
+use crate::context::GcpContext;
+use crate::error::Error;
+use crate::provider::gcp::list_hierarchy;
+
+/// Lists one level of the GCP resource hierarchy for the project browser.
+///
+/// The outcome is reported through [`AppMessage::HierarchyLoaded`], so the
+/// browser stops waiting for the level even when listing failed.
+pub struct LoadHierarchyCmd {
+    context: GcpContext,
+    parent: Option<String>,
+}
+
+impl LoadHierarchyCmd {
+    #[must_use]
+    pub const fn new(context: GcpContext, parent: Option<String>) -> Self {
+        Self { context, parent }
+    }
+}
+
+#[async_trait]
+impl Command for LoadHierarchyCmd {
+    fn name(&self) -> String {
+        self.parent.as_ref().map_or_else(
+            || "Listing organizations".to_string(),
+            |parent| format!("Listing {parent}"),
+        )
+    }
+
+    async fn execute(self: Box<Self>, action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let result = list_hierarchy(&self.context, self.parent.as_deref())
+            .await
+            .map_err(|err| Error::from_report(&err));
+        action_tx.send(AppMessage::HierarchyLoaded {
+            parent: self.parent,
+            result,
+        })?;
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+use color_eyre::Result;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::app::AppMessage;
+use crate::commands::Command;
+use crate::registry::ResourceLink;
+
+/// Switches to the service a [`ResourceLink`] points into and shows the
+/// linked resource there.
+pub struct OpenLinkCmd {
+    link: ResourceLink,
+}
+
+impl OpenLinkCmd {
+    #[must_use]
+    pub const fn new(link: ResourceLink) -> Self {
+        Self { link }
+    }
+}
+
+#[async_trait]
+impl Command for OpenLinkCmd {
+    fn name(&self) -> String {
+        format!("Opening {}", self.link)
+    }
+
+    async fn execute(self: Box<Self>, action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        action_tx.send(AppMessage::OpenLink(self.link))?;
+        Ok(())
+    }
+}
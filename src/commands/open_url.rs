@@ -0,0 +1,77 @@
+use std::process::Stdio;
+
+use async_trait::async_trait;
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use tokio::process::Command as Process;
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::debug;
+
+use crate::app::AppMessage;
+use crate::clipboard;
+use crate::commands::Command;
+use crate::ui::ToastType;
+
+/// Opens a URL in the default browser. Without a browser, e.g. over SSH, the
+/// URL is copied to the clipboard instead.
+pub struct OpenUrlCmd {
+    url: String,
+}
+
+impl OpenUrlCmd {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+}
+
+/// The platform's program for opening a URL with its default handler.
+fn opener(url: &str) -> Process {
+    let mut process = if cfg!(target_os = "macos") {
+        Process::new("open")
+    } else if cfg!(windows) {
+        // The empty argument is the window title `start` would take the URL for
+        let mut process = Process::new("cmd");
+        process.args(["/C", "start", ""]);
+        process
+    } else {
+        Process::new("xdg-open")
+    };
+    process.arg(url);
+    process
+}
+
+#[async_trait]
+impl Command for OpenUrlCmd {
+    fn name(&self) -> String {
+        format!("Opening {}", self.url)
+    }
+
+    async fn execute(self: Box<Self>, action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        // The opener must not write into the terminal the UI is drawn on
+        let status = opener(&self.url)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await;
+        match status {
+            Ok(status) if status.success() => return Ok(()),
+            Ok(status) => debug!("Opening {} exited with {status}", self.url),
+            Err(err) => debug!("Opening {} failed: {err}", self.url),
+        }
+
+        let provider = clipboard::provider();
+        if provider.warning().is_some() {
+            return Err(eyre!("No browser to open {}", self.url));
+        }
+        let url = self.url;
+        tokio::task::spawn_blocking(move || provider.copy(&url))
+            .await?
+            .map_err(|err| eyre!("No browser to open the URL and copying it failed: {err}"))?;
+        action_tx.send(AppMessage::ShowToast {
+            message: "No browser found, copied the URL".to_string(),
+            toast_type: ToastType::Info,
+        })?;
+        Ok(())
+    }
+}
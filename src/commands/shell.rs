@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+use color_eyre::Result;
+use tokio::process::Command as Process;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::app::AppMessage;
+use crate::commands::Command;
+
+/// Runs a shell command in the background and reports its captured output.
+pub struct ShellCmd {
+    name: String,
+    command: String,
+}
+
+impl ShellCmd {
+    pub fn new(name: impl Into<String>, command: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            command: command.into(),
+        }
+    }
+}
+
+/// The user's shell, falling back to `sh`.
+#[must_use]
+pub fn shell() -> String {
+    std::env::var("SHELL").unwrap_or_else(|_| "sh".to_string())
+}
+
+#[async_trait]
+impl Command for ShellCmd {
+    fn name(&self) -> String {
+        format!("Running {}", self.name)
+    }
+
+    async fn execute(self: Box<Self>, action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let output = Process::new(shell())
+            .arg("-c")
+            .arg(&self.command)
+            .kill_on_drop(true)
+            .output()
+            .await?;
+
+        let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !stderr.is_empty() {
+            if !text.is_empty() && !text.ends_with('\n') {
+                text.push('\n');
+            }
+            text.push_str(&stderr);
+        }
+        if text.is_empty() {
+            text = "(no output)".to_string();
+        }
+
+        action_tx.send(AppMessage::AppendOutput {
+            title: format!("{} ({})", self.name, output.status),
+            output: text,
+            success: output.status.success(),
+        })?;
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use color_eyre::Result;
+use futures::stream::BoxStream;
+use futures::{Stream, StreamExt};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
+
+use crate::app::AppMessage;
+use crate::commands::Command;
+
+/// Feeds the items of a stream to a service as they arrive, e.g. tailed log
+/// lines or the results of a watch, until the stream ends or is cancelled.
+///
+/// Pass a token from [`ServiceShell::view_token`] to stop the stream once
+/// the screen it feeds is left. An error ends the stream and is reported
+/// like that of any other command.
+///
+/// [`ServiceShell::view_token`]: crate::service::ServiceShell::view_token
+pub struct StreamCmd<M> {
+    name: String,
+    resource: Option<String>,
+    stream: BoxStream<'static, Result<M>>,
+    tx: UnboundedSender<M>,
+    cancel: CancellationToken,
+}
+
+impl<M: Send + 'static> StreamCmd<M> {
+    pub fn new(
+        name: impl Into<String>,
+        stream: impl Stream<Item = Result<M>> + Send + 'static,
+        tx: UnboundedSender<M>,
+        cancel: CancellationToken,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            resource: None,
+            stream: stream.boxed(),
+            tx,
+            cancel,
+        }
+    }
+
+    #[must_use]
+    pub fn with_resource(mut self, resource: impl Into<String>) -> Self {
+        self.resource = Some(resource.into());
+        self
+    }
+}
+
+#[async_trait]
+impl<M: Send + 'static> Command for StreamCmd<M> {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn resource(&self) -> Option<String> {
+        self.resource.clone()
+    }
+
+    async fn execute(mut self: Box<Self>, action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        loop {
+            tokio::select! {
+                () = self.cancel.cancelled() => break,
+                next = self.stream.next() => {
+                    let Some(msg) = next else { break };
+                    // A closed queue means the service is gone
+                    if self.tx.send(msg?).is_err() {
+                        break;
+                    }
+                    action_tx.send(AppMessage::CommandEmitted)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
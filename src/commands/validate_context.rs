@@ -0,0 +1,38 @@
+use async_trait::async_trait;
+use color_eyre::Result;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::app::AppMessage;
+use crate::commands::Command;
+use crate::context::CloudContext;
+
+/// Checks a context's credentials and project in the background.
+///
+/// The outcome is reported through [`AppMessage::ContextValidated`], a
+/// failed check is not a command error.
+pub struct ValidateContextCmd {
+    context: CloudContext,
+}
+
+impl ValidateContextCmd {
+    #[must_use]
+    pub const fn new(context: CloudContext) -> Self {
+        Self { context }
+    }
+}
+
+#[async_trait]
+impl Command for ValidateContextCmd {
+    fn name(&self) -> String {
+        format!("Checking {}", self.context.name())
+    }
+
+    async fn execute(self: Box<Self>, action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let health = self.context.validate().await;
+        action_tx.send(AppMessage::ContextValidated {
+            context: self.context.name().to_string(),
+            health,
+        })?;
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use std::time::Instant;
+
+use async_trait::async_trait;
+use color_eyre::Result;
+use futures::future::BoxFuture;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::app::AppMessage;
+use crate::commands::Command;
+use crate::registry::{ServiceId, WarmClient};
+
+/// Initializes a service client in the background and reports the outcome.
+///
+/// Failures are reported through [`AppMessage::ServiceWarmedUp`] rather than
+/// as command errors; the service retries on its own when it is entered.
+pub struct WarmUpCmd {
+    context: String,
+    service: ServiceId,
+    display_name: &'static str,
+    future: BoxFuture<'static, Result<WarmClient>>,
+}
+
+impl WarmUpCmd {
+    pub fn new(
+        context: impl Into<String>,
+        service: ServiceId,
+        display_name: &'static str,
+        future: BoxFuture<'static, Result<WarmClient>>,
+    ) -> Self {
+        Self {
+            context: context.into(),
+            service,
+            display_name,
+            future,
+        }
+    }
+}
+
+#[async_trait]
+impl Command for WarmUpCmd {
+    fn name(&self) -> String {
+        format!("Initializing {}", self.display_name)
+    }
+
+    async fn execute(self: Box<Self>, action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let start = Instant::now();
+        let result = self.future.await.map_err(|err| err.to_string());
+        action_tx.send(AppMessage::ServiceWarmedUp {
+            context: self.context,
+            service: self.service,
+            result,
+            elapsed: start.elapsed(),
+        })?;
+        Ok(())
+    }
+}
@@ -0,0 +1,256 @@
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use color_eyre::Result;
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::warn;
+
+use crate::app::AppMessage;
+use crate::commands::Command;
+
+/// One step of a [`Workflow`], sharing the context `C` with the others.
+#[async_trait]
+pub trait WorkflowStep<C>: Send + Sync {
+    /// Short label shown under the workflow in the command panel.
+    fn name(&self) -> String;
+
+    /// Perform the step.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error to stop the workflow and roll back finished steps.
+    async fn run(&self, context: &mut C) -> Result<()>;
+
+    /// Undo the step after a later one failed.
+    ///
+    /// # Errors
+    ///
+    /// Failures are logged; the remaining steps are still rolled back.
+    async fn rollback(&self, context: &mut C) -> Result<()> {
+        _ = context;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepStatus {
+    Pending,
+    Running,
+    Done,
+    Failed,
+    RolledBack,
+}
+
+/// A step as last reported by the workflow.
+#[derive(Debug, Clone)]
+pub struct TrackedStep {
+    pub name: String,
+    pub status: StepStatus,
+    started: Option<Instant>,
+    finished: Option<Instant>,
+}
+
+impl TrackedStep {
+    const fn new(name: String) -> Self {
+        Self {
+            name,
+            status: StepStatus::Pending,
+            started: None,
+            finished: None,
+        }
+    }
+
+    /// How long the step ran, so far if it still runs.
+    pub fn elapsed(&self) -> Option<Duration> {
+        let started = self.started?;
+        Some(self.finished.unwrap_or_else(Instant::now) - started)
+    }
+}
+
+/// Status of each step, shared with the command panel while the workflow runs.
+#[derive(Debug, Clone, Default)]
+pub struct StepTracker(Arc<Mutex<Vec<TrackedStep>>>);
+
+impl StepTracker {
+    pub fn steps(&self) -> Vec<TrackedStep> {
+        self.0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// The step that stopped the workflow, if any.
+    #[must_use]
+    pub fn failed_step(&self) -> Option<String> {
+        self.steps()
+            .into_iter()
+            .find(|step| step.status == StepStatus::Failed)
+            .map(|step| step.name)
+    }
+
+    fn set(&self, index: usize, status: StepStatus) {
+        if let Some(step) = self
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_mut(index)
+        {
+            match status {
+                StepStatus::Running => step.started = Some(Instant::now()),
+                StepStatus::Done | StepStatus::Failed => step.finished = Some(Instant::now()),
+                StepStatus::Pending | StepStatus::RolledBack => {}
+            }
+            step.status = status;
+        }
+    }
+}
+
+/// Runs steps in order as a single command.
+///
+/// When a step fails, the steps before it are rolled back in reverse order
+/// and the workflow fails with the step's error.
+pub struct Workflow<C> {
+    name: String,
+    context: C,
+    steps: Vec<Box<dyn WorkflowStep<C>>>,
+    tracker: StepTracker,
+}
+
+impl<C: Send + 'static> Workflow<C> {
+    pub fn new(name: impl Into<String>, context: C) -> Self {
+        Self {
+            name: name.into(),
+            context,
+            steps: Vec::new(),
+            tracker: StepTracker::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn step(mut self, step: impl WorkflowStep<C> + 'static) -> Self {
+        self.tracker
+            .0
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(TrackedStep::new(step.name()));
+        self.steps.push(Box::new(step));
+        self
+    }
+
+    async fn run(mut self) -> Result<()> {
+        for (index, step) in self.steps.iter().enumerate() {
+            self.tracker.set(index, StepStatus::Running);
+            if let Err(err) = step.run(&mut self.context).await {
+                self.tracker.set(index, StepStatus::Failed);
+                for (index, step) in self.steps[..index].iter().enumerate().rev() {
+                    match step.rollback(&mut self.context).await {
+                        Ok(()) => self.tracker.set(index, StepStatus::RolledBack),
+                        Err(rollback_err) => warn!(
+                            "Rolling back '{}' of '{}' failed: {rollback_err}",
+                            step.name(),
+                            self.name
+                        ),
+                    }
+                }
+                return Err(err.wrap_err(format!("{} failed", step.name())));
+            }
+            self.tracker.set(index, StepStatus::Done);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<C: Send + 'static> Command for Workflow<C> {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn mutates(&self) -> bool {
+        true
+    }
+
+    fn steps(&self) -> Option<StepTracker> {
+        Some(self.tracker.clone())
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        self.run().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::eyre;
+
+    use super::*;
+
+    type Log = Arc<Mutex<Vec<String>>>;
+
+    struct Record {
+        name: &'static str,
+        fail: bool,
+        log: Log,
+    }
+
+    #[async_trait]
+    impl WorkflowStep<()> for Record {
+        fn name(&self) -> String {
+            self.name.to_string()
+        }
+
+        async fn run(&self, _context: &mut ()) -> Result<()> {
+            if self.fail {
+                return Err(eyre!("boom"));
+            }
+            self.log.lock().unwrap().push(format!("run {}", self.name));
+            Ok(())
+        }
+
+        async fn rollback(&self, _context: &mut ()) -> Result<()> {
+            self.log.lock().unwrap().push(format!("undo {}", self.name));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rollback_in_reverse_order() {
+        let log = Log::default();
+        let step = |name, fail| Record {
+            name,
+            fail,
+            log: log.clone(),
+        };
+        let workflow = Workflow::new("test", ())
+            .step(step("create", false))
+            .step(step("label", false))
+            .step(step("verify", true))
+            .step(step("notify", false));
+        let tracker = workflow.tracker.clone();
+
+        let err = workflow.run().await.unwrap_err();
+        assert_eq!(err.to_string(), "verify failed");
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["run create", "run label", "undo label", "undo create"]
+        );
+        let steps: Vec<(String, StepStatus)> = tracker
+            .steps()
+            .into_iter()
+            .map(|step| (step.name, step.status))
+            .collect();
+        assert_eq!(
+            steps,
+            [
+                ("create".to_string(), StepStatus::RolledBack),
+                ("label".to_string(), StepStatus::RolledBack),
+                ("verify".to_string(), StepStatus::Failed),
+                ("notify".to_string(), StepStatus::Pending),
+            ]
+        );
+        assert_eq!(tracker.failed_step().as_deref(), Some("verify"));
+        assert!(tracker.steps()[0].elapsed().is_some());
+        assert!(tracker.steps()[3].elapsed().is_none());
+    }
+}
@@ -0,0 +1,79 @@
+//! Shell completion for the command line.
+//!
+//! The script from `lazycloud completions <shell>` calls back into lazycloud
+//! with `COMPLETE=<shell>` set, so values such as context names are completed
+//! from what exists when Tab is pressed rather than when the script was made.
+
+use std::ffi::OsStr;
+use std::io;
+
+use clap::CommandFactory;
+use clap_complete::engine::CompletionCandidate;
+use clap_complete::env::{CompleteEnv, Shells};
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+
+use crate::cli::Args;
+use crate::config::profile::available_profiles;
+use crate::registry::ServiceRegistry;
+use crate::{context, provider};
+
+const BIN: &str = "lazycloud";
+const VAR: &str = "COMPLETE";
+const SHELLS: Shells<'static> = Shells::builtins();
+
+/// Answer a completion request from the shell and exit, if this is one.
+pub fn handle_request() {
+    CompleteEnv::with_factory(Args::command).var(VAR).complete();
+}
+
+/// Print the script registering completions for `shell`.
+pub fn print_registration(shell: &str) -> Result<()> {
+    let completer = SHELLS
+        .completer(shell)
+        .ok_or_else(|| eyre!("Unsupported shell: {shell}"))?;
+    completer.write_registration(VAR, BIN, BIN, BIN, &mut io::stdout())?;
+    Ok(())
+}
+
+/// Names of the shells completions can be printed for.
+pub fn shells() -> Vec<&'static str> {
+    SHELLS.names().collect()
+}
+
+/// Names of the saved contexts.
+pub fn complete_contexts(current: &OsStr) -> Vec<CompletionCandidate> {
+    let names = context::load_contexts()
+        .iter()
+        .map(|context| context.name().to_string())
+        .collect();
+    candidates(names, current)
+}
+
+/// Keys of the services any provider offers.
+pub fn complete_services(current: &OsStr) -> Vec<CompletionCandidate> {
+    let mut registry = ServiceRegistry::new();
+    provider::register_all(&mut registry, false);
+    let mut keys: Vec<String> = registry
+        .all_providers()
+        .iter()
+        .map(|provider| provider.service_key().to_string())
+        .collect();
+    keys.sort();
+    keys.dedup();
+    candidates(keys, current)
+}
+
+/// Names of the config profiles.
+pub fn complete_profiles(current: &OsStr) -> Vec<CompletionCandidate> {
+    candidates(available_profiles(), current)
+}
+
+fn candidates(values: Vec<String>, current: &OsStr) -> Vec<CompletionCandidate> {
+    let current = current.to_string_lossy();
+    values
+        .into_iter()
+        .filter(|value| value.starts_with(current.as_ref()))
+        .map(CompletionCandidate::new)
+        .collect()
+}
@@ -1,14 +1,43 @@
 pub mod actions;
+pub mod autosave;
+pub mod conflicts;
+pub mod custom_actions;
+pub mod file;
 pub mod key;
 pub mod keybindings;
 pub mod loader;
+pub mod paths;
+pub mod profile;
 pub mod resolver;
+pub mod state;
+
+use std::path::PathBuf;
 
 pub use actions::*;
+pub use autosave::AutoSave;
+pub use custom_actions::{ActionContext, ActionMode, CustomAction};
 use keybindings::KeybindingsConfig;
-pub use loader::{config_dir, load, save_last_context, save_theme};
+pub use paths::config_dir;
+pub use profile::{available_profiles, load_with_profile};
 pub use resolver::KeyResolver;
 use serde::{Deserialize, Serialize};
+pub use state::{
+    CommandRecord,
+    Workspace,
+    load_state,
+    save_bookmarks,
+    save_command_history,
+    save_last_location,
+    save_last_version,
+    save_profile,
+    save_workspaces,
+};
+
+use crate::clipboard::ClipboardBackend;
+use crate::cost::CostConfig;
+use crate::encryption::EncryptionConfig;
+use crate::i18n::Locale;
+use crate::time::DisplayTimezone;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThemeConfig {
@@ -23,6 +52,229 @@ impl Default for ThemeConfig {
     }
 }
 
+/// Naming and hygiene rules checked by the secret linter.
+///
+/// Every rule is optional; an empty section disables linting.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct LintConfig {
+    /// Regex every secret name must match (e.g. `^[a-z][a-z0-9-]*$`).
+    #[serde(default)]
+    pub name_pattern: Option<String>,
+    /// Label keys every secret must carry.
+    #[serde(default)]
+    pub required_labels: Vec<String>,
+    /// Maximum size of the latest payload, in bytes.
+    #[serde(default)]
+    pub max_payload_bytes: Option<usize>,
+}
+
+/// Thresholds of the report on expiring and stale secrets, in days.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReportConfig {
+    /// Report secrets expiring within this many days.
+    pub expiring_within: u32,
+    /// Report secrets whose newest version is older than this many days.
+    pub not_rotated_for: u32,
+    /// Report secrets as unused when the project had no secret accesses for
+    /// this many days. Cloud Monitoring only counts accesses per project.
+    pub not_accessed_for: u32,
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        Self {
+            expiring_within: 30,
+            not_rotated_for: 90,
+            not_accessed_for: 30,
+        }
+    }
+}
+
+/// Staging area for deleted secrets.
+///
+/// Secrets still in the trash when lazycloud exits are not deleted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrashConfig {
+    /// Move deleted secrets to the trash instead of deleting them right away.
+    pub enabled: bool,
+    /// Seconds before a trashed secret is deleted; 0 waits until the trash is emptied.
+    pub grace_period: u64,
+}
+
+impl Default for TrashConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            grace_period: 600,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusBarMode {
+    /// Compact on terminals shorter than `compact_below` rows.
+    #[default]
+    Auto,
+    Full,
+    /// A single line with the context and keybinding hints.
+    Compact,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusBarSection {
+    Context,
+    Keybindings,
+    Logo,
+}
+
+/// Rendering for slow connections such as SSH: fewer frames, plain borders
+/// and no animation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LowBandwidth {
+    /// Switch to it once drawing is consistently slow.
+    #[default]
+    Auto,
+    On,
+    Off,
+}
+
+/// Layout of the status bar at the top of the screen.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatusBarConfig {
+    pub mode: StatusBarMode,
+    /// Sections to show, left to right; empty hides the status bar.
+    pub sections: Vec<StatusBarSection>,
+    pub compact_below: u16,
+}
+
+impl Default for StatusBarConfig {
+    fn default() -> Self {
+        Self {
+            mode: StatusBarMode::Auto,
+            sections: vec![
+                StatusBarSection::Context,
+                StatusBarSection::Keybindings,
+                StatusBarSection::Logo,
+            ],
+            compact_below: 30,
+        }
+    }
+}
+
+/// Window title showing where lazycloud is, e.g. `lazycloud — prod — GCP > Secrets`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TerminalTitleConfig {
+    pub enabled: bool,
+}
+
+impl Default for TerminalTitleConfig {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+/// How values are presented across the interface.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplayConfig {
+    /// Timezone of displayed timestamps: `utc`, `local` or a name like `Europe/Zurich`.
+    pub timezone: DisplayTimezone,
+    /// Show the changelog on the first run after an upgrade.
+    pub whats_new: bool,
+    /// Show static indicators instead of spinners and animated progress,
+    /// e.g. over slow SSH connections.
+    pub reduced_motion: bool,
+    pub low_bandwidth: LowBandwidth,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            timezone: DisplayTimezone::default(),
+            whats_new: true,
+            reduced_motion: false,
+            low_bandwidth: LowBandwidth::default(),
+        }
+    }
+}
+
+/// Secrets kept offline in an encrypted file, shown as the `local` context.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VaultConfig {
+    pub enabled: bool,
+    /// The vault file; its extension picks the encryption as for saved
+    /// payloads. Defaults to `vault.age` in the data directory.
+    pub path: Option<PathBuf>,
+}
+
+impl VaultConfig {
+    /// The vault file, if enabled.
+    pub fn path(&self) -> Option<PathBuf> {
+        if !self.enabled {
+            return None;
+        }
+        self.path.clone().or_else(paths::vault_path)
+    }
+}
+
+/// Local diagnostics for performance work; nothing is sent anywhere.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiagnosticsConfig {
+    /// Log every service message and command at debug level.
+    pub log_messages: bool,
+    /// Collect command latencies and cache hit rates for the stats overlay.
+    pub metrics: bool,
+}
+
+impl Default for DiagnosticsConfig {
+    fn default() -> Self {
+        Self {
+            log_messages: false,
+            metrics: true,
+        }
+    }
+}
+
+/// Finished commands kept for the command history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryConfig {
+    /// Commands remembered across sessions; 0 keeps none after exit.
+    pub size: usize,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self { size: 200 }
+    }
+}
+
+/// What is kept of a session for the next one.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct SessionConfig {
+    /// Reopen the service and resource the last session ended on when
+    /// started without a context or service.
+    pub restore: bool,
+}
+
+/// Checks for new lazycloud releases, off unless enabled.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct UpdateConfig {
+    /// Ask GitHub for the latest release on startup.
+    pub check: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AppConfig {
     #[serde(default)]
@@ -30,5 +282,54 @@ pub struct AppConfig {
     #[serde(default)]
     pub keybindings: KeybindingsConfig,
     #[serde(default)]
-    pub last_context: Option<String>,
+    pub lint: LintConfig,
+    #[serde(default)]
+    pub report: ReportConfig,
+    #[serde(default)]
+    pub trash: TrashConfig,
+    #[serde(default)]
+    pub status_bar: StatusBarConfig,
+    #[serde(default)]
+    pub terminal_title: TerminalTitleConfig,
+    #[serde(default)]
+    pub display: DisplayConfig,
+    #[serde(default)]
+    pub diagnostics: DiagnosticsConfig,
+    #[serde(default)]
+    pub history: HistoryConfig,
+    #[serde(default)]
+    pub session: SessionConfig,
+    /// Estimated charges of billed operations.
+    #[serde(default)]
+    pub costs: CostConfig,
+    /// Recipients of saved secrets, encrypted when the file ends in `.age`,
+    /// `.gpg` or `.asc`.
+    #[serde(default)]
+    pub encryption: EncryptionConfig,
+    #[serde(default)]
+    pub vault: VaultConfig,
+    /// How text is copied: `auto`, `system`, `command`, `osc52` or `none`.
+    #[serde(default)]
+    pub clipboard: ClipboardBackend,
+    #[serde(default)]
+    pub updates: UpdateConfig,
+    /// Language of the interface; detected from `LANG` when unset.
+    #[serde(default)]
+    pub locale: Option<Locale>,
+    #[serde(default)]
+    pub actions: Vec<CustomAction>,
+    /// Context names to show; a trailing `*` matches any suffix. Empty shows all.
+    #[serde(default)]
+    pub contexts: Vec<String>,
+}
+
+impl AppConfig {
+    pub fn shows_context(&self, name: &str) -> bool {
+        self.contexts.is_empty()
+            || self.contexts.iter().any(|pattern| {
+                pattern
+                    .strip_suffix('*')
+                    .map_or_else(|| name == pattern, |prefix| name.starts_with(prefix))
+            })
+    }
 }
@@ -1,10 +1,38 @@
+use crate::config::key::KeyBinding;
+use crate::config::keybindings::KeybindingsConfig;
+
+/// An action bound to a key in one scope of the [`KeybindingsConfig`].
+pub trait Action: Copy + 'static {
+    /// Table of the scope in the config file, e.g. `secrets`.
+    const SCOPE: &'static str;
+    /// Every action of the scope, in the order of the config fields.
+    const ALL: &'static [Self];
+
+    /// Key of the action in its table, e.g. `view_payload`.
+    fn name(self) -> &'static str;
+
+    fn binding(self, kb: &KeybindingsConfig) -> &KeyBinding;
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GlobalAction {
     Quit,
     Help,
     Theme,
+    Profile,
+    Account,
     Back,
     CommandsToggle,
+    OutputToggle,
+    Stats,
+    SwitchPrevious,
+    ReleaseNotes,
+    History,
+    Bookmark,
+    Bookmarks,
+    Workspaces,
+    FocusNext,
+    FocusPrev,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -13,9 +41,13 @@ pub enum NavAction {
     Down,
     PageUp,
     PageDown,
+    HalfPageUp,
+    HalfPageDown,
     Home,
     End,
     Select,
+    Expand,
+    Preview,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -35,6 +67,30 @@ pub enum SecretsAction {
     Iam,
     Replication,
     Reload,
+    DeepSearch,
+    CopyToContext,
+    Lint,
+    Terraform,
+    Undo,
+    Trash,
+    EmptyTrash,
+    AddLabel,
+    EditLabel,
+    AccessStats,
+    Permissions,
+    Prefetch,
+    Export,
+    OpenConsole,
+    Annotations,
+    AnnotationsColumn,
+    Topics,
+    Accessors,
+    TestAccess,
+    FindInContexts,
+    SyncToCluster,
+    RegistryCredentials,
+    Duplicates,
+    StaleReport,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -45,12 +101,73 @@ pub enum VersionsAction {
     Enable,
     Destroy,
     Reload,
+    Terraform,
+    Save,
+    OnlyEnabled,
+    HideDestroyed,
+    Jump,
+    OpenConsole,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PayloadAction {
     Copy,
     Reload,
+    Save,
+    Compare,
+    QrCode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceAction {
+    New,
+    Delete,
+    Reload,
+    OpenConsole,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextAction {
+    Browse,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputAction {
+    Copy,
+    Clear,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryAction {
+    FailedOnly,
+    SlowOnly,
+    CycleService,
+    Retry,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookmarkAction {
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkspaceAction {
+    Save,
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewerAction {
+    Wrap,
+    LineNumbers,
+    NextMatch,
+    PrevMatch,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromoteAction {
+    Payload,
+    Labels,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -59,3 +176,542 @@ pub enum DialogAction {
     Cancel,
     Dismiss,
 }
+
+impl Action for GlobalAction {
+    const SCOPE: &'static str = "global";
+    const ALL: &'static [Self] = &[
+        Self::Quit,
+        Self::Help,
+        Self::Theme,
+        Self::Profile,
+        Self::Account,
+        Self::Back,
+        Self::CommandsToggle,
+        Self::OutputToggle,
+        Self::Stats,
+        Self::SwitchPrevious,
+        Self::ReleaseNotes,
+        Self::History,
+        Self::Bookmark,
+        Self::Bookmarks,
+        Self::Workspaces,
+        Self::FocusNext,
+        Self::FocusPrev,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Quit => "quit",
+            Self::Help => "help",
+            Self::Theme => "theme",
+            Self::Profile => "profile",
+            Self::Account => "account",
+            Self::Back => "back",
+            Self::CommandsToggle => "commands_toggle",
+            Self::OutputToggle => "output_toggle",
+            Self::Stats => "stats",
+            Self::SwitchPrevious => "switch_previous",
+            Self::ReleaseNotes => "release_notes",
+            Self::History => "history",
+            Self::Bookmark => "bookmark",
+            Self::Bookmarks => "bookmarks",
+            Self::Workspaces => "workspaces",
+            Self::FocusNext => "focus_next",
+            Self::FocusPrev => "focus_prev",
+        }
+    }
+
+    fn binding(self, kb: &KeybindingsConfig) -> &KeyBinding {
+        let kb = &kb.global;
+        match self {
+            Self::Quit => &kb.quit,
+            Self::Help => &kb.help,
+            Self::Theme => &kb.theme,
+            Self::Profile => &kb.profile,
+            Self::Account => &kb.account,
+            Self::Back => &kb.back,
+            Self::CommandsToggle => &kb.commands_toggle,
+            Self::OutputToggle => &kb.output_toggle,
+            Self::Stats => &kb.stats,
+            Self::SwitchPrevious => &kb.switch_previous,
+            Self::ReleaseNotes => &kb.release_notes,
+            Self::History => &kb.history,
+            Self::Bookmark => &kb.bookmark,
+            Self::Bookmarks => &kb.bookmarks,
+            Self::Workspaces => &kb.workspaces,
+            Self::FocusNext => &kb.focus_next,
+            Self::FocusPrev => &kb.focus_prev,
+        }
+    }
+}
+
+impl Action for NavAction {
+    const SCOPE: &'static str = "navigation";
+    const ALL: &'static [Self] = &[
+        Self::Up,
+        Self::Down,
+        Self::PageUp,
+        Self::PageDown,
+        Self::HalfPageUp,
+        Self::HalfPageDown,
+        Self::Home,
+        Self::End,
+        Self::Select,
+        Self::Expand,
+        Self::Preview,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Up => "up",
+            Self::Down => "down",
+            Self::PageUp => "page_up",
+            Self::PageDown => "page_down",
+            Self::HalfPageUp => "half_page_up",
+            Self::HalfPageDown => "half_page_down",
+            Self::Home => "home",
+            Self::End => "end",
+            Self::Select => "select",
+            Self::Expand => "expand",
+            Self::Preview => "preview",
+        }
+    }
+
+    fn binding(self, kb: &KeybindingsConfig) -> &KeyBinding {
+        let kb = &kb.navigation;
+        match self {
+            Self::Up => &kb.up,
+            Self::Down => &kb.down,
+            Self::PageUp => &kb.page_up,
+            Self::PageDown => &kb.page_down,
+            Self::HalfPageUp => &kb.half_page_up,
+            Self::HalfPageDown => &kb.half_page_down,
+            Self::Home => &kb.home,
+            Self::End => &kb.end,
+            Self::Select => &kb.select,
+            Self::Expand => &kb.expand,
+            Self::Preview => &kb.preview,
+        }
+    }
+}
+
+impl Action for SearchAction {
+    const SCOPE: &'static str = "search";
+    const ALL: &'static [Self] = &[Self::Toggle, Self::Exit];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Toggle => "toggle",
+            Self::Exit => "exit",
+        }
+    }
+
+    fn binding(self, kb: &KeybindingsConfig) -> &KeyBinding {
+        let kb = &kb.search;
+        match self {
+            Self::Toggle => &kb.toggle,
+            Self::Exit => &kb.exit,
+        }
+    }
+}
+
+impl Action for SecretsAction {
+    const SCOPE: &'static str = "secrets";
+    const ALL: &'static [Self] = &[
+        Self::ViewPayload,
+        Self::Copy,
+        Self::Versions,
+        Self::New,
+        Self::Delete,
+        Self::Labels,
+        Self::Iam,
+        Self::Replication,
+        Self::Reload,
+        Self::DeepSearch,
+        Self::CopyToContext,
+        Self::Lint,
+        Self::Terraform,
+        Self::Undo,
+        Self::Trash,
+        Self::EmptyTrash,
+        Self::AddLabel,
+        Self::EditLabel,
+        Self::AccessStats,
+        Self::Permissions,
+        Self::Prefetch,
+        Self::Export,
+        Self::OpenConsole,
+        Self::Annotations,
+        Self::AnnotationsColumn,
+        Self::Topics,
+        Self::Accessors,
+        Self::TestAccess,
+        Self::FindInContexts,
+        Self::SyncToCluster,
+        Self::RegistryCredentials,
+        Self::Duplicates,
+        Self::StaleReport,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::ViewPayload => "view_payload",
+            Self::Copy => "copy",
+            Self::Versions => "versions",
+            Self::New => "new",
+            Self::Delete => "delete",
+            Self::Labels => "labels",
+            Self::Iam => "iam",
+            Self::Replication => "replication",
+            Self::Reload => "reload",
+            Self::DeepSearch => "deep_search",
+            Self::CopyToContext => "copy_to_context",
+            Self::Lint => "lint",
+            Self::Terraform => "terraform",
+            Self::Undo => "undo",
+            Self::Trash => "trash",
+            Self::EmptyTrash => "empty_trash",
+            Self::AddLabel => "add_label",
+            Self::EditLabel => "edit_label",
+            Self::AccessStats => "access_stats",
+            Self::Permissions => "permissions",
+            Self::Prefetch => "prefetch",
+            Self::Export => "export",
+            Self::OpenConsole => "open_console",
+            Self::Annotations => "annotations",
+            Self::AnnotationsColumn => "annotations_column",
+            Self::Topics => "topics",
+            Self::Accessors => "accessors",
+            Self::TestAccess => "test_access",
+            Self::FindInContexts => "find_in_contexts",
+            Self::SyncToCluster => "sync_to_cluster",
+            Self::RegistryCredentials => "registry_credentials",
+            Self::Duplicates => "duplicates",
+            Self::StaleReport => "stale_report",
+        }
+    }
+
+    fn binding(self, kb: &KeybindingsConfig) -> &KeyBinding {
+        let kb = &kb.secrets;
+        match self {
+            Self::ViewPayload => &kb.view_payload,
+            Self::Copy => &kb.copy,
+            Self::Versions => &kb.versions,
+            Self::New => &kb.new,
+            Self::Delete => &kb.delete,
+            Self::Labels => &kb.labels,
+            Self::Iam => &kb.iam,
+            Self::Replication => &kb.replication,
+            Self::Reload => &kb.reload,
+            Self::DeepSearch => &kb.deep_search,
+            Self::CopyToContext => &kb.copy_to_context,
+            Self::Lint => &kb.lint,
+            Self::Terraform => &kb.terraform,
+            Self::Undo => &kb.undo,
+            Self::Trash => &kb.trash,
+            Self::EmptyTrash => &kb.empty_trash,
+            Self::AddLabel => &kb.add_label,
+            Self::EditLabel => &kb.edit_label,
+            Self::AccessStats => &kb.access_stats,
+            Self::Permissions => &kb.permissions,
+            Self::Prefetch => &kb.prefetch,
+            Self::Export => &kb.export,
+            Self::OpenConsole => &kb.open_console,
+            Self::Annotations => &kb.annotations,
+            Self::AnnotationsColumn => &kb.annotations_column,
+            Self::Topics => &kb.topics,
+            Self::Accessors => &kb.accessors,
+            Self::TestAccess => &kb.test_access,
+            Self::FindInContexts => &kb.find_in_contexts,
+            Self::SyncToCluster => &kb.sync_to_cluster,
+            Self::RegistryCredentials => &kb.registry_credentials,
+            Self::Duplicates => &kb.duplicates,
+            Self::StaleReport => &kb.stale_report,
+        }
+    }
+}
+
+impl Action for VersionsAction {
+    const SCOPE: &'static str = "versions";
+    const ALL: &'static [Self] = &[
+        Self::ViewPayload,
+        Self::Add,
+        Self::Disable,
+        Self::Enable,
+        Self::Destroy,
+        Self::Reload,
+        Self::Terraform,
+        Self::Save,
+        Self::OnlyEnabled,
+        Self::HideDestroyed,
+        Self::Jump,
+        Self::OpenConsole,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::ViewPayload => "view_payload",
+            Self::Add => "add",
+            Self::Disable => "disable",
+            Self::Enable => "enable",
+            Self::Destroy => "destroy",
+            Self::Reload => "reload",
+            Self::Terraform => "terraform",
+            Self::Save => "save",
+            Self::OnlyEnabled => "only_enabled",
+            Self::HideDestroyed => "hide_destroyed",
+            Self::Jump => "jump",
+            Self::OpenConsole => "open_console",
+        }
+    }
+
+    fn binding(self, kb: &KeybindingsConfig) -> &KeyBinding {
+        let kb = &kb.versions;
+        match self {
+            Self::ViewPayload => &kb.view_payload,
+            Self::Add => &kb.add,
+            Self::Disable => &kb.disable,
+            Self::Enable => &kb.enable,
+            Self::Destroy => &kb.destroy,
+            Self::Reload => &kb.reload,
+            Self::Terraform => &kb.terraform,
+            Self::Save => &kb.save,
+            Self::OnlyEnabled => &kb.only_enabled,
+            Self::HideDestroyed => &kb.hide_destroyed,
+            Self::Jump => &kb.jump,
+            Self::OpenConsole => &kb.open_console,
+        }
+    }
+}
+
+impl Action for PayloadAction {
+    const SCOPE: &'static str = "payload";
+    const ALL: &'static [Self] = &[
+        Self::Copy,
+        Self::Reload,
+        Self::Save,
+        Self::Compare,
+        Self::QrCode,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Copy => "copy",
+            Self::Reload => "reload",
+            Self::Save => "save",
+            Self::Compare => "compare",
+            Self::QrCode => "qr_code",
+        }
+    }
+
+    fn binding(self, kb: &KeybindingsConfig) -> &KeyBinding {
+        let kb = &kb.payload;
+        match self {
+            Self::Copy => &kb.copy,
+            Self::Reload => &kb.reload,
+            Self::Save => &kb.save,
+            Self::Compare => &kb.compare,
+            Self::QrCode => &kb.qr_code,
+        }
+    }
+}
+
+impl Action for ResourceAction {
+    const SCOPE: &'static str = "resources";
+    const ALL: &'static [Self] = &[Self::New, Self::Delete, Self::Reload, Self::OpenConsole];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::New => "new",
+            Self::Delete => "delete",
+            Self::Reload => "reload",
+            Self::OpenConsole => "open_console",
+        }
+    }
+
+    fn binding(self, kb: &KeybindingsConfig) -> &KeyBinding {
+        let kb = &kb.resources;
+        match self {
+            Self::New => &kb.new,
+            Self::Delete => &kb.delete,
+            Self::Reload => &kb.reload,
+            Self::OpenConsole => &kb.open_console,
+        }
+    }
+}
+
+impl Action for ContextAction {
+    const SCOPE: &'static str = "contexts";
+    const ALL: &'static [Self] = &[Self::Browse];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Browse => "browse",
+        }
+    }
+
+    fn binding(self, kb: &KeybindingsConfig) -> &KeyBinding {
+        let kb = &kb.contexts;
+        match self {
+            Self::Browse => &kb.browse,
+        }
+    }
+}
+
+impl Action for OutputAction {
+    const SCOPE: &'static str = "output";
+    const ALL: &'static [Self] = &[Self::Copy, Self::Clear];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Copy => "copy",
+            Self::Clear => "clear",
+        }
+    }
+
+    fn binding(self, kb: &KeybindingsConfig) -> &KeyBinding {
+        let kb = &kb.output;
+        match self {
+            Self::Copy => &kb.copy,
+            Self::Clear => &kb.clear,
+        }
+    }
+}
+
+impl Action for HistoryAction {
+    const SCOPE: &'static str = "history";
+    const ALL: &'static [Self] = &[
+        Self::FailedOnly,
+        Self::SlowOnly,
+        Self::CycleService,
+        Self::Retry,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::FailedOnly => "failed_only",
+            Self::SlowOnly => "slow_only",
+            Self::CycleService => "cycle_service",
+            Self::Retry => "retry",
+        }
+    }
+
+    fn binding(self, kb: &KeybindingsConfig) -> &KeyBinding {
+        let kb = &kb.history;
+        match self {
+            Self::FailedOnly => &kb.failed_only,
+            Self::SlowOnly => &kb.slow_only,
+            Self::CycleService => &kb.cycle_service,
+            Self::Retry => &kb.retry,
+        }
+    }
+}
+
+impl Action for BookmarkAction {
+    const SCOPE: &'static str = "bookmarks";
+    const ALL: &'static [Self] = &[Self::Delete];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Delete => "delete",
+        }
+    }
+
+    fn binding(self, kb: &KeybindingsConfig) -> &KeyBinding {
+        let kb = &kb.bookmarks;
+        match self {
+            Self::Delete => &kb.delete,
+        }
+    }
+}
+
+impl Action for WorkspaceAction {
+    const SCOPE: &'static str = "workspaces";
+    const ALL: &'static [Self] = &[Self::Save, Self::Delete];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Save => "save",
+            Self::Delete => "delete",
+        }
+    }
+
+    fn binding(self, kb: &KeybindingsConfig) -> &KeyBinding {
+        let kb = &kb.workspaces;
+        match self {
+            Self::Save => &kb.save,
+            Self::Delete => &kb.delete,
+        }
+    }
+}
+
+impl Action for ViewerAction {
+    const SCOPE: &'static str = "viewer";
+    const ALL: &'static [Self] = &[
+        Self::Wrap,
+        Self::LineNumbers,
+        Self::NextMatch,
+        Self::PrevMatch,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Wrap => "wrap",
+            Self::LineNumbers => "line_numbers",
+            Self::NextMatch => "next_match",
+            Self::PrevMatch => "prev_match",
+        }
+    }
+
+    fn binding(self, kb: &KeybindingsConfig) -> &KeyBinding {
+        let kb = &kb.viewer;
+        match self {
+            Self::Wrap => &kb.wrap,
+            Self::LineNumbers => &kb.line_numbers,
+            Self::NextMatch => &kb.next_match,
+            Self::PrevMatch => &kb.prev_match,
+        }
+    }
+}
+
+impl Action for PromoteAction {
+    const SCOPE: &'static str = "promote";
+    const ALL: &'static [Self] = &[Self::Payload, Self::Labels];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Payload => "payload",
+            Self::Labels => "labels",
+        }
+    }
+
+    fn binding(self, kb: &KeybindingsConfig) -> &KeyBinding {
+        let kb = &kb.promote;
+        match self {
+            Self::Payload => &kb.payload,
+            Self::Labels => &kb.labels,
+        }
+    }
+}
+
+impl Action for DialogAction {
+    const SCOPE: &'static str = "dialog";
+    const ALL: &'static [Self] = &[Self::Confirm, Self::Cancel, Self::Dismiss];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Confirm => "confirm",
+            Self::Cancel => "cancel",
+            Self::Dismiss => "dismiss",
+        }
+    }
+
+    fn binding(self, kb: &KeybindingsConfig) -> &KeyBinding {
+        let kb = &kb.dialog;
+        match self {
+            Self::Confirm => &kb.confirm,
+            Self::Cancel => &kb.cancel,
+            Self::Dismiss => &kb.dismiss,
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! Saving what changes often, such as the last context while switching
+//! through services, once it has settled instead of on every change.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use color_eyre::Result;
+
+use crate::config::loader::save_theme;
+use crate::config::state::save_last_used;
+
+/// How long nothing may change before pending changes are written.
+const SAVE_DELAY: Duration = Duration::from_secs(2);
+
+/// Changes waiting to be written, see [`AutoSave::tick`].
+#[derive(Debug, Default)]
+pub struct AutoSave {
+    last_context: Option<String>,
+    /// Service last opened, by context name.
+    last_services: BTreeMap<String, String>,
+    /// Theme name, and the profile it was picked in.
+    theme: Option<(String, Option<String>)>,
+    /// When the pending changes are written, `None` without any.
+    due: Option<Instant>,
+}
+
+impl AutoSave {
+    pub fn set_last_context(&mut self, context_name: &str) {
+        self.last_context = Some(context_name.to_string());
+        self.changed(Instant::now());
+    }
+
+    pub fn set_last_service(&mut self, context_name: &str, service: &str) {
+        self.last_services
+            .insert(context_name.to_string(), service.to_string());
+        self.changed(Instant::now());
+    }
+
+    pub fn set_theme(&mut self, theme_name: &str, profile: Option<&str>) {
+        self.theme = Some((theme_name.to_string(), profile.map(str::to_string)));
+        self.changed(Instant::now());
+    }
+
+    fn changed(&mut self, now: Instant) {
+        self.due = Some(now + SAVE_DELAY);
+    }
+
+    fn is_due(&self, now: Instant) -> bool {
+        self.due.is_some_and(|due| now >= due)
+    }
+
+    /// Write the pending changes if nothing changed for a while.
+    pub fn tick(&mut self) -> Result<()> {
+        if self.is_due(Instant::now()) {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Write the pending changes now, e.g. before exiting.
+    ///
+    /// # Errors
+    /// Returns the first failed write; the others are still attempted.
+    pub fn flush(&mut self) -> Result<()> {
+        if self.due.take().is_none() {
+            return Ok(());
+        }
+        let state = if self.last_context.is_some() || !self.last_services.is_empty() {
+            save_last_used(
+                self.last_context.take().as_deref(),
+                &std::mem::take(&mut self.last_services),
+            )
+        } else {
+            Ok(())
+        };
+        let theme = match self.theme.take() {
+            Some((name, profile)) => save_theme(&name, profile.as_deref()),
+            None => Ok(()),
+        };
+        state.and(theme)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saves_once_settled() {
+        let mut autosave = AutoSave::default();
+        let start = Instant::now();
+        assert!(!autosave.is_due(start + SAVE_DELAY));
+
+        autosave.changed(start);
+        assert!(!autosave.is_due(start + SAVE_DELAY / 2));
+
+        // Another change pushes the save back
+        autosave.changed(start + SAVE_DELAY / 2);
+        assert!(!autosave.is_due(start + SAVE_DELAY));
+        assert!(autosave.is_due(start + SAVE_DELAY * 2));
+    }
+}
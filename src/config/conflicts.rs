@@ -0,0 +1,284 @@
+//! Keys bound to more than one action where both could fire.
+//!
+//! Each scope lists the actions a key can reach on one screen, in the order
+//! they get to handle it: the table first, then the screen, the service and
+//! finally the global keys. When two of them share a key, the first one wins
+//! and the other can never be triggered there. Service actions are checked
+//! like a table screen of their own. Dialogs and the search input only see
+//! their own keys. The scopes take their actions from the action enums, so
+//! a new action is checked without being listed here.
+
+use std::fmt;
+
+use crate::config::actions::{
+    BookmarkAction,
+    ContextAction,
+    DialogAction,
+    GlobalAction,
+    HistoryAction,
+    NavAction,
+    OutputAction,
+    PayloadAction,
+    PromoteAction,
+    ResourceAction,
+    SearchAction,
+    SecretsAction,
+    VersionsAction,
+    ViewerAction,
+    WorkspaceAction,
+};
+use crate::config::key::{Chord, KeyBinding};
+use crate::config::keybindings::{KeybindingsConfig, NamedBindings};
+use crate::i18n::t_args;
+
+/// A key in `scope` that triggers `winner` and shadows the other actions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyConflict {
+    pub scope: String,
+    pub key: String,
+    pub winner: String,
+    pub shadowed: Vec<String>,
+}
+
+impl fmt::Display for KeyConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = t_args(
+            "{scope}: {key} runs {winner}, not {shadowed}",
+            &[
+                ("scope", &self.scope),
+                ("key", &self.key),
+                ("winner", &self.winner),
+                ("shadowed", &self.shadowed.join(", ")),
+            ],
+        );
+        f.write_str(&text)
+    }
+}
+
+/// Actions that only name the key of another action in hints, e.g. the
+/// secret list opens a payload when a row is selected. They share its key
+/// on purpose.
+const ALIASES: &[(&str, &str)] = &[
+    ("secrets.view_payload", "navigation.select"),
+    ("versions.view_payload", "navigation.select"),
+];
+
+/// Every conflict in `kb`, by scope and then by key.
+pub fn find_conflicts(kb: &KeybindingsConfig) -> Vec<KeyConflict> {
+    let table = table_actions(kb);
+    let global = kb.scope::<GlobalAction>();
+    let confirm: NamedBindings = kb
+        .scope::<DialogAction>()
+        .into_iter()
+        .filter(|(name, _)| name != "dialog.dismiss")
+        .collect();
+
+    let scopes = [
+        Scope::table("secrets", kb.scope::<SecretsAction>()),
+        Scope::table("versions", kb.scope::<VersionsAction>()),
+        Scope::table(
+            "payload",
+            [kb.scope::<PayloadAction>(), kb.scope::<ViewerAction>()].concat(),
+        ),
+        Scope::table("resources", kb.scope::<ResourceAction>()),
+        Scope::table("contexts", kb.scope::<ContextAction>()),
+        Scope::table("history", kb.scope::<HistoryAction>()),
+        Scope::table("bookmarks", kb.scope::<BookmarkAction>()),
+        Scope::table("workspaces", kb.scope::<WorkspaceAction>()),
+        // Typing a query leaves only the keys that end it
+        Scope::modal(
+            "search",
+            [
+                kb.scope::<SearchAction>(),
+                named("navigation.select", &kb.navigation.select),
+            ]
+            .concat(),
+        ),
+        // The output pane scrolls itself before the view beside it gets keys
+        Scope {
+            name: "output",
+            actions: [kb.scope::<OutputAction>(), kb.scope::<NavAction>()].concat(),
+            table: false,
+            global: true,
+        },
+        Scope::modal(
+            "promote",
+            [kb.scope::<PromoteAction>(), confirm.clone()].concat(),
+        ),
+        // Dialogs that only dismiss have no other key to conflict with
+        Scope::modal("dialog", confirm),
+    ];
+    let mut conflicts: Vec<KeyConflict> = scopes
+        .into_iter()
+        .flat_map(|scope| {
+            let table = if scope.table { table.as_slice() } else { &[] };
+            let global = if scope.global { global.as_slice() } else { &[] };
+            let actions: NamedBindings = table
+                .iter()
+                .chain(&scope.actions)
+                .chain(global)
+                .cloned()
+                .collect();
+            scope_conflicts(scope.name, &actions)
+        })
+        .collect();
+
+    for (scope, bindings) in &kb.services {
+        let actions: NamedBindings = table
+            .iter()
+            .cloned()
+            .chain(
+                bindings
+                    .iter()
+                    .map(|(name, binding)| (format!("{scope}.{name}"), binding)),
+            )
+            .chain(global.iter().cloned())
+            .collect();
+        conflicts.extend(scope_conflicts(scope, &actions));
+    }
+    conflicts
+}
+
+/// The actions a key can reach on one kind of screen.
+struct Scope<'a> {
+    name: &'static str,
+    actions: NamedBindings<'a>,
+    /// Whether the screen scrolls and searches like a table, which gets keys
+    /// first.
+    table: bool,
+    /// Whether unhandled keys reach the global actions, unlike in dialogs.
+    global: bool,
+}
+
+impl<'a> Scope<'a> {
+    const fn table(name: &'static str, actions: NamedBindings<'a>) -> Self {
+        Self {
+            name,
+            actions,
+            table: true,
+            global: true,
+        }
+    }
+
+    const fn modal(name: &'static str, actions: NamedBindings<'a>) -> Self {
+        Self {
+            name,
+            actions,
+            table: false,
+            global: false,
+        }
+    }
+}
+
+fn named<'a>(name: &str, binding: &'a KeyBinding) -> NamedBindings<'a> {
+    vec![(name.to_string(), binding)]
+}
+
+/// Keys the table handles before the screen sees them.
+///
+/// The search exit key is left out, it only applies while a query is set
+/// and otherwise falls through to the screen. While typing a query it is
+/// checked in the `search` scope.
+fn table_actions(kb: &KeybindingsConfig) -> NamedBindings<'_> {
+    [
+        kb.scope::<NavAction>(),
+        named("search.toggle", &kb.search.toggle),
+    ]
+    .concat()
+}
+
+/// Conflicts among `actions`, listed in the order they handle keys.
+fn scope_conflicts(scope: &str, actions: &[(String, &KeyBinding)]) -> Vec<KeyConflict> {
+    let mut conflicts: Vec<(&Chord, KeyConflict)> = Vec::new();
+    for (action, binding) in actions {
+        for chord in binding.chords() {
+            match conflicts.iter_mut().find(|(seen, _)| *seen == chord) {
+                Some((_, conflict)) if conflict.winner == *action => {}
+                Some((_, conflict)) if is_alias(action, &conflict.winner) => {}
+                Some((_, conflict)) => conflict.shadowed.push(action.clone()),
+                None => conflicts.push((
+                    chord,
+                    KeyConflict {
+                        scope: scope.to_string(),
+                        key: chord.display(),
+                        winner: action.clone(),
+                        shadowed: Vec::new(),
+                    },
+                )),
+            }
+        }
+    }
+    conflicts
+        .into_iter()
+        .map(|(_, conflict)| conflict)
+        .filter(|conflict| !conflict.shadowed.is_empty())
+        .collect()
+}
+
+fn is_alias(a: &str, b: &str) -> bool {
+    ALIASES.contains(&(a, b)) || ALIASES.contains(&(b, a))
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use super::*;
+    use crate::config::key::Key;
+
+    #[test]
+    fn test_defaults_have_no_conflicts() {
+        assert_eq!(find_conflicts(&KeybindingsConfig::default()), []);
+    }
+
+    #[test]
+    fn test_first_action_wins() {
+        let mut kb = KeybindingsConfig::default();
+        kb.versions.disable = Key::new(KeyCode::Char('D')).into();
+        kb.global.quit = Key::new(KeyCode::Char('D')).into();
+
+        let conflicts = find_conflicts(&kb);
+        let scopes: Vec<&str> = conflicts.iter().map(|c| c.scope.as_str()).collect();
+        assert_eq!(scopes, ["versions", "payload"]);
+        assert_eq!(conflicts[0].winner, "versions.disable");
+        assert_eq!(conflicts[0].shadowed, ["versions.destroy", "global.quit"]);
+        assert_eq!(conflicts[1].winner, "payload.compare");
+    }
+
+    #[test]
+    fn test_service_actions_conflict_with_global() {
+        let mut kb = KeybindingsConfig::default();
+        kb.services.entry("aws.s3".to_string()).or_default().insert(
+            "open_console".to_string(),
+            Key::new(KeyCode::Char('q')).into(),
+        );
+
+        let conflicts = find_conflicts(&kb);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].scope, "aws.s3");
+        assert_eq!(conflicts[0].winner, "aws.s3.open_console");
+        assert_eq!(conflicts[0].shadowed, ["global.quit"]);
+    }
+
+    #[test]
+    fn test_every_action_of_a_scope_is_checked() {
+        let mut kb = KeybindingsConfig::default();
+        kb.secrets.empty_trash = Key::new(KeyCode::Char('q')).into();
+        kb.promote.payload = Key::new(KeyCode::Char('y')).into();
+        // Shares the select key on purpose
+        kb.secrets.view_payload = Key::new(KeyCode::Enter).into();
+
+        let conflicts = find_conflicts(&kb);
+        let found: Vec<(&str, &str)> = conflicts
+            .iter()
+            .map(|c| (c.winner.as_str(), c.shadowed[0].as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("secrets.empty_trash", "global.quit"),
+                ("promote.payload", "dialog.confirm"),
+            ]
+        );
+    }
+}
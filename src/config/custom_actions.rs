@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use crossterm::event::KeyEvent;
+use serde::{Deserialize, Serialize};
+
+use crate::config::key::KeyBinding;
+
+/// How a custom action's command is run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActionMode {
+    /// Suspend the TUI and hand the terminal to the command.
+    #[default]
+    Foreground,
+    /// Run without leaving the TUI and show the captured output.
+    Background,
+}
+
+/// A user-defined shell command bound to a key.
+///
+/// ```toml
+/// [[actions]]
+/// name = "Describe"
+/// key = "ctrl+d"
+/// scope = "secret-manager/secrets"
+/// command = "gcloud secrets describe {{name}} --project {{project}}"
+/// mode = "background"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomAction {
+    pub name: String,
+    pub key: KeyBinding,
+    /// Where the action is available: a service (`secret-manager`), a screen
+    /// within a service (`secret-manager/versions`), or everywhere if omitted.
+    #[serde(default)]
+    pub scope: Option<String>,
+    /// Shell command with `{{placeholder}}` variables.
+    pub command: String,
+    #[serde(default)]
+    pub mode: ActionMode,
+    /// Wait for Enter before returning to the TUI (foreground only).
+    #[serde(default)]
+    pub wait: bool,
+}
+
+impl CustomAction {
+    /// Whether `key` triggers this action, after `prefix` if a key sequence
+    /// is in progress.
+    pub fn matches(&self, prefix: Option<&KeyEvent>, key: &KeyEvent, ctx: &ActionContext) -> bool {
+        self.key.matches_after(prefix, key) && self.in_scope(ctx)
+    }
+
+    pub fn in_scope(&self, ctx: &ActionContext) -> bool {
+        let Some(scope) = &self.scope else {
+            return true;
+        };
+        let (service, screen) = match scope.split_once('/') {
+            Some((service, screen)) => (service, Some(screen)),
+            None => (scope.as_str(), None),
+        };
+        ctx.service.as_deref() == Some(service)
+            && screen.is_none_or(|screen| ctx.screen == Some(screen))
+    }
+
+    /// Substitute placeholders with shell-quoted values from the context.
+    ///
+    /// # Errors
+    /// Returns an error if the template uses a placeholder the current view
+    /// does not provide (e.g. `{{name}}` with nothing selected).
+    pub fn render_command(&self, ctx: &ActionContext) -> Result<String> {
+        let mut out = String::with_capacity(self.command.len());
+        let mut rest = self.command.as_str();
+
+        while let Some(start) = rest.find("{{") {
+            out.push_str(&rest[..start]);
+            let after = &rest[start + 2..];
+            let end = after
+                .find("}}")
+                .ok_or_else(|| eyre!("Unclosed placeholder in action '{}'", self.name))?;
+            let var = after[..end].trim();
+            let value = ctx.vars.get(var).ok_or_else(|| {
+                eyre!(
+                    "Action '{}' needs {{{{{var}}}}}, which is not available here",
+                    self.name
+                )
+            })?;
+            out.push_str(&shell_quote(value));
+            rest = &after[end + 2..];
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+}
+
+/// What the current view exposes to custom actions.
+#[derive(Debug, Clone, Default)]
+pub struct ActionContext {
+    /// Service key, e.g. `secret-manager`.
+    pub service: Option<String>,
+    /// Screen identifier within the service, e.g. `versions`.
+    pub screen: Option<&'static str>,
+    /// Placeholder values, e.g. `name` for the selected resource.
+    pub vars: HashMap<&'static str, String>,
+}
+
+impl ActionContext {
+    pub fn screen(screen: &'static str) -> Self {
+        Self {
+            screen: Some(screen),
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn with_var(mut self, key: &'static str, value: impl Into<String>) -> Self {
+        self.vars.insert(key, value.into());
+        self
+    }
+}
+
+/// Quote a value for POSIX shells unless it only contains safe characters.
+pub fn shell_quote(value: &str) -> String {
+    let safe = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:@=,+".contains(c));
+    if safe {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    use super::*;
+    use crate::config::key::Key;
+
+    fn action(scope: Option<&str>, command: &str) -> CustomAction {
+        CustomAction {
+            name: "test".to_string(),
+            key: Key::new(KeyCode::Char('x')).into(),
+            scope: scope.map(str::to_string),
+            command: command.to_string(),
+            mode: ActionMode::Background,
+            wait: false,
+        }
+    }
+
+    fn secrets_ctx() -> ActionContext {
+        let mut ctx = ActionContext::screen("secrets").with_var("name", "db-password");
+        ctx.service = Some("secret-manager".to_string());
+        ctx.with_var("project", "my project")
+    }
+
+    #[test]
+    fn test_scope_matching() {
+        let key = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE);
+        let ctx = secrets_ctx();
+        assert!(action(None, "").matches(None, &key, &ctx));
+        assert!(action(Some("secret-manager"), "").matches(None, &key, &ctx));
+        assert!(action(Some("secret-manager/secrets"), "").matches(None, &key, &ctx));
+        assert!(!action(Some("secret-manager/versions"), "").matches(None, &key, &ctx));
+        assert!(!action(Some("storage"), "").matches(None, &key, &ctx));
+        assert!(!action(Some("secret-manager"), "").matches(None, &key, &ActionContext::default()));
+    }
+
+    #[test]
+    fn test_render_command_quotes_values() {
+        let cmd = action(
+            None,
+            "gcloud secrets describe {{name}} --project {{ project }}",
+        )
+        .render_command(&secrets_ctx())
+        .unwrap();
+        assert_eq!(
+            cmd,
+            "gcloud secrets describe db-password --project 'my project'"
+        );
+    }
+
+    #[test]
+    fn test_render_command_missing_placeholder() {
+        let ctx = ActionContext::default();
+        assert!(action(None, "echo {{name}}").render_command(&ctx).is_err());
+        assert!(action(None, "echo {{name").render_command(&ctx).is_err());
+        assert_eq!(
+            action(None, "echo hi").render_command(&ctx).unwrap(),
+            "echo hi"
+        );
+    }
+
+    #[test]
+    fn test_shell_quote_escapes_single_quotes() {
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert_eq!(shell_quote(""), "''");
+    }
+}
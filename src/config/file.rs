@@ -0,0 +1,114 @@
+//! Writing the files lazycloud keeps, without leaving them half-written or
+//! undoing edits made while it runs.
+//!
+//! Files are written to a temporary file next to them that is then renamed
+//! over the old one, so a crash or full disk never leaves a truncated
+//! `config.toml` behind. Read-modify-write goes through [`update`], which
+//! reads the file right before writing and starts over if it was changed by
+//! someone else in between.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use color_eyre::Result;
+use color_eyre::eyre::bail;
+use tracing::debug;
+
+/// Times [`update`] retries an edit that raced with another writer.
+const UPDATE_ATTEMPTS: usize = 3;
+
+/// Write `content` to `path` atomically, creating its directory if needed.
+pub fn write_atomic(path: &Path, content: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let temp = temp_path(path);
+    let written = (|| -> io::Result<()> {
+        let mut file = fs::File::create(&temp)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        // Keep permissions of the file replaced, e.g. a private config
+        if let Ok(metadata) = fs::metadata(path) {
+            fs::set_permissions(&temp, metadata.permissions())?;
+        }
+        fs::rename(&temp, path)
+    })();
+    if let Err(err) = written {
+        let _ = fs::remove_file(&temp);
+        return Err(err.into());
+    }
+    Ok(())
+}
+
+/// Rewrite the file at `path` with what `edit` makes of its content, `None`
+/// if it does not exist yet.
+///
+/// The file is read right before writing, so changes made outside lazycloud
+/// since it was loaded are kept. If it changes again while `edit` runs, the
+/// edit is redone on the new content. An error from `edit` leaves the file
+/// untouched.
+pub fn update(path: &Path, mut edit: impl FnMut(Option<&str>) -> Result<String>) -> Result<()> {
+    for _ in 0..UPDATE_ATTEMPTS {
+        let before = modified(path);
+        let content = match fs::read_to_string(path) {
+            Ok(content) => Some(content),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err.into()),
+        };
+        let new_content = edit(content.as_deref())?;
+        if modified(path) != before {
+            debug!(path = %path.display(), "Changed while saving, reading it again");
+            continue;
+        }
+        if content.as_deref() == Some(new_content.as_str()) {
+            return Ok(());
+        }
+        return write_atomic(path, &new_content);
+    }
+    bail!("{} keeps changing, not saving over it", path.display())
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// `.config.toml.tmp` next to `config.toml`, on the same file system so the
+/// rename is atomic.
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map_or_else(|| "lazycloud".into(), |name| name.to_string_lossy());
+    path.with_file_name(format!(".{name}.tmp"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_keeps_outside_edits() {
+        let dir = std::env::temp_dir().join(format!("lazycloud-file-{}", std::process::id()));
+        let path = dir.join("state.toml");
+
+        update(&path, |content| {
+            assert_eq!(content, None);
+            Ok("a = 1\n".to_string())
+        })
+        .unwrap();
+        assert!(!temp_path(&path).exists());
+
+        // Edited by hand in the meantime
+        fs::write(&path, "a = 1\nb = 2\n").unwrap();
+        update(&path, |content| Ok(format!("{}c = 3\n", content.unwrap()))).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a = 1\nb = 2\nc = 3\n");
+
+        assert!(update(&path, |_| bail!("invalid")).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a = 1\nb = 2\nc = 3\n");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -32,6 +32,18 @@ impl Key {
         }
     }
 
+    /// The key pressed in `event`; shift is implied by the case of characters.
+    pub fn from_event(event: &KeyEvent) -> Self {
+        let modifiers = match event.code {
+            KeyCode::Char(_) => event.modifiers - KeyModifiers::SHIFT,
+            _ => event.modifiers,
+        };
+        Self {
+            code: event.code,
+            modifiers,
+        }
+    }
+
     pub fn matches(&self, event: &KeyEvent) -> bool {
         // For character keys, compare case-insensitively when shift is involved
         match (self.code, event.code) {
@@ -59,20 +71,28 @@ impl Key {
                     && (expected_mods & !KeyModifiers::SHIFT)
                         == (actual_mods & !KeyModifiers::SHIFT)
             }
+            // Not every terminal reports shift along with shift+tab
+            (KeyCode::BackTab, KeyCode::BackTab) => {
+                self.modifiers | KeyModifiers::SHIFT == event.modifiers | KeyModifiers::SHIFT
+            }
             _ => self.code == event.code && self.modifiers == event.modifiers,
         }
     }
 
     pub fn display(&self) -> String {
         let mut parts = Vec::new();
+        let modifiers = match self.code {
+            KeyCode::BackTab => self.modifiers | KeyModifiers::SHIFT,
+            _ => self.modifiers,
+        };
 
-        if self.modifiers.contains(KeyModifiers::CONTROL) {
+        if modifiers.contains(KeyModifiers::CONTROL) {
             parts.push("ctrl".to_string());
         }
-        if self.modifiers.contains(KeyModifiers::ALT) {
+        if modifiers.contains(KeyModifiers::ALT) {
             parts.push("alt".to_string());
         }
-        if self.modifiers.contains(KeyModifiers::SHIFT) {
+        if modifiers.contains(KeyModifiers::SHIFT) {
             parts.push("shift".to_string());
         }
 
@@ -81,7 +101,7 @@ impl Key {
             KeyCode::Char(c) => c.to_string(),
             KeyCode::Enter => "Enter".to_string(),
             KeyCode::Esc => "Esc".to_string(),
-            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::Tab | KeyCode::BackTab => "Tab".to_string(),
             KeyCode::Backspace => "Backspace".to_string(),
             KeyCode::Delete => "Delete".to_string(),
             KeyCode::Insert => "Insert".to_string(),
@@ -128,7 +148,13 @@ impl FromStr for Key {
         let code = match key_part.to_lowercase().as_str() {
             "enter" | "return" => KeyCode::Enter,
             "esc" | "escape" => KeyCode::Esc,
+            // Terminals report shift+tab as a key of its own
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
             "tab" => KeyCode::Tab,
+            "backtab" => {
+                modifiers |= KeyModifiers::SHIFT;
+                KeyCode::BackTab
+            }
             "backspace" => KeyCode::Backspace,
             "delete" | "del" => KeyCode::Delete,
             "insert" | "ins" => KeyCode::Insert,
@@ -185,59 +211,179 @@ impl<'de> Deserialize<'de> for Key {
     }
 }
 
+/// One key, or two keys pressed in sequence.
+///
+/// Sequences are written space-separated in the config, e.g. `"g g"`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Chord {
+    first: Key,
+    second: Option<Key>,
+}
+
+impl Chord {
+    pub const fn then(first: Key, second: Key) -> Self {
+        Self {
+            first,
+            second: Some(second),
+        }
+    }
+
+    /// Whether `event` completes this chord, given the key pressed before it
+    /// if a chord is in progress.
+    pub fn matches(&self, prefix: Option<&KeyEvent>, event: &KeyEvent) -> bool {
+        match (&self.second, prefix) {
+            (None, None) => self.first.matches(event),
+            (Some(second), Some(prefix)) => self.first.matches(prefix) && second.matches(event),
+            _ => false,
+        }
+    }
+
+    pub const fn is_sequence(&self) -> bool {
+        self.second.is_some()
+    }
+
+    /// Whether `event` is the first key of a two-key sequence.
+    pub fn starts_with(&self, event: &KeyEvent) -> bool {
+        self.is_sequence() && self.first.matches(event)
+    }
+
+    pub fn display(&self) -> String {
+        let mut display = self.first.display();
+        if let Some(second) = &self.second {
+            display.push(' ');
+            display.push_str(&second.display());
+        }
+        display
+    }
+}
+
+impl From<Key> for Chord {
+    fn from(key: Key) -> Self {
+        Self {
+            first: key,
+            second: None,
+        }
+    }
+}
+
+impl FromStr for Chord {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let keys = s
+            .split_whitespace()
+            .map(Key::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        match keys.as_slice() {
+            [first] => Ok(first.clone().into()),
+            [first, second] => Ok(Self::then(first.clone(), second.clone())),
+            [] => Err("Empty key".to_string()),
+            _ => Err(format!("Key sequences are limited to two keys: {s}")),
+        }
+    }
+}
+
+impl Serialize for Chord {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.display())
+    }
+}
+
+impl<'de> Deserialize<'de> for Chord {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Self::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum KeyBinding {
-    Single(Key),
-    Multiple(Vec<Key>),
+    Single(Chord),
+    Multiple(Vec<Chord>),
 }
 
 impl KeyBinding {
     pub const fn single(key: Key) -> Self {
-        Self::Single(key)
+        Self::Single(Chord {
+            first: key,
+            second: None,
+        })
     }
 
-    pub const fn multiple(keys: Vec<Key>) -> Self {
-        Self::Multiple(keys)
+    pub fn multiple<C: Into<Chord>>(chords: impl IntoIterator<Item = C>) -> Self {
+        Self::Multiple(chords.into_iter().map(Into::into).collect())
     }
 
-    pub fn matches(&self, event: &KeyEvent) -> bool {
+    pub(super) fn chords(&self) -> &[Chord] {
         match self {
-            Self::Single(key) => key.matches(event),
-            Self::Multiple(keys) => keys.iter().any(|k| k.matches(event)),
+            Self::Single(chord) => std::slice::from_ref(chord),
+            Self::Multiple(chords) => chords,
         }
     }
 
+    /// Whether `event` triggers a single-key binding.
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        self.matches_after(None, event)
+    }
+
+    /// Whether `event` triggers this binding, completing a sequence if
+    /// `prefix` was pressed just before it.
+    pub fn matches_after(&self, prefix: Option<&KeyEvent>, event: &KeyEvent) -> bool {
+        self.chords()
+            .iter()
+            .any(|chord| chord.matches(prefix, event))
+    }
+
+    /// Whether `event` is the first key of one of this binding's sequences.
+    pub fn starts_chord(&self, event: &KeyEvent) -> bool {
+        self.chords().iter().any(|chord| chord.starts_with(event))
+    }
+
     pub fn display(&self) -> String {
-        match self {
-            Self::Single(key) => key.display(),
-            Self::Multiple(keys) => keys.iter().map(Key::display).collect::<Vec<_>>().join("/"),
-        }
+        self.chords()
+            .iter()
+            .map(Chord::display)
+            .collect::<Vec<_>>()
+            .join("/")
     }
 
     pub fn first_key(&self) -> &Key {
-        match self {
-            Self::Single(key) => key,
-            Self::Multiple(keys) => keys.first().expect("Multiple must have at least one key"),
-        }
+        &self
+            .chords()
+            .first()
+            .expect("Multiple must have at least one key")
+            .first
     }
 }
 
 impl Default for KeyBinding {
     fn default() -> Self {
-        Self::Single(Key::new(KeyCode::Null))
+        Self::single(Key::new(KeyCode::Null))
     }
 }
 
 impl From<Key> for KeyBinding {
     fn from(key: Key) -> Self {
-        Self::Single(key)
+        Self::single(key)
+    }
+}
+
+impl From<Chord> for KeyBinding {
+    fn from(chord: Chord) -> Self {
+        Self::Single(chord)
     }
 }
 
 impl From<Vec<Key>> for KeyBinding {
     fn from(keys: Vec<Key>) -> Self {
-        Self::Multiple(keys)
+        Self::multiple(keys)
     }
 }
 
@@ -271,6 +417,34 @@ mod tests {
         assert!(key.matches(&event));
     }
 
+    #[test]
+    fn test_chord() {
+        let binding = KeyBinding::multiple([
+            Chord::from_str("g g").unwrap(),
+            Chord::from(Key::new(KeyCode::Home)),
+        ]);
+        let g = KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE);
+        let home = KeyEvent::new(KeyCode::Home, KeyModifiers::NONE);
+
+        assert!(binding.starts_chord(&g));
+        assert!(!binding.matches(&g));
+        assert!(binding.matches_after(Some(&g), &g));
+        assert!(binding.matches(&home));
+        assert!(!binding.matches_after(Some(&g), &home));
+        assert_eq!(binding.display(), "g g/Home");
+        assert!(Chord::from_str("g g g").is_err());
+    }
+
+    #[test]
+    fn test_back_tab() {
+        let key = Key::from_str("shift+Tab").unwrap();
+        assert_eq!(key, Key::with_shift(KeyCode::BackTab));
+        assert_eq!(key.display(), "shift+Tab");
+        assert!(key.matches(&KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT)));
+        assert!(key.matches(&KeyEvent::new(KeyCode::BackTab, KeyModifiers::NONE)));
+        assert!(!key.matches(&KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE)));
+    }
+
     #[test]
     fn test_uppercase_key() {
         let key = Key::new(KeyCode::Char('G'));
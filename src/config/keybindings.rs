@@ -1,35 +1,87 @@
+use std::collections::BTreeMap;
+
 use crossterm::event::KeyCode;
 use serde::{Deserialize, Serialize};
 
-use crate::config::key::{Key, KeyBinding};
+use crate::config::actions::{
+    Action,
+    BookmarkAction,
+    ContextAction,
+    DialogAction,
+    GlobalAction,
+    HistoryAction,
+    NavAction,
+    OutputAction,
+    PayloadAction,
+    PromoteAction,
+    ResourceAction,
+    SearchAction,
+    SecretsAction,
+    VersionsAction,
+    ViewerAction,
+    WorkspaceAction,
+};
+use crate::config::key::{Chord, Key, KeyBinding};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct GlobalKeybindings {
     pub quit: KeyBinding,
     pub help: KeyBinding,
     pub theme: KeyBinding,
+    pub profile: KeyBinding,
+    /// Switch the gcloud account of the active context.
+    pub account: KeyBinding,
     pub back: KeyBinding,
     pub commands_toggle: KeyBinding,
+    pub output_toggle: KeyBinding,
+    pub stats: KeyBinding,
+    /// Jump back to the context and service used before the current one.
+    pub switch_previous: KeyBinding,
+    /// Show the notes of an available update, or else the changelog.
+    pub release_notes: KeyBinding,
+    /// Browse the commands run in this and earlier sessions.
+    pub history: KeyBinding,
+    /// Bookmark the service and resource on display.
+    pub bookmark: KeyBinding,
+    /// Browse the bookmarks of every context.
+    pub bookmarks: KeyBinding,
+    /// Save or restore the open services as a named workspace.
+    pub workspaces: KeyBinding,
+    /// Move focus to the next pane or field, e.g. into the output pane.
+    pub focus_next: KeyBinding,
+    pub focus_prev: KeyBinding,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct NavigationKeybindings {
     pub up: KeyBinding,
     pub down: KeyBinding,
     pub page_up: KeyBinding,
     pub page_down: KeyBinding,
+    pub half_page_up: KeyBinding,
+    pub half_page_down: KeyBinding,
     pub home: KeyBinding,
     pub end: KeyBinding,
     pub select: KeyBinding,
+    /// Show the details of the selected table row beneath it.
+    pub expand: KeyBinding,
+    /// Show the details of the selected table row in a pane beside the table.
+    pub preview: KeyBinding,
+    /// Accept vim-style count prefixes in lists, e.g. `5j` or `20G`.
+    pub counts: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SearchKeybindings {
     pub toggle: KeyBinding,
     pub exit: KeyBinding,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SecretListKeybindings {
     pub view_payload: KeyBinding,
     pub copy: KeyBinding,
@@ -40,9 +92,43 @@ pub struct SecretListKeybindings {
     pub iam: KeyBinding,
     pub replication: KeyBinding,
     pub reload: KeyBinding,
+    pub deep_search: KeyBinding,
+    pub copy_to_context: KeyBinding,
+    pub lint: KeyBinding,
+    pub terraform: KeyBinding,
+    pub undo: KeyBinding,
+    pub trash: KeyBinding,
+    pub empty_trash: KeyBinding,
+    pub add_label: KeyBinding,
+    pub edit_label: KeyBinding,
+    pub access_stats: KeyBinding,
+    pub permissions: KeyBinding,
+    pub prefetch: KeyBinding,
+    pub export: KeyBinding,
+    pub open_console: KeyBinding,
+    pub annotations: KeyBinding,
+    /// Show or hide the annotation count in the secret list.
+    pub annotations_column: KeyBinding,
+    /// Pub/Sub topics notified of changes to the selected secret.
+    pub topics: KeyBinding,
+    /// Who can access the selected secret, one row per IAM member.
+    pub accessors: KeyBinding,
+    /// Which permissions the current credentials hold on the selected secret.
+    pub test_access: KeyBinding,
+    /// Look the selected secret up in several contexts at once.
+    pub find_in_contexts: KeyBinding,
+    /// Write the selected secret into a Kubernetes Secret.
+    pub sync_to_cluster: KeyBinding,
+    /// Docker registry credentials from the selected secret.
+    pub registry_credentials: KeyBinding,
+    /// Group the listed secrets whose latest payloads are identical.
+    pub duplicates: KeyBinding,
+    /// Report the listed secrets that are expiring, not rotated or unused.
+    pub stale_report: KeyBinding,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct VersionListKeybindings {
     pub view_payload: KeyBinding,
     pub add: KeyBinding,
@@ -50,22 +136,111 @@ pub struct VersionListKeybindings {
     pub enable: KeyBinding,
     pub destroy: KeyBinding,
     pub reload: KeyBinding,
+    pub terraform: KeyBinding,
+    pub save: KeyBinding,
+    pub only_enabled: KeyBinding,
+    pub hide_destroyed: KeyBinding,
+    pub jump: KeyBinding,
+    pub open_console: KeyBinding,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PayloadKeybindings {
     pub copy: KeyBinding,
     pub reload: KeyBinding,
+    pub save: KeyBinding,
+    pub compare: KeyBinding,
+    /// Show a short payload as a QR code.
+    pub qr_code: KeyBinding,
+}
+
+/// Keys of services built on the generic resource browser.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResourceKeybindings {
+    pub new: KeyBinding,
+    pub delete: KeyBinding,
+    pub reload: KeyBinding,
+    pub open_console: KeyBinding,
+}
+
+/// Keys of the context selector.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContextKeybindings {
+    /// Pick a project from the organizations and folders it belongs to.
+    pub browse: KeyBinding,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutputKeybindings {
+    pub copy: KeyBinding,
+    pub clear: KeyBinding,
+}
+
+/// Keys of the command history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistoryKeybindings {
+    pub failed_only: KeyBinding,
+    /// Only commands that took a while.
+    pub slow_only: KeyBinding,
+    /// Step through the services that ran commands.
+    pub cycle_service: KeyBinding,
+    /// Run the selected command again.
+    pub retry: KeyBinding,
+}
+
+/// Keys of the bookmark manager.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BookmarkKeybindings {
+    pub delete: KeyBinding,
+}
+
+/// Keys of the workspace picker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkspaceKeybindings {
+    /// Save the open services under a new name.
+    pub save: KeyBinding,
+    pub delete: KeyBinding,
 }
 
+/// Keys of text views such as payloads and diffs.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewerKeybindings {
+    pub wrap: KeyBinding,
+    pub line_numbers: KeyBinding,
+    /// Jump to the next line matching the search.
+    pub next_match: KeyBinding,
+    pub prev_match: KeyBinding,
+}
+
+/// Option toggles of the wizard copying a secret to another context.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PromoteKeybindings {
+    pub payload: KeyBinding,
+    pub labels: KeyBinding,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct DialogKeybindings {
     pub confirm: KeyBinding,
     pub cancel: KeyBinding,
     pub dismiss: KeyBinding,
 }
 
+/// Bindings named by scope and action, e.g. `secrets.iam`.
+pub type NamedBindings<'a> = Vec<(String, &'a KeyBinding)>;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct KeybindingsConfig {
     pub global: GlobalKeybindings,
     pub navigation: NavigationKeybindings,
@@ -73,7 +248,66 @@ pub struct KeybindingsConfig {
     pub secrets: SecretListKeybindings,
     pub versions: VersionListKeybindings,
     pub payload: PayloadKeybindings,
+    pub resources: ResourceKeybindings,
+    pub contexts: ContextKeybindings,
+    pub output: OutputKeybindings,
+    pub history: HistoryKeybindings,
+    pub bookmarks: BookmarkKeybindings,
+    pub workspaces: WorkspaceKeybindings,
+    pub viewer: ViewerKeybindings,
+    pub promote: PromoteKeybindings,
     pub dialog: DialogKeybindings,
+    /// Keys of actions declared by services, by scope such as `aws.s3` and
+    /// action name, see [`ServiceProvider::actions`].
+    ///
+    /// [`ServiceProvider::actions`]: crate::registry::ServiceProvider::actions
+    pub services: BTreeMap<String, BTreeMap<String, KeyBinding>>,
+}
+
+impl KeybindingsConfig {
+    /// The key of a service action, if the service declared it.
+    pub fn service(&self, scope: &str, action: &str) -> Option<&KeyBinding> {
+        self.services.get(scope)?.get(action)
+    }
+
+    /// The bindings of one scope named `scope.action`, in config order.
+    pub fn scope<A: Action>(&self) -> NamedBindings<'_> {
+        A::ALL
+            .iter()
+            .map(|action| {
+                (
+                    format!("{}.{}", A::SCOPE, action.name()),
+                    action.binding(self),
+                )
+            })
+            .collect()
+    }
+
+    /// Every binding, including the keys of service actions.
+    pub fn bindings(&self) -> impl Iterator<Item = &KeyBinding> {
+        let scopes: [fn(&Self) -> NamedBindings<'_>; 15] = [
+            Self::scope::<GlobalAction>,
+            Self::scope::<NavAction>,
+            Self::scope::<SearchAction>,
+            Self::scope::<SecretsAction>,
+            Self::scope::<VersionsAction>,
+            Self::scope::<PayloadAction>,
+            Self::scope::<ResourceAction>,
+            Self::scope::<ContextAction>,
+            Self::scope::<OutputAction>,
+            Self::scope::<HistoryAction>,
+            Self::scope::<BookmarkAction>,
+            Self::scope::<WorkspaceAction>,
+            Self::scope::<ViewerAction>,
+            Self::scope::<PromoteAction>,
+            Self::scope::<DialogAction>,
+        ];
+        scopes
+            .into_iter()
+            .flat_map(|scope| scope(self))
+            .map(|(_, binding)| binding)
+            .chain(self.services.values().flat_map(BTreeMap::values))
+    }
 }
 
 impl Default for GlobalKeybindings {
@@ -82,8 +316,22 @@ impl Default for GlobalKeybindings {
             quit: Key::new(KeyCode::Char('q')).into(),
             help: Key::new(KeyCode::Char('?')).into(),
             theme: Key::new(KeyCode::Char('t')).into(),
+            profile: Key::new(KeyCode::Char('P')).into(),
+            account: Key::new(KeyCode::Char('U')).into(),
             back: Key::new(KeyCode::Esc).into(),
             commands_toggle: Key::new(KeyCode::Char('c')).into(),
+            output_toggle: Key::new(KeyCode::Char('o')).into(),
+            stats: Key::new(KeyCode::F(12)).into(),
+            // Terminals report ctrl+^ as ctrl+6
+            switch_previous: Key::with_ctrl(KeyCode::Char('6')).into(),
+            release_notes: Key::new(KeyCode::Char('N')).into(),
+            history: Key::with_ctrl(KeyCode::Char('r')).into(),
+            // As vim sets and jumps to marks
+            bookmark: Key::new(KeyCode::Char('M')).into(),
+            bookmarks: Key::new(KeyCode::Char('\'')).into(),
+            workspaces: Key::with_ctrl(KeyCode::Char('w')).into(),
+            focus_next: Key::new(KeyCode::Tab).into(),
+            focus_prev: Key::with_shift(KeyCode::BackTab).into(),
         }
     }
 }
@@ -95,9 +343,17 @@ impl Default for NavigationKeybindings {
             down: KeyBinding::multiple(vec![Key::new(KeyCode::Char('j')), Key::new(KeyCode::Down)]),
             page_up: Key::new(KeyCode::PageUp).into(),
             page_down: Key::new(KeyCode::PageDown).into(),
-            home: KeyBinding::multiple(vec![Key::new(KeyCode::Char('g')), Key::new(KeyCode::Home)]),
+            half_page_up: Key::with_ctrl(KeyCode::Char('u')).into(),
+            half_page_down: Key::with_ctrl(KeyCode::Char('d')).into(),
+            home: KeyBinding::multiple([
+                Chord::then(Key::new(KeyCode::Char('g')), Key::new(KeyCode::Char('g'))),
+                Key::new(KeyCode::Home).into(),
+            ]),
             end: KeyBinding::multiple(vec![Key::new(KeyCode::Char('G')), Key::new(KeyCode::End)]),
             select: Key::new(KeyCode::Enter).into(),
+            expand: Key::new(KeyCode::Char(' ')).into(),
+            preview: Key::with_ctrl(KeyCode::Char('p')).into(),
+            counts: true,
         }
     }
 }
@@ -123,9 +379,37 @@ impl Default for SecretListKeybindings {
                 Key::new(KeyCode::Delete),
             ]),
             labels: Key::new(KeyCode::Char('l')).into(),
-            iam: Key::new(KeyCode::Char('i')).into(),
+            // "g i" as in "go to IAM", like the other views reached by a key
+            iam: KeyBinding::multiple([
+                Key::new(KeyCode::Char('i')).into(),
+                Chord::then(Key::new(KeyCode::Char('g')), Key::new(KeyCode::Char('i'))),
+            ]),
             replication: Key::new(KeyCode::Char('R')).into(),
             reload: Key::new(KeyCode::Char('r')).into(),
+            deep_search: Key::new(KeyCode::Char('S')).into(),
+            copy_to_context: Key::new(KeyCode::Char('p')).into(),
+            lint: Key::new(KeyCode::Char('L')).into(),
+            terraform: Key::new(KeyCode::Char('T')).into(),
+            undo: Key::new(KeyCode::Char('u')).into(),
+            trash: Key::new(KeyCode::Char('X')).into(),
+            empty_trash: Key::new(KeyCode::Char('E')).into(),
+            add_label: Key::new(KeyCode::Char('a')).into(),
+            edit_label: Key::new(KeyCode::Char('e')).into(),
+            access_stats: Key::new(KeyCode::Char('A')).into(),
+            permissions: Key::new(KeyCode::Char('I')).into(),
+            prefetch: Key::new(KeyCode::Char('f')).into(),
+            export: Key::new(KeyCode::Char('x')).into(),
+            open_console: Key::new(KeyCode::Char('O')).into(),
+            annotations: Key::new(KeyCode::Char('m')).into(),
+            annotations_column: Key::new(KeyCode::Char('C')).into(),
+            topics: Key::new(KeyCode::Char('B')).into(),
+            accessors: Key::new(KeyCode::Char('w')).into(),
+            test_access: Key::new(KeyCode::Char('W')).into(),
+            find_in_contexts: Key::new(KeyCode::Char('F')).into(),
+            sync_to_cluster: Key::new(KeyCode::Char('K')).into(),
+            registry_credentials: Key::new(KeyCode::Char('J')).into(),
+            duplicates: Key::new(KeyCode::Char('Z')).into(),
+            stale_report: Key::new(KeyCode::Char('H')).into(),
         }
     }
 }
@@ -139,6 +423,12 @@ impl Default for VersionListKeybindings {
             enable: Key::new(KeyCode::Char('e')).into(),
             destroy: Key::new(KeyCode::Char('D')).into(),
             reload: Key::new(KeyCode::Char('r')).into(),
+            terraform: Key::new(KeyCode::Char('T')).into(),
+            save: Key::new(KeyCode::Char('s')).into(),
+            only_enabled: Key::new(KeyCode::Char('E')).into(),
+            hide_destroyed: Key::new(KeyCode::Char('H')).into(),
+            jump: Key::new(KeyCode::Char(':')).into(),
+            open_console: Key::new(KeyCode::Char('O')).into(),
         }
     }
 }
@@ -148,6 +438,88 @@ impl Default for PayloadKeybindings {
         Self {
             copy: Key::new(KeyCode::Char('y')).into(),
             reload: Key::new(KeyCode::Char('r')).into(),
+            save: Key::new(KeyCode::Char('s')).into(),
+            compare: Key::new(KeyCode::Char('D')).into(),
+            qr_code: Key::new(KeyCode::Char('Q')).into(),
+        }
+    }
+}
+
+impl Default for ResourceKeybindings {
+    fn default() -> Self {
+        Self {
+            new: Key::new(KeyCode::Char('n')).into(),
+            delete: KeyBinding::multiple(vec![
+                Key::new(KeyCode::Char('d')),
+                Key::new(KeyCode::Delete),
+            ]),
+            reload: Key::new(KeyCode::Char('r')).into(),
+            open_console: Key::new(KeyCode::Char('O')).into(),
+        }
+    }
+}
+
+impl Default for ContextKeybindings {
+    fn default() -> Self {
+        Self {
+            browse: Key::new(KeyCode::Char('b')).into(),
+        }
+    }
+}
+
+impl Default for OutputKeybindings {
+    fn default() -> Self {
+        Self {
+            copy: Key::new(KeyCode::Char('y')).into(),
+            clear: Key::new(KeyCode::Char('x')).into(),
+        }
+    }
+}
+
+impl Default for HistoryKeybindings {
+    fn default() -> Self {
+        Self {
+            failed_only: Key::new(KeyCode::Char('f')).into(),
+            slow_only: Key::new(KeyCode::Char('d')).into(),
+            cycle_service: Key::new(KeyCode::Char('s')).into(),
+            retry: Key::new(KeyCode::Char('r')).into(),
+        }
+    }
+}
+
+impl Default for BookmarkKeybindings {
+    fn default() -> Self {
+        Self {
+            delete: Key::new(KeyCode::Char('d')).into(),
+        }
+    }
+}
+
+impl Default for WorkspaceKeybindings {
+    fn default() -> Self {
+        Self {
+            save: Key::new(KeyCode::Char('s')).into(),
+            delete: Key::new(KeyCode::Char('d')).into(),
+        }
+    }
+}
+
+impl Default for ViewerKeybindings {
+    fn default() -> Self {
+        Self {
+            wrap: Key::new(KeyCode::Char('w')).into(),
+            line_numbers: Key::new(KeyCode::Char('#')).into(),
+            next_match: Key::new(KeyCode::Char('n')).into(),
+            prev_match: Key::new(KeyCode::Char('p')).into(),
+        }
+    }
+}
+
+impl Default for PromoteKeybindings {
+    fn default() -> Self {
+        Self {
+            payload: Key::new(KeyCode::Char('p')).into(),
+            labels: Key::new(KeyCode::Char('l')).into(),
         }
     }
 }
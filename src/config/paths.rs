@@ -0,0 +1,161 @@
+//! Where lazycloud keeps its files.
+//!
+//! Files are split by the XDG base directory spec:
+//! - config: what the user edits (`config.toml`, `contexts.json`, profiles, plugins)
+//! - state: what lazycloud remembers between runs (`state.toml`, logs)
+//! - data: what the user keeps with lazycloud (the local vault)
+//! - cache: what can be deleted at any time
+//!
+//! `XDG_*_HOME` is honored on every platform; otherwise the platform
+//! defaults from the `dirs` crate are used.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use color_eyre::Result;
+use tracing::{info, warn};
+
+use crate::config::state;
+
+const APP_DIR: &str = "lazycloud";
+pub const CONFIG_FILE: &str = "config.toml";
+pub const STATE_FILE: &str = "state.toml";
+const LOGS_DIR: &str = "logs";
+const VAULT_FILE: &str = "vault.age";
+
+fn base_dir(var: &str, fallback: Option<PathBuf>) -> Option<PathBuf> {
+    std::env::var_os(var)
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or(fallback)
+        .map(|dir| dir.join(APP_DIR))
+}
+
+pub fn config_dir() -> Option<PathBuf> {
+    base_dir("XDG_CONFIG_HOME", dirs::config_dir())
+}
+
+/// Falls back to the local data directory on platforms without a state dir.
+pub fn state_dir() -> Option<PathBuf> {
+    base_dir(
+        "XDG_STATE_HOME",
+        dirs::state_dir().or_else(dirs::data_local_dir),
+    )
+}
+
+pub fn data_dir() -> Option<PathBuf> {
+    base_dir("XDG_DATA_HOME", dirs::data_dir())
+}
+
+pub fn cache_dir() -> Option<PathBuf> {
+    base_dir("XDG_CACHE_HOME", dirs::cache_dir())
+}
+
+pub fn log_dir() -> Option<PathBuf> {
+    state_dir().map(|dir| dir.join(LOGS_DIR))
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(CONFIG_FILE))
+}
+
+pub fn state_path() -> Option<PathBuf> {
+    state_dir().map(|dir| dir.join(STATE_FILE))
+}
+
+/// Default location of the local vault, see [`VaultConfig`](crate::config::VaultConfig).
+pub fn vault_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join(VAULT_FILE))
+}
+
+/// Every resolved location, for `lazycloud paths`.
+pub fn all() -> Vec<(&'static str, Option<PathBuf>)> {
+    vec![
+        ("config", config_path()),
+        (
+            "contexts",
+            config_dir().map(|dir| dir.join("contexts.json")),
+        ),
+        ("profiles", config_dir().map(|dir| dir.join("profiles"))),
+        ("plugins", config_dir().map(|dir| dir.join("plugins"))),
+        ("state", state_path()),
+        ("logs", log_dir()),
+        ("vault", vault_path()),
+        ("cache", cache_dir()),
+    ]
+}
+
+/// Move the logs from the local data directory, where they used to live.
+///
+/// Runs before logging is set up, which creates the new log directory, so
+/// it returns what it did for the caller to log.
+pub fn migrate_logs() -> Result<Option<String>> {
+    let old_logs = dirs::data_local_dir().map(|dir| dir.join(APP_DIR).join(LOGS_DIR));
+    let (Some(old), Some(new)) = (old_logs, log_dir()) else {
+        return Ok(None);
+    };
+    Ok(move_dir(&old, &new)?
+        .then(|| format!("Moved logs from {} to {}", old.display(), new.display())))
+}
+
+/// Move the remaining files from the pre-XDG layout to their new homes.
+///
+/// A step that fails is logged and skipped, so a malformed old file does
+/// not keep lazycloud from starting.
+pub fn migrate() {
+    // With `XDG_CONFIG_HOME` set, macOS no longer uses Application Support
+    let old_config = dirs::config_dir().map(|dir| dir.join(APP_DIR));
+    if let (Some(old), Some(new)) = (old_config, config_dir()) {
+        match move_dir(&old, &new) {
+            Ok(true) => info!("Moved config from {} to {}", old.display(), new.display()),
+            Ok(false) => {}
+            Err(err) => warn!("Failed to move config from {}: {err}", old.display()),
+        }
+    }
+
+    if let Some(path) = config_path() {
+        match state::migrate_from_config(&path) {
+            Ok(Some(context)) => {
+                info!("Moved last_context '{context}' from config.toml to state.toml");
+            }
+            Ok(None) => {}
+            Err(err) => warn!(
+                "Skipped moving last_context out of {}: {err}",
+                path.display()
+            ),
+        }
+    }
+}
+
+/// Rename `from` to `to` if `from` exists and `to` does not.
+fn move_dir(from: &Path, to: &Path) -> Result<bool> {
+    if from == to || !from.is_dir() || to.exists() {
+        return Ok(false);
+    }
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(from, to)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_move_dir_keeps_existing_target() {
+        let root = std::env::temp_dir().join(format!("lazycloud-paths-{}", std::process::id()));
+        let (old, new) = (root.join("old"), root.join("new").join(APP_DIR));
+        fs::create_dir_all(&old).unwrap();
+        fs::write(old.join(CONFIG_FILE), "theme = 'dark'").unwrap();
+
+        assert!(move_dir(&old, &new).unwrap());
+        assert!(new.join(CONFIG_FILE).exists() && !old.exists());
+
+        fs::create_dir_all(&old).unwrap();
+        assert!(!move_dir(&old, &new).unwrap());
+        assert!(old.exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
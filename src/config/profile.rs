@@ -0,0 +1,170 @@
+//! Named configuration profiles.
+//!
+//! A profile is a partial `config.toml` in the `profiles` directory, e.g.
+//! `profiles/work.toml`. Its tables are merged over the base config key by
+//! key; any other value, including arrays, replaces the base value.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use tracing::debug;
+
+use crate::config::paths::{config_dir, config_path};
+use crate::config::{AppConfig, file};
+
+const PROFILES_DIR: &str = "profiles";
+
+pub fn profiles_dir() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(PROFILES_DIR))
+}
+
+/// Names of all profiles, sorted.
+pub fn available_profiles() -> Vec<String> {
+    let Some(Ok(entries)) = profiles_dir().map(fs::read_dir) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Load the base config with `profile` merged over it.
+///
+/// # Errors
+/// Returns an error if either file is invalid or the profile does not exist.
+pub fn load_with_profile(profile: Option<&str>) -> Result<AppConfig> {
+    let mut config = match config_path() {
+        Some(path) => read_table(&path)?,
+        None => toml::Table::new(),
+    };
+
+    if let Some(name) = profile {
+        merge(&mut config, read_table(&profile_path(name)?)?);
+        debug!(profile = name, "Applied config profile");
+    }
+
+    Ok(config.try_into()?)
+}
+
+/// Set the theme in a profile, leaving the rest of the file untouched.
+pub(super) fn save_theme(profile: &str, theme_name: &str) -> Result<()> {
+    set_theme(&profile_path(profile)?, theme_name)?;
+    debug!(profile, "Saved theme to profile");
+    Ok(())
+}
+
+/// Set the theme in the config file at `path`, leaving the rest as it is on
+/// disk, including edits made while lazycloud runs.
+///
+/// # Errors
+/// Returns an error, without writing, if the file is not valid TOML.
+pub(super) fn set_theme(path: &Path, theme_name: &str) -> Result<()> {
+    file::update(path, |content| {
+        let mut table = match content {
+            Some(content) => parse_table(path, content)?,
+            None => toml::Table::new(),
+        };
+        let theme = table
+            .entry("theme")
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        if let toml::Value::Table(theme) = theme {
+            theme.insert("name".to_string(), theme_name.into());
+        }
+        Ok(toml::to_string_pretty(&table)?)
+    })?;
+    debug!("Saved theme to {}", path.display());
+    Ok(())
+}
+
+/// Path of the profile `name`, which must be a plain file name so that it
+/// cannot point outside the profiles directory.
+fn profile_path(name: &str) -> Result<PathBuf> {
+    if name.is_empty() || name.contains("..") || name.contains(['/', '\\']) {
+        return Err(eyre!("Invalid profile name '{name}'"));
+    }
+    profiles_dir()
+        .map(|dir| dir.join(format!("{name}.toml")))
+        .filter(|path| path.exists())
+        .ok_or_else(|| {
+            eyre!(
+                "Profile '{name}' not found. Available: {}",
+                available_profiles().join(", ")
+            )
+        })
+}
+
+fn read_table(path: &Path) -> Result<toml::Table> {
+    if !path.exists() {
+        return Ok(toml::Table::new());
+    }
+    parse_table(path, &fs::read_to_string(path)?)
+}
+
+fn parse_table(path: &Path, content: &str) -> Result<toml::Table> {
+    toml::from_str(content).map_err(|err| eyre!("Invalid {}: {err}", path.display()))
+}
+
+fn merge(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(overlay)) => merge(base, overlay),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_overrides_nested_keys() {
+        let mut base: toml::Table = toml::from_str(
+            r#"
+            contexts = ["a", "b"]
+            [theme]
+            name = "Catppuccin Mocha"
+            [keybindings.global]
+            quit = "q"
+            help = "?"
+            "#,
+        )
+        .unwrap();
+        let overlay: toml::Table = toml::from_str(
+            r#"
+            contexts = ["work-*"]
+            [keybindings.global]
+            quit = "Q"
+            "#,
+        )
+        .unwrap();
+
+        merge(&mut base, overlay);
+
+        let config: AppConfig = base.try_into().unwrap();
+        assert_eq!(config.contexts, vec!["work-*"]);
+        assert_eq!(config.theme.name, "Catppuccin Mocha");
+        assert_eq!(config.keybindings.global.quit.display(), "Q");
+        assert_eq!(config.keybindings.global.help.display(), "?");
+    }
+
+    #[test]
+    fn test_profile_names_stay_in_profiles_dir() {
+        for name in ["../config", "work/../../x", "/etc/passwd", "a\\b", ".."] {
+            let err = profile_path(name).unwrap_err();
+            assert!(
+                err.to_string().starts_with("Invalid profile name"),
+                "{name}"
+            );
+        }
+    }
+}
@@ -1,183 +1,263 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use crossterm::event::KeyEvent;
 
 use crate::config::actions::{
+    Action,
+    BookmarkAction,
+    ContextAction,
     DialogAction,
     GlobalAction,
+    HistoryAction,
     NavAction,
+    OutputAction,
     PayloadAction,
+    PromoteAction,
+    ResourceAction,
     SearchAction,
     SecretsAction,
     VersionsAction,
+    ViewerAction,
+    WorkspaceAction,
 };
+use crate::config::key::{Chord, KeyBinding};
 use crate::config::keybindings::KeybindingsConfig;
 
+/// How long the first key of a sequence waits for the second.
+const CHORD_TIMEOUT: Duration = Duration::from_secs(1);
+
 pub struct KeyResolver {
     pub keybindings: Arc<KeybindingsConfig>,
+    /// Every two-key sequence in the config, to recognize their first key.
+    chords: Vec<Chord>,
+    /// First key of a sequence waiting for its second key.
+    pending: Mutex<Option<(KeyEvent, Instant)>>,
 }
 
 impl KeyResolver {
-    pub const fn new(keybindings: Arc<KeybindingsConfig>) -> Self {
-        Self { keybindings }
+    pub fn new(keybindings: Arc<KeybindingsConfig>) -> Self {
+        let chords = keybindings
+            .bindings()
+            .flat_map(KeyBinding::chords)
+            .filter(|chord| chord.is_sequence())
+            .cloned()
+            .collect();
+        Self {
+            keybindings,
+            chords,
+            pending: Mutex::new(None),
+        }
+    }
+
+    // Sequences
+
+    /// Whether `event` is the first key of a configured sequence.
+    pub fn starts_chord(&self, event: &KeyEvent) -> bool {
+        self.chords.iter().any(|chord| chord.starts_with(event))
+    }
+
+    /// Hold `event` as the first key of a sequence.
+    ///
+    /// While it is pending, bindings only match as the second key of a
+    /// sequence starting with it.
+    pub fn set_pending(&self, event: KeyEvent) {
+        *self.lock_pending() = Some((event, Instant::now()));
+    }
+
+    pub fn clear_pending(&self) {
+        *self.lock_pending() = None;
+    }
+
+    /// The pending first key, unless it has timed out.
+    pub fn pending(&self) -> Option<KeyEvent> {
+        self.lock_pending()
+            .filter(|(_, since)| since.elapsed() < CHORD_TIMEOUT)
+            .map(|(event, _)| event)
+    }
+
+    fn lock_pending(&self) -> std::sync::MutexGuard<'_, Option<(KeyEvent, Instant)>> {
+        self.pending
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn hit(&self, binding: &KeyBinding, event: &KeyEvent) -> bool {
+        binding.matches_after(self.pending().as_ref(), event)
     }
 
     // Global actions
     pub fn matches_global(&self, event: &KeyEvent, action: GlobalAction) -> bool {
-        let kb = &self.keybindings.global;
-        match action {
-            GlobalAction::Quit => kb.quit.matches(event),
-            GlobalAction::Help => kb.help.matches(event),
-            GlobalAction::Theme => kb.theme.matches(event),
-            GlobalAction::Back => kb.back.matches(event),
-            GlobalAction::CommandsToggle => kb.commands_toggle.matches(event),
-        }
+        self.hit(action.binding(&self.keybindings), event)
     }
 
     pub fn display_global(&self, action: GlobalAction) -> String {
-        let kb = &self.keybindings.global;
-        match action {
-            GlobalAction::Quit => kb.quit.display(),
-            GlobalAction::Help => kb.help.display(),
-            GlobalAction::Theme => kb.theme.display(),
-            GlobalAction::Back => kb.back.display(),
-            GlobalAction::CommandsToggle => kb.commands_toggle.display(),
-        }
+        action.binding(&self.keybindings).display()
     }
 
     // Navigation actions
+    pub fn counts_enabled(&self) -> bool {
+        self.keybindings.navigation.counts
+    }
+
     pub fn matches_nav(&self, event: &KeyEvent, action: NavAction) -> bool {
-        let kb = &self.keybindings.navigation;
-        match action {
-            NavAction::Up => kb.up.matches(event),
-            NavAction::Down => kb.down.matches(event),
-            NavAction::PageUp => kb.page_up.matches(event),
-            NavAction::PageDown => kb.page_down.matches(event),
-            NavAction::Home => kb.home.matches(event),
-            NavAction::End => kb.end.matches(event),
-            NavAction::Select => kb.select.matches(event),
-        }
+        self.hit(action.binding(&self.keybindings), event)
     }
 
     pub fn display_nav(&self, action: NavAction) -> String {
-        let kb = &self.keybindings.navigation;
-        match action {
-            NavAction::Up => kb.up.display(),
-            NavAction::Down => kb.down.display(),
-            NavAction::PageUp => kb.page_up.display(),
-            NavAction::PageDown => kb.page_down.display(),
-            NavAction::Home => kb.home.display(),
-            NavAction::End => kb.end.display(),
-            NavAction::Select => kb.select.display(),
-        }
+        action.binding(&self.keybindings).display()
     }
 
     // Search actions
     pub fn matches_search(&self, event: &KeyEvent, action: SearchAction) -> bool {
-        let kb = &self.keybindings.search;
-        match action {
-            SearchAction::Toggle => kb.toggle.matches(event),
-            SearchAction::Exit => kb.exit.matches(event),
-        }
+        self.hit(action.binding(&self.keybindings), event)
     }
 
     pub fn display_search(&self, action: SearchAction) -> String {
-        let kb = &self.keybindings.search;
-        match action {
-            SearchAction::Toggle => kb.toggle.display(),
-            SearchAction::Exit => kb.exit.display(),
-        }
+        action.binding(&self.keybindings).display()
     }
 
     // Secrets actions
     pub fn matches_secrets(&self, event: &KeyEvent, action: SecretsAction) -> bool {
-        let kb = &self.keybindings.secrets;
-        match action {
-            SecretsAction::ViewPayload => kb.view_payload.matches(event),
-            SecretsAction::Copy => kb.copy.matches(event),
-            SecretsAction::Versions => kb.versions.matches(event),
-            SecretsAction::New => kb.new.matches(event),
-            SecretsAction::Delete => kb.delete.matches(event),
-            SecretsAction::Labels => kb.labels.matches(event),
-            SecretsAction::Iam => kb.iam.matches(event),
-            SecretsAction::Replication => kb.replication.matches(event),
-            SecretsAction::Reload => kb.reload.matches(event),
-        }
+        self.hit(action.binding(&self.keybindings), event)
     }
 
     pub fn display_secrets(&self, action: SecretsAction) -> String {
-        let kb = &self.keybindings.secrets;
-        match action {
-            SecretsAction::ViewPayload => kb.view_payload.display(),
-            SecretsAction::Copy => kb.copy.display(),
-            SecretsAction::Versions => kb.versions.display(),
-            SecretsAction::New => kb.new.display(),
-            SecretsAction::Delete => kb.delete.display(),
-            SecretsAction::Labels => kb.labels.display(),
-            SecretsAction::Iam => kb.iam.display(),
-            SecretsAction::Replication => kb.replication.display(),
-            SecretsAction::Reload => kb.reload.display(),
-        }
+        action.binding(&self.keybindings).display()
     }
 
     // Versions actions
     pub fn matches_versions(&self, event: &KeyEvent, action: VersionsAction) -> bool {
-        let kb = &self.keybindings.versions;
-        match action {
-            VersionsAction::ViewPayload => kb.view_payload.matches(event),
-            VersionsAction::Add => kb.add.matches(event),
-            VersionsAction::Disable => kb.disable.matches(event),
-            VersionsAction::Enable => kb.enable.matches(event),
-            VersionsAction::Destroy => kb.destroy.matches(event),
-            VersionsAction::Reload => kb.reload.matches(event),
-        }
+        self.hit(action.binding(&self.keybindings), event)
     }
 
     pub fn display_versions(&self, action: VersionsAction) -> String {
-        let kb = &self.keybindings.versions;
-        match action {
-            VersionsAction::ViewPayload => kb.view_payload.display(),
-            VersionsAction::Add => kb.add.display(),
-            VersionsAction::Disable => kb.disable.display(),
-            VersionsAction::Enable => kb.enable.display(),
-            VersionsAction::Destroy => kb.destroy.display(),
-            VersionsAction::Reload => kb.reload.display(),
-        }
+        action.binding(&self.keybindings).display()
     }
 
     // Payload actions
     pub fn matches_payload(&self, event: &KeyEvent, action: PayloadAction) -> bool {
-        let kb = &self.keybindings.payload;
-        match action {
-            PayloadAction::Copy => kb.copy.matches(event),
-            PayloadAction::Reload => kb.reload.matches(event),
-        }
+        self.hit(action.binding(&self.keybindings), event)
     }
 
     pub fn display_payload(&self, action: PayloadAction) -> String {
-        let kb = &self.keybindings.payload;
-        match action {
-            PayloadAction::Copy => kb.copy.display(),
-            PayloadAction::Reload => kb.reload.display(),
-        }
+        action.binding(&self.keybindings).display()
+    }
+
+    // Resource browser actions
+    pub fn matches_resource(&self, event: &KeyEvent, action: ResourceAction) -> bool {
+        self.hit(action.binding(&self.keybindings), event)
+    }
+
+    pub fn display_resource(&self, action: ResourceAction) -> String {
+        action.binding(&self.keybindings).display()
+    }
+
+    // Context selector actions
+    pub fn matches_context(&self, event: &KeyEvent, action: ContextAction) -> bool {
+        self.hit(action.binding(&self.keybindings), event)
+    }
+
+    pub fn display_context(&self, action: ContextAction) -> String {
+        action.binding(&self.keybindings).display()
+    }
+
+    // Command history actions
+    pub fn matches_history(&self, event: &KeyEvent, action: HistoryAction) -> bool {
+        self.hit(action.binding(&self.keybindings), event)
+    }
+
+    pub fn display_history(&self, action: HistoryAction) -> String {
+        action.binding(&self.keybindings).display()
+    }
+
+    // Bookmark manager actions
+    pub fn matches_bookmarks(&self, event: &KeyEvent, action: BookmarkAction) -> bool {
+        self.hit(action.binding(&self.keybindings), event)
+    }
+
+    pub fn display_bookmarks(&self, action: BookmarkAction) -> String {
+        action.binding(&self.keybindings).display()
+    }
+
+    // Workspace picker actions
+    pub fn matches_workspaces(&self, event: &KeyEvent, action: WorkspaceAction) -> bool {
+        self.hit(action.binding(&self.keybindings), event)
+    }
+
+    pub fn display_workspaces(&self, action: WorkspaceAction) -> String {
+        action.binding(&self.keybindings).display()
+    }
+
+    // Text viewer actions
+    pub fn matches_viewer(&self, event: &KeyEvent, action: ViewerAction) -> bool {
+        self.hit(action.binding(&self.keybindings), event)
+    }
+
+    pub fn display_viewer(&self, action: ViewerAction) -> String {
+        action.binding(&self.keybindings).display()
+    }
+
+    // Copy to context wizard actions
+    pub fn matches_promote(&self, event: &KeyEvent, action: PromoteAction) -> bool {
+        self.hit(action.binding(&self.keybindings), event)
+    }
+
+    pub fn display_promote(&self, action: PromoteAction) -> String {
+        action.binding(&self.keybindings).display()
+    }
+
+    // Output pane actions
+    pub fn matches_output(&self, event: &KeyEvent, action: OutputAction) -> bool {
+        self.hit(action.binding(&self.keybindings), event)
+    }
+
+    pub fn display_output(&self, action: OutputAction) -> String {
+        action.binding(&self.keybindings).display()
     }
 
     // Dialog actions
     pub fn matches_dialog(&self, event: &KeyEvent, action: DialogAction) -> bool {
-        let kb = &self.keybindings.dialog;
-        match action {
-            DialogAction::Confirm => kb.confirm.matches(event),
-            DialogAction::Cancel => kb.cancel.matches(event),
-            DialogAction::Dismiss => kb.dismiss.matches(event),
-        }
+        self.hit(action.binding(&self.keybindings), event)
     }
 
     pub fn display_dialog(&self, action: DialogAction) -> String {
-        let kb = &self.keybindings.dialog;
-        match action {
-            DialogAction::Confirm => kb.confirm.display(),
-            DialogAction::Cancel => kb.cancel.display(),
-            DialogAction::Dismiss => kb.dismiss.display(),
-        }
+        action.binding(&self.keybindings).display()
+    }
+
+    // Service actions, declared by the service under its scope
+    pub fn matches_service(&self, event: &KeyEvent, scope: &str, action: &str) -> bool {
+        self.keybindings
+            .service(scope, action)
+            .is_some_and(|binding| self.hit(binding, event))
+    }
+
+    pub fn display_service(&self, scope: &str, action: &str) -> String {
+        self.keybindings
+            .service(scope, action)
+            .map(KeyBinding::display)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    use super::*;
+
+    #[test]
+    fn test_go_to_iam_sequence() {
+        let resolver = KeyResolver::new(Arc::new(KeybindingsConfig::default()));
+        let g = KeyEvent::new(KeyCode::Char('g'), KeyModifiers::NONE);
+        let i = KeyEvent::new(KeyCode::Char('i'), KeyModifiers::NONE);
+
+        assert!(resolver.starts_chord(&g));
+        assert!(resolver.matches_secrets(&i, SecretsAction::Iam));
+        resolver.set_pending(g);
+        assert!(resolver.matches_secrets(&i, SecretsAction::Iam));
+        assert!(!resolver.matches_nav(&i, NavAction::Home));
+        assert!(resolver.matches_nav(&g, NavAction::Home));
+        assert_eq!(resolver.display_secrets(SecretsAction::Iam), "i/g i");
     }
 }
@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use color_eyre::Result;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::config::file;
+use crate::config::paths::state_path;
+
+/// What lazycloud remembers between runs, stored in `state.toml`.
+///
+/// Unlike [`AppConfig`](crate::config::AppConfig), this is written by the app
+/// and not meant to be edited by hand.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct State {
+    #[serde(default)]
+    pub last_context: Option<String>,
+    /// Profile selected in the app, used when `--profile` is not given.
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Service last opened in each context, by context name.
+    #[serde(default)]
+    pub last_services: BTreeMap<String, String>,
+    /// Version that ran last, to show what changed after an upgrade.
+    #[serde(default)]
+    pub last_version: Option<String>,
+    /// Most recently finished commands, newest first.
+    #[serde(default)]
+    pub command_history: Vec<CommandRecord>,
+    /// Bookmarked resource links, newest first.
+    #[serde(default)]
+    pub bookmarks: Vec<String>,
+    /// Link to where the last session was left, for `session.restore`.
+    #[serde(default)]
+    pub last_location: Option<String>,
+    /// Named sets of open services, sorted by name.
+    #[serde(default)]
+    pub workspaces: Vec<Workspace>,
+}
+
+/// The open services saved under a name, e.g. `oncall`, to return to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Workspace {
+    pub name: String,
+    /// Link to the service and resource on display.
+    pub location: String,
+    /// Link to the service kept for switching back, if any.
+    #[serde(default)]
+    pub previous: Option<String>,
+    /// Whether the output pane is open.
+    #[serde(default)]
+    pub output: bool,
+}
+
+/// A finished command, as kept in the command history.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandRecord {
+    pub name: String,
+    /// Display name of the service that ran it, if any.
+    #[serde(default)]
+    pub service: Option<String>,
+    #[serde(default)]
+    pub resource: Option<String>,
+    pub success: bool,
+    pub duration_ms: u64,
+    /// Unix timestamp of when it finished.
+    pub completed_at: i64,
+    /// Run of this session the command can be re-issued from, if any.
+    #[serde(skip)]
+    pub retry_id: Option<u64>,
+}
+
+/// Load the state, falling back to defaults if it is missing or unreadable.
+pub fn load_state() -> State {
+    let Some(path) = state_path() else {
+        return State::default();
+    };
+    let Ok(content) = fs::read_to_string(&path) else {
+        return State::default();
+    };
+    toml::from_str(&content).unwrap_or_else(|err| {
+        warn!(path = %path.display(), %err, "Failed to parse state file");
+        State::default()
+    })
+}
+
+pub fn save_state(state: &State) -> Result<()> {
+    update_state(|saved| *saved = state.clone())
+}
+
+/// Change the saved state with `edit`, keeping what other runs saved since
+/// it was loaded.
+fn update_state(mut edit: impl FnMut(&mut State)) -> Result<()> {
+    let Some(path) = state_path() else {
+        warn!("Could not determine state directory");
+        return Ok(());
+    };
+    file::update(&path, |content| {
+        let mut state = content
+            .and_then(|content| {
+                toml::from_str(content)
+                    .inspect_err(|err| {
+                        warn!(path = %path.display(), %err, "Replacing unreadable state file");
+                    })
+                    .ok()
+            })
+            .unwrap_or_default();
+        edit(&mut state);
+        Ok(toml::to_string_pretty(&state)?)
+    })?;
+    debug!("Saved state to {}", path.display());
+    Ok(())
+}
+
+/// Save the context and the services last used in each context, see
+/// [`AutoSave`](crate::config::autosave::AutoSave).
+pub(super) fn save_last_used(
+    context_name: Option<&str>,
+    services: &BTreeMap<String, String>,
+) -> Result<()> {
+    update_state(|state| {
+        if let Some(context_name) = context_name {
+            state.last_context = Some(context_name.to_string());
+        }
+        state.last_services.extend(services.clone());
+    })
+}
+
+pub fn save_last_version(version: &str) -> Result<()> {
+    update_state(|state| state.last_version = Some(version.to_string()))
+}
+
+pub fn save_command_history(records: &[CommandRecord]) -> Result<()> {
+    update_state(|state| state.command_history = records.to_vec())
+}
+
+pub fn save_bookmarks(bookmarks: &[String]) -> Result<()> {
+    update_state(|state| state.bookmarks = bookmarks.to_vec())
+}
+
+pub fn save_last_location(link: Option<&str>) -> Result<()> {
+    update_state(|state| state.last_location = link.map(str::to_string))
+}
+
+pub fn save_workspaces(workspaces: &[Workspace]) -> Result<()> {
+    update_state(|state| state.workspaces = workspaces.to_vec())
+}
+
+pub fn save_profile(profile: Option<&str>) -> Result<()> {
+    update_state(|state| state.profile = profile.map(str::to_string))
+}
+
+/// Move `last_context` out of an old `config.toml` into the state file.
+///
+/// Returns the migrated context name, if there was one.
+pub(super) fn migrate_from_config(config_path: &Path) -> Result<Option<String>> {
+    let Ok(raw) = fs::read_to_string(config_path) else {
+        return Ok(None);
+    };
+    let mut table: toml::Table = toml::from_str(&raw)?;
+    let Some(toml::Value::String(context)) = table.remove("last_context") else {
+        return Ok(None);
+    };
+
+    // A state file written by a newer run wins over the stale config value
+    if state_path().is_some_and(|path| !path.exists()) {
+        save_state(&State {
+            last_context: Some(context.clone()),
+            ..State::default()
+        })?;
+    }
+    file::write_atomic(config_path, &toml::to_string_pretty(&table)?)?;
+    Ok(Some(context))
+}
@@ -1,22 +1,38 @@
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, LazyLock, PoisonError, RwLock};
 
 use color_eyre::eyre::{Result, eyre};
 use crossterm::event::KeyEvent;
 use google_cloud_auth::credentials::Credentials;
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Rect};
+use ratatui::style::Style;
 use ratatui::widgets::Cell;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info};
 
 use crate::Theme;
-use crate::config::{KeyResolver, config_dir};
+use crate::config::{ContextAction, KeyResolver, config_dir, file};
+use crate::error::Error;
+use crate::i18n::t;
 use crate::provider::Provider;
-use crate::provider::gcp::discover_gcloud_configs;
+use crate::provider::gcp::{GcloudCredentials, discover_gcloud_configs, validate_context};
 use crate::search::Matcher;
-use crate::ui::{ColumnDef, Component, EventResult, Screen, Table, TableEvent, TableRow};
+use crate::ui::{
+    ColumnDef,
+    Component,
+    EventResult,
+    Keybinding,
+    Screen,
+    Table,
+    TableEvent,
+    TableRow,
+};
 
 const CONTEXTS_FILE: &str = "contexts.json";
+/// Name and project of the local vault's context.
+const VAULT_CONTEXT: &str = "local";
 
 /// Cloud context containing connection and authentication details.
 ///
@@ -36,19 +52,84 @@ pub struct GcpContext {
     pub region: Option<String>,
     pub zone: Option<String>,
     pub auth: AuthMethod,
+    /// Other gcloud accounts that can be switched to for this project.
+    #[serde(default)]
+    pub accounts: Vec<String>,
+    /// Other projects whose secrets are listed together with this project's.
+    #[serde(default)]
+    pub projects: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AuthMethod {
     ApplicationDefault,
+    /// Tokens from gcloud for `account`, used after switching accounts.
+    Gcloud,
+    /// Fixture data for `--demo`, without credentials.
+    Demo,
+    /// The local vault in the file at `path`, without credentials.
+    Vault {
+        path: PathBuf,
+    },
 }
 
 impl GcpContext {
     pub fn create_credentials(&self) -> Result<Credentials> {
         match &self.auth {
             AuthMethod::ApplicationDefault => {
-                Ok(google_cloud_auth::credentials::Builder::default().build()?)
+                Ok(google_cloud_auth::credentials::Builder::default()
+                    .build()
+                    .map_err(|err| Error::Auth(err.to_string()))?)
             }
+            AuthMethod::Gcloud => Ok(GcloudCredentials::new(self.account.clone()).into()),
+            AuthMethod::Demo => Err(eyre!("Demo contexts have no credentials")),
+            AuthMethod::Vault { .. } => Err(eyre!("The local vault has no credentials")),
+        }
+    }
+
+    /// The active account followed by the others, without duplicates.
+    pub fn all_accounts(&self) -> Vec<&str> {
+        let mut accounts = vec![self.account.as_str()];
+        for account in &self.accounts {
+            if !accounts.contains(&account.as_str()) {
+                accounts.push(account);
+            }
+        }
+        accounts
+    }
+
+    /// The context's project followed by the others, without duplicates.
+    pub fn all_projects(&self) -> Vec<&str> {
+        let mut projects = vec![self.project_id.as_str()];
+        for project in &self.projects {
+            if !projects.contains(&project.as_str()) {
+                projects.push(project);
+            }
+        }
+        projects
+    }
+
+    /// This context used with `account` instead, through gcloud.
+    #[must_use]
+    pub fn with_account(&self, account: &str) -> Self {
+        if account == self.account {
+            return self.clone();
+        }
+        let accounts = self
+            .all_accounts()
+            .into_iter()
+            .filter(|other| *other != account)
+            .map(str::to_string)
+            .collect();
+        let auth = match &self.auth {
+            AuthMethod::ApplicationDefault | AuthMethod::Gcloud => AuthMethod::Gcloud,
+            auth => auth.clone(),
+        };
+        Self {
+            account: account.to_string(),
+            auth,
+            accounts,
+            ..self.clone()
         }
     }
 }
@@ -57,10 +138,25 @@ impl CloudContext {
     /// Get the provider for this context.
     pub const fn provider(&self) -> Provider {
         match self {
+            Self::Gcp(ctx) if matches!(ctx.auth, AuthMethod::Vault { .. }) => Provider::Local,
             Self::Gcp(_) => Provider::Gcp,
         }
     }
 
+    /// Accounts the context can be used with, the active one first.
+    pub fn accounts(&self) -> Vec<String> {
+        match self {
+            Self::Gcp(ctx) => ctx.all_accounts().into_iter().map(str::to_string).collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_account(&self, account: &str) -> Self {
+        match self {
+            Self::Gcp(ctx) => Self::Gcp(ctx.with_account(account)),
+        }
+    }
+
     /// Get a short display name for this context.
     pub fn name(&self) -> &str {
         match self {
@@ -69,13 +165,87 @@ impl CloudContext {
     }
 }
 
+/// Whether a context can be used, checked in the background.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContextHealth {
+    Checking,
+    Healthy,
+    /// Credentials are missing, expired or rejected.
+    CredentialsExpired(String),
+    /// The credentials work but the project does not exist or is not visible.
+    ProjectInaccessible(String),
+    /// The check itself failed, e.g. without a network connection.
+    Unknown(String),
+}
+
+impl ContextHealth {
+    fn from_result(result: Result<()>) -> Self {
+        let Err(report) = result else {
+            return Self::Healthy;
+        };
+        match Error::from_report(&report) {
+            Error::Auth(message) => Self::CredentialsExpired(message),
+            Error::NotFound(message) | Error::PermissionDenied(message) => {
+                Self::ProjectInaccessible(message)
+            }
+            err => Self::Unknown(err.message().to_string()),
+        }
+    }
+}
+
+impl CloudContext {
+    /// Check that the credentials work and the project is reachable.
+    pub async fn validate(&self) -> ContextHealth {
+        match self {
+            Self::Gcp(ctx) if matches!(ctx.auth, AuthMethod::Demo | AuthMethod::Vault { .. }) => {
+                ContextHealth::Healthy
+            }
+            Self::Gcp(ctx) => ContextHealth::from_result(validate_context(ctx).await),
+        }
+    }
+}
+
 impl std::fmt::Display for CloudContext {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.name())
     }
 }
 
+/// The local vault's file, if enabled.
+static VAULT: LazyLock<RwLock<Option<PathBuf>>> = LazyLock::new(RwLock::default);
+
+/// List the local vault in the file at `path` after the saved contexts from
+/// now on, or no vault for `None`.
+pub fn set_vault(path: Option<PathBuf>) {
+    *VAULT.write().unwrap_or_else(PoisonError::into_inner) = path;
+}
+
+/// The local vault as a context, listed like the cloud ones so secrets can
+/// be copied between it and them.
+fn vault_context(path: PathBuf) -> CloudContext {
+    CloudContext::Gcp(GcpContext {
+        display_name: VAULT_CONTEXT.to_string(),
+        project_id: VAULT_CONTEXT.to_string(),
+        account: String::new(),
+        region: None,
+        zone: None,
+        auth: AuthMethod::Vault { path },
+        accounts: Vec::new(),
+        projects: Vec::new(),
+    })
+}
+
+/// Saved contexts, followed by the local vault if enabled.
 pub fn load_contexts() -> Vec<CloudContext> {
+    let mut contexts = load_saved_contexts();
+    let vault = VAULT.read().unwrap_or_else(PoisonError::into_inner).clone();
+    if let Some(path) = vault {
+        contexts.push(vault_context(path));
+    }
+    contexts
+}
+
+fn load_saved_contexts() -> Vec<CloudContext> {
     if let Some(config_dir) = config_dir() {
         let path = config_dir.join(CONTEXTS_FILE);
         match std::fs::read_to_string(&path) {
@@ -95,12 +265,16 @@ pub fn load_contexts() -> Vec<CloudContext> {
     Vec::new()
 }
 
+/// Save the cloud contexts; the local vault is listed from the config instead.
 pub fn save_contexts(contexts: &[CloudContext]) -> Result<()> {
     if let Some(config_dir) = config_dir() {
-        std::fs::create_dir_all(&config_dir)?;
         let path = config_dir.join(CONTEXTS_FILE);
-        let data = serde_json::to_string_pretty(contexts)?;
-        std::fs::write(&path, data)?;
+        let contexts: Vec<&CloudContext> = contexts
+            .iter()
+            .filter(|ctx| ctx.provider() != Provider::Local)
+            .collect();
+        let data = serde_json::to_string_pretty(&contexts)?;
+        file::write_atomic(&path, &data)?;
         info!(path = %path.display(), count = contexts.len(), "Saved contexts");
     }
     Ok(())
@@ -122,11 +296,39 @@ pub fn find_by_name(contexts: &[CloudContext], name: &str) -> Result<CloudContex
         })
 }
 
+/// Contexts for `--demo`, backed by the embedded fixtures.
+///
+/// They are never saved, so the real context list is left untouched.
+pub fn demo_contexts() -> Vec<CloudContext> {
+    [("demo-dev", "acme-dev"), ("demo-prod", "acme-prod")]
+        .into_iter()
+        .map(|(name, project)| {
+            CloudContext::Gcp(GcpContext {
+                display_name: name.to_string(),
+                project_id: project.to_string(),
+                account: "demo@acme.example".to_string(),
+                region: Some("europe-west4".to_string()),
+                zone: None,
+                auth: AuthMethod::Demo,
+                accounts: vec!["ops@acme.example".to_string()],
+                projects: Vec::new(),
+            })
+        })
+        .collect()
+}
+
 pub fn reconcile_contexts() -> Result<Vec<CloudContext>> {
     debug!("Starting context reconciliation");
     let mut contexts = load_contexts();
     let discovered_configs = discover_gcloud_configs();
-    debug!(count = discovered_configs.len(), "Discovered gcloud configurations");
+    debug!(
+        count = discovered_configs.len(),
+        "Discovered gcloud configurations"
+    );
+    let project_accounts: Vec<(String, String)> = discovered_configs
+        .iter()
+        .map(|config| (config.core.project.clone(), config.core.account.clone()))
+        .collect();
 
     let mut new_count = 0;
     for config in discovered_configs {
@@ -141,14 +343,32 @@ pub fn reconcile_contexts() -> Result<Vec<CloudContext>> {
                 region: config.compute.region,
                 zone: config.compute.zone,
                 auth: AuthMethod::ApplicationDefault,
+                accounts: Vec::new(),
+                projects: Vec::new(),
             }));
             new_count += 1;
         }
     }
 
-    if new_count > 0 {
+    // Accounts of other configurations for the same project can be switched to
+    let mut added_accounts = 0;
+    for CloudContext::Gcp(ctx) in &mut contexts {
+        for (project, account) in &project_accounts {
+            if *project == ctx.project_id && !ctx.all_accounts().contains(&account.as_str()) {
+                ctx.accounts.push(account.clone());
+                added_accounts += 1;
+            }
+        }
+    }
+
+    if new_count > 0 || added_accounts > 0 {
         save_contexts(&contexts)?;
-        info!(new_count, total = contexts.len(), "Reconciliation complete with new contexts");
+        info!(
+            new_count,
+            added_accounts,
+            total = contexts.len(),
+            "Reconciliation complete with new contexts"
+        );
     } else {
         debug!("Reconciliation complete, no new contexts found");
     }
@@ -170,18 +390,18 @@ impl TableRow for CloudContext {
         COLUMNS
     }
 
-    fn render_cells(&self, _theme: &Theme) -> Vec<Cell<'static>> {
+    fn render_cells(&self, theme: &Theme) -> Vec<Cell<'static>> {
         match self {
             Self::Gcp(ctx) => vec![
                 Cell::from(ctx.display_name.clone()),
-                Cell::from("GCP"),
+                Cell::from(self.provider().display_name()),
                 Cell::from(ctx.project_id.clone()),
                 Cell::from(ctx.account.clone()),
                 Cell::from(
                     ctx.region
                         .clone()
                         .or_else(|| ctx.zone.clone())
-                        .unwrap_or_else(|| "—".to_string()),
+                        .unwrap_or_else(|| theme.symbols.none.to_string()),
                 ),
             ],
         }
@@ -204,35 +424,133 @@ impl TableRow for CloudContext {
     }
 }
 
+#[derive(Clone)]
+struct ContextItem {
+    context: CloudContext,
+    health: Option<ContextHealth>,
+}
+
+impl TableRow for ContextItem {
+    fn columns() -> &'static [ColumnDef] {
+        static COLUMNS: &[ColumnDef] = &[
+            ColumnDef::new("Name", Constraint::Min(20)),
+            ColumnDef::new("Provider", Constraint::Length(10)),
+            ColumnDef::new("Project", Constraint::Min(20)),
+            ColumnDef::new("Account", Constraint::Min(24)),
+            ColumnDef::new("Region", Constraint::Length(20)),
+            ColumnDef::new("Health", Constraint::Length(16)),
+        ];
+        COLUMNS
+    }
+
+    fn render_cells(&self, theme: &Theme) -> Vec<Cell<'static>> {
+        let health = match &self.health {
+            None => Cell::from(""),
+            Some(ContextHealth::Checking) => {
+                Cell::from(format!("{} {}", theme.symbols.pending, t("Checking")))
+                    .style(Style::default().fg(theme.overlay1()))
+            }
+            Some(ContextHealth::Healthy) => {
+                Cell::from(format!("{} {}", theme.symbols.ready, t("OK")))
+                    .style(Style::default().fg(theme.green()))
+            }
+            Some(ContextHealth::CredentialsExpired(_)) => {
+                Cell::from(format!("{} {}", theme.symbols.failure, t("Login expired")))
+                    .style(Style::default().fg(theme.red()))
+            }
+            Some(ContextHealth::ProjectInaccessible(_)) => {
+                Cell::from(format!("{} {}", theme.symbols.warning, t("No access")))
+                    .style(Style::default().fg(theme.yellow()))
+            }
+            Some(ContextHealth::Unknown(_)) => {
+                Cell::from(format!("{} {}", theme.symbols.info, t("Unknown")))
+                    .style(Style::default().fg(theme.overlay1()))
+            }
+        };
+        let mut cells = self.context.render_cells(theme);
+        cells.push(health);
+        cells
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        self.context.matches(query)
+    }
+}
+
+pub enum ContextEvent {
+    Selected(CloudContext),
+    /// Pick a project with the credentials of the highlighted context.
+    Browse(Option<CloudContext>),
+}
+
 pub struct ContextSelectorView {
-    table: Table<CloudContext>,
+    items: Vec<ContextItem>,
+    table: Table<ContextItem>,
+    resolver: Arc<KeyResolver>,
 }
 
 impl ContextSelectorView {
-    pub fn new(resolver: Arc<KeyResolver>) -> Result<Self> {
-        Ok(Self::with_contexts(reconcile_contexts()?, resolver))
+    pub fn with_contexts(
+        contexts: Vec<CloudContext>,
+        health: &HashMap<String, ContextHealth>,
+        resolver: Arc<KeyResolver>,
+    ) -> Self {
+        let items: Vec<ContextItem> = contexts
+            .into_iter()
+            .map(|context| ContextItem {
+                health: health.get(context.name()).cloned(),
+                context,
+            })
+            .collect();
+        Self {
+            table: Table::new(items.clone(), resolver.clone())
+                .with_title(format!(" {} ", t("Contexts"))),
+            items,
+            resolver,
+        }
     }
 
-    pub fn with_contexts(contexts: Vec<CloudContext>, resolver: Arc<KeyResolver>) -> Self {
-        Self {
-            table: Table::new(contexts, resolver).with_title(" Contexts "),
+    /// Update the health shown for a context.
+    pub fn set_health(&mut self, name: &str, health: &ContextHealth) {
+        for item in &mut self.items {
+            if item.context.name() == name {
+                item.health = Some(health.clone());
+            }
         }
+        self.table.set_items(self.items.clone());
     }
 }
 
 impl Screen for ContextSelectorView {
-    type Output = CloudContext;
+    type Output = ContextEvent;
 
     fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
         let result = self.table.handle_key(key)?;
         Ok(match result {
-            EventResult::Event(TableEvent::Activated(context)) => context.into(),
+            EventResult::Event(TableEvent::Activated(item)) => {
+                ContextEvent::Selected(item.context).into()
+            }
             EventResult::Consumed | EventResult::Event(_) => EventResult::Consumed,
+            EventResult::Ignored if self.resolver.matches_context(&key, ContextAction::Browse) => {
+                let selected = self.table.selected_item().map(|item| item.context.clone());
+                ContextEvent::Browse(selected).into()
+            }
             EventResult::Ignored => EventResult::Ignored,
         })
     }
 
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
     fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
         self.table.render(frame, area, theme);
     }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        vec![Keybinding::hint(
+            self.resolver.display_context(ContextAction::Browse),
+            "Browse projects",
+        )]
+    }
 }
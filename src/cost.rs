@@ -0,0 +1,141 @@
+//! Estimated charges of operations the cloud provider bills for.
+//!
+//! Each billed [`Operation`] has a price per unit, the list price unless
+//! `costs.rates` in the config sets another one. Bulk operations show an
+//! estimate before they run, and the status bar sums up what the session
+//! has spent so far. Estimates are rough: free tiers, discounts and taxes
+//! are ignored, and a stored version counts as one month of storage.
+
+use std::collections::BTreeMap;
+use std::sync::{LazyLock, PoisonError, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::t_args;
+
+/// The `costs` config section.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CostConfig {
+    /// Show estimates and the session cost.
+    pub enabled: bool,
+    /// Price in USD per unit by operation, e.g. `"secretmanager.access" = 0.000003`.
+    pub rates: BTreeMap<String, f64>,
+}
+
+impl Default for CostConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            rates: BTreeMap::new(),
+        }
+    }
+}
+
+/// An operation with a price per unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    /// Reading a secret version's payload.
+    SecretAccess,
+    /// Storing a secret version, billed per month while it is active.
+    SecretVersion,
+}
+
+impl Operation {
+    /// Key of the operation in `costs.rates`.
+    pub const fn key(self) -> &'static str {
+        match self {
+            Self::SecretAccess => "secretmanager.access",
+            Self::SecretVersion => "secretmanager.version",
+        }
+    }
+
+    /// List price in USD per unit.
+    const fn list_price(self) -> f64 {
+        match self {
+            // $0.03 per 10,000 access operations
+            Self::SecretAccess => 0.000_003,
+            // $0.06 per active version and month
+            Self::SecretVersion => 0.06,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Session {
+    config: CostConfig,
+    spent: f64,
+}
+
+static SESSION: LazyLock<RwLock<Session>> = LazyLock::new(RwLock::default);
+
+/// Use the rates of `config` from now on. What was spent so far is kept.
+pub fn set_config(config: &CostConfig) {
+    SESSION
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .config = config.clone();
+}
+
+/// Estimated price of `count` units of `operation`, `None` when disabled.
+pub fn estimate(operation: Operation, count: usize) -> Option<f64> {
+    let session = SESSION.read().unwrap_or_else(PoisonError::into_inner);
+    session.config.enabled.then(|| {
+        let rate = session
+            .config
+            .rates
+            .get(operation.key())
+            .copied()
+            .unwrap_or_else(|| operation.list_price());
+        // Counts stay far below the range where f64 loses precision
+        #[allow(clippy::cast_precision_loss)]
+        let count = count as f64;
+        rate * count
+    })
+}
+
+/// Add `count` units of `operation` to the session cost.
+pub fn record(operation: Operation, count: usize) {
+    if let Some(cost) = estimate(operation, count) {
+        SESSION
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .spent += cost;
+    }
+}
+
+/// Estimated cost of the session, once anything billed has run.
+pub fn session_cost() -> Option<f64> {
+    let session = SESSION.read().unwrap_or_else(PoisonError::into_inner);
+    (session.config.enabled && session.spent > 0.0).then_some(session.spent)
+}
+
+/// An amount in USD, e.g. `$1.20`, or `< $0.01` for fractions of a cent.
+pub fn format_cost(usd: f64) -> String {
+    if usd < 0.01 {
+        "< $0.01".to_string()
+    } else {
+        format!("${usd:.2}")
+    }
+}
+
+/// The line shown in confirm dialogs, `None` when estimates are disabled.
+pub fn estimate_line(operation: Operation, count: usize) -> Option<String> {
+    let usd = estimate(operation, count)?;
+    Some(t_args(
+        "Estimated cost: {amount}",
+        &[("amount", &format_cost(usd))],
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_cost() {
+        assert_eq!(format_cost(0.000_3), "< $0.01");
+        assert_eq!(format_cost(0.06), "$0.06");
+        assert_eq!(format_cost(12.345), "$12.35");
+    }
+}
@@ -0,0 +1,184 @@
+//! Encrypting the files secrets are saved to, and decrypting them when read
+//! back.
+//!
+//! The extension of a path picks the tool: `.age` files are encrypted with
+//! `age`, `.gpg` and `.asc` files with `gpg`, to the recipients of the
+//! `encryption` config section. Any other path is plain text. Both tools run
+//! as programs, so their own keyrings and agents apply.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{LazyLock, PoisonError, RwLock};
+
+use color_eyre::Result;
+use color_eyre::eyre::{bail, eyre};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command as Process;
+
+/// The `encryption` config section.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EncryptionConfig {
+    /// Public key `.age` files are encrypted to, e.g. `age1...`.
+    pub age_recipient: Option<String>,
+    /// Identity file decrypting `.age` files.
+    pub age_identity: Option<PathBuf>,
+    /// Key ID or email `.gpg` and `.asc` files are encrypted to.
+    pub gpg_recipient: Option<String>,
+}
+
+/// How a file is encrypted, by its extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    Age,
+    Gpg,
+    /// GPG with ASCII armor.
+    GpgArmored,
+}
+
+impl Scheme {
+    /// The scheme of `path`, `None` for plain text.
+    pub fn of(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "age" => Some(Self::Age),
+            "gpg" => Some(Self::Gpg),
+            "asc" => Some(Self::GpgArmored),
+            _ => None,
+        }
+    }
+}
+
+static CONFIG: LazyLock<RwLock<EncryptionConfig>> = LazyLock::new(RwLock::default);
+
+/// Use the recipients of `config` from now on.
+pub fn set_config(config: &EncryptionConfig) {
+    *CONFIG.write().unwrap_or_else(PoisonError::into_inner) = config.clone();
+}
+
+/// Extension suggested for new files, so they are encrypted unless the user
+/// removes it: `.age` with an age recipient configured, else `.gpg` with a
+/// GPG one.
+pub fn suggested_extension() -> Option<&'static str> {
+    let config = CONFIG.read().unwrap_or_else(PoisonError::into_inner);
+    if config.age_recipient.is_some() {
+        Some("age")
+    } else if config.gpg_recipient.is_some() {
+        Some("gpg")
+    } else {
+        None
+    }
+}
+
+/// `data` encrypted as the extension of `path` asks for.
+pub async fn encrypt_for(path: &Path, data: Vec<u8>) -> Result<Vec<u8>> {
+    let Some(scheme) = Scheme::of(path) else {
+        return Ok(data);
+    };
+    let config = CONFIG
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+    match scheme {
+        Scheme::Age => {
+            let recipient = config.age_recipient.ok_or_else(|| {
+                eyre!("Set encryption.age_recipient in the config to save .age files")
+            })?;
+            run("age", &["--encrypt", "--recipient", &recipient], data).await
+        }
+        Scheme::Gpg | Scheme::GpgArmored => {
+            let recipient = config.gpg_recipient.ok_or_else(|| {
+                eyre!("Set encryption.gpg_recipient in the config to save .gpg files")
+            })?;
+            let mut args = vec!["--batch", "--yes", "--encrypt", "--recipient", &recipient];
+            if scheme == Scheme::GpgArmored {
+                args.push("--armor");
+            }
+            run("gpg", &args, data).await
+        }
+    }
+}
+
+/// The contents of the file at `path`, decrypted if its extension says it is
+/// encrypted.
+pub async fn decrypt_from(path: &Path, data: Vec<u8>) -> Result<Vec<u8>> {
+    match Scheme::of(path) {
+        None => Ok(data),
+        Some(Scheme::Age) => {
+            let identity = CONFIG
+                .read()
+                .unwrap_or_else(PoisonError::into_inner)
+                .age_identity
+                .clone()
+                .ok_or_else(|| {
+                    eyre!("Set encryption.age_identity in the config to read .age files")
+                })?;
+            let identity = identity.to_string_lossy();
+            run("age", &["--decrypt", "--identity", &identity], data).await
+        }
+        Some(Scheme::Gpg | Scheme::GpgArmored) => {
+            run("gpg", &["--batch", "--quiet", "--decrypt"], data).await
+        }
+    }
+}
+
+/// Output of `program` fed `input` on stdin.
+async fn run(program: &str, args: &[&str], input: Vec<u8>) -> Result<Vec<u8>> {
+    let mut child = Process::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|err| eyre!("Cannot run {program}: {err}"))?;
+    let mut stdin = child
+        .stdin
+        .take()
+        .ok_or_else(|| eyre!("No stdin for {program}"))?;
+    // Written alongside reading the output, a large input would fill the pipe
+    let writer = tokio::spawn(async move { stdin.write_all(&input).await });
+
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("{program} exited with {}: {}", output.status, stderr.trim());
+    }
+    writer.await??;
+    Ok(output.stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scheme_from_extension() {
+        assert_eq!(Scheme::of(Path::new("db-password.age")), Some(Scheme::Age));
+        assert_eq!(Scheme::of(Path::new("key.json.GPG")), Some(Scheme::Gpg));
+        assert_eq!(Scheme::of(Path::new("key.asc")), Some(Scheme::GpgArmored));
+        assert_eq!(Scheme::of(Path::new("key.json")), None);
+        assert_eq!(Scheme::of(Path::new("age")), None);
+    }
+
+    #[tokio::test]
+    async fn test_plain_paths_pass_through() {
+        let data = b"hunter2".to_vec();
+        let path = Path::new("db-password.txt");
+        assert_eq!(encrypt_for(path, data.clone()).await.unwrap(), data);
+        assert_eq!(decrypt_from(path, data.clone()).await.unwrap(), data);
+    }
+
+    #[tokio::test]
+    async fn test_missing_keys_are_reported() {
+        let error = encrypt_for(Path::new("db-password.age"), Vec::new())
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("encryption.age_recipient"));
+        let error = decrypt_from(Path::new("db-password.age"), Vec::new())
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("encryption.age_identity"));
+    }
+}
@@ -0,0 +1,177 @@
+//! Failures the app tells apart.
+//!
+//! Most code returns [`color_eyre::Result`]. Clients convert failed API calls
+//! into an [`Error`] before it is wrapped in a report, so the app can recover
+//! the kind with [`Error::from_report`] and react to it: ask to
+//! re-authenticate, suggest retrying, or show the failure as final.
+
+use std::fmt;
+
+use color_eyre::Report;
+use google_cloud_gax::error::Error as GaxError;
+use google_cloud_gax::error::rpc::Code;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// Credentials are missing, expired or rejected.
+    Auth(String),
+    NotFound(String),
+    PermissionDenied(String),
+    /// A quota or rate limit was hit; retrying later helps.
+    RateLimited(String),
+    /// The request did not reach the API or timed out.
+    Network(String),
+    Internal(String),
+}
+
+impl Error {
+    /// The kind of the [`Error`] in `report`, or [`Error::Internal`], with
+    /// the messages of the whole chain so added context is kept.
+    pub fn from_report(report: &Report) -> Self {
+        let message = report
+            .chain()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(": ");
+        match report
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<Self>())
+        {
+            Some(Self::Auth(_)) => Self::Auth(message),
+            Some(Self::NotFound(_)) => Self::NotFound(message),
+            Some(Self::PermissionDenied(_)) => Self::PermissionDenied(message),
+            Some(Self::RateLimited(_)) => Self::RateLimited(message),
+            Some(Self::Network(_)) => Self::Network(message),
+            Some(Self::Internal(_)) | None => Self::Internal(message),
+        }
+    }
+
+    /// Kind of a failed HTTP request to a REST API.
+    pub fn from_http_status(status: u16, message: impl Into<String>) -> Self {
+        let message = message.into();
+        match status {
+            401 => Self::Auth(message),
+            403 => Self::PermissionDenied(message),
+            404 => Self::NotFound(message),
+            429 => Self::RateLimited(message),
+            502..=504 => Self::Network(message),
+            _ => Self::Internal(message),
+        }
+    }
+
+    /// Whether the same request may succeed if sent again.
+    pub const fn is_retryable(&self) -> bool {
+        matches!(self, Self::RateLimited(_) | Self::Network(_))
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Self::Auth(message)
+            | Self::NotFound(message)
+            | Self::PermissionDenied(message)
+            | Self::RateLimited(message)
+            | Self::Network(message)
+            | Self::Internal(message) => message,
+        }
+    }
+
+    pub const fn title(&self) -> &'static str {
+        match self {
+            Self::Auth(_) => "Authentication required",
+            Self::NotFound(_) => "Not found",
+            Self::PermissionDenied(_) => "Permission denied",
+            Self::RateLimited(_) => "Rate limited",
+            Self::Network(_) => "Network error",
+            Self::Internal(_) => "Error",
+        }
+    }
+
+    /// What the user can do about it.
+    pub const fn hint(&self) -> Option<&'static str> {
+        match self {
+            Self::Auth(_) => {
+                Some("Run `gcloud auth application-default login`, then reload the view.")
+            }
+            Self::NotFound(_) => Some("It may have been deleted elsewhere, reload the view."),
+            Self::PermissionDenied(_) => Some("Ask a project owner to grant you access."),
+            Self::RateLimited(_) => Some("Wait a moment and try again."),
+            Self::Network(_) => Some("Check your connection and try again."),
+            Self::Internal(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<GaxError> for Error {
+    fn from(err: GaxError) -> Self {
+        if err.is_authentication() {
+            return Self::Auth(err.to_string());
+        }
+        if err.is_timeout() || err.is_connect() || err.is_io() || err.is_transport() {
+            return Self::Network(err.to_string());
+        }
+        if err.is_exhausted() {
+            return Self::RateLimited(err.to_string());
+        }
+        let Some(status) = err.status() else {
+            return err.http_status_code().map_or_else(
+                || Self::Internal(err.to_string()),
+                |code| Self::from_http_status(code, err.to_string()),
+            );
+        };
+        let message = status.message.clone();
+        match status.code {
+            Code::Unauthenticated => Self::Auth(message),
+            Code::NotFound => Self::NotFound(message),
+            Code::PermissionDenied => Self::PermissionDenied(message),
+            Code::ResourceExhausted => Self::RateLimited(message),
+            Code::Unavailable | Code::DeadlineExceeded => Self::Network(message),
+            _ => Self::Internal(message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use color_eyre::eyre::eyre;
+    use google_cloud_gax::error::rpc::Status;
+
+    use super::*;
+
+    #[test]
+    fn test_from_gax_status() {
+        let status = |code| GaxError::service(Status::default().set_code(code).set_message("boom"));
+        assert_eq!(
+            Error::from(status(Code::NotFound)),
+            Error::NotFound("boom".to_string())
+        );
+        assert_eq!(
+            Error::from(status(Code::Unauthenticated)),
+            Error::Auth("boom".to_string())
+        );
+        assert!(Error::from(status(Code::ResourceExhausted)).is_retryable());
+        assert!(Error::from(GaxError::timeout("slow")).is_retryable());
+        assert!(!Error::from(status(Code::InvalidArgument)).is_retryable());
+    }
+
+    #[test]
+    fn test_from_report() {
+        let report = Report::new(Error::PermissionDenied("no".to_string()))
+            .wrap_err("Failed to delete secret");
+        assert_eq!(
+            Error::from_report(&report),
+            Error::PermissionDenied("Failed to delete secret: no".to_string())
+        );
+        assert_eq!(
+            Error::from_report(&eyre!("plain")),
+            Error::Internal("plain".to_string())
+        );
+    }
+}
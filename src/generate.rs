@@ -0,0 +1,105 @@
+//! Random values for new secrets and versions.
+//!
+//! Hex and base64 values are encoded random bytes like `openssl rand`, so
+//! their length is the number of bytes. Passphrases are words picked from a
+//! short list of common four-letter words.
+
+use std::fmt::Write;
+
+use rand::Rng;
+use rand::distr::{Alphanumeric, SampleString};
+use rand::seq::IndexedRandom;
+
+use crate::clipboard::base64;
+
+const WORDS: &str = include_str!("generate/words.txt");
+
+/// Longest value that can be generated, in the charset's unit.
+pub const MAX_LENGTH: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Charset {
+    Hex,
+    Base64,
+    Alphanumeric,
+    Passphrase,
+}
+
+impl Charset {
+    pub const ALL: [Self; 4] = [
+        Self::Hex,
+        Self::Base64,
+        Self::Alphanumeric,
+        Self::Passphrase,
+    ];
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Hex => "Hex",
+            Self::Base64 => "Base64",
+            Self::Alphanumeric => "Alphanumeric",
+            Self::Passphrase => "Passphrase",
+        }
+    }
+
+    /// What the length counts.
+    pub const fn unit(self) -> &'static str {
+        match self {
+            Self::Hex | Self::Base64 => "bytes",
+            Self::Alphanumeric => "characters",
+            Self::Passphrase => "words",
+        }
+    }
+
+    pub const fn default_length(self) -> usize {
+        match self {
+            Self::Hex | Self::Base64 | Self::Alphanumeric => 32,
+            Self::Passphrase => 6,
+        }
+    }
+
+    /// A new random value of `length` units.
+    pub fn generate(self, length: usize) -> String {
+        let mut rng = rand::rng();
+        match self {
+            Self::Hex => {
+                random_bytes(&mut rng, length)
+                    .iter()
+                    .fold(String::new(), |mut hex, byte| {
+                        let _ = write!(hex, "{byte:02x}");
+                        hex
+                    })
+            }
+            Self::Base64 => base64(&random_bytes(&mut rng, length)),
+            Self::Alphanumeric => Alphanumeric.sample_string(&mut rng, length),
+            Self::Passphrase => {
+                let words: Vec<&str> = WORDS.lines().collect();
+                (0..length)
+                    .filter_map(|_| words.choose(&mut rng).copied())
+                    .collect::<Vec<_>>()
+                    .join("-")
+            }
+        }
+    }
+}
+
+fn random_bytes(rng: &mut impl Rng, length: usize) -> Vec<u8> {
+    let mut bytes = vec![0; length];
+    rng.fill_bytes(&mut bytes);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_lengths() {
+        assert_eq!(Charset::Hex.generate(16).len(), 32);
+        assert_eq!(Charset::Base64.generate(32).len(), 44);
+        let value = Charset::Alphanumeric.generate(20);
+        assert_eq!(value.len(), 20);
+        assert!(value.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert_eq!(Charset::Passphrase.generate(5).split('-').count(), 5);
+    }
+}
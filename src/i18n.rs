@@ -0,0 +1,151 @@
+//! Translations of user-facing strings.
+//!
+//! The English text doubles as the message id: screens wrap literals in [`t`]
+//! (or [`t_args`] for text with `{placeholders}`) and get the translation for
+//! the active locale. Text missing from a catalog falls back to English, so a
+//! partial translation never hides anything. Keybinding descriptions and
+//! table headers are translated where they are rendered, so services only
+//! need to add their strings to the catalogs.
+//!
+//! Catalogs are TOML files in `src/i18n/` mapping English text to the
+//! translation, embedded into the binary.
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    En,
+    De,
+}
+
+impl Locale {
+    pub const ALL: [Self; 2] = [Self::En, Self::De];
+
+    /// Locale from `LC_ALL`, `LC_MESSAGES` or `LANG`, e.g. `de_CH.UTF-8`.
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .map_or_else(Self::default, |value| Self::from_tag(&value))
+    }
+
+    fn from_tag(tag: &str) -> Self {
+        let language = tag.split(['_', '-', '.', '@']).next().unwrap_or_default();
+        match language.to_ascii_lowercase().as_str() {
+            "de" => Self::De,
+            _ => Self::En,
+        }
+    }
+
+    const fn catalog_source(self) -> Option<&'static str> {
+        match self {
+            Self::En => None,
+            Self::De => Some(include_str!("i18n/de.toml")),
+        }
+    }
+}
+
+static LOCALE: AtomicU8 = AtomicU8::new(Locale::En as u8);
+
+static CATALOGS: LazyLock<HashMap<Locale, HashMap<String, String>>> = LazyLock::new(|| {
+    Locale::ALL
+        .into_iter()
+        .filter_map(|locale| Some((locale, locale.catalog_source()?)))
+        .map(|(locale, source)| {
+            let catalog = toml::from_str(source).expect("bundled catalog is valid TOML");
+            (locale, catalog)
+        })
+        .collect()
+});
+
+/// Switch the language of all text rendered from now on.
+pub fn set_locale(locale: Locale) {
+    LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+pub fn locale() -> Locale {
+    let current = LOCALE.load(Ordering::Relaxed);
+    Locale::ALL
+        .into_iter()
+        .find(|locale| *locale as u8 == current)
+        .unwrap_or_default()
+}
+
+/// `msgid` in the active locale.
+pub fn t(msgid: &str) -> &str {
+    translate(locale(), msgid)
+}
+
+/// `msgid` in the active locale with each `{name}` replaced by its value.
+pub fn t_args(msgid: &str, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(t(msgid).to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), value)
+        })
+}
+
+fn translate(locale: Locale, msgid: &str) -> &str {
+    CATALOGS
+        .get(&locale)
+        .and_then(|catalog| catalog.get(msgid))
+        .map_or(msgid, String::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Names of the `{placeholders}` in `text`, sorted.
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = text
+            .split('{')
+            .skip(1)
+            .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn test_catalogs_keep_placeholders() {
+        for (locale, catalog) in CATALOGS.iter() {
+            for (msgid, translation) in catalog {
+                assert!(
+                    !translation.is_empty(),
+                    "{locale:?}: empty translation of {msgid:?}"
+                );
+                assert_eq!(
+                    placeholders(msgid),
+                    placeholders(translation),
+                    "{locale:?}: placeholders differ in {msgid:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_translate_falls_back_to_english() {
+        assert_eq!(translate(Locale::De, "Quit"), "Beenden");
+        assert_eq!(
+            translate(Locale::De, "not in any catalog"),
+            "not in any catalog"
+        );
+        assert_eq!(translate(Locale::En, "Quit"), "Quit");
+    }
+
+    #[test]
+    fn test_locale_from_tag() {
+        assert_eq!(Locale::from_tag("de_CH.UTF-8"), Locale::De);
+        assert_eq!(Locale::from_tag("DE"), Locale::De);
+        assert_eq!(Locale::from_tag("en_US.UTF-8"), Locale::En);
+        assert_eq!(Locale::from_tag("C"), Locale::En);
+    }
+}
@@ -1,25 +1,45 @@
+use std::fmt;
+use std::panic::PanicHookInfo;
 use std::sync::Arc;
 
-use clap::Parser;
+use clap::{CommandFactory, Parser};
 use color_eyre::Result;
-use tracing::info;
+use color_eyre::config::HookBuilder;
+use color_eyre::section::PanicMessage;
+use tracing::{info, warn};
 use tracing_appender::non_blocking::WorkerGuard;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
 
 use crate::app::App;
-use crate::config::KeyResolver;
+use crate::config::{KeyResolver, LowBandwidth};
+use crate::i18n::Locale;
 use crate::registry::ServiceRegistry;
+use crate::startup::StartupProfile;
 
 mod app;
+mod changelog;
 mod cli;
+mod clipboard;
 pub mod commands;
+mod completions;
 mod config;
 mod context;
+mod cost;
+mod encryption;
+mod error;
+mod generate;
+mod i18n;
+mod middleware;
 mod provider;
+mod redact;
 mod registry;
 mod search;
 pub mod service;
+mod startup;
+mod terraform;
 mod theme;
+mod time;
 pub mod tui;
 mod ui;
 
@@ -27,31 +47,135 @@ pub use theme::Theme;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    color_eyre::install()?;
+    HookBuilder::default()
+        .panic_message(RedactedPanicMessage)
+        .install()?;
+    completions::handle_request();
+    let args = cli::Args::parse();
+
+    match &args.command {
+        Some(cli::CliCommand::Paths) => {
+            print_paths();
+            return Ok(());
+        }
+        Some(cli::CliCommand::Man) => return print_man_page(),
+        Some(cli::CliCommand::Completions { shell }) => {
+            return completions::print_registration(shell);
+        }
+        Some(cli::CliCommand::Open { .. }) | None => {}
+    }
+
+    let moved_logs = config::paths::migrate_logs();
     let _guard = initialize_logging()?;
     info!("Starting lazycloud");
+    match moved_logs {
+        Ok(Some(message)) => info!("{message}"),
+        Ok(None) => {}
+        Err(err) => warn!("Failed to move the old logs: {err}"),
+    }
+    config::paths::migrate();
+    let mut profile = StartupProfile::new(args.profile_startup);
 
-    let args = cli::Args::parse();
-
-    let config = Arc::new(config::load()?);
-    let resolver = Arc::new(KeyResolver::new(Arc::new(config.keybindings.clone())));
+    let (config, config_profile) =
+        profile.measure("config load", || load_config(args.profile.as_deref()))?;
+    i18n::set_locale(config.locale.unwrap_or_else(Locale::from_env));
+    if let Err(err) = time::set_timezone(&config.display.timezone) {
+        warn!("{err}, showing times in UTC");
+    }
+    cost::set_config(&config.costs);
+    clipboard::set_backend(config.clipboard);
+    encryption::set_config(&config.encryption);
+    context::set_vault(config.vault.path());
+    ui::widgets::set_reduced_motion(
+        config.display.reduced_motion || config.display.low_bandwidth == LowBandwidth::On,
+    );
+    let config = Arc::new(config);
     let theme = theme::theme_from_name(&config.theme.name);
+    let contexts = if args.demo {
+        context::demo_contexts()
+    } else {
+        profile.measure("context discovery", context::reconcile_contexts)?
+    };
 
     let mut registry = ServiceRegistry::new();
-    provider::register_all(&mut registry);
+    profile.measure("service registry", || {
+        provider::register_all(&mut registry, args.demo);
+    });
+    let keybindings = registry.keybindings(&config.keybindings);
+    let resolver = Arc::new(KeyResolver::new(Arc::new(keybindings)));
 
-    let mut app = App::new(registry, config, resolver, theme)?;
+    let mut app = App::new(
+        registry,
+        contexts,
+        config,
+        config_profile,
+        resolver,
+        theme,
+        profile,
+    );
     app.apply_cli_args(&args)?;
+    app.show_whats_new();
+    app.check_for_update();
     app.run().await?;
 
     Ok(())
 }
 
+/// Panic message of crash reports, with secret values masked.
+struct RedactedPanicMessage;
+
+impl PanicMessage for RedactedPanicMessage {
+    fn display(&self, info: &PanicHookInfo<'_>, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let payload = info
+            .payload()
+            .downcast_ref::<String>()
+            .map(String::as_str)
+            .or_else(|| info.payload().downcast_ref::<&str>().copied())
+            .unwrap_or("<non string panic payload>");
+        writeln!(f, "The application panicked (crashed).")?;
+        writeln!(f, "Message:  {}", redact::scrub(payload))?;
+        if let Some(location) = info.location() {
+            write!(f, "Location: {location}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Load the config with the profile from `--profile`, or else the one last
+/// selected in the app.
+///
+/// A remembered profile that no longer loads falls back to the base config
+/// instead of blocking startup.
+fn load_config(requested: Option<&str>) -> Result<(config::AppConfig, Option<String>)> {
+    if let Some(name) = requested {
+        return Ok((
+            config::load_with_profile(Some(name))?,
+            Some(name.to_string()),
+        ));
+    }
+    if let Some(name) = config::load_state().profile {
+        match config::load_with_profile(Some(&name)) {
+            Ok(config) => return Ok((config, Some(name))),
+            Err(err) => warn!("Ignoring saved profile: {err}"),
+        }
+    }
+    Ok((config::load_with_profile(None)?, None))
+}
+
+fn print_paths() {
+    for (name, path) in config::paths::all() {
+        let path = path.map_or_else(|| "(unavailable)".to_string(), |p| p.display().to_string());
+        println!("{name:<10}{path}");
+    }
+}
+
+fn print_man_page() -> Result<()> {
+    clap_mangen::Man::new(cli::Args::command()).render(&mut std::io::stdout())?;
+    Ok(())
+}
+
 fn initialize_logging() -> Result<WorkerGuard> {
-    let directory = dirs::data_local_dir().map_or_else(
-        || std::path::PathBuf::from("logs"),
-        |path| path.join("lazycloud").join("logs"),
-    );
+    let directory = config::paths::log_dir().unwrap_or_else(|| std::path::PathBuf::from("logs"));
     std::fs::create_dir_all(&directory)?;
 
     let file_appender = tracing_appender::rolling::daily(&directory, "lazycloud.log");
@@ -61,7 +185,7 @@ fn initialize_logging() -> Result<WorkerGuard> {
         .with(tracing_subscriber::EnvFilter::from_default_env())
         .with(
             tracing_subscriber::fmt::layer()
-                .with_writer(non_blocking)
+                .with_writer(redact::Scrubbed(non_blocking))
                 .with_ansi(false)
                 .with_file(true)
                 .with_line_number(true)
@@ -0,0 +1,305 @@
+//! Hooks around the service update loop and command execution.
+//!
+//! Every [`Middleware`] in the app's [`MiddlewareStack`] sees each service
+//! message once it was handled, each finished command and each cache lookup.
+//! The built-in ones log messages and commands, and collect [`Metrics`]
+//! shown on the stats overlay. Nothing leaves the process.
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use tracing::debug;
+
+use crate::config::DiagnosticsConfig;
+
+pub trait Middleware: Send + Sync {
+    /// A service handled `message`, see [`message_name`].
+    fn on_message(&self, service: &str, message: &str, elapsed: Duration) {
+        _ = (service, message, elapsed);
+    }
+
+    /// A command finished.
+    fn on_command(&self, name: &str, elapsed: Duration, success: bool) {
+        _ = (name, elapsed, success);
+    }
+
+    /// A service looked up `key` in its cache.
+    fn on_cache(&self, key: &str, hit: bool) {
+        _ = (key, hit);
+    }
+}
+
+/// The middlewares enabled by the config, called in order.
+#[derive(Clone, Default)]
+pub struct MiddlewareStack {
+    layers: Vec<Arc<dyn Middleware>>,
+}
+
+impl MiddlewareStack {
+    /// The stack for `config`, and the metrics it collects if enabled.
+    pub fn from_config(config: &DiagnosticsConfig) -> (Self, Option<Arc<Metrics>>) {
+        let mut stack = Self::default();
+        if config.log_messages {
+            stack.push(Arc::new(LogMiddleware));
+        }
+        let metrics = config.metrics.then(|| Arc::new(Metrics::default()));
+        if let Some(metrics) = &metrics {
+            stack.push(metrics.clone());
+        }
+        (stack, metrics)
+    }
+
+    pub fn push(&mut self, middleware: Arc<dyn Middleware>) {
+        self.layers.push(middleware);
+    }
+
+    /// Whether calls can be skipped, e.g. to avoid formatting messages.
+    pub const fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+}
+
+impl Middleware for MiddlewareStack {
+    fn on_message(&self, service: &str, message: &str, elapsed: Duration) {
+        for layer in &self.layers {
+            layer.on_message(service, message, elapsed);
+        }
+    }
+
+    fn on_command(&self, name: &str, elapsed: Duration, success: bool) {
+        for layer in &self.layers {
+            layer.on_command(name, elapsed, success);
+        }
+    }
+
+    fn on_cache(&self, key: &str, hit: bool) {
+        for layer in &self.layers {
+            layer.on_cache(key, hit);
+        }
+    }
+}
+
+/// Name of an enum message without its fields, e.g. `Secret::Loaded`, so
+/// payloads and secret values never reach the log.
+pub fn message_name(message: &impl Debug) -> String {
+    let debug = format!("{message:?}");
+    let path: Vec<&str> = debug
+        .split('(')
+        .map_while(|segment| {
+            let name = segment.split([' ', ')']).next().unwrap_or_default();
+            let is_variant = name.starts_with(|c: char| c.is_ascii_uppercase())
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            // Anything but a nested message after the name ends the path
+            is_variant.then_some((name, name.len() < segment.len()))
+        })
+        .scan(false, |done, (name, last)| {
+            if *done {
+                return None;
+            }
+            *done = last;
+            Some(name)
+        })
+        .collect();
+    if path.is_empty() {
+        "<message>".to_string()
+    } else {
+        path.join("::")
+    }
+}
+
+// === Logging ===
+
+/// Logs every message and command at debug level.
+struct LogMiddleware;
+
+impl Middleware for LogMiddleware {
+    fn on_message(&self, service: &str, message: &str, elapsed: Duration) {
+        debug!(
+            service,
+            message,
+            elapsed_us = elapsed.as_micros(),
+            "Handled message"
+        );
+    }
+
+    fn on_command(&self, name: &str, elapsed: Duration, success: bool) {
+        debug!(
+            command = name,
+            elapsed_ms = elapsed.as_millis(),
+            success,
+            "Command finished"
+        );
+    }
+}
+
+// === Metrics ===
+
+/// Durations of a kind of message or command.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timing {
+    pub count: u64,
+    pub failures: u64,
+    pub total: Duration,
+    pub max: Duration,
+}
+
+impl Timing {
+    fn record(&mut self, elapsed: Duration, success: bool) {
+        self.count += 1;
+        if !success {
+            self.failures += 1;
+        }
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+    }
+
+    pub fn average(&self) -> Duration {
+        u32::try_from(self.count)
+            .ok()
+            .filter(|count| *count > 0)
+            .map_or(Duration::ZERO, |count| self.total / count)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheStats {
+    const fn record(&mut self, hit: bool) {
+        if hit {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+    }
+
+    /// Share of lookups that were hits, from 0 to 1.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// Counters collected for this session, by kind.
+#[derive(Debug, Clone, Default)]
+pub struct MetricsSnapshot {
+    /// By command name with quoted resource names left out.
+    pub commands: BTreeMap<String, Timing>,
+    /// By service and message name.
+    pub messages: BTreeMap<String, Timing>,
+    /// By the part of the cache key before the first `/`.
+    pub cache: BTreeMap<String, CacheStats>,
+}
+
+/// Collects command latencies, message handling times and cache hit rates.
+#[derive(Default)]
+pub struct Metrics {
+    data: Mutex<MetricsSnapshot>,
+}
+
+impl Metrics {
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MetricsSnapshot> {
+        self.data.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Middleware for Metrics {
+    fn on_message(&self, service: &str, message: &str, elapsed: Duration) {
+        self.lock()
+            .messages
+            .entry(format!("{service} {message}"))
+            .or_default()
+            .record(elapsed, true);
+    }
+
+    fn on_command(&self, name: &str, elapsed: Duration, success: bool) {
+        self.lock()
+            .commands
+            .entry(anonymize(name))
+            .or_default()
+            .record(elapsed, success);
+    }
+
+    fn on_cache(&self, key: &str, hit: bool) {
+        let kind = key.split('/').next().unwrap_or(key).to_string();
+        self.lock().cache.entry(kind).or_default().record(hit);
+    }
+}
+
+/// `Deleting 'api-key'` becomes `Deleting '…'`, so commands on different
+/// resources are counted together.
+fn anonymize(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    for (i, part) in name.split('\'').enumerate() {
+        if i > 0 {
+            result.push('\'');
+        }
+        result.push_str(if i % 2 == 1 { "…" } else { part });
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    enum Inner {
+        Load,
+        Loaded(Vec<String>),
+        Created { name: String },
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    enum Outer {
+        Initialize,
+        Secret(Inner),
+    }
+
+    #[test]
+    fn test_message_name() {
+        assert_eq!(message_name(&Outer::Initialize), "Initialize");
+        assert_eq!(message_name(&Outer::Secret(Inner::Load)), "Secret::Load");
+        let loaded = Outer::Secret(Inner::Loaded(vec!["Token(secret)".to_string()]));
+        assert_eq!(message_name(&loaded), "Secret::Loaded");
+        let created = Outer::Secret(Inner::Created {
+            name: "Api".to_string(),
+        });
+        assert_eq!(message_name(&created), "Secret::Created");
+        assert_eq!(message_name(&"open"), "<message>");
+    }
+
+    #[test]
+    fn test_metrics() {
+        let metrics = Metrics::default();
+        metrics.on_command("Deleting 'a'", Duration::from_millis(10), true);
+        metrics.on_command("Deleting 'b'", Duration::from_millis(30), false);
+        metrics.on_cache("versions/a", true);
+        metrics.on_cache("versions/b", false);
+        metrics.on_cache("secrets", true);
+
+        let snapshot = metrics.snapshot();
+        let deleting = snapshot.commands["Deleting '…'"];
+        assert_eq!((deleting.count, deleting.failures), (2, 1));
+        assert_eq!(deleting.average(), Duration::from_millis(20));
+        assert_eq!(deleting.max, Duration::from_millis(30));
+        assert!((snapshot.cache["versions"].hit_rate() - 0.5).abs() < f64::EPSILON);
+        assert_eq!(snapshot.cache["secrets"].hits, 1);
+    }
+}
@@ -1,8 +1,10 @@
 //! Provider registration module.
 //!
-//! This module registers all available service providers with the registry.
+//! This module registers all available service providers with the registry:
+//! the built-in ones and those provided by plugins.
 
 pub mod gcp;
+mod plugin;
 
 use std::fmt;
 
@@ -13,6 +15,8 @@ pub enum Provider {
     Aws,
     Azure,
     Gcp,
+    /// The local vault, on this machine.
+    Local,
 }
 
 impl Provider {
@@ -22,6 +26,7 @@ impl Provider {
             Self::Aws => "AWS",
             Self::Azure => "Azure",
             Self::Gcp => "GCP",
+            Self::Local => "Local",
         }
     }
 
@@ -31,8 +36,16 @@ impl Provider {
             Self::Aws => "aws",
             Self::Azure => "azure",
             Self::Gcp => "gcp",
+            Self::Local => "local",
         }
     }
+
+    /// The provider with the identifier `id`, see [`Provider::id`].
+    pub fn from_id(id: &str) -> Option<Self> {
+        [Self::Aws, Self::Azure, Self::Gcp, Self::Local]
+            .into_iter()
+            .find(|provider| provider.id() == id)
+    }
 }
 
 impl fmt::Display for Provider {
@@ -42,6 +55,12 @@ impl fmt::Display for Provider {
 }
 
 /// Register all providers with the given registry.
-pub fn register_all(registry: &mut ServiceRegistry) {
+///
+/// Plugins talk to real accounts through their own tooling, so demo mode
+/// only registers the built-in services.
+pub fn register_all(registry: &mut ServiceRegistry, demo: bool) {
     gcp::register(registry);
+    if !demo {
+        plugin::register(registry);
+    }
 }
@@ -1,11 +1,92 @@
 mod config;
+mod console;
+mod gcloud_auth;
+mod hierarchy;
+mod kms;
+mod monitoring;
+mod org_policy;
+mod pubsub;
+mod resource_manager;
 pub mod secret_manager;
+mod service_accounts;
 
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use google_cloud_auth::credentials::{CacheableResource, Credentials};
+use http::{Extensions, HeaderMap};
+
+use crate::context::{AuthMethod, GcpContext};
+use crate::error::Error;
 pub use crate::provider::gcp::config::discover_gcloud_configs;
+pub use crate::provider::gcp::gcloud_auth::GcloudCredentials;
+pub use crate::provider::gcp::hierarchy::{HierarchyEvent, ProjectBrowserView};
+use crate::provider::gcp::kms::KmsProvider;
+pub use crate::provider::gcp::resource_manager::HierarchyNode;
+use crate::provider::gcp::resource_manager::ResourceManagerClient;
 use crate::provider::gcp::secret_manager::SecretManagerProvider;
+use crate::provider::gcp::service_accounts::ServiceAccounts;
 use crate::registry::ServiceRegistry;
+use crate::service::ResourceProvider;
 
 /// Register all GCP services with the registry.
 pub fn register(registry: &mut ServiceRegistry) {
     registry.register(SecretManagerProvider);
+    registry.register(ResourceProvider::<ServiceAccounts>::default());
+    registry.register(KmsProvider);
+}
+
+/// Check that the context's credentials work and its project is reachable.
+pub async fn validate_context(context: &GcpContext) -> Result<()> {
+    ResourceManagerClient::new(context)?.get_project().await
+}
+
+/// Organizations, folders and projects below `parent`, listed with the
+/// context's credentials.
+pub async fn list_hierarchy(
+    context: &GcpContext,
+    parent: Option<&str>,
+) -> Result<Vec<HierarchyNode>> {
+    if matches!(context.auth, AuthMethod::Demo) {
+        return Ok(hierarchy::demo_children(parent));
+    }
+    ResourceManagerClient::new(context)?
+        .list_children(parent)
+        .await
+}
+
+/// Authorization headers for calling a REST API directly.
+async fn request_headers(credentials: &Credentials) -> Result<HeaderMap> {
+    match credentials
+        .headers(Extensions::new())
+        .await
+        .map_err(|err| Error::Auth(err.to_string()))?
+    {
+        CacheableResource::New { data, .. } => Ok(data),
+        CacheableResource::NotModified => Err(eyre!("Credentials did not return request headers")),
+    }
+}
+
+/// Send a REST request with the context's credentials, turning an error
+/// status into an [`Error`] naming the `api` that failed.
+async fn send(
+    api: &str,
+    request: reqwest::RequestBuilder,
+    credentials: &Credentials,
+) -> Result<reqwest::Response> {
+    let headers = request_headers(credentials).await?;
+    let response = request
+        .headers(headers)
+        .send()
+        .await
+        .map_err(|err| Error::Network(err.to_string()))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(Error::from_http_status(
+            status.as_u16(),
+            format!("{api} request failed ({status}): {body}"),
+        )
+        .into());
+    }
+    Ok(response)
 }
@@ -33,21 +33,19 @@ pub fn discover_gcloud_configs() -> Vec<GcloudConfig> {
     let mut contexts = Vec::new();
 
     #[cfg(target_os = "macos")]
-    let config_dir = match dirs::home_dir() {
-        Some(dir) => dir.join(".config").join("gcloud").join("configurations"),
-        None => {
-            error!("Could not determine home directory for gcloud config");
-            return contexts;
-        }
+    let config_dir = if let Some(dir) = dirs::home_dir() {
+        dir.join(".config").join("gcloud").join("configurations")
+    } else {
+        error!("Could not determine home directory for gcloud config");
+        return contexts;
     };
 
     #[cfg(not(target_os = "macos"))]
-    let config_dir = match dirs::config_dir() {
-        Some(dir) => dir.join("gcloud").join("configurations"),
-        None => {
-            error!("Could not determine config directory for gcloud config");
-            return contexts;
-        }
+    let config_dir = if let Some(dir) = dirs::config_dir() {
+        dir.join("gcloud").join("configurations")
+    } else {
+        error!("Could not determine config directory for gcloud config");
+        return contexts;
     };
 
     debug!(path = %config_dir.display(), "Searching for gcloud configurations");
@@ -92,6 +90,9 @@ pub fn discover_gcloud_configs() -> Vec<GcloudConfig> {
         }
     }
 
-    info!(count = contexts.len(), "GCP configuration discovery complete");
+    info!(
+        count = contexts.len(),
+        "GCP configuration discovery complete"
+    );
     contexts
 }
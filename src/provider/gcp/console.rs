@@ -0,0 +1,26 @@
+//! Links into the Google Cloud console.
+
+const CONSOLE: &str = "https://console.cloud.google.com";
+
+/// Page of a secret, listing its versions.
+pub fn secret_url(project_id: &str, secret: &str) -> String {
+    format!("{CONSOLE}/security/secret-manager/secret/{secret}/versions?project={project_id}")
+}
+
+/// Details page of a service account, by email or unique id.
+pub fn service_account_url(project_id: &str, account: &str) -> String {
+    format!("{CONSOLE}/iam-admin/serviceaccounts/details/{account}?project={project_id}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_url() {
+        assert_eq!(
+            secret_url("my-project", "db-password"),
+            "https://console.cloud.google.com/security/secret-manager/secret/db-password/versions?project=my-project"
+        );
+    }
+}
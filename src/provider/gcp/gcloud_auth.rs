@@ -0,0 +1,87 @@
+//! Credentials of a gcloud account other than the application default one.
+//!
+//! Application Default Credentials hold a single account. Any other account
+//! the user logged into with `gcloud auth login` is reached through gcloud
+//! itself, which refreshes its tokens as needed.
+
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use google_cloud_auth::credentials::{CacheableResource, CredentialsProvider, EntityTag};
+use google_cloud_auth::errors::CredentialsError;
+use http::header::{AUTHORIZATION, HeaderValue};
+use http::{Extensions, HeaderMap};
+use tokio::process::Command as Process;
+
+/// gcloud hands out tokens valid for an hour; fetch a new one well before.
+const TOKEN_LIFETIME: Duration = Duration::from_mins(45);
+
+/// Access tokens from `gcloud auth print-access-token` for one account.
+#[derive(Debug)]
+pub struct GcloudCredentials {
+    account: String,
+    token: Mutex<Option<(String, Instant)>>,
+}
+
+impl GcloudCredentials {
+    #[must_use]
+    pub const fn new(account: String) -> Self {
+        Self {
+            account,
+            token: Mutex::new(None),
+        }
+    }
+
+    async fn token(&self) -> Result<String, CredentialsError> {
+        let cached = self
+            .token
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+            .filter(|(_, fetched)| fetched.elapsed() < TOKEN_LIFETIME);
+        if let Some((token, _)) = cached {
+            return Ok(token);
+        }
+
+        let output = Process::new("gcloud")
+            .args(["auth", "print-access-token", "--account", &self.account])
+            .output()
+            .await
+            .map_err(|err| {
+                CredentialsError::from_msg(false, format!("Failed to run gcloud: {err}"))
+            })?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(CredentialsError::from_msg(
+                false,
+                format!("No token for {}: {}", self.account, stderr.trim()),
+            ));
+        }
+        let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        *self.token.lock().unwrap_or_else(PoisonError::into_inner) =
+            Some((token.clone(), Instant::now()));
+        Ok(token)
+    }
+}
+
+impl CredentialsProvider for GcloudCredentials {
+    async fn headers(
+        &self,
+        _extensions: Extensions,
+    ) -> Result<CacheableResource<HeaderMap>, CredentialsError> {
+        let token = self.token().await?;
+        let mut value = HeaderValue::from_str(&format!("Bearer {token}"))
+            .map_err(|err| CredentialsError::from_msg(false, err.to_string()))?;
+        value.set_sensitive(true);
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, value);
+        Ok(CacheableResource::New {
+            entity_tag: EntityTag::new(),
+            data: headers,
+        })
+    }
+
+    async fn universe_domain(&self) -> Option<String> {
+        Some("googleapis.com".to_string())
+    }
+}
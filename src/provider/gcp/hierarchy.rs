@@ -0,0 +1,166 @@
+//! Browser for the organization, folder and project hierarchy.
+//!
+//! Opened from the context selector, it lists what the credentials of a
+//! context can see and turns the chosen project into a context of its own.
+//! Each level is loaded by the App through [`HierarchyEvent::Load`] when a
+//! node is first expanded and handed back with
+//! [`ProjectBrowserView::set_children`].
+
+use std::sync::Arc;
+
+use color_eyre::Result;
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::Span;
+
+use crate::Theme;
+use crate::config::{KeyResolver, NavAction};
+use crate::context::GcpContext;
+use crate::i18n::t;
+use crate::provider::gcp::resource_manager::{HierarchyNode, NodeKind};
+use crate::ui::{Component, EventResult, Keybinding, Screen, Tree, TreeEvent, TreeItem};
+
+pub enum HierarchyEvent {
+    /// The children of `parent` are needed, the top level for `None`.
+    Load(Option<String>),
+    /// A project was chosen.
+    Activate(GcpContext),
+}
+
+impl TreeItem for HierarchyNode {
+    fn key(&self) -> &str {
+        &self.name
+    }
+
+    fn is_branch(&self) -> bool {
+        self.kind != NodeKind::Project
+    }
+
+    fn label(&self) -> String {
+        self.display_name.clone()
+    }
+
+    fn render_extra(&self, theme: &Theme) -> Vec<Span<'static>> {
+        let (kind, color) = match self.kind {
+            NodeKind::Organization => (t("Organization"), theme.mauve()),
+            NodeKind::Folder => (t("Folder"), theme.blue()),
+            NodeKind::Project => (t("Project"), theme.green()),
+        };
+        let id = self.project_id.clone().unwrap_or_else(|| self.name.clone());
+        vec![
+            Span::styled(format!("  {kind}"), Style::default().fg(color)),
+            Span::styled(format!("  {id}"), Style::default().fg(theme.overlay1())),
+        ]
+    }
+}
+
+pub struct ProjectBrowserView {
+    /// Context whose credentials list the hierarchy and that new contexts
+    /// are based on.
+    base: GcpContext,
+    tree: Tree<HierarchyNode>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl ProjectBrowserView {
+    /// Start at the top level, which still has to be loaded.
+    pub fn new(base: GcpContext, resolver: Arc<KeyResolver>) -> Self {
+        Self {
+            base,
+            tree: Tree::new(resolver.clone()),
+            resolver,
+        }
+    }
+
+    pub const fn base(&self) -> &GcpContext {
+        &self.base
+    }
+
+    /// Show the children loaded for `parent`, if it is still in the tree.
+    pub fn set_children(&mut self, parent: Option<&str>, nodes: Vec<HierarchyNode>) {
+        self.tree.set_children(parent, nodes);
+    }
+
+    /// A context for `project`, with the account and credentials of the base.
+    fn context_for(&self, project: &HierarchyNode) -> GcpContext {
+        let project_id = project
+            .project_id
+            .clone()
+            .unwrap_or_else(|| project.display_name.clone());
+        GcpContext {
+            display_name: project_id.clone(),
+            project_id,
+            region: None,
+            zone: None,
+            projects: Vec::new(),
+            ..self.base.clone()
+        }
+    }
+}
+
+impl Screen for ProjectBrowserView {
+    type Output = HierarchyEvent;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        Ok(match self.tree.handle_key(key)? {
+            EventResult::Event(TreeEvent::Load(node)) => {
+                HierarchyEvent::Load(Some(node.name)).into()
+            }
+            EventResult::Event(TreeEvent::Activated(node)) => {
+                HierarchyEvent::Activate(self.context_for(&node)).into()
+            }
+            EventResult::Consumed => EventResult::Consumed,
+            EventResult::Ignored => EventResult::Ignored,
+        })
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let title = std::iter::once(t("Organizations").to_string())
+            .chain(self.tree.path())
+            .collect::<Vec<_>>()
+            .join(" / ");
+        self.tree.set_title(format!(" {title} "));
+        self.tree.render(frame, area, theme);
+    }
+
+    fn breadcrumbs(&self) -> Vec<String> {
+        let mut breadcrumbs = vec![t("Browse Projects").to_string()];
+        breadcrumbs.extend(self.tree.path());
+        breadcrumbs
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        vec![
+            Keybinding::hint(self.resolver.display_nav(NavAction::Select), "Open"),
+            Keybinding::hint(self.resolver.display_nav(NavAction::Expand), "Expand"),
+        ]
+    }
+}
+
+/// The hierarchy above the demo contexts.
+pub fn demo_children(parent: Option<&str>) -> Vec<HierarchyNode> {
+    let node = |kind, name: &str, display_name: &str| HierarchyNode {
+        kind,
+        name: name.to_string(),
+        display_name: display_name.to_string(),
+        project_id: (kind == NodeKind::Project).then(|| display_name.to_string()),
+    };
+    match parent {
+        None => vec![
+            node(NodeKind::Organization, "organizations/100", "acme.example"),
+            node(NodeKind::Project, "projects/900", "acme-sandbox"),
+        ],
+        Some("organizations/100") => vec![
+            node(NodeKind::Folder, "folders/200", "Development"),
+            node(NodeKind::Folder, "folders/300", "Production"),
+        ],
+        Some("folders/200") => vec![
+            node(NodeKind::Project, "projects/201", "acme-dev"),
+            node(NodeKind::Project, "projects/202", "acme-staging"),
+        ],
+        Some("folders/300") => vec![node(NodeKind::Project, "projects/301", "acme-prod")],
+        Some(_) => Vec::new(),
+    }
+}
@@ -0,0 +1,336 @@
+//! Cloud KMS over the v1 REST endpoint, or seeded keys for `--demo`.
+
+use color_eyre::Result;
+use futures::{StreamExt, TryStreamExt, stream};
+use google_cloud_auth::credentials::Credentials;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+
+use crate::clipboard::{base64, base64_decode};
+use crate::context::{AuthMethod, GcpContext};
+use crate::error::Error;
+use crate::provider::gcp::kms::keys::{CryptoKey, CryptoKeyVersion, KeyRing, VersionTemplate};
+use crate::provider::gcp::send;
+
+const KMS_API: &str = "https://cloudkms.googleapis.com/v1";
+
+/// Locations searched for key rings at once. Key rings are listed per
+/// location and a project sees every KMS location, most of them empty.
+const LOCATION_CONCURRENCY: usize = 8;
+
+/// Marks demo ciphertexts, which are the key name and plaintext in clear.
+const DEMO_CIPHERTEXT_PREFIX: &str = "lazycloud-demo-kms:";
+
+#[derive(Clone, Debug)]
+pub struct KmsClient {
+    backend: Backend,
+    project_id: String,
+}
+
+#[derive(Clone, Debug)]
+enum Backend {
+    Gcp {
+        http: reqwest::Client,
+        credentials: Credentials,
+    },
+    /// Seeded key rings for `--demo`; encryption only wraps the plaintext.
+    Demo,
+}
+
+impl KmsClient {
+    pub fn new(context: &GcpContext) -> Result<Self> {
+        let backend = if matches!(context.auth, AuthMethod::Demo) {
+            Backend::Demo
+        } else {
+            Backend::Gcp {
+                http: reqwest::Client::new(),
+                credentials: context.create_credentials()?,
+            }
+        };
+        Ok(Self {
+            backend,
+            project_id: context.project_id.clone(),
+        })
+    }
+
+    /// Key rings of every location, sorted by location and name.
+    pub async fn list_key_rings(&self) -> Result<Vec<KeyRing>> {
+        if matches!(self.backend, Backend::Demo) {
+            return Ok(demo_key_rings(&self.project_id));
+        }
+        let locations: Vec<Location> = self
+            .list(&format!("{KMS_API}/projects/{}/locations", self.project_id))
+            .await?;
+        let mut rings: Vec<KeyRing> = stream::iter(locations)
+            .map(|location| async move {
+                self.list::<KeyRing>(&format!("{KMS_API}/{}/keyRings", location.name))
+                    .await
+            })
+            .buffer_unordered(LOCATION_CONCURRENCY)
+            .try_concat()
+            .await?;
+        rings.sort_by(|a, b| (a.location(), a.id()).cmp(&(b.location(), b.id())));
+        Ok(rings)
+    }
+
+    pub async fn list_crypto_keys(&self, ring: &KeyRing) -> Result<Vec<CryptoKey>> {
+        if matches!(self.backend, Backend::Demo) {
+            return Ok(demo_crypto_keys(ring));
+        }
+        self.list(&format!("{KMS_API}/{}/cryptoKeys", ring.name))
+            .await
+    }
+
+    /// Versions of `key`, newest first.
+    pub async fn list_versions(&self, key: &CryptoKey) -> Result<Vec<CryptoKeyVersion>> {
+        let mut versions = if matches!(self.backend, Backend::Demo) {
+            demo_versions(key)
+        } else {
+            self.list(&format!("{KMS_API}/{}/cryptoKeyVersions", key.name))
+                .await?
+        };
+        versions.sort_by_key(|version| std::cmp::Reverse(version.number()));
+        Ok(versions)
+    }
+
+    /// `plaintext` encrypted with the primary version of `key`, as base64.
+    pub async fn encrypt(&self, key: &CryptoKey, plaintext: &[u8]) -> Result<String> {
+        let (http, credentials) = match &self.backend {
+            Backend::Gcp { http, credentials } => (http, credentials),
+            Backend::Demo => {
+                let mut data = format!("{DEMO_CIPHERTEXT_PREFIX}{}\n", key.name).into_bytes();
+                data.extend_from_slice(plaintext);
+                return Ok(base64(&data));
+            }
+        };
+        let request = http
+            .post(format!("{KMS_API}/{}:encrypt", key.name))
+            .json(&json!({ "plaintext": base64(plaintext) }));
+        let response: EncryptResponse = send("KMS", request, credentials).await?.json().await?;
+        Ok(response.ciphertext)
+    }
+
+    /// The plaintext of the base64 `ciphertext`, which must have been
+    /// encrypted with a version of `key`.
+    pub async fn decrypt(&self, key: &CryptoKey, ciphertext: &str) -> Result<Vec<u8>> {
+        let (http, credentials) = match &self.backend {
+            Backend::Gcp { http, credentials } => (http, credentials),
+            Backend::Demo => return demo_decrypt(key, ciphertext),
+        };
+        let request = http
+            .post(format!("{KMS_API}/{}:decrypt", key.name))
+            .json(&json!({ "ciphertext": ciphertext }));
+        let response: DecryptResponse = send("KMS", request, credentials).await?.json().await?;
+        base64_decode(&response.plaintext)
+            .ok_or_else(|| Error::Internal("KMS returned an invalid plaintext".to_string()).into())
+    }
+
+    /// Every item of a paged list.
+    async fn list<T: DeserializeOwned>(&self, url: &str) -> Result<Vec<T>> {
+        let Backend::Gcp { http, credentials } = &self.backend else {
+            return Ok(Vec::new());
+        };
+        let mut items = Vec::new();
+        let mut page_token = String::new();
+        loop {
+            let request = http
+                .get(url)
+                .query(&[("pageSize", "1000"), ("pageToken", &page_token)]);
+            let page: Page<T> = send("KMS", request, credentials).await?.json().await?;
+            items.extend(page.items);
+            match page.next_page_token {
+                Some(token) if !token.is_empty() => page_token = token,
+                _ => break,
+            }
+        }
+        Ok(items)
+    }
+}
+
+/// A page of any KMS list, whose items are named after what is listed.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Page<T> {
+    #[serde(
+        default = "Vec::new",
+        alias = "locations",
+        alias = "keyRings",
+        alias = "cryptoKeys",
+        alias = "cryptoKeyVersions"
+    )]
+    items: Vec<T>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Location {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct EncryptResponse {
+    ciphertext: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DecryptResponse {
+    /// Left out when the plaintext is empty.
+    #[serde(default)]
+    plaintext: String,
+}
+
+// === Demo ===
+
+fn demo_key_rings(project_id: &str) -> Vec<KeyRing> {
+    let ring = |location: &str, id: &str, created: &str| KeyRing {
+        name: format!("projects/{project_id}/locations/{location}/keyRings/{id}"),
+        create_time: created.to_string(),
+    };
+    vec![
+        ring("europe-west1", "app-secrets", "2024-02-12T08:30:00Z"),
+        ring("europe-west1", "backups", "2024-06-03T14:10:00Z"),
+        ring("global", "signing", "2023-11-20T10:00:00Z"),
+    ]
+}
+
+fn demo_crypto_keys(ring: &KeyRing) -> Vec<CryptoKey> {
+    let key = |id: &str, purpose: &str, algorithm: &str, rotation: Option<(&str, &str)>| {
+        let name = format!("{}/cryptoKeys/{id}", ring.name);
+        let primary = (purpose == "ENCRYPT_DECRYPT").then(|| CryptoKeyVersion {
+            name: format!("{name}/cryptoKeyVersions/{}", demo_version_count(id)),
+            state: "ENABLED".to_string(),
+            algorithm: algorithm.to_string(),
+            protection_level: "SOFTWARE".to_string(),
+            create_time: "2026-08-01T09:00:00Z".to_string(),
+            destroy_time: None,
+        });
+        CryptoKey {
+            name,
+            purpose: purpose.to_string(),
+            create_time: ring.create_time.clone(),
+            rotation_period: rotation.map(|(period, _)| period.to_string()),
+            next_rotation_time: rotation.map(|(_, next)| next.to_string()),
+            primary,
+            version_template: VersionTemplate {
+                algorithm: algorithm.to_string(),
+                protection_level: "SOFTWARE".to_string(),
+            },
+        }
+    };
+    match ring.id() {
+        "app-secrets" => vec![
+            key(
+                "secret-manager-cmek",
+                "ENCRYPT_DECRYPT",
+                "GOOGLE_SYMMETRIC_ENCRYPTION",
+                Some(("7776000s", "2026-10-30T09:00:00Z")),
+            ),
+            key(
+                "config-encryption",
+                "ENCRYPT_DECRYPT",
+                "GOOGLE_SYMMETRIC_ENCRYPTION",
+                None,
+            ),
+        ],
+        "backups" => vec![key(
+            "archive",
+            "ENCRYPT_DECRYPT",
+            "GOOGLE_SYMMETRIC_ENCRYPTION",
+            Some(("31536000s", "2027-06-03T14:10:00Z")),
+        )],
+        "signing" => vec![key(
+            "release-signing",
+            "ASYMMETRIC_SIGN",
+            "EC_SIGN_P256_SHA256",
+            None,
+        )],
+        _ => Vec::new(),
+    }
+}
+
+/// Versions the demo key `id` has, the highest being the primary.
+fn demo_version_count(id: &str) -> u32 {
+    match id {
+        "secret-manager-cmek" => 4,
+        "archive" => 2,
+        _ => 1,
+    }
+}
+
+fn demo_versions(key: &CryptoKey) -> Vec<CryptoKeyVersion> {
+    let count = demo_version_count(key.id());
+    (1..=count)
+        .map(|number| {
+            let (state, destroy_time) = match count - number {
+                0 | 1 => ("ENABLED", None),
+                2 => ("DISABLED", None),
+                _ => ("DESTROYED", Some("2026-03-02T09:00:00Z".to_string())),
+            };
+            CryptoKeyVersion {
+                name: format!("{}/cryptoKeyVersions/{number}", key.name),
+                state: state.to_string(),
+                algorithm: key.version_template.algorithm.clone(),
+                protection_level: key.version_template.protection_level.clone(),
+                create_time: format!("2026-0{}-01T09:00:00Z", number.min(8)),
+                destroy_time,
+            }
+        })
+        .collect()
+}
+
+/// The plaintext of a demo ciphertext, refused like KMS does if it is not
+/// one of `key`.
+fn demo_decrypt(key: &CryptoKey, ciphertext: &str) -> Result<Vec<u8>> {
+    let invalid = || Error::Internal("Decryption failed: the ciphertext is invalid.".to_string());
+    let data = base64_decode(ciphertext).ok_or_else(invalid)?;
+    let header = format!("{DEMO_CIPHERTEXT_PREFIX}{}\n", key.name);
+    data.strip_prefix(header.as_bytes())
+        .map(<[u8]>::to_vec)
+        .ok_or_else(|| invalid().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pages() {
+        let json = r#"{"keyRings": [{"name": "projects/p/locations/global/keyRings/a",
+            "createTime": "2024-01-01T00:00:00Z"}], "nextPageToken": "next", "totalSize": 2}"#;
+        let page: Page<KeyRing> = serde_json::from_str(json).unwrap();
+        assert_eq!(page.items[0].id(), "a");
+        assert_eq!(page.next_page_token.as_deref(), Some("next"));
+
+        let empty: Page<CryptoKey> = serde_json::from_str(r#"{"totalSize": 0}"#).unwrap();
+        assert!(empty.items.is_empty());
+    }
+
+    #[test]
+    fn test_demo_round_trip() {
+        let client = KmsClient {
+            backend: Backend::Demo,
+            project_id: "demo".to_string(),
+        };
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let rings = client.list_key_rings().await.unwrap();
+            let keys = client.list_crypto_keys(&rings[0]).await.unwrap();
+            let (cmek, other) = (&keys[0], &keys[1]);
+
+            let ciphertext = client.encrypt(cmek, b"db-password").await.unwrap();
+            assert_eq!(
+                client.decrypt(cmek, &ciphertext).await.unwrap(),
+                b"db-password"
+            );
+            assert!(client.decrypt(other, &ciphertext).await.is_err());
+            assert!(client.decrypt(cmek, "not base64!").await.is_err());
+
+            let versions = client.list_versions(cmek).await.unwrap();
+            assert_eq!(versions[0].number(), 4);
+            assert_eq!(versions.last().unwrap().state, "DESTROYED");
+        });
+    }
+}
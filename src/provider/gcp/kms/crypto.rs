@@ -0,0 +1,459 @@
+//! Encrypting and decrypting small payloads with a symmetric key, e.g. to
+//! check which key a base64 blob from a config file or a CMEK-protected
+//! export was encrypted with.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use color_eyre::eyre::bail;
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::Theme;
+use crate::app::AppMessage;
+use crate::clipboard::{base64, base64_decode};
+use crate::commands::{Command, CopyToClipboardCmd};
+use crate::config::{DialogAction, KeyResolver, PayloadAction};
+use crate::provider::gcp::kms::client::KmsClient;
+use crate::provider::gcp::kms::keys::CryptoKey;
+use crate::provider::gcp::kms::service::{Kms, KmsMsg, report};
+use crate::service::ServiceMsg;
+use crate::ui::{Component, EventResult, Modal, Result, TextInput, TextInputEvent};
+
+/// Cache key of the plaintext on display, masked in the log while shown.
+const PLAINTEXT_CACHE_KEY: &str = "plaintext";
+
+/// Largest plaintext or ciphertext KMS encrypts or decrypts directly.
+const MAX_INPUT_BYTES: usize = 64 * 1024;
+
+// === Models ===
+
+/// A ciphertext as typed or pasted, without the line breaks and spaces
+/// tools wrap base64 in.
+fn normalize_ciphertext(text: &str) -> String {
+    text.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+/// Refuse inputs over the size KMS accepts, before sending them.
+fn check_size(bytes: usize) -> std::result::Result<(), String> {
+    if bytes > MAX_INPUT_BYTES {
+        return Err(format!("{bytes} bytes, KMS takes at most 64 KiB"));
+    }
+    Ok(())
+}
+
+/// A decrypted plaintext as shown: the text, or base64 if it is binary.
+fn plaintext_display(plaintext: Vec<u8>) -> (String, bool) {
+    match String::from_utf8(plaintext) {
+        Ok(text) => (text, false),
+        Err(err) => (base64(err.as_bytes()), true),
+    }
+}
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum CryptoMsg {
+    StartEncrypt(CryptoKey),
+    StartDecrypt(CryptoKey),
+    Encrypt {
+        key: CryptoKey,
+        plaintext: String,
+    },
+    Decrypt {
+        key: CryptoKey,
+        ciphertext: String,
+    },
+    Encrypted {
+        key: CryptoKey,
+        ciphertext: String,
+    },
+    Decrypted {
+        key: CryptoKey,
+        plaintext: Vec<u8>,
+    },
+    /// Copy the result on display.
+    Copy {
+        text: String,
+        label: String,
+    },
+    /// Close the result, forgetting a plaintext.
+    Close,
+}
+
+impl From<CryptoMsg> for KmsMsg {
+    fn from(msg: CryptoMsg) -> Self {
+        Self::Crypto(msg)
+    }
+}
+
+impl From<CryptoMsg> for EventResult<KmsMsg> {
+    fn from(msg: CryptoMsg) -> Self {
+        Self::Event(KmsMsg::Crypto(msg))
+    }
+}
+
+// === Dialogs ===
+
+/// Asks for the plaintext to encrypt or the base64 ciphertext to decrypt.
+pub struct CryptoInputDialog {
+    key: CryptoKey,
+    decrypt: bool,
+    input: TextInput,
+}
+
+impl CryptoInputDialog {
+    pub fn encrypt(key: CryptoKey) -> Self {
+        let input = TextInput::new(format!("Plaintext to encrypt with '{}'", key.id()))
+            .with_placeholder("Text, at most 64 KiB")
+            .with_validator(|value| check_size(value.len()));
+        Self {
+            key,
+            decrypt: false,
+            input,
+        }
+    }
+
+    pub fn decrypt(key: CryptoKey) -> Self {
+        let input = TextInput::new(format!("Ciphertext to decrypt with '{}'", key.id()))
+            .with_placeholder("Base64, e.g. CiQA...")
+            .with_validator(|value| {
+                let ciphertext = normalize_ciphertext(value);
+                match base64_decode(&ciphertext) {
+                    Some(bytes) if !ciphertext.is_empty() => check_size(bytes.len()),
+                    _ => Err("Paste the ciphertext as base64".to_string()),
+                }
+            });
+        Self {
+            key,
+            decrypt: true,
+            input,
+        }
+    }
+}
+
+impl Modal for CryptoInputDialog {
+    type Output = KmsMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        Ok(match self.input.handle_key(key)? {
+            EventResult::Event(TextInputEvent::Submitted(value)) => {
+                let key = self.key.clone();
+                if self.decrypt {
+                    CryptoMsg::Decrypt {
+                        key,
+                        ciphertext: normalize_ciphertext(&value),
+                    }
+                    .into()
+                } else {
+                    CryptoMsg::Encrypt {
+                        key,
+                        plaintext: value,
+                    }
+                    .into()
+                }
+            }
+            EventResult::Event(TextInputEvent::Cancelled) => KmsMsg::DialogCancelled.into(),
+            _ => EventResult::Consumed,
+        })
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.input.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.input.render(frame, area, theme);
+    }
+}
+
+/// The ciphertext or plaintext a key produced, with a key to copy it.
+pub struct CryptoResultDialog {
+    title: String,
+    note: Option<&'static str>,
+    text: String,
+    label: String,
+    resolver: Arc<KeyResolver>,
+}
+
+impl Modal for CryptoResultDialog {
+    type Output = KmsMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if self.resolver.matches_payload(&key, PayloadAction::Copy) {
+            return Ok(CryptoMsg::Copy {
+                text: self.text.clone(),
+                label: self.label.clone(),
+            }
+            .into());
+        }
+        if self.resolver.matches_dialog(&key, DialogAction::Dismiss) {
+            return Ok(CryptoMsg::Close.into());
+        }
+        Ok(EventResult::Consumed)
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = area.centered(Constraint::Percentage(70), Constraint::Percentage(50));
+        frame.render_widget(Clear, popup_area);
+
+        let key_style = Style::default()
+            .fg(theme.peach())
+            .add_modifier(Modifier::BOLD);
+        let hint_style = Style::default().fg(theme.overlay1());
+
+        let mut lines = Vec::new();
+        if let Some(note) = self.note {
+            lines.push(Line::from(Span::styled(note, hint_style)));
+            lines.push(Line::from(""));
+        }
+        lines.extend(self.text.lines().map(|line| {
+            Line::from(Span::styled(
+                line.to_string(),
+                Style::default().fg(theme.text()),
+            ))
+        }));
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("[{}] ", self.resolver.display_payload(PayloadAction::Copy)),
+                key_style,
+            ),
+            Span::styled("Copy  ", hint_style),
+            Span::styled(
+                format!("[{}] ", self.resolver.display_dialog(DialogAction::Dismiss)),
+                key_style,
+            ),
+            Span::styled("Close", hint_style),
+        ]));
+
+        let block = Block::default()
+            .title(format!(" {} ", self.title))
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.lavender()))
+            .style(Style::default().bg(theme.base()));
+
+        frame.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .wrap(Wrap { trim: false }),
+            popup_area,
+        );
+    }
+}
+
+// === Update Logic ===
+
+pub(super) fn update(state: &mut Kms, msg: CryptoMsg) -> color_eyre::Result<ServiceMsg> {
+    match msg {
+        CryptoMsg::StartEncrypt(key) => {
+            ensure_symmetric(&key)?;
+            state.display_overlay(CryptoInputDialog::encrypt(key));
+            Ok(ServiceMsg::Idle)
+        }
+
+        CryptoMsg::StartDecrypt(key) => {
+            ensure_symmetric(&key)?;
+            state.display_overlay(CryptoInputDialog::decrypt(key));
+            Ok(ServiceMsg::Idle)
+        }
+
+        CryptoMsg::Encrypt { key, plaintext } => {
+            state.close_overlay();
+            state.display_loading_spinner("Encrypting...");
+            Ok(EncryptCmd {
+                client: state.get_client()?,
+                key,
+                plaintext,
+                tx: state.get_msg_sender(),
+            }
+            .into())
+        }
+
+        CryptoMsg::Decrypt { key, ciphertext } => {
+            state.close_overlay();
+            state.display_loading_spinner("Decrypting...");
+            Ok(DecryptCmd {
+                client: state.get_client()?,
+                key,
+                ciphertext,
+                tx: state.get_msg_sender(),
+            }
+            .into())
+        }
+
+        CryptoMsg::Encrypted { key, ciphertext } => {
+            state.hide_loading_spinner();
+            state.display_overlay(CryptoResultDialog {
+                title: format!("Encrypted with '{}'", key.id()),
+                note: None,
+                text: ciphertext,
+                label: format!("ciphertext from '{}'", key.id()),
+                resolver: state.get_resolver(),
+            });
+            Ok(ServiceMsg::Idle)
+        }
+
+        CryptoMsg::Decrypted { key, plaintext } => {
+            state.hide_loading_spinner();
+            let (text, binary) = plaintext_display(plaintext);
+            state.cache_sensitive(PLAINTEXT_CACHE_KEY, (), &text);
+            state.display_overlay(CryptoResultDialog {
+                title: format!("Decrypted with '{}'", key.id()),
+                note: binary.then_some("Binary plaintext, shown as base64"),
+                text,
+                label: format!("plaintext from '{}'", key.id()),
+                resolver: state.get_resolver(),
+            });
+            Ok(ServiceMsg::Idle)
+        }
+
+        CryptoMsg::Copy { text, label } => Ok(CopyToClipboardCmd::new(text, label).into()),
+
+        CryptoMsg::Close => {
+            state.invalidate(PLAINTEXT_CACHE_KEY);
+            state.close_overlay();
+            Ok(ServiceMsg::Idle)
+        }
+    }
+}
+
+/// Refuse keys KMS can't encrypt or decrypt with directly.
+fn ensure_symmetric(key: &CryptoKey) -> color_eyre::Result<()> {
+    if !key.is_symmetric() {
+        bail!(
+            "'{}' is a {} key, only symmetric encryption keys can encrypt and decrypt here",
+            key.id(),
+            key.purpose
+        );
+    }
+    Ok(())
+}
+
+// === Commands ===
+
+struct EncryptCmd {
+    client: KmsClient,
+    key: CryptoKey,
+    plaintext: String,
+    tx: UnboundedSender<KmsMsg>,
+}
+
+#[async_trait]
+impl Command for EncryptCmd {
+    fn name(&self) -> String {
+        format!("Encrypting with '{}'", self.key.id())
+    }
+
+    fn resource(&self) -> Option<String> {
+        Some(self.key.name.clone())
+    }
+
+    async fn execute(
+        self: Box<Self>,
+        _action_tx: UnboundedSender<AppMessage>,
+    ) -> color_eyre::Result<()> {
+        let result = self
+            .client
+            .encrypt(&self.key, self.plaintext.as_bytes())
+            .await;
+        let Self { key, tx, .. } = *self;
+        report(&tx, result, |ciphertext| CryptoMsg::Encrypted {
+            key,
+            ciphertext,
+        })
+    }
+}
+
+struct DecryptCmd {
+    client: KmsClient,
+    key: CryptoKey,
+    ciphertext: String,
+    tx: UnboundedSender<KmsMsg>,
+}
+
+#[async_trait]
+impl Command for DecryptCmd {
+    fn name(&self) -> String {
+        format!("Decrypting with '{}'", self.key.id())
+    }
+
+    fn resource(&self) -> Option<String> {
+        Some(self.key.name.clone())
+    }
+
+    async fn execute(
+        self: Box<Self>,
+        _action_tx: UnboundedSender<AppMessage>,
+    ) -> color_eyre::Result<()> {
+        let result = self.client.decrypt(&self.key, &self.ciphertext).await;
+        let Self { key, tx, .. } = *self;
+        report(&tx, result, |plaintext| CryptoMsg::Decrypted {
+            key,
+            plaintext,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    use super::*;
+    use crate::config::keybindings::KeybindingsConfig;
+
+    #[test]
+    fn test_ciphertext_input() {
+        assert_eq!(normalize_ciphertext("CiQA\n  abcd==\r\n"), "CiQAabcd==");
+        assert_eq!(
+            plaintext_display(b"hunter2".to_vec()),
+            ("hunter2".to_string(), false)
+        );
+        assert_eq!(
+            plaintext_display(vec![0xff, 0x00]),
+            (base64(&[0xff, 0x00]), true)
+        );
+    }
+
+    #[test]
+    fn test_input_size_limit() {
+        assert_eq!(check_size(MAX_INPUT_BYTES), Ok(()));
+        assert!(check_size(MAX_INPUT_BYTES + 1).is_err());
+    }
+
+    #[test]
+    fn test_result_keys() {
+        let resolver = Arc::new(KeyResolver::new(Arc::new(KeybindingsConfig::default())));
+        let mut dialog = CryptoResultDialog {
+            title: "Encrypted".to_string(),
+            note: None,
+            text: "CiQA".to_string(),
+            label: "ciphertext".to_string(),
+            resolver,
+        };
+        let mut press = |code| {
+            dialog
+                .handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+                .unwrap()
+        };
+        assert!(matches!(
+            press(KeyCode::Char('y')),
+            EventResult::Event(KmsMsg::Crypto(CryptoMsg::Copy { .. }))
+        ));
+        assert!(matches!(
+            press(KeyCode::Esc),
+            EventResult::Event(KmsMsg::Crypto(CryptoMsg::Close))
+        ));
+    }
+}
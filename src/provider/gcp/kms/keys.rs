@@ -0,0 +1,709 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::Style;
+use ratatui::widgets::Cell;
+use serde::Deserialize;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::Theme;
+use crate::app::AppMessage;
+use crate::commands::Command;
+use crate::config::{ActionContext, KeyResolver, NavAction, SearchAction};
+use crate::provider::gcp::kms::client::KmsClient;
+use crate::provider::gcp::kms::crypto::CryptoMsg;
+use crate::provider::gcp::kms::service::{
+    DECRYPT,
+    ENCRYPT,
+    Kms,
+    KmsMsg,
+    RELOAD,
+    display_action,
+    matches_action,
+    report,
+};
+use crate::search::Matcher;
+use crate::service::ServiceMsg;
+use crate::time::{self, format_timestamp};
+use crate::ui::{
+    ColumnDef,
+    Component,
+    EventResult,
+    Keybinding,
+    Result,
+    Screen,
+    Table,
+    TableEvent,
+    TableRow,
+};
+
+/// Purpose of the keys that can encrypt and decrypt data themselves.
+pub const SYMMETRIC_PURPOSE: &str = "ENCRYPT_DECRYPT";
+
+// === Models ===
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyRing {
+    /// Full resource name, `projects/*/locations/*/keyRings/*`.
+    pub name: String,
+    #[serde(default)]
+    pub create_time: String,
+}
+
+impl KeyRing {
+    pub fn id(&self) -> &str {
+        last_segment(&self.name)
+    }
+
+    pub fn location(&self) -> &str {
+        self.name
+            .split('/')
+            .skip_while(|segment| *segment != "locations")
+            .nth(1)
+            .unwrap_or_default()
+    }
+}
+
+impl TableRow for KeyRing {
+    fn columns() -> &'static [ColumnDef] {
+        static COLUMNS: &[ColumnDef] = &[
+            ColumnDef::new("Key ring", Constraint::Min(25)),
+            ColumnDef::new("Location", Constraint::Length(24)),
+            ColumnDef::new("Created", Constraint::Length(18)),
+        ];
+        COLUMNS
+    }
+
+    fn render_cells(&self, _theme: &Theme) -> Vec<Cell<'static>> {
+        vec![
+            Cell::from(self.id().to_string()),
+            Cell::from(self.location().to_string()),
+            Cell::from(format_time(&self.create_time)),
+        ]
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        let matcher = Matcher::new();
+        matcher.matches(self.id(), query) || matcher.matches(self.location(), query)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionTemplate {
+    #[serde(default)]
+    pub algorithm: String,
+    #[serde(default)]
+    pub protection_level: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CryptoKey {
+    /// Full resource name, `projects/*/locations/*/keyRings/*/cryptoKeys/*`.
+    pub name: String,
+    #[serde(default)]
+    pub purpose: String,
+    #[serde(default)]
+    pub create_time: String,
+    /// Seconds between automatic rotations, e.g. `7776000s`.
+    pub rotation_period: Option<String>,
+    pub next_rotation_time: Option<String>,
+    /// Version used to encrypt, only set for symmetric keys.
+    pub primary: Option<CryptoKeyVersion>,
+    #[serde(default)]
+    pub version_template: VersionTemplate,
+}
+
+impl CryptoKey {
+    pub fn id(&self) -> &str {
+        last_segment(&self.name)
+    }
+
+    pub fn is_symmetric(&self) -> bool {
+        self.purpose == SYMMETRIC_PURPOSE
+    }
+
+    /// How often the key rotates and when next, e.g. `every 90 days, next
+    /// 2026-10-30 09:00`, or `manual`.
+    pub fn rotation_schedule(&self) -> String {
+        let Some(period) = self.rotation_period.as_deref() else {
+            return "manual".to_string();
+        };
+        let every = format!("every {}", format_period(period));
+        match self.next_rotation_time.as_deref() {
+            Some(next) => format!("{every}, next {}", format_time(next)),
+            None => every,
+        }
+    }
+
+    fn primary_display(&self) -> String {
+        self.primary.as_ref().map_or_else(
+            || "-".to_string(),
+            |version| format!("{} ({})", version.id(), state_label(&version.state)),
+        )
+    }
+}
+
+impl TableRow for CryptoKey {
+    fn columns() -> &'static [ColumnDef] {
+        static COLUMNS: &[ColumnDef] = &[
+            ColumnDef::new("Key", Constraint::Min(25)),
+            ColumnDef::new("Purpose", Constraint::Length(20)),
+            ColumnDef::new("Primary", Constraint::Length(14)),
+            ColumnDef::new("Protection", Constraint::Length(11)),
+            ColumnDef::new("Rotation", Constraint::Min(30)),
+        ];
+        COLUMNS
+    }
+
+    fn render_cells(&self, theme: &Theme) -> Vec<Cell<'static>> {
+        let rotation_color = if self.rotation_period.is_some() {
+            theme.text()
+        } else {
+            theme.overlay1()
+        };
+        vec![
+            Cell::from(self.id().to_string()),
+            Cell::from(self.purpose.clone()),
+            Cell::from(self.primary_display()),
+            Cell::from(self.version_template.protection_level.clone()),
+            Cell::from(self.rotation_schedule()).style(Style::default().fg(rotation_color)),
+        ]
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        let matcher = Matcher::new();
+        matcher.matches(self.id(), query) || matcher.matches(&self.purpose, query)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CryptoKeyVersion {
+    pub name: String,
+    #[serde(default)]
+    pub state: String,
+    #[serde(default)]
+    pub algorithm: String,
+    #[serde(default)]
+    pub protection_level: String,
+    #[serde(default)]
+    pub create_time: String,
+    /// When a version scheduled for destruction is destroyed.
+    pub destroy_time: Option<String>,
+}
+
+impl CryptoKeyVersion {
+    pub fn id(&self) -> &str {
+        last_segment(&self.name)
+    }
+
+    /// Version number, for sorting; 0 if the name has none.
+    pub fn number(&self) -> u64 {
+        self.id().parse().unwrap_or_default()
+    }
+}
+
+impl TableRow for CryptoKeyVersion {
+    fn columns() -> &'static [ColumnDef] {
+        static COLUMNS: &[ColumnDef] = &[
+            ColumnDef::new("Version", Constraint::Length(8)),
+            ColumnDef::new("State", Constraint::Length(20)),
+            ColumnDef::new("Algorithm", Constraint::Min(28)),
+            ColumnDef::new("Protection", Constraint::Length(11)),
+            ColumnDef::new("Created", Constraint::Length(18)),
+            ColumnDef::new("Destroyed", Constraint::Length(18)),
+        ];
+        COLUMNS
+    }
+
+    fn render_cells(&self, theme: &Theme) -> Vec<Cell<'static>> {
+        let state_color = match self.state.as_str() {
+            "ENABLED" => theme.green(),
+            "DESTROYED" | "DESTROY_SCHEDULED" => theme.red(),
+            _ => theme.overlay1(),
+        };
+        vec![
+            Cell::from(self.id().to_string()),
+            Cell::from(state_label(&self.state)).style(Style::default().fg(state_color)),
+            Cell::from(self.algorithm.clone()),
+            Cell::from(self.protection_level.clone()),
+            Cell::from(format_time(&self.create_time)),
+            Cell::from(
+                self.destroy_time
+                    .as_deref()
+                    .map(format_time)
+                    .unwrap_or_default(),
+            ),
+        ]
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        let matcher = Matcher::new();
+        matcher.matches(self.id(), query)
+            || matcher.matches(&self.state, query)
+            || matcher.matches(&self.algorithm, query)
+    }
+}
+
+fn last_segment(name: &str) -> &str {
+    name.rsplit('/').next().unwrap_or(name)
+}
+
+/// `DESTROY_SCHEDULED` as `Destroy scheduled`.
+fn state_label(state: &str) -> String {
+    let lower = state.replace('_', " ").to_lowercase();
+    let mut chars = lower.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+/// An RFC 3339 timestamp in the display timezone, or as given if it is not
+/// one.
+fn format_time(time: &str) -> String {
+    DateTime::parse_from_rfc3339(time).map_or_else(
+        |_| time.to_string(),
+        |at| format_timestamp(at.with_timezone(&Utc)),
+    )
+}
+
+/// A protobuf duration such as `7776000s` in the largest whole unit, e.g.
+/// `90 days`.
+fn format_period(period: &str) -> String {
+    // Fractions of a second don't matter for rotation
+    let Some(seconds) = period
+        .strip_suffix('s')
+        .and_then(|seconds| seconds.split('.').next())
+        .and_then(|seconds| seconds.parse::<u64>().ok())
+    else {
+        return period.to_string();
+    };
+    time::format_period(seconds)
+}
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum KeysMsg {
+    LoadRings,
+    RingsLoaded(Vec<KeyRing>),
+    /// Show the keys of a ring, replacing its key list when reloading.
+    OpenRing(KeyRing),
+    KeysLoaded {
+        ring: KeyRing,
+        keys: Vec<CryptoKey>,
+    },
+    OpenKey(CryptoKey),
+    VersionsLoaded {
+        key: CryptoKey,
+        versions: Vec<CryptoKeyVersion>,
+    },
+}
+
+impl From<KeysMsg> for KmsMsg {
+    fn from(msg: KeysMsg) -> Self {
+        Self::Keys(msg)
+    }
+}
+
+impl From<KeysMsg> for EventResult<KmsMsg> {
+    fn from(msg: KeysMsg) -> Self {
+        Self::Event(KmsMsg::Keys(msg))
+    }
+}
+
+// === Screens ===
+
+pub struct KeyRingsScreen {
+    table: Table<KeyRing>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl KeyRingsScreen {
+    pub fn new(rings: Vec<KeyRing>, resolver: Arc<KeyResolver>) -> Self {
+        Self {
+            table: Table::new(rings, resolver.clone()).with_title(" Key Rings "),
+            resolver,
+        }
+    }
+}
+
+impl Screen for KeyRingsScreen {
+    type Output = KmsMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        let result = self.table.handle_key(key)?;
+        if let EventResult::Event(TableEvent::Activated(ring)) = result {
+            return Ok(KeysMsg::OpenRing(ring).into());
+        }
+        if result.is_consumed() {
+            return Ok(EventResult::Consumed);
+        }
+
+        if matches_action(&self.resolver, &key, RELOAD) {
+            return Ok(KeysMsg::LoadRings.into());
+        }
+
+        Ok(EventResult::Ignored)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.table.render(frame, area, theme);
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        vec![
+            Keybinding::hint(self.resolver.display_nav(NavAction::Select), "Keys"),
+            Keybinding::hint(self.resolver.display_search(SearchAction::Toggle), "Search"),
+            Keybinding::new(display_action(&self.resolver, RELOAD), "Reload"),
+        ]
+    }
+
+    fn action_context(&self) -> ActionContext {
+        let ctx = ActionContext::screen("key_rings");
+        match self.table.selected_item() {
+            Some(ring) => ctx
+                .with_var("name", ring.name.clone())
+                .with_var("location", ring.location()),
+            None => ctx,
+        }
+    }
+}
+
+pub struct CryptoKeysScreen {
+    ring: KeyRing,
+    table: Table<CryptoKey>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl CryptoKeysScreen {
+    pub fn new(ring: KeyRing, keys: Vec<CryptoKey>, resolver: Arc<KeyResolver>) -> Self {
+        let title = format!(" {} - Keys ", ring.id());
+        Self {
+            ring,
+            table: Table::new(keys, resolver.clone()).with_title(title),
+            resolver,
+        }
+    }
+}
+
+impl Screen for CryptoKeysScreen {
+    type Output = KmsMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        let result = self.table.handle_key(key)?;
+        if let EventResult::Event(TableEvent::Activated(crypto_key)) = result {
+            return Ok(KeysMsg::OpenKey(crypto_key).into());
+        }
+        if result.is_consumed() {
+            return Ok(EventResult::Consumed);
+        }
+
+        if matches_action(&self.resolver, &key, RELOAD) {
+            return Ok(KeysMsg::OpenRing(self.ring.clone()).into());
+        }
+        if let Some(msg) = crypto_action(&self.resolver, &key, self.table.selected_item()) {
+            return Ok(msg.into());
+        }
+
+        Ok(EventResult::Ignored)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.table.render(frame, area, theme);
+    }
+
+    fn breadcrumbs(&self) -> Vec<String> {
+        vec![self.ring.id().to_string()]
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        vec![
+            Keybinding::hint(self.resolver.display_nav(NavAction::Select), "Versions"),
+            Keybinding::hint(display_action(&self.resolver, ENCRYPT), "Encrypt"),
+            Keybinding::hint(display_action(&self.resolver, DECRYPT), "Decrypt"),
+            Keybinding::hint(self.resolver.display_search(SearchAction::Toggle), "Search"),
+            Keybinding::new(display_action(&self.resolver, RELOAD), "Reload"),
+        ]
+    }
+
+    fn action_context(&self) -> ActionContext {
+        let ctx = ActionContext::screen("keys").with_var("key_ring", self.ring.name.clone());
+        match self.table.selected_item() {
+            Some(key) => ctx.with_var("name", key.name.clone()),
+            None => ctx,
+        }
+    }
+}
+
+pub struct KeyVersionsScreen {
+    key: CryptoKey,
+    table: Table<CryptoKeyVersion>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl KeyVersionsScreen {
+    pub fn new(
+        key: CryptoKey,
+        versions: Vec<CryptoKeyVersion>,
+        resolver: Arc<KeyResolver>,
+    ) -> Self {
+        let title = format!(
+            " {} - Versions - Rotation {} ",
+            key.id(),
+            key.rotation_schedule()
+        );
+        Self {
+            key,
+            table: Table::new(versions, resolver.clone()).with_title(title),
+            resolver,
+        }
+    }
+}
+
+impl Screen for KeyVersionsScreen {
+    type Output = KmsMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if self.table.handle_key(key)?.is_consumed() {
+            return Ok(EventResult::Consumed);
+        }
+
+        if matches_action(&self.resolver, &key, RELOAD) {
+            return Ok(KeysMsg::OpenKey(self.key.clone()).into());
+        }
+        if let Some(msg) = crypto_action(&self.resolver, &key, Some(&self.key)) {
+            return Ok(msg.into());
+        }
+
+        Ok(EventResult::Ignored)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.table.render(frame, area, theme);
+    }
+
+    fn breadcrumbs(&self) -> Vec<String> {
+        vec![self.key.id().to_string()]
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        vec![
+            Keybinding::hint(display_action(&self.resolver, ENCRYPT), "Encrypt"),
+            Keybinding::hint(display_action(&self.resolver, DECRYPT), "Decrypt"),
+            Keybinding::hint(self.resolver.display_search(SearchAction::Toggle), "Search"),
+            Keybinding::new(display_action(&self.resolver, RELOAD), "Reload"),
+        ]
+    }
+
+    fn action_context(&self) -> ActionContext {
+        ActionContext::screen("versions").with_var("name", self.key.name.clone())
+    }
+}
+
+/// The encrypt or decrypt dialog for `crypto_key`, if `key` opens one.
+fn crypto_action(
+    resolver: &KeyResolver,
+    key: &KeyEvent,
+    crypto_key: Option<&CryptoKey>,
+) -> Option<CryptoMsg> {
+    let crypto_key = crypto_key?.clone();
+    if matches_action(resolver, key, ENCRYPT) {
+        return Some(CryptoMsg::StartEncrypt(crypto_key));
+    }
+    if matches_action(resolver, key, DECRYPT) {
+        return Some(CryptoMsg::StartDecrypt(crypto_key));
+    }
+    None
+}
+
+// === Update Logic ===
+
+pub(super) fn update(state: &mut Kms, msg: KeysMsg) -> color_eyre::Result<ServiceMsg> {
+    let resolver = state.get_resolver();
+    match msg {
+        KeysMsg::LoadRings => {
+            state.display_loading_spinner("Loading key rings...");
+            Ok(LoadKeyRingsCmd {
+                client: state.get_client()?,
+                tx: state.get_msg_sender(),
+            }
+            .into())
+        }
+
+        KeysMsg::RingsLoaded(rings) => {
+            state.hide_loading_spinner();
+            state.pop_to_root();
+            state.push_view(KeyRingsScreen::new(rings, resolver));
+            Ok(ServiceMsg::Idle)
+        }
+
+        KeysMsg::OpenRing(ring) => {
+            state.display_loading_spinner("Loading keys...");
+            Ok(LoadCryptoKeysCmd {
+                client: state.get_client()?,
+                ring,
+                tx: state.get_msg_sender(),
+            }
+            .into())
+        }
+
+        KeysMsg::KeysLoaded { ring, keys } => {
+            state.hide_loading_spinner();
+            // Reloading the keys replaces them instead of stacking up
+            if state.current_screen_is("keys") {
+                state.pop_view();
+            }
+            state.push_view(CryptoKeysScreen::new(ring, keys, resolver));
+            Ok(ServiceMsg::Idle)
+        }
+
+        KeysMsg::OpenKey(key) => {
+            state.display_loading_spinner("Loading versions...");
+            Ok(LoadVersionsCmd {
+                client: state.get_client()?,
+                key,
+                tx: state.get_msg_sender(),
+            }
+            .into())
+        }
+
+        KeysMsg::VersionsLoaded { key, versions } => {
+            state.hide_loading_spinner();
+            if state.current_screen_is("versions") {
+                state.pop_view();
+            }
+            state.push_view(KeyVersionsScreen::new(key, versions, resolver));
+            Ok(ServiceMsg::Idle)
+        }
+    }
+}
+
+// === Commands ===
+
+struct LoadKeyRingsCmd {
+    client: KmsClient,
+    tx: UnboundedSender<KmsMsg>,
+}
+
+#[async_trait]
+impl Command for LoadKeyRingsCmd {
+    fn name(&self) -> String {
+        "Loading key rings".to_string()
+    }
+
+    async fn execute(
+        self: Box<Self>,
+        _action_tx: UnboundedSender<AppMessage>,
+    ) -> color_eyre::Result<()> {
+        let result = self.client.list_key_rings().await;
+        report(&self.tx, result, KeysMsg::RingsLoaded)
+    }
+}
+
+struct LoadCryptoKeysCmd {
+    client: KmsClient,
+    ring: KeyRing,
+    tx: UnboundedSender<KmsMsg>,
+}
+
+#[async_trait]
+impl Command for LoadCryptoKeysCmd {
+    fn name(&self) -> String {
+        format!("Loading keys of '{}'", self.ring.id())
+    }
+
+    fn resource(&self) -> Option<String> {
+        Some(self.ring.name.clone())
+    }
+
+    async fn execute(
+        self: Box<Self>,
+        _action_tx: UnboundedSender<AppMessage>,
+    ) -> color_eyre::Result<()> {
+        let result = self.client.list_crypto_keys(&self.ring).await;
+        let Self { ring, tx, .. } = *self;
+        report(&tx, result, |keys| KeysMsg::KeysLoaded { ring, keys })
+    }
+}
+
+struct LoadVersionsCmd {
+    client: KmsClient,
+    key: CryptoKey,
+    tx: UnboundedSender<KmsMsg>,
+}
+
+#[async_trait]
+impl Command for LoadVersionsCmd {
+    fn name(&self) -> String {
+        format!("Loading versions of '{}'", self.key.id())
+    }
+
+    fn resource(&self) -> Option<String> {
+        Some(self.key.name.clone())
+    }
+
+    async fn execute(
+        self: Box<Self>,
+        _action_tx: UnboundedSender<AppMessage>,
+    ) -> color_eyre::Result<()> {
+        let result = self.client.list_versions(&self.key).await;
+        let Self { key, tx, .. } = *self;
+        report(&tx, result, |versions| KeysMsg::VersionsLoaded {
+            key,
+            versions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotation_schedule() {
+        assert_eq!(format_period("7776000s"), "90 days");
+        assert_eq!(format_period("86400s"), "1 day");
+        assert_eq!(format_period("90000s"), "25 hours");
+        assert_eq!(format_period("86401.5s"), "86401 seconds");
+        assert_eq!(format_period("soon"), "soon");
+
+        let mut key = CryptoKey {
+            name: "projects/p/locations/global/keyRings/r/cryptoKeys/k".to_string(),
+            purpose: SYMMETRIC_PURPOSE.to_string(),
+            create_time: String::new(),
+            rotation_period: None,
+            next_rotation_time: None,
+            primary: None,
+            version_template: VersionTemplate::default(),
+        };
+        assert_eq!(key.rotation_schedule(), "manual");
+        key.rotation_period = Some("2592000s".to_string());
+        key.next_rotation_time = Some("2030-01-31T14:30:00.123456Z".to_string());
+        assert_eq!(
+            key.rotation_schedule(),
+            "every 30 days, next 2030-01-31 14:30"
+        );
+        assert_eq!(key.id(), "k");
+        assert_eq!(state_label("DESTROY_SCHEDULED"), "Destroy scheduled");
+    }
+}
@@ -0,0 +1,224 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use color_eyre::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use futures::future::BoxFuture;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::app::AppMessage;
+use crate::commands::Command;
+use crate::config::key::Key;
+use crate::config::{AppConfig, KeyResolver};
+use crate::context::{CloudContext, GcpContext};
+use crate::provider::Provider;
+use crate::provider::gcp::kms::client::KmsClient;
+use crate::provider::gcp::kms::crypto::{self, CryptoMsg};
+use crate::provider::gcp::kms::keys::{self, KeysMsg};
+use crate::registry::{ActionDescriptor, ServiceProvider, WarmClient};
+use crate::service::{Service, ServiceMsg, ServiceShell, ServiceState};
+
+/// Names of the service's own key actions, see [`KmsProvider::actions`].
+pub(super) const RELOAD: &str = "reload";
+pub(super) const ENCRYPT: &str = "encrypt";
+pub(super) const DECRYPT: &str = "decrypt";
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum KmsMsg {
+    Initialize,
+    ClientInitialized(KmsClient),
+
+    DialogCancelled,
+    /// A request failed; the error is shown by the app.
+    Failed,
+
+    Keys(KeysMsg),
+    Crypto(CryptoMsg),
+}
+
+// === Provider ===
+
+pub struct KmsProvider;
+
+impl ServiceProvider for KmsProvider {
+    fn provider(&self) -> Provider {
+        Provider::Gcp
+    }
+
+    fn service_key(&self) -> &'static str {
+        "kms"
+    }
+
+    fn display_name(&self) -> &'static str {
+        "Key Management"
+    }
+
+    fn description(&self) -> &'static str {
+        "Browse encryption keys and their rotation, encrypt and decrypt data"
+    }
+
+    fn actions(&self) -> Vec<ActionDescriptor> {
+        let action = |name: &str, description: &str, key| ActionDescriptor {
+            name: name.to_string(),
+            description: description.to_string(),
+            default: Key::new(KeyCode::Char(key)).into(),
+        };
+        vec![
+            action(RELOAD, "Reload", 'r'),
+            action(ENCRYPT, "Encrypt", 'e'),
+            action(DECRYPT, "Decrypt", 'd'),
+        ]
+    }
+
+    fn warm_up(&self, ctx: &CloudContext) -> Option<BoxFuture<'static, Result<WarmClient>>> {
+        let CloudContext::Gcp(gcp_ctx) = ctx.clone();
+        Some(Box::pin(async move {
+            let client = KmsClient::new(&gcp_ctx)?;
+            Ok(Arc::new(client) as WarmClient)
+        }))
+    }
+
+    fn create_service(
+        &self,
+        ctx: &CloudContext,
+        config: Arc<AppConfig>,
+        resolver: Arc<KeyResolver>,
+        client: Option<WarmClient>,
+    ) -> Box<dyn Service> {
+        let CloudContext::Gcp(gcp_ctx) = ctx;
+        let client = client
+            .and_then(|client| client.downcast::<KmsClient>().ok())
+            .map(|client| (*client).clone());
+        let state = KmsState {
+            context: gcp_ctx.clone(),
+            client,
+        };
+        Box::new(Kms::new(state, config, resolver))
+    }
+}
+
+/// Whether `key` triggers the service's own `action`.
+pub(super) fn matches_action(resolver: &KeyResolver, key: &KeyEvent, action: &str) -> bool {
+    resolver.matches_service(key, &KmsProvider.action_scope(), action)
+}
+
+pub(super) fn display_action(resolver: &KeyResolver, action: &str) -> String {
+    resolver.display_service(&KmsProvider.action_scope(), action)
+}
+
+// === Service ===
+
+/// Cloud KMS on top of the generic [`ServiceShell`].
+pub type Kms = ServiceShell<KmsState, KmsMsg>;
+
+pub struct KmsState {
+    context: GcpContext,
+    client: Option<KmsClient>,
+}
+
+impl Kms {
+    pub(super) fn get_client(&self) -> Result<KmsClient> {
+        self.state()
+            .client
+            .clone()
+            .ok_or_else(|| color_eyre::eyre::eyre!("KMS client not initialized"))
+    }
+}
+
+impl ServiceState<KmsMsg> for KmsState {
+    fn name(&self) -> &'static str {
+        "Key Management"
+    }
+
+    fn service_key(&self) -> &'static str {
+        KmsProvider.service_key()
+    }
+
+    fn init(shell: &mut Kms) {
+        // Skip connecting if the client was warmed up during service selection
+        match shell.state().client.clone() {
+            Some(client) => shell.queue(KmsMsg::ClientInitialized(client)),
+            None => shell.queue(KmsMsg::Initialize),
+        }
+    }
+
+    fn update(shell: &mut Kms, msg: KmsMsg) -> Result<ServiceMsg> {
+        match msg {
+            // === Lifecycle ===
+            KmsMsg::Initialize => {
+                shell.display_loading_spinner("Initializing Key Management...");
+                Ok(InitClientCmd {
+                    context: shell.state().context.clone(),
+                    tx: shell.get_msg_sender(),
+                }
+                .into())
+            }
+
+            KmsMsg::ClientInitialized(client) => {
+                shell.state_mut().client = Some(client);
+                shell.queue(KeysMsg::LoadRings.into());
+                Ok(ServiceMsg::Idle)
+            }
+
+            // === Navigation ===
+            KmsMsg::DialogCancelled => {
+                shell.close_overlay();
+                Ok(ServiceMsg::Idle)
+            }
+
+            KmsMsg::Failed => {
+                shell.hide_loading_spinner();
+                if !shell.has_view() {
+                    return Ok(ServiceMsg::Close);
+                }
+                Ok(ServiceMsg::Idle)
+            }
+
+            // === Feature Dispatching ===
+            KmsMsg::Keys(msg) => keys::update(shell, msg),
+            KmsMsg::Crypto(msg) => crypto::update(shell, msg),
+        }
+    }
+}
+
+// === Commands ===
+
+/// Send the outcome of a request, or [`KmsMsg::Failed`] and the error.
+///
+/// The service may have been closed in the meantime, so a send that finds no
+/// receiver is not an error.
+pub(super) fn report<T, M: Into<KmsMsg>>(
+    tx: &UnboundedSender<KmsMsg>,
+    result: Result<T>,
+    msg: impl FnOnce(T) -> M,
+) -> Result<()> {
+    match result {
+        Ok(value) => {
+            let _ = tx.send(msg(value).into());
+            Ok(())
+        }
+        Err(err) => {
+            let _ = tx.send(KmsMsg::Failed);
+            Err(err)
+        }
+    }
+}
+
+struct InitClientCmd {
+    context: GcpContext,
+    tx: UnboundedSender<KmsMsg>,
+}
+
+#[async_trait]
+impl Command for InitClientCmd {
+    fn name(&self) -> String {
+        format!("Connecting to {}", self.context.display_name)
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let result = KmsClient::new(&self.context);
+        report(&self.tx, result, KmsMsg::ClientInitialized)
+    }
+}
@@ -0,0 +1,168 @@
+//! Minimal Cloud Monitoring client for metric charts.
+//!
+//! There is no generated Rust client for the Monitoring API in use, so this
+//! calls the v3 REST endpoint directly with the context's credentials.
+
+use chrono::{DateTime, TimeDelta, Utc};
+use color_eyre::Result;
+use google_cloud_auth::credentials::Credentials;
+use serde::Deserialize;
+
+use crate::context::GcpContext;
+use crate::error::Error;
+use crate::provider::gcp::request_headers;
+
+const MONITORING_API: &str = "https://monitoring.googleapis.com/v3";
+
+/// Summed metric value over one alignment period.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetricPoint {
+    pub start: DateTime<Utc>,
+    pub value: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct MonitoringClient {
+    http: reqwest::Client,
+    credentials: Credentials,
+    project_id: String,
+}
+
+impl MonitoringClient {
+    pub fn new(context: &GcpContext) -> Result<Self> {
+        Ok(Self {
+            http: reqwest::Client::new(),
+            credentials: context.create_credentials()?,
+            project_id: context.project_id.clone(),
+        })
+    }
+
+    /// Sum a counter metric matching `filter` into buckets of `period`,
+    /// covering the last `buckets` periods. Empty buckets are zero.
+    pub async fn sum_over_time(
+        &self,
+        filter: &str,
+        period: TimeDelta,
+        buckets: u32,
+    ) -> Result<Vec<MetricPoint>> {
+        let end = Utc::now();
+        let start = end - period * i32::try_from(buckets)?;
+        let period_param = format!("{}s", period.num_seconds());
+        let url = format!("{MONITORING_API}/projects/{}/timeSeries", self.project_id);
+
+        let headers = request_headers(&self.credentials).await?;
+        let response = self
+            .http
+            .get(url)
+            .headers(headers)
+            .query(&[
+                ("filter", filter),
+                ("interval.startTime", &start.to_rfc3339()),
+                ("interval.endTime", &end.to_rfc3339()),
+                ("aggregation.alignmentPeriod", &period_param),
+                ("aggregation.perSeriesAligner", "ALIGN_SUM"),
+                ("aggregation.crossSeriesReducer", "REDUCE_SUM"),
+            ])
+            .send()
+            .await
+            .map_err(|err| Error::Network(err.to_string()))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(Error::from_http_status(
+                status.as_u16(),
+                format!("Cloud Monitoring request failed ({status}): {body}"),
+            )
+            .into());
+        }
+        let response: ListTimeSeriesResponse = response.json().await?;
+
+        let mut points: Vec<MetricPoint> = (0..buckets)
+            .map(|i| MetricPoint {
+                start: start + period * i32::try_from(i).unwrap_or(i32::MAX),
+                value: 0,
+            })
+            .collect();
+        for point in response.time_series.iter().flat_map(|ts| &ts.points) {
+            let Ok(at) = DateTime::parse_from_rfc3339(&point.interval.end_time) else {
+                continue;
+            };
+            // Points are stamped with the end of their period
+            let offset = (at.with_timezone(&Utc) - start).num_seconds() - 1;
+            let Ok(index) = usize::try_from(offset / period.num_seconds()) else {
+                continue;
+            };
+            if let Some(bucket) = points.get_mut(index) {
+                bucket.value += point.value.as_u64();
+            }
+        }
+        Ok(points)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListTimeSeriesResponse {
+    #[serde(default)]
+    time_series: Vec<TimeSeries>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TimeSeries {
+    #[serde(default)]
+    points: Vec<Point>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Point {
+    interval: Interval,
+    value: TypedValue,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Interval {
+    end_time: String,
+}
+
+/// The API encodes int64 values as strings.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TypedValue {
+    int64_value: Option<String>,
+    double_value: Option<f64>,
+}
+
+impl TypedValue {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn as_u64(&self) -> u64 {
+        self.int64_value
+            .as_deref()
+            .and_then(|v| v.parse().ok())
+            .or_else(|| self.double_value.map(|v| v.max(0.0).round() as u64))
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_time_series() {
+        let json = r#"{"timeSeries": [{"points": [
+            {"interval": {"endTime": "2024-01-02T00:00:00Z"}, "value": {"int64Value": "42"}},
+            {"interval": {"endTime": "2024-01-01T00:00:00Z"}, "value": {"doubleValue": 1.6}}
+        ]}]}"#;
+        let response: ListTimeSeriesResponse = serde_json::from_str(json).unwrap();
+        let values: Vec<u64> = response.time_series[0]
+            .points
+            .iter()
+            .map(|p| p.value.as_u64())
+            .collect();
+        assert_eq!(values, vec![42, 2]);
+
+        let empty: ListTimeSeriesResponse = serde_json::from_str("{}").unwrap();
+        assert!(empty.time_series.is_empty());
+    }
+}
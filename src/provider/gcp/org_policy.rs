@@ -0,0 +1,232 @@
+//! Minimal Organization Policy client for the locations resources may be
+//! created in.
+//!
+//! Like the Monitoring client, this calls the v2 REST endpoint directly with
+//! the context's credentials. Only the effective `gcp.resourceLocations`
+//! policy is read, so choices such as replica locations can be checked
+//! before a request is rejected.
+
+use color_eyre::Result;
+use google_cloud_auth::credentials::Credentials;
+use serde::Deserialize;
+
+use crate::context::GcpContext;
+use crate::error::Error;
+use crate::provider::gcp::request_headers;
+
+const ORG_POLICY_API: &str = "https://orgpolicy.googleapis.com/v2";
+
+/// List constraint restricting where resources are created.
+const RESOURCE_LOCATIONS: &str = "gcp.resourceLocations";
+
+/// Regions in the European Union, for the `in:eu-locations` value group.
+const EU_REGIONS: &[&str] = &[
+    "europe-central2",
+    "europe-north1",
+    "europe-north2",
+    "europe-southwest1",
+    "europe-west1",
+    "europe-west3",
+    "europe-west4",
+    "europe-west8",
+    "europe-west9",
+    "europe-west10",
+    "europe-west12",
+];
+
+/// The locations the effective `gcp.resourceLocations` policy allows.
+///
+/// The default allows every location, as without a policy.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocationPolicy {
+    deny_all: bool,
+    /// Locations and value groups such as `in:europe-locations`; empty allows
+    /// any location not denied.
+    allowed: Vec<String>,
+    denied: Vec<String>,
+}
+
+impl LocationPolicy {
+    /// Why `location` may not be used, `None` if it may.
+    pub fn denial(&self, location: &str) -> Option<String> {
+        if self.deny_all {
+            return Some("all locations denied".to_string());
+        }
+        if let Some(value) = self.denied.iter().find(|value| covers(value, location)) {
+            return Some(format!("denied by {value}"));
+        }
+        if !self.allowed.is_empty() && !self.allowed.iter().any(|value| covers(value, location)) {
+            return Some("not in the allowed locations".to_string());
+        }
+        None
+    }
+
+    /// Combine the unconditional rules of a policy. Rules with a condition
+    /// depend on tags of the resource and are left out.
+    fn from_spec(spec: PolicySpec) -> Self {
+        let mut policy = Self::default();
+        let mut allow_all = false;
+        for rule in spec
+            .rules
+            .into_iter()
+            .filter(|rule| rule.condition.is_none())
+        {
+            allow_all |= rule.allow_all;
+            policy.deny_all |= rule.deny_all;
+            if let Some(values) = rule.values {
+                policy.allowed.extend(values.allowed_values);
+                policy.denied.extend(values.denied_values);
+            }
+        }
+        if allow_all {
+            policy.allowed.clear();
+        }
+        policy
+    }
+}
+
+/// Whether a policy value, a location or a value group, includes the
+/// region `location`.
+fn covers(value: &str, location: &str) -> bool {
+    let value = value.strip_prefix("is:").unwrap_or(value);
+    let Some(group) = value
+        .strip_prefix("in:")
+        .and_then(|group| group.strip_suffix("-locations"))
+    else {
+        return value == location;
+    };
+    match group {
+        "eu" => EU_REGIONS.contains(&location),
+        "northamerica" => location.starts_with("us-") || location.starts_with("northamerica-"),
+        // Continents, e.g. `in:asia-locations`, and single regions
+        group => group == location || location.starts_with(&format!("{group}-")),
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct OrgPolicyClient {
+    http: reqwest::Client,
+    credentials: Credentials,
+    project_id: String,
+}
+
+impl OrgPolicyClient {
+    pub fn new(context: &GcpContext) -> Result<Self> {
+        Ok(Self {
+            http: reqwest::Client::new(),
+            credentials: context.create_credentials()?,
+            project_id: context.project_id.clone(),
+        })
+    }
+
+    /// The policy on resource locations in effect for the project, inherited
+    /// from its folders and organization.
+    pub async fn resource_locations(&self) -> Result<LocationPolicy> {
+        let url = format!(
+            "{ORG_POLICY_API}/projects/{}/policies/{RESOURCE_LOCATIONS}:getEffectivePolicy",
+            self.project_id
+        );
+        let headers = request_headers(&self.credentials).await?;
+        let response = self
+            .http
+            .get(url)
+            .headers(headers)
+            .send()
+            .await
+            .map_err(|err| Error::Network(err.to_string()))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(Error::from_http_status(
+                status.as_u16(),
+                format!("Organization Policy request failed ({status}): {body}"),
+            )
+            .into());
+        }
+        let policy: Policy = response.json().await?;
+        Ok(policy
+            .spec
+            .map(LocationPolicy::from_spec)
+            .unwrap_or_default())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Policy {
+    spec: Option<PolicySpec>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PolicySpec {
+    #[serde(default)]
+    rules: Vec<PolicyRule>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PolicyRule {
+    values: Option<StringValues>,
+    #[serde(default)]
+    allow_all: bool,
+    #[serde(default)]
+    deny_all: bool,
+    condition: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StringValues {
+    #[serde(default)]
+    allowed_values: Vec<String>,
+    #[serde(default)]
+    denied_values: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(json: &str) -> LocationPolicy {
+        let policy: Policy = serde_json::from_str(json).unwrap();
+        policy
+            .spec
+            .map(LocationPolicy::from_spec)
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_allowed_value_groups() {
+        let policy = policy(
+            r#"{"spec": {"rules": [
+                {"values": {"allowedValues": ["in:eu-locations", "is:us-east1"]}},
+                {"values": {"deniedValues": ["in:europe-west4-locations"]}},
+                {"condition": {"expression": "tag"}, "allowAll": true}
+            ]}}"#,
+        );
+        assert_eq!(policy.denial("europe-west1"), None);
+        assert_eq!(policy.denial("us-east1"), None);
+        assert_eq!(
+            policy.denial("europe-west2").as_deref(),
+            Some("not in the allowed locations")
+        );
+        assert_eq!(
+            policy.denial("europe-west4").as_deref(),
+            Some("denied by in:europe-west4-locations")
+        );
+    }
+
+    #[test]
+    fn test_unrestricted_policies() {
+        assert_eq!(policy("{}"), LocationPolicy::default());
+        assert_eq!(
+            policy(r#"{"spec": {"rules": [{"allowAll": true}]}}"#).denial("asia-east1"),
+            None
+        );
+        assert_eq!(
+            policy(r#"{"spec": {"rules": [{"denyAll": true}]}}"#)
+                .denial("us-east1")
+                .as_deref(),
+            Some("all locations denied")
+        );
+    }
+}
@@ -0,0 +1,96 @@
+//! Minimal Pub/Sub client for picking notification topics.
+//!
+//! Only topics are listed, so like [`super::monitoring`] this calls the v1
+//! REST endpoint directly with the context's credentials.
+
+use color_eyre::Result;
+use google_cloud_auth::credentials::Credentials;
+use serde::Deserialize;
+
+use crate::context::GcpContext;
+use crate::error::Error;
+use crate::provider::gcp::request_headers;
+
+const PUBSUB_API: &str = "https://pubsub.googleapis.com/v1";
+
+#[derive(Clone, Debug)]
+pub struct PubSubClient {
+    http: reqwest::Client,
+    credentials: Credentials,
+    project_id: String,
+}
+
+impl PubSubClient {
+    pub fn new(context: &GcpContext) -> Result<Self> {
+        Ok(Self {
+            http: reqwest::Client::new(),
+            credentials: context.create_credentials()?,
+            project_id: context.project_id.clone(),
+        })
+    }
+
+    /// Full names of the topics in the project, sorted.
+    pub async fn list_topics(&self) -> Result<Vec<String>> {
+        let url = format!("{PUBSUB_API}/projects/{}/topics", self.project_id);
+        let mut topics = Vec::new();
+        let mut page_token = String::new();
+        loop {
+            let headers = request_headers(&self.credentials).await?;
+            let response = self
+                .http
+                .get(&url)
+                .headers(headers)
+                .query(&[("pageSize", "100"), ("pageToken", &page_token)])
+                .send()
+                .await
+                .map_err(|err| Error::Network(err.to_string()))?;
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                return Err(Error::from_http_status(
+                    status.as_u16(),
+                    format!("Pub/Sub request failed ({status}): {body}"),
+                )
+                .into());
+            }
+            let page: ListTopicsResponse = response.json().await?;
+            topics.extend(page.topics.into_iter().map(|topic| topic.name));
+            match page.next_page_token {
+                Some(token) if !token.is_empty() => page_token = token,
+                _ => break,
+            }
+        }
+        topics.sort();
+        Ok(topics)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListTopicsResponse {
+    #[serde(default)]
+    topics: Vec<Topic>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Topic {
+    name: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_topics() {
+        let json = r#"{"topics": [{"name": "projects/p/topics/a", "labels": {}}],
+            "nextPageToken": "next"}"#;
+        let response: ListTopicsResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.topics[0].name, "projects/p/topics/a");
+        assert_eq!(response.next_page_token.as_deref(), Some("next"));
+
+        let empty: ListTopicsResponse = serde_json::from_str("{}").unwrap();
+        assert!(empty.topics.is_empty());
+    }
+}
@@ -0,0 +1,277 @@
+//! Minimal Resource Manager client for project and IAM permission checks and
+//! for browsing the organization, folder and project hierarchy.
+//!
+//! Like the Monitoring client, this calls the REST endpoints directly with
+//! the context's credentials. Folders only exist in the v3 API, so the
+//! hierarchy is listed through it.
+
+use std::collections::HashSet;
+
+use color_eyre::Result;
+use google_cloud_auth::credentials::Credentials;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::context::GcpContext;
+use crate::error::Error;
+use crate::provider::gcp::request_headers;
+
+const RESOURCE_MANAGER_API: &str = "https://cloudresourcemanager.googleapis.com/v1";
+const RESOURCE_MANAGER_V3_API: &str = "https://cloudresourcemanager.googleapis.com/v3";
+
+/// An organization, folder or project in the resource hierarchy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HierarchyNode {
+    pub kind: NodeKind,
+    /// Resource name, e.g. `folders/123`.
+    pub name: String,
+    pub display_name: String,
+    /// Set for projects only.
+    pub project_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+    Organization,
+    Folder,
+    Project,
+}
+
+#[derive(Clone, Debug)]
+pub struct ResourceManagerClient {
+    http: reqwest::Client,
+    credentials: Credentials,
+    project_id: String,
+}
+
+impl ResourceManagerClient {
+    pub fn new(context: &GcpContext) -> Result<Self> {
+        Ok(Self {
+            http: reqwest::Client::new(),
+            credentials: context.create_credentials()?,
+            project_id: context.project_id.clone(),
+        })
+    }
+
+    /// Fetch the project, to check that it exists and the caller can see it.
+    pub async fn get_project(&self) -> Result<()> {
+        let url = format!("{RESOURCE_MANAGER_API}/projects/{}", self.project_id);
+        let headers = request_headers(&self.credentials).await?;
+        let response = self
+            .http
+            .get(url)
+            .headers(headers)
+            .send()
+            .await
+            .map_err(|err| Error::Network(err.to_string()))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(Error::from_http_status(
+                status.as_u16(),
+                format!("Resource Manager request failed ({status}): {body}"),
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// The subset of `permissions` the caller holds on the project.
+    pub async fn test_permissions(&self, permissions: &[&str]) -> Result<HashSet<String>> {
+        let url = format!(
+            "{RESOURCE_MANAGER_API}/projects/{}:testIamPermissions",
+            self.project_id
+        );
+        let headers = request_headers(&self.credentials).await?;
+        let response = self
+            .http
+            .post(url)
+            .headers(headers)
+            .json(&TestIamPermissionsRequest { permissions })
+            .send()
+            .await
+            .map_err(|err| Error::Network(err.to_string()))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(Error::from_http_status(
+                status.as_u16(),
+                format!("Resource Manager request failed ({status}): {body}"),
+            )
+            .into());
+        }
+        let response: TestIamPermissionsResponse = response.json().await?;
+        Ok(response.permissions.into_iter().collect())
+    }
+
+    /// Folders and projects directly below `parent`, such as
+    /// `organizations/123`. Without a parent, the organizations the caller
+    /// can see and the projects outside of any organization.
+    pub async fn list_children(&self, parent: Option<&str>) -> Result<Vec<HierarchyNode>> {
+        let (containers, projects) = if let Some(parent) = parent {
+            let query = [("parent", parent)];
+            let folders: Vec<V3Resource> = self.list_all("folders", &query).await?;
+            let projects: Vec<V3Resource> = self.list_all("projects", &query).await?;
+            (
+                folders
+                    .into_iter()
+                    .map(|folder| folder.into_node(NodeKind::Folder))
+                    .collect::<Vec<_>>(),
+                projects,
+            )
+        } else {
+            let organizations: Vec<V3Resource> = self.list_all("organizations:search", &[]).await?;
+            let projects: Vec<V3Resource> = self.list_all("projects:search", &[]).await?;
+            (
+                organizations
+                    .into_iter()
+                    .map(|org| org.into_node(NodeKind::Organization))
+                    .collect(),
+                // Projects in an organization are reached through it
+                projects
+                    .into_iter()
+                    .filter(|project| project.parent.is_none())
+                    .collect(),
+            )
+        };
+        let projects = projects
+            .into_iter()
+            .filter(V3Resource::is_active)
+            .map(|project| project.into_node(NodeKind::Project));
+        let mut nodes: Vec<HierarchyNode> = containers.into_iter().chain(projects).collect();
+        // Organizations and folders above projects, each by name
+        nodes.sort_by(|a, b| {
+            (a.kind == NodeKind::Project, &a.display_name)
+                .cmp(&(b.kind == NodeKind::Project, &b.display_name))
+        });
+        Ok(nodes)
+    }
+
+    /// Every page of a v3 list or search call.
+    async fn list_all<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        query: &[(&str, &str)],
+    ) -> Result<Vec<T>> {
+        let url = format!("{RESOURCE_MANAGER_V3_API}/{method}");
+        let mut items = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let headers = request_headers(&self.credentials).await?;
+            let mut request = self.http.get(&url).headers(headers).query(query);
+            if let Some(token) = &page_token {
+                request = request.query(&[("pageToken", token)]);
+            }
+            let response = request
+                .send()
+                .await
+                .map_err(|err| Error::Network(err.to_string()))?;
+            if !response.status().is_success() {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                return Err(Error::from_http_status(
+                    status.as_u16(),
+                    format!("Resource Manager request failed ({status}): {body}"),
+                )
+                .into());
+            }
+            let page: Page<T> = response.json().await?;
+            items.extend(page.items);
+            page_token = page.next_page_token.filter(|token| !token.is_empty());
+            if page_token.is_none() {
+                return Ok(items);
+            }
+        }
+    }
+}
+
+/// A page of organizations, folders or projects.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", bound = "T: DeserializeOwned")]
+struct Page<T> {
+    #[serde(
+        default,
+        alias = "organizations",
+        alias = "folders",
+        alias = "projects"
+    )]
+    items: Vec<T>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct V3Resource {
+    name: String,
+    #[serde(default)]
+    display_name: String,
+    project_id: Option<String>,
+    parent: Option<String>,
+    state: Option<String>,
+}
+
+impl V3Resource {
+    /// Projects pending deletion can no longer be used.
+    fn is_active(&self) -> bool {
+        self.state.as_deref().is_none_or(|state| state == "ACTIVE")
+    }
+
+    fn into_node(self, kind: NodeKind) -> HierarchyNode {
+        let display_name = if self.display_name.is_empty() {
+            self.project_id.clone().unwrap_or_else(|| self.name.clone())
+        } else {
+            self.display_name
+        };
+        HierarchyNode {
+            kind,
+            name: self.name,
+            display_name,
+            project_id: self.project_id,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct TestIamPermissionsRequest<'a> {
+    permissions: &'a [&'a str],
+}
+
+/// Permissions the caller lacks are left out, an empty list is omitted.
+#[derive(Debug, Deserialize)]
+struct TestIamPermissionsResponse {
+    #[serde(default)]
+    permissions: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_of_projects() {
+        let page: Page<V3Resource> = serde_json::from_str(
+            r#"{
+                "projects": [
+                    {"name": "projects/1", "projectId": "acme-dev", "displayName": "Acme Dev",
+                     "parent": "folders/2", "state": "ACTIVE"},
+                    {"name": "projects/3", "projectId": "acme-old", "state": "DELETE_REQUESTED"}
+                ],
+                "nextPageToken": "next"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(page.next_page_token.as_deref(), Some("next"));
+        let nodes: Vec<HierarchyNode> = page
+            .items
+            .into_iter()
+            .filter(V3Resource::is_active)
+            .map(|project| project.into_node(NodeKind::Project))
+            .collect();
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].display_name, "Acme Dev");
+        assert_eq!(nodes[0].project_id.as_deref(), Some("acme-dev"));
+
+        let empty: Page<V3Resource> = serde_json::from_str("{}").unwrap();
+        assert!(empty.items.is_empty());
+    }
+}
@@ -1,7 +1,30 @@
+mod access_stats;
+mod access_test;
+mod accessors;
+mod annotations;
 mod client;
+mod compare;
+mod deep_search;
+mod demo;
+mod duplicates;
+mod enrich;
+mod fan_out;
+mod kube_sync;
+mod lint;
 mod payload;
+mod permissions;
+mod promote;
+mod registry;
 mod secrets;
 mod service;
+#[cfg(test)]
+mod snapshot_tests;
+mod stale;
+mod terraform;
+mod topics;
+mod trash;
+mod undo;
+mod vault;
 mod versions;
 
 pub use service::{SecretManager, SecretManagerProvider};
@@ -0,0 +1,234 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::Theme;
+use crate::app::AppMessage;
+use crate::commands::Command;
+use crate::config::{ActionContext, KeyResolver, SecretsAction};
+use crate::provider::gcp::monitoring::MetricPoint;
+use crate::provider::gcp::secret_manager::SecretManager;
+use crate::provider::gcp::secret_manager::client::SecretManagerClient;
+use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
+use crate::service::ServiceMsg;
+use crate::ui::widgets::charts::{BarChart, Sparkline};
+use crate::ui::{EventResult, Keybinding, Result, Screen};
+
+/// Days of history shown in the chart.
+const HISTORY_DAYS: u32 = 30;
+
+// === Models ===
+
+/// Daily secret access requests in the project.
+#[derive(Debug, Clone)]
+pub struct AccessStats {
+    pub project_id: String,
+    pub daily: Vec<MetricPoint>,
+}
+
+impl AccessStats {
+    pub fn total(&self) -> u64 {
+        self.daily.iter().map(|point| point.value).sum()
+    }
+
+    /// Start of the most recent day with any access.
+    pub fn last_access(&self) -> Option<DateTime<Utc>> {
+        self.daily
+            .iter()
+            .rev()
+            .find(|point| point.value > 0)
+            .map(|point| point.start)
+    }
+}
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum AccessStatsMsg {
+    Load,
+    Loaded(AccessStats),
+}
+
+impl From<AccessStatsMsg> for SecretManagerMsg {
+    fn from(msg: AccessStatsMsg) -> Self {
+        Self::AccessStats(msg)
+    }
+}
+
+impl From<AccessStatsMsg> for EventResult<SecretManagerMsg> {
+    fn from(msg: AccessStatsMsg) -> Self {
+        Self::Event(SecretManagerMsg::AccessStats(msg))
+    }
+}
+
+// === Screens ===
+
+pub struct AccessStatsScreen {
+    stats: AccessStats,
+    resolver: Arc<KeyResolver>,
+}
+
+impl AccessStatsScreen {
+    pub const fn new(stats: AccessStats, resolver: Arc<KeyResolver>) -> Self {
+        Self { stats, resolver }
+    }
+
+    fn render_summary(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let label_style = Style::default().fg(theme.overlay1());
+        let value_style = Style::default()
+            .fg(theme.text())
+            .add_modifier(Modifier::BOLD);
+        let last_access = self.stats.last_access().map_or_else(
+            || format!("none in the last {HISTORY_DAYS} days"),
+            |day| day.format("%Y-%m-%d").to_string(),
+        );
+
+        let daily: Vec<u64> = self.stats.daily.iter().map(|point| point.value).collect();
+        let trend_width = u16::try_from(daily.len()).unwrap_or(u16::MAX);
+        let [text, trend] =
+            Layout::horizontal([Constraint::Min(0), Constraint::Length(trend_width)]).areas(area);
+        let lines = vec![
+            Line::from(vec![
+                Span::styled("Accesses:     ", label_style),
+                Span::styled(self.stats.total().to_string(), value_style),
+            ]),
+            Line::from(vec![
+                Span::styled("Last access:  ", label_style),
+                Span::styled(last_access, value_style),
+            ]),
+            Line::from(Span::styled(
+                "Cloud Monitoring reports AccessSecretVersion calls for the whole project, \
+                 not per secret.",
+                label_style,
+            )),
+        ];
+        frame.render_widget(Paragraph::new(lines), text);
+
+        // One cell per day, aligned with the accesses line
+        let trend = Rect {
+            height: trend.height.min(1),
+            ..trend
+        };
+        Sparkline::new(&daily).render(frame, trend, theme);
+    }
+
+    fn render_chart(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let bars: Vec<(String, u64)> = self
+            .stats
+            .daily
+            .iter()
+            .map(|point| (point.start.format("%d").to_string(), point.value))
+            .collect();
+        BarChart::new(&bars).render(frame, area, theme);
+    }
+}
+
+impl Screen for AccessStatsScreen {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if self.resolver.matches_secrets(&key, SecretsAction::Reload) {
+            return Ok(AccessStatsMsg::Load.into());
+        }
+        Ok(EventResult::Ignored)
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.border()))
+            .title(format!(
+                " Secret accesses in {} - last {HISTORY_DAYS} days ",
+                self.stats.project_id
+            ))
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            );
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let [summary, chart] =
+            Layout::vertical([Constraint::Length(4), Constraint::Min(0)]).areas(inner);
+        self.render_summary(frame, summary, theme);
+        self.render_chart(frame, chart, theme);
+    }
+
+    fn breadcrumbs(&self) -> Vec<String> {
+        vec!["Access stats".to_string()]
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        vec![Keybinding::new(
+            self.resolver.display_secrets(SecretsAction::Reload),
+            "Reload",
+        )]
+    }
+
+    fn action_context(&self) -> ActionContext {
+        ActionContext::screen("access_stats")
+    }
+}
+
+// === Update Logic ===
+
+pub(super) fn update(state: &mut SecretManager, msg: AccessStatsMsg) -> Result<ServiceMsg> {
+    match msg {
+        AccessStatsMsg::Load => {
+            state.display_loading_spinner("Loading access stats...");
+
+            Ok(FetchAccessStatsCmd {
+                project_id: state.get_context().project_id.clone(),
+                client: state.get_client()?,
+                tx: state.get_msg_sender(),
+            }
+            .into())
+        }
+
+        AccessStatsMsg::Loaded(loaded) => {
+            // Replace the screen when reloading
+            if state.current_screen_is("access_stats") {
+                state.pop_view();
+            }
+            state.push_view(AccessStatsScreen::new(loaded, state.get_resolver()));
+            Ok(ServiceMsg::Idle)
+        }
+    }
+}
+
+// === Commands ===
+
+struct FetchAccessStatsCmd {
+    client: SecretManagerClient,
+    project_id: String,
+    tx: UnboundedSender<SecretManagerMsg>,
+}
+
+#[async_trait]
+impl Command for FetchAccessStatsCmd {
+    fn name(&self) -> String {
+        format!("Loading secret access stats for '{}'", self.project_id)
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let daily = self.client.access_counts(HISTORY_DAYS).await?;
+        self.tx.send(
+            AccessStatsMsg::Loaded(AccessStats {
+                project_id: self.project_id,
+                daily,
+            })
+            .into(),
+        )?;
+        Ok(())
+    }
+}
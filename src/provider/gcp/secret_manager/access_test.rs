@@ -0,0 +1,204 @@
+//! Test which permissions the current credentials hold on a single secret.
+//!
+//! Unlike the project-wide pre-flight check in [`super::permissions`], this
+//! asks the secret itself, so bindings on the secret count too. It is meant
+//! for debugging a 403 on one secret.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::Theme;
+use crate::app::AppMessage;
+use crate::commands::Command;
+use crate::config::{DialogAction, KeyResolver};
+use crate::i18n::t;
+use crate::provider::gcp::secret_manager::SecretManager;
+use crate::provider::gcp::secret_manager::client::SecretManagerClient;
+use crate::provider::gcp::secret_manager::secrets::Secret;
+use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
+use crate::service::ServiceMsg;
+use crate::ui::text::{display_width, padding};
+use crate::ui::{EventResult, Modal, Result};
+
+/// Permissions tested, with what they allow.
+const TESTED: &[(&str, &str)] = &[
+    ("View metadata", "secretmanager.secrets.get"),
+    ("Access payloads", "secretmanager.versions.access"),
+    ("Update", "secretmanager.secrets.update"),
+    ("Delete", "secretmanager.secrets.delete"),
+    ("Change IAM policy", "secretmanager.secrets.setIamPolicy"),
+];
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum AccessTestMsg {
+    Run(Secret),
+    Completed {
+        secret: Secret,
+        granted: HashSet<String>,
+    },
+}
+
+impl From<AccessTestMsg> for SecretManagerMsg {
+    fn from(msg: AccessTestMsg) -> Self {
+        Self::AccessTest(msg)
+    }
+}
+
+impl From<AccessTestMsg> for EventResult<SecretManagerMsg> {
+    fn from(msg: AccessTestMsg) -> Self {
+        Self::Event(SecretManagerMsg::AccessTest(msg))
+    }
+}
+
+// === Dialogs ===
+
+/// Lists the tested permissions and whether the credentials hold them.
+pub struct AccessTestOverlay {
+    secret: Secret,
+    granted: HashSet<String>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl AccessTestOverlay {
+    pub const fn new(secret: Secret, granted: HashSet<String>, resolver: Arc<KeyResolver>) -> Self {
+        Self {
+            secret,
+            granted,
+            resolver,
+        }
+    }
+
+    fn lines(&self, theme: &Theme) -> Vec<Line<'static>> {
+        let label_style = Style::default().fg(theme.text());
+        let permission_style = Style::default().fg(theme.overlay1());
+        let label_width = TESTED
+            .iter()
+            .map(|(label, _)| display_width(t(label)))
+            .max()
+            .unwrap_or_default();
+
+        TESTED
+            .iter()
+            .map(|&(label, permission)| {
+                let (symbol, symbol_style) = if self.granted.contains(permission) {
+                    (theme.symbols.success, Style::default().fg(theme.green()))
+                } else {
+                    (theme.symbols.failure, Style::default().fg(theme.red()))
+                };
+                let label = t(label);
+                Line::from(vec![
+                    Span::styled(format!(" {symbol} "), symbol_style),
+                    Span::styled(
+                        format!("{label}{}  ", padding(label, label_width)),
+                        label_style,
+                    ),
+                    Span::styled(permission, permission_style),
+                ])
+            })
+            .collect()
+    }
+}
+
+impl Modal for AccessTestOverlay {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if self.resolver.matches_dialog(&key, DialogAction::Dismiss) {
+            return Ok(SecretManagerMsg::DialogCancelled.into());
+        }
+        Ok(EventResult::Consumed)
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let lines = self.lines(theme);
+        let height = u16::try_from(lines.len() + 2).unwrap_or(u16::MAX);
+        let popup_area = area.centered(Constraint::Percentage(70), Constraint::Length(height));
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(format!(" {} - {} ", self.secret.name, t("Test access")))
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.border()))
+            .style(Style::default().bg(theme.base()));
+        frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+    }
+}
+
+// === Update Logic ===
+
+pub(super) fn update(state: &mut SecretManager, msg: AccessTestMsg) -> Result<ServiceMsg> {
+    match msg {
+        AccessTestMsg::Run(secret) => {
+            state.display_loading_spinner("Testing access...");
+
+            Ok(TestAccessCmd {
+                client: state.get_client_for(&secret)?,
+                secret,
+                tx: state.get_msg_sender(),
+            }
+            .into())
+        }
+
+        AccessTestMsg::Completed { secret, granted } => {
+            state.hide_loading_spinner();
+            state.display_overlay(AccessTestOverlay::new(
+                secret,
+                granted,
+                state.get_resolver(),
+            ));
+            Ok(ServiceMsg::Idle)
+        }
+    }
+}
+
+// === Commands ===
+
+struct TestAccessCmd {
+    client: SecretManagerClient,
+    secret: Secret,
+    tx: UnboundedSender<SecretManagerMsg>,
+}
+
+#[async_trait]
+impl Command for TestAccessCmd {
+    fn name(&self) -> String {
+        format!("Testing access to '{}'", self.secret.name)
+    }
+
+    fn resource(&self) -> Option<String> {
+        Some(self.secret.name.clone())
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let permissions: Vec<&str> = TESTED.iter().map(|(_, permission)| *permission).collect();
+        let granted = self
+            .client
+            .test_secret_permissions(&self.secret.name, &permissions)
+            .await?;
+        self.tx.send(
+            AccessTestMsg::Completed {
+                secret: self.secret,
+                granted,
+            }
+            .into(),
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,406 @@
+//! Who can access a secret: its IAM bindings flattened into one row per
+//! member with all the roles it holds.
+//!
+//! Group membership is not resolved, so a group stands for everyone in it.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::Style;
+use ratatui::widgets::Cell;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::Theme;
+use crate::app::AppMessage;
+use crate::commands::{Command, ExportRow, OpenLinkCmd, TableExport};
+use crate::config::{ActionContext, KeyResolver, NavAction, SearchAction, SecretsAction};
+use crate::provider::gcp::secret_manager::SecretManager;
+use crate::provider::gcp::secret_manager::client::SecretManagerClient;
+use crate::provider::gcp::secret_manager::secrets::{IamPolicy, Secret, SecretsMsg};
+use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
+use crate::provider::gcp::service_accounts::ServiceAccounts;
+use crate::registry::{ResourceLink, ServiceId};
+use crate::search::Matcher;
+use crate::service::{ResourceKind, ServiceMsg};
+use crate::ui::{
+    ColumnDef,
+    Component,
+    EventResult,
+    Keybinding,
+    Result,
+    Screen,
+    Table,
+    TableEvent,
+    TableRow,
+};
+
+/// Members that grant access to anyone, signed in or not.
+const PUBLIC_MEMBERS: &[&str] = &["allUsers", "allAuthenticatedUsers"];
+
+// === Models ===
+
+/// A principal with every role it is bound to on the secret.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Accessor {
+    pub member: String,
+    pub roles: Vec<String>,
+}
+
+impl Accessor {
+    /// Whether the member covers everyone rather than a known principal.
+    pub fn is_public(&self) -> bool {
+        PUBLIC_MEMBERS.contains(&self.member.as_str())
+    }
+
+    /// Kind of principal, the prefix of the member such as `user`.
+    pub fn kind(&self) -> &str {
+        if self.is_public() {
+            return "public";
+        }
+        self.member
+            .split_once(':')
+            .map_or("other", |(kind, _)| kind)
+    }
+
+    /// The member without its kind prefix.
+    fn principal(&self) -> &str {
+        self.member
+            .split_once(':')
+            .map_or(&self.member, |(_, principal)| principal)
+    }
+
+    /// Where the member is managed, for service accounts.
+    pub fn link(&self) -> Option<ResourceLink> {
+        (self.kind() == "serviceAccount").then(|| {
+            ResourceLink::new(
+                ServiceId::gcp(ServiceAccounts::SERVICE_KEY),
+                self.principal(),
+            )
+        })
+    }
+}
+
+impl TableRow for Accessor {
+    fn columns() -> &'static [ColumnDef] {
+        static COLUMNS: &[ColumnDef] = &[
+            ColumnDef::new("Member", Constraint::Min(30)),
+            ColumnDef::new("Type", Constraint::Length(16)),
+            ColumnDef::new("Roles", Constraint::Min(30)),
+        ];
+        COLUMNS
+    }
+
+    fn render_cells(&self, theme: &Theme) -> Vec<Cell<'static>> {
+        let roles = self
+            .roles
+            .iter()
+            .map(|role| role.trim_start_matches("roles/"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let cells = vec![
+            Cell::from(self.principal().to_string()),
+            Cell::from(self.kind().to_string()),
+            Cell::from(roles),
+        ];
+        if self.is_public() {
+            let style = Style::default().fg(theme.red());
+            return cells.into_iter().map(|cell| cell.style(style)).collect();
+        }
+        cells
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        let matcher = Matcher::new();
+        matcher.matches(&self.member, query)
+            || self.roles.iter().any(|role| matcher.matches(role, query))
+    }
+
+    fn details(&self) -> Vec<(&'static str, String)> {
+        self.roles
+            .iter()
+            .enumerate()
+            .map(|(i, role)| (if i == 0 { "Roles" } else { "" }, role.clone()))
+            .collect()
+    }
+}
+
+impl ExportRow for Accessor {
+    fn export_values(&self) -> Vec<String> {
+        vec![
+            self.principal().to_string(),
+            self.kind().to_string(),
+            self.roles.join(", "),
+        ]
+    }
+}
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum AccessorsMsg {
+    Load(Secret),
+    Loaded {
+        secret: Secret,
+        policy: IamPolicy,
+    },
+    /// Switch to where the member is managed.
+    Open(ResourceLink),
+}
+
+impl From<AccessorsMsg> for SecretManagerMsg {
+    fn from(msg: AccessorsMsg) -> Self {
+        Self::Accessors(msg)
+    }
+}
+
+impl From<AccessorsMsg> for EventResult<SecretManagerMsg> {
+    fn from(msg: AccessorsMsg) -> Self {
+        Self::Event(SecretManagerMsg::Accessors(msg))
+    }
+}
+
+// === Screens ===
+
+pub struct AccessorsScreen {
+    secret: Secret,
+    table: Table<Accessor>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl AccessorsScreen {
+    pub fn new(secret: Secret, policy: &IamPolicy, resolver: Arc<KeyResolver>) -> Self {
+        let accessors = expand_bindings(policy);
+        let public = accessors.iter().filter(|a| a.is_public()).count();
+        let title = if public > 0 {
+            format!(" {} - Who can access ({public} public) ", secret.name)
+        } else {
+            format!(" {} - Who can access ", secret.name)
+        };
+        Self {
+            secret,
+            table: Table::new(accessors, resolver.clone()).with_title(title),
+            resolver,
+        }
+    }
+}
+
+impl Screen for AccessorsScreen {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        let result = self.table.handle_key(key)?;
+        if let EventResult::Event(TableEvent::Activated(accessor)) = result {
+            return Ok(accessor.link().map_or(EventResult::Consumed, |link| {
+                AccessorsMsg::Open(link).into()
+            }));
+        }
+        if result.is_consumed() {
+            return Ok(EventResult::Consumed);
+        }
+
+        if self.resolver.matches_secrets(&key, SecretsAction::Reload) {
+            return Ok(AccessorsMsg::Load(self.secret.clone()).into());
+        }
+        if self.resolver.matches_secrets(&key, SecretsAction::Export) {
+            return Ok(SecretsMsg::StartExport {
+                file_name: format!("{}-accessors.json", self.secret.name),
+                export: TableExport::of(&self.table),
+            }
+            .into());
+        }
+
+        Ok(EventResult::Ignored)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.table.render(frame, area, theme);
+    }
+
+    fn breadcrumbs(&self) -> Vec<String> {
+        vec!["Who can access".to_string()]
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        let mut keybindings = vec![
+            Keybinding::hint(self.resolver.display_search(SearchAction::Toggle), "Search"),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::Export),
+                "Export",
+            ),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::Reload),
+                "Reload",
+            ),
+        ];
+        if self
+            .table
+            .selected_item()
+            .is_some_and(|accessor| accessor.link().is_some())
+        {
+            keybindings.insert(
+                0,
+                Keybinding::hint(self.resolver.display_nav(NavAction::Select), "Open account"),
+            );
+        }
+        keybindings
+    }
+
+    fn action_context(&self) -> ActionContext {
+        let ctx = ActionContext::screen("accessors").with_var("name", &self.secret.name);
+        match self.table.selected_item() {
+            Some(accessor) => ctx.with_var("member", &accessor.member),
+            None => ctx,
+        }
+    }
+}
+
+// === Update Logic ===
+
+pub(super) fn update(state: &mut SecretManager, msg: AccessorsMsg) -> Result<ServiceMsg> {
+    match msg {
+        AccessorsMsg::Load(secret) => {
+            state.display_loading_spinner("Loading IAM policy...");
+
+            Ok(FetchAccessorsCmd {
+                client: state.get_client_for(&secret)?,
+                secret,
+                tx: state.get_msg_sender(),
+            }
+            .into())
+        }
+
+        AccessorsMsg::Loaded { secret, policy } => {
+            state.hide_loading_spinner();
+            // Replace the screen when reloading
+            if state.current_screen_is("accessors") {
+                state.pop_view();
+            }
+            state.push_view(AccessorsScreen::new(secret, &policy, state.get_resolver()));
+            Ok(ServiceMsg::Idle)
+        }
+
+        AccessorsMsg::Open(link) => Ok(OpenLinkCmd::new(link).into()),
+    }
+}
+
+// === Helper Functions ===
+
+/// One accessor per member, public members first and the rest by member.
+fn expand_bindings(policy: &IamPolicy) -> Vec<Accessor> {
+    let mut roles: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for binding in &policy.bindings {
+        for member in &binding.members {
+            let member_roles = roles.entry(member).or_default();
+            if !member_roles.contains(&binding.role) {
+                member_roles.push(binding.role.clone());
+            }
+        }
+    }
+    let mut accessors: Vec<Accessor> = roles
+        .into_iter()
+        .map(|(member, mut roles)| {
+            roles.sort();
+            Accessor {
+                member: member.to_string(),
+                roles,
+            }
+        })
+        .collect();
+    // Stable, so the members stay sorted within each group
+    accessors.sort_by_key(|accessor| !accessor.is_public());
+    accessors
+}
+
+// === Commands ===
+
+struct FetchAccessorsCmd {
+    client: SecretManagerClient,
+    secret: Secret,
+    tx: UnboundedSender<SecretManagerMsg>,
+}
+
+#[async_trait]
+impl Command for FetchAccessorsCmd {
+    fn name(&self) -> String {
+        format!("Loading who can access '{}'", self.secret.name)
+    }
+
+    fn resource(&self) -> Option<String> {
+        Some(self.secret.name.clone())
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let policy = self.client.get_iam_policy(&self.secret.name).await?;
+        self.tx.send(
+            AccessorsMsg::Loaded {
+                secret: self.secret,
+                policy,
+            }
+            .into(),
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::gcp::secret_manager::secrets::IamBinding;
+
+    fn binding(role: &str, members: &[&str]) -> IamBinding {
+        IamBinding {
+            role: role.to_string(),
+            members: members.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn test_expand_bindings() {
+        let policy = IamPolicy {
+            bindings: vec![
+                binding(
+                    "roles/secretmanager.secretAccessor",
+                    &["user:ada@example.com", "allUsers"],
+                ),
+                binding(
+                    "roles/secretmanager.viewer",
+                    &["group:ops@example.com", "user:ada@example.com"],
+                ),
+            ],
+        };
+        let accessors = expand_bindings(&policy);
+        let members: Vec<&str> = accessors.iter().map(|a| a.member.as_str()).collect();
+        assert_eq!(
+            members,
+            ["allUsers", "group:ops@example.com", "user:ada@example.com"]
+        );
+        assert!(accessors[0].is_public());
+        assert_eq!(accessors[0].kind(), "public");
+        assert_eq!(accessors[2].kind(), "user");
+        assert_eq!(
+            accessors[2].roles,
+            [
+                "roles/secretmanager.secretAccessor",
+                "roles/secretmanager.viewer"
+            ]
+        );
+        assert_eq!(accessors[2].link(), None);
+
+        let account = Accessor {
+            member: "serviceAccount:ci@p.iam.gserviceaccount.com".to_string(),
+            roles: Vec::new(),
+        };
+        assert_eq!(
+            account.link().unwrap().to_string(),
+            "gcp:service-accounts/ci@p.iam.gserviceaccount.com"
+        );
+    }
+}
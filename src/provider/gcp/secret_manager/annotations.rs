@@ -0,0 +1,571 @@
+//! Annotations: free-form metadata of a secret, kept apart from labels.
+//!
+//! Unlike labels, annotation values are not restricted to a character set
+//! and may be long, so they are edited in a text area and shown in full in
+//! the expanded row.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::widgets::Cell;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::Theme;
+use crate::app::AppMessage;
+use crate::commands::{Command, ExportRow, TableExport};
+use crate::config::{ActionContext, KeyResolver, SearchAction, SecretsAction};
+use crate::provider::gcp::secret_manager::SecretManager;
+use crate::provider::gcp::secret_manager::client::SecretManagerClient;
+use crate::provider::gcp::secret_manager::secrets::{Secret, SecretsMsg};
+use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
+use crate::provider::gcp::secret_manager::undo::UndoEntry;
+use crate::search::Matcher;
+use crate::service::ServiceMsg;
+use crate::ui::{
+    ColumnDef,
+    Component,
+    ConfirmDialog,
+    ConfirmEvent,
+    EventResult,
+    Keybinding,
+    Modal,
+    Result,
+    Screen,
+    Table,
+    TableEvent,
+    TableRow,
+    TextArea,
+    TextAreaEvent,
+    TextInput,
+    TextInputEvent,
+};
+
+/// Maximum characters in an annotation key.
+const MAX_KEY_LENGTH: usize = 63;
+/// Maximum bytes of an annotation key.
+const MAX_KEY_BYTES: usize = 128;
+/// Limit on the bytes of all annotation keys and values of a secret.
+const MAX_TOTAL_BYTES: usize = 16 * 1024;
+
+// === Models ===
+
+#[derive(Clone, Debug)]
+pub struct AnnotationEntry {
+    pub key: String,
+    pub value: String,
+}
+
+impl TableRow for AnnotationEntry {
+    fn columns() -> &'static [ColumnDef] {
+        static COLUMNS: &[ColumnDef] = &[
+            ColumnDef::new("Key", Constraint::Length(30)),
+            ColumnDef::new("Value", Constraint::Min(30)),
+        ];
+        COLUMNS
+    }
+
+    fn render_cells(&self, theme: &Theme) -> Vec<Cell<'static>> {
+        // Only the first line fits; the expanded row shows the rest
+        let mut lines = self.value.lines();
+        let mut value = lines.next().unwrap_or_default().to_string();
+        if lines.next().is_some() {
+            value.push_str(theme.symbols.ellipsis);
+        }
+        vec![Cell::from(self.key.clone()), Cell::from(value)]
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        let matcher = Matcher::new();
+        matcher.matches(&self.key, query) || matcher.matches(&self.value, query)
+    }
+
+    fn details(&self) -> Vec<(&'static str, String)> {
+        self.value
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (if i == 0 { "Value" } else { "" }, line.to_string()))
+            .collect()
+    }
+}
+
+impl ExportRow for AnnotationEntry {
+    fn export_values(&self) -> Vec<String> {
+        vec![self.key.clone(), self.value.clone()]
+    }
+}
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum AnnotationsMsg {
+    View(Secret),
+    /// Open the annotation editor; `key: None` adds a new annotation.
+    Edit {
+        secret: Secret,
+        key: Option<String>,
+    },
+    ConfirmDelete {
+        secret: Secret,
+        key: String,
+    },
+    Update {
+        secret: Secret,
+        annotations: HashMap<String, String>,
+    },
+    Updated {
+        secret: Secret,
+        previous: HashMap<String, String>,
+    },
+}
+
+impl From<AnnotationsMsg> for SecretManagerMsg {
+    fn from(msg: AnnotationsMsg) -> Self {
+        Self::Annotations(msg)
+    }
+}
+
+impl From<AnnotationsMsg> for EventResult<SecretManagerMsg> {
+    fn from(msg: AnnotationsMsg) -> Self {
+        Self::Event(SecretManagerMsg::Annotations(msg))
+    }
+}
+
+// === Screens ===
+
+pub struct AnnotationsScreen {
+    secret: Secret,
+    table: Table<AnnotationEntry>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl AnnotationsScreen {
+    pub fn new(secret: Secret, resolver: Arc<KeyResolver>) -> Self {
+        let mut annotations: Vec<AnnotationEntry> = secret
+            .annotations
+            .iter()
+            .map(|(k, v)| AnnotationEntry {
+                key: k.clone(),
+                value: v.clone(),
+            })
+            .collect();
+        annotations.sort_by(|a, b| a.key.cmp(&b.key));
+
+        let title = format!(" {} - Annotations ", secret.name);
+        Self {
+            secret,
+            table: Table::new(annotations, resolver.clone()).with_title(title),
+            resolver,
+        }
+    }
+}
+
+impl Screen for AnnotationsScreen {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        let result = self.table.handle_key(key)?;
+        if let EventResult::Event(TableEvent::Activated(annotation)) = result {
+            return Ok(AnnotationsMsg::Edit {
+                secret: self.secret.clone(),
+                key: Some(annotation.key),
+            }
+            .into());
+        }
+        if result.is_consumed() {
+            return Ok(EventResult::Consumed);
+        }
+
+        if self.resolver.matches_secrets(&key, SecretsAction::AddLabel) {
+            return Ok(AnnotationsMsg::Edit {
+                secret: self.secret.clone(),
+                key: None,
+            }
+            .into());
+        }
+        if self
+            .resolver
+            .matches_secrets(&key, SecretsAction::EditLabel)
+            && let Some(annotation) = self.table.selected_item()
+        {
+            return Ok(AnnotationsMsg::Edit {
+                secret: self.secret.clone(),
+                key: Some(annotation.key.clone()),
+            }
+            .into());
+        }
+        if self.resolver.matches_secrets(&key, SecretsAction::Delete)
+            && let Some(annotation) = self.table.selected_item()
+        {
+            return Ok(AnnotationsMsg::ConfirmDelete {
+                secret: self.secret.clone(),
+                key: annotation.key.clone(),
+            }
+            .into());
+        }
+        if self.resolver.matches_secrets(&key, SecretsAction::Export) {
+            return Ok(SecretsMsg::StartExport {
+                file_name: format!("{}-annotations.json", self.secret.name),
+                export: TableExport::of(&self.table),
+            }
+            .into());
+        }
+
+        Ok(EventResult::Ignored)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.table.render(frame, area, theme);
+    }
+
+    fn breadcrumbs(&self) -> Vec<String> {
+        vec!["Annotations".to_string()]
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        vec![
+            Keybinding::hint(self.resolver.display_search(SearchAction::Toggle), "Search"),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::AddLabel),
+                "Add",
+            ),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::EditLabel),
+                "Edit",
+            ),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::Delete),
+                "Delete",
+            ),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::Export),
+                "Export",
+            ),
+        ]
+    }
+
+    fn action_context(&self) -> ActionContext {
+        ActionContext::screen("annotations").with_var("name", &self.secret.name)
+    }
+}
+
+// === Dialogs ===
+
+/// Which half of an annotation the dialog is asking for.
+enum AnnotationStep {
+    Key,
+    Value,
+}
+
+/// Adds or edits an annotation: the key first, then the value in a text area
+/// since values may span lines.
+pub struct AnnotationDialog {
+    secret: Secret,
+    /// The other annotations of the secret, kept when the dialog is submitted.
+    others: HashMap<String, String>,
+    step: AnnotationStep,
+    key_input: TextInput,
+    value_input: TextArea,
+}
+
+impl AnnotationDialog {
+    pub fn new(secret: Secret, key: Option<&str>) -> Self {
+        let mut others = secret.annotations.clone();
+        let (key_input, value_input) = match key.and_then(|key| others.remove_entry(key)) {
+            Some((key, value)) => {
+                let mut value_input = TextArea::new(format!("Annotation '{key}'"));
+                value_input.set_value(&value);
+                (
+                    TextInput::new("Edit Annotation Key").with_value(key),
+                    value_input,
+                )
+            }
+            None => (
+                TextInput::new("Add Annotation").with_placeholder("key"),
+                TextArea::new("Annotation Value"),
+            ),
+        };
+        let existing = others.clone();
+        let key_input = key_input.with_validator(move |key| {
+            validate_key(key)?;
+            if existing.contains_key(key) {
+                return Err(format!("Annotation '{key}' already exists"));
+            }
+            Ok(())
+        });
+        Self {
+            secret,
+            others,
+            step: AnnotationStep::Key,
+            key_input,
+            value_input,
+        }
+    }
+
+    fn handle_value_key(&mut self, key: KeyEvent) -> Result<EventResult<SecretManagerMsg>> {
+        Ok(match self.value_input.handle_key(key)? {
+            EventResult::Event(TextAreaEvent::Submitted(value)) => {
+                let mut annotations = self.others.clone();
+                annotations.insert(self.key_input.value().trim().to_string(), value);
+                match validate_size(&annotations) {
+                    Ok(()) => AnnotationsMsg::Update {
+                        secret: self.secret.clone(),
+                        annotations,
+                    }
+                    .into(),
+                    Err(err) => {
+                        self.value_input.set_error(err);
+                        EventResult::Consumed
+                    }
+                }
+            }
+            EventResult::Event(TextAreaEvent::Cancelled) => {
+                SecretManagerMsg::DialogCancelled.into()
+            }
+            _ => EventResult::Consumed,
+        })
+    }
+}
+
+impl Modal for AnnotationDialog {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if matches!(self.step, AnnotationStep::Value) {
+            return self.handle_value_key(key);
+        }
+        Ok(match self.key_input.handle_key(key)? {
+            EventResult::Event(TextInputEvent::Submitted(key)) => {
+                if validate_key(key.trim()).is_ok() {
+                    self.step = AnnotationStep::Value;
+                }
+                EventResult::Consumed
+            }
+            EventResult::Event(TextInputEvent::Cancelled) => {
+                SecretManagerMsg::DialogCancelled.into()
+            }
+            _ => EventResult::Consumed,
+        })
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(match self.step {
+            AnnotationStep::Key => self.key_input.handle_paste(text)?.consumed_only(),
+            AnnotationStep::Value => self.value_input.handle_paste(text)?.consumed_only(),
+        })
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        match self.step {
+            AnnotationStep::Key => self.key_input.render(frame, area, theme),
+            AnnotationStep::Value => self.value_input.render(frame, area, theme),
+        }
+    }
+}
+
+pub struct DeleteAnnotationDialog {
+    secret: Secret,
+    key: String,
+    dialog: ConfirmDialog,
+}
+
+impl DeleteAnnotationDialog {
+    pub fn new(secret: Secret, key: String, resolver: Arc<KeyResolver>) -> Self {
+        let dialog = ConfirmDialog::new(
+            format!("Remove the annotation \"{key}\" from \"{}\"?", secret.name),
+            resolver,
+        )
+        .with_title("Delete Annotation")
+        .with_confirm_text("Delete")
+        .with_cancel_text("Cancel");
+
+        Self {
+            secret,
+            key,
+            dialog,
+        }
+    }
+}
+
+impl Modal for DeleteAnnotationDialog {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        Ok(match self.dialog.handle_key(key)? {
+            EventResult::Event(ConfirmEvent::Confirmed) => {
+                let mut annotations = self.secret.annotations.clone();
+                annotations.remove(&self.key);
+                AnnotationsMsg::Update {
+                    secret: self.secret.clone(),
+                    annotations,
+                }
+                .into()
+            }
+            EventResult::Event(ConfirmEvent::Cancelled) => SecretManagerMsg::DialogCancelled.into(),
+            _ => EventResult::Consumed,
+        })
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.dialog.render(frame, area, theme);
+    }
+}
+
+// === Update Logic ===
+
+pub(super) fn update(state: &mut SecretManager, msg: AnnotationsMsg) -> Result<ServiceMsg> {
+    let resolver = state.get_resolver();
+    match msg {
+        AnnotationsMsg::View(secret) => {
+            state.push_view(AnnotationsScreen::new(secret, resolver));
+            Ok(ServiceMsg::Idle)
+        }
+
+        AnnotationsMsg::Edit { secret, key } => {
+            state.display_overlay(AnnotationDialog::new(secret, key.as_deref()));
+            Ok(ServiceMsg::Idle)
+        }
+
+        AnnotationsMsg::ConfirmDelete { secret, key } => {
+            state.display_overlay(DeleteAnnotationDialog::new(secret, key, resolver));
+            Ok(ServiceMsg::Idle)
+        }
+
+        AnnotationsMsg::Update {
+            secret,
+            annotations,
+        } => {
+            state.close_overlay();
+            state.display_loading_spinner("Updating annotations...");
+
+            Ok(UpdateAnnotationsCmd {
+                client: state.get_client_for(&secret)?,
+                secret,
+                annotations,
+                tx: state.get_msg_sender(),
+            }
+            .into())
+        }
+
+        AnnotationsMsg::Updated { secret, previous } => {
+            state.hide_loading_spinner();
+            state.record_undo(UndoEntry::Annotations {
+                secret: secret.clone(),
+                previous,
+            });
+            state.invalidate_secrets_cache();
+            state.pop_view();
+            state.push_view(AnnotationsScreen::new(secret, resolver));
+            Ok(ServiceMsg::Idle)
+        }
+    }
+}
+
+// === Helper Functions ===
+
+/// Check a key against the GCP constraints, so it fails before the request.
+fn validate_key(key: &str) -> std::result::Result<(), String> {
+    if key.is_empty() {
+        return Err("Annotation key is required".to_string());
+    }
+    if key.chars().count() > MAX_KEY_LENGTH || key.len() > MAX_KEY_BYTES {
+        return Err(format!(
+            "Annotation keys are limited to {MAX_KEY_LENGTH} characters"
+        ));
+    }
+    let ends_alphanumeric = key.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && key.ends_with(|c: char| c.is_ascii_alphanumeric());
+    let valid_chars = key
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !ends_alphanumeric || !valid_chars {
+        return Err(format!(
+            "Invalid annotation key '{key}': use letters, digits, '-', '_' and '.', \
+             starting and ending with a letter or digit"
+        ));
+    }
+    Ok(())
+}
+
+/// Check that all keys and values together stay under the size limit.
+fn validate_size(annotations: &HashMap<String, String>) -> std::result::Result<(), String> {
+    let total: usize = annotations.iter().map(|(k, v)| k.len() + v.len()).sum();
+    if total >= MAX_TOTAL_BYTES {
+        return Err(format!(
+            "Annotations are limited to {} KiB in total",
+            MAX_TOTAL_BYTES / 1024
+        ));
+    }
+    Ok(())
+}
+
+// === Commands ===
+
+struct UpdateAnnotationsCmd {
+    client: SecretManagerClient,
+    secret: Secret,
+    annotations: HashMap<String, String>,
+    tx: UnboundedSender<SecretManagerMsg>,
+}
+
+#[async_trait]
+impl Command for UpdateAnnotationsCmd {
+    fn name(&self) -> String {
+        format!("Updating annotations on '{}'", self.secret.name)
+    }
+
+    fn mutates(&self) -> bool {
+        true
+    }
+
+    fn resource(&self) -> Option<String> {
+        Some(self.secret.name.clone())
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let secret = self
+            .client
+            .update_annotations(&self.secret.name, self.annotations)
+            .await?;
+        self.tx.send(
+            AnnotationsMsg::Updated {
+                secret,
+                previous: self.secret.annotations,
+            }
+            .into(),
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_key() {
+        assert!(validate_key("owner").is_ok());
+        assert!(validate_key("Rotation.Runbook_v2").is_ok());
+        assert!(validate_key("").is_err());
+        assert!(validate_key("-owner").is_err());
+        assert!(validate_key("owner.").is_err());
+        assert!(validate_key("team/owner").is_err());
+        assert!(validate_key(&"k".repeat(64)).is_err());
+    }
+
+    #[test]
+    fn test_validate_size() {
+        let small = HashMap::from([("runbook".to_string(), "x".repeat(1024))]);
+        assert!(validate_size(&small).is_ok());
+
+        let large = HashMap::from([("runbook".to_string(), "x".repeat(MAX_TOTAL_BYTES))]);
+        assert!(validate_size(&large).is_err());
+    }
+}
@@ -1,26 +1,62 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use chrono::{DateTime, Utc};
+use chrono::TimeDelta;
 use color_eyre::Result;
+use color_eyre::eyre::eyre;
 use google_cloud_secretmanager_v1::client::SecretManagerService as GcpSecretManagerClient;
 use google_cloud_secretmanager_v1::model;
-use google_cloud_wkt::FieldMask;
+use google_cloud_wkt::{FieldMask, Timestamp};
 use tokio_util::bytes::Bytes;
 
-use crate::context::GcpContext;
+use crate::context::{AuthMethod, GcpContext};
+use crate::cost::{self, Operation};
+use crate::error::Error;
+use crate::provider::gcp::monitoring::{MetricPoint, MonitoringClient};
+use crate::provider::gcp::org_policy::{LocationPolicy, OrgPolicyClient};
+use crate::provider::gcp::pubsub::PubSubClient;
+use crate::provider::gcp::resource_manager::ResourceManagerClient;
+use crate::provider::gcp::secret_manager::demo::{DemoSecretManager, VersionState};
 use crate::provider::gcp::secret_manager::payload::SecretPayload;
 use crate::provider::gcp::secret_manager::secrets::{
     IamBinding,
     IamPolicy,
+    NewSecret,
     ReplicationConfig,
+    Rotation,
     Secret,
+    SecretExtras,
 };
+use crate::provider::gcp::secret_manager::vault::LocalVault;
 use crate::provider::gcp::secret_manager::versions::SecretVersion;
+use crate::time::format_epoch;
+
+/// Access requests are only reported per project, not per secret.
+const ACCESS_METRIC_FILTER: &str = concat!(
+    r#"metric.type="serviceruntime.googleapis.com/api/request_count""#,
+    r#" AND resource.type="consumed_api""#,
+    r#" AND resource.labels.service="secretmanager.googleapis.com""#,
+    r#" AND resource.labels.method="google.cloud.secretmanager.v1.SecretManagerService.AccessSecretVersion""#,
+);
 
 #[derive(Clone, Debug)]
 pub struct SecretManagerClient {
-    client: GcpSecretManagerClient,
+    backend: Backend,
     project_id: String,
+    /// Saved after every change, for the local vault's in-memory backend.
+    vault: Option<LocalVault>,
+}
+
+#[derive(Clone, Debug)]
+enum Backend {
+    Gcp {
+        client: GcpSecretManagerClient,
+        monitoring: MonitoringClient,
+        org_policy: OrgPolicyClient,
+        pubsub: PubSubClient,
+        resource_manager: ResourceManagerClient,
+    },
+    /// Fixture data for `--demo`, no requests leave the process.
+    Demo(DemoSecretManager),
 }
 
 impl SecretManagerClient {
@@ -28,6 +64,25 @@ impl SecretManagerClient {
     ///
     /// Uses the gcloud CLI credentials for the specified account.
     pub async fn new(context: &GcpContext) -> Result<Self> {
+        match &context.auth {
+            AuthMethod::Demo => {
+                return Ok(Self {
+                    backend: Backend::Demo(DemoSecretManager::open(&context.project_id)),
+                    project_id: context.project_id.clone(),
+                    vault: None,
+                });
+            }
+            AuthMethod::Vault { path } => {
+                let vault = LocalVault::open(path).await?;
+                return Ok(Self {
+                    backend: Backend::Demo(vault.store().clone()),
+                    project_id: context.project_id.clone(),
+                    vault: Some(vault),
+                });
+            }
+            AuthMethod::ApplicationDefault | AuthMethod::Gcloud => {}
+        }
+
         let credentials = context.create_credentials()?;
 
         let client = GcpSecretManagerClient::builder()
@@ -36,49 +91,135 @@ impl SecretManagerClient {
             .await?;
 
         Ok(Self {
-            client,
+            backend: Backend::Gcp {
+                client,
+                monitoring: MonitoringClient::new(context)?,
+                org_policy: OrgPolicyClient::new(context)?,
+                pubsub: PubSubClient::new(context)?,
+                resource_manager: ResourceManagerClient::new(context)?,
+            },
             project_id: context.project_id.clone(),
+            vault: None,
         })
     }
 
+    /// Make `change` to the demo store, saving the vault holding it.
+    async fn saved<T>(&self, change: impl FnOnce() -> Result<T>) -> Result<T> {
+        match &self.vault {
+            Some(vault) => vault.apply(change).await,
+            None => change(),
+        }
+    }
+
+    /// This client for the secrets of `project_id`, one of the context's
+    /// projects.
+    ///
+    /// Monitoring, Pub/Sub and the policies stay with the context's own
+    /// project.
+    #[must_use]
+    pub fn in_project(&self, project_id: &str) -> Self {
+        if project_id == self.project_id {
+            return self.clone();
+        }
+        // The vault holds a single store, the demo one per project
+        let backend = match &self.backend {
+            Backend::Demo(_) if self.vault.is_none() => {
+                Backend::Demo(DemoSecretManager::open(project_id))
+            }
+            backend => backend.clone(),
+        };
+        Self {
+            backend,
+            project_id: project_id.to_string(),
+            vault: self.vault.clone(),
+        }
+    }
+
+    /// `secret` in this client's project, for the demo backend which does not
+    /// know its project.
+    fn with_project(&self, mut secret: Secret) -> Secret {
+        secret.project.clone_from(&self.project_id);
+        secret
+    }
+
+    /// Daily `AccessSecretVersion` calls in the project over the last `days`.
+    pub async fn access_counts(&self, days: u32) -> Result<Vec<MetricPoint>> {
+        match &self.backend {
+            Backend::Gcp { monitoring, .. } => {
+                monitoring
+                    .sum_over_time(ACCESS_METRIC_FILTER, TimeDelta::days(1), days)
+                    .await
+            }
+            Backend::Demo(demo) => Ok(demo.access_counts(days)),
+        }
+    }
+
+    /// The subset of `permissions` the account holds on the project.
+    pub async fn test_permissions(&self, permissions: &[&str]) -> Result<HashSet<String>> {
+        match &self.backend {
+            Backend::Gcp {
+                resource_manager, ..
+            } => resource_manager.test_permissions(permissions).await,
+            Backend::Demo(demo) => Ok(demo.test_permissions(permissions)),
+        }
+    }
+
+    /// The locations the organization policy allows resources in.
+    pub async fn location_policy(&self) -> Result<LocationPolicy> {
+        match &self.backend {
+            Backend::Gcp { org_policy, .. } => org_policy.resource_locations().await,
+            Backend::Demo(_) => Ok(LocationPolicy::default()),
+        }
+    }
+
+    /// Pub/Sub topics in the project that can be notified of changes.
+    pub async fn list_topics(&self) -> Result<Vec<String>> {
+        match &self.backend {
+            Backend::Gcp { pubsub, .. } => pubsub.list_topics().await,
+            Backend::Demo(demo) => Ok(demo.list_topics()),
+        }
+    }
+
     pub async fn list_secrets(&self) -> Result<Vec<Secret>> {
+        let client = match &self.backend {
+            Backend::Gcp { client, .. } => client,
+            Backend::Demo(demo) => {
+                let secrets = demo.list_secrets().into_iter();
+                return Ok(secrets.map(|secret| self.with_project(secret)).collect());
+            }
+        };
         let parent = format!("projects/{}", self.project_id);
 
-        let response = self.client.list_secrets().set_parent(parent).send().await?;
+        let response = client
+            .list_secrets()
+            .set_parent(parent)
+            .send()
+            .await
+            .map_err(Error::from)?;
 
         let mut secrets = Vec::new();
         for secret in response.secrets {
             if let Some(name) = secret.name.split('/').next_back() {
-                let replication = parse_replication(secret.replication.as_ref());
-                let expire_time = secret
-                    .expire_time()
-                    .as_ref()
-                    .map(|t| format_timestamp(t.seconds()));
-
-                secrets.push(Secret {
-                    name: name.to_string(),
-                    replication,
-                    created_at: secret
-                        .create_time
-                        .as_ref()
-                        .map_or_else(|| "Unknown".to_string(), |t| format_timestamp(t.seconds())),
-                    expire_time,
-                    labels: secret.labels.clone(),
-                });
+                let name = name.to_string();
+                secrets.push(secret_from_model(&self.project_id, &name, secret));
             }
         }
         Ok(secrets)
     }
 
     pub async fn list_versions(&self, secret_id: &str) -> Result<Vec<SecretVersion>> {
+        let client = match &self.backend {
+            Backend::Gcp { client, .. } => client,
+            Backend::Demo(demo) => return demo.list_versions(secret_id),
+        };
         let parent = format!("projects/{}/secrets/{}", self.project_id, secret_id);
 
-        let response = self
-            .client
+        let response = client
             .list_secret_versions()
             .set_parent(parent)
             .send()
-            .await?;
+            .await
+            .map_err(Error::from)?;
 
         let mut versions = Vec::new();
         for version in response.versions {
@@ -89,7 +230,7 @@ impl SecretManagerClient {
                     created_at: version
                         .create_time
                         .as_ref()
-                        .map_or_else(|| "Unknown".to_string(), |t| format_timestamp(t.seconds())),
+                        .map_or_else(|| "Unknown".to_string(), |t| format_epoch(t.seconds())),
                 });
             }
         }
@@ -97,17 +238,22 @@ impl SecretManagerClient {
     }
 
     pub async fn access_version(&self, secret_id: &str, version_id: &str) -> Result<SecretPayload> {
+        let client = match &self.backend {
+            Backend::Gcp { client, .. } => client,
+            Backend::Demo(demo) => return demo.access_version(secret_id, version_id),
+        };
         let name = format!(
             "projects/{}/secrets/{}/versions/{}",
             self.project_id, secret_id, version_id
         );
 
-        let response = self
-            .client
+        let response = client
             .access_secret_version()
             .set_name(name)
             .send()
-            .await?;
+            .await
+            .map_err(Error::from)?;
+        cost::record(Operation::SecretAccess, 1);
 
         if let Some(payload) = response.payload {
             let data = String::from_utf8_lossy(&payload.data).to_string();
@@ -122,18 +268,48 @@ impl SecretManagerClient {
         }
     }
 
+    /// Raw payload bytes of a version; `version_id` may be `latest`.
+    pub async fn access_version_bytes(&self, secret_id: &str, version_id: &str) -> Result<Bytes> {
+        let client = match &self.backend {
+            Backend::Gcp { client, .. } => client,
+            Backend::Demo(demo) => return demo.access_version_bytes(secret_id, version_id),
+        };
+        let name = format!(
+            "projects/{}/secrets/{}/versions/{}",
+            self.project_id, secret_id, version_id
+        );
+
+        let response = client
+            .access_secret_version()
+            .set_name(name)
+            .send()
+            .await
+            .map_err(Error::from)?;
+        cost::record(Operation::SecretAccess, 1);
+
+        response
+            .payload
+            .map(|payload| payload.data)
+            .ok_or_else(|| color_eyre::eyre::eyre!("No payload found for the secret version"))
+    }
+
     pub async fn access_latest_version(&self, secret_id: &str) -> Result<SecretPayload> {
+        let client = match &self.backend {
+            Backend::Gcp { client, .. } => client,
+            Backend::Demo(demo) => return demo.access_version(secret_id, "latest"),
+        };
         let name = format!(
             "projects/{}/secrets/{}/versions/latest",
             self.project_id, secret_id
         );
 
-        let response = self
-            .client
+        let response = client
             .access_secret_version()
             .set_name(name)
             .send()
-            .await?;
+            .await
+            .map_err(Error::from)?;
+        cost::record(Operation::SecretAccess, 1);
 
         if let Some(payload) = response.payload {
             let data = String::from_utf8_lossy(&payload.data).to_string();
@@ -148,57 +324,82 @@ impl SecretManagerClient {
         }
     }
 
-    /// Create a new secret without an initial version.
-    pub async fn create_secret(&self, secret_id: &str) -> Result<Secret> {
+    /// Create a new secret with the given labels and no initial version.
+    pub async fn create_secret_with_labels(
+        &self,
+        secret_id: &str,
+        labels: HashMap<String, String>,
+    ) -> Result<Secret> {
+        self.create_secret_with_options(&NewSecret {
+            name: secret_id.to_string(),
+            labels,
+            ..NewSecret::default()
+        })
+        .await
+    }
+
+    /// Create a new secret, adding the payload as its first version if given.
+    pub async fn create_secret_with_options(&self, options: &NewSecret) -> Result<Secret> {
+        let client = match &self.backend {
+            Backend::Gcp { client, .. } => client,
+            Backend::Demo(demo) => {
+                let secret = || {
+                    demo.create_secret(options)
+                        .map(|secret| self.with_project(secret))
+                };
+                return self.saved(secret).await;
+            }
+        };
         let parent = format!("projects/{}", self.project_id);
 
-        let secret = model::Secret::default().set_replication(
-            model::Replication::default().set_automatic(model::replication::Automatic::default()),
-        );
+        let replication = match &options.replication {
+            ReplicationConfig::Automatic => model::Replication::default()
+                .set_automatic(model::replication::Automatic::default()),
+            ReplicationConfig::UserManaged { locations } => model::Replication::default()
+                .set_user_managed(model::replication::UserManaged::default().set_replicas(
+                    locations.iter().map(|location| {
+                        model::replication::user_managed::Replica::default().set_location(location)
+                    }),
+                )),
+        };
+        let mut secret = model::Secret::default()
+            .set_replication(replication)
+            .set_labels(options.labels.clone());
+        if let Some(expire_time) = options.expire_time {
+            secret = secret.set_expire_time(Timestamp::clamp(expire_time.timestamp(), 0));
+        }
 
-        let response = self
-            .client
+        let response = client
             .create_secret()
             .set_parent(parent)
-            .set_secret_id(secret_id)
+            .set_secret_id(&options.name)
             .set_secret(secret)
             .send()
-            .await?;
-
-        Ok(Secret {
-            name: secret_id.to_string(),
-            replication: parse_replication(response.replication.as_ref()),
-            created_at: response
-                .create_time
-                .as_ref()
-                .map_or_else(|| "Unknown".to_string(), |t| format_timestamp(t.seconds())),
-            expire_time: response
-                .expire_time()
-                .map(|t| format_timestamp(t.seconds())),
-            labels: response.labels,
-        })
-    }
-
-    /// Create a new secret with an initial payload.
-    pub async fn create_secret_with_payload(
-        &self,
-        secret_id: &str,
-        payload: &[u8],
-    ) -> Result<Secret> {
-        // First create the secret
-        let secret = self.create_secret(secret_id).await?;
+            .await
+            .map_err(Error::from)?;
 
-        // Then add the initial version
-        self.add_secret_version(secret_id, payload).await?;
+        if let Some(payload) = &options.payload {
+            self.add_secret_version(&options.name, payload.as_bytes())
+                .await?;
+        }
 
-        Ok(secret)
+        Ok(secret_from_model(&self.project_id, &options.name, response))
     }
 
     /// Delete a secret and all its versions.
     pub async fn delete_secret(&self, secret_id: &str) -> Result<()> {
+        let client = match &self.backend {
+            Backend::Gcp { client, .. } => client,
+            Backend::Demo(demo) => return self.saved(|| demo.delete_secret(secret_id)).await,
+        };
         let name = format!("projects/{}/secrets/{}", self.project_id, secret_id);
 
-        self.client.delete_secret().set_name(name).send().await?;
+        client
+            .delete_secret()
+            .set_name(name)
+            .send()
+            .await
+            .map_err(Error::from)?;
 
         Ok(())
     }
@@ -209,17 +410,29 @@ impl SecretManagerClient {
         secret_id: &str,
         payload: &[u8],
     ) -> Result<SecretVersion> {
+        let client = match &self.backend {
+            Backend::Gcp { client, .. } => client,
+            Backend::Demo(demo) => {
+                if self.vault.is_some() && std::str::from_utf8(payload).is_err() {
+                    return Err(eyre!("The local vault only holds text payloads"));
+                }
+                return self
+                    .saved(|| demo.add_secret_version(secret_id, payload))
+                    .await;
+            }
+        };
         let parent = format!("projects/{}/secrets/{}", self.project_id, secret_id);
 
         let payload_model = model::SecretPayload::default().set_data(Bytes::from(payload.to_vec()));
 
-        let response = self
-            .client
+        let response = client
             .add_secret_version()
             .set_parent(parent)
             .set_payload(payload_model)
             .send()
-            .await?;
+            .await
+            .map_err(Error::from)?;
+        cost::record(Operation::SecretVersion, 1);
 
         let version_id = response
             .name
@@ -234,7 +447,7 @@ impl SecretManagerClient {
             created_at: response
                 .create_time
                 .as_ref()
-                .map_or_else(|| "Unknown".to_string(), |t| format_timestamp(t.seconds())),
+                .map_or_else(|| "Unknown".to_string(), |t| format_epoch(t.seconds())),
         })
     }
 
@@ -244,17 +457,25 @@ impl SecretManagerClient {
         secret_id: &str,
         version_id: &str,
     ) -> Result<SecretVersion> {
+        let client = match &self.backend {
+            Backend::Gcp { client, .. } => client,
+            Backend::Demo(demo) => {
+                let version =
+                    || demo.set_version_state(secret_id, version_id, VersionState::Disabled);
+                return self.saved(version).await;
+            }
+        };
         let name = format!(
             "projects/{}/secrets/{}/versions/{}",
             self.project_id, secret_id, version_id
         );
 
-        let response = self
-            .client
+        let response = client
             .disable_secret_version()
             .set_name(name)
             .send()
-            .await?;
+            .await
+            .map_err(Error::from)?;
 
         Ok(SecretVersion {
             version_id: version_id.to_string(),
@@ -262,23 +483,31 @@ impl SecretManagerClient {
             created_at: response
                 .create_time
                 .as_ref()
-                .map_or_else(|| "Unknown".to_string(), |t| format_timestamp(t.seconds())),
+                .map_or_else(|| "Unknown".to_string(), |t| format_epoch(t.seconds())),
         })
     }
 
     /// Enable a previously disabled secret version.
     pub async fn enable_version(&self, secret_id: &str, version_id: &str) -> Result<SecretVersion> {
+        let client = match &self.backend {
+            Backend::Gcp { client, .. } => client,
+            Backend::Demo(demo) => {
+                let version =
+                    || demo.set_version_state(secret_id, version_id, VersionState::Enabled);
+                return self.saved(version).await;
+            }
+        };
         let name = format!(
             "projects/{}/secrets/{}/versions/{}",
             self.project_id, secret_id, version_id
         );
 
-        let response = self
-            .client
+        let response = client
             .enable_secret_version()
             .set_name(name)
             .send()
-            .await?;
+            .await
+            .map_err(Error::from)?;
 
         Ok(SecretVersion {
             version_id: version_id.to_string(),
@@ -286,7 +515,7 @@ impl SecretManagerClient {
             created_at: response
                 .create_time
                 .as_ref()
-                .map_or_else(|| "Unknown".to_string(), |t| format_timestamp(t.seconds())),
+                .map_or_else(|| "Unknown".to_string(), |t| format_epoch(t.seconds())),
         })
     }
 
@@ -296,17 +525,25 @@ impl SecretManagerClient {
         secret_id: &str,
         version_id: &str,
     ) -> Result<SecretVersion> {
+        let client = match &self.backend {
+            Backend::Gcp { client, .. } => client,
+            Backend::Demo(demo) => {
+                let version =
+                    || demo.set_version_state(secret_id, version_id, VersionState::Destroyed);
+                return self.saved(version).await;
+            }
+        };
         let name = format!(
             "projects/{}/secrets/{}/versions/{}",
             self.project_id, secret_id, version_id
         );
 
-        let response = self
-            .client
+        let response = client
             .destroy_secret_version()
             .set_name(name)
             .send()
-            .await?;
+            .await
+            .map_err(Error::from)?;
 
         Ok(SecretVersion {
             version_id: version_id.to_string(),
@@ -314,7 +551,7 @@ impl SecretManagerClient {
             created_at: response
                 .create_time
                 .as_ref()
-                .map_or_else(|| "Unknown".to_string(), |t| format_timestamp(t.seconds())),
+                .map_or_else(|| "Unknown".to_string(), |t| format_epoch(t.seconds())),
         })
     }
 
@@ -324,6 +561,16 @@ impl SecretManagerClient {
         secret_id: &str,
         labels: HashMap<String, String>,
     ) -> Result<Secret> {
+        let client = match &self.backend {
+            Backend::Gcp { client, .. } => client,
+            Backend::Demo(demo) => {
+                let secret = || {
+                    demo.update_labels(secret_id, labels)
+                        .map(|secret| self.with_project(secret))
+                };
+                return self.saved(secret).await;
+            }
+        };
         let name = format!("projects/{}/secrets/{}", self.project_id, secret_id);
 
         let mut secret = model::Secret::default();
@@ -332,38 +579,99 @@ impl SecretManagerClient {
 
         let update_mask = FieldMask::default().set_paths(vec!["labels".to_string()]);
 
-        let response = self
-            .client
+        let response = client
             .update_secret()
             .set_secret(secret)
             .set_update_mask(update_mask)
             .send()
-            .await?;
+            .await
+            .map_err(Error::from)?;
 
-        Ok(Secret {
-            name: secret_id.to_string(),
-            replication: parse_replication(response.replication.as_ref()),
-            created_at: response
-                .create_time
-                .as_ref()
-                .map_or_else(|| "Unknown".to_string(), |t| format_timestamp(t.seconds())),
-            expire_time: response
-                .expire_time()
-                .map(|t| format_timestamp(t.seconds())),
-            labels: response.labels,
-        })
+        Ok(secret_from_model(&self.project_id, secret_id, response))
+    }
+
+    /// Update secret annotations.
+    pub async fn update_annotations(
+        &self,
+        secret_id: &str,
+        annotations: HashMap<String, String>,
+    ) -> Result<Secret> {
+        let client = match &self.backend {
+            Backend::Gcp { client, .. } => client,
+            Backend::Demo(demo) => {
+                let secret = || {
+                    demo.update_annotations(secret_id, annotations)
+                        .map(|secret| self.with_project(secret))
+                };
+                return self.saved(secret).await;
+            }
+        };
+        let name = format!("projects/{}/secrets/{}", self.project_id, secret_id);
+
+        let mut secret = model::Secret::default();
+        secret.name.clone_from(&name);
+        secret.annotations.clone_from(&annotations);
+
+        let update_mask = FieldMask::default().set_paths(vec!["annotations".to_string()]);
+
+        let response = client
+            .update_secret()
+            .set_secret(secret)
+            .set_update_mask(update_mask)
+            .send()
+            .await
+            .map_err(Error::from)?;
+
+        Ok(secret_from_model(&self.project_id, secret_id, response))
+    }
+
+    /// Replace the Pub/Sub topics notified of changes to a secret.
+    pub async fn update_topics(&self, secret_id: &str, topics: Vec<String>) -> Result<Secret> {
+        let client = match &self.backend {
+            Backend::Gcp { client, .. } => client,
+            Backend::Demo(demo) => {
+                let secret = || {
+                    demo.update_topics(secret_id, topics)
+                        .map(|secret| self.with_project(secret))
+                };
+                return self.saved(secret).await;
+            }
+        };
+        let name = format!("projects/{}/secrets/{}", self.project_id, secret_id);
+
+        let secret = model::Secret::default().set_name(name).set_topics(
+            topics
+                .into_iter()
+                .map(|topic| model::Topic::default().set_name(topic)),
+        );
+
+        let update_mask = FieldMask::default().set_paths(vec!["topics".to_string()]);
+
+        let response = client
+            .update_secret()
+            .set_secret(secret)
+            .set_update_mask(update_mask)
+            .send()
+            .await
+            .map_err(Error::from)?;
+
+        Ok(secret_from_model(&self.project_id, secret_id, response))
     }
 
     /// Get the IAM policy for a secret.
     pub async fn get_iam_policy(&self, secret_id: &str) -> Result<IamPolicy> {
+        let client = match &self.backend {
+            Backend::Gcp { client, .. } => client,
+            Backend::Demo(demo) => return demo.get_iam_policy(secret_id),
+        };
         let resource = format!("projects/{}/secrets/{}", self.project_id, secret_id);
 
-        let response = self
-            .client
+        let response = client
             .get_iam_policy()
             .set_resource(resource)
             .send()
-            .await?;
+            .await
+            .map_err(Error::from)?;
 
         let bindings = response
             .bindings
@@ -377,33 +685,116 @@ impl SecretManagerClient {
         Ok(IamPolicy { bindings })
     }
 
+    /// The subset of `permissions` the account holds on a single secret.
+    pub async fn test_secret_permissions(
+        &self,
+        secret_id: &str,
+        permissions: &[&str],
+    ) -> Result<HashSet<String>> {
+        let client = match &self.backend {
+            Backend::Gcp { client, .. } => client,
+            Backend::Demo(demo) => return demo.test_secret_permissions(secret_id, permissions),
+        };
+        let resource = format!("projects/{}/secrets/{}", self.project_id, secret_id);
+
+        let response = client
+            .test_iam_permissions()
+            .set_resource(resource)
+            .set_permissions(permissions.iter().copied())
+            .send()
+            .await
+            .map_err(Error::from)?;
+
+        Ok(response.permissions.into_iter().collect())
+    }
+
     /// Get secret metadata including replication configuration.
     pub async fn get_secret(&self, secret_id: &str) -> Result<Secret> {
-        let name = format!("projects/{}/secrets/{}", self.project_id, secret_id);
-        let response = self.client.get_secret().set_name(name).send().await?;
+        let client = match &self.backend {
+            Backend::Gcp { client, .. } => client,
+            Backend::Demo(demo) => {
+                return demo
+                    .get_secret(secret_id)
+                    .map(|secret| self.with_project(secret));
+            }
+        };
+        let response = self.fetch_secret(client, secret_id).await?;
+        Ok(secret_from_model(&self.project_id, secret_id, response))
+    }
 
-        Ok(Secret {
-            name: secret_id.to_string(),
-            replication: parse_replication(response.replication.as_ref()),
-            created_at: response
-                .create_time
-                .as_ref()
-                .map_or_else(|| "Unknown".to_string(), |t| format_timestamp(t.seconds())),
-            expire_time: response
-                .expire_time()
-                .map(|t| format_timestamp(t.seconds())),
-            labels: response.labels,
-        })
+    /// Like [`Self::get_secret`], with the [`SecretExtras`] the secret list
+    /// leaves out.
+    pub async fn get_secret_with_extras(&self, secret_id: &str) -> Result<Secret> {
+        let (mut secret, rotation) = match &self.backend {
+            Backend::Gcp { client, .. } => {
+                let response = self.fetch_secret(client, secret_id).await?;
+                let rotation = response.rotation.as_ref().map(|rotation| Rotation {
+                    period: rotation
+                        .rotation_period
+                        .as_ref()
+                        .and_then(|period| u64::try_from(period.seconds()).ok()),
+                    next: rotation
+                        .next_rotation_time
+                        .as_ref()
+                        .map(|t| format_epoch(t.seconds())),
+                });
+                (
+                    secret_from_model(&self.project_id, secret_id, response),
+                    rotation,
+                )
+            }
+            Backend::Demo(demo) => (self.with_project(demo.get_secret(secret_id)?), None),
+        };
+        let versions = self.list_versions(secret_id).await?;
+        let latest = versions
+            .iter()
+            .max_by_key(|version| version.version_id.parse::<u64>().unwrap_or_default())
+            .map(|version| version.state.clone());
+        secret.extras = Some(SecretExtras {
+            rotation,
+            versions: versions.len(),
+            latest,
+        });
+        Ok(secret)
+    }
+
+    async fn fetch_secret(
+        &self,
+        client: &GcpSecretManagerClient,
+        secret_id: &str,
+    ) -> Result<model::Secret> {
+        let name = format!("projects/{}/secrets/{}", self.project_id, secret_id);
+        Ok(client
+            .get_secret()
+            .set_name(name)
+            .send()
+            .await
+            .map_err(Error::from)?)
     }
 }
 
 // === Utilities ===
 
-fn format_timestamp(seconds: i64) -> String {
-    DateTime::<Utc>::from_timestamp(seconds, 0).map_or_else(
-        || "Unknown".to_string(),
-        |dt| dt.format("%Y-%m-%d %H:%M").to_string(),
-    )
+fn secret_from_model(project_id: &str, secret_id: &str, secret: model::Secret) -> Secret {
+    Secret {
+        name: secret_id.to_string(),
+        project: project_id.to_string(),
+        replication: parse_replication(secret.replication.as_ref()),
+        created_at: secret
+            .create_time
+            .as_ref()
+            .map_or_else(|| "Unknown".to_string(), |t| format_epoch(t.seconds())),
+        expire_time: secret.expire_time().map(|t| format_epoch(t.seconds())),
+        labels: secret.labels,
+        annotations: secret.annotations,
+        topics: topic_names(&secret.topics),
+        etag: secret.etag,
+        extras: None,
+    }
+}
+
+fn topic_names(topics: &[model::Topic]) -> Vec<String> {
+    topics.iter().map(|topic| topic.name.clone()).collect()
 }
 
 fn parse_replication(replication: Option<&model::Replication>) -> ReplicationConfig {
@@ -0,0 +1,287 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use color_eyre::eyre::eyre;
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use similar::TextDiff;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::app::AppMessage;
+use crate::commands::Command;
+use crate::config::KeyResolver;
+use crate::provider::gcp::secret_manager::SecretManager;
+use crate::provider::gcp::secret_manager::payload::{SecretPayload, expand_home, version_label};
+use crate::provider::gcp::secret_manager::secrets::Secret;
+use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
+use crate::provider::gcp::secret_manager::versions::SecretVersion;
+use crate::service::ServiceMsg;
+use crate::ui::{
+    Component,
+    EventResult,
+    Keybinding,
+    Modal,
+    Result,
+    Screen,
+    TextInput,
+    TextInputEvent,
+    TextViewer,
+    ToastType,
+};
+use crate::{Theme, encryption};
+
+/// Unchanged lines shown around each change.
+const CONTEXT_LINES: usize = 3;
+
+// === Models ===
+
+/// A payload and where it came from, compared against a local file.
+#[derive(Debug, Clone)]
+pub struct ComparedPayload {
+    pub secret: Secret,
+    pub version: Option<SecretVersion>,
+    pub payload: SecretPayload,
+}
+
+impl ComparedPayload {
+    fn label(&self) -> String {
+        format!(
+            "{} v{}",
+            self.secret.name,
+            version_label(self.version.as_ref())
+        )
+    }
+}
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum CompareMsg {
+    /// Ask for the file to compare the payload with.
+    Start(Box<ComparedPayload>),
+    Run {
+        payload: Box<ComparedPayload>,
+        path: PathBuf,
+    },
+    /// Unified diff lines from the secret to the file.
+    Loaded {
+        secret: String,
+        file: String,
+        diff: Vec<String>,
+    },
+}
+
+impl From<CompareMsg> for SecretManagerMsg {
+    fn from(msg: CompareMsg) -> Self {
+        Self::Compare(msg)
+    }
+}
+
+impl From<CompareMsg> for EventResult<SecretManagerMsg> {
+    fn from(msg: CompareMsg) -> Self {
+        Self::Event(SecretManagerMsg::Compare(msg))
+    }
+}
+
+// === Screens ===
+
+pub struct DiffScreen {
+    /// Labels of the two compared sides.
+    sides: (String, String),
+    viewer: TextViewer,
+}
+
+impl DiffScreen {
+    pub fn new(sides: (String, String), lines: &[String], resolver: Arc<KeyResolver>) -> Self {
+        Self {
+            sides,
+            viewer: TextViewer::new(&lines.join("\n"), resolver).with_syntax("diff"),
+        }
+    }
+}
+
+impl Screen for DiffScreen {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        Ok(self.viewer.handle_key(key)?.consumed_only())
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.viewer.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.viewer.set_title(format!(
+            " {} {} {} ",
+            self.sides.0, theme.symbols.arrow, self.sides.1
+        ));
+        self.viewer.render(frame, area, theme);
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        self.viewer.keybindings()
+    }
+
+    fn breadcrumbs(&self) -> Vec<String> {
+        vec!["Diff".to_string()]
+    }
+}
+
+// === Dialogs ===
+
+pub struct CompareFileDialog {
+    payload: Box<ComparedPayload>,
+    input: TextInput,
+}
+
+impl CompareFileDialog {
+    pub fn new(payload: Box<ComparedPayload>) -> Self {
+        Self {
+            payload,
+            input: TextInput::new("Compare With File").with_placeholder(".env"),
+        }
+    }
+}
+
+impl Modal for CompareFileDialog {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        Ok(match self.input.handle_key(key)? {
+            EventResult::Event(TextInputEvent::Submitted(path)) if !path.trim().is_empty() => {
+                CompareMsg::Run {
+                    payload: self.payload.clone(),
+                    path: expand_home(path.trim()),
+                }
+                .into()
+            }
+            EventResult::Event(TextInputEvent::Cancelled) => {
+                SecretManagerMsg::DialogCancelled.into()
+            }
+            _ => EventResult::Consumed,
+        })
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.input.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.input.render(frame, area, theme);
+    }
+}
+
+// === Update Logic ===
+
+pub(super) fn update(state: &mut SecretManager, msg: CompareMsg) -> ServiceMsg {
+    match msg {
+        CompareMsg::Start(payload) => {
+            state.display_overlay(CompareFileDialog::new(payload));
+            ServiceMsg::Idle
+        }
+
+        CompareMsg::Run { payload, path } => {
+            state.close_overlay();
+
+            CompareFileCmd {
+                payload,
+                path,
+                tx: state.get_msg_sender(),
+            }
+            .into()
+        }
+
+        CompareMsg::Loaded { secret, file, diff } => {
+            state.push_view(DiffScreen::new((secret, file), &diff, state.get_resolver()));
+            ServiceMsg::Idle
+        }
+    }
+}
+
+// === Helper Functions ===
+
+/// Unified diff from `old` to `new`, one entry per line, empty if equal.
+pub(super) fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str) -> Vec<String> {
+    if old == new {
+        return Vec::new();
+    }
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(CONTEXT_LINES)
+        .header(old_label, new_label)
+        .to_string()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+// === Commands ===
+
+struct CompareFileCmd {
+    payload: Box<ComparedPayload>,
+    path: PathBuf,
+    tx: UnboundedSender<SecretManagerMsg>,
+}
+
+#[async_trait]
+impl Command for CompareFileCmd {
+    fn name(&self) -> String {
+        format!(
+            "Comparing '{}' with {}",
+            self.payload.label(),
+            self.path.display()
+        )
+    }
+
+    async fn execute(self: Box<Self>, action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let content = tokio::fs::read(&self.path)
+            .await
+            .map_err(|err| eyre!("Cannot read {}: {err}", self.path.display()))?;
+        let content = encryption::decrypt_from(&self.path, content).await?;
+        let content = String::from_utf8(content)
+            .map_err(|_| eyre!("{} is not valid UTF-8", self.path.display()))?;
+
+        let file = self.path.display().to_string();
+        let diff = unified_diff(
+            &self.payload.payload.data,
+            &content,
+            &self.payload.label(),
+            &file,
+        );
+        if diff.is_empty() {
+            action_tx.send(AppMessage::ShowToast {
+                message: format!("{} matches {file}", self.payload.label()),
+                toast_type: ToastType::Success,
+            })?;
+        } else {
+            self.tx.send(
+                CompareMsg::Loaded {
+                    secret: self.payload.label(),
+                    file,
+                    diff,
+                }
+                .into(),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_diff() {
+        assert!(unified_diff("A=1\n", "A=1\n", "secret", "file").is_empty());
+
+        let diff = unified_diff("A=1\nB=2\n", "A=1\nB=3\n", "secret", "file");
+        assert_eq!(diff[0], "--- secret");
+        assert_eq!(diff[1], "+++ file");
+        assert!(diff.contains(&"-B=2".to_string()));
+        assert!(diff.contains(&"+B=3".to_string()));
+    }
+}
@@ -0,0 +1,588 @@
+use std::pin::pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use color_eyre::eyre::eyre;
+use crossterm::event::KeyEvent;
+use futures::{StreamExt, stream};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph};
+use regex::{Regex, RegexBuilder};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+use crate::Theme;
+use crate::app::AppMessage;
+use crate::commands::Command;
+use crate::config::{KeyResolver, NavAction, SearchAction};
+use crate::cost::{self, Operation};
+use crate::provider::gcp::secret_manager::SecretManager;
+use crate::provider::gcp::secret_manager::client::SecretManagerClient;
+use crate::provider::gcp::secret_manager::payload::PayloadMsg;
+use crate::provider::gcp::secret_manager::secrets::Secret;
+use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
+use crate::search::Matcher;
+use crate::service::ServiceMsg;
+use crate::ui::text::display_width;
+use crate::ui::widgets::ProgressBar;
+use crate::ui::{
+    ColumnDef,
+    Component,
+    EventResult,
+    Keybinding,
+    Modal,
+    Result,
+    Screen,
+    Table,
+    TableEvent,
+    TableRow,
+    TextInput,
+    TextInputEvent,
+    ToastType,
+};
+
+/// Maximum number of payloads fetched at the same time.
+const MAX_CONCURRENT_FETCHES: usize = 8;
+
+/// Number of lines shown above and below a match in the preview.
+const CONTEXT_LINES: usize = 2;
+
+/// Height of the context preview: the match, its context, and borders.
+const PREVIEW_HEIGHT: u16 = 7;
+
+/// Widest the progress bar grows on wide terminals.
+const MAX_BAR_WIDTH: usize = 40;
+
+// === Models ===
+
+/// What to look for in secret payloads.
+///
+/// Queries wrapped in slashes (`/pattern/`) are compiled as a case-insensitive
+/// regex, everything else is a case-insensitive substring search.
+#[derive(Debug, Clone)]
+pub enum SearchPattern {
+    Substring(String),
+    Regex(Regex),
+}
+
+impl SearchPattern {
+    pub fn parse(query: &str) -> Result<Self> {
+        if query.len() > 2
+            && let Some(pattern) = query.strip_prefix('/').and_then(|q| q.strip_suffix('/'))
+        {
+            let regex = RegexBuilder::new(pattern)
+                .case_insensitive(true)
+                .build()
+                .map_err(|err| eyre!("Invalid search regex: {err}"))?;
+            return Ok(Self::Regex(regex));
+        }
+        Ok(Self::Substring(query.to_lowercase()))
+    }
+
+    pub fn is_match(&self, line: &str) -> bool {
+        match self {
+            Self::Substring(needle) => line.to_lowercase().contains(needle.as_str()),
+            Self::Regex(regex) => regex.is_match(line),
+        }
+    }
+}
+
+/// A single line in a secret payload that matched the search pattern.
+#[derive(Debug, Clone)]
+pub struct ContentMatch {
+    pub secret: Secret,
+    /// 1-based line number of the match.
+    pub line_number: usize,
+    pub line: String,
+    /// Surrounding lines (including the match) as `(line_number, text)` pairs.
+    pub context: Vec<(usize, String)>,
+}
+
+impl TableRow for ContentMatch {
+    fn columns() -> &'static [ColumnDef] {
+        static COLUMNS: &[ColumnDef] = &[
+            ColumnDef::new("Secret", Constraint::Min(20)),
+            ColumnDef::new("Line", Constraint::Length(6)),
+            ColumnDef::new("Match", Constraint::Min(30)),
+        ];
+        COLUMNS
+    }
+
+    fn render_cells(&self, _theme: &Theme) -> Vec<Cell<'static>> {
+        vec![
+            Cell::from(self.secret.name.clone()),
+            Cell::from(self.line_number.to_string()),
+            Cell::from(self.line.trim().to_string()),
+        ]
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        let matcher = Matcher::new();
+        matcher.matches(&self.secret.name, query) || matcher.matches(&self.line, query)
+    }
+}
+
+/// Find every line of `data` matching `pattern`, with surrounding context.
+pub fn find_matches(secret: &Secret, data: &str, pattern: &SearchPattern) -> Vec<ContentMatch> {
+    let lines: Vec<&str> = data.lines().collect();
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| pattern.is_match(line))
+        .map(|(idx, line)| {
+            let start = idx.saturating_sub(CONTEXT_LINES);
+            let end = (idx + CONTEXT_LINES + 1).min(lines.len());
+            ContentMatch {
+                secret: secret.clone(),
+                line_number: idx + 1,
+                line: (*line).to_string(),
+                context: (start..end)
+                    .map(|i| (i + 1, lines[i].to_string()))
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// Search state shared between the running command and the results screen.
+#[derive(Default)]
+pub struct DeepSearchState {
+    total: AtomicUsize,
+    searched: AtomicUsize,
+    failed: AtomicUsize,
+    finished: AtomicBool,
+    matches: Mutex<Vec<ContentMatch>>,
+}
+
+impl DeepSearchState {
+    fn new(total: usize) -> Self {
+        Self {
+            total: AtomicUsize::new(total),
+            ..Self::default()
+        }
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.lock().map_or(0, |m| m.len())
+    }
+
+    fn snapshot(&self) -> Vec<ContentMatch> {
+        self.matches.lock().map(|m| m.clone()).unwrap_or_default()
+    }
+}
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum DeepSearchMsg {
+    /// Ask for a query to search the given secrets with.
+    Start(Vec<Secret>),
+    Run {
+        secrets: Vec<Secret>,
+        query: String,
+    },
+    ViewPayload(Box<Secret>),
+}
+
+impl From<DeepSearchMsg> for SecretManagerMsg {
+    fn from(msg: DeepSearchMsg) -> Self {
+        Self::DeepSearch(msg)
+    }
+}
+
+impl From<DeepSearchMsg> for EventResult<SecretManagerMsg> {
+    fn from(msg: DeepSearchMsg) -> Self {
+        Self::Event(SecretManagerMsg::DeepSearch(msg))
+    }
+}
+
+// === Screens ===
+
+pub struct DeepSearchScreen {
+    query: String,
+    state: Arc<DeepSearchState>,
+    cancel: CancellationToken,
+    table: Table<ContentMatch>,
+    synced_matches: usize,
+    resolver: Arc<KeyResolver>,
+}
+
+impl DeepSearchScreen {
+    pub fn new(
+        query: String,
+        state: Arc<DeepSearchState>,
+        cancel: CancellationToken,
+        resolver: Arc<KeyResolver>,
+    ) -> Self {
+        let title = format!(" Deep Search: {query} ");
+        Self {
+            query,
+            state,
+            cancel,
+            table: Table::new(Vec::new(), resolver.clone()).with_title(title),
+            synced_matches: 0,
+            resolver,
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        !self.state.finished.load(Ordering::Relaxed) && !self.cancel.is_cancelled()
+    }
+
+    /// Pull in matches found by the background search since the last frame.
+    fn sync_matches(&mut self) {
+        let count = self.state.match_count();
+        if count != self.synced_matches {
+            self.table.set_items(self.state.snapshot());
+            self.synced_matches = count;
+        }
+    }
+
+    fn render_progress(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let total = self.state.total.load(Ordering::Relaxed);
+        let searched = self.state.searched.load(Ordering::Relaxed);
+        let failed = self.state.failed.load(Ordering::Relaxed);
+        let matches = self.state.match_count();
+
+        let (status, color) = if self.is_running() {
+            ("Searching", theme.yellow())
+        } else if self.cancel.is_cancelled() && searched < total {
+            ("Cancelled", theme.peach())
+        } else {
+            ("Done", theme.green())
+        };
+
+        let status = format!(" {status} ");
+        let summary = format!(" secrets {} {matches} matches", theme.symbols.middle_dot);
+        let unreadable = if failed > 0 {
+            format!(" {} {failed} unreadable", theme.symbols.middle_dot)
+        } else {
+            String::new()
+        };
+        let bar_width = usize::from(area.width)
+            .saturating_sub(
+                display_width(&status) + display_width(&summary) + display_width(&unreadable),
+            )
+            .min(MAX_BAR_WIDTH);
+
+        let mut spans = vec![Span::styled(
+            status,
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        )];
+        spans.extend(
+            ProgressBar::new(searched, total)
+                .line(bar_width, theme)
+                .spans,
+        );
+        spans.push(Span::styled(summary, Style::default().fg(theme.subtext0())));
+        spans.push(Span::styled(unreadable, Style::default().fg(theme.red())));
+
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
+    fn render_preview(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.border()))
+            .title(" Context ")
+            .title_style(Style::default().fg(theme.mauve()));
+
+        let lines: Vec<Line> = self.table.selected_item().map_or_else(Vec::new, |m| {
+            m.context
+                .iter()
+                .map(|(number, text)| {
+                    let style = if *number == m.line_number {
+                        Style::default()
+                            .fg(theme.yellow())
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(theme.subtext0())
+                    };
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{number:>5} {} ", theme.symbols.separator),
+                            Style::default().fg(theme.overlay0()),
+                        ),
+                        Span::styled(text.clone(), style),
+                    ])
+                })
+                .collect()
+        });
+
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
+
+impl Drop for DeepSearchScreen {
+    fn drop(&mut self) {
+        // Leaving the screen stops any fetches still in flight
+        self.cancel.cancel();
+    }
+}
+
+impl Screen for DeepSearchScreen {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        // While the search runs, Esc stops it instead of leaving the screen
+        if self.is_running() && self.resolver.matches_search(&key, SearchAction::Exit) {
+            self.cancel.cancel();
+            return Ok(EventResult::Consumed);
+        }
+
+        let result = self.table.handle_key(key)?;
+        if let EventResult::Event(TableEvent::Activated(content_match)) = result {
+            return Ok(DeepSearchMsg::ViewPayload(Box::new(content_match.secret)).into());
+        }
+        if result.is_consumed() {
+            return Ok(EventResult::Consumed);
+        }
+
+        Ok(EventResult::Ignored)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.sync_matches();
+
+        let [progress_area, table_area, preview_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(5),
+            Constraint::Length(PREVIEW_HEIGHT),
+        ])
+        .areas(area);
+
+        self.render_progress(frame, progress_area, theme);
+        self.table.render(frame, table_area, theme);
+        self.render_preview(frame, preview_area, theme);
+    }
+
+    fn breadcrumbs(&self) -> Vec<String> {
+        vec![format!("Search \"{}\"", self.query)]
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        vec![
+            Keybinding::hint(self.resolver.display_nav(NavAction::Select), "Payload"),
+            Keybinding::hint(
+                self.resolver.display_search(SearchAction::Exit),
+                "Stop search",
+            ),
+            Keybinding::hint(self.resolver.display_search(SearchAction::Toggle), "Filter"),
+        ]
+    }
+}
+
+// === Dialogs ===
+
+pub struct DeepSearchDialog {
+    secrets: Vec<Secret>,
+    input: TextInput,
+}
+
+impl DeepSearchDialog {
+    pub fn new(secrets: Vec<Secret>) -> Self {
+        let mut label = format!("Search contents of {} secrets", secrets.len());
+        // Every payload is read once, each read is billed
+        if let Some(estimate) = cost::estimate_line(Operation::SecretAccess, secrets.len()) {
+            label = format!("{label} ({estimate})");
+        }
+        Self {
+            secrets,
+            input: TextInput::new(label).with_placeholder("text or /regex/"),
+        }
+    }
+}
+
+impl Modal for DeepSearchDialog {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        Ok(match self.input.handle_key(key)? {
+            EventResult::Event(TextInputEvent::Submitted(query)) if !query.is_empty() => {
+                DeepSearchMsg::Run {
+                    secrets: self.secrets.clone(),
+                    query,
+                }
+                .into()
+            }
+            EventResult::Event(TextInputEvent::Cancelled) => {
+                SecretManagerMsg::DialogCancelled.into()
+            }
+            _ => EventResult::Consumed,
+        })
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.input.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.input.render(frame, area, theme);
+    }
+}
+
+// === Update Logic ===
+
+pub(super) fn update(state: &mut SecretManager, msg: DeepSearchMsg) -> Result<ServiceMsg> {
+    match msg {
+        DeepSearchMsg::Start(secrets) => {
+            if secrets.is_empty() {
+                return Ok(ServiceMsg::Idle);
+            }
+            state.display_overlay(DeepSearchDialog::new(secrets));
+            Ok(ServiceMsg::Idle)
+        }
+
+        DeepSearchMsg::Run { secrets, query } => {
+            let pattern = SearchPattern::parse(&query)?;
+            state.close_overlay();
+
+            let search_state = Arc::new(DeepSearchState::new(secrets.len()));
+            let cancel = CancellationToken::new();
+            state.push_view(DeepSearchScreen::new(
+                query.clone(),
+                search_state.clone(),
+                cancel.clone(),
+                state.get_resolver(),
+            ));
+
+            Ok(DeepSearchCmd {
+                client: state.get_client()?,
+                secrets,
+                query,
+                pattern,
+                state: search_state,
+                cancel,
+            }
+            .into())
+        }
+
+        DeepSearchMsg::ViewPayload(secret) => {
+            state.queue(
+                PayloadMsg::Load {
+                    secret: *secret,
+                    version: None,
+                }
+                .into(),
+            );
+            Ok(ServiceMsg::Idle)
+        }
+    }
+}
+
+// === Commands ===
+
+struct DeepSearchCmd {
+    client: SecretManagerClient,
+    secrets: Vec<Secret>,
+    query: String,
+    pattern: SearchPattern,
+    state: Arc<DeepSearchState>,
+    cancel: CancellationToken,
+}
+
+#[async_trait]
+impl Command for DeepSearchCmd {
+    fn name(&self) -> String {
+        format!(
+            "Searching {} secrets for '{}'",
+            self.secrets.len(),
+            self.query
+        )
+    }
+
+    async fn execute(self: Box<Self>, action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let mut fetches = pin!(
+            stream::iter(self.secrets.clone())
+                .map(|secret| {
+                    let client = self.client.in_project(&secret.project);
+                    async move {
+                        let result = client.access_latest_version(&secret.name).await;
+                        (secret, result)
+                    }
+                })
+                .buffer_unordered(MAX_CONCURRENT_FETCHES)
+        );
+
+        loop {
+            tokio::select! {
+                () = self.cancel.cancelled() => break,
+                next = fetches.next() => {
+                    let Some((secret, result)) = next else { break };
+                    match result {
+                        Ok(payload) => {
+                            let found = find_matches(&secret, &payload.data, &self.pattern);
+                            if !found.is_empty()
+                                && let Ok(mut matches) = self.state.matches.lock()
+                            {
+                                matches.extend(found);
+                            }
+                        }
+                        Err(err) => {
+                            warn!(secret = %secret.name, %err, "Deep search could not read payload");
+                            self.state.failed.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                    self.state.searched.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+        self.state.finished.store(true, Ordering::Relaxed);
+
+        let matches = self.state.match_count();
+        let message = if self.cancel.is_cancelled() {
+            format!("Search cancelled ({matches} matches so far)")
+        } else {
+            format!("Search finished: {matches} matches")
+        };
+        action_tx.send(AppMessage::ShowToast {
+            message,
+            toast_type: ToastType::Info,
+        })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_substring_is_case_insensitive() {
+        let pattern = SearchPattern::parse("Password").unwrap();
+        assert!(pattern.is_match("DB_PASSWORD=hunter2"));
+        assert!(!pattern.is_match("DB_USER=admin"));
+    }
+
+    #[test]
+    fn test_regex_pattern() {
+        let pattern = SearchPattern::parse("/^host=.*\\.internal$/").unwrap();
+        assert!(matches!(pattern, SearchPattern::Regex(_)));
+        assert!(pattern.is_match("HOST=db.internal"));
+        assert!(!pattern.is_match("host=db.example.com"));
+        assert!(SearchPattern::parse("/(unclosed/").is_err());
+    }
+
+    #[test]
+    fn test_find_matches_with_context() {
+        let data = "a\nb\nneedle\nc\nd\ne";
+        let pattern = SearchPattern::parse("needle").unwrap();
+        let matches = find_matches(&Secret::test("db-config"), data, &pattern);
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].line_number, 3);
+        let context: Vec<usize> = matches[0].context.iter().map(|(n, _)| *n).collect();
+        assert_eq!(context, vec![1, 2, 3, 4, 5]);
+    }
+}
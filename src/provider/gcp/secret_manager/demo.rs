@@ -0,0 +1,617 @@
+//! In-memory Secret Manager for `lazycloud --demo`.
+//!
+//! Projects are seeded from embedded fixtures the first time they are opened
+//! and live until the process exits, so changes made in the demo (new
+//! versions, labels, deleted secrets) stay visible across screens and
+//! contexts without touching any cloud account. The local vault keeps its
+//! secrets in the same store, saved in the fixture format.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, LazyLock, Mutex, PoisonError};
+
+use chrono::{TimeDelta, Utc};
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use serde::{Deserialize, Serialize};
+use tokio_util::bytes::Bytes;
+
+use crate::error::Error;
+use crate::provider::gcp::monitoring::MetricPoint;
+use crate::provider::gcp::secret_manager::payload::SecretPayload;
+use crate::provider::gcp::secret_manager::secrets::{
+    IamBinding,
+    IamPolicy,
+    NewSecret,
+    ReplicationConfig,
+    Secret,
+};
+use crate::provider::gcp::secret_manager::versions::SecretVersion;
+use crate::time::format_timestamp;
+
+const FIXTURES: &str = include_str!("fixtures/demo.json");
+
+/// Projects opened in this session, by project ID.
+static PROJECTS: LazyLock<Mutex<HashMap<String, Arc<Mutex<DemoProject>>>>> =
+    LazyLock::new(Mutex::default);
+
+// Version states as formatted from the API enum
+const ENABLED: &str = "Enabled";
+const DISABLED: &str = "Disabled";
+const DESTROYED: &str = "Destroyed";
+
+// === Fixtures ===
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(super) struct ProjectFixture {
+    #[serde(default)]
+    daily_accesses: Vec<u64>,
+    /// IAM permissions the demo user lacks in this project.
+    #[serde(default)]
+    denied_permissions: HashSet<String>,
+    /// Pub/Sub topics in the project, as full topic names.
+    #[serde(default)]
+    topics: Vec<String>,
+    #[serde(default)]
+    secrets: Vec<SecretFixture>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SecretFixture {
+    name: String,
+    created_at: String,
+    expire_time: Option<String>,
+    #[serde(default)]
+    labels: HashMap<String, String>,
+    #[serde(default)]
+    annotations: HashMap<String, String>,
+    #[serde(default)]
+    topics: Vec<String>,
+    /// User-managed replica locations; automatic replication if empty.
+    #[serde(default)]
+    locations: Vec<String>,
+    #[serde(default)]
+    iam: Vec<BindingFixture>,
+    #[serde(default)]
+    versions: Vec<VersionFixture>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BindingFixture {
+    role: String,
+    members: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct VersionFixture {
+    state: String,
+    created_at: String,
+    payload: String,
+}
+
+fn load_fixture(project_id: &str) -> ProjectFixture {
+    let mut fixtures: HashMap<String, ProjectFixture> =
+        serde_json::from_str(FIXTURES).expect("embedded demo fixtures are valid");
+    fixtures.remove(project_id).unwrap_or_default()
+}
+
+// === Backend ===
+
+#[derive(Debug)]
+struct DemoVersion {
+    version: SecretVersion,
+    payload: Bytes,
+}
+
+#[derive(Debug)]
+struct DemoSecret {
+    secret: Secret,
+    versions: Vec<DemoVersion>,
+    bindings: Vec<IamBinding>,
+}
+
+#[derive(Debug)]
+struct DemoProject {
+    daily_accesses: Vec<u64>,
+    denied_permissions: HashSet<String>,
+    topics: Vec<String>,
+    secrets: BTreeMap<String, DemoSecret>,
+}
+
+impl From<ProjectFixture> for DemoProject {
+    fn from(fixture: ProjectFixture) -> Self {
+        let secrets = fixture
+            .secrets
+            .into_iter()
+            .map(|secret| {
+                let replication = if secret.locations.is_empty() {
+                    ReplicationConfig::Automatic
+                } else {
+                    ReplicationConfig::UserManaged {
+                        locations: secret.locations,
+                    }
+                };
+                let versions = secret
+                    .versions
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, version)| DemoVersion {
+                        version: SecretVersion {
+                            version_id: (i + 1).to_string(),
+                            state: version.state,
+                            created_at: version.created_at,
+                        },
+                        payload: Bytes::from(version.payload),
+                    })
+                    .collect();
+                let bindings = secret
+                    .iam
+                    .into_iter()
+                    .map(|binding| IamBinding {
+                        role: binding.role,
+                        members: binding.members,
+                    })
+                    .collect();
+                let demo = DemoSecret {
+                    secret: Secret {
+                        name: secret.name.clone(),
+                        // Filled in by the client, which knows the project
+                        project: String::new(),
+                        replication,
+                        created_at: secret.created_at,
+                        expire_time: secret.expire_time,
+                        labels: secret.labels,
+                        annotations: secret.annotations,
+                        topics: secret.topics,
+                        etag: String::new(),
+                        extras: None,
+                    },
+                    versions,
+                    bindings,
+                };
+                (secret.name, demo)
+            })
+            .collect();
+        Self {
+            daily_accesses: fixture.daily_accesses,
+            denied_permissions: fixture.denied_permissions,
+            topics: fixture.topics,
+            secrets,
+        }
+    }
+}
+
+impl TryFrom<&DemoProject> for ProjectFixture {
+    type Error = color_eyre::Report;
+
+    fn try_from(project: &DemoProject) -> Result<Self> {
+        let secrets = project
+            .secrets
+            .values()
+            .map(|demo| {
+                let locations = match &demo.secret.replication {
+                    ReplicationConfig::Automatic => Vec::new(),
+                    ReplicationConfig::UserManaged { locations } => locations.clone(),
+                };
+                let versions = demo
+                    .versions
+                    .iter()
+                    .map(|version| {
+                        let payload =
+                            String::from_utf8(version.payload.to_vec()).map_err(|_| {
+                                eyre!(
+                                    "Version {} of {} is not text",
+                                    version.version.version_id,
+                                    demo.secret.name
+                                )
+                            })?;
+                        Ok(VersionFixture {
+                            state: version.version.state.clone(),
+                            created_at: version.version.created_at.clone(),
+                            payload,
+                        })
+                    })
+                    .collect::<Result<_>>()?;
+                Ok(SecretFixture {
+                    name: demo.secret.name.clone(),
+                    created_at: demo.secret.created_at.clone(),
+                    expire_time: demo.secret.expire_time.clone(),
+                    labels: demo.secret.labels.clone(),
+                    annotations: demo.secret.annotations.clone(),
+                    topics: demo.secret.topics.clone(),
+                    locations,
+                    iam: demo
+                        .bindings
+                        .iter()
+                        .map(|binding| BindingFixture {
+                            role: binding.role.clone(),
+                            members: binding.members.clone(),
+                        })
+                        .collect(),
+                    versions,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            daily_accesses: project.daily_accesses.clone(),
+            denied_permissions: project.denied_permissions.clone(),
+            topics: project.topics.clone(),
+            secrets,
+        })
+    }
+}
+
+/// Fixture-backed stand-in for the Secret Manager API of one project.
+#[derive(Clone, Debug)]
+pub struct DemoSecretManager {
+    project: Arc<Mutex<DemoProject>>,
+}
+
+impl DemoSecretManager {
+    /// Open the demo project, seeding it from the fixtures on first use.
+    pub fn open(project_id: &str) -> Self {
+        let project = PROJECTS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(project_id.to_string())
+            .or_insert_with(|| Arc::new(Mutex::new(load_fixture(project_id).into())))
+            .clone();
+        Self { project }
+    }
+
+    /// A store of its own holding `fixture`, e.g. the contents of the vault.
+    pub(super) fn from_fixture(fixture: ProjectFixture) -> Self {
+        Self {
+            project: Arc::new(Mutex::new(fixture.into())),
+        }
+    }
+
+    /// Everything in the store, in the fixture format.
+    pub(super) fn to_fixture(&self) -> Result<ProjectFixture> {
+        self.with(|project| ProjectFixture::try_from(&*project))
+    }
+
+    /// Replace everything in the store by `fixture`.
+    pub(super) fn restore(&self, fixture: ProjectFixture) {
+        self.with(|project| *project = fixture.into());
+    }
+
+    fn with<T>(&self, f: impl FnOnce(&mut DemoProject) -> T) -> T {
+        f(&mut self.project.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    pub fn access_counts(&self, days: u32) -> Vec<MetricPoint> {
+        let daily = self.with(|project| project.daily_accesses.clone());
+        let days = days as usize;
+        let start = Utc::now() - TimeDelta::days(1) * i32::try_from(days).unwrap_or(i32::MAX);
+        let padding = days.saturating_sub(daily.len());
+        let recent = daily.iter().skip(daily.len().saturating_sub(days));
+        std::iter::repeat_n(&0, padding)
+            .chain(recent)
+            .zip(0..)
+            .map(|(value, i)| MetricPoint {
+                start: start + TimeDelta::days(i),
+                value: *value,
+            })
+            .collect()
+    }
+
+    /// The subset of `permissions` the demo user holds.
+    pub fn test_permissions(&self, permissions: &[&str]) -> HashSet<String> {
+        self.with(|project| {
+            permissions
+                .iter()
+                .filter(|permission| !project.denied_permissions.contains(**permission))
+                .map(ToString::to_string)
+                .collect()
+        })
+    }
+
+    /// Like [`Self::test_permissions`], for a secret that must exist.
+    pub fn test_secret_permissions(
+        &self,
+        secret_id: &str,
+        permissions: &[&str],
+    ) -> Result<HashSet<String>> {
+        self.with(|project| secret_mut(project, secret_id).map(|_| ()))?;
+        Ok(self.test_permissions(permissions))
+    }
+
+    pub fn list_topics(&self) -> Vec<String> {
+        self.with(|project| project.topics.clone())
+    }
+
+    pub fn list_secrets(&self) -> Vec<Secret> {
+        self.with(|project| {
+            project
+                .secrets
+                .values()
+                .map(|secret| secret.secret.clone())
+                .collect()
+        })
+    }
+
+    pub fn get_secret(&self, secret_id: &str) -> Result<Secret> {
+        self.with(|project| Ok(secret_mut(project, secret_id)?.secret.clone()))
+    }
+
+    pub fn get_iam_policy(&self, secret_id: &str) -> Result<IamPolicy> {
+        self.with(|project| {
+            Ok(IamPolicy {
+                bindings: secret_mut(project, secret_id)?.bindings.clone(),
+            })
+        })
+    }
+
+    pub fn create_secret(&self, options: &NewSecret) -> Result<Secret> {
+        let secret = Secret {
+            name: options.name.clone(),
+            project: String::new(),
+            replication: options.replication.clone(),
+            created_at: now(),
+            expire_time: options.expire_time.map(format_timestamp),
+            labels: options.labels.clone(),
+            annotations: HashMap::new(),
+            topics: Vec::new(),
+            etag: String::new(),
+            extras: None,
+        };
+        let mut demo = DemoSecret {
+            secret: secret.clone(),
+            versions: Vec::new(),
+            bindings: Vec::new(),
+        };
+        if let Some(payload) = &options.payload {
+            add_version(&mut demo, payload.as_bytes());
+        }
+        self.with(|project| {
+            if project.secrets.contains_key(&options.name) {
+                return Err(eyre!("Secret [{}] already exists", options.name));
+            }
+            project.secrets.insert(options.name.clone(), demo);
+            Ok(secret)
+        })
+    }
+
+    pub fn delete_secret(&self, secret_id: &str) -> Result<()> {
+        self.with(|project| {
+            project
+                .secrets
+                .remove(secret_id)
+                .map(|_| ())
+                .ok_or_else(|| not_found(secret_id).into())
+        })
+    }
+
+    pub fn update_labels(
+        &self,
+        secret_id: &str,
+        labels: HashMap<String, String>,
+    ) -> Result<Secret> {
+        self.with(|project| {
+            let secret = secret_mut(project, secret_id)?;
+            secret.secret.labels = labels;
+            Ok(secret.secret.clone())
+        })
+    }
+
+    pub fn update_annotations(
+        &self,
+        secret_id: &str,
+        annotations: HashMap<String, String>,
+    ) -> Result<Secret> {
+        self.with(|project| {
+            let secret = secret_mut(project, secret_id)?;
+            secret.secret.annotations = annotations;
+            Ok(secret.secret.clone())
+        })
+    }
+
+    pub fn update_topics(&self, secret_id: &str, topics: Vec<String>) -> Result<Secret> {
+        self.with(|project| {
+            let secret = secret_mut(project, secret_id)?;
+            secret.secret.topics = topics;
+            Ok(secret.secret.clone())
+        })
+    }
+
+    pub fn list_versions(&self, secret_id: &str) -> Result<Vec<SecretVersion>> {
+        self.with(|project| {
+            Ok(secret_mut(project, secret_id)?
+                .versions
+                .iter()
+                .rev()
+                .map(|version| version.version.clone())
+                .collect())
+        })
+    }
+
+    /// Payload of a version; `version_id` may be `latest`.
+    pub fn access_version_bytes(&self, secret_id: &str, version_id: &str) -> Result<Bytes> {
+        self.with(|project| {
+            let version = version_mut(project, secret_id, version_id)?;
+            if version.version.state != ENABLED {
+                return Err(eyre!(
+                    "Secret version [{secret_id}/{}] is in {} state",
+                    version.version.version_id,
+                    version.version.state.to_uppercase()
+                ));
+            }
+            Ok(version.payload.clone())
+        })
+    }
+
+    pub fn access_version(&self, secret_id: &str, version_id: &str) -> Result<SecretPayload> {
+        let data = self.access_version_bytes(secret_id, version_id)?;
+        Ok(SecretPayload {
+            data: String::from_utf8_lossy(&data).to_string(),
+            is_binary: false,
+        })
+    }
+
+    pub fn add_secret_version(&self, secret_id: &str, payload: &[u8]) -> Result<SecretVersion> {
+        self.with(|project| Ok(add_version(secret_mut(project, secret_id)?, payload)))
+    }
+
+    pub fn set_version_state(
+        &self,
+        secret_id: &str,
+        version_id: &str,
+        state: VersionState,
+    ) -> Result<SecretVersion> {
+        self.with(|project| {
+            let version = version_mut(project, secret_id, version_id)?;
+            if version.version.state == DESTROYED {
+                return Err(eyre!(
+                    "Secret version [{secret_id}/{version_id}] is in DESTROYED state"
+                ));
+            }
+            version.version.state = match state {
+                VersionState::Enabled => ENABLED,
+                VersionState::Disabled => DISABLED,
+                VersionState::Destroyed => {
+                    version.payload = Bytes::new();
+                    DESTROYED
+                }
+            }
+            .to_string();
+            Ok(version.version.clone())
+        })
+    }
+}
+
+/// Target state for [`DemoSecretManager::set_version_state`].
+#[derive(Debug, Clone, Copy)]
+pub enum VersionState {
+    Enabled,
+    Disabled,
+    Destroyed,
+}
+
+// === Helper Functions ===
+
+fn now() -> String {
+    format_timestamp(Utc::now())
+}
+
+fn not_found(secret_id: &str) -> Error {
+    Error::NotFound(format!("Secret [{secret_id}] not found"))
+}
+
+fn secret_mut<'a>(project: &'a mut DemoProject, secret_id: &str) -> Result<&'a mut DemoSecret> {
+    project
+        .secrets
+        .get_mut(secret_id)
+        .ok_or_else(|| not_found(secret_id).into())
+}
+
+/// A version by ID, or the newest one for `latest`.
+fn version_mut<'a>(
+    project: &'a mut DemoProject,
+    secret_id: &str,
+    version_id: &str,
+) -> Result<&'a mut DemoVersion> {
+    let versions = &mut secret_mut(project, secret_id)?.versions;
+    if version_id == "latest" {
+        versions.last_mut()
+    } else {
+        versions
+            .iter_mut()
+            .find(|version| version.version.version_id == version_id)
+    }
+    .ok_or_else(|| {
+        Error::NotFound(format!(
+            "Secret version [{secret_id}/{version_id}] not found"
+        ))
+        .into()
+    })
+}
+
+fn add_version(secret: &mut DemoSecret, payload: &[u8]) -> SecretVersion {
+    let version = SecretVersion {
+        version_id: (secret.versions.len() + 1).to_string(),
+        state: ENABLED.to_string(),
+        created_at: now(),
+    };
+    secret.versions.push(DemoVersion {
+        version: version.clone(),
+        payload: Bytes::copy_from_slice(payload),
+    });
+    version
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixtures_parse() {
+        let fixtures: HashMap<String, ProjectFixture> = serde_json::from_str(FIXTURES).unwrap();
+        assert!(fixtures.values().all(|project| !project.secrets.is_empty()));
+    }
+
+    #[test]
+    fn test_fixture_round_trip() {
+        let demo = DemoSecretManager::from_fixture(load_fixture("acme-dev"));
+        let json = serde_json::to_string(&demo.to_fixture().unwrap()).unwrap();
+        let copy = DemoSecretManager::from_fixture(serde_json::from_str(&json).unwrap());
+
+        let names = |store: &DemoSecretManager| -> Vec<String> {
+            store
+                .list_secrets()
+                .into_iter()
+                .map(|secret| secret.name)
+                .collect()
+        };
+        assert_eq!(names(&copy), names(&demo));
+        let name = &names(&demo)[0];
+        assert_eq!(
+            copy.list_versions(name).unwrap().len(),
+            demo.list_versions(name).unwrap().len()
+        );
+
+        demo.add_secret_version(name, &[0xff, 0xfe]).unwrap();
+        assert!(demo.to_fixture().is_err());
+    }
+
+    #[test]
+    fn test_version_lifecycle() {
+        let demo = DemoSecretManager {
+            project: Arc::new(Mutex::new(ProjectFixture::default().into())),
+        };
+        demo.create_secret(&NewSecret {
+            name: "token".to_string(),
+            payload: Some("v1".to_string()),
+            ..NewSecret::default()
+        })
+        .unwrap();
+        assert!(
+            demo.create_secret(&NewSecret {
+                name: "token".to_string(),
+                ..NewSecret::default()
+            })
+            .is_err()
+        );
+
+        demo.add_secret_version("token", b"v2").unwrap();
+        assert_eq!(demo.access_version("token", "latest").unwrap().data, "v2");
+
+        demo.set_version_state("token", "2", VersionState::Disabled)
+            .unwrap();
+        assert!(demo.access_version_bytes("token", "latest").is_err());
+        assert_eq!(demo.access_version("token", "1").unwrap().data, "v1");
+
+        demo.set_version_state("token", "1", VersionState::Destroyed)
+            .unwrap();
+        assert!(
+            demo.set_version_state("token", "1", VersionState::Enabled)
+                .is_err()
+        );
+        let states: Vec<String> = demo
+            .list_versions("token")
+            .unwrap()
+            .into_iter()
+            .map(|version| version.state)
+            .collect();
+        assert_eq!(states, vec![DISABLED, DESTROYED]);
+    }
+}
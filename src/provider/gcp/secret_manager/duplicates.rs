@@ -0,0 +1,441 @@
+//! Finding secrets whose latest payloads are identical, e.g. credentials
+//! copied from one secret into another.
+//!
+//! Payloads are only hashed, never kept. The hash is keyed anew for every
+//! run, so it says nothing about a payload outside of it.
+
+use std::collections::HashMap;
+use std::hash::{BuildHasher, RandomState};
+use std::pin::pin;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use crossterm::event::KeyEvent;
+use futures::{StreamExt, stream};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::Style;
+use ratatui::widgets::Cell;
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::warn;
+
+use crate::Theme;
+use crate::app::AppMessage;
+use crate::commands::{Command, Progress};
+use crate::config::{KeyResolver, NavAction, SearchAction};
+use crate::cost::{self, Operation};
+use crate::provider::gcp::secret_manager::SecretManager;
+use crate::provider::gcp::secret_manager::client::SecretManagerClient;
+use crate::provider::gcp::secret_manager::payload::PayloadMsg;
+use crate::provider::gcp::secret_manager::secrets::Secret;
+use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
+use crate::search::Matcher;
+use crate::service::ServiceMsg;
+use crate::ui::{
+    ColumnDef,
+    Component,
+    ConfirmDialog,
+    ConfirmEvent,
+    EventResult,
+    Keybinding,
+    Modal,
+    Result,
+    Screen,
+    Table,
+    TableEvent,
+    TableRow,
+};
+
+/// Maximum number of payloads fetched at the same time.
+const MAX_CONCURRENT_FETCHES: usize = 8;
+
+// === Models ===
+
+/// A secret sharing its latest payload with others in the same group.
+#[derive(Debug, Clone)]
+pub struct DuplicateRow {
+    /// 1-based, groups with the most secrets first.
+    pub group: usize,
+    /// Secrets in the group, including this one.
+    pub copies: usize,
+    pub secret: Secret,
+}
+
+impl TableRow for DuplicateRow {
+    fn columns() -> &'static [ColumnDef] {
+        static COLUMNS: &[ColumnDef] = &[
+            ColumnDef::new("Group", Constraint::Length(7)),
+            ColumnDef::new("Secret", Constraint::Min(20)),
+            ColumnDef::new("Copies", Constraint::Length(8)),
+            ColumnDef::new("Created", Constraint::Length(18)),
+        ];
+        COLUMNS
+    }
+
+    fn render_cells(&self, theme: &Theme) -> Vec<Cell<'static>> {
+        // Alternate colors so neighbouring groups are told apart
+        let color = if self.group % 2 == 1 {
+            theme.peach()
+        } else {
+            theme.mauve()
+        };
+        vec![
+            Cell::from(format!("#{}", self.group)).style(Style::default().fg(color)),
+            Cell::from(self.secret.name.clone()),
+            Cell::from(self.copies.to_string()),
+            Cell::from(self.secret.created_at.clone()),
+        ]
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        Matcher::new().matches(&self.secret.name, query)
+    }
+
+    fn details(&self) -> Vec<(&'static str, String)> {
+        let mut details = vec![
+            ("Group", format!("#{}", self.group)),
+            ("Copies", self.copies.to_string()),
+        ];
+        details.extend(self.secret.details());
+        details
+    }
+}
+
+/// Rows for every group of two or more secrets with the same payload hash.
+///
+/// Larger groups come first, ties by the name of their first secret. Within
+/// a group, secrets are sorted by name.
+pub fn group_duplicates(hashes: Vec<(Secret, u64)>) -> Vec<DuplicateRow> {
+    let mut by_hash: HashMap<u64, Vec<Secret>> = HashMap::new();
+    for (secret, hash) in hashes {
+        by_hash.entry(hash).or_default().push(secret);
+    }
+
+    let mut groups: Vec<Vec<Secret>> = by_hash
+        .into_values()
+        .filter(|secrets| secrets.len() > 1)
+        .map(|mut secrets| {
+            secrets.sort_by(|a, b| a.name.cmp(&b.name));
+            secrets
+        })
+        .collect();
+    groups.sort_by(|a, b| {
+        b.len()
+            .cmp(&a.len())
+            .then_with(|| a[0].name.cmp(&b[0].name))
+    });
+
+    groups
+        .into_iter()
+        .enumerate()
+        .flat_map(|(i, secrets)| {
+            let copies = secrets.len();
+            secrets.into_iter().map(move |secret| DuplicateRow {
+                group: i + 1,
+                copies,
+                secret,
+            })
+        })
+        .collect()
+}
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum DuplicatesMsg {
+    /// Ask before reading the payloads of these secrets.
+    Start(Vec<Secret>),
+    Run(Vec<Secret>),
+    Completed {
+        rows: Vec<DuplicateRow>,
+        /// Secrets whose payload could not be read, e.g. without versions.
+        unreadable: usize,
+    },
+    ViewPayload(Box<Secret>),
+}
+
+impl From<DuplicatesMsg> for SecretManagerMsg {
+    fn from(msg: DuplicatesMsg) -> Self {
+        Self::Duplicates(msg)
+    }
+}
+
+impl From<DuplicatesMsg> for EventResult<SecretManagerMsg> {
+    fn from(msg: DuplicatesMsg) -> Self {
+        Self::Event(SecretManagerMsg::Duplicates(msg))
+    }
+}
+
+// === Screens ===
+
+pub struct DuplicatesScreen {
+    table: Table<DuplicateRow>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl DuplicatesScreen {
+    pub fn new(rows: Vec<DuplicateRow>, unreadable: usize, resolver: Arc<KeyResolver>) -> Self {
+        let groups = rows.last().map_or(0, |row| row.group);
+        let mut title = format!(" Duplicate Payloads: {groups} groups ");
+        if unreadable > 0 {
+            title = format!("{title}({unreadable} unreadable) ");
+        }
+        Self {
+            table: Table::new(rows, resolver.clone()).with_title(title),
+            resolver,
+        }
+    }
+}
+
+impl Screen for DuplicatesScreen {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        let result = self.table.handle_key(key)?;
+        if let EventResult::Event(TableEvent::Activated(row)) = result {
+            return Ok(DuplicatesMsg::ViewPayload(Box::new(row.secret)).into());
+        }
+        if result.is_consumed() {
+            return Ok(EventResult::Consumed);
+        }
+
+        Ok(EventResult::Ignored)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.table.render(frame, area, theme);
+    }
+
+    fn breadcrumbs(&self) -> Vec<String> {
+        vec!["Duplicates".to_string()]
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        vec![
+            Keybinding::hint(self.resolver.display_nav(NavAction::Select), "Payload"),
+            Keybinding::hint(self.resolver.display_search(SearchAction::Toggle), "Search"),
+        ]
+    }
+}
+
+// === Dialogs ===
+
+/// Confirms reading every payload, with an estimate of the access charges.
+pub struct DuplicatesDialog {
+    secrets: Vec<Secret>,
+    dialog: ConfirmDialog,
+}
+
+impl DuplicatesDialog {
+    /// `uncached` is the number of payloads not fetched before, only those
+    /// are read and billed.
+    pub fn new(secrets: Vec<Secret>, uncached: usize, resolver: Arc<KeyResolver>) -> Self {
+        let dialog = ConfirmDialog::new(
+            format!(
+                "Read the latest payload of {} secrets to find identical ones?",
+                secrets.len()
+            ),
+            resolver,
+        )
+        .with_title("Find Duplicates")
+        .with_confirm_text("Compare")
+        .with_cancel_text("Cancel")
+        .with_cost(cost::estimate_line(Operation::SecretAccess, uncached));
+
+        Self { secrets, dialog }
+    }
+}
+
+impl Modal for DuplicatesDialog {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        Ok(match self.dialog.handle_key(key)? {
+            EventResult::Event(ConfirmEvent::Confirmed) => {
+                DuplicatesMsg::Run(self.secrets.clone()).into()
+            }
+            EventResult::Event(ConfirmEvent::Cancelled) => SecretManagerMsg::DialogCancelled.into(),
+            _ => EventResult::Consumed,
+        })
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.dialog.render(frame, area, theme);
+    }
+}
+
+// === Update Logic ===
+
+pub(super) fn update(state: &mut SecretManager, msg: DuplicatesMsg) -> Result<ServiceMsg> {
+    match msg {
+        DuplicatesMsg::Start(secrets) => {
+            if secrets.len() < 2 {
+                return Ok(ServiceMsg::Idle);
+            }
+            let uncached = secrets
+                .iter()
+                .filter(|secret| state.get_cached_payload(secret, None).is_none())
+                .count();
+            state.display_overlay(DuplicatesDialog::new(
+                secrets,
+                uncached,
+                state.get_resolver(),
+            ));
+            Ok(ServiceMsg::Idle)
+        }
+
+        DuplicatesMsg::Run(secrets) => {
+            state.close_overlay();
+            // Payloads already fetched are not read again
+            let seed = RandomState::new();
+            let (cached, secrets): (Vec<_>, Vec<_>) = secrets
+                .into_iter()
+                .map(|secret| {
+                    let payload = state.get_cached_payload(&secret, None);
+                    (secret, payload)
+                })
+                .partition(|(_, payload)| payload.is_some());
+            let hashes = cached
+                .into_iter()
+                .filter_map(|(secret, payload)| {
+                    let hash = hash_payload(&seed, &payload?.data)?;
+                    Some((secret, hash))
+                })
+                .collect();
+            let secrets: Vec<Secret> = secrets.into_iter().map(|(secret, _)| secret).collect();
+
+            Ok(FindDuplicatesCmd {
+                client: state.get_client()?,
+                progress: Progress::new(secrets.len()),
+                secrets,
+                seed,
+                hashes,
+                tx: state.get_msg_sender(),
+            }
+            .into())
+        }
+
+        DuplicatesMsg::Completed { rows, unreadable } => {
+            state.push_view(DuplicatesScreen::new(
+                rows,
+                unreadable,
+                state.get_resolver(),
+            ));
+            Ok(ServiceMsg::Idle)
+        }
+
+        DuplicatesMsg::ViewPayload(secret) => {
+            state.queue(
+                PayloadMsg::Load {
+                    secret: *secret,
+                    version: None,
+                }
+                .into(),
+            );
+            Ok(ServiceMsg::Idle)
+        }
+    }
+}
+
+/// Empty payloads are left out, they are alike without being copies.
+fn hash_payload(seed: &RandomState, data: &str) -> Option<u64> {
+    (!data.is_empty()).then(|| seed.hash_one(data))
+}
+
+// === Commands ===
+
+struct FindDuplicatesCmd {
+    client: SecretManagerClient,
+    secrets: Vec<Secret>,
+    /// Keys the hash, the same for every payload of a run.
+    seed: RandomState,
+    /// Hashes of the payloads that were cached.
+    hashes: Vec<(Secret, u64)>,
+    progress: Progress,
+    tx: UnboundedSender<SecretManagerMsg>,
+}
+
+#[async_trait]
+impl Command for FindDuplicatesCmd {
+    fn name(&self) -> String {
+        format!("Comparing payloads of {} secrets", self.secrets.len())
+    }
+
+    fn progress(&self) -> Option<Progress> {
+        Some(self.progress.clone())
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let mut fetches = pin!(
+            stream::iter(self.secrets.clone())
+                .map(|secret| {
+                    let client = self.client.in_project(&secret.project);
+                    async move {
+                        let result = client.access_latest_version(&secret.name).await;
+                        (secret, result)
+                    }
+                })
+                .buffer_unordered(MAX_CONCURRENT_FETCHES)
+        );
+
+        let mut hashes = self.hashes.clone();
+        let mut unreadable = 0;
+        while let Some((secret, result)) = fetches.next().await {
+            match result {
+                Ok(payload) => {
+                    if let Some(hash) = hash_payload(&self.seed, &payload.data) {
+                        hashes.push((secret, hash));
+                    }
+                }
+                Err(err) => {
+                    warn!(secret = %secret.name, %err, "Could not read payload to compare");
+                    unreadable += 1;
+                }
+            }
+            self.progress.advance();
+        }
+
+        let rows = group_duplicates(hashes);
+        self.tx
+            .send(DuplicatesMsg::Completed { rows, unreadable }.into())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_group_duplicates() {
+        let seed = RandomState::new();
+        let payloads = [
+            ("smtp-b", "hunter2"),
+            ("api-key", "sk_live_1"),
+            ("smtp-a", "hunter2"),
+            ("unique", "only-once"),
+            ("smtp-c", "hunter2"),
+            ("api-key-copy", "sk_live_1"),
+            ("empty-a", ""),
+            ("empty-b", ""),
+        ];
+        let hashes = payloads
+            .iter()
+            .filter_map(|(name, data)| Some((Secret::test(name), hash_payload(&seed, data)?)))
+            .collect();
+
+        let rows = group_duplicates(hashes);
+        let names: Vec<&str> = rows.iter().map(|row| row.secret.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["smtp-a", "smtp-b", "smtp-c", "api-key", "api-key-copy"]
+        );
+        let groups: Vec<(usize, usize)> = rows.iter().map(|row| (row.group, row.copies)).collect();
+        assert_eq!(groups, vec![(1, 3), (1, 3), (1, 3), (2, 2), (2, 2)]);
+    }
+}
@@ -0,0 +1,142 @@
+//! Filling in what the secret list leaves out, such as rotation and the
+//! number of versions, for the rows in view.
+//!
+//! The list is shown as soon as it is loaded. Rows scrolled into view are
+//! then fetched one by one in the background and updated in place. A row
+//! changed by a refresh is fetched again the next time it is in view, one
+//! that failed only once the list is loaded again.
+
+use std::pin::pin;
+
+use async_trait::async_trait;
+use futures::{StreamExt, stream};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::debug;
+
+use crate::app::AppMessage;
+use crate::commands::Command;
+use crate::provider::gcp::secret_manager::SecretManager;
+use crate::provider::gcp::secret_manager::client::SecretManagerClient;
+use crate::provider::gcp::secret_manager::secrets::{Secret, SecretListScreen};
+use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
+use crate::service::ServiceMsg;
+use crate::ui::{EventResult, Result};
+
+/// Secrets fetched at the same time.
+const MAX_CONCURRENT_FETCHES: usize = 4;
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum EnrichMsg {
+    /// Fetch the extras of these secrets, shown without them.
+    Load(Vec<Secret>),
+    Loaded(Box<Secret>),
+}
+
+impl From<EnrichMsg> for SecretManagerMsg {
+    fn from(msg: EnrichMsg) -> Self {
+        Self::Enrich(msg)
+    }
+}
+
+impl From<EnrichMsg> for EventResult<SecretManagerMsg> {
+    fn from(msg: EnrichMsg) -> Self {
+        Self::Event(SecretManagerMsg::Enrich(msg))
+    }
+}
+
+// === Update Logic ===
+
+pub(super) fn update(state: &mut SecretManager, msg: EnrichMsg) -> Result<ServiceMsg> {
+    match msg {
+        EnrichMsg::Load(secrets) => {
+            let secrets: Vec<Secret> = secrets
+                .into_iter()
+                .filter(|secret| secret.extras.is_none() && state.start_enriching(secret))
+                .collect();
+            if secrets.is_empty() {
+                return Ok(ServiceMsg::Idle);
+            }
+            Ok(EnrichSecretsCmd {
+                client: state.get_client()?,
+                secrets,
+                tx: state.get_msg_sender(),
+            }
+            .into())
+        }
+
+        EnrichMsg::Loaded(secret) => {
+            state.finish_enriching(&secret);
+            if let Some(screen) = state.current_view_mut::<SecretListScreen>() {
+                screen.set_enriched(*secret);
+            }
+            Ok(ServiceMsg::Idle)
+        }
+    }
+}
+
+/// Fetch the extras of the secrets in view that don't have them yet.
+pub(super) fn load_visible(state: &mut SecretManager) {
+    if let Some(screen) = state.current_view_mut::<SecretListScreen>() {
+        let mut secrets = screen.visible_without_extras();
+        secrets.retain(|secret| !state.is_enriching(secret));
+        if !secrets.is_empty() {
+            state.queue(EnrichMsg::Load(secrets).into());
+        }
+    }
+}
+
+// === Commands ===
+
+/// Fetches secrets with their extras, sending each as it arrives.
+struct EnrichSecretsCmd {
+    client: SecretManagerClient,
+    secrets: Vec<Secret>,
+    tx: UnboundedSender<SecretManagerMsg>,
+}
+
+#[async_trait]
+impl Command for EnrichSecretsCmd {
+    fn name(&self) -> String {
+        match self.secrets.as_slice() {
+            [secret] => format!("Loading details of '{}'", secret.name),
+            secrets => format!("Loading details of {} secrets", secrets.len()),
+        }
+    }
+
+    async fn execute(
+        self: Box<Self>,
+        _action_tx: UnboundedSender<AppMessage>,
+    ) -> color_eyre::Result<()> {
+        let mut fetches = pin!(
+            stream::iter(self.secrets)
+                .map(|secret| {
+                    let client = self.client.in_project(&secret.project);
+                    async move {
+                        let result = client.get_secret_with_extras(&secret.name).await;
+                        (secret.name, result)
+                    }
+                })
+                .buffer_unordered(MAX_CONCURRENT_FETCHES)
+        );
+
+        // Only the list is missing something, so failures are not reported
+        while let Some((name, result)) = fetches.next().await {
+            match result {
+                // The service may have been closed in the meantime
+                Ok(secret) => {
+                    if self
+                        .tx
+                        .send(EnrichMsg::Loaded(Box::new(secret)).into())
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+                Err(err) => debug!(secret = %name, %err, "Could not load secret details"),
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,493 @@
+//! Look a secret up in several contexts at once, e.g. to check whether it
+//! exists in dev, staging and prod.
+//!
+//! Only reads: each picked context gets its own client and is asked for the
+//! secret's metadata, never its payload.
+
+use std::sync::Arc;
+
+use color_eyre::eyre::eyre;
+use crossterm::event::{KeyCode, KeyEvent};
+use futures::{StreamExt, stream};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Clear, ListItem, Paragraph};
+
+use crate::Theme;
+use crate::commands::StreamCmd;
+use crate::config::{GlobalAction, KeyResolver, SearchAction};
+use crate::context::{CloudContext, GcpContext, load_contexts};
+use crate::error::Error;
+use crate::provider::gcp::secret_manager::SecretManager;
+use crate::provider::gcp::secret_manager::client::SecretManagerClient;
+use crate::provider::gcp::secret_manager::secrets::Secret;
+use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
+use crate::search::Matcher;
+use crate::service::ServiceMsg;
+use crate::ui::{
+    ColumnDef,
+    Component,
+    EventResult,
+    Keybinding,
+    List,
+    ListEvent,
+    ListRow,
+    Modal,
+    Result,
+    Screen,
+    Table,
+    TableRow,
+};
+
+/// Contexts queried at the same time.
+const MAX_CONCURRENT_LOOKUPS: usize = 4;
+
+// === Models ===
+
+/// What a context knows about the secret.
+#[derive(Debug, Clone)]
+pub enum Lookup {
+    /// Not answered yet.
+    Pending,
+    Found(Box<Secret>),
+    Missing,
+    Failed(String),
+}
+
+/// The lookup in one context, a row of [`FanOutScreen`].
+#[derive(Debug, Clone)]
+pub struct ContextLookup {
+    pub context: GcpContext,
+    pub lookup: Lookup,
+}
+
+impl ContextLookup {
+    const fn status(&self) -> &'static str {
+        match self.lookup {
+            Lookup::Pending => "pending",
+            Lookup::Found(_) => "found",
+            Lookup::Missing => "missing",
+            Lookup::Failed(_) => "error",
+        }
+    }
+}
+
+impl TableRow for ContextLookup {
+    fn columns() -> &'static [ColumnDef] {
+        static COLUMNS: &[ColumnDef] = &[
+            ColumnDef::new("Context", Constraint::Min(16)),
+            ColumnDef::new("Project", Constraint::Min(20)),
+            ColumnDef::new("Status", Constraint::Length(8)),
+            ColumnDef::new("Created", Constraint::Length(18)),
+            ColumnDef::new("Replication", Constraint::Length(14)),
+            ColumnDef::new("Labels", Constraint::Min(10)),
+        ];
+        COLUMNS
+    }
+
+    fn render_cells(&self, theme: &Theme) -> Vec<Cell<'static>> {
+        let status_color = match self.lookup {
+            Lookup::Pending => theme.overlay1(),
+            Lookup::Found(_) => theme.green(),
+            Lookup::Missing => theme.yellow(),
+            Lookup::Failed(_) => theme.red(),
+        };
+        let mut cells = vec![
+            Cell::from(self.context.display_name.clone()),
+            Cell::from(self.context.project_id.clone()),
+            Cell::from(self.status()).style(Style::default().fg(status_color)),
+        ];
+        match &self.lookup {
+            Lookup::Found(secret) => {
+                let mut labels: Vec<String> = secret
+                    .labels
+                    .iter()
+                    .map(|(key, value)| format!("{key}={value}"))
+                    .collect();
+                labels.sort();
+                cells.extend([
+                    Cell::from(secret.created_at.clone()),
+                    Cell::from(secret.replication.short_display()),
+                    Cell::from(labels.join(", ")),
+                ]);
+            }
+            Lookup::Pending | Lookup::Missing => {}
+            Lookup::Failed(message) => {
+                cells.extend([
+                    Cell::from(""),
+                    Cell::from(""),
+                    Cell::from(message.clone()).style(Style::default().fg(theme.overlay1())),
+                ]);
+            }
+        }
+        cells
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        let matcher = Matcher::new();
+        matcher.matches(&self.context.display_name, query)
+            || matcher.matches(&self.context.project_id, query)
+            || matcher.matches(self.status(), query)
+    }
+
+    fn details(&self) -> Vec<(&'static str, String)> {
+        match &self.lookup {
+            Lookup::Failed(message) => vec![("Error", message.clone())],
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ContextItem {
+    context: GcpContext,
+    checked: bool,
+}
+
+impl ListRow for ContextItem {
+    fn render_row(&self, theme: &Theme) -> ListItem<'static> {
+        let (mark, style) = if self.checked {
+            ("[x] ", Style::default().fg(theme.green()))
+        } else {
+            ("[ ] ", Style::default().fg(theme.text()))
+        };
+        ListItem::new(Line::from(vec![
+            Span::styled(mark, style),
+            Span::styled(self.context.display_name.clone(), style),
+            Span::styled(
+                format!("  {}", self.context.project_id),
+                Style::default().fg(theme.overlay1()),
+            ),
+        ]))
+    }
+}
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum FanOutMsg {
+    /// Pick the contexts to look the secret up in.
+    Start(Secret),
+    Run {
+        secret: Secret,
+        contexts: Vec<GcpContext>,
+    },
+    /// A context answered, streamed in while the others are still asked.
+    Answered(ContextLookup),
+}
+
+impl From<FanOutMsg> for SecretManagerMsg {
+    fn from(msg: FanOutMsg) -> Self {
+        Self::FanOut(msg)
+    }
+}
+
+impl From<FanOutMsg> for EventResult<SecretManagerMsg> {
+    fn from(msg: FanOutMsg) -> Self {
+        Self::Event(SecretManagerMsg::FanOut(msg))
+    }
+}
+
+// === Dialogs ===
+
+/// Checklist of the GCP contexts to look the secret up in.
+pub struct ContextPickerDialog {
+    secret: Secret,
+    contexts: List<ContextItem>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl ContextPickerDialog {
+    /// Lists `contexts` with `current` checked.
+    pub fn new(
+        secret: Secret,
+        contexts: Vec<GcpContext>,
+        current: &str,
+        resolver: Arc<KeyResolver>,
+    ) -> Self {
+        let items = contexts
+            .into_iter()
+            .map(|context| ContextItem {
+                checked: context.display_name == current,
+                context,
+            })
+            .collect();
+        Self {
+            secret,
+            contexts: List::new(items, resolver.clone()),
+            resolver,
+        }
+    }
+
+    fn toggle_selected(&mut self) {
+        let Some(selected) = self
+            .contexts
+            .selected()
+            .map(|item| item.context.display_name.clone())
+        else {
+            return;
+        };
+        let items = self
+            .contexts
+            .items()
+            .iter()
+            .cloned()
+            .map(|mut item| {
+                if item.context.display_name == selected {
+                    item.checked = !item.checked;
+                }
+                item
+            })
+            .collect();
+        self.contexts.set_items(items);
+    }
+
+    fn checked(&self) -> Vec<GcpContext> {
+        self.contexts
+            .items()
+            .iter()
+            .filter(|item| item.checked)
+            .map(|item| item.context.clone())
+            .collect()
+    }
+}
+
+impl Modal for ContextPickerDialog {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if key.code == KeyCode::Char(' ') {
+            self.toggle_selected();
+            return Ok(EventResult::Consumed);
+        }
+        if self.resolver.matches_global(&key, GlobalAction::Back) {
+            return Ok(SecretManagerMsg::DialogCancelled.into());
+        }
+        if let EventResult::Event(ListEvent::Activated(item)) = self.contexts.handle_key(key)? {
+            // Without any checked, look in the highlighted context alone
+            let mut contexts = self.checked();
+            if contexts.is_empty() {
+                contexts.push(item.context);
+            }
+            return Ok(FanOutMsg::Run {
+                secret: self.secret.clone(),
+                contexts,
+            }
+            .into());
+        }
+        Ok(EventResult::Consumed)
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = area.centered(Constraint::Percentage(60), Constraint::Percentage(60));
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(format!(" Find '{}' in Contexts ", self.secret.name))
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.lavender()))
+            .style(Style::default().bg(theme.base()));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [hint_area, list_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Min(1)]).areas(inner);
+        frame.render_widget(
+            Paragraph::new("Space to pick contexts, Enter to look them up")
+                .style(Style::default().fg(theme.subtext0())),
+            hint_area,
+        );
+        self.contexts.render(frame, list_area, theme);
+    }
+}
+
+// === Screens ===
+
+/// One row per picked context, in the order they were listed, filled in as
+/// the contexts answer.
+pub struct FanOutScreen {
+    secret: Secret,
+    table: Table<ContextLookup>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl FanOutScreen {
+    /// Rows for `contexts`, each pending until it answers.
+    pub fn new(secret: Secret, contexts: Vec<GcpContext>, resolver: Arc<KeyResolver>) -> Self {
+        let rows = contexts
+            .into_iter()
+            .map(|context| ContextLookup {
+                context,
+                lookup: Lookup::Pending,
+            })
+            .collect();
+        let mut screen = Self {
+            secret,
+            table: Table::new(rows, resolver.clone()),
+            resolver,
+        };
+        screen.update_title();
+        screen
+    }
+
+    /// Fill in the row of the context that answered.
+    fn set_answer(&mut self, answer: ContextLookup) {
+        let mut rows = self.table.items().to_vec();
+        if let Some(row) = rows
+            .iter_mut()
+            .find(|row| row.context.display_name == answer.context.display_name)
+        {
+            *row = answer;
+        }
+        self.table.set_items(rows);
+        self.update_title();
+    }
+
+    fn update_title(&mut self) {
+        let rows = self.table.items();
+        let found = rows
+            .iter()
+            .filter(|row| matches!(row.lookup, Lookup::Found(_)))
+            .count();
+        let pending = rows
+            .iter()
+            .filter(|row| matches!(row.lookup, Lookup::Pending))
+            .count();
+        let pending = if pending > 0 {
+            format!(", {pending} pending")
+        } else {
+            String::new()
+        };
+        let title = format!(
+            " '{}': found in {found} of {} contexts{pending} ",
+            self.secret.name,
+            rows.len()
+        );
+        self.table.set_title(title);
+    }
+}
+
+impl Screen for FanOutScreen {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if self.table.handle_key(key)?.is_consumed() {
+            return Ok(EventResult::Consumed);
+        }
+        Ok(EventResult::Ignored)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.table.render(frame, area, theme);
+    }
+
+    fn breadcrumbs(&self) -> Vec<String> {
+        vec![self.secret.name.clone(), "Contexts".to_string()]
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        vec![Keybinding::hint(
+            self.resolver.display_search(SearchAction::Toggle),
+            "Search",
+        )]
+    }
+}
+
+// === Update Logic ===
+
+pub(super) fn update(state: &mut SecretManager, msg: FanOutMsg) -> Result<ServiceMsg> {
+    match msg {
+        FanOutMsg::Start(secret) => {
+            let contexts: Vec<GcpContext> = load_contexts()
+                .into_iter()
+                .map(|ctx| match ctx {
+                    CloudContext::Gcp(gcp) => gcp,
+                })
+                .collect();
+            let current = state.get_context().display_name.clone();
+            if contexts.iter().all(|ctx| ctx.display_name == current) {
+                return Err(eyre!("No other GCP contexts configured to look in"));
+            }
+
+            state.display_overlay(ContextPickerDialog::new(
+                secret,
+                contexts,
+                &current,
+                state.get_resolver(),
+            ));
+            Ok(ServiceMsg::Idle)
+        }
+
+        FanOutMsg::Run { secret, contexts } => {
+            state.close_overlay();
+            state.push_view(FanOutScreen::new(
+                secret.clone(),
+                contexts.clone(),
+                state.get_resolver(),
+            ));
+            Ok(look_up_all(secret, contexts, state).into())
+        }
+
+        FanOutMsg::Answered(answer) => {
+            if let Some(screen) = state.current_view_mut::<FanOutScreen>() {
+                screen.set_answer(answer);
+            }
+            Ok(ServiceMsg::Idle)
+        }
+    }
+}
+
+// === Commands ===
+
+/// Read the secret's metadata in `context`; a missing secret is no failure.
+async fn look_up(context: &GcpContext, secret_id: &str) -> Lookup {
+    let result = match SecretManagerClient::new(context).await {
+        Ok(client) => client.get_secret(secret_id).await,
+        Err(err) => Err(err),
+    };
+    match result {
+        Ok(secret) => Lookup::Found(Box::new(secret)),
+        Err(err) => match Error::from_report(&err) {
+            Error::NotFound(_) => Lookup::Missing,
+            err => Lookup::Failed(err.message().to_string()),
+        },
+    }
+}
+
+/// Ask the contexts a few at a time, streaming each answer to the screen
+/// until it is left.
+fn look_up_all(
+    secret: Secret,
+    contexts: Vec<GcpContext>,
+    state: &mut SecretManager,
+) -> StreamCmd<SecretManagerMsg> {
+    let name = format!(
+        "Looking up '{}' in {} contexts",
+        secret.name,
+        contexts.len()
+    );
+    let secret_id = secret.name.clone();
+    let answers = stream::iter(contexts)
+        .map(move |context| {
+            let secret_id = secret_id.clone();
+            async move {
+                let lookup = look_up(&context, &secret_id).await;
+                Ok(FanOutMsg::Answered(ContextLookup { context, lookup }).into())
+            }
+        })
+        .buffer_unordered(MAX_CONCURRENT_LOOKUPS);
+    StreamCmd::new(name, answers, state.get_msg_sender(), state.view_token())
+        .with_resource(secret.name)
+}
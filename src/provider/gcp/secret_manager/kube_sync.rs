@@ -0,0 +1,1023 @@
+//! Sync a secret into a Kubernetes Secret, e.g. to hand a database password
+//! to the workloads of a cluster.
+//!
+//! The cluster is reached through `kubectl`, so the contexts and credentials
+//! of its kubeconfig apply. The latest payload becomes one key of the
+//! Kubernetes Secret, or the registry credentials of an image-pull Secret;
+//! keys already in it are kept.
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+use std::process::Stdio;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use color_eyre::eyre::{bail, eyre};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, ListItem, Paragraph, Wrap};
+use serde_json::{Value, json};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command as Process;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::app::AppMessage;
+use crate::clipboard::{base64, base64_decode};
+use crate::commands::{Command, Workflow, WorkflowStep};
+use crate::config::{DialogAction, KeyResolver};
+use crate::provider::gcp::secret_manager::SecretManager;
+use crate::provider::gcp::secret_manager::client::SecretManagerClient;
+use crate::provider::gcp::secret_manager::compare::unified_diff;
+use crate::provider::gcp::secret_manager::registry::RegistryLogin;
+use crate::provider::gcp::secret_manager::secrets::Secret;
+use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
+use crate::service::ServiceMsg;
+use crate::ui::{
+    Component,
+    EventResult,
+    List,
+    ListEvent,
+    ListRow,
+    Modal,
+    Result,
+    TextInput,
+    TextInputEvent,
+};
+use crate::{Theme, redact};
+
+/// Namespace suggested for the Kubernetes Secret.
+const DEFAULT_NAMESPACE: &str = "default";
+
+// === Models ===
+
+/// Key the payload is stored under in the Kubernetes Secret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyNaming {
+    /// `db-password` stays `db-password`.
+    #[default]
+    SecretName,
+    /// `db-password` becomes `DB_PASSWORD`, ready for `envFrom`.
+    EnvVar,
+    /// Always `value`.
+    Value,
+}
+
+impl KeyNaming {
+    const fn next(self) -> Self {
+        match self {
+            Self::SecretName => Self::EnvVar,
+            Self::EnvVar => Self::Value,
+            Self::Value => Self::SecretName,
+        }
+    }
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::SecretName => "secret name",
+            Self::EnvVar => "environment variable",
+            Self::Value => "fixed",
+        }
+    }
+
+    /// The key for the secret `secret_id`.
+    fn key(self, secret_id: &str) -> String {
+        match self {
+            Self::SecretName => secret_id.to_string(),
+            Self::EnvVar => secret_id.to_ascii_uppercase().replace('-', "_"),
+            Self::Value => "value".to_string(),
+        }
+    }
+}
+
+/// What the Kubernetes Secret is made of.
+#[derive(Debug, Clone)]
+pub enum SyncContent {
+    /// The payload, under a key named as picked.
+    Payload(KeyNaming),
+    /// Registry credentials with the payload as password, for pulling images.
+    PullSecret(RegistryLogin),
+}
+
+impl SyncContent {
+    /// Type of the Kubernetes Secret when created.
+    const fn secret_type(&self) -> &'static str {
+        match self {
+            Self::Payload(_) => "Opaque",
+            Self::PullSecret(_) => "kubernetes.io/dockerconfigjson",
+        }
+    }
+
+    /// Key and value written for the secret `secret_id` with `payload`.
+    fn entry(&self, secret_id: &str, payload: &[u8]) -> Result<(String, Vec<u8>)> {
+        match self {
+            Self::Payload(naming) => Ok((naming.key(secret_id), payload.to_vec())),
+            Self::PullSecret(login) => {
+                let password = std::str::from_utf8(payload)
+                    .map_err(|_| eyre!("The payload of '{secret_id}' is not a text password"))?;
+                Ok((
+                    ".dockerconfigjson".to_string(),
+                    login.docker_config(password).into_bytes(),
+                ))
+            }
+        }
+    }
+}
+
+/// A Kubernetes Secret in one kubeconfig context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KubeTarget {
+    pub cluster: String,
+    pub namespace: String,
+    pub name: String,
+}
+
+impl KubeTarget {
+    /// Arguments pointing `kubectl` at the target's context and namespace.
+    fn scope(&self) -> [&str; 4] {
+        ["--context", &self.cluster, "--namespace", &self.namespace]
+    }
+}
+
+impl fmt::Display for KubeTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}/{}", self.cluster, self.namespace, self.name)
+    }
+}
+
+/// Name of the Kubernetes Secret suggested for `secret_id`. Kubernetes names
+/// are lowercase and take no underscores.
+fn kube_name(secret_id: &str) -> String {
+    secret_id
+        .to_ascii_lowercase()
+        .replace('_', "-")
+        .trim_matches('-')
+        .to_string()
+}
+
+/// Whether `name` can name a Kubernetes namespace or Secret.
+fn is_kube_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 253
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '.'))
+        && name.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && name.ends_with(|c: char| c.is_ascii_alphanumeric())
+}
+
+/// Namespace and name of `namespace/name`.
+fn parse_target(value: &str) -> std::result::Result<(String, String), String> {
+    let (namespace, name) = value
+        .trim()
+        .split_once('/')
+        .ok_or_else(|| "Enter namespace/name".to_string())?;
+    if !is_kube_name(namespace) || namespace.contains('.') {
+        return Err(format!("'{namespace}' is not a valid namespace"));
+    }
+    if !is_kube_name(name) {
+        return Err(format!("'{name}' is not a valid Secret name"));
+    }
+    Ok((namespace.to_string(), name.to_string()))
+}
+
+/// Everything needed to write the payload into the cluster.
+///
+/// Built by a dry run reading the Kubernetes Secret as it is, so the user can
+/// review the change before anything is written.
+#[derive(Debug, Clone)]
+pub struct SyncPlan {
+    pub secret: Secret,
+    pub target: KubeTarget,
+    /// Type given to the Kubernetes Secret if it is created.
+    pub secret_type: &'static str,
+    /// Key the payload is written to.
+    pub key: String,
+    /// Base64 data of the Kubernetes Secret now, `None` if it doesn't exist.
+    pub current: Option<BTreeMap<String, String>>,
+    /// Base64 data it has after the sync.
+    pub desired: BTreeMap<String, String>,
+}
+
+impl SyncPlan {
+    fn new(
+        secret: Secret,
+        target: KubeTarget,
+        secret_type: &'static str,
+        (key, value): (String, Vec<u8>),
+        current: Option<BTreeMap<String, String>>,
+    ) -> Self {
+        let mut desired = current.clone().unwrap_or_default();
+        desired.insert(key.clone(), base64(&value));
+        Self {
+            secret,
+            target,
+            secret_type,
+            key,
+            current,
+            desired,
+        }
+    }
+
+    /// Unified diff of the decoded data, empty when already in sync.
+    fn diff(&self) -> Vec<String> {
+        let current = self.current.as_ref().map(data_text).unwrap_or_default();
+        unified_diff(&current, &data_text(&self.desired), "current", "desired")
+    }
+
+    /// True if executing the plan would not change anything.
+    fn is_noop(&self) -> bool {
+        self.current.as_ref() == Some(&self.desired)
+    }
+}
+
+/// `data` decoded as `key: value` lines, multi-line values as YAML blocks.
+fn data_text(data: &BTreeMap<String, String>) -> String {
+    let mut text = String::new();
+    for (key, value) in data {
+        let value = base64_decode(value).map_or_else(
+            || value.clone(),
+            |bytes| String::from_utf8_lossy(&bytes).into_owned(),
+        );
+        if value.contains('\n') {
+            _ = writeln!(text, "{key}: |");
+            for line in value.lines() {
+                _ = writeln!(text, "  {line}");
+            }
+        } else {
+            _ = writeln!(text, "{key}: {value}");
+        }
+    }
+    text
+}
+
+#[derive(Debug, Clone)]
+struct ClusterItem(String);
+
+impl ListRow for ClusterItem {
+    fn render_row(&self, theme: &Theme) -> ListItem<'static> {
+        ListItem::new(Span::styled(
+            self.0.clone(),
+            Style::default().fg(theme.text()),
+        ))
+    }
+}
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum KubeSyncMsg {
+    /// List the kubeconfig contexts to sync the secret to.
+    Start(Secret),
+    /// List them to write an image-pull Secret to.
+    StartPullSecret {
+        secret: Secret,
+        login: RegistryLogin,
+    },
+    Clusters {
+        secret: Secret,
+        content: SyncContent,
+        clusters: Vec<String>,
+    },
+    Plan {
+        secret: Secret,
+        target: KubeTarget,
+        content: SyncContent,
+    },
+    Planned(Box<SyncPlan>),
+    Execute(Box<SyncPlan>),
+}
+
+impl From<KubeSyncMsg> for SecretManagerMsg {
+    fn from(msg: KubeSyncMsg) -> Self {
+        Self::KubeSync(msg)
+    }
+}
+
+impl From<KubeSyncMsg> for EventResult<SecretManagerMsg> {
+    fn from(msg: KubeSyncMsg) -> Self {
+        Self::Event(SecretManagerMsg::KubeSync(msg))
+    }
+}
+
+// === Wizards & Dialogs ===
+
+enum SyncWizardStep {
+    Cluster,
+    Target,
+    Key,
+}
+
+/// Picks the cluster, the Kubernetes Secret and, for a payload, its key.
+pub struct SyncWizard {
+    secret: Secret,
+    step: SyncWizardStep,
+    clusters: List<ClusterItem>,
+    cluster: Option<String>,
+    target: TextInput,
+    content: SyncContent,
+    resolver: Arc<KeyResolver>,
+}
+
+impl SyncWizard {
+    pub fn new(
+        secret: Secret,
+        content: SyncContent,
+        clusters: Vec<String>,
+        resolver: Arc<KeyResolver>,
+    ) -> Self {
+        let target = format!("{DEFAULT_NAMESPACE}/{}", kube_name(&secret.name));
+        Self {
+            secret,
+            step: SyncWizardStep::Cluster,
+            clusters: List::new(
+                clusters.into_iter().map(ClusterItem).collect(),
+                resolver.clone(),
+            ),
+            cluster: None,
+            target: TextInput::new("Namespace/Name")
+                .with_value(target)
+                .with_validator(|value| parse_target(value).map(|_| ())),
+            content,
+            resolver,
+        }
+    }
+
+    /// The plan request for the picked target.
+    fn plan(&self) -> Option<KubeSyncMsg> {
+        let cluster = self.cluster.clone()?;
+        let (namespace, name) = parse_target(self.target.value()).ok()?;
+        Some(KubeSyncMsg::Plan {
+            secret: self.secret.clone(),
+            target: KubeTarget {
+                cluster,
+                namespace,
+                name,
+            },
+            content: self.content.clone(),
+        })
+    }
+
+    fn handle_cluster_key(&mut self, key: KeyEvent) -> Result<EventResult<SecretManagerMsg>> {
+        if key.code == KeyCode::Esc {
+            return Ok(SecretManagerMsg::DialogCancelled.into());
+        }
+        if let EventResult::Event(ListEvent::Activated(cluster)) = self.clusters.handle_key(key)? {
+            self.cluster = Some(cluster.0);
+            self.step = SyncWizardStep::Target;
+        }
+        Ok(EventResult::Consumed)
+    }
+
+    fn handle_target_key(&mut self, key: KeyEvent) -> Result<EventResult<SecretManagerMsg>> {
+        match self.target.handle_key(key)? {
+            EventResult::Event(TextInputEvent::Submitted(_)) => {
+                if matches!(self.content, SyncContent::PullSecret(_)) {
+                    return Ok(self.plan().map_or(EventResult::Consumed, Into::into));
+                }
+                self.step = SyncWizardStep::Key;
+            }
+            EventResult::Event(TextInputEvent::Cancelled) => self.step = SyncWizardStep::Cluster,
+            _ => {}
+        }
+        Ok(EventResult::Consumed)
+    }
+
+    fn handle_naming_key(&mut self, key: KeyEvent) -> EventResult<SecretManagerMsg> {
+        if key.code == KeyCode::Char('k') {
+            if let SyncContent::Payload(naming) = &mut self.content {
+                *naming = naming.next();
+            }
+        } else if self.resolver.matches_dialog(&key, DialogAction::Cancel) {
+            self.step = SyncWizardStep::Target;
+        } else if self.resolver.matches_dialog(&key, DialogAction::Confirm)
+            && let Some(plan) = self.plan()
+        {
+            return plan.into();
+        }
+        EventResult::Consumed
+    }
+
+    fn render_clusters(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = area.centered(Constraint::Percentage(60), Constraint::Percentage(60));
+        frame.render_widget(Clear, popup_area);
+
+        let title = match self.content {
+            SyncContent::Payload(_) => format!(" Sync '{}' to Cluster ", self.secret.name),
+            SyncContent::PullSecret(_) => {
+                format!(" Image-pull Secret from '{}' ", self.secret.name)
+            }
+        };
+        let block = Block::default()
+            .title(title)
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.lavender()))
+            .style(Style::default().bg(theme.base()));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [hint_area, list_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Min(1)]).areas(inner);
+        frame.render_widget(
+            Paragraph::new("Enter to pick the kubeconfig context")
+                .style(Style::default().fg(theme.subtext0())),
+            hint_area,
+        );
+        self.clusters.render(frame, list_area, theme);
+    }
+
+    fn render_naming(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let SyncContent::Payload(naming) = self.content else {
+            return;
+        };
+        let popup_area = area.centered(Constraint::Percentage(50), Constraint::Length(9));
+        frame.render_widget(Clear, popup_area);
+
+        let key_style = Style::default()
+            .fg(theme.peach())
+            .add_modifier(Modifier::BOLD);
+        let text_style = Style::default().fg(theme.text());
+        let hint_style = Style::default().fg(theme.overlay1());
+
+        let lines = vec![
+            Line::from(vec![
+                Span::styled("Target: ", hint_style),
+                Span::styled(
+                    format!(
+                        "{}: {}",
+                        self.cluster.as_deref().unwrap_or_default(),
+                        self.target.value().trim()
+                    ),
+                    text_style.add_modifier(Modifier::BOLD),
+                ),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("[k] ", key_style),
+                Span::styled("Key: ", text_style),
+                Span::styled(
+                    naming.key(&self.secret.name),
+                    Style::default()
+                        .fg(theme.yellow())
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!(" ({})", naming.label()), hint_style),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled(
+                format!(
+                    "{} to preview (dry run), {} to change the target",
+                    self.resolver.display_dialog(DialogAction::Confirm),
+                    self.resolver.display_dialog(DialogAction::Cancel),
+                ),
+                hint_style,
+            )),
+        ];
+
+        let block = Block::default()
+            .title(format!(" Sync '{}' ", self.secret.name))
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.lavender()))
+            .style(Style::default().bg(theme.base()));
+
+        frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+    }
+}
+
+impl Modal for SyncWizard {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        match self.step {
+            SyncWizardStep::Cluster => self.handle_cluster_key(key),
+            SyncWizardStep::Target => self.handle_target_key(key),
+            SyncWizardStep::Key => Ok(self.handle_naming_key(key)),
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        match self.step {
+            SyncWizardStep::Target => Ok(self.target.handle_paste(text)?.consumed_only()),
+            SyncWizardStep::Cluster | SyncWizardStep::Key => Ok(EventResult::Consumed),
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        match self.step {
+            SyncWizardStep::Cluster => self.render_clusters(frame, area, theme),
+            SyncWizardStep::Target => self.target.render(frame, area, theme),
+            SyncWizardStep::Key => self.render_naming(frame, area, theme),
+        }
+    }
+}
+
+/// Shows the diff of the dry run and asks for confirmation.
+///
+/// The diff holds the decoded values, which are masked in the log and error
+/// messages while the dialog is open.
+pub struct SyncPreviewDialog {
+    plan: SyncPlan,
+    diff: Vec<String>,
+    /// Scope and keys the decoded values are registered under with [`redact`].
+    scope: String,
+    masked: Vec<String>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl SyncPreviewDialog {
+    pub fn new(plan: SyncPlan, resolver: Arc<KeyResolver>) -> Self {
+        let scope = redact::new_scope("kube_sync");
+        let mut masked = Vec::new();
+        let sides = [
+            ("current", plan.current.as_ref()),
+            ("desired", Some(&plan.desired)),
+        ];
+        for (side, data) in sides {
+            for (key, value) in data.into_iter().flatten() {
+                if let Some(bytes) = base64_decode(value) {
+                    let mask_key = format!("{}/{side}/{key}", plan.target);
+                    redact::register(&scope, &mask_key, &String::from_utf8_lossy(&bytes));
+                    masked.push(mask_key);
+                }
+            }
+        }
+        Self {
+            diff: plan.diff(),
+            plan,
+            scope,
+            masked,
+            resolver,
+        }
+    }
+}
+
+impl Drop for SyncPreviewDialog {
+    fn drop(&mut self) {
+        for key in &self.masked {
+            redact::forget(&self.scope, key);
+        }
+    }
+}
+
+impl Modal for SyncPreviewDialog {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if self.resolver.matches_dialog(&key, DialogAction::Confirm) && !self.plan.is_noop() {
+            return Ok(KubeSyncMsg::Execute(Box::new(self.plan.clone())).into());
+        }
+        if self.resolver.matches_dialog(&key, DialogAction::Cancel) {
+            return Ok(SecretManagerMsg::DialogCancelled.into());
+        }
+        Ok(EventResult::Consumed)
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = area.centered(Constraint::Percentage(70), Constraint::Percentage(70));
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(" Dry Run ")
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.lavender()))
+            .style(Style::default().bg(theme.base()));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [summary_area, footer_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+
+        let action = if self.plan.current.is_some() {
+            "Update "
+        } else {
+            "Create "
+        };
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(action, Style::default().fg(theme.overlay1())),
+                Span::styled(
+                    self.plan.target.to_string(),
+                    Style::default()
+                        .fg(theme.yellow())
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(" key ", Style::default().fg(theme.overlay1())),
+                Span::styled(
+                    self.plan.key.clone(),
+                    Style::default()
+                        .fg(theme.text())
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]),
+            Line::from(""),
+        ];
+        lines.extend(self.diff.iter().map(|line| {
+            let color = if line.starts_with("+++") || line.starts_with("---") {
+                theme.overlay1()
+            } else if line.starts_with('+') {
+                theme.green()
+            } else if line.starts_with('-') {
+                theme.red()
+            } else if line.starts_with("@@") {
+                theme.mauve()
+            } else {
+                theme.text()
+            };
+            Line::from(Span::styled(line.clone(), Style::default().fg(color)))
+        }));
+        frame.render_widget(
+            Paragraph::new(lines).wrap(Wrap { trim: false }),
+            summary_area,
+        );
+
+        let cancel = self.resolver.display_dialog(DialogAction::Cancel);
+        let footer = if self.plan.is_noop() {
+            Line::from(Span::styled(
+                format!("Already in sync. Press {cancel} to close."),
+                Style::default().fg(theme.overlay1()),
+            ))
+        } else {
+            let key_style = Style::default()
+                .fg(theme.peach())
+                .add_modifier(Modifier::BOLD);
+            Line::from(vec![
+                Span::styled(
+                    format!("[{}]", self.resolver.display_dialog(DialogAction::Confirm)),
+                    key_style,
+                ),
+                Span::styled(" Sync    ", Style::default().fg(theme.green())),
+                Span::styled(format!("[{cancel}]"), key_style),
+                Span::styled(" Cancel", Style::default().fg(theme.overlay1())),
+            ])
+        };
+        frame.render_widget(
+            Paragraph::new(footer).alignment(Alignment::Center),
+            footer_area,
+        );
+    }
+}
+
+// === Update Logic ===
+
+pub(super) fn update(state: &mut SecretManager, msg: KubeSyncMsg) -> Result<ServiceMsg> {
+    match msg {
+        KubeSyncMsg::Start(secret) => Ok(ListClustersCmd {
+            secret,
+            content: SyncContent::Payload(KeyNaming::default()),
+            tx: state.get_msg_sender(),
+        }
+        .into()),
+
+        KubeSyncMsg::StartPullSecret { secret, login } => Ok(ListClustersCmd {
+            secret,
+            content: SyncContent::PullSecret(login),
+            tx: state.get_msg_sender(),
+        }
+        .into()),
+
+        KubeSyncMsg::Clusters {
+            secret,
+            content,
+            clusters,
+        } => {
+            if clusters.is_empty() {
+                return Err(eyre!("No Kubernetes contexts configured in the kubeconfig"));
+            }
+            state.display_overlay(SyncWizard::new(
+                secret,
+                content,
+                clusters,
+                state.get_resolver(),
+            ));
+            Ok(ServiceMsg::Idle)
+        }
+
+        KubeSyncMsg::Plan {
+            secret,
+            target,
+            content,
+        } => {
+            state.close_overlay();
+            state.display_loading_spinner("Preparing dry run...");
+
+            Ok(PlanSyncCmd {
+                client: state.get_client_for(&secret)?,
+                secret,
+                target,
+                content,
+                tx: state.get_msg_sender(),
+            }
+            .into())
+        }
+
+        KubeSyncMsg::Planned(plan) => {
+            state.hide_loading_spinner();
+            state.display_overlay(SyncPreviewDialog::new(*plan, state.get_resolver()));
+            Ok(ServiceMsg::Idle)
+        }
+
+        KubeSyncMsg::Execute(plan) => {
+            state.close_overlay();
+            Ok(sync_workflow(*plan).into())
+        }
+    }
+}
+
+// === Commands ===
+
+/// Output of `kubectl` with `args`, fed `input` on stdin if given.
+async fn kubectl(args: &[&str], input: Option<Vec<u8>>) -> Result<Vec<u8>> {
+    let mut child = Process::new("kubectl")
+        .args(args)
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|err| eyre!("Cannot run kubectl: {err}"))?;
+    // Written alongside reading the output, a large input would fill the pipe
+    let writer = match (input, child.stdin.take()) {
+        (Some(input), Some(mut stdin)) => {
+            Some(tokio::spawn(async move { stdin.write_all(&input).await }))
+        }
+        _ => None,
+    };
+
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("kubectl exited with {}: {}", output.status, stderr.trim());
+    }
+    if let Some(writer) = writer {
+        writer.await??;
+    }
+    Ok(output.stdout)
+}
+
+/// Base64 data of the Kubernetes Secret, `None` if it doesn't exist.
+async fn read_kube_secret(target: &KubeTarget) -> Result<Option<BTreeMap<String, String>>> {
+    let mut args = target.scope().to_vec();
+    args.extend([
+        "get",
+        "secret",
+        &target.name,
+        "--ignore-not-found",
+        "-o",
+        "json",
+    ]);
+    let output = kubectl(&args, None).await?;
+    if output.iter().all(u8::is_ascii_whitespace) {
+        return Ok(None);
+    }
+    let secret: Value = serde_json::from_slice(&output)?;
+    let data = secret["data"]
+        .as_object()
+        .map(|data| {
+            data.iter()
+                .filter_map(|(key, value)| Some((key.clone(), value.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(Some(data))
+}
+
+/// Make the Kubernetes Secret hold `data`. Pass a type when creating it, an
+/// existing Secret keeps its own.
+///
+/// Applied server-side: a client-side apply would copy the data into the
+/// `last-applied-configuration` annotation, where `kubectl describe` and
+/// metadata-only listings show it.
+async fn apply_kube_secret(
+    target: &KubeTarget,
+    data: &BTreeMap<String, String>,
+    secret_type: Option<&str>,
+) -> Result<()> {
+    let mut manifest = json!({
+        "apiVersion": "v1",
+        "kind": "Secret",
+        "metadata": {
+            "name": target.name,
+            "namespace": target.namespace,
+        },
+        "data": data,
+    });
+    if let Some(secret_type) = secret_type {
+        manifest["type"] = json!(secret_type);
+    }
+    let mut args = target.scope().to_vec();
+    args.extend([
+        "apply",
+        "--server-side",
+        "--field-manager=lazycloud",
+        "--force-conflicts",
+        "-f",
+        "-",
+    ]);
+    kubectl(&args, Some(serde_json::to_vec(&manifest)?)).await?;
+    Ok(())
+}
+
+struct ListClustersCmd {
+    secret: Secret,
+    content: SyncContent,
+    tx: UnboundedSender<SecretManagerMsg>,
+}
+
+#[async_trait]
+impl Command for ListClustersCmd {
+    fn name(&self) -> String {
+        "Listing Kubernetes contexts".to_string()
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let output = kubectl(&["config", "get-contexts", "-o", "name"], None).await?;
+        let clusters = String::from_utf8_lossy(&output)
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect();
+        self.tx.send(
+            KubeSyncMsg::Clusters {
+                secret: self.secret,
+                content: self.content,
+                clusters,
+            }
+            .into(),
+        )?;
+        Ok(())
+    }
+}
+
+/// Dry run: reads the payload and the Kubernetes Secret without writing.
+struct PlanSyncCmd {
+    client: SecretManagerClient,
+    secret: Secret,
+    target: KubeTarget,
+    content: SyncContent,
+    tx: UnboundedSender<SecretManagerMsg>,
+}
+
+#[async_trait]
+impl Command for PlanSyncCmd {
+    fn name(&self) -> String {
+        format!("Planning sync of '{}' to {}", self.secret.name, self.target)
+    }
+
+    fn resource(&self) -> Option<String> {
+        Some(self.secret.name.clone())
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let payload = self
+            .client
+            .access_version_bytes(&self.secret.name, "latest")
+            .await?;
+        let entry = self.content.entry(&self.secret.name, &payload)?;
+        let current = read_kube_secret(&self.target).await?;
+
+        self.tx.send(
+            KubeSyncMsg::Planned(Box::new(SyncPlan::new(
+                self.secret,
+                self.target,
+                self.content.secret_type(),
+                entry,
+                current,
+            )))
+            .into(),
+        )?;
+        Ok(())
+    }
+}
+
+/// Writes the Kubernetes Secret, restoring its previous data if it does not
+/// read back as planned.
+fn sync_workflow(plan: SyncPlan) -> Workflow<SyncPlan> {
+    let name = format!("Syncing '{}' to {}", plan.secret.name, plan.target);
+    Workflow::new(name, plan)
+        .step(ApplySecretStep)
+        .step(VerifySyncStep)
+}
+
+struct ApplySecretStep;
+
+#[async_trait]
+impl WorkflowStep<SyncPlan> for ApplySecretStep {
+    fn name(&self) -> String {
+        "Apply Kubernetes Secret".to_string()
+    }
+
+    async fn run(&self, plan: &mut SyncPlan) -> Result<()> {
+        let secret_type = plan.current.is_none().then_some(plan.secret_type);
+        apply_kube_secret(&plan.target, &plan.desired, secret_type).await
+    }
+
+    async fn rollback(&self, plan: &mut SyncPlan) -> Result<()> {
+        if let Some(data) = &plan.current {
+            return apply_kube_secret(&plan.target, data, None).await;
+        }
+        let mut args = plan.target.scope().to_vec();
+        args.extend(["delete", "secret", &plan.target.name]);
+        kubectl(&args, None).await?;
+        Ok(())
+    }
+}
+
+struct VerifySyncStep;
+
+#[async_trait]
+impl WorkflowStep<SyncPlan> for VerifySyncStep {
+    fn name(&self) -> String {
+        "Verify".to_string()
+    }
+
+    async fn run(&self, plan: &mut SyncPlan) -> Result<()> {
+        let data = read_kube_secret(&plan.target)
+            .await?
+            .ok_or_else(|| eyre!("{} was not created", plan.target))?;
+        if data.get(&plan.key) != plan.desired.get(&plan.key) {
+            bail!("Key '{}' of {} does not match", plan.key, plan.target);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::config::keybindings::KeybindingsConfig;
+
+    #[test]
+    fn test_key_and_target_names() {
+        assert_eq!(KeyNaming::SecretName.key("db-password"), "db-password");
+        assert_eq!(KeyNaming::EnvVar.key("db-password"), "DB_PASSWORD");
+        assert_eq!(KeyNaming::Value.key("db-password"), "value");
+
+        assert_eq!(kube_name("Stripe_API_key"), "stripe-api-key");
+        assert_eq!(
+            parse_target(" prod/stripe-api-key "),
+            Ok(("prod".to_string(), "stripe-api-key".to_string()))
+        );
+        assert!(parse_target("stripe-api-key").is_err());
+        assert!(parse_target("prod/Stripe_key").is_err());
+        assert!(parse_target("prod.eu/key").is_err());
+    }
+
+    #[test]
+    fn test_plan_keeps_other_keys() {
+        let current = BTreeMap::from([
+            ("username".to_string(), base64(b"admin")),
+            ("password".to_string(), base64(b"old")),
+        ]);
+        let target = KubeTarget {
+            cluster: "kind".to_string(),
+            namespace: "default".to_string(),
+            name: "db".to_string(),
+        };
+        let plan = SyncPlan::new(
+            Secret::test("db-password"),
+            target,
+            "Opaque",
+            ("password".to_string(), b"new".to_vec()),
+            Some(current),
+        );
+
+        assert!(!plan.is_noop());
+        assert_eq!(plan.desired["username"], base64(b"admin"));
+        let diff = plan.diff();
+        assert!(diff.contains(&"-password: old".to_string()));
+        assert!(diff.contains(&"+password: new".to_string()));
+        assert!(diff.contains(&" username: admin".to_string()));
+
+        let resolver = Arc::new(KeyResolver::new(Arc::new(KeybindingsConfig::default())));
+        let dialog = SyncPreviewDialog::new(plan, resolver);
+        assert_eq!(redact::scrub("login admin"), "login [redacted]");
+        drop(dialog);
+        assert_eq!(redact::scrub("login admin"), "login admin");
+    }
+}
@@ -0,0 +1,517 @@
+use std::pin::pin;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use color_eyre::eyre::eyre;
+use crossterm::event::KeyEvent;
+use futures::{StreamExt, future, stream};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::Style;
+use ratatui::widgets::Cell;
+use regex::Regex;
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::warn;
+
+use crate::Theme;
+use crate::app::AppMessage;
+use crate::commands::Command;
+use crate::config::{KeyResolver, LintConfig, NavAction, SearchAction};
+use crate::provider::gcp::secret_manager::SecretManager;
+use crate::provider::gcp::secret_manager::client::SecretManagerClient;
+use crate::provider::gcp::secret_manager::secrets::{Secret, SecretsMsg};
+use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
+use crate::search::Matcher;
+use crate::service::ServiceMsg;
+use crate::ui::{
+    ColumnDef,
+    Component,
+    EventResult,
+    Keybinding,
+    Result,
+    Screen,
+    Table,
+    TableEvent,
+    TableRow,
+};
+
+/// Maximum number of payloads fetched at the same time when checking sizes.
+const MAX_CONCURRENT_FETCHES: usize = 8;
+
+// === Models ===
+
+/// Lint rules compiled from the `[lint]` config section.
+#[derive(Debug, Clone, Default)]
+pub struct LintRules {
+    name_pattern: Option<Regex>,
+    required_labels: Vec<String>,
+    max_payload_bytes: Option<usize>,
+}
+
+impl LintRules {
+    pub fn from_config(config: &LintConfig) -> Result<Self> {
+        let name_pattern = config
+            .name_pattern
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(|err| eyre!("Invalid lint name_pattern: {err}"))?;
+
+        Ok(Self {
+            name_pattern,
+            required_labels: config.required_labels.clone(),
+            max_payload_bytes: config.max_payload_bytes,
+        })
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.name_pattern.is_none()
+            && self.required_labels.is_empty()
+            && self.max_payload_bytes.is_none()
+    }
+
+    /// Check the rules that only need secret metadata (name and labels).
+    pub fn check_metadata(&self, secret: &Secret) -> Vec<LintViolation> {
+        let mut violations = Vec::new();
+
+        if let Some(pattern) = &self.name_pattern
+            && !pattern.is_match(&secret.name)
+        {
+            violations.push(LintViolation {
+                secret: secret.clone(),
+                rule: LintRule::NamePattern,
+                message: format!("Name does not match /{}/", pattern.as_str()),
+            });
+        }
+
+        for label in &self.required_labels {
+            if !secret.labels.contains_key(label) {
+                violations.push(LintViolation {
+                    secret: secret.clone(),
+                    rule: LintRule::RequiredLabel,
+                    message: format!("Missing label '{label}'"),
+                });
+            }
+        }
+
+        violations
+    }
+
+    /// Check the size of the latest payload, if a limit is configured.
+    pub fn check_payload(&self, secret: &Secret, size: usize) -> Option<LintViolation> {
+        let limit = self.max_payload_bytes?;
+        (size > limit).then(|| LintViolation {
+            secret: secret.clone(),
+            rule: LintRule::PayloadSize,
+            message: format!("Payload is {size} bytes (limit {limit})"),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintRule {
+    NamePattern,
+    RequiredLabel,
+    PayloadSize,
+}
+
+impl LintRule {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::NamePattern => "name",
+            Self::RequiredLabel => "labels",
+            Self::PayloadSize => "size",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LintViolation {
+    pub secret: Secret,
+    pub rule: LintRule,
+    pub message: String,
+}
+
+impl TableRow for LintViolation {
+    fn columns() -> &'static [ColumnDef] {
+        static COLUMNS: &[ColumnDef] = &[
+            ColumnDef::new("Secret", Constraint::Min(20)),
+            ColumnDef::new("Rule", Constraint::Length(8)),
+            ColumnDef::new("Violation", Constraint::Min(30)),
+        ];
+        COLUMNS
+    }
+
+    fn render_cells(&self, theme: &Theme) -> Vec<Cell<'static>> {
+        vec![
+            Cell::from(self.secret.name.clone()),
+            Cell::from(self.rule.label()).style(Style::default().fg(theme.peach())),
+            Cell::from(self.message.clone()),
+        ]
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        let matcher = Matcher::new();
+        matcher.matches(&self.secret.name, query)
+            || matcher.matches(self.rule.label(), query)
+            || matcher.matches(&self.message, query)
+    }
+}
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum LintMsg {
+    /// Lint every secret in the project against the configured rules.
+    Run,
+    /// The secrets were not cached yet; lint them once listed.
+    Loaded(Vec<Secret>),
+    Completed {
+        /// Number of secrets checked, so an empty report is not mistaken
+        /// for a clean one.
+        checked: usize,
+        violations: Vec<LintViolation>,
+    },
+    /// Open the resource view that lets the user fix a violation.
+    Jump(Box<LintViolation>),
+}
+
+impl From<LintMsg> for SecretManagerMsg {
+    fn from(msg: LintMsg) -> Self {
+        Self::Lint(msg)
+    }
+}
+
+impl From<LintMsg> for EventResult<SecretManagerMsg> {
+    fn from(msg: LintMsg) -> Self {
+        Self::Event(SecretManagerMsg::Lint(msg))
+    }
+}
+
+// === Screens ===
+
+pub struct LintScreen {
+    table: Table<LintViolation>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl LintScreen {
+    pub fn new(checked: usize, violations: Vec<LintViolation>, resolver: Arc<KeyResolver>) -> Self {
+        let title = format!(
+            " Lint: {} violations in {checked} secrets ",
+            violations.len()
+        );
+        Self {
+            table: Table::new(violations, resolver.clone()).with_title(title),
+            resolver,
+        }
+    }
+}
+
+impl Screen for LintScreen {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        let result = self.table.handle_key(key)?;
+        if let EventResult::Event(TableEvent::Activated(violation)) = result {
+            return Ok(LintMsg::Jump(Box::new(violation)).into());
+        }
+        if result.is_consumed() {
+            return Ok(EventResult::Consumed);
+        }
+
+        Ok(EventResult::Ignored)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.table.render(frame, area, theme);
+    }
+
+    fn breadcrumbs(&self) -> Vec<String> {
+        vec!["Lint".to_string()]
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        vec![
+            Keybinding::hint(self.resolver.display_nav(NavAction::Select), "Go to"),
+            Keybinding::hint(self.resolver.display_search(SearchAction::Toggle), "Search"),
+        ]
+    }
+}
+
+// === Update Logic ===
+
+pub(super) fn update(state: &mut SecretManager, msg: LintMsg) -> Result<ServiceMsg> {
+    match msg {
+        LintMsg::Run => {
+            let rules = LintRules::from_config(&state.get_config().lint)?;
+            if rules.is_empty() {
+                return Err(eyre!(
+                    "No lint rules configured. Add a [lint] section to the config file."
+                ));
+            }
+            let Some(secrets) = state.get_cached_secrets() else {
+                state.display_loading_spinner("Loading secrets...");
+                return Ok(FetchSecretsCmd {
+                    client: state.get_client()?,
+                    projects: state.get_projects(),
+                    tx: state.get_msg_sender(),
+                }
+                .into());
+            };
+            let violations: Vec<LintViolation> = secrets
+                .iter()
+                .flat_map(|s| rules.check_metadata(s))
+                .collect();
+
+            if rules.max_payload_bytes.is_none() {
+                state.queue(
+                    LintMsg::Completed {
+                        checked: secrets.len(),
+                        violations,
+                    }
+                    .into(),
+                );
+                return Ok(ServiceMsg::Idle);
+            }
+
+            state.display_loading_spinner("Checking payload sizes...");
+            Ok(LintCmd {
+                client: state.get_client()?,
+                secrets,
+                rules,
+                violations,
+                tx: state.get_msg_sender(),
+            }
+            .into())
+        }
+
+        LintMsg::Loaded(mut secrets) => {
+            state.hide_loading_spinner();
+            secrets.retain(|secret| !state.trash().contains(secret));
+            state.cache_secrets(&secrets);
+            state.queue(LintMsg::Run.into());
+            Ok(ServiceMsg::Idle)
+        }
+
+        LintMsg::Completed {
+            checked,
+            violations,
+        } => {
+            state.push_view(LintScreen::new(checked, violations, state.get_resolver()));
+            Ok(ServiceMsg::Idle)
+        }
+
+        LintMsg::Jump(violation) => {
+            let secret = violation.secret;
+            let msg = match violation.rule {
+                LintRule::NamePattern => SecretsMsg::ViewVersions(secret),
+                LintRule::RequiredLabel => SecretsMsg::ViewLabels(secret),
+                LintRule::PayloadSize => SecretsMsg::ViewPayload(secret),
+            };
+            state.queue(msg.into());
+            Ok(ServiceMsg::Idle)
+        }
+    }
+}
+
+// === Commands ===
+
+struct FetchSecretsCmd {
+    client: SecretManagerClient,
+    projects: Vec<String>,
+    tx: UnboundedSender<SecretManagerMsg>,
+}
+
+#[async_trait]
+impl Command for FetchSecretsCmd {
+    fn name(&self) -> String {
+        "Loading secrets to lint".to_string()
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let lists = future::try_join_all(self.projects.iter().map(|project| {
+            let client = self.client.in_project(project);
+            async move { client.list_secrets().await }
+        }))
+        .await?;
+        let secrets = lists.into_iter().flatten().collect();
+        self.tx.send(LintMsg::Loaded(secrets).into())?;
+        Ok(())
+    }
+}
+
+struct LintCmd {
+    client: SecretManagerClient,
+    secrets: Vec<Secret>,
+    rules: LintRules,
+    /// Metadata violations found before any payload was fetched.
+    violations: Vec<LintViolation>,
+    tx: UnboundedSender<SecretManagerMsg>,
+}
+
+#[async_trait]
+impl Command for LintCmd {
+    fn name(&self) -> String {
+        format!("Linting {} secrets", self.secrets.len())
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let mut fetches = pin!(
+            stream::iter(self.secrets.clone())
+                .map(|secret| {
+                    let client = self.client.in_project(&secret.project);
+                    async move {
+                        let result = client.access_version_bytes(&secret.name, "latest").await;
+                        (secret, result)
+                    }
+                })
+                .buffer_unordered(MAX_CONCURRENT_FETCHES)
+        );
+
+        let mut violations = self.violations.clone();
+        while let Some((secret, result)) = fetches.next().await {
+            match result {
+                // The raw bytes, as a binary payload decodes to a longer string
+                Ok(data) => violations.extend(self.rules.check_payload(&secret, data.len())),
+                Err(err) => warn!(secret = %secret.name, %err, "Lint could not read payload"),
+            }
+        }
+
+        // Keep the output stable regardless of fetch completion order
+        violations.sort_by(|a, b| a.secret.name.cmp(&b.secret.name));
+        self.tx.send(
+            LintMsg::Completed {
+                checked: self.secrets.len(),
+                violations,
+            }
+            .into(),
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use tokio::sync::mpsc::unbounded_channel;
+
+    use super::*;
+    use crate::context::{AuthMethod, GcpContext};
+
+    fn secret(name: &str, labels: &[(&str, &str)]) -> Secret {
+        Secret {
+            labels: labels
+                .iter()
+                .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+                .collect::<HashMap<_, _>>(),
+            ..Secret::test(name)
+        }
+    }
+
+    fn rules() -> LintRules {
+        LintRules::from_config(&LintConfig {
+            name_pattern: Some("^[a-z][a-z0-9-]*$".to_string()),
+            required_labels: vec!["team".to_string(), "env".to_string()],
+            max_payload_bytes: Some(16),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_empty_config_has_no_rules() {
+        let rules = LintRules::from_config(&LintConfig::default()).unwrap();
+        assert!(rules.is_empty());
+        assert!(
+            rules
+                .check_metadata(&secret("Anything_Goes", &[]))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_check_metadata() {
+        let rules = rules();
+        assert!(
+            rules
+                .check_metadata(&secret("db-password", &[("team", "core"), ("env", "prod")]))
+                .is_empty()
+        );
+
+        let violations = rules.check_metadata(&secret("DB_PASSWORD", &[("team", "core")]));
+        let found: Vec<LintRule> = violations.iter().map(|v| v.rule).collect();
+        assert_eq!(found, vec![LintRule::NamePattern, LintRule::RequiredLabel]);
+        assert_eq!(violations[1].message, "Missing label 'env'");
+    }
+
+    #[test]
+    fn test_check_payload_and_invalid_pattern() {
+        let rules = rules();
+        let secret = secret("db-password", &[]);
+        assert!(rules.check_payload(&secret, 16).is_none());
+        assert!(rules.check_payload(&secret, 17).is_some());
+
+        let config = LintConfig {
+            name_pattern: Some("(unclosed".to_string()),
+            ..LintConfig::default()
+        };
+        assert!(LintRules::from_config(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_lint_measures_payload_bytes() {
+        let client = SecretManagerClient::new(&GcpContext {
+            display_name: "acme-dev".to_string(),
+            project_id: "acme-dev".to_string(),
+            account: "demo@acme.example".to_string(),
+            region: None,
+            zone: None,
+            auth: AuthMethod::Demo,
+            accounts: Vec::new(),
+            projects: Vec::new(),
+        })
+        .await
+        .unwrap();
+        let secrets = client.list_secrets().await.unwrap();
+        let size = client
+            .access_version_bytes("stripe-api-key", "latest")
+            .await
+            .unwrap()
+            .len();
+
+        let (tx, mut rx) = unbounded_channel();
+        let (action_tx, _action_rx) = unbounded_channel();
+        Box::new(LintCmd {
+            client,
+            secrets: secrets.clone(),
+            rules: LintRules {
+                max_payload_bytes: Some(0),
+                ..LintRules::default()
+            },
+            violations: Vec::new(),
+            tx,
+        })
+        .execute(action_tx)
+        .await
+        .unwrap();
+
+        let Some(SecretManagerMsg::Lint(LintMsg::Completed {
+            checked,
+            violations,
+        })) = rx.recv().await
+        else {
+            panic!("expected a lint report");
+        };
+        assert_eq!(checked, secrets.len());
+        let stripe = violations
+            .iter()
+            .find(|violation| violation.secret.name == "stripe-api-key")
+            .unwrap();
+        assert_eq!(stripe.message, format!("Payload is {size} bytes (limit 0)"));
+    }
+}
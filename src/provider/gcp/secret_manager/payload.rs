@@ -1,23 +1,56 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::pin::pin;
 use std::sync::Arc;
 
+use async_trait::async_trait;
+use crossterm::event::KeyEvent;
+use futures::{StreamExt, stream};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Margin, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Clear};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::warn;
+
 use crate::app::AppMessage;
-use crate::commands::{Command, CopyToClipboardCmd};
-use crate::config::{KeyResolver, PayloadAction};
+use crate::commands::{Command, CopyToClipboardCmd, Progress};
+use crate::config::{ActionContext, KeyResolver, PayloadAction};
+use crate::cost::{self, Operation};
+use crate::i18n::t;
+use crate::provider::gcp::secret_manager::SecretManager;
 use crate::provider::gcp::secret_manager::client::SecretManagerClient;
+use crate::provider::gcp::secret_manager::compare::{CompareMsg, ComparedPayload};
 use crate::provider::gcp::secret_manager::secrets::Secret;
 use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
 use crate::provider::gcp::secret_manager::versions::SecretVersion;
-use crate::provider::gcp::secret_manager::SecretManager;
 use crate::service::ServiceMsg;
-use crate::ui::{EventResult, Keybinding, Result, Screen};
-use crate::Theme;
-use async_trait::async_trait;
-use crossterm::event::KeyEvent;
-use ratatui::layout::Rect;
-use ratatui::style::{Modifier, Style};
-use ratatui::widgets::{Block, Borders, Paragraph};
-use ratatui::Frame;
-use tokio::sync::mpsc::UnboundedSender;
+use crate::ui::widgets::QrCode;
+use crate::ui::{
+    Component,
+    ConfirmDialog,
+    ConfirmEvent,
+    EventResult,
+    Keybinding,
+    Modal,
+    Result,
+    Screen,
+    TextInput,
+    TextInputEvent,
+    TextViewer,
+    ToastType,
+    detect_syntax,
+};
+use crate::{Theme, encryption};
+
+/// Maximum number of payloads prefetched at the same time.
+const MAX_CONCURRENT_FETCHES: usize = 8;
+
+/// Longest payload shown as a QR code. Tokens and OTP seeds fit, and the code
+/// stays small enough to scan from a terminal.
+const MAX_QR_CODE_BYTES: usize = 128;
 
 // === Models ===
 
@@ -40,10 +73,40 @@ pub enum PayloadMsg {
         version: Option<SecretVersion>,
         payload: SecretPayload,
     },
+    /// Warm the cache with the latest payload of each secret.
+    Prefetch(Vec<Secret>),
+    /// Prefetch once the estimated cost was confirmed.
+    PrefetchConfirmed(Vec<Secret>),
+    /// A latest payload fetched in the background, cached without opening it.
+    Prefetched {
+        secret: Secret,
+        payload: SecretPayload,
+    },
     Copy {
         data: String,
         description: String,
     },
+    /// Ask where to save the payload of a version (`None` for latest).
+    StartSave {
+        secret: Secret,
+        version: Option<SecretVersion>,
+    },
+    /// Write the raw payload bytes to `path`.
+    Save {
+        secret: Secret,
+        version: Option<SecretVersion>,
+        path: PathBuf,
+        overwrite: bool,
+    },
+    /// Warn before showing a payload as a QR code.
+    ConfirmQrCode {
+        title: String,
+        data: String,
+    },
+    ShowQrCode {
+        title: String,
+        data: String,
+    },
 }
 
 impl From<PayloadMsg> for SecretManagerMsg {
@@ -64,22 +127,43 @@ pub struct PayloadScreen {
     secret: Secret,
     version: Option<SecretVersion>,
     payload: SecretPayload,
+    viewer: TextViewer,
     resolver: Arc<KeyResolver>,
 }
 
 impl PayloadScreen {
-    pub const fn new(
+    pub fn new(
         secret: Secret,
         version: Option<SecretVersion>,
         payload: SecretPayload,
         resolver: Arc<KeyResolver>,
     ) -> Self {
-        Self {
+        let mut viewer = TextViewer::new(&payload.data, resolver.clone());
+        if let Some(syntax) = detect_syntax(&payload.data) {
+            viewer = viewer.with_syntax(syntax);
+        }
+        let mut screen = Self {
             secret,
             version,
             payload,
+            viewer,
             resolver,
-        }
+        };
+        screen.viewer.set_title(format!(" {} ", screen.title()));
+        screen
+    }
+
+    fn title(&self) -> String {
+        format!(
+            "{} - v{}",
+            self.secret.name,
+            version_label(self.version.as_ref())
+        )
+    }
+
+    /// Short text payloads can be shown as a QR code.
+    const fn fits_qr_code(&self) -> bool {
+        !self.payload.is_binary && self.payload.data.len() <= MAX_QR_CODE_BYTES
     }
 }
 
@@ -87,6 +171,9 @@ impl Screen for PayloadScreen {
     type Output = SecretManagerMsg;
 
     fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if self.viewer.handle_key(key)?.is_consumed() {
+            return Ok(EventResult::Consumed);
+        }
         if self.resolver.matches_payload(&key, PayloadAction::Reload) {
             return Ok(PayloadMsg::Load {
                 secret: self.secret.clone(),
@@ -105,47 +192,319 @@ impl Screen for PayloadScreen {
             }
             .into());
         }
+        if self.resolver.matches_payload(&key, PayloadAction::Compare) && !self.payload.is_binary {
+            return Ok(CompareMsg::Start(Box::new(ComparedPayload {
+                secret: self.secret.clone(),
+                version: self.version.clone(),
+                payload: self.payload.clone(),
+            }))
+            .into());
+        }
+        if self.resolver.matches_payload(&key, PayloadAction::Save) {
+            return Ok(PayloadMsg::StartSave {
+                secret: self.secret.clone(),
+                version: self.version.clone(),
+            }
+            .into());
+        }
+        if self.resolver.matches_payload(&key, PayloadAction::QrCode) && self.fits_qr_code() {
+            return Ok(PayloadMsg::ConfirmQrCode {
+                title: self.title(),
+                data: self.payload.data.clone(),
+            }
+            .into());
+        }
         Ok(EventResult::Ignored)
     }
 
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.viewer.handle_paste(text)?.consumed_only())
+    }
+
     fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.viewer.render(frame, area, theme);
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        let mut bindings = vec![
+            Keybinding::hint(self.resolver.display_payload(PayloadAction::Copy), "Copy"),
+            Keybinding::hint(
+                self.resolver.display_payload(PayloadAction::Save),
+                "Save to file",
+            ),
+        ];
+        if !self.payload.is_binary {
+            bindings.push(Keybinding::new(
+                self.resolver.display_payload(PayloadAction::Compare),
+                "Diff with file",
+            ));
+        }
+        if self.fits_qr_code() {
+            bindings.push(Keybinding::new(
+                self.resolver.display_payload(PayloadAction::QrCode),
+                "Show as QR code",
+            ));
+        }
+        bindings.push(Keybinding::new(
+            self.resolver.display_payload(PayloadAction::Reload),
+            "Reload",
+        ));
+        bindings.extend(self.viewer.keybindings());
+        bindings
+    }
+
+    fn action_context(&self) -> ActionContext {
         let version = self
             .version
             .as_ref()
             .map_or("latest", |v| v.version_id.as_str());
-        let title = format!(" {} - v{} ", self.secret.name, version);
-
-        let p = Paragraph::new(self.payload.data.as_str())
-            .style(Style::default().fg(theme.text()))
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .border_type(theme.border_type)
-                    .border_style(Style::default().fg(theme.border()))
-                    .title(title)
-                    .title_style(
-                        Style::default()
-                            .fg(theme.mauve())
-                            .add_modifier(Modifier::BOLD),
-                    ),
-            );
-
-        frame.render_widget(p, area);
+        ActionContext::screen("payload")
+            .with_var("name", &self.secret.name)
+            .with_var("version", version)
     }
+}
 
-    fn keybindings(&self) -> Vec<Keybinding> {
-        vec![
-            Keybinding::hint(self.resolver.display_payload(PayloadAction::Copy), "Copy"),
-            Keybinding::new(
-                self.resolver.display_payload(PayloadAction::Reload),
-                "Reload",
-            ),
-        ]
+// === Dialogs ===
+
+pub struct SavePayloadDialog {
+    secret: Secret,
+    version: Option<SecretVersion>,
+    input: TextInput,
+}
+
+impl SavePayloadDialog {
+    pub fn new(secret: Secret, version: Option<SecretVersion>) -> Self {
+        let mut file_name = format!("{}-{}", secret.name, version_label(version.as_ref()));
+        if let Some(extension) = encryption::suggested_extension() {
+            file_name = format!("{file_name}.{extension}");
+        }
+        Self {
+            secret,
+            version,
+            input: TextInput::new("Save Payload To").with_value(file_name),
+        }
+    }
+}
+
+impl Modal for SavePayloadDialog {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        Ok(match self.input.handle_key(key)? {
+            EventResult::Event(TextInputEvent::Submitted(path)) if !path.trim().is_empty() => {
+                PayloadMsg::Save {
+                    secret: self.secret.clone(),
+                    version: self.version.clone(),
+                    path: expand_home(path.trim()),
+                    overwrite: false,
+                }
+                .into()
+            }
+            EventResult::Event(TextInputEvent::Cancelled) => {
+                SecretManagerMsg::DialogCancelled.into()
+            }
+            _ => EventResult::Consumed,
+        })
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.input.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.input.render(frame, area, theme);
+    }
+}
+
+pub struct OverwriteFileDialog {
+    secret: Secret,
+    version: Option<SecretVersion>,
+    path: PathBuf,
+    dialog: ConfirmDialog,
+}
+
+impl OverwriteFileDialog {
+    pub fn new(
+        secret: Secret,
+        version: Option<SecretVersion>,
+        path: PathBuf,
+        resolver: Arc<KeyResolver>,
+    ) -> Self {
+        let dialog = ConfirmDialog::new(
+            format!("\"{}\" already exists. Overwrite it?", path.display()),
+            resolver,
+        )
+        .with_title("Overwrite File")
+        .with_confirm_text("Overwrite")
+        .with_cancel_text("Cancel")
+        .danger();
+
+        Self {
+            secret,
+            version,
+            path,
+            dialog,
+        }
+    }
+}
+
+impl Modal for OverwriteFileDialog {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        Ok(match self.dialog.handle_key(key)? {
+            EventResult::Event(ConfirmEvent::Confirmed) => PayloadMsg::Save {
+                secret: self.secret.clone(),
+                version: self.version.clone(),
+                path: self.path.clone(),
+                overwrite: true,
+            }
+            .into(),
+            EventResult::Event(ConfirmEvent::Cancelled) => SecretManagerMsg::DialogCancelled.into(),
+            _ => EventResult::Consumed,
+        })
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.dialog.render(frame, area, theme);
+    }
+}
+
+/// Warns that a QR code puts the payload in plain view before showing it.
+pub struct QrCodeWarningDialog {
+    title: String,
+    data: String,
+    dialog: ConfirmDialog,
+}
+
+impl QrCodeWarningDialog {
+    pub fn new(title: String, data: String, resolver: Arc<KeyResolver>) -> Self {
+        let dialog = ConfirmDialog::new(
+            "The payload will be readable by anyone who can see or photograph the screen. \
+             Show it as a QR code?",
+            resolver,
+        )
+        .with_title("Show as QR Code")
+        .with_confirm_text("Show")
+        .with_cancel_text("Cancel")
+        .danger();
+
+        Self {
+            title,
+            data,
+            dialog,
+        }
+    }
+}
+
+impl Modal for QrCodeWarningDialog {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        Ok(match self.dialog.handle_key(key)? {
+            EventResult::Event(ConfirmEvent::Confirmed) => PayloadMsg::ShowQrCode {
+                title: self.title.clone(),
+                data: self.data.clone(),
+            }
+            .into(),
+            EventResult::Event(ConfirmEvent::Cancelled) => SecretManagerMsg::DialogCancelled.into(),
+            _ => EventResult::Consumed,
+        })
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.dialog.render(frame, area, theme);
+    }
+}
+
+/// A payload as a QR code, closed by any key.
+pub struct QrCodeDialog {
+    title: String,
+    code: QrCode,
+}
+
+impl QrCodeDialog {
+    pub const fn new(title: String, code: QrCode) -> Self {
+        Self { title, code }
+    }
+}
+
+impl Modal for QrCodeDialog {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, _key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        Ok(SecretManagerMsg::DialogCancelled.into())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let (width, height) = self.code.size();
+        // Border, margin and the line below the code
+        let popup_area = area.centered(
+            Constraint::Length(width.saturating_add(4)),
+            Constraint::Length(height.saturating_add(3)),
+        );
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(format!(" {} ", self.title))
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .title_bottom(Line::from(format!(" {} ", t("Press any key to close"))).centered())
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.red()))
+            .style(Style::default().bg(theme.base()));
+        let inner = block.inner(popup_area).inner(Margin::new(1, 0));
+        frame.render_widget(block, popup_area);
+        self.code.render(frame, inner, theme);
+    }
+}
+
+/// Confirms prefetching with an estimate of the access charges.
+pub struct PrefetchDialog {
+    secrets: Vec<Secret>,
+    dialog: ConfirmDialog,
+}
+
+impl PrefetchDialog {
+    pub fn new(secrets: Vec<Secret>, resolver: Arc<KeyResolver>) -> Self {
+        let dialog = ConfirmDialog::new(
+            format!("Fetch the latest payload of {} secrets?", secrets.len()),
+            resolver,
+        )
+        .with_title("Prefetch Payloads")
+        .with_confirm_text("Fetch")
+        .with_cancel_text("Cancel")
+        .with_cost(cost::estimate_line(Operation::SecretAccess, secrets.len()));
+
+        Self { secrets, dialog }
+    }
+}
+
+impl Modal for PrefetchDialog {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        Ok(match self.dialog.handle_key(key)? {
+            EventResult::Event(ConfirmEvent::Confirmed) => {
+                PayloadMsg::PrefetchConfirmed(self.secrets.clone()).into()
+            }
+            EventResult::Event(ConfirmEvent::Cancelled) => SecretManagerMsg::DialogCancelled.into(),
+            _ => EventResult::Consumed,
+        })
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.dialog.render(frame, area, theme);
     }
 }
 
 // === Update Logic ===
 
+#[allow(clippy::too_many_lines)]
 pub(super) fn update(state: &mut SecretManager, msg: PayloadMsg) -> Result<ServiceMsg> {
     match msg {
         PayloadMsg::Load { secret, version } => {
@@ -164,15 +523,15 @@ pub(super) fn update(state: &mut SecretManager, msg: PayloadMsg) -> Result<Servi
 
             match version {
                 Some(v) => Ok(FetchPayloadCmd {
+                    client: state.get_client_for(&secret)?,
                     secret,
                     version: v,
-                    client: state.get_client()?,
                     tx: state.get_msg_sender(),
                 }
                 .into()),
                 None => Ok(FetchLatestPayloadCmd {
+                    client: state.get_client_for(&secret)?,
                     secret,
-                    client: state.get_client()?,
                     tx: state.get_msg_sender(),
                 }
                 .into()),
@@ -195,12 +554,113 @@ pub(super) fn update(state: &mut SecretManager, msg: PayloadMsg) -> Result<Servi
             Ok(ServiceMsg::Idle)
         }
 
+        PayloadMsg::Prefetch(secrets) => {
+            let secrets: Vec<Secret> = secrets
+                .into_iter()
+                .filter(|secret| state.get_cached_payload(secret, None).is_none())
+                .collect();
+            if secrets.is_empty() {
+                return Ok(ServiceMsg::Idle);
+            }
+            // Fetching is billed per access, so ask first when costs are shown
+            if cost::estimate(Operation::SecretAccess, secrets.len()).is_some() {
+                state.display_overlay(PrefetchDialog::new(secrets, state.get_resolver()));
+                return Ok(ServiceMsg::Idle);
+            }
+            update(state, PayloadMsg::PrefetchConfirmed(secrets))
+        }
+
+        PayloadMsg::PrefetchConfirmed(secrets) => {
+            state.close_overlay();
+            Ok(PrefetchPayloadsCmd {
+                progress: Progress::new(secrets.len()),
+                secrets,
+                client: state.get_client()?,
+                tx: state.get_msg_sender(),
+            }
+            .into())
+        }
+
+        PayloadMsg::Prefetched { secret, payload } => {
+            state.cache_payload(&secret, None, payload);
+            Ok(ServiceMsg::Idle)
+        }
+
         PayloadMsg::Copy { data, description } => {
             Ok(CopyToClipboardCmd::new(data, description).into())
         }
+
+        PayloadMsg::ConfirmQrCode { title, data } => {
+            state.display_overlay(QrCodeWarningDialog::new(title, data, state.get_resolver()));
+            Ok(ServiceMsg::Idle)
+        }
+
+        PayloadMsg::ShowQrCode { title, data } => {
+            match QrCode::new(data.as_bytes()) {
+                Some(code) => state.display_overlay(QrCodeDialog::new(title, code)),
+                None => state.close_overlay(),
+            }
+            Ok(ServiceMsg::Idle)
+        }
+
+        PayloadMsg::StartSave { secret, version } => {
+            state.display_overlay(SavePayloadDialog::new(secret, version));
+            Ok(ServiceMsg::Idle)
+        }
+
+        PayloadMsg::Save {
+            secret,
+            version,
+            path,
+            overwrite,
+        } => {
+            if !overwrite && path.exists() {
+                let dialog = OverwriteFileDialog::new(secret, version, path, state.get_resolver());
+                state.display_overlay(dialog);
+                return Ok(ServiceMsg::Idle);
+            }
+            state.close_overlay();
+
+            Ok(SavePayloadCmd {
+                client: state.get_client_for(&secret)?,
+                secret,
+                version,
+                path,
+            }
+            .into())
+        }
+    }
+}
+
+// === Helper Functions ===
+
+pub(super) fn version_label(version: Option<&SecretVersion>) -> &str {
+    version.map_or("latest", |v| v.version_id.as_str())
+}
+
+pub(super) fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
     }
 }
 
+/// Write `data` to `path`, readable only by the current user.
+fn write_private(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // The mode only applies to new files
+        if path.exists() {
+            fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+        }
+    }
+    options.open(path)?.write_all(data)
+}
+
 // === Commands ===
 
 struct FetchPayloadCmd {
@@ -219,6 +679,10 @@ impl Command for FetchPayloadCmd {
         )
     }
 
+    fn resource(&self) -> Option<String> {
+        Some(self.secret.name.clone())
+    }
+
     async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
         let payload = self
             .client
@@ -248,6 +712,10 @@ impl Command for FetchLatestPayloadCmd {
         format!("Loading '{}' (latest)", self.secret.name)
     }
 
+    fn resource(&self) -> Option<String> {
+        Some(self.secret.name.clone())
+    }
+
     async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
         let payload = self.client.access_latest_version(&self.secret.name).await?;
         self.tx.send(
@@ -261,3 +729,107 @@ impl Command for FetchLatestPayloadCmd {
         Ok(())
     }
 }
+
+struct PrefetchPayloadsCmd {
+    client: SecretManagerClient,
+    secrets: Vec<Secret>,
+    progress: Progress,
+    tx: UnboundedSender<SecretManagerMsg>,
+}
+
+#[async_trait]
+impl Command for PrefetchPayloadsCmd {
+    fn name(&self) -> String {
+        "Prefetching payloads".to_string()
+    }
+
+    fn progress(&self) -> Option<Progress> {
+        Some(self.progress.clone())
+    }
+
+    async fn execute(self: Box<Self>, action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let mut fetches = pin!(
+            stream::iter(self.secrets)
+                .map(|secret| {
+                    let client = self.client.in_project(&secret.project);
+                    async move {
+                        let result = client.access_latest_version(&secret.name).await;
+                        (secret, result)
+                    }
+                })
+                .buffer_unordered(MAX_CONCURRENT_FETCHES)
+        );
+
+        let mut failed = 0;
+        while let Some((secret, result)) = fetches.next().await {
+            match result {
+                Ok(payload) => self
+                    .tx
+                    .send(PayloadMsg::Prefetched { secret, payload }.into())?,
+                Err(err) => {
+                    warn!(secret = %secret.name, %err, "Could not prefetch payload");
+                    failed += 1;
+                }
+            }
+            self.progress.advance();
+        }
+
+        let (done, _) = self.progress.get();
+        let (message, toast_type) = if failed == 0 {
+            (format!("Prefetched {done} payloads"), ToastType::Success)
+        } else {
+            (
+                format!("Prefetched {} payloads, {failed} failed", done - failed),
+                ToastType::Warning,
+            )
+        };
+        action_tx.send(AppMessage::ShowToast {
+            message,
+            toast_type,
+        })?;
+        Ok(())
+    }
+}
+
+struct SavePayloadCmd {
+    client: SecretManagerClient,
+    secret: Secret,
+    version: Option<SecretVersion>,
+    path: PathBuf,
+}
+
+#[async_trait]
+impl Command for SavePayloadCmd {
+    fn name(&self) -> String {
+        format!(
+            "Saving '{}' v{} to {}",
+            self.secret.name,
+            version_label(self.version.as_ref()),
+            self.path.display()
+        )
+    }
+
+    fn mutates(&self) -> bool {
+        true
+    }
+
+    fn resource(&self) -> Option<String> {
+        Some(self.secret.name.clone())
+    }
+
+    async fn execute(self: Box<Self>, action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let data = self
+            .client
+            .access_version_bytes(&self.secret.name, version_label(self.version.as_ref()))
+            .await?;
+        let size = data.len();
+        let data = encryption::encrypt_for(&self.path, data.to_vec()).await?;
+        write_private(&self.path, &data)?;
+
+        action_tx.send(AppMessage::ShowToast {
+            message: format!("Saved {size} bytes to {}", self.path.display()),
+            toast_type: ToastType::Success,
+        })?;
+        Ok(())
+    }
+}
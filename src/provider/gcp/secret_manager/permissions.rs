@@ -0,0 +1,516 @@
+//! IAM permission pre-flight check.
+//!
+//! When the client connects, the service asks which of the permissions its
+//! actions need the account holds. Actions it lacks a permission for are
+//! greyed out in the keybindings and refused before any request is sent, and
+//! the permissions overlay lists what is missing.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::warn;
+
+use crate::Theme;
+use crate::app::AppMessage;
+use crate::commands::Command;
+use crate::config::{DialogAction, KeyResolver, SecretsAction, VersionsAction};
+use crate::error::Error;
+use crate::i18n::t;
+use crate::provider::gcp::secret_manager::SecretManager;
+use crate::provider::gcp::secret_manager::access_stats::AccessStatsMsg;
+use crate::provider::gcp::secret_manager::accessors::AccessorsMsg;
+use crate::provider::gcp::secret_manager::annotations::AnnotationsMsg;
+use crate::provider::gcp::secret_manager::client::SecretManagerClient;
+use crate::provider::gcp::secret_manager::deep_search::DeepSearchMsg;
+use crate::provider::gcp::secret_manager::duplicates::DuplicatesMsg;
+use crate::provider::gcp::secret_manager::kube_sync::KubeSyncMsg;
+use crate::provider::gcp::secret_manager::payload::PayloadMsg;
+use crate::provider::gcp::secret_manager::promote::PromoteMsg;
+use crate::provider::gcp::secret_manager::registry::RegistryMsg;
+use crate::provider::gcp::secret_manager::secrets::SecretsMsg;
+use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
+use crate::provider::gcp::secret_manager::stale::StaleMsg;
+use crate::provider::gcp::secret_manager::topics::TopicsMsg;
+use crate::provider::gcp::secret_manager::versions::VersionsMsg;
+use crate::service::ServiceMsg;
+use crate::ui::text::{display_width, padding};
+use crate::ui::{EventResult, Modal, Result};
+
+// === Models ===
+
+/// A keybinding that needs an IAM permission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GatedAction {
+    Secrets(SecretsAction),
+    Versions(VersionsAction),
+}
+
+impl GatedAction {
+    /// Screen the keybinding belongs to.
+    const fn screen(self) -> &'static str {
+        match self {
+            Self::Secrets(_) => "secrets",
+            Self::Versions(_) => "versions",
+        }
+    }
+
+    fn display(self, resolver: &KeyResolver) -> String {
+        match self {
+            Self::Secrets(action) => resolver.display_secrets(action),
+            Self::Versions(action) => resolver.display_versions(action),
+        }
+    }
+
+    /// The action `msg` starts, for messages sent by these keybindings.
+    const fn from_msg(msg: &SecretManagerMsg) -> Option<Self> {
+        let action = match msg {
+            SecretManagerMsg::Secret(msg) => match msg {
+                SecretsMsg::ViewPayload(_) => Self::Secrets(SecretsAction::ViewPayload),
+                SecretsMsg::CopyPayload(_) => Self::Secrets(SecretsAction::Copy),
+                SecretsMsg::ViewVersions(_) => Self::Secrets(SecretsAction::Versions),
+                SecretsMsg::StartCreation => Self::Secrets(SecretsAction::New),
+                SecretsMsg::ConfirmDelete(_) => Self::Secrets(SecretsAction::Delete),
+                SecretsMsg::ViewLabels(_) => Self::Secrets(SecretsAction::Labels),
+                SecretsMsg::ViewIamPolicy(_) => Self::Secrets(SecretsAction::Iam),
+                _ => return None,
+            },
+            SecretManagerMsg::DeepSearch(DeepSearchMsg::Start(_)) => {
+                Self::Secrets(SecretsAction::DeepSearch)
+            }
+            SecretManagerMsg::Duplicates(DuplicatesMsg::Start(_)) => {
+                Self::Secrets(SecretsAction::Duplicates)
+            }
+            SecretManagerMsg::Stale(StaleMsg::Run(_)) => Self::Secrets(SecretsAction::StaleReport),
+            SecretManagerMsg::Promote(PromoteMsg::Start(_)) => {
+                Self::Secrets(SecretsAction::CopyToContext)
+            }
+            SecretManagerMsg::KubeSync(KubeSyncMsg::Start(_)) => {
+                Self::Secrets(SecretsAction::SyncToCluster)
+            }
+            SecretManagerMsg::Registry(RegistryMsg::Start(_)) => {
+                Self::Secrets(SecretsAction::RegistryCredentials)
+            }
+            SecretManagerMsg::AccessStats(AccessStatsMsg::Load) => {
+                Self::Secrets(SecretsAction::AccessStats)
+            }
+            SecretManagerMsg::Annotations(AnnotationsMsg::View(_)) => {
+                Self::Secrets(SecretsAction::Annotations)
+            }
+            SecretManagerMsg::Topics(TopicsMsg::View(_)) => Self::Secrets(SecretsAction::Topics),
+            SecretManagerMsg::Accessors(AccessorsMsg::Load(_)) => {
+                Self::Secrets(SecretsAction::Accessors)
+            }
+            SecretManagerMsg::Version(msg) => match msg {
+                VersionsMsg::ViewPayload { .. } => Self::Versions(VersionsAction::ViewPayload),
+                VersionsMsg::StartCreation(_) => Self::Versions(VersionsAction::Add),
+                VersionsMsg::Disable { .. } => Self::Versions(VersionsAction::Disable),
+                VersionsMsg::Enable { .. } => Self::Versions(VersionsAction::Enable),
+                VersionsMsg::ConfirmDestroy { .. } => Self::Versions(VersionsAction::Destroy),
+                _ => return None,
+            },
+            SecretManagerMsg::Payload(PayloadMsg::StartSave { .. }) => {
+                Self::Versions(VersionsAction::Save)
+            }
+            _ => return None,
+        };
+        Some(action)
+    }
+}
+
+/// What an action is called in the overlay and the permission it needs.
+#[derive(Debug)]
+pub struct Requirement {
+    pub action: GatedAction,
+    pub label: &'static str,
+    pub permission: &'static str,
+}
+
+const fn secrets(
+    action: SecretsAction,
+    label: &'static str,
+    permission: &'static str,
+) -> Requirement {
+    Requirement {
+        action: GatedAction::Secrets(action),
+        label,
+        permission,
+    }
+}
+
+const fn versions(
+    action: VersionsAction,
+    label: &'static str,
+    permission: &'static str,
+) -> Requirement {
+    Requirement {
+        action: GatedAction::Versions(action),
+        label,
+        permission,
+    }
+}
+
+const REQUIREMENTS: &[Requirement] = &[
+    secrets(
+        SecretsAction::New,
+        "Create secrets",
+        "secretmanager.secrets.create",
+    ),
+    secrets(
+        SecretsAction::Delete,
+        "Delete secrets",
+        "secretmanager.secrets.delete",
+    ),
+    secrets(
+        SecretsAction::Labels,
+        "Edit labels",
+        "secretmanager.secrets.update",
+    ),
+    secrets(
+        SecretsAction::Annotations,
+        "Edit annotations",
+        "secretmanager.secrets.update",
+    ),
+    secrets(
+        SecretsAction::Topics,
+        "Edit notification topics",
+        "secretmanager.secrets.update",
+    ),
+    secrets(
+        SecretsAction::Iam,
+        "View IAM policies",
+        "secretmanager.secrets.getIamPolicy",
+    ),
+    secrets(
+        SecretsAction::Accessors,
+        "View who can access",
+        "secretmanager.secrets.getIamPolicy",
+    ),
+    secrets(
+        SecretsAction::Versions,
+        "List versions",
+        "secretmanager.versions.list",
+    ),
+    secrets(
+        SecretsAction::StaleReport,
+        "Report stale secrets",
+        "secretmanager.versions.list",
+    ),
+    secrets(
+        SecretsAction::ViewPayload,
+        "View payloads",
+        "secretmanager.versions.access",
+    ),
+    secrets(
+        SecretsAction::Copy,
+        "Copy payloads",
+        "secretmanager.versions.access",
+    ),
+    secrets(
+        SecretsAction::DeepSearch,
+        "Search contents",
+        "secretmanager.versions.access",
+    ),
+    secrets(
+        SecretsAction::Duplicates,
+        "Find duplicates",
+        "secretmanager.versions.access",
+    ),
+    secrets(
+        SecretsAction::CopyToContext,
+        "Copy to context",
+        "secretmanager.versions.access",
+    ),
+    secrets(
+        SecretsAction::SyncToCluster,
+        "Sync to cluster",
+        "secretmanager.versions.access",
+    ),
+    secrets(
+        SecretsAction::RegistryCredentials,
+        "Registry credentials",
+        "secretmanager.versions.access",
+    ),
+    secrets(
+        SecretsAction::AccessStats,
+        "View access stats",
+        "monitoring.timeSeries.list",
+    ),
+    versions(
+        VersionsAction::ViewPayload,
+        "View payloads",
+        "secretmanager.versions.access",
+    ),
+    versions(
+        VersionsAction::Save,
+        "Save payloads",
+        "secretmanager.versions.access",
+    ),
+    versions(
+        VersionsAction::Add,
+        "Add versions",
+        "secretmanager.versions.add",
+    ),
+    versions(
+        VersionsAction::Disable,
+        "Disable versions",
+        "secretmanager.versions.disable",
+    ),
+    versions(
+        VersionsAction::Enable,
+        "Enable versions",
+        "secretmanager.versions.enable",
+    ),
+    versions(
+        VersionsAction::Destroy,
+        "Destroy versions",
+        "secretmanager.versions.destroy",
+    ),
+];
+
+/// Permissions the account holds in the project.
+///
+/// Until the check has run, or if it failed, every action is allowed and left
+/// to the API to refuse.
+#[derive(Debug, Clone, Default)]
+pub struct Permissions {
+    granted: Option<HashSet<String>>,
+}
+
+impl Permissions {
+    pub const fn new(granted: HashSet<String>) -> Self {
+        Self {
+            granted: Some(granted),
+        }
+    }
+
+    /// Every permission checked, without duplicates.
+    fn checked() -> Vec<&'static str> {
+        let mut permissions: Vec<&str> = REQUIREMENTS.iter().map(|req| req.permission).collect();
+        permissions.sort_unstable();
+        permissions.dedup();
+        permissions
+    }
+
+    pub const fn is_known(&self) -> bool {
+        self.granted.is_some()
+    }
+
+    pub fn allows(&self, permission: &str) -> bool {
+        self.granted
+            .as_ref()
+            .is_none_or(|granted| granted.contains(permission))
+    }
+
+    /// The requirement `msg` fails, if it starts an action the account may
+    /// not perform.
+    pub fn denied(&self, msg: &SecretManagerMsg) -> Option<&'static Requirement> {
+        let action = GatedAction::from_msg(msg)?;
+        REQUIREMENTS
+            .iter()
+            .find(|req| req.action == action && !self.allows(req.permission))
+    }
+
+    /// Keys of the actions on `screen` the account may not perform.
+    pub fn denied_keys(&self, screen: &str, resolver: &KeyResolver) -> HashSet<String> {
+        REQUIREMENTS
+            .iter()
+            .filter(|req| req.action.screen() == screen && !self.allows(req.permission))
+            .map(|req| req.action.display(resolver))
+            .collect()
+    }
+}
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum PermissionsMsg {
+    Check,
+    Checked(HashSet<String>),
+    /// An action was refused for lack of a permission.
+    Denied(&'static Requirement),
+    Show,
+}
+
+impl From<PermissionsMsg> for SecretManagerMsg {
+    fn from(msg: PermissionsMsg) -> Self {
+        Self::Permissions(msg)
+    }
+}
+
+impl From<PermissionsMsg> for EventResult<SecretManagerMsg> {
+    fn from(msg: PermissionsMsg) -> Self {
+        Self::Event(SecretManagerMsg::Permissions(msg))
+    }
+}
+
+// === Dialogs ===
+
+/// Lists each action with the permission it needs and whether it is granted.
+pub struct PermissionsOverlay {
+    permissions: Permissions,
+    resolver: Arc<KeyResolver>,
+}
+
+impl PermissionsOverlay {
+    pub const fn new(permissions: Permissions, resolver: Arc<KeyResolver>) -> Self {
+        Self {
+            permissions,
+            resolver,
+        }
+    }
+
+    fn lines(&self, theme: &Theme) -> Vec<Line<'static>> {
+        let label_style = Style::default().fg(theme.text());
+        let permission_style = Style::default().fg(theme.overlay1());
+
+        if !self.permissions.is_known() {
+            return vec![Line::from(Span::styled(
+                t("Permissions could not be checked, all actions are enabled."),
+                label_style,
+            ))];
+        }
+
+        let label_width = REQUIREMENTS
+            .iter()
+            .map(|req| display_width(t(req.label)))
+            .max()
+            .unwrap_or_default();
+        REQUIREMENTS
+            .iter()
+            .map(|req| {
+                let (symbol, symbol_style) = if self.permissions.allows(req.permission) {
+                    (theme.symbols.success, Style::default().fg(theme.green()))
+                } else {
+                    (theme.symbols.failure, Style::default().fg(theme.red()))
+                };
+                let label = t(req.label);
+                Line::from(vec![
+                    Span::styled(format!(" {symbol} "), symbol_style),
+                    Span::styled(
+                        format!("{label}{}  ", padding(label, label_width)),
+                        label_style,
+                    ),
+                    Span::styled(req.permission, permission_style),
+                ])
+            })
+            .collect()
+    }
+}
+
+impl Modal for PermissionsOverlay {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if self.resolver.matches_dialog(&key, DialogAction::Dismiss) {
+            return Ok(SecretManagerMsg::DialogCancelled.into());
+        }
+        Ok(EventResult::Consumed)
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let lines = self.lines(theme);
+        let height = u16::try_from(lines.len() + 2).unwrap_or(u16::MAX);
+        let popup_area = area.centered(Constraint::Percentage(70), Constraint::Length(height));
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(format!(" {} ", t("Permissions")))
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.border()))
+            .style(Style::default().bg(theme.base()));
+        frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+    }
+}
+
+// === Update Logic ===
+
+pub(super) fn update(state: &mut SecretManager, msg: PermissionsMsg) -> Result<ServiceMsg> {
+    match msg {
+        PermissionsMsg::Check => Ok(CheckPermissionsCmd {
+            client: state.get_client()?,
+            tx: state.get_msg_sender(),
+        }
+        .into()),
+
+        PermissionsMsg::Checked(granted) => {
+            state.set_permissions(Permissions::new(granted));
+            Ok(ServiceMsg::Idle)
+        }
+
+        PermissionsMsg::Denied(req) => Err(Error::PermissionDenied(format!(
+            "{} needs the {} permission",
+            req.label, req.permission
+        ))
+        .into()),
+
+        PermissionsMsg::Show => {
+            let overlay =
+                PermissionsOverlay::new(state.permissions().clone(), state.get_resolver());
+            state.display_overlay(overlay);
+            Ok(ServiceMsg::Idle)
+        }
+    }
+}
+
+// === Commands ===
+
+struct CheckPermissionsCmd {
+    client: SecretManagerClient,
+    tx: UnboundedSender<SecretManagerMsg>,
+}
+
+#[async_trait]
+impl Command for CheckPermissionsCmd {
+    fn name(&self) -> String {
+        "Checking permissions".to_string()
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        // Without the check every action stays enabled, so a failure is
+        // not worth interrupting the user for
+        match self.client.test_permissions(&Permissions::checked()).await {
+            Ok(granted) => self.tx.send(PermissionsMsg::Checked(granted).into())?,
+            Err(err) => warn!("Failed to check permissions: {err:#}"),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::provider::gcp::secret_manager::secrets::Secret;
+
+    #[test]
+    fn test_denied_actions() {
+        let delete: SecretManagerMsg = SecretsMsg::ConfirmDelete(Secret::test("token")).into();
+        let reload: SecretManagerMsg = SecretsMsg::Load.into();
+        assert!(Permissions::default().denied(&delete).is_none());
+
+        let permissions =
+            Permissions::new(HashSet::from(["secretmanager.versions.access".to_string()]));
+        let req = permissions.denied(&delete).unwrap();
+        assert_eq!(req.permission, "secretmanager.secrets.delete");
+        assert!(permissions.denied(&reload).is_none());
+        assert!(
+            permissions
+                .denied(&SecretsMsg::ViewPayload(Secret::test("token")).into())
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,681 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use color_eyre::eyre::eyre;
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::{Alignment, Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::Theme;
+use crate::app::AppMessage;
+use crate::commands::Command;
+use crate::config::{DialogAction, KeyResolver, PromoteAction};
+use crate::context::{CloudContext, GcpContext, load_contexts};
+use crate::error::Error;
+use crate::provider::gcp::secret_manager::SecretManager;
+use crate::provider::gcp::secret_manager::client::SecretManagerClient;
+use crate::provider::gcp::secret_manager::secrets::Secret;
+use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
+use crate::service::ServiceMsg;
+use crate::ui::{Component, EventResult, Modal, Result, Table, TableEvent, ToastType};
+
+// === Models ===
+
+/// Everything needed to copy a secret into another context.
+///
+/// Built by a dry run against both contexts, so the user can review exactly
+/// what will be written before anything changes in the target project.
+#[derive(Debug, Clone)]
+pub struct PromotionPlan {
+    pub secret: Secret,
+    pub target: GcpContext,
+    /// Labels to apply when the secret is created in the target.
+    pub labels: HashMap<String, String>,
+    /// Latest payload of the source secret, if it should be copied.
+    pub payload: Option<Vec<u8>>,
+    /// Whether a secret with the same name already exists in the target.
+    pub target_exists: bool,
+}
+
+impl PromotionPlan {
+    /// Human-readable list of the changes this plan will make.
+    pub fn steps(&self) -> Vec<String> {
+        let mut steps = Vec::new();
+        if self.target_exists {
+            steps.push(format!(
+                "Secret '{}' already exists in {} and is left as is",
+                self.secret.name, self.target.project_id
+            ));
+        } else {
+            steps.push(format!(
+                "Create secret '{}' in {}",
+                self.secret.name, self.target.project_id
+            ));
+            if !self.labels.is_empty() {
+                let mut labels: Vec<String> = self
+                    .labels
+                    .iter()
+                    .map(|(k, v)| format!("{k}={v}"))
+                    .collect();
+                labels.sort();
+                steps.push(format!("Apply labels: {}", labels.join(", ")));
+            }
+        }
+        match &self.payload {
+            Some(payload) => steps.push(format!(
+                "Add a new version with the latest payload ({} bytes)",
+                payload.len()
+            )),
+            None => steps.push("Do not copy any payload".to_string()),
+        }
+        steps
+    }
+
+    /// True if executing the plan would not change anything.
+    pub const fn is_noop(&self) -> bool {
+        self.target_exists && self.payload.is_none()
+    }
+}
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum PromoteMsg {
+    Start(Secret),
+    Plan {
+        secret: Secret,
+        target: GcpContext,
+        include_payload: bool,
+        preserve_labels: bool,
+    },
+    Planned(PromotionPlan),
+    Execute(PromotionPlan),
+}
+
+impl From<PromoteMsg> for SecretManagerMsg {
+    fn from(msg: PromoteMsg) -> Self {
+        Self::Promote(msg)
+    }
+}
+
+impl From<PromoteMsg> for EventResult<SecretManagerMsg> {
+    fn from(msg: PromoteMsg) -> Self {
+        Self::Event(SecretManagerMsg::Promote(msg))
+    }
+}
+
+// === Wizards & Dialogs ===
+
+enum PromoteWizardStep {
+    Target,
+    Options,
+}
+
+/// Picks the target context and copy options for a secret.
+pub struct PromoteSecretWizard {
+    secret: Secret,
+    step: PromoteWizardStep,
+    targets: Table<CloudContext>,
+    target: Option<GcpContext>,
+    include_payload: bool,
+    preserve_labels: bool,
+    resolver: Arc<KeyResolver>,
+}
+
+impl PromoteSecretWizard {
+    pub fn new(secret: Secret, targets: Vec<CloudContext>, resolver: Arc<KeyResolver>) -> Self {
+        let title = format!(" Copy '{}' to context ", secret.name);
+        Self {
+            secret,
+            step: PromoteWizardStep::Target,
+            targets: Table::new(targets, resolver.clone()).with_title(title),
+            target: None,
+            include_payload: true,
+            preserve_labels: true,
+            resolver,
+        }
+    }
+
+    fn handle_target_key(&mut self, key: KeyEvent) -> Result<EventResult<SecretManagerMsg>> {
+        let result = self.targets.handle_key(key)?;
+        if let EventResult::Event(TableEvent::Activated(CloudContext::Gcp(ctx))) = result {
+            self.target = Some(ctx);
+            self.step = PromoteWizardStep::Options;
+            return Ok(EventResult::Consumed);
+        }
+        if result.is_consumed() {
+            return Ok(EventResult::Consumed);
+        }
+        if self.resolver.matches_dialog(&key, DialogAction::Cancel) {
+            return Ok(SecretManagerMsg::DialogCancelled.into());
+        }
+        Ok(EventResult::Consumed)
+    }
+
+    fn handle_options_key(&mut self, key: KeyEvent) -> EventResult<SecretManagerMsg> {
+        if self.resolver.matches_promote(&key, PromoteAction::Payload) {
+            self.include_payload = !self.include_payload;
+        } else if self.resolver.matches_promote(&key, PromoteAction::Labels) {
+            self.preserve_labels = !self.preserve_labels;
+        } else if self.resolver.matches_dialog(&key, DialogAction::Cancel) {
+            self.step = PromoteWizardStep::Target;
+        } else if self.resolver.matches_dialog(&key, DialogAction::Confirm)
+            && let Some(target) = self.target.clone()
+        {
+            return PromoteMsg::Plan {
+                secret: self.secret.clone(),
+                target,
+                include_payload: self.include_payload,
+                preserve_labels: self.preserve_labels,
+            }
+            .into();
+        }
+        EventResult::Consumed
+    }
+
+    fn render_options(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = area.centered(Constraint::Percentage(50), Constraint::Length(10));
+        frame.render_widget(Clear, popup_area);
+
+        let key_style = Style::default()
+            .fg(theme.peach())
+            .add_modifier(Modifier::BOLD);
+        let text_style = Style::default().fg(theme.text());
+        let hint_style = Style::default().fg(theme.overlay1());
+        let checkbox = |checked: bool| if checked { "[x]" } else { "[ ]" };
+
+        let target = self.target.as_ref().map_or_else(String::new, |t| {
+            format!("{} ({})", t.display_name, t.project_id)
+        });
+
+        let lines = vec![
+            Line::from(vec![
+                Span::styled("Target: ", hint_style),
+                Span::styled(target, text_style.add_modifier(Modifier::BOLD)),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled(
+                    format!(
+                        "[{}] ",
+                        self.resolver.display_promote(PromoteAction::Payload)
+                    ),
+                    key_style,
+                ),
+                Span::styled(
+                    format!("{} Copy latest payload", checkbox(self.include_payload)),
+                    text_style,
+                ),
+            ]),
+            Line::from(vec![
+                Span::styled(
+                    format!(
+                        "[{}] ",
+                        self.resolver.display_promote(PromoteAction::Labels)
+                    ),
+                    key_style,
+                ),
+                Span::styled(
+                    format!("{} Preserve labels", checkbox(self.preserve_labels)),
+                    text_style,
+                ),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled(
+                format!(
+                    "{} to preview (dry run), {} to pick another context",
+                    self.resolver.display_dialog(DialogAction::Confirm),
+                    self.resolver.display_dialog(DialogAction::Cancel),
+                ),
+                hint_style,
+            )),
+        ];
+
+        let block = Block::default()
+            .title(format!(" Copy '{}' ", self.secret.name))
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.lavender()))
+            .style(Style::default().bg(theme.base()));
+
+        frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+    }
+}
+
+impl Modal for PromoteSecretWizard {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        match self.step {
+            PromoteWizardStep::Target => self.handle_target_key(key),
+            PromoteWizardStep::Options => Ok(self.handle_options_key(key)),
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        match self.step {
+            PromoteWizardStep::Target => Ok(self.targets.handle_paste(text)?.consumed_only()),
+            PromoteWizardStep::Options => Ok(EventResult::Consumed),
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        match self.step {
+            PromoteWizardStep::Target => {
+                let popup_area =
+                    area.centered(Constraint::Percentage(70), Constraint::Percentage(60));
+                frame.render_widget(Clear, popup_area);
+                frame.render_widget(
+                    Block::default().style(Style::default().bg(theme.base())),
+                    popup_area,
+                );
+                self.targets.render(frame, popup_area, theme);
+            }
+            PromoteWizardStep::Options => self.render_options(frame, area, theme),
+        }
+    }
+}
+
+/// Shows the result of the dry run and asks for confirmation.
+pub struct PromotionPreviewDialog {
+    plan: PromotionPlan,
+    resolver: Arc<KeyResolver>,
+}
+
+impl PromotionPreviewDialog {
+    pub const fn new(plan: PromotionPlan, resolver: Arc<KeyResolver>) -> Self {
+        Self { plan, resolver }
+    }
+}
+
+impl Modal for PromotionPreviewDialog {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if self.resolver.matches_dialog(&key, DialogAction::Confirm) && !self.plan.is_noop() {
+            return Ok(PromoteMsg::Execute(self.plan.clone()).into());
+        }
+        if self.resolver.matches_dialog(&key, DialogAction::Cancel) {
+            return Ok(SecretManagerMsg::DialogCancelled.into());
+        }
+        Ok(EventResult::Consumed)
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let steps = self.plan.steps();
+        #[allow(clippy::cast_possible_truncation)]
+        let height = steps.len() as u16 + 8;
+        let popup_area = area.centered(Constraint::Percentage(60), Constraint::Length(height));
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(" Dry Run ")
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.lavender()))
+            .style(Style::default().bg(theme.base()));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [summary_area, footer_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(inner);
+
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled("From ", Style::default().fg(theme.overlay1())),
+                Span::styled(
+                    self.plan.secret.name.clone(),
+                    Style::default()
+                        .fg(theme.text())
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(" to ", Style::default().fg(theme.overlay1())),
+                Span::styled(
+                    format!(
+                        "{} ({})",
+                        self.plan.target.display_name, self.plan.target.project_id
+                    ),
+                    Style::default()
+                        .fg(theme.yellow())
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]),
+            Line::from(""),
+        ];
+        lines.extend(steps.into_iter().map(|step| {
+            Line::from(vec![
+                Span::styled(
+                    format!("  {} ", theme.symbols.bullet),
+                    Style::default().fg(theme.peach()),
+                ),
+                Span::styled(step, Style::default().fg(theme.text())),
+            ])
+        }));
+        frame.render_widget(Paragraph::new(lines), summary_area);
+
+        let cancel = self.resolver.display_dialog(DialogAction::Cancel);
+        let footer = if self.plan.is_noop() {
+            Line::from(Span::styled(
+                format!("Nothing to copy. Press {cancel} to close."),
+                Style::default().fg(theme.overlay1()),
+            ))
+        } else {
+            let key_style = Style::default()
+                .fg(theme.peach())
+                .add_modifier(Modifier::BOLD);
+            Line::from(vec![
+                Span::styled(
+                    format!("[{}]", self.resolver.display_dialog(DialogAction::Confirm)),
+                    key_style,
+                ),
+                Span::styled(" Copy    ", Style::default().fg(theme.green())),
+                Span::styled(format!("[{cancel}]"), key_style),
+                Span::styled(" Cancel", Style::default().fg(theme.overlay1())),
+            ])
+        };
+        frame.render_widget(
+            Paragraph::new(footer).alignment(Alignment::Center),
+            footer_area,
+        );
+    }
+}
+
+// === Update Logic ===
+
+pub(super) fn update(state: &mut SecretManager, msg: PromoteMsg) -> Result<ServiceMsg> {
+    match msg {
+        PromoteMsg::Start(secret) => {
+            let current = state.get_context().display_name.clone();
+            let targets: Vec<CloudContext> = load_contexts()
+                .into_iter()
+                .filter(|ctx| matches!(ctx, CloudContext::Gcp(gcp) if gcp.display_name != current))
+                .collect();
+
+            if targets.is_empty() {
+                return Err(eyre!("No other GCP contexts configured to copy secrets to"));
+            }
+
+            state.display_overlay(PromoteSecretWizard::new(
+                secret,
+                targets,
+                state.get_resolver(),
+            ));
+            Ok(ServiceMsg::Idle)
+        }
+
+        PromoteMsg::Plan {
+            secret,
+            target,
+            include_payload,
+            preserve_labels,
+        } => {
+            state.close_overlay();
+            state.display_loading_spinner("Preparing dry run...");
+
+            Ok(PlanPromotionCmd {
+                source: state.get_client_for(&secret)?,
+                secret,
+                target,
+                include_payload,
+                preserve_labels,
+                tx: state.get_msg_sender(),
+            }
+            .into())
+        }
+
+        PromoteMsg::Planned(plan) => {
+            state.hide_loading_spinner();
+            state.display_overlay(PromotionPreviewDialog::new(plan, state.get_resolver()));
+            Ok(ServiceMsg::Idle)
+        }
+
+        PromoteMsg::Execute(plan) => {
+            state.close_overlay();
+            Ok(ExecutePromotionCmd { plan }.into())
+        }
+    }
+}
+
+// === Commands ===
+
+/// Dry run: reads the source secret and checks the target without writing.
+struct PlanPromotionCmd {
+    source: SecretManagerClient,
+    secret: Secret,
+    target: GcpContext,
+    include_payload: bool,
+    preserve_labels: bool,
+    tx: UnboundedSender<SecretManagerMsg>,
+}
+
+#[async_trait]
+impl Command for PlanPromotionCmd {
+    fn name(&self) -> String {
+        format!(
+            "Planning copy of '{}' to {}",
+            self.secret.name, self.target.display_name
+        )
+    }
+
+    fn resource(&self) -> Option<String> {
+        Some(self.secret.name.clone())
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let target_client = SecretManagerClient::new(&self.target).await?;
+        let target_exists = match target_client.get_secret(&self.secret.name).await {
+            Ok(_) => true,
+            Err(err) => match Error::from_report(&err) {
+                Error::NotFound(_) => false,
+                _ => return Err(err),
+            },
+        };
+
+        let payload = if self.include_payload {
+            Some(
+                self.source
+                    .access_version_bytes(&self.secret.name, "latest")
+                    .await?
+                    .to_vec(),
+            )
+        } else {
+            None
+        };
+
+        let labels = if self.preserve_labels {
+            self.secret.labels.clone()
+        } else {
+            HashMap::new()
+        };
+
+        self.tx.send(
+            PromoteMsg::Planned(PromotionPlan {
+                secret: self.secret,
+                target: self.target,
+                labels,
+                payload,
+                target_exists,
+            })
+            .into(),
+        )?;
+        Ok(())
+    }
+}
+
+struct ExecutePromotionCmd {
+    plan: PromotionPlan,
+}
+
+#[async_trait]
+impl Command for ExecutePromotionCmd {
+    fn name(&self) -> String {
+        format!(
+            "Copying '{}' to {}",
+            self.plan.secret.name, self.plan.target.display_name
+        )
+    }
+
+    fn mutates(&self) -> bool {
+        true
+    }
+
+    async fn execute(self: Box<Self>, action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let plan = self.plan;
+        let client = SecretManagerClient::new(&plan.target).await?;
+
+        if !plan.target_exists {
+            client
+                .create_secret_with_labels(&plan.secret.name, plan.labels.clone())
+                .await?;
+        }
+        if let Some(payload) = &plan.payload {
+            client
+                .add_secret_version(&plan.secret.name, payload)
+                .await?;
+        }
+
+        action_tx.send(AppMessage::ShowToast {
+            message: format!(
+                "Copied '{}' to {}",
+                plan.secret.name, plan.target.display_name
+            ),
+            toast_type: ToastType::Success,
+        })?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::config::keybindings::KeybindingsConfig;
+    use crate::context::AuthMethod;
+    use crate::provider::gcp::secret_manager::demo::DemoSecretManager;
+
+    fn context(project_id: &str) -> GcpContext {
+        GcpContext {
+            display_name: project_id.to_string(),
+            project_id: project_id.to_string(),
+            account: "demo@acme.example".to_string(),
+            region: None,
+            zone: None,
+            auth: AuthMethod::Demo,
+            accounts: Vec::new(),
+            projects: Vec::new(),
+        }
+    }
+
+    async fn plan(
+        source: &SecretManagerClient,
+        target: &str,
+        include_payload: bool,
+    ) -> PromotionPlan {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let (action_tx, _action_rx) = mpsc::unbounded_channel();
+        let cmd = PlanPromotionCmd {
+            source: source.clone(),
+            secret: source.get_secret("stripe-api-key").await.unwrap(),
+            target: context(target),
+            include_payload,
+            preserve_labels: true,
+            tx,
+        };
+        Box::new(cmd).execute(action_tx).await.unwrap();
+        match rx.recv().await {
+            Some(SecretManagerMsg::Promote(PromoteMsg::Planned(plan))) => plan,
+            other => panic!("expected a plan, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plan_and_execute_copy_payload_bytes() {
+        let source = SecretManagerClient::new(&context("acme-dev"))
+            .await
+            .unwrap();
+        let latest = source
+            .access_version_bytes("stripe-api-key", "latest")
+            .await
+            .unwrap();
+
+        let first = plan(&source, "acme-promote", true).await;
+        assert!(!first.target_exists);
+        assert_eq!(first.payload.as_deref(), Some(latest.as_ref()));
+        assert_eq!(
+            first.steps()[0],
+            "Create secret 'stripe-api-key' in acme-promote"
+        );
+        assert_eq!(first.steps()[1], "Apply labels: env=dev, team=payments");
+
+        let (action_tx, _action_rx) = mpsc::unbounded_channel();
+        Box::new(ExecutePromotionCmd { plan: first })
+            .execute(action_tx)
+            .await
+            .unwrap();
+        let target = SecretManagerClient::new(&context("acme-promote"))
+            .await
+            .unwrap();
+        assert_eq!(
+            target
+                .access_version_bytes("stripe-api-key", "latest")
+                .await
+                .unwrap(),
+            latest
+        );
+
+        let second = plan(&source, "acme-promote", false).await;
+        assert!(second.target_exists);
+        assert!(second.is_noop());
+    }
+
+    #[test]
+    fn test_options_keys_toggle_and_plan() {
+        let resolver = Arc::new(KeyResolver::new(Arc::new(KeybindingsConfig::default())));
+        let demo = DemoSecretManager::open("acme-dev");
+        let mut wizard = PromoteSecretWizard::new(
+            demo.get_secret("stripe-api-key").unwrap(),
+            Vec::new(),
+            resolver,
+        );
+        wizard.target = Some(context("acme-prod"));
+        wizard.step = PromoteWizardStep::Options;
+        let mut press = |code| {
+            wizard
+                .handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+                .unwrap()
+        };
+
+        press(KeyCode::Char('p'));
+        let result = press(KeyCode::Enter);
+        let EventResult::Event(SecretManagerMsg::Promote(PromoteMsg::Plan {
+            include_payload,
+            preserve_labels,
+            ..
+        })) = result
+        else {
+            panic!("expected a plan request");
+        };
+        assert!(!include_payload);
+        assert!(preserve_labels);
+
+        press(KeyCode::Esc);
+        assert!(matches!(wizard.step, PromoteWizardStep::Target));
+    }
+}
@@ -0,0 +1,383 @@
+//! Docker/OCI registry credentials from a secret holding a password, token
+//! or service account key.
+//!
+//! The credentials are handed off as a `docker login` command, a
+//! `.dockerconfigjson` payload or a Kubernetes image-pull Secret. The login
+//! command reads the payload with gcloud when run, so it never lands on the
+//! clipboard.
+
+use async_trait::async_trait;
+use color_eyre::eyre::{bail, eyre};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use serde_json::json;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::Theme;
+use crate::app::AppMessage;
+use crate::clipboard::base64;
+use crate::commands::{Command, CopyToClipboardCmd};
+use crate::config::custom_actions::shell_quote;
+use crate::context::{AuthMethod, GcpContext};
+use crate::provider::gcp::secret_manager::SecretManager;
+use crate::provider::gcp::secret_manager::client::SecretManagerClient;
+use crate::provider::gcp::secret_manager::kube_sync::KubeSyncMsg;
+use crate::provider::gcp::secret_manager::secrets::Secret;
+use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
+use crate::service::ServiceMsg;
+use crate::ui::{Component, EventResult, Modal, Result, TextInput, TextInputEvent};
+
+/// Username registries accept with a service account key as password.
+const JSON_KEY_USERNAME: &str = "_json_key";
+
+// === Models ===
+
+/// Who logs in to which registry; the password is the secret's payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistryLogin {
+    pub registry: String,
+    pub username: String,
+}
+
+impl RegistryLogin {
+    /// `docker login` reading the latest payload of `secret_id` in `project`.
+    fn login_command(&self, secret_id: &str, project: &str) -> String {
+        format!(
+            "gcloud secrets versions access latest --secret={} --project={} | docker login {} --username {} --password-stdin",
+            shell_quote(secret_id),
+            shell_quote(project),
+            shell_quote(&self.registry),
+            shell_quote(&self.username),
+        )
+    }
+
+    /// The `.dockerconfigjson` payload logging in with `password`. A final
+    /// line break is dropped, as `docker login --password-stdin` does.
+    pub fn docker_config(&self, password: &str) -> String {
+        let password = password.trim_end_matches(['\r', '\n']);
+        let auth = base64(format!("{}:{password}", self.username).as_bytes());
+        json!({
+            "auths": {
+                self.registry.clone(): {
+                    "username": self.username,
+                    "password": password,
+                    "auth": auth,
+                }
+            }
+        })
+        .to_string()
+    }
+}
+
+/// Registry suggested for a context, its Artifact Registry if it has a region.
+fn suggested_registry(context: &GcpContext) -> Option<String> {
+    context
+        .region
+        .as_ref()
+        .map(|region| format!("{region}-docker.pkg.dev"))
+}
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum RegistryMsg {
+    /// Ask for the registry and username to log in with.
+    Start(Secret),
+    CopyLoginCommand {
+        secret: Secret,
+        login: RegistryLogin,
+    },
+    CopyDockerConfig {
+        secret: Secret,
+        login: RegistryLogin,
+    },
+}
+
+impl From<RegistryMsg> for SecretManagerMsg {
+    fn from(msg: RegistryMsg) -> Self {
+        Self::Registry(msg)
+    }
+}
+
+impl From<RegistryMsg> for EventResult<SecretManagerMsg> {
+    fn from(msg: RegistryMsg) -> Self {
+        Self::Event(SecretManagerMsg::Registry(msg))
+    }
+}
+
+// === Wizards ===
+
+enum RegistryWizardStep {
+    Registry,
+    Username,
+    Output,
+}
+
+/// Asks for the registry and username, then what to hand the credentials
+/// off as.
+pub struct RegistryWizard {
+    secret: Secret,
+    step: RegistryWizardStep,
+    registry: TextInput,
+    username: TextInput,
+}
+
+impl RegistryWizard {
+    pub fn new(secret: Secret, registry: Option<String>) -> Self {
+        let input = TextInput::new("Registry")
+            .with_placeholder("europe-docker.pkg.dev")
+            .with_validator(|value| {
+                if value.trim().is_empty() || value.trim().contains(char::is_whitespace) {
+                    Err("Enter a registry host, e.g. ghcr.io".to_string())
+                } else {
+                    Ok(())
+                }
+            });
+        Self {
+            secret,
+            step: RegistryWizardStep::Registry,
+            registry: match registry {
+                Some(registry) => input.with_value(registry),
+                None => input,
+            },
+            username: TextInput::new("Username")
+                .with_value(JSON_KEY_USERNAME)
+                .with_validator(|value| {
+                    if value.trim().is_empty() {
+                        Err("Enter a username".to_string())
+                    } else {
+                        Ok(())
+                    }
+                }),
+        }
+    }
+
+    fn login(&self) -> RegistryLogin {
+        RegistryLogin {
+            registry: self.registry.value().trim().to_string(),
+            username: self.username.value().trim().to_string(),
+        }
+    }
+
+    fn handle_output_key(&mut self, key: KeyEvent) -> EventResult<SecretManagerMsg> {
+        let secret = self.secret.clone();
+        let login = self.login();
+        match key.code {
+            KeyCode::Char('l') => RegistryMsg::CopyLoginCommand { secret, login }.into(),
+            KeyCode::Char('j') => RegistryMsg::CopyDockerConfig { secret, login }.into(),
+            KeyCode::Char('k') => KubeSyncMsg::StartPullSecret { secret, login }.into(),
+            KeyCode::Esc => {
+                self.step = RegistryWizardStep::Username;
+                EventResult::Consumed
+            }
+            _ => EventResult::Consumed,
+        }
+    }
+
+    fn render_output(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = area.centered(Constraint::Percentage(50), Constraint::Length(10));
+        frame.render_widget(Clear, popup_area);
+
+        let key_style = Style::default()
+            .fg(theme.peach())
+            .add_modifier(Modifier::BOLD);
+        let text_style = Style::default().fg(theme.text());
+        let hint_style = Style::default().fg(theme.overlay1());
+        let login = self.login();
+
+        let lines = vec![
+            Line::from(vec![
+                Span::styled("Login: ", hint_style),
+                Span::styled(
+                    format!("{} @ {}", login.username, login.registry),
+                    text_style.add_modifier(Modifier::BOLD),
+                ),
+            ]),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("[l] ", key_style),
+                Span::styled("Copy docker login command", text_style),
+            ]),
+            Line::from(vec![
+                Span::styled("[j] ", key_style),
+                Span::styled("Copy .dockerconfigjson", text_style),
+            ]),
+            Line::from(vec![
+                Span::styled("[k] ", key_style),
+                Span::styled("Write an image-pull Secret to a cluster", text_style),
+            ]),
+            Line::from(""),
+            Line::from(Span::styled("Esc to change the username", hint_style)),
+        ];
+
+        let block = Block::default()
+            .title(format!(
+                " Registry Credentials from '{}' ",
+                self.secret.name
+            ))
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.lavender()))
+            .style(Style::default().bg(theme.base()));
+
+        frame.render_widget(Paragraph::new(lines).block(block), popup_area);
+    }
+}
+
+impl Modal for RegistryWizard {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        Ok(match self.step {
+            RegistryWizardStep::Registry => match self.registry.handle_key(key)? {
+                EventResult::Event(TextInputEvent::Submitted(_)) => {
+                    self.step = RegistryWizardStep::Username;
+                    EventResult::Consumed
+                }
+                EventResult::Event(TextInputEvent::Cancelled) => {
+                    SecretManagerMsg::DialogCancelled.into()
+                }
+                _ => EventResult::Consumed,
+            },
+            RegistryWizardStep::Username => {
+                match self.username.handle_key(key)? {
+                    EventResult::Event(TextInputEvent::Submitted(_)) => {
+                        self.step = RegistryWizardStep::Output;
+                    }
+                    EventResult::Event(TextInputEvent::Cancelled) => {
+                        self.step = RegistryWizardStep::Registry;
+                    }
+                    _ => {}
+                }
+                EventResult::Consumed
+            }
+            RegistryWizardStep::Output => self.handle_output_key(key),
+        })
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        match self.step {
+            RegistryWizardStep::Registry => Ok(self.registry.handle_paste(text)?.consumed_only()),
+            RegistryWizardStep::Username => Ok(self.username.handle_paste(text)?.consumed_only()),
+            RegistryWizardStep::Output => Ok(EventResult::Consumed),
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        match self.step {
+            RegistryWizardStep::Registry => self.registry.render(frame, area, theme),
+            RegistryWizardStep::Username => self.username.render(frame, area, theme),
+            RegistryWizardStep::Output => self.render_output(frame, area, theme),
+        }
+    }
+}
+
+// === Update Logic ===
+
+pub(super) fn update(state: &mut SecretManager, msg: RegistryMsg) -> Result<ServiceMsg> {
+    match msg {
+        RegistryMsg::Start(secret) => {
+            let registry = suggested_registry(state.get_context());
+            state.display_overlay(RegistryWizard::new(secret, registry));
+            Ok(ServiceMsg::Idle)
+        }
+
+        RegistryMsg::CopyLoginCommand { secret, login } => {
+            state.close_overlay();
+            let context = state.get_context();
+            if matches!(context.auth, AuthMethod::Vault { .. }) {
+                bail!(
+                    "The local vault can't be read by gcloud, copy the .dockerconfigjson instead"
+                );
+            }
+            let command = login.login_command(&secret.name, &secret.project);
+            Ok(
+                CopyToClipboardCmd::new(command, format!("docker login for '{}'", secret.name))
+                    .into(),
+            )
+        }
+
+        RegistryMsg::CopyDockerConfig { secret, login } => {
+            state.close_overlay();
+            Ok(CopyDockerConfigCmd {
+                client: state.get_client_for(&secret)?,
+                secret,
+                login,
+            }
+            .into())
+        }
+    }
+}
+
+// === Commands ===
+
+/// Reads the payload and copies the `.dockerconfigjson` made from it.
+struct CopyDockerConfigCmd {
+    client: SecretManagerClient,
+    secret: Secret,
+    login: RegistryLogin,
+}
+
+#[async_trait]
+impl Command for CopyDockerConfigCmd {
+    fn name(&self) -> String {
+        format!("Building .dockerconfigjson from '{}'", self.secret.name)
+    }
+
+    fn resource(&self) -> Option<String> {
+        Some(self.secret.name.clone())
+    }
+
+    async fn execute(self: Box<Self>, action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let payload = self.client.access_latest_version(&self.secret.name).await?;
+        if payload.is_binary {
+            return Err(eyre!(
+                "The payload of '{}' is not a text password",
+                self.secret.name
+            ));
+        }
+        let config = self.login.docker_config(&payload.data);
+        Box::new(CopyToClipboardCmd::new(
+            config,
+            format!(".dockerconfigjson for '{}'", self.secret.name),
+        ))
+        .execute(action_tx)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_credentials() {
+        let login = RegistryLogin {
+            registry: "europe-docker.pkg.dev".to_string(),
+            username: JSON_KEY_USERNAME.to_string(),
+        };
+        assert_eq!(
+            login.login_command("ci-pull-key", "my project"),
+            "gcloud secrets versions access latest --secret=ci-pull-key --project='my project' | docker login europe-docker.pkg.dev --username _json_key --password-stdin"
+        );
+
+        let config: serde_json::Value =
+            serde_json::from_str(&login.docker_config("{\"type\": \"service_account\"}\n"))
+                .unwrap();
+        let auth = &config["auths"]["europe-docker.pkg.dev"];
+        assert_eq!(auth["password"], "{\"type\": \"service_account\"}");
+        assert_eq!(
+            auth["auth"],
+            base64(b"_json_key:{\"type\": \"service_account\"}")
+        );
+    }
+}
@@ -1,53 +1,191 @@
 use std::collections::HashMap;
 use std::fmt::Display;
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
-use crossterm::event::KeyEvent;
+use chrono::{DateTime, Utc};
+use color_eyre::eyre::eyre;
+use crossterm::event::{KeyCode, KeyEvent};
+use futures::future;
 use ratatui::Frame;
-use ratatui::layout::{Constraint, Rect};
+use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, BorderType, Borders, Cell, Paragraph};
+use ratatui::widgets::{Block, Borders, Cell, Clear, ListItem, Paragraph};
 use tokio::sync::mpsc::UnboundedSender;
-use crate::app::AppMessage;
+use tracing::warn;
+
 use crate::Theme;
-use crate::commands::{Command, CopyToClipboardCmd};
-use crate::config::{KeyResolver, SearchAction, SecretsAction};
+use crate::app::AppMessage;
+use crate::commands::{
+    Command,
+    CopyToClipboardCmd,
+    ExportRow,
+    ExportTableCmd,
+    OpenUrlCmd,
+    TableExport,
+    Workflow,
+    WorkflowStep,
+};
+use crate::config::{
+    ActionContext,
+    DialogAction,
+    GlobalAction,
+    KeyResolver,
+    SearchAction,
+    SecretsAction,
+};
+use crate::provider::gcp::console::secret_url;
+use crate::provider::gcp::org_policy::LocationPolicy;
 use crate::provider::gcp::secret_manager::SecretManager;
+use crate::provider::gcp::secret_manager::access_stats::AccessStatsMsg;
+use crate::provider::gcp::secret_manager::access_test::AccessTestMsg;
+use crate::provider::gcp::secret_manager::accessors::AccessorsMsg;
+use crate::provider::gcp::secret_manager::annotations::AnnotationsMsg;
 use crate::provider::gcp::secret_manager::client::SecretManagerClient;
-use crate::provider::gcp::secret_manager::payload::PayloadMsg;
+use crate::provider::gcp::secret_manager::deep_search::DeepSearchMsg;
+use crate::provider::gcp::secret_manager::duplicates::DuplicatesMsg;
+use crate::provider::gcp::secret_manager::enrich::{self, EnrichMsg};
+use crate::provider::gcp::secret_manager::fan_out::FanOutMsg;
+use crate::provider::gcp::secret_manager::kube_sync::KubeSyncMsg;
+use crate::provider::gcp::secret_manager::lint::LintMsg;
+use crate::provider::gcp::secret_manager::payload::{PayloadMsg, expand_home};
+use crate::provider::gcp::secret_manager::promote::PromoteMsg;
+use crate::provider::gcp::secret_manager::registry::RegistryMsg;
 use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
+use crate::provider::gcp::secret_manager::stale::StaleMsg;
+use crate::provider::gcp::secret_manager::terraform::TerraformMsg;
+use crate::provider::gcp::secret_manager::topics::TopicsMsg;
+use crate::provider::gcp::secret_manager::trash::TrashMsg;
+use crate::provider::gcp::secret_manager::undo::UndoEntry;
 use crate::provider::gcp::secret_manager::versions::VersionsMsg;
 use crate::search::Matcher;
 use crate::service::ServiceMsg;
+use crate::theme::Symbols;
+use crate::time::{self, format_timestamp, timezone_label};
+use crate::ui::text::{display_width, take_width};
 use crate::ui::{
     ColumnDef,
     Component,
     ConfirmDialog,
     ConfirmEvent,
+    DateTimeInput,
+    DateTimeInputEvent,
     EventResult,
     Keybinding,
+    List,
+    ListEvent,
+    ListRow,
     Modal,
     Result,
     Screen,
     Table,
     TableEvent,
     TableRow,
+    TextArea,
+    TextAreaEvent,
     TextInput,
     TextInputEvent,
 };
 
+/// Shortest time between two refreshes of the secret list.
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+/// Header of the annotation count column, hidden until toggled.
+const ANNOTATIONS_COLUMN: &str = "Annotations";
+/// Header of the project column, shown for contexts with several projects.
+const PROJECT_COLUMN: &str = "Project";
+
 // === Models ===
 
 /// A secret managed by GCP.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Secret {
     pub name: String,
+    /// Project the secret is in, one of the context's projects.
+    pub project: String,
     pub replication: ReplicationConfig,
     pub created_at: String,
     pub expire_time: Option<String>,
     pub labels: HashMap<String, String>,
+    /// Free-form metadata, unlike labels not usable in filters.
+    pub annotations: HashMap<String, String>,
+    /// Pub/Sub topics notified of changes, as full topic names.
+    pub topics: Vec<String>,
+    /// Changes with every update of the secret, empty where unknown.
+    pub etag: String,
+    /// What the secret list leaves out, `None` until it is loaded for the
+    /// row, see [`enrich`](super::enrich).
+    pub extras: Option<SecretExtras>,
+}
+
+/// Metadata only a secret's own request and its version list return.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecretExtras {
+    pub rotation: Option<Rotation>,
+    /// Versions in any state, including destroyed ones.
+    pub versions: usize,
+    /// State of the newest version, `None` without any versions.
+    pub latest: Option<String>,
+}
+
+/// When a secret is due to be rotated, which Secret Manager announces on
+/// its topics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rotation {
+    /// Seconds between rotations, if they repeat.
+    pub period: Option<u64>,
+    pub next: Option<String>,
+}
+
+impl Rotation {
+    /// The period, e.g. `30 days`, or `once` for a single rotation.
+    pub fn short_display(&self) -> String {
+        self.period
+            .map_or_else(|| "once".to_string(), time::format_period)
+    }
+}
+
+/// State of a secret's newest version, standing out unless it is enabled
+/// since the secret then resolves to nothing usable.
+fn latest_cell(state: Option<&str>, theme: &Theme) -> Cell<'static> {
+    let Some(state) = state else {
+        return Cell::from(theme.symbols.none);
+    };
+    let style = if state.contains("Destroyed") {
+        Style::default().fg(theme.red())
+    } else if state.contains("Disabled") {
+        Style::default().fg(theme.peach())
+    } else {
+        Style::default()
+    };
+    Cell::from(state.to_string()).style(style)
+}
+
+impl Secret {
+    /// Full name of the secret, telling apart equally named secrets of
+    /// different projects.
+    pub fn resource_name(&self) -> String {
+        format!("projects/{}/secrets/{}", self.project, self.name)
+    }
+
+    /// A secret named `name` in `acme-dev` with nothing else set.
+    #[cfg(test)]
+    pub fn test(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            project: "acme-dev".to_string(),
+            replication: ReplicationConfig::Automatic,
+            created_at: "2024-01-01 00:00".to_string(),
+            expire_time: None,
+            labels: HashMap::new(),
+            annotations: HashMap::new(),
+            topics: Vec::new(),
+            etag: String::new(),
+            extras: None,
+        }
+    }
 }
 
 impl Display for Secret {
@@ -60,10 +198,15 @@ impl TableRow for Secret {
     fn columns() -> &'static [ColumnDef] {
         static COLUMNS: &[ColumnDef] = &[
             ColumnDef::new("Name", Constraint::Min(20)),
+            ColumnDef::new(PROJECT_COLUMN, Constraint::Length(20)),
             ColumnDef::new("Replication", Constraint::Length(14)),
             ColumnDef::new("Created", Constraint::Length(18)),
             ColumnDef::new("Expiration", Constraint::Length(18)),
             ColumnDef::new("Labels", Constraint::Length(23)),
+            ColumnDef::new("Rotation", Constraint::Length(10)),
+            ColumnDef::new("Versions", Constraint::Length(8)),
+            ColumnDef::new("Latest", Constraint::Length(10)),
+            ColumnDef::new(ANNOTATIONS_COLUMN, Constraint::Length(11)),
         ];
         COLUMNS
     }
@@ -72,16 +215,40 @@ impl TableRow for Secret {
         self.render_cells_with_query(theme, "")
     }
 
-    fn render_cells_with_query(&self, _theme: &Theme, query: &str) -> Vec<Cell<'static>> {
-        let labels_display = format_labels(&self.labels, query);
-        let expiration = self.expire_time.clone().unwrap_or_else(|| "—".to_string());
+    fn render_cells_with_query(&self, theme: &Theme, query: &str) -> Vec<Cell<'static>> {
+        let labels_display = format_labels(&self.labels, query, theme.symbols);
+        let expiration = self
+            .expire_time
+            .clone()
+            .unwrap_or_else(|| theme.symbols.none.to_string());
+        // Filled in once the row was in view, see `enrich`
+        let pending = || Cell::from(theme.symbols.pending);
+        let (rotation, versions, latest) = self.extras.as_ref().map_or_else(
+            || (pending(), pending(), pending()),
+            |extras| {
+                let rotation = extras
+                    .rotation
+                    .as_ref()
+                    .map_or_else(|| theme.symbols.none.to_string(), Rotation::short_display);
+                (
+                    Cell::from(rotation),
+                    Cell::from(extras.versions.to_string()),
+                    latest_cell(extras.latest.as_deref(), theme),
+                )
+            },
+        );
 
         vec![
             Cell::from(self.name.clone()),
+            Cell::from(self.project.clone()),
             Cell::from(self.replication.short_display()),
             Cell::from(self.created_at.clone()),
             Cell::from(expiration),
             Cell::from(labels_display),
+            rotation,
+            versions,
+            latest,
+            Cell::from(self.annotations.len().to_string()),
         ]
     }
 
@@ -123,12 +290,55 @@ impl TableRow for Secret {
 
         false
     }
+
+    fn details(&self) -> Vec<(&'static str, String)> {
+        let replication = match &self.replication {
+            ReplicationConfig::Automatic => "Automatic".to_string(),
+            ReplicationConfig::UserManaged { locations } => locations.join(", "),
+        };
+        let mut details = vec![
+            ("Replication", replication),
+            ("Created", self.created_at.clone()),
+        ];
+        if let Some(expire_time) = &self.expire_time {
+            details.push(("Expiration", expire_time.clone()));
+        }
+        if let Some(extras) = &self.extras {
+            if let Some(rotation) = &extras.rotation {
+                let next = rotation.next.as_deref().unwrap_or("unscheduled");
+                details.push((
+                    "Rotation",
+                    format!("{}, next {next}", rotation.short_display()),
+                ));
+            }
+            details.push(("Versions", extras.versions.to_string()));
+            if let Some(latest) = &extras.latest {
+                details.push(("Latest", latest.clone()));
+            }
+        }
+        let mut labels: Vec<_> = self.labels.iter().collect();
+        labels.sort();
+        details.extend(labels.into_iter().enumerate().map(|(i, (key, value))| {
+            (if i == 0 { "Labels" } else { "" }, format!("{key}:{value}"))
+        }));
+        if !self.annotations.is_empty() {
+            details.push(("Annotations", self.annotations.len().to_string()));
+        }
+        details.extend(
+            self.topics
+                .iter()
+                .enumerate()
+                .map(|(i, topic)| (if i == 0 { "Topics" } else { "" }, topic.clone())),
+        );
+        details
+    }
 }
 
 /// Replication configuration for a secret.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum ReplicationConfig {
     /// Automatic replication managed by GCP.
+    #[default]
     Automatic,
     /// User-managed replication with specific locations.
     UserManaged { locations: Vec<String> },
@@ -146,6 +356,17 @@ impl ReplicationConfig {
     }
 }
 
+/// Everything the creation wizard collects for a new secret.
+#[derive(Debug, Clone, Default)]
+pub struct NewSecret {
+    pub name: String,
+    /// Added as the first version when set.
+    pub payload: Option<String>,
+    pub replication: ReplicationConfig,
+    pub labels: HashMap<String, String>,
+    pub expire_time: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug, Clone)]
 pub struct IamPolicy {
     pub bindings: Vec<IamBinding>,
@@ -187,6 +408,20 @@ impl TableRow for IamBinding {
         let matcher = Matcher::new();
         matcher.matches(&self.role, query) || self.members.iter().any(|m| matcher.matches(m, query))
     }
+
+    fn details(&self) -> Vec<(&'static str, String)> {
+        self.members
+            .iter()
+            .enumerate()
+            .map(|(i, member)| (if i == 0 { "Members" } else { "" }, member.clone()))
+            .collect()
+    }
+}
+
+impl ExportRow for IamBinding {
+    fn export_values(&self) -> Vec<String> {
+        vec![self.role.clone(), self.members.join(", ")]
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -214,30 +449,47 @@ impl TableRow for LabelEntry {
     }
 }
 
+impl ExportRow for LabelEntry {
+    fn export_values(&self) -> Vec<String> {
+        vec![self.key.clone(), self.value.clone()]
+    }
+}
+
 // === Messages ===
 
 #[derive(Debug, Clone)]
 pub enum SecretsMsg {
     Load,
     Loaded(Vec<Secret>),
+    /// Fetch the list again and update the shown one in place.
+    Refresh,
+    Refreshed(Vec<Secret>),
 
     StartCreation,
-    Create {
-        name: String,
-        payload: Option<String>,
-    },
+    /// The location policy to offer replica locations by, `None` if it could
+    /// not be read.
+    LocationPolicyLoaded(Option<LocationPolicy>),
+    Create(NewSecret),
     Created(Secret),
 
     ConfirmDelete(Secret),
     Delete(Secret),
-    Deleted(String),
+    Deleted(Secret),
 
     ViewLabels(Secret),
+    /// Open the label editor; `key: None` adds a new label.
+    EditLabel {
+        secret: Secret,
+        key: Option<String>,
+    },
     UpdateLabels {
         secret: Secret,
         labels: HashMap<String, String>,
     },
-    LabelsUpdated(Secret),
+    LabelsUpdated {
+        secret: Secret,
+        previous: HashMap<String, String>,
+    },
 
     ViewIamPolicy(Secret),
     IamPolicyLoaded {
@@ -245,6 +497,17 @@ pub enum SecretsMsg {
         policy: IamPolicy,
     },
 
+    /// Ask where to write the rows of a labels or IAM policy view, starting
+    /// from `file_name`.
+    StartExport {
+        file_name: String,
+        export: TableExport,
+    },
+    Export {
+        export: TableExport,
+        path: PathBuf,
+    },
+
     ViewReplicationInfo(Secret),
     ReplicationInfoLoaded {
         secret: Secret,
@@ -259,6 +522,9 @@ pub enum SecretsMsg {
         data: String,
         secret_name: String,
     },
+
+    /// Open the secret's page in the Cloud console.
+    OpenConsole(Secret),
 }
 
 impl From<SecretsMsg> for SecretManagerMsg {
@@ -283,27 +549,92 @@ pub struct SecretListScreen {
 impl SecretListScreen {
     pub fn new(secrets: Vec<Secret>, resolver: Arc<KeyResolver>) -> Self {
         Self {
-            table: Table::new(secrets, resolver.clone()).with_title(" Secrets "),
+            table: Table::new(secrets, resolver.clone())
+                .with_title(" Secrets ")
+                .with_hidden_column(ANNOTATIONS_COLUMN)
+                .with_hidden_column(PROJECT_COLUMN),
             resolver,
         }
     }
+
+    /// Show the project of each secret, for a list joined from several.
+    #[must_use]
+    pub fn with_project_column(mut self) -> Self {
+        self.table.toggle_column(PROJECT_COLUMN);
+        self
+    }
+
+    /// Show refreshed secrets, keeping the selected secret and scroll position.
+    ///
+    /// Extras already loaded are kept for secrets that did not change.
+    fn set_secrets(&mut self, mut secrets: Vec<Secret>) {
+        let selected = self.table.selected_item().map(Secret::resource_name);
+        let extras: HashMap<_, _> = self
+            .table
+            .items()
+            .iter()
+            .filter_map(|secret| {
+                let extras = secret.extras.clone()?;
+                Some(((secret.resource_name(), secret.etag.clone()), extras))
+            })
+            .collect();
+        for secret in &mut secrets {
+            secret.extras = extras
+                .get(&(secret.resource_name(), secret.etag.clone()))
+                .cloned();
+        }
+        self.table.set_items(secrets);
+        if let Some(name) = selected
+            && self
+                .table
+                .filtered_items()
+                .any(|secret| secret.resource_name() == name)
+        {
+            self.table
+                .select_where(|secret| secret.resource_name() == name);
+        }
+    }
+
+    /// Secrets in view whose extras are not loaded yet.
+    pub(super) fn visible_without_extras(&mut self) -> Vec<Secret> {
+        self.table
+            .visible_items()
+            .filter(|secret| secret.extras.is_none())
+            .cloned()
+            .collect()
+    }
+
+    /// Replace a row with the same secret, loaded with its extras.
+    pub(super) fn set_enriched(&mut self, secret: Secret) {
+        let name = secret.resource_name();
+        self.table
+            .update_where(|row| row.resource_name() == name, |row| *row = secret);
+    }
 }
 
 impl Screen for SecretListScreen {
     type Output = SecretManagerMsg;
 
+    #[allow(clippy::too_many_lines)]
     fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
         let result = self.table.handle_key(key)?;
 
         if let EventResult::Event(TableEvent::Activated(secret)) = result {
             return Ok(SecretsMsg::ViewPayload(secret).into());
         }
+        // Other rows may have scrolled into view
+        if let EventResult::Event(TableEvent::Changed(_) | TableEvent::SearchChanged(_)) = result {
+            let secrets = self.visible_without_extras();
+            if !secrets.is_empty() {
+                return Ok(EnrichMsg::Load(secrets).into());
+            }
+        }
         if result.is_consumed() {
             return Ok(EventResult::Consumed);
         }
 
         if self.resolver.matches_secrets(&key, SecretsAction::Reload) {
-            return Ok(SecretsMsg::Load.into());
+            return Ok(SecretsMsg::Refresh.into());
         }
         if self.resolver.matches_secrets(&key, SecretsAction::New) {
             return Ok(SecretsMsg::StartCreation.into());
@@ -328,11 +659,51 @@ impl Screen for SecretListScreen {
         {
             return Ok(SecretsMsg::ViewLabels(secret.clone()).into());
         }
+        if self
+            .resolver
+            .matches_secrets(&key, SecretsAction::Annotations)
+            && let Some(secret) = self.table.selected_item()
+        {
+            return Ok(AnnotationsMsg::View(secret.clone()).into());
+        }
+        if self
+            .resolver
+            .matches_secrets(&key, SecretsAction::AnnotationsColumn)
+        {
+            self.table.toggle_column(ANNOTATIONS_COLUMN);
+            return Ok(EventResult::Consumed);
+        }
+        if self.resolver.matches_secrets(&key, SecretsAction::Topics)
+            && let Some(secret) = self.table.selected_item()
+        {
+            return Ok(TopicsMsg::View(secret.clone()).into());
+        }
         if self.resolver.matches_secrets(&key, SecretsAction::Iam)
             && let Some(secret) = self.table.selected_item()
         {
             return Ok(SecretsMsg::ViewIamPolicy(secret.clone()).into());
         }
+        if self
+            .resolver
+            .matches_secrets(&key, SecretsAction::Accessors)
+            && let Some(secret) = self.table.selected_item()
+        {
+            return Ok(AccessorsMsg::Load(secret.clone()).into());
+        }
+        if self
+            .resolver
+            .matches_secrets(&key, SecretsAction::TestAccess)
+            && let Some(secret) = self.table.selected_item()
+        {
+            return Ok(AccessTestMsg::Run(secret.clone()).into());
+        }
+        if self
+            .resolver
+            .matches_secrets(&key, SecretsAction::FindInContexts)
+            && let Some(secret) = self.table.selected_item()
+        {
+            return Ok(FanOutMsg::Start(secret.clone()).into());
+        }
         if self
             .resolver
             .matches_secrets(&key, SecretsAction::Replication)
@@ -340,10 +711,86 @@ impl Screen for SecretListScreen {
         {
             return Ok(SecretsMsg::ViewReplicationInfo(secret.clone()).into());
         }
+        if self
+            .resolver
+            .matches_secrets(&key, SecretsAction::DeepSearch)
+        {
+            let secrets = self.table.filtered_items().cloned().collect();
+            return Ok(DeepSearchMsg::Start(secrets).into());
+        }
+        if self
+            .resolver
+            .matches_secrets(&key, SecretsAction::Duplicates)
+        {
+            let secrets = self.table.filtered_items().cloned().collect();
+            return Ok(DuplicatesMsg::Start(secrets).into());
+        }
+        if self.resolver.matches_secrets(&key, SecretsAction::Prefetch) {
+            let secrets = self.table.filtered_items().cloned().collect();
+            return Ok(PayloadMsg::Prefetch(secrets).into());
+        }
+        if self
+            .resolver
+            .matches_secrets(&key, SecretsAction::CopyToContext)
+            && let Some(secret) = self.table.selected_item()
+        {
+            return Ok(PromoteMsg::Start(secret.clone()).into());
+        }
+        if self
+            .resolver
+            .matches_secrets(&key, SecretsAction::SyncToCluster)
+            && let Some(secret) = self.table.selected_item()
+        {
+            return Ok(KubeSyncMsg::Start(secret.clone()).into());
+        }
+        if self
+            .resolver
+            .matches_secrets(&key, SecretsAction::RegistryCredentials)
+            && let Some(secret) = self.table.selected_item()
+        {
+            return Ok(RegistryMsg::Start(secret.clone()).into());
+        }
+        if self.resolver.matches_secrets(&key, SecretsAction::Lint) {
+            return Ok(LintMsg::Run.into());
+        }
+        if self
+            .resolver
+            .matches_secrets(&key, SecretsAction::StaleReport)
+        {
+            let secrets = self.table.filtered_items().cloned().collect();
+            return Ok(StaleMsg::Run(secrets).into());
+        }
+        if self.resolver.matches_secrets(&key, SecretsAction::Trash) {
+            return Ok(TrashMsg::View.into());
+        }
+        if self
+            .resolver
+            .matches_secrets(&key, SecretsAction::AccessStats)
+        {
+            return Ok(AccessStatsMsg::Load.into());
+        }
+        if self
+            .resolver
+            .matches_secrets(&key, SecretsAction::Terraform)
+            && let Some(secret) = self.table.selected_item()
+        {
+            return Ok(TerraformMsg::CopySecret(secret.clone()).into());
+        }
+        if self
+            .resolver
+            .matches_secrets(&key, SecretsAction::OpenConsole)
+            && let Some(secret) = self.table.selected_item()
+        {
+            return Ok(SecretsMsg::OpenConsole(secret.clone()).into());
+        }
 
         Ok(EventResult::Ignored)
     }
 
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
     fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
         self.table.render(frame, area, theme);
     }
@@ -369,17 +816,93 @@ impl Screen for SecretListScreen {
                 self.resolver.display_secrets(SecretsAction::Labels),
                 "Labels",
             ),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::Annotations),
+                "Annotations",
+            ),
+            Keybinding::new(
+                self.resolver
+                    .display_secrets(SecretsAction::AnnotationsColumn),
+                "Annotation count",
+            ),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::Topics),
+                "Topics",
+            ),
             Keybinding::new(self.resolver.display_secrets(SecretsAction::Iam), "IAM"),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::Accessors),
+                "Who can access",
+            ),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::TestAccess),
+                "Test access",
+            ),
             Keybinding::new(
                 self.resolver.display_secrets(SecretsAction::Replication),
                 "Replication",
             ),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::DeepSearch),
+                "Search contents",
+            ),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::Duplicates),
+                "Find duplicates",
+            ),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::Prefetch),
+                "Prefetch payloads",
+            ),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::CopyToContext),
+                "Copy to context",
+            ),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::FindInContexts),
+                "Find in contexts",
+            ),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::SyncToCluster),
+                "Sync to cluster",
+            ),
+            Keybinding::new(
+                self.resolver
+                    .display_secrets(SecretsAction::RegistryCredentials),
+                "Registry credentials",
+            ),
+            Keybinding::new(self.resolver.display_secrets(SecretsAction::Lint), "Lint"),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::StaleReport),
+                "Stale secrets",
+            ),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::AccessStats),
+                "Access stats",
+            ),
+            Keybinding::new(self.resolver.display_secrets(SecretsAction::Trash), "Trash"),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::Terraform),
+                "Copy Terraform",
+            ),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::OpenConsole),
+                "Open in console",
+            ),
             Keybinding::new(
                 self.resolver.display_secrets(SecretsAction::Reload),
                 "Reload",
             ),
         ]
     }
+
+    fn action_context(&self) -> ActionContext {
+        let ctx = ActionContext::screen("secrets");
+        match self.table.selected_item() {
+            Some(secret) => ctx.with_var("name", &secret.name),
+            None => ctx,
+        }
+    }
 }
 
 pub struct LabelsScreen {
@@ -423,10 +946,39 @@ impl Screen for LabelsScreen {
         if self.resolver.matches_secrets(&key, SecretsAction::Reload) {
             return Ok(SecretsMsg::ViewLabels(self.secret.clone()).into());
         }
+        if self.resolver.matches_secrets(&key, SecretsAction::AddLabel) {
+            return Ok(SecretsMsg::EditLabel {
+                secret: self.secret.clone(),
+                key: None,
+            }
+            .into());
+        }
+        if self
+            .resolver
+            .matches_secrets(&key, SecretsAction::EditLabel)
+            && let Some(label) = self.table.selected_item()
+        {
+            return Ok(SecretsMsg::EditLabel {
+                secret: self.secret.clone(),
+                key: Some(label.key.clone()),
+            }
+            .into());
+        }
+        if self.resolver.matches_secrets(&key, SecretsAction::Export) {
+            return Ok(SecretsMsg::StartExport {
+                file_name: format!("{}-labels.json", self.secret.name),
+                export: TableExport::of(&self.table),
+            }
+            .into());
+        }
 
         Ok(EventResult::Ignored)
     }
 
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
     fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
         self.table.render(frame, area, theme);
     }
@@ -434,6 +986,18 @@ impl Screen for LabelsScreen {
     fn keybindings(&self) -> Vec<Keybinding> {
         vec![
             Keybinding::hint(self.resolver.display_search(SearchAction::Toggle), "Search"),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::AddLabel),
+                "Add",
+            ),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::EditLabel),
+                "Edit",
+            ),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::Export),
+                "Export",
+            ),
             Keybinding::new(
                 self.resolver.display_secrets(SecretsAction::Reload),
                 "Reload",
@@ -471,10 +1035,21 @@ impl Screen for IamPolicyScreen {
         if self.resolver.matches_secrets(&key, SecretsAction::Reload) {
             return Ok(SecretsMsg::ViewIamPolicy(self.secret.clone()).into());
         }
+        if self.resolver.matches_secrets(&key, SecretsAction::Export) {
+            return Ok(SecretsMsg::StartExport {
+                file_name: format!("{}-iam.json", self.secret.name),
+                export: TableExport::of(&self.table),
+            }
+            .into());
+        }
 
         Ok(EventResult::Ignored)
     }
 
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
     fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
         self.table.render(frame, area, theme);
     }
@@ -482,6 +1057,10 @@ impl Screen for IamPolicyScreen {
     fn keybindings(&self) -> Vec<Keybinding> {
         vec![
             Keybinding::hint(self.resolver.display_search(SearchAction::Toggle), "Search"),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::Export),
+                "Export",
+            ),
             Keybinding::new(
                 self.resolver.display_secrets(SecretsAction::Reload),
                 "Reload",
@@ -581,7 +1160,7 @@ impl Screen for ReplicationScreen {
                     .add_modifier(Modifier::BOLD),
             )
             .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
+            .border_type(theme.border_type)
             .border_style(Style::default().fg(theme.surface1()))
             .style(Style::default().bg(theme.base()));
 
@@ -600,74 +1179,434 @@ impl Screen for ReplicationScreen {
 
 // === Wizards & Dialogs ===
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum CreateSecretWizardStep {
     Name,
     Payload,
+    Replication,
+    Labels,
+    Expiration,
+    Review,
+}
+
+#[derive(Debug, Clone)]
+struct LocationItem {
+    location: &'static str,
+    checked: bool,
+    /// Why the organization policy does not allow the location.
+    denial: Option<String>,
+}
+
+impl ListRow for LocationItem {
+    fn render_row(&self, theme: &Theme) -> ListItem<'static> {
+        if let Some(denial) = &self.denial {
+            let style = Style::default().fg(theme.overlay0());
+            return ListItem::new(Line::from(vec![
+                Span::styled("[-] ", style),
+                Span::styled(self.location, style),
+                Span::styled(format!("  {denial}"), style.add_modifier(Modifier::ITALIC)),
+            ]));
+        }
+        let (mark, style) = if self.checked {
+            ("[x] ", Style::default().fg(theme.green()))
+        } else {
+            ("[ ] ", Style::default().fg(theme.text()))
+        };
+        ListItem::new(Line::from(vec![
+            Span::styled(mark, style),
+            Span::styled(self.location, style),
+        ]))
+    }
 }
 
 pub struct CreateSecretWizard {
     step: CreateSecretWizardStep,
     name_input: TextInput,
-    payload_input: TextInput,
+    payload_input: TextArea,
+    locations: List<LocationItem>,
+    /// Whether locations were checked against the organization policy.
+    policy_known: bool,
+    labels_input: TextInput,
+    expiration_input: DateTimeInput,
+    secret: NewSecret,
+    /// Why the last submitted value was rejected.
+    error: Option<String>,
+    resolver: Arc<KeyResolver>,
 }
 
 impl CreateSecretWizard {
-    pub fn new() -> Self {
+    pub fn new(resolver: Arc<KeyResolver>) -> Self {
+        let locations = REPLICA_LOCATIONS
+            .iter()
+            .map(|location| LocationItem {
+                location,
+                checked: false,
+                denial: None,
+            })
+            .collect();
         Self {
             step: CreateSecretWizardStep::Name,
             name_input: TextInput::new("Secret Name").with_placeholder("my-secret"),
-            payload_input: TextInput::new("Initial Payload (optional)"),
+            payload_input: TextArea::new("Initial Payload (optional)").with_generator(),
+            locations: List::new(locations, resolver.clone()),
+            policy_known: true,
+            labels_input: TextInput::new("Labels (optional)")
+                .with_placeholder("team=platform, env=prod")
+                .with_validator(|labels| parse_labels(labels).map(|_| ())),
+            expiration_input: DateTimeInput::new("Expires (optional)").future_only(),
+            secret: NewSecret::default(),
+            error: None,
+            resolver,
         }
     }
-}
 
-impl Modal for CreateSecretWizard {
-    type Output = SecretManagerMsg;
+    /// Disable the locations `policy` does not allow; `None` notes that the
+    /// policy is unknown and keeps every location.
+    pub fn with_location_policy(mut self, policy: Option<&LocationPolicy>) -> Self {
+        self.policy_known = policy.is_some();
+        if let Some(policy) = policy {
+            let items = self
+                .locations
+                .items()
+                .iter()
+                .cloned()
+                .map(|mut item| {
+                    item.denial = policy.denial(item.location);
+                    item
+                })
+                .collect();
+            self.locations.set_items(items);
+        }
+        self
+    }
 
-    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
-        Ok(match self.step {
-            CreateSecretWizardStep::Name => match self.name_input.handle_key(key)? {
-                EventResult::Event(TextInputEvent::Submitted(name)) if !name.is_empty() => {
-                    self.step = CreateSecretWizardStep::Payload;
-                    EventResult::Consumed
-                }
-                EventResult::Event(TextInputEvent::Cancelled) => {
-                    SecretManagerMsg::DialogCancelled.into()
-                }
-                _ => EventResult::Consumed,
-            },
-            CreateSecretWizardStep::Payload => match self.payload_input.handle_key(key)? {
-                EventResult::Event(TextInputEvent::Submitted(payload)) => {
-                    let name = self.name_input.value().to_string();
-                    let payload = if payload.is_empty() {
-                        None
-                    } else {
-                        Some(payload)
-                    };
-                    SecretsMsg::Create { name, payload }.into()
-                }
-                EventResult::Event(TextInputEvent::Cancelled) => {
-                    SecretManagerMsg::DialogCancelled.into()
+    fn toggle_location(&mut self) {
+        let Some(selected) = self
+            .locations
+            .selected()
+            .filter(|item| item.denial.is_none())
+            .map(|item| item.location)
+        else {
+            return;
+        };
+        let items = self
+            .locations
+            .items()
+            .iter()
+            .cloned()
+            .map(|mut item| {
+                if item.location == selected {
+                    item.checked = !item.checked;
                 }
-                _ => EventResult::Consumed,
-            },
-        })
+                item
+            })
+            .collect();
+        self.locations.set_items(items);
     }
 
-    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
-        match self.step {
-            CreateSecretWizardStep::Name => self.name_input.render(frame, area, theme),
-            CreateSecretWizardStep::Payload => self.payload_input.render(frame, area, theme),
-        }
+    fn checked_locations(&self) -> Vec<String> {
+        self.locations
+            .items()
+            .iter()
+            .filter(|item| item.checked)
+            .map(|item| item.location.to_string())
+            .collect()
     }
-}
 
-pub struct DeleteSecretDialog {
-    secret: Secret,
+    /// Handle a text step: `apply` stores the submitted value or rejects it.
+    fn handle_text_step(
+        &mut self,
+        key: KeyEvent,
+        next: CreateSecretWizardStep,
+        apply: impl FnOnce(&mut NewSecret, &str) -> std::result::Result<(), String>,
+    ) -> Result<EventResult<SecretManagerMsg>> {
+        let input = match self.step {
+            CreateSecretWizardStep::Name => &mut self.name_input,
+            _ => &mut self.labels_input,
+        };
+        Ok(match input.handle_key(key)? {
+            EventResult::Event(TextInputEvent::Submitted(value)) => {
+                match apply(&mut self.secret, value.trim()) {
+                    Ok(()) => {
+                        self.error = None;
+                        self.step = next;
+                    }
+                    Err(err) => self.error = Some(err),
+                }
+                EventResult::Consumed
+            }
+            EventResult::Event(TextInputEvent::Cancelled) => {
+                SecretManagerMsg::DialogCancelled.into()
+            }
+            _ => EventResult::Consumed,
+        })
+    }
+
+    fn render_locations(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = area.centered(Constraint::Percentage(50), Constraint::Percentage(60));
+        frame.render_widget(Clear, popup_area);
+
+        let block = popup_block(" Replication (Space to toggle, Enter to continue) ", theme);
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [hint_area, policy_area, list_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Min(1),
+        ])
+        .areas(inner);
+        let allowed = self
+            .locations
+            .items()
+            .iter()
+            .filter(|item| item.denial.is_none())
+            .count();
+        let restricted = allowed < self.locations.items().len();
+        let hint = match self.checked_locations().len() {
+            0 if restricted => {
+                "Automatic: replicated across all regions, the policy may reject it".to_string()
+            }
+            0 => "Automatic: replicated across all regions".to_string(),
+            n => format!("User-managed: {n} location(s)"),
+        };
+        frame.render_widget(
+            Paragraph::new(hint).style(Style::default().fg(theme.subtext0())),
+            hint_area,
+        );
+        let policy = if !self.policy_known {
+            "Organization policy unknown, all locations shown".to_string()
+        } else if restricted {
+            format!(
+                "Organization policy allows {allowed} of {} locations",
+                self.locations.items().len()
+            )
+        } else {
+            String::new()
+        };
+        frame.render_widget(
+            Paragraph::new(policy).style(Style::default().fg(theme.overlay0())),
+            policy_area,
+        );
+        self.locations.render(frame, list_area, theme);
+    }
+
+    fn render_review(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let label_style = Style::default()
+            .fg(theme.subtext0())
+            .add_modifier(Modifier::BOLD);
+        let value_style = Style::default().fg(theme.text());
+        let key_style = Style::default()
+            .fg(theme.peach())
+            .add_modifier(Modifier::BOLD);
+
+        let secret = &self.secret;
+        let payload = secret
+            .payload
+            .as_ref()
+            .map_or_else(|| "none".to_string(), |p| format!("{} bytes", p.len()));
+        let replication = match &secret.replication {
+            ReplicationConfig::Automatic => "Automatic".to_string(),
+            ReplicationConfig::UserManaged { locations } => locations.join(", "),
+        };
+        let mut labels: Vec<String> = secret
+            .labels
+            .iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect();
+        labels.sort();
+        let labels = if labels.is_empty() {
+            "none".to_string()
+        } else {
+            labels.join(", ")
+        };
+        let expires = secret.expire_time.map_or_else(
+            || "never".to_string(),
+            |at| format!("{} {}", format_timestamp(at), timezone_label()),
+        );
+
+        let row = |label: &'static str, value: String| {
+            Line::from(vec![
+                Span::styled(format!("{label:<13}"), label_style),
+                Span::styled(value, value_style),
+            ])
+        };
+        let lines = vec![
+            Line::from(""),
+            row("Name", secret.name.clone()),
+            row("Payload", payload),
+            row("Replication", replication),
+            row("Labels", labels),
+            row("Expires", expires),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled(
+                    format!("[{}]", self.resolver.display_dialog(DialogAction::Confirm)),
+                    key_style,
+                ),
+                Span::raw(" Create    "),
+                Span::styled(
+                    format!("[{}]", self.resolver.display_dialog(DialogAction::Cancel)),
+                    key_style,
+                ),
+                Span::raw(" Cancel"),
+            ]),
+        ];
+
+        let popup_area = area.centered(Constraint::Percentage(50), Constraint::Length(10));
+        frame.render_widget(Clear, popup_area);
+        let paragraph = Paragraph::new(lines).block(popup_block(" Create Secret ", theme));
+        frame.render_widget(paragraph, popup_area);
+    }
+
+    fn render_error(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let Some(error) = &self.error else {
+            return;
+        };
+        // Just below the text input popup
+        let input_area = area.centered(Constraint::Percentage(50), Constraint::Length(5));
+        let error_area = Rect {
+            y: input_area.bottom(),
+            height: 1,
+            ..input_area
+        }
+        .intersection(area);
+        frame.render_widget(Clear, error_area);
+        frame.render_widget(
+            Paragraph::new(error.as_str()).style(Style::default().fg(theme.red()).bg(theme.base())),
+            error_area,
+        );
+    }
+}
+
+impl Modal for CreateSecretWizard {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        match self.step {
+            CreateSecretWizardStep::Name => {
+                self.handle_text_step(key, CreateSecretWizardStep::Payload, |secret, name| {
+                    if name.is_empty() {
+                        return Err("Name is required".to_string());
+                    }
+                    secret.name = name.to_string();
+                    Ok(())
+                })
+            }
+            CreateSecretWizardStep::Payload => Ok(match self.payload_input.handle_key(key)? {
+                EventResult::Event(TextAreaEvent::Submitted(payload)) => {
+                    self.secret.payload = (!payload.is_empty()).then_some(payload);
+                    self.step = CreateSecretWizardStep::Replication;
+                    EventResult::Consumed
+                }
+                EventResult::Event(TextAreaEvent::Cancelled) => {
+                    SecretManagerMsg::DialogCancelled.into()
+                }
+                _ => EventResult::Consumed,
+            }),
+            CreateSecretWizardStep::Replication => {
+                if key.code == KeyCode::Char(' ') {
+                    self.toggle_location();
+                    return Ok(EventResult::Consumed);
+                }
+                if self.resolver.matches_global(&key, GlobalAction::Back) {
+                    return Ok(SecretManagerMsg::DialogCancelled.into());
+                }
+                if let EventResult::Event(ListEvent::Activated(_)) =
+                    self.locations.handle_key(key)?
+                {
+                    let locations = self.checked_locations();
+                    self.secret.replication = if locations.is_empty() {
+                        ReplicationConfig::Automatic
+                    } else {
+                        ReplicationConfig::UserManaged { locations }
+                    };
+                    self.step = CreateSecretWizardStep::Labels;
+                }
+                Ok(EventResult::Consumed)
+            }
+            CreateSecretWizardStep::Labels => {
+                self.handle_text_step(key, CreateSecretWizardStep::Expiration, |secret, labels| {
+                    secret.labels = parse_labels(labels)?;
+                    Ok(())
+                })
+            }
+            CreateSecretWizardStep::Expiration => {
+                Ok(match self.expiration_input.handle_key(key)? {
+                    EventResult::Event(DateTimeInputEvent::Submitted(expires)) => {
+                        self.secret.expire_time = expires;
+                        self.step = CreateSecretWizardStep::Review;
+                        EventResult::Consumed
+                    }
+                    EventResult::Event(DateTimeInputEvent::Cancelled) => {
+                        SecretManagerMsg::DialogCancelled.into()
+                    }
+                    _ => EventResult::Consumed,
+                })
+            }
+            CreateSecretWizardStep::Review => {
+                if self.resolver.matches_dialog(&key, DialogAction::Confirm) {
+                    return Ok(SecretsMsg::Create(self.secret.clone()).into());
+                }
+                if self.resolver.matches_dialog(&key, DialogAction::Cancel) {
+                    return Ok(SecretManagerMsg::DialogCancelled.into());
+                }
+                Ok(EventResult::Consumed)
+            }
+        }
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        let result = match self.step {
+            CreateSecretWizardStep::Name => self.name_input.handle_paste(text)?.consumed_only(),
+            CreateSecretWizardStep::Payload => {
+                self.payload_input.handle_paste(text)?.consumed_only()
+            }
+            CreateSecretWizardStep::Labels => self.labels_input.handle_paste(text)?.consumed_only(),
+            CreateSecretWizardStep::Expiration => {
+                self.expiration_input.handle_paste(text)?.consumed_only()
+            }
+            CreateSecretWizardStep::Replication | CreateSecretWizardStep::Review => {
+                EventResult::Consumed
+            }
+        };
+        Ok(result)
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        match self.step {
+            CreateSecretWizardStep::Name => self.name_input.render(frame, area, theme),
+            CreateSecretWizardStep::Payload => self.payload_input.render(frame, area, theme),
+            CreateSecretWizardStep::Replication => self.render_locations(frame, area, theme),
+            CreateSecretWizardStep::Labels => self.labels_input.render(frame, area, theme),
+            CreateSecretWizardStep::Expiration => {
+                self.expiration_input.render(frame, area, theme);
+            }
+            CreateSecretWizardStep::Review => self.render_review(frame, area, theme),
+        }
+        self.render_error(frame, area, theme);
+    }
+}
+
+pub struct DeleteSecretDialog {
+    secret: Secret,
     dialog: ConfirmDialog,
 }
 
 impl DeleteSecretDialog {
+    /// Confirmation for moving the secret to the trash instead of deleting it.
+    pub fn trash(secret: Secret, resolver: Arc<KeyResolver>) -> Self {
+        let dialog = ConfirmDialog::new(
+            format!("Move the secret \"{}\" to the trash?", secret.name),
+            resolver,
+        )
+        .with_title("Delete Secret")
+        .with_confirm_text("Move to trash")
+        .with_cancel_text("Cancel");
+
+        Self { secret, dialog }
+    }
+
     pub fn new(secret: Secret, resolver: Arc<KeyResolver>) -> Self {
         let dialog = ConfirmDialog::new(
             format!(
@@ -703,8 +1642,132 @@ impl Modal for DeleteSecretDialog {
     }
 }
 
+/// Adds or edits a single `key=value` label, validated while typing.
+pub struct LabelDialog {
+    secret: Secret,
+    /// The other labels of the secret, kept when the dialog is submitted.
+    others: HashMap<String, String>,
+    input: TextInput,
+}
+
+impl LabelDialog {
+    pub fn new(secret: Secret, key: Option<&str>) -> Self {
+        let mut others = secret.labels.clone();
+        let input = match key.and_then(|key| others.remove_entry(key)) {
+            Some((key, value)) => TextInput::new("Edit Label").with_value(format!("{key}={value}")),
+            None => TextInput::new("Add Label").with_placeholder("key=value"),
+        };
+        let existing = others.clone();
+        let input = input.with_validator(move |label| {
+            let (key, _) = parse_label(label)?;
+            if existing.contains_key(&key) {
+                return Err(format!("Label '{key}' already exists"));
+            }
+            if existing.len() >= MAX_LABELS {
+                return Err(format!("A secret can have at most {MAX_LABELS} labels"));
+            }
+            Ok(())
+        });
+        Self {
+            secret,
+            others,
+            input,
+        }
+    }
+}
+
+impl Modal for LabelDialog {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        Ok(match self.input.handle_key(key)? {
+            EventResult::Event(TextInputEvent::Submitted(label)) => match parse_label(&label) {
+                Ok((key, value)) => {
+                    let mut labels = self.others.clone();
+                    labels.insert(key, value);
+                    SecretsMsg::UpdateLabels {
+                        secret: self.secret.clone(),
+                        labels,
+                    }
+                    .into()
+                }
+                Err(_) => EventResult::Consumed,
+            },
+            EventResult::Event(TextInputEvent::Cancelled) => {
+                SecretManagerMsg::DialogCancelled.into()
+            }
+            _ => EventResult::Consumed,
+        })
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.input.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.input.render(frame, area, theme);
+    }
+}
+
+/// Asks for the file to export a view to, JSON unless it ends in `.csv`.
+pub struct ExportDialog {
+    export: TableExport,
+    input: TextInput,
+}
+
+impl ExportDialog {
+    pub fn new(file_name: String, export: TableExport) -> Self {
+        Self {
+            export,
+            input: TextInput::new("Export To").with_value(file_name),
+        }
+    }
+}
+
+impl Modal for ExportDialog {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        Ok(match self.input.handle_key(key)? {
+            EventResult::Event(TextInputEvent::Submitted(path)) if !path.trim().is_empty() => {
+                SecretsMsg::Export {
+                    export: self.export.clone(),
+                    path: expand_home(path.trim()),
+                }
+                .into()
+            }
+            EventResult::Event(TextInputEvent::Cancelled) => {
+                SecretManagerMsg::DialogCancelled.into()
+            }
+            _ => EventResult::Consumed,
+        })
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.input.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.input.render(frame, area, theme);
+    }
+}
+
 // === Update Logic ===
 
+/// The secret list, telling the projects apart if the context has several.
+fn secret_list(
+    state: &SecretManager,
+    secrets: Vec<Secret>,
+    resolver: Arc<KeyResolver>,
+) -> SecretListScreen {
+    let screen = SecretListScreen::new(secrets, resolver);
+    if state.get_context().all_projects().len() > 1 {
+        screen.with_project_column()
+    } else {
+        screen
+    }
+}
+
 // Flat message dispatcher — splitting reduces readability
 #[allow(clippy::too_many_lines)]
 pub(super) fn update(state: &mut SecretManager, msg: SecretsMsg) -> Result<ServiceMsg> {
@@ -713,44 +1776,108 @@ pub(super) fn update(state: &mut SecretManager, msg: SecretsMsg) -> Result<Servi
     match msg {
         SecretsMsg::Load => {
             if let Some(secrets) = state.get_cached_secrets() {
-                state.push_view(SecretListScreen::new(secrets, resolver));
+                state.push_view(secret_list(state, secrets, resolver));
+                enrich::load_visible(state);
                 return Ok(ServiceMsg::Idle);
             }
 
             state.display_loading_spinner("Loading secrets...");
+            state.mark_secrets_fetched();
 
             Ok(FetchSecretsCmd {
                 client: state.get_client()?,
+                projects: state.get_projects(),
+                refresh: false,
                 tx: state.get_msg_sender(),
             }
             .into())
         }
 
-        SecretsMsg::Loaded(secrets) => {
+        SecretsMsg::Loaded(mut secrets) => {
             state.hide_loading_spinner();
+            secrets.retain(|secret| !state.trash().contains(secret));
             state.cache_secrets(&secrets);
-            state.push_view(SecretListScreen::new(secrets, resolver));
+            let mut screen = secret_list(state, secrets, resolver);
+            // A link from another service opens the payload of its secret
+            if let Some(name) = state.take_linked_secret() {
+                if !screen.table.select_where(|secret| secret.name == name) {
+                    state.push_view(screen);
+                    return Err(eyre!("The secret '{name}' was not found"));
+                }
+                if let Some(secret) = screen.table.selected_item() {
+                    state.queue(SecretsMsg::ViewPayload(secret.clone()).into());
+                }
+            }
+            state.reset_enriching();
+            state.push_view(screen);
+            enrich::load_visible(state);
             Ok(ServiceMsg::Idle)
         }
 
-        SecretsMsg::StartCreation => {
-            state.display_overlay(CreateSecretWizard::new());
+        SecretsMsg::Refresh => {
+            if state.secrets_fetched_within(MIN_REFRESH_INTERVAL) {
+                return Ok(ServiceMsg::Idle);
+            }
+            state.mark_secrets_fetched();
+
+            Ok(FetchSecretsCmd {
+                client: state.get_client()?,
+                projects: state.get_projects(),
+                refresh: true,
+                tx: state.get_msg_sender(),
+            }
+            .into())
+        }
+
+        SecretsMsg::Refreshed(mut secrets) => {
+            secrets.retain(|secret| !state.trash().contains(secret));
+            // Equal etags and fields: keep the table as it is to avoid flicker
+            if state.get_cached_secrets().as_ref() == Some(&secrets) {
+                return Ok(ServiceMsg::Idle);
+            }
+            state.cache_secrets(&secrets);
+            if let Some(screen) = state.current_view_mut::<SecretListScreen>() {
+                screen.set_secrets(secrets);
+            }
+            enrich::load_visible(state);
             Ok(ServiceMsg::Idle)
         }
 
-        SecretsMsg::Create { name, payload } => {
-            state.display_loading_spinner("Creating secret...");
-            state.close_overlay();
+        SecretsMsg::StartCreation => {
+            if let Some(policy) = state.get_cached_location_policy() {
+                state.display_overlay(
+                    CreateSecretWizard::new(resolver).with_location_policy(Some(&policy)),
+                );
+                return Ok(ServiceMsg::Idle);
+            }
+            state.display_loading_spinner("Checking allowed locations...");
 
-            Ok(CreateSecretCmd {
-                name,
-                payload,
+            Ok(FetchLocationPolicyCmd {
                 client: state.get_client()?,
                 tx: state.get_msg_sender(),
             }
             .into())
         }
 
+        SecretsMsg::LocationPolicyLoaded(policy) => {
+            state.hide_loading_spinner();
+            state.display_overlay(
+                CreateSecretWizard::new(resolver).with_location_policy(policy.as_ref()),
+            );
+            // A policy that could not be read is asked for again next time
+            if let Some(policy) = policy {
+                state.cache_location_policy(policy);
+            }
+            Ok(ServiceMsg::Idle)
+        }
+
+        SecretsMsg::Create(secret) => {
+            state.display_loading_spinner("Creating secret...");
+            state.close_overlay();
+
+            Ok(create_secret_workflow(secret, state.get_client()?, state.get_msg_sender()).into())
+        }
+
         SecretsMsg::Created(_secret) => {
             state.invalidate_secrets_cache();
             state.queue(SecretsMsg::Load.into());
@@ -758,7 +1885,16 @@ pub(super) fn update(state: &mut SecretManager, msg: SecretsMsg) -> Result<Servi
         }
 
         SecretsMsg::ConfirmDelete(secret) => {
-            state.display_overlay(DeleteSecretDialog::new(secret, resolver));
+            if state.get_config().trash.enabled {
+                state.display_overlay(DeleteSecretDialog::trash(secret, resolver));
+            } else {
+                state.display_overlay(DeleteSecretDialog::new(secret, resolver));
+            }
+            Ok(ServiceMsg::Idle)
+        }
+
+        SecretsMsg::Delete(secret) if state.get_config().trash.enabled => {
+            state.queue(TrashMsg::Stage(secret).into());
             Ok(ServiceMsg::Idle)
         }
 
@@ -767,14 +1903,19 @@ pub(super) fn update(state: &mut SecretManager, msg: SecretsMsg) -> Result<Servi
             state.close_overlay();
 
             Ok(DeleteSecretCmd {
+                client: state.get_client_for(&secret)?,
                 secret,
-                client: state.get_client()?,
                 tx: state.get_msg_sender(),
             }
             .into())
         }
 
-        SecretsMsg::Deleted(_name) => {
+        SecretsMsg::Deleted(secret) => {
+            // Older entries would act on the recreated secret, if any
+            state.forget_undo(&secret);
+            if let Some(payload) = state.get_cached_payload(&secret, None) {
+                state.record_undo(UndoEntry::Delete { secret, payload });
+            }
             state.invalidate_secrets_cache();
             state.pop_to_root();
             state.queue(SecretsMsg::Load.into());
@@ -802,20 +1943,30 @@ pub(super) fn update(state: &mut SecretManager, msg: SecretsMsg) -> Result<Servi
             Ok(ServiceMsg::Idle)
         }
 
+        SecretsMsg::EditLabel { secret, key } => {
+            state.display_overlay(LabelDialog::new(secret, key.as_deref()));
+            Ok(ServiceMsg::Idle)
+        }
+
         SecretsMsg::UpdateLabels { secret, labels } => {
+            state.close_overlay();
             state.display_loading_spinner("Updating labels...");
 
             Ok(UpdateLabelsCmd {
+                client: state.get_client_for(&secret)?,
                 secret,
                 labels,
-                client: state.get_client()?,
                 tx: state.get_msg_sender(),
             }
             .into())
         }
 
-        SecretsMsg::LabelsUpdated(secret) => {
+        SecretsMsg::LabelsUpdated { secret, previous } => {
             state.hide_loading_spinner();
+            state.record_undo(UndoEntry::Labels {
+                secret: secret.clone(),
+                previous,
+            });
             state.invalidate_secrets_cache();
             state.pop_view();
             state.push_view(LabelsScreen::new(secret, resolver));
@@ -826,8 +1977,8 @@ pub(super) fn update(state: &mut SecretManager, msg: SecretsMsg) -> Result<Servi
             state.display_loading_spinner("Loading IAM policy...");
 
             Ok(FetchIamPolicyCmd {
+                client: state.get_client_for(&secret)?,
                 secret,
-                client: state.get_client()?,
                 tx: state.get_msg_sender(),
             }
             .into())
@@ -839,12 +1990,27 @@ pub(super) fn update(state: &mut SecretManager, msg: SecretsMsg) -> Result<Servi
             Ok(ServiceMsg::Idle)
         }
 
+        SecretsMsg::StartExport { file_name, export } => {
+            state.display_overlay(ExportDialog::new(file_name, export));
+            Ok(ServiceMsg::Idle)
+        }
+
+        SecretsMsg::Export { export, path } => {
+            state.close_overlay();
+            Ok(ExportTableCmd::new(export, path).into())
+        }
+
+        SecretsMsg::OpenConsole(secret) => {
+            let url = secret_url(&secret.project, &secret.name);
+            Ok(OpenUrlCmd::new(url).into())
+        }
+
         SecretsMsg::ViewReplicationInfo(secret) => {
             state.display_loading_spinner("Loading replication info...");
 
             Ok(FetchSecretMetadataCmd {
+                client: state.get_client_for(&secret)?,
                 secret,
-                client: state.get_client()?,
                 tx: state.get_msg_sender(),
             }
             .into())
@@ -860,8 +2026,8 @@ pub(super) fn update(state: &mut SecretManager, msg: SecretsMsg) -> Result<Servi
         }
 
         SecretsMsg::CopyPayload(secret) => Ok(LoadPayloadCmd {
+            client: state.get_client_for(&secret)?,
             secret,
-            client: state.get_client()?,
             tx: state.get_msg_sender(),
         }
         .into()),
@@ -875,9 +2041,104 @@ pub(super) fn update(state: &mut SecretManager, msg: SecretsMsg) -> Result<Servi
 
 // === Helper Functions ===
 
-fn format_labels(labels: &HashMap<String, String>, query: &str) -> String {
+/// Labels allowed on a single secret.
+const MAX_LABELS: usize = 64;
+/// Maximum characters in a label key or value.
+const MAX_LABEL_LENGTH: usize = 63;
+
+/// Regions offered for user-managed replication.
+const REPLICA_LOCATIONS: &[&str] = &[
+    "us-central1",
+    "us-east1",
+    "us-east4",
+    "us-west1",
+    "us-west2",
+    "northamerica-northeast1",
+    "southamerica-east1",
+    "europe-west1",
+    "europe-west2",
+    "europe-west3",
+    "europe-west4",
+    "europe-west6",
+    "europe-north1",
+    "asia-east1",
+    "asia-northeast1",
+    "asia-south1",
+    "asia-southeast1",
+    "australia-southeast1",
+];
+
+fn popup_block(title: &'static str, theme: &Theme) -> Block<'static> {
+    Block::default()
+        .title(title)
+        .title_style(
+            Style::default()
+                .fg(theme.mauve())
+                .add_modifier(Modifier::BOLD),
+        )
+        .borders(Borders::ALL)
+        .border_type(theme.border_type)
+        .border_style(Style::default().fg(theme.lavender()))
+        .style(Style::default().bg(theme.base()))
+}
+
+/// Whether `c` may appear in a label key or value.
+fn is_label_char(c: char) -> bool {
+    c.is_lowercase() || c.is_numeric() || c == '_' || c == '-'
+}
+
+/// Check a label against the GCP constraints, so they fail before the request.
+fn validate_label(key: &str, value: &str) -> std::result::Result<(), String> {
+    if key.is_empty() {
+        return Err("Label key is required".to_string());
+    }
+    if key.chars().count() > MAX_LABEL_LENGTH || value.chars().count() > MAX_LABEL_LENGTH {
+        return Err(format!(
+            "Label keys and values are limited to {MAX_LABEL_LENGTH} characters"
+        ));
+    }
+    if !key.starts_with(char::is_lowercase) || !key.chars().all(is_label_char) {
+        return Err(format!(
+            "Invalid label key '{key}': start with a lowercase letter, \
+             then use lowercase letters, digits, '_' and '-'"
+        ));
+    }
+    if !value.chars().all(is_label_char) {
+        return Err(format!(
+            "Invalid label value '{value}': use lowercase letters, digits, '_' and '-'"
+        ));
+    }
+    Ok(())
+}
+
+/// Parse a single `key=value` label; a bare key gets an empty value.
+fn parse_label(input: &str) -> std::result::Result<(String, String), String> {
+    let (key, value) = input.split_once('=').unwrap_or((input, ""));
+    let (key, value) = (key.trim(), value.trim());
+    validate_label(key, value)?;
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// Parse `key=value` pairs separated by commas.
+fn parse_labels(input: &str) -> std::result::Result<HashMap<String, String>, String> {
+    let mut labels = HashMap::new();
+    for pair in input
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+    {
+        let (key, value) = parse_label(pair)?;
+        labels.insert(key, value);
+    }
+    if labels.len() > MAX_LABELS {
+        return Err(format!("A secret can have at most {MAX_LABELS} labels"));
+    }
+    Ok(labels)
+}
+
+fn format_labels(labels: &HashMap<String, String>, query: &str, symbols: &Symbols) -> String {
     if labels.is_empty() {
-        return "—".to_string();
+        return symbols.none.to_string();
     }
 
     // Find the best matching label if there's a query
@@ -899,27 +2160,32 @@ fn format_labels(labels: &HashMap<String, String>, query: &str) -> String {
         };
 
         // Truncate if too long
-        if label.len() > 20 {
+        if display_width(&label) > 20 {
             let suffix = if labels.len() > 1 {
-                format!("… +{}", labels.len() - 1)
+                format!("{} +{}", symbols.ellipsis, labels.len() - 1)
             } else {
-                "…".to_string()
+                symbols.ellipsis.to_string()
             };
-            format!("{}{}", &label[..17], suffix)
+            format!("{}{}", take_width(&label, 17), suffix)
         } else if labels.len() > 1 {
             format!("{} +{}", label, labels.len() - 1)
         } else {
             label
         }
     } else {
-        "—".to_string()
+        symbols.none.to_string()
     }
 }
 
 // === Commands ===
 
+#[derive(Clone)]
 struct FetchSecretsCmd {
     client: SecretManagerClient,
+    /// Projects to list, their secrets joined into one list.
+    projects: Vec<String>,
+    /// Update the shown list instead of opening a new one.
+    refresh: bool,
     tx: UnboundedSender<SecretManagerMsg>,
 }
 
@@ -929,35 +2195,138 @@ impl Command for FetchSecretsCmd {
         "Loading secrets".to_string()
     }
 
+    fn retry(&self) -> Option<Box<dyn Command>> {
+        Some(Box::new(self.clone()))
+    }
+
     async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
-        let secrets = self.client.list_secrets().await?;
-        self.tx.send(SecretsMsg::Loaded(secrets).into())?;
+        let lists = future::try_join_all(self.projects.iter().map(|project| {
+            let client = self.client.in_project(project);
+            async move { client.list_secrets().await }
+        }))
+        .await?;
+        let secrets = lists.into_iter().flatten().collect();
+        let msg = if self.refresh {
+            SecretsMsg::Refreshed(secrets)
+        } else {
+            SecretsMsg::Loaded(secrets)
+        };
+        self.tx.send(msg.into())?;
         Ok(())
     }
 }
 
-struct CreateSecretCmd {
+/// Creates a secret step by step, deleting it again if a later step fails.
+fn create_secret_workflow(
+    secret: NewSecret,
+    client: SecretManagerClient,
+    tx: UnboundedSender<SecretManagerMsg>,
+) -> Workflow<CreateSecretContext> {
+    let name = format!("Creating '{}'", secret.name);
+    let has_payload = secret.payload.is_some();
+    let has_labels = !secret.labels.is_empty();
+    let mut workflow = Workflow::new(
+        name,
+        CreateSecretContext {
+            client,
+            secret,
+            created: None,
+            tx,
+        },
+    )
+    .step(CreateSecretStep);
+    if has_payload {
+        workflow = workflow.step(AddVersionStep);
+    }
+    if has_labels {
+        workflow = workflow.step(SetLabelsStep);
+    }
+    workflow.step(VerifySecretStep)
+}
+
+struct CreateSecretContext {
     client: SecretManagerClient,
-    name: String,
-    payload: Option<String>,
+    secret: NewSecret,
+    created: Option<Secret>,
     tx: UnboundedSender<SecretManagerMsg>,
 }
 
+struct CreateSecretStep;
+
 #[async_trait]
-impl Command for CreateSecretCmd {
+impl WorkflowStep<CreateSecretContext> for CreateSecretStep {
     fn name(&self) -> String {
-        format!("Creating '{}'", self.name)
+        "Create secret".to_string()
     }
 
-    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
-        let secret = if let Some(payload) = self.payload {
-            self.client
-                .create_secret_with_payload(&self.name, payload.as_bytes())
-                .await?
-        } else {
-            self.client.create_secret(&self.name).await?
+    async fn run(&self, context: &mut CreateSecretContext) -> Result<()> {
+        // Payload and labels are added by their own steps
+        let options = NewSecret {
+            payload: None,
+            labels: HashMap::new(),
+            ..context.secret.clone()
         };
-        self.tx.send(SecretsMsg::Created(secret).into())?;
+        context.created = Some(context.client.create_secret_with_options(&options).await?);
+        Ok(())
+    }
+
+    async fn rollback(&self, context: &mut CreateSecretContext) -> Result<()> {
+        context.client.delete_secret(&context.secret.name).await
+    }
+}
+
+struct AddVersionStep;
+
+#[async_trait]
+impl WorkflowStep<CreateSecretContext> for AddVersionStep {
+    fn name(&self) -> String {
+        "Add version".to_string()
+    }
+
+    async fn run(&self, context: &mut CreateSecretContext) -> Result<()> {
+        if let Some(payload) = &context.secret.payload {
+            context
+                .client
+                .add_secret_version(&context.secret.name, payload.as_bytes())
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+struct SetLabelsStep;
+
+#[async_trait]
+impl WorkflowStep<CreateSecretContext> for SetLabelsStep {
+    fn name(&self) -> String {
+        "Set labels".to_string()
+    }
+
+    async fn run(&self, context: &mut CreateSecretContext) -> Result<()> {
+        let secret = context
+            .client
+            .update_labels(&context.secret.name, context.secret.labels.clone())
+            .await?;
+        context.created = Some(secret);
+        Ok(())
+    }
+}
+
+struct VerifySecretStep;
+
+#[async_trait]
+impl WorkflowStep<CreateSecretContext> for VerifySecretStep {
+    fn name(&self) -> String {
+        "Verify".to_string()
+    }
+
+    async fn run(&self, context: &mut CreateSecretContext) -> Result<()> {
+        let secret = context.client.get_secret(&context.secret.name).await?;
+        if secret.labels != context.secret.labels {
+            return Err(eyre!("Labels on '{}' do not match", secret.name));
+        }
+        context.created = Some(secret.clone());
+        context.tx.send(SecretsMsg::Created(secret).into())?;
         Ok(())
     }
 }
@@ -974,9 +2343,17 @@ impl Command for DeleteSecretCmd {
         format!("Deleting '{}'", self.secret.name)
     }
 
+    fn mutates(&self) -> bool {
+        true
+    }
+
+    fn resource(&self) -> Option<String> {
+        Some(self.secret.name.clone())
+    }
+
     async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
         self.client.delete_secret(&self.secret.name).await?;
-        self.tx.send(SecretsMsg::Deleted(self.secret.name).into())?;
+        self.tx.send(SecretsMsg::Deleted(self.secret).into())?;
         Ok(())
     }
 }
@@ -994,12 +2371,26 @@ impl Command for UpdateLabelsCmd {
         format!("Updating labels on '{}'", self.secret.name)
     }
 
+    fn mutates(&self) -> bool {
+        true
+    }
+
+    fn resource(&self) -> Option<String> {
+        Some(self.secret.name.clone())
+    }
+
     async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
         let secret = self
             .client
             .update_labels(&self.secret.name, self.labels)
             .await?;
-        self.tx.send(SecretsMsg::LabelsUpdated(secret).into())?;
+        self.tx.send(
+            SecretsMsg::LabelsUpdated {
+                secret,
+                previous: self.secret.labels,
+            }
+            .into(),
+        )?;
         Ok(())
     }
 }
@@ -1016,6 +2407,10 @@ impl Command for FetchIamPolicyCmd {
         format!("Loading IAM for '{}'", self.secret.name)
     }
 
+    fn resource(&self) -> Option<String> {
+        Some(self.secret.name.clone())
+    }
+
     async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
         let policy = self.client.get_iam_policy(&self.secret.name).await?;
         self.tx.send(
@@ -1029,6 +2424,32 @@ impl Command for FetchIamPolicyCmd {
     }
 }
 
+struct FetchLocationPolicyCmd {
+    client: SecretManagerClient,
+    tx: UnboundedSender<SecretManagerMsg>,
+}
+
+#[async_trait]
+impl Command for FetchLocationPolicyCmd {
+    fn name(&self) -> String {
+        "Checking allowed locations".to_string()
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        // Without the policy every location is offered, as before
+        let policy = match self.client.location_policy().await {
+            Ok(policy) => Some(policy),
+            Err(err) => {
+                warn!(%err, "Could not read the organization policy on locations");
+                None
+            }
+        };
+        self.tx
+            .send(SecretsMsg::LocationPolicyLoaded(policy).into())?;
+        Ok(())
+    }
+}
+
 struct FetchSecretMetadataCmd {
     client: SecretManagerClient,
     secret: Secret,
@@ -1041,6 +2462,10 @@ impl Command for FetchSecretMetadataCmd {
         format!("Loading metadata for '{}'", self.secret.name)
     }
 
+    fn resource(&self) -> Option<String> {
+        Some(self.secret.name.clone())
+    }
+
     async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
         let secret = self.client.get_secret(&self.secret.name).await?;
         let replication = secret.replication.clone();
@@ -1067,6 +2492,10 @@ impl Command for LoadPayloadCmd {
         format!("Loading payload for '{}'", self.secret.name)
     }
 
+    fn resource(&self) -> Option<String> {
+        Some(self.secret.name.clone())
+    }
+
     async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
         let payload = self.client.access_latest_version(&self.secret.name).await?;
         self.tx.send(
@@ -1079,3 +2508,112 @@ impl Command for LoadPayloadCmd {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+
+    use super::*;
+    use crate::config::keybindings::KeybindingsConfig;
+
+    #[test]
+    fn test_parse_labels() {
+        let labels = parse_labels("team=platform, env = prod,, legacy").unwrap();
+        assert_eq!(labels.len(), 3);
+        assert_eq!(labels["env"], "prod");
+        assert_eq!(labels["legacy"], "");
+        assert!(parse_labels("Team=x").is_err());
+        assert!(parse_labels("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_validate_label() {
+        assert!(validate_label("région", "été_2024").is_ok());
+        assert!(validate_label("env", "").is_ok());
+        assert!(validate_label("", "x").is_err());
+        assert!(validate_label("1env", "x").is_err());
+        assert!(validate_label("env", "Prod").is_err());
+        assert!(validate_label("env", "a.b").is_err());
+        assert!(validate_label(&"k".repeat(64), "").is_err());
+
+        let many: Vec<String> = (0..=MAX_LABELS).map(|i| format!("l{i}")).collect();
+        assert!(parse_labels(&many.join(",")).is_err());
+    }
+
+    #[test]
+    fn test_format_labels_truncates_by_width() {
+        let symbols = &Symbols::ASCII;
+        let labels = HashMap::from([("チーム".to_string(), "決済プラットフォーム".to_string())]);
+        // 27 cells wide, cut at 17 cells without splitting a character
+        assert_eq!(format_labels(&labels, "", symbols), "チーム:決済プラッ...");
+
+        let labels = HashMap::from([("env".to_string(), "prod".to_string())]);
+        assert_eq!(format_labels(&labels, "", symbols), "env:prod");
+    }
+
+    #[test]
+    fn test_refresh_keeps_selection() {
+        let resolver = Arc::new(KeyResolver::new(Arc::new(KeybindingsConfig::default())));
+        let mut screen =
+            SecretListScreen::new(vec![Secret::test("a"), Secret::test("b")], resolver);
+        let _ = screen.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+
+        screen.set_secrets(vec![
+            Secret::test("0"),
+            Secret::test("a"),
+            Secret::test("b"),
+        ]);
+        assert_eq!(screen.table.selected_item().unwrap().name, "b");
+    }
+
+    #[test]
+    fn test_refresh_keeps_extras_of_unchanged_secrets() {
+        let secret = |name: &str, etag: &str| Secret {
+            etag: etag.to_string(),
+            ..Secret::test(name)
+        };
+        let resolver = Arc::new(KeyResolver::new(Arc::new(KeybindingsConfig::default())));
+        let mut screen = SecretListScreen::new(vec![secret("a", "1"), secret("b", "1")], resolver);
+        assert_eq!(screen.visible_without_extras().len(), 2);
+
+        for name in ["a", "b"] {
+            screen.set_enriched(Secret {
+                extras: Some(SecretExtras {
+                    rotation: None,
+                    versions: 3,
+                    latest: Some("Enabled".to_string()),
+                }),
+                ..secret(name, "1")
+            });
+        }
+        assert!(screen.visible_without_extras().is_empty());
+
+        // Only the secret changed since has to be loaded again
+        screen.set_secrets(vec![secret("a", "1"), secret("b", "2")]);
+        let missing = screen.visible_without_extras();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].name, "b");
+    }
+    #[test]
+    fn test_equally_named_secrets_of_other_projects_stay_apart() {
+        let secret = |project: &str| Secret {
+            project: project.to_string(),
+            ..Secret::test("db-password")
+        };
+        let resolver = Arc::new(KeyResolver::new(Arc::new(KeybindingsConfig::default())));
+        let mut screen =
+            SecretListScreen::new(vec![secret("acme-dev"), secret("acme-prod")], resolver);
+
+        screen.set_enriched(Secret {
+            extras: Some(SecretExtras {
+                rotation: None,
+                versions: 1,
+                latest: None,
+            }),
+            ..secret("acme-prod")
+        });
+        let missing = screen.visible_without_extras();
+        assert_eq!(missing.len(), 1);
+        assert_eq!(missing[0].project, "acme-dev");
+    }
+}
@@ -1,26 +1,74 @@
-use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use color_eyre::Result;
+use crossterm::event::KeyEvent;
+use futures::future::BoxFuture;
+use tokio::sync::mpsc::UnboundedSender;
 
 use crate::app::AppMessage;
 use crate::commands::Command;
-use crate::config::{GlobalAction, KeyResolver};
+use crate::config::{AppConfig, KeyResolver, SecretsAction};
 use crate::context::{CloudContext, GcpContext};
+use crate::provider::Provider;
+use crate::provider::gcp::org_policy::LocationPolicy;
+use crate::provider::gcp::secret_manager::access_stats::AccessStatsMsg;
+use crate::provider::gcp::secret_manager::access_test::AccessTestMsg;
+use crate::provider::gcp::secret_manager::accessors::AccessorsMsg;
+use crate::provider::gcp::secret_manager::annotations::AnnotationsMsg;
 use crate::provider::gcp::secret_manager::client::SecretManagerClient;
+use crate::provider::gcp::secret_manager::compare::CompareMsg;
+use crate::provider::gcp::secret_manager::deep_search::DeepSearchMsg;
+use crate::provider::gcp::secret_manager::duplicates::DuplicatesMsg;
+use crate::provider::gcp::secret_manager::enrich::EnrichMsg;
+use crate::provider::gcp::secret_manager::fan_out::FanOutMsg;
+use crate::provider::gcp::secret_manager::kube_sync::KubeSyncMsg;
+use crate::provider::gcp::secret_manager::lint::LintMsg;
 use crate::provider::gcp::secret_manager::payload::{PayloadMsg, SecretPayload};
+use crate::provider::gcp::secret_manager::permissions::{Permissions, PermissionsMsg};
+use crate::provider::gcp::secret_manager::promote::PromoteMsg;
+use crate::provider::gcp::secret_manager::registry::RegistryMsg;
 use crate::provider::gcp::secret_manager::secrets::{Secret, SecretsMsg};
+use crate::provider::gcp::secret_manager::stale::StaleMsg;
+use crate::provider::gcp::secret_manager::terraform::TerraformMsg;
+use crate::provider::gcp::secret_manager::topics::TopicsMsg;
+use crate::provider::gcp::secret_manager::trash::{Trash, TrashMsg};
+use crate::provider::gcp::secret_manager::undo::{UndoEntry, UndoMsg, UndoStack};
 use crate::provider::gcp::secret_manager::versions::{SecretVersion, VersionsMsg};
-use crate::provider::gcp::secret_manager::{payload, secrets, versions};
-use crate::provider::Provider;
-use crate::registry::ServiceProvider;
-use crate::service::{Service, ServiceMsg};
-use crate::ui::{Component, EventResult, EventResultExt, Keybinding, Modal, Screen, Spinner};
-use crate::Theme;
-use async_trait::async_trait;
-use color_eyre::Result;
-use crossterm::event::KeyEvent;
-use ratatui::layout::Rect;
-use ratatui::Frame;
-use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use crate::provider::gcp::secret_manager::{
+    access_stats,
+    access_test,
+    accessors,
+    annotations,
+    compare,
+    deep_search,
+    duplicates,
+    enrich,
+    fan_out,
+    kube_sync,
+    lint,
+    payload,
+    permissions,
+    promote,
+    registry,
+    secrets,
+    stale,
+    terraform,
+    topics,
+    trash,
+    undo,
+    versions,
+};
+use crate::registry::{ServiceProvider, WarmClient};
+use crate::service::{Service, ServiceMsg, ServiceShell, ServiceState};
+use crate::ui::{EventResult, Keybinding};
+
+/// Cache key of the secret list.
+const SECRETS_CACHE_KEY: &str = "secrets";
+/// Cache key of the organization policy on resource locations.
+const LOCATION_POLICY_CACHE_KEY: &str = "location_policy";
 
 // === Messages ===
 
@@ -28,13 +76,34 @@ use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
 pub enum SecretManagerMsg {
     Initialize,
     ClientInitialized(SecretManagerClient),
+    /// Connect again after the context changed, keeping the views.
+    Reconnect,
+    ClientReconnected(SecretManagerClient),
 
-    NavigateBack,
     DialogCancelled,
 
     Secret(SecretsMsg),
+    Annotations(AnnotationsMsg),
+    Topics(TopicsMsg),
     Version(VersionsMsg),
     Payload(PayloadMsg),
+    Compare(CompareMsg),
+    AccessStats(AccessStatsMsg),
+    Accessors(AccessorsMsg),
+    AccessTest(AccessTestMsg),
+    DeepSearch(DeepSearchMsg),
+    Duplicates(DuplicatesMsg),
+    Stale(StaleMsg),
+    Promote(PromoteMsg),
+    FanOut(FanOutMsg),
+    KubeSync(KubeSyncMsg),
+    Registry(RegistryMsg),
+    Lint(LintMsg),
+    Terraform(TerraformMsg),
+    Trash(TrashMsg),
+    Undo(UndoMsg),
+    Permissions(PermissionsMsg),
+    Enrich(EnrichMsg),
 }
 
 // === Provider ===
@@ -62,142 +131,174 @@ impl ServiceProvider for SecretManagerProvider {
         None
     }
 
-    fn create_service(&self, ctx: &CloudContext, resolver: Arc<KeyResolver>) -> Box<dyn Service> {
+    /// Also manages the secrets of the local vault.
+    fn is_available(&self, ctx: &CloudContext) -> bool {
+        matches!(ctx.provider(), Provider::Gcp | Provider::Local)
+    }
+
+    fn warm_up(&self, ctx: &CloudContext) -> Option<BoxFuture<'static, Result<WarmClient>>> {
+        let CloudContext::Gcp(gcp_ctx) = ctx.clone();
+        Some(Box::pin(async move {
+            let client = SecretManagerClient::new(&gcp_ctx).await?;
+            Ok(Arc::new(client) as WarmClient)
+        }))
+    }
+
+    fn create_service(
+        &self,
+        ctx: &CloudContext,
+        config: Arc<AppConfig>,
+        resolver: Arc<KeyResolver>,
+        client: Option<WarmClient>,
+    ) -> Box<dyn Service> {
         let CloudContext::Gcp(gcp_ctx) = ctx;
-        Box::new(SecretManager::new(gcp_ctx.clone(), resolver))
+        let client = client
+            .and_then(|client| client.downcast::<SecretManagerClient>().ok())
+            .map(|client| (*client).clone());
+        let state = SecretManagerState::new(gcp_ctx.clone(), client);
+        Box::new(SecretManager::new(state, config, resolver))
     }
 }
 
 // === Service ===
 
-pub struct SecretManager {
+/// Secret Manager on top of the generic [`ServiceShell`].
+pub type SecretManager = ServiceShell<SecretManagerState, SecretManagerMsg>;
+
+pub struct SecretManagerState {
     context: GcpContext,
-    spinner: Spinner,
     client: Option<SecretManagerClient>,
-    screen_stack: Vec<Box<dyn Screen<Output = SecretManagerMsg>>>,
-    loading: Option<&'static str>,
-    modal: Option<Box<dyn Modal<Output = SecretManagerMsg>>>,
-    msg_tx: UnboundedSender<SecretManagerMsg>,
-    msg_rx: UnboundedReceiver<SecretManagerMsg>,
-    cached_secrets: Option<Vec<Secret>>,
-    /// Key: secret name
-    cached_versions: HashMap<String, Vec<SecretVersion>>,
-    /// Key: "`secret_name/version_id`"
-    cached_payloads: HashMap<String, SecretPayload>,
-    resolver: Arc<KeyResolver>,
+    trash: Trash,
+    undo: UndoStack,
+    permissions: Permissions,
+    /// When the secret list was last requested, to rate-limit refreshes.
+    secrets_fetched_at: Option<Instant>,
+    /// Secret whose payload to show once the list is loaded, see
+    /// [`Service::open_resource`].
+    linked_secret: Option<String>,
+    /// Secrets whose extras were requested since the list was loaded, see
+    /// [`enrich`].
+    enriching: HashSet<String>,
 }
 
-impl SecretManager {
-    pub fn new(ctx: GcpContext, resolver: Arc<KeyResolver>) -> Self {
-        let (msg_tx, msg_rx) = mpsc::unbounded_channel();
+impl SecretManagerState {
+    pub fn new(context: GcpContext, client: Option<SecretManagerClient>) -> Self {
         Self {
-            context: ctx,
-            spinner: Spinner::new(),
-            client: None,
-            screen_stack: Vec::new(),
-            loading: Some("Initializing..."),
-            modal: None,
-            msg_tx,
-            msg_rx,
-            cached_secrets: None,
-            cached_versions: HashMap::new(),
-            cached_payloads: HashMap::new(),
-            resolver,
+            context,
+            client,
+            trash: Trash::default(),
+            undo: UndoStack::default(),
+            permissions: Permissions::default(),
+            secrets_fetched_at: None,
+            linked_secret: None,
+            enriching: HashSet::new(),
         }
     }
+}
 
-    pub(super) fn get_resolver(&self) -> Arc<KeyResolver> {
-        self.resolver.clone()
+impl SecretManager {
+    pub(super) const fn get_context(&self) -> &GcpContext {
+        &self.state().context
     }
 
     // === Public helpers for feature slices ===
 
     pub(super) fn get_client(&self) -> Result<SecretManagerClient> {
-        self.client
+        self.state()
+            .client
             .clone()
             .ok_or_else(|| color_eyre::eyre::eyre!("Secret Manager client not initialized"))
     }
 
-    pub(super) fn get_msg_sender(&self) -> UnboundedSender<SecretManagerMsg> {
-        self.msg_tx.clone()
+    /// The client for the project `secret` is in.
+    pub(super) fn get_client_for(&self, secret: &Secret) -> Result<SecretManagerClient> {
+        Ok(self.get_client()?.in_project(&secret.project))
     }
 
-    pub(super) fn queue(&self, msg: SecretManagerMsg) {
-        let _ = self.msg_tx.send(msg);
+    /// The context's project followed by the others listed with it.
+    pub(super) fn get_projects(&self) -> Vec<String> {
+        self.get_context()
+            .all_projects()
+            .into_iter()
+            .map(str::to_string)
+            .collect()
     }
 
-    // === Screen stack management ===
+    // === Caching: Secrets ===
 
-    pub(super) fn push_view<T: Screen<Output = SecretManagerMsg> + 'static>(&mut self, screen: T) {
-        self.hide_loading_spinner();
-        self.screen_stack.push(Box::new(screen));
+    pub(super) fn get_cached_secrets(&self) -> Option<Vec<Secret>> {
+        self.cached(SECRETS_CACHE_KEY)
     }
 
-    pub(super) fn pop_view(&mut self) -> bool {
-        if self.screen_stack.len() > 1 {
-            self.screen_stack.pop();
-            true
-        } else {
-            false
-        }
+    pub(super) fn cache_secrets(&mut self, secrets: &[Secret]) {
+        self.cache(SECRETS_CACHE_KEY, secrets.to_vec());
     }
 
-    pub(super) fn pop_to_root(&mut self) {
-        while self.screen_stack.len() > 1 {
-            self.screen_stack.pop();
-        }
-        self.screen_stack.clear();
+    pub(super) fn invalidate_secrets_cache(&mut self) {
+        self.invalidate(SECRETS_CACHE_KEY);
     }
 
-    // === Modal management ===
+    pub(super) fn mark_secrets_fetched(&mut self) {
+        self.state_mut().secrets_fetched_at = Some(Instant::now());
+    }
 
-    pub(super) fn display_overlay<T: Modal<Output = SecretManagerMsg> + 'static>(
-        &mut self,
-        modal: T,
-    ) {
-        self.modal = Some(Box::new(modal));
+    pub(super) const fn take_linked_secret(&mut self) -> Option<String> {
+        self.state_mut().linked_secret.take()
     }
 
-    pub(super) fn close_overlay(&mut self) {
-        self.modal = None;
+    pub(super) fn secrets_fetched_within(&self, interval: Duration) -> bool {
+        self.state()
+            .secrets_fetched_at
+            .is_some_and(|at| at.elapsed() < interval)
     }
 
-    // === Loading spinner ===
+    // === Caching: Organization policy ===
 
-    pub(super) const fn display_loading_spinner(&mut self, label: &'static str) {
-        self.loading = Some(label);
+    pub(super) fn get_cached_location_policy(&self) -> Option<LocationPolicy> {
+        self.cached(LOCATION_POLICY_CACHE_KEY)
     }
 
-    pub(super) const fn hide_loading_spinner(&mut self) {
-        self.loading = None;
+    pub(super) fn cache_location_policy(&mut self, policy: LocationPolicy) {
+        self.cache(LOCATION_POLICY_CACHE_KEY, policy);
     }
 
-    // === Caching: Secrets ===
+    // === Enrichment ===
 
-    pub(super) fn get_cached_secrets(&self) -> Option<Vec<Secret>> {
-        self.cached_secrets.clone()
+    /// Note that the extras of `secret` are requested, returning false if they
+    /// already were.
+    pub(super) fn start_enriching(&mut self, secret: &Secret) -> bool {
+        self.state_mut().enriching.insert(secret.resource_name())
     }
 
-    pub(super) fn cache_secrets(&mut self, secrets: &[Secret]) {
-        self.cached_secrets = Some(secrets.to_vec());
+    pub(super) fn is_enriching(&self, secret: &Secret) -> bool {
+        self.state().enriching.contains(&secret.resource_name())
     }
 
-    pub(super) fn invalidate_secrets_cache(&mut self) {
-        self.cached_secrets = None;
+    pub(super) fn finish_enriching(&mut self, secret: &Secret) {
+        self.state_mut().enriching.remove(&secret.resource_name());
+    }
+
+    /// Request extras afresh, e.g. for a newly loaded list.
+    pub(super) fn reset_enriching(&mut self) {
+        self.state_mut().enriching.clear();
     }
 
     // === Caching: Versions ===
 
     pub(super) fn get_cached_versions(&self, secret: &Secret) -> Option<Vec<SecretVersion>> {
-        self.cached_versions.get(&secret.name).cloned()
+        self.cached(&Self::versions_cache_key(secret))
     }
 
     pub(super) fn cache_versions(&mut self, secret: &Secret, versions: Vec<SecretVersion>) {
-        self.cached_versions.insert(secret.name.clone(), versions);
+        self.cache(Self::versions_cache_key(secret), versions);
     }
 
     pub(super) fn invalidate_versions_cache(&mut self, secret: &Secret) {
-        self.cached_versions.remove(&secret.name);
+        self.invalidate(&Self::versions_cache_key(secret));
+    }
+
+    fn versions_cache_key(secret: &Secret) -> String {
+        format!("versions/{}", secret.resource_name())
     }
 
     // === Caching: Payloads ===
@@ -207,8 +308,7 @@ impl SecretManager {
         secret: &Secret,
         version: Option<&SecretVersion>,
     ) -> Option<SecretPayload> {
-        let cache_key = Self::payload_cache_key(secret, version);
-        self.cached_payloads.get(&cache_key).cloned()
+        self.cached(&Self::payload_cache_key(secret, version))
     }
 
     pub(super) fn cache_payload(
@@ -217,156 +317,227 @@ impl SecretManager {
         version: Option<&SecretVersion>,
         payload: SecretPayload,
     ) {
-        let cache_key = Self::payload_cache_key(secret, version);
-        self.cached_payloads.insert(cache_key, payload);
+        let data = payload.data.clone();
+        self.cache_sensitive(Self::payload_cache_key(secret, version), payload, &data);
     }
 
     fn payload_cache_key(secret: &Secret, version: Option<&SecretVersion>) -> String {
         let version_id = version.map_or("latest", |v| v.version_id.as_str());
-        format!("{}/{}", secret.name, version_id)
+        format!("payloads/{}/{}", secret.resource_name(), version_id)
+    }
+
+    // === Trash ===
+
+    pub(super) const fn trash(&self) -> &Trash {
+        &self.state().trash
+    }
+
+    pub(super) const fn trash_mut(&mut self) -> &mut Trash {
+        &mut self.state_mut().trash
+    }
+
+    // === Undo ===
+
+    pub(super) fn record_undo(&mut self, entry: UndoEntry) {
+        self.state_mut().undo.push(entry);
     }
 
-    // === Message processing ===
+    pub(super) fn last_undo(&self) -> Option<&UndoEntry> {
+        self.state().undo.last()
+    }
+
+    pub(super) fn pop_undo(&mut self) -> Option<UndoEntry> {
+        self.state_mut().undo.pop()
+    }
+
+    pub(super) fn forget_undo(&mut self, secret: &Secret) {
+        self.state_mut().undo.forget(secret);
+    }
+
+    // === Permissions ===
+
+    pub(super) const fn permissions(&self) -> &Permissions {
+        &self.state().permissions
+    }
+
+    pub(super) fn set_permissions(&mut self, permissions: Permissions) {
+        self.state_mut().permissions = permissions;
+    }
+}
 
-    fn current_screen(&self) -> Option<&dyn Screen<Output = SecretManagerMsg>> {
-        self.screen_stack.last().map(|b| &**b)
+impl ServiceState<SecretManagerMsg> for SecretManagerState {
+    fn name(&self) -> &'static str {
+        "Secret Manager"
+    }
+
+    fn service_key(&self) -> &'static str {
+        SecretManagerProvider.service_key()
     }
 
-    fn current_screen_mut(&mut self) -> Option<&mut Box<dyn Screen<Output = SecretManagerMsg>>> {
-        self.screen_stack.last_mut()
+    fn init(shell: &mut SecretManager) {
+        // Skip connecting if the client was warmed up during service selection
+        match shell.state().client.clone() {
+            Some(client) => shell.queue(SecretManagerMsg::ClientInitialized(client)),
+            None => shell.queue(SecretManagerMsg::Initialize),
+        }
     }
 
-    fn process_message(&mut self, msg: SecretManagerMsg) -> Result<ServiceMsg> {
+    fn update(shell: &mut SecretManager, msg: SecretManagerMsg) -> Result<ServiceMsg> {
         match msg {
             // === Lifecycle ===
             SecretManagerMsg::Initialize => {
-                self.loading = Some("Initializing Secret Manager...");
+                shell.display_loading_spinner("Initializing Secret Manager...");
                 Ok(InitClientCmd {
-                    context: self.context.clone(),
-                    tx: self.msg_tx.clone(),
+                    context: shell.get_context().clone(),
+                    reconnect: false,
+                    tx: shell.get_msg_sender(),
                 }
                 .into())
             }
 
+            SecretManagerMsg::Reconnect => Ok(InitClientCmd {
+                context: shell.get_context().clone(),
+                reconnect: true,
+                tx: shell.get_msg_sender(),
+            }
+            .into()),
+
             SecretManagerMsg::ClientInitialized(client) => {
-                self.client = Some(client);
-                self.queue(SecretsMsg::Load.into());
+                shell.state_mut().client = Some(client);
+                shell.queue(PermissionsMsg::Check.into());
+                shell.queue(SecretsMsg::Load.into());
                 Ok(ServiceMsg::Idle)
             }
 
-            // === Navigation ===
-            SecretManagerMsg::NavigateBack => {
-                if self.pop_view() {
-                    Ok(ServiceMsg::Idle)
-                } else {
-                    Ok(ServiceMsg::Close)
-                }
+            SecretManagerMsg::ClientReconnected(client) => {
+                shell.state_mut().client = Some(client);
+                shell.queue(PermissionsMsg::Check.into());
+                shell.queue(SecretsMsg::Refresh.into());
+                Ok(ServiceMsg::Idle)
             }
 
+            // === Navigation ===
             SecretManagerMsg::DialogCancelled => {
-                self.close_overlay();
+                shell.close_overlay();
                 Ok(ServiceMsg::Idle)
             }
 
             // === Feature Dispatching ===
-            SecretManagerMsg::Secret(msg) => secrets::update(self, msg),
-            SecretManagerMsg::Version(msg) => versions::update(self, msg),
-            SecretManagerMsg::Payload(msg) => payload::update(self, msg),
+            SecretManagerMsg::Secret(msg) => secrets::update(shell, msg),
+            SecretManagerMsg::Annotations(msg) => annotations::update(shell, msg),
+            SecretManagerMsg::Topics(msg) => topics::update(shell, msg),
+            SecretManagerMsg::Version(msg) => versions::update(shell, msg),
+            SecretManagerMsg::Payload(msg) => payload::update(shell, msg),
+            SecretManagerMsg::Compare(msg) => Ok(compare::update(shell, msg)),
+            SecretManagerMsg::AccessStats(msg) => access_stats::update(shell, msg),
+            SecretManagerMsg::Accessors(msg) => accessors::update(shell, msg),
+            SecretManagerMsg::AccessTest(msg) => access_test::update(shell, msg),
+            SecretManagerMsg::DeepSearch(msg) => deep_search::update(shell, msg),
+            SecretManagerMsg::Duplicates(msg) => duplicates::update(shell, msg),
+            SecretManagerMsg::Stale(msg) => stale::update(shell, msg),
+            SecretManagerMsg::Promote(msg) => promote::update(shell, msg),
+            SecretManagerMsg::FanOut(msg) => fan_out::update(shell, msg),
+            SecretManagerMsg::KubeSync(msg) => kube_sync::update(shell, msg),
+            SecretManagerMsg::Registry(msg) => registry::update(shell, msg),
+            SecretManagerMsg::Lint(msg) => lint::update(shell, msg),
+            SecretManagerMsg::Terraform(msg) => Ok(terraform::update(&msg)),
+            SecretManagerMsg::Trash(msg) => trash::update(shell, msg),
+            SecretManagerMsg::Undo(msg) => undo::update(shell, msg),
+            SecretManagerMsg::Permissions(msg) => permissions::update(shell, msg),
+            SecretManagerMsg::Enrich(msg) => enrich::update(shell, msg),
         }
     }
-}
 
-impl Service for SecretManager {
-    fn init(&mut self) {
-        self.queue(SecretManagerMsg::Initialize);
+    fn switch_context(shell: &mut SecretManager, context: &CloudContext) -> bool {
+        let CloudContext::Gcp(gcp_ctx) = context;
+        let state = shell.state_mut();
+        state.context = gcp_ctx.clone();
+        state.client = None;
+        state.secrets_fetched_at = None;
+        // What the previous account could see says nothing about this one
+        shell.clear_cache();
+        shell.queue(SecretManagerMsg::Reconnect);
+        true
     }
 
-    fn handle_tick(&mut self) {
-        if self.loading.is_some() {
-            self.spinner.handle_tick();
-        }
+    fn open_resource(shell: &mut SecretManager, secret_name: &str) -> bool {
+        shell.state_mut().linked_secret = Some(secret_name.to_string());
+        true
     }
 
-    fn handle_key(&mut self, key: KeyEvent) -> EventResult<()> {
-        if self.loading.is_some() {
-            return EventResult::Ignored;
+    /// The selected or viewed secret, unless it is in the trash.
+    fn current_resource(shell: &SecretManager) -> Option<String> {
+        let mut ctx = shell.action_context();
+        if ctx.screen == Some("trash") {
+            return None;
         }
+        ctx.vars.remove("name")
+    }
 
-        // Handle modal first if present (captures all input)
-        if let Some(modal) = &mut self.modal {
-            let (consumed, msg) = modal.handle_key(key).process();
-            if let Some(msg) = msg {
-                self.queue(msg);
-            }
-            if consumed {
-                return EventResult::Consumed;
-            }
+    fn handle_tick(shell: &mut SecretManager) {
+        if shell.trash().has_expired() {
+            shell.queue(TrashMsg::Expire.into());
         }
+        // Rows also come into view when the terminal is resized
+        enrich::load_visible(shell);
+    }
 
-        // Handle current screen
-        if let Some(screen) = self.current_screen_mut() {
-            let (consumed, msg) = screen.handle_key(key).process();
-            if let Some(msg) = msg {
-                self.queue(msg);
-            }
-            if consumed {
-                return EventResult::Consumed;
-            }
+    fn handle_key(shell: &mut SecretManager, key: KeyEvent) -> EventResult<()> {
+        let resolver = shell.get_resolver();
+        if resolver.matches_secrets(&key, SecretsAction::Permissions) {
+            shell.queue(PermissionsMsg::Show.into());
+            return EventResult::Consumed;
         }
 
-        // Global navigation
-        if self.resolver.matches_global(&key, GlobalAction::Back) {
-            self.queue(SecretManagerMsg::NavigateBack);
+        if !shell.state().undo.is_empty() && resolver.matches_secrets(&key, SecretsAction::Undo) {
+            shell.queue(UndoMsg::Confirm.into());
             return EventResult::Consumed;
         }
 
         EventResult::Ignored
     }
 
-    fn update(&mut self) -> Result<ServiceMsg> {
-        let mut commands: Vec<Box<dyn Command>> = Vec::new();
-
-        while let Ok(msg) = self.msg_rx.try_recv() {
-            match self.process_message(msg)? {
-                ServiceMsg::Idle => {}
-                ServiceMsg::Run(cmds) => commands.extend(cmds),
-                ServiceMsg::Close => return Ok(ServiceMsg::Close),
-            }
-        }
+    /// Refuse actions the account lacks a permission for.
+    fn gate(shell: &SecretManager, msg: SecretManagerMsg) -> SecretManagerMsg {
+        shell
+            .permissions()
+            .denied(&msg)
+            .map_or(msg, |req| PermissionsMsg::Denied(req).into())
+    }
 
-        if commands.is_empty() {
-            Ok(ServiceMsg::Idle)
-        } else {
-            Ok(ServiceMsg::Run(commands))
+    /// Trashed secrets are only deleted while the service runs.
+    fn unsaved_work(shell: &SecretManager) -> Vec<String> {
+        match shell.trash().len() {
+            0 => Vec::new(),
+            count => vec![format!("{count} secret(s) in the trash, not deleted yet")],
         }
     }
 
-    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
-        if let Some(label) = self.loading {
-            self.spinner.set_label(label);
-            self.spinner.render(frame, area, theme);
-        } else if let Some(screen) = self.current_screen_mut() {
-            screen.render(frame, area, theme);
+    fn keybindings(shell: &SecretManager, keybindings: &mut Vec<Keybinding>) {
+        let resolver = shell.get_resolver();
+        if let Some(screen) = shell.current_screen_name() {
+            let denied = shell.permissions().denied_keys(screen, &resolver);
+            for keybinding in keybindings.iter_mut() {
+                keybinding.disabled = denied.contains(&keybinding.key);
+            }
         }
-
-        // Render modal on top if present
-        if let Some(modal) = &mut self.modal {
-            modal.render(frame, area, theme);
+        let state = shell.state();
+        if !state.trash.is_empty() {
+            keybindings.push(Keybinding::hint(
+                resolver.display_secrets(SecretsAction::Trash),
+                format!("Trash ({})", state.trash.len()),
+            ));
         }
-    }
-
-    fn breadcrumbs(&self) -> Vec<String> {
-        let mut bc = vec!["Secret Manager".to_string()];
-        for screen in &self.screen_stack {
-            bc.extend(screen.breadcrumbs());
+        if !state.undo.is_empty() {
+            keybindings.push(Keybinding::hint(
+                resolver.display_secrets(SecretsAction::Undo),
+                "Undo",
+            ));
         }
-        bc
-    }
-
-    fn keybindings(&self) -> Vec<Keybinding> {
-        self.current_screen()
-            .map(Screen::keybindings)
-            .unwrap_or_default()
+        keybindings.push(Keybinding::new(
+            resolver.display_secrets(SecretsAction::Permissions),
+            "Permissions",
+        ));
     }
 }
 
@@ -374,6 +545,8 @@ impl Service for SecretManager {
 
 struct InitClientCmd {
     context: GcpContext,
+    /// Whether the service is already running, see [`SecretManagerMsg::Reconnect`].
+    reconnect: bool,
     tx: UnboundedSender<SecretManagerMsg>,
 }
 
@@ -385,7 +558,11 @@ impl Command for InitClientCmd {
 
     async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
         let client = SecretManagerClient::new(&self.context).await?;
-        self.tx.send(SecretManagerMsg::ClientInitialized(client))?;
+        if self.reconnect {
+            self.tx.send(SecretManagerMsg::ClientReconnected(client))?;
+        } else {
+            self.tx.send(SecretManagerMsg::ClientInitialized(client))?;
+        }
         Ok(())
     }
 }
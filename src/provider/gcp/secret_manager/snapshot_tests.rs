@@ -0,0 +1,121 @@
+//! Rendering snapshots of the main Secret Manager screens and dialogs.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::config::KeyResolver;
+use crate::config::keybindings::KeybindingsConfig;
+use crate::provider::gcp::secret_manager::payload::{PayloadScreen, SecretPayload};
+use crate::provider::gcp::secret_manager::secrets::{
+    CreateSecretWizard,
+    DeleteSecretDialog,
+    LabelDialog,
+    ReplicationConfig,
+    Secret,
+    SecretListScreen,
+};
+use crate::provider::gcp::secret_manager::versions::{
+    CreateVersionDialog,
+    SecretVersion,
+    VersionListScreen,
+};
+use crate::ui::snapshot::{SIZES, assert_snapshots};
+use crate::ui::{Modal, Screen};
+
+fn resolver() -> Arc<KeyResolver> {
+    Arc::new(KeyResolver::new(Arc::new(KeybindingsConfig::default())))
+}
+
+fn secret(name: &str, labels: &[(&str, &str)]) -> Secret {
+    Secret {
+        created_at: "2024-03-02 09:14".to_string(),
+        labels: labels
+            .iter()
+            .map(|(key, value)| ((*key).to_string(), (*value).to_string()))
+            .collect::<HashMap<_, _>>(),
+        ..Secret::test(name)
+    }
+}
+
+fn secrets() -> Vec<Secret> {
+    vec![
+        secret("app-env", &[("team", "web")]),
+        Secret {
+            replication: ReplicationConfig::UserManaged {
+                locations: vec!["europe-west4".to_string(), "europe-west1".to_string()],
+            },
+            ..secret("postgres-credentials", &[("team", "platform")])
+        },
+        Secret {
+            expire_time: Some("2025-02-01 12:00".to_string()),
+            ..secret("legacy-smtp-password", &[])
+        },
+    ]
+}
+
+fn versions() -> Vec<SecretVersion> {
+    [("3", "Enabled"), ("2", "Disabled"), ("1", "Destroyed")]
+        .into_iter()
+        .map(|(id, state)| SecretVersion {
+            version_id: id.to_string(),
+            state: state.to_string(),
+            created_at: "2024-11-05 11:02".to_string(),
+        })
+        .collect()
+}
+
+#[test]
+fn test_secret_list() {
+    let mut screen = SecretListScreen::new(secrets(), resolver());
+    assert_snapshots!("secret_list", &SIZES, |frame, theme| {
+        screen.render(frame, frame.area(), theme);
+    });
+}
+
+#[test]
+fn test_versions() {
+    let mut screen = VersionListScreen::new(secrets().remove(0), versions(), resolver());
+    assert_snapshots!("versions", &SIZES[..1], |frame, theme| {
+        screen.render(frame, frame.area(), theme);
+    });
+}
+
+#[test]
+fn test_payload() {
+    let payload = SecretPayload {
+        data: "{\n  \"host\": \"10.12.0.4\",\n  \"port\": 5432\n}".to_string(),
+        is_binary: false,
+    };
+    let mut screen = PayloadScreen::new(
+        secrets().remove(1),
+        Some(versions().remove(0)),
+        payload,
+        resolver(),
+    );
+    assert_snapshots!("payload", &SIZES[..1], |frame, theme| {
+        screen.render(frame, frame.area(), theme);
+    });
+}
+
+#[test]
+fn test_dialogs() {
+    let mut wizard = CreateSecretWizard::new(resolver());
+    assert_snapshots!("create_secret", &SIZES[..1], |frame, theme| {
+        wizard.render(frame, frame.area(), theme);
+    });
+
+    let mut delete = DeleteSecretDialog::new(secrets().remove(0), resolver());
+    assert_snapshots!("delete_secret", &SIZES[..1], |frame, theme| {
+        delete.render(frame, frame.area(), theme);
+    });
+
+    let mut label = LabelDialog::new(secrets().remove(0), Some("team"));
+    assert_snapshots!("edit_label", &SIZES[..1], |frame, theme| {
+        label.render(frame, frame.area(), theme);
+    });
+
+    let mut version = CreateVersionDialog::new(secrets().remove(0), resolver());
+    assert_snapshots!("create_version", &SIZES[..1], |frame, theme| {
+        version.render(frame, frame.area(), theme);
+    });
+}
@@ -0,0 +1,485 @@
+//! Report on secrets that need attention: expired or expiring soon, without
+//! a new version for a long time, or not accessed at all.
+//!
+//! Thresholds come from the `[report]` config section. Cloud Monitoring only
+//! counts secret accesses per project, so secrets are reported as not
+//! accessed when the whole project had no accesses in that time.
+
+use std::pin::pin;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, TimeDelta, Utc};
+use crossterm::event::KeyEvent;
+use futures::{StreamExt, stream};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::Style;
+use ratatui::widgets::Cell;
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::warn;
+
+use crate::app::AppMessage;
+use crate::commands::{Command, ExportRow, Progress, TableExport};
+use crate::config::{KeyResolver, NavAction, ReportConfig, SearchAction, SecretsAction};
+use crate::provider::gcp::secret_manager::SecretManager;
+use crate::provider::gcp::secret_manager::client::SecretManagerClient;
+use crate::provider::gcp::secret_manager::secrets::{Secret, SecretsMsg};
+use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
+use crate::provider::gcp::secret_manager::versions::SecretVersion;
+use crate::search::Matcher;
+use crate::service::ServiceMsg;
+use crate::ui::{
+    ColumnDef,
+    Component,
+    EventResult,
+    Keybinding,
+    Result,
+    Screen,
+    Table,
+    TableEvent,
+    TableRow,
+};
+use crate::{Theme, time};
+
+/// Maximum number of version lists fetched at the same time.
+const MAX_CONCURRENT_FETCHES: usize = 8;
+
+// === Models ===
+
+/// Why a secret is in the report, in the order the report lists them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Finding {
+    Expired,
+    Expiring,
+    NotRotated,
+    NotAccessed,
+}
+
+impl Finding {
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Expired => "Expired",
+            Self::Expiring => "Expiring",
+            Self::NotRotated => "Not rotated",
+            Self::NotAccessed => "Not accessed",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StaleSecret {
+    pub secret: Secret,
+    pub finding: Finding,
+    pub detail: String,
+}
+
+impl TableRow for StaleSecret {
+    fn columns() -> &'static [ColumnDef] {
+        static COLUMNS: &[ColumnDef] = &[
+            ColumnDef::new("Secret", Constraint::Min(20)),
+            ColumnDef::new("Finding", Constraint::Length(14)),
+            ColumnDef::new("Detail", Constraint::Min(30)),
+        ];
+        COLUMNS
+    }
+
+    fn render_cells(&self, theme: &Theme) -> Vec<Cell<'static>> {
+        let color = match self.finding {
+            Finding::Expired => theme.red(),
+            Finding::Expiring => theme.peach(),
+            Finding::NotRotated | Finding::NotAccessed => theme.yellow(),
+        };
+        vec![
+            Cell::from(self.secret.name.clone()),
+            Cell::from(self.finding.label()).style(Style::default().fg(color)),
+            Cell::from(self.detail.clone()),
+        ]
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        let matcher = Matcher::new();
+        matcher.matches(&self.secret.name, query) || matcher.matches(self.finding.label(), query)
+    }
+}
+
+impl ExportRow for StaleSecret {
+    fn export_values(&self) -> Vec<String> {
+        vec![
+            self.secret.name.clone(),
+            self.finding.label().to_string(),
+            self.detail.clone(),
+        ]
+    }
+}
+
+/// Days from `now` to `at` for details, e.g. `in 3 days` or `12 days ago`.
+fn days_from(now: DateTime<Utc>, at: DateTime<Utc>) -> String {
+    let days = (at - now).num_days();
+    match days {
+        0 => "today".to_string(),
+        1 => "in 1 day".to_string(),
+        -1 => "1 day ago".to_string(),
+        days if days > 0 => format!("in {days} days"),
+        days => format!("{} days ago", -days),
+    }
+}
+
+/// Reports a secret that expired or expires within the configured days.
+pub fn check_expiration(
+    secret: &Secret,
+    now: DateTime<Utc>,
+    config: &ReportConfig,
+) -> Option<StaleSecret> {
+    let expire_time = secret.expire_time.as_deref()?;
+    let at = time::parse_moment(expire_time, now).ok()?;
+    let finding = if at <= now {
+        Finding::Expired
+    } else if at - now <= TimeDelta::days(config.expiring_within.into()) {
+        Finding::Expiring
+    } else {
+        return None;
+    };
+    let verb = if finding == Finding::Expired {
+        "expired"
+    } else {
+        "expires"
+    };
+    Some(StaleSecret {
+        secret: secret.clone(),
+        finding,
+        detail: format!("{verb} {expire_time}, {}", days_from(now, at)),
+    })
+}
+
+/// Reports a secret whose newest version is older than the configured days.
+pub fn check_rotation(
+    secret: &Secret,
+    versions: &[SecretVersion],
+    now: DateTime<Utc>,
+    config: &ReportConfig,
+) -> Option<StaleSecret> {
+    let newest = versions
+        .iter()
+        .max_by_key(|version| version.version_id.parse::<u64>().unwrap_or_default())?;
+    let at = time::parse_moment(&newest.created_at, now).ok()?;
+    if now - at < TimeDelta::days(config.not_rotated_for.into()) {
+        return None;
+    }
+    Some(StaleSecret {
+        secret: secret.clone(),
+        finding: Finding::NotRotated,
+        detail: format!(
+            "newest version {} from {}, {}",
+            newest.version_id,
+            newest.created_at,
+            days_from(now, at)
+        ),
+    })
+}
+
+/// Reports a secret older than the configured days, for a project without
+/// any secret accesses in that time.
+pub fn check_access(
+    secret: &Secret,
+    now: DateTime<Utc>,
+    config: &ReportConfig,
+) -> Option<StaleSecret> {
+    let created = time::parse_moment(&secret.created_at, now).ok()?;
+    if now - created < TimeDelta::days(config.not_accessed_for.into()) {
+        return None;
+    }
+    Some(StaleSecret {
+        secret: secret.clone(),
+        finding: Finding::NotAccessed,
+        detail: format!(
+            "no accesses in the project for {} days",
+            config.not_accessed_for
+        ),
+    })
+}
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum StaleMsg {
+    /// Check these secrets against the configured thresholds.
+    Run(Vec<Secret>),
+    Completed {
+        rows: Vec<StaleSecret>,
+        /// Whether the project's accesses could be read from Cloud Monitoring.
+        accesses_checked: bool,
+    },
+}
+
+impl From<StaleMsg> for SecretManagerMsg {
+    fn from(msg: StaleMsg) -> Self {
+        Self::Stale(msg)
+    }
+}
+
+impl From<StaleMsg> for EventResult<SecretManagerMsg> {
+    fn from(msg: StaleMsg) -> Self {
+        Self::Event(SecretManagerMsg::Stale(msg))
+    }
+}
+
+// === Screens ===
+
+pub struct StaleScreen {
+    table: Table<StaleSecret>,
+    resolver: Arc<KeyResolver>,
+    project_id: String,
+}
+
+impl StaleScreen {
+    pub fn new(
+        rows: Vec<StaleSecret>,
+        accesses_checked: bool,
+        project_id: String,
+        resolver: Arc<KeyResolver>,
+    ) -> Self {
+        let mut title = format!(" Stale Secrets: {} findings ", rows.len());
+        if !accesses_checked {
+            title.push_str("(accesses unavailable) ");
+        }
+        Self {
+            table: Table::new(rows, resolver.clone()).with_title(title),
+            resolver,
+            project_id,
+        }
+    }
+}
+
+impl Screen for StaleScreen {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        let result = self.table.handle_key(key)?;
+        if let EventResult::Event(TableEvent::Activated(row)) = result {
+            return Ok(SecretsMsg::ViewVersions(row.secret).into());
+        }
+        if result.is_consumed() {
+            return Ok(EventResult::Consumed);
+        }
+
+        if self.resolver.matches_secrets(&key, SecretsAction::Export) {
+            return Ok(SecretsMsg::StartExport {
+                file_name: format!("{}-stale-secrets.csv", self.project_id),
+                export: TableExport::of(&self.table),
+            }
+            .into());
+        }
+
+        Ok(EventResult::Ignored)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.table.render(frame, area, theme);
+    }
+
+    fn breadcrumbs(&self) -> Vec<String> {
+        vec!["Stale secrets".to_string()]
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        vec![
+            Keybinding::hint(self.resolver.display_nav(NavAction::Select), "Versions"),
+            Keybinding::hint(self.resolver.display_search(SearchAction::Toggle), "Search"),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::Export),
+                "Export",
+            ),
+        ]
+    }
+}
+
+// === Update Logic ===
+
+pub(super) fn update(state: &mut SecretManager, msg: StaleMsg) -> Result<ServiceMsg> {
+    match msg {
+        StaleMsg::Run(secrets) => {
+            if secrets.is_empty() {
+                return Ok(ServiceMsg::Idle);
+            }
+            let config = state.get_config().report.clone();
+            let now = Utc::now();
+            let expiring = secrets
+                .iter()
+                .filter_map(|secret| check_expiration(secret, now, &config))
+                .collect();
+
+            Ok(StaleReportCmd {
+                client: state.get_client()?,
+                progress: Progress::new(secrets.len()),
+                secrets,
+                config,
+                rows: expiring,
+                tx: state.get_msg_sender(),
+            }
+            .into())
+        }
+
+        StaleMsg::Completed {
+            rows,
+            accesses_checked,
+        } => {
+            let project_id = state.get_context().project_id.clone();
+            state.push_view(StaleScreen::new(
+                rows,
+                accesses_checked,
+                project_id,
+                state.get_resolver(),
+            ));
+            Ok(ServiceMsg::Idle)
+        }
+    }
+}
+
+// === Commands ===
+
+struct StaleReportCmd {
+    client: SecretManagerClient,
+    secrets: Vec<Secret>,
+    config: ReportConfig,
+    /// Expiring secrets, found before anything was fetched.
+    rows: Vec<StaleSecret>,
+    progress: Progress,
+    tx: UnboundedSender<SecretManagerMsg>,
+}
+
+#[async_trait]
+impl Command for StaleReportCmd {
+    fn name(&self) -> String {
+        format!("Checking {} secrets for stale ones", self.secrets.len())
+    }
+
+    fn progress(&self) -> Option<Progress> {
+        Some(self.progress.clone())
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let now = Utc::now();
+        let mut rows = self.rows.clone();
+
+        // The report is still useful without monitoring permissions
+        let accesses = match self
+            .client
+            .access_counts(self.config.not_accessed_for)
+            .await
+        {
+            Ok(daily) => Some(daily.iter().map(|point| point.value).sum::<u64>()),
+            Err(err) => {
+                warn!(%err, "Could not read secret accesses for the report");
+                None
+            }
+        };
+        if accesses == Some(0) {
+            rows.extend(
+                self.secrets
+                    .iter()
+                    .filter_map(|secret| check_access(secret, now, &self.config)),
+            );
+        }
+
+        let mut fetches = pin!(
+            stream::iter(self.secrets.clone())
+                .map(|secret| {
+                    let client = self.client.in_project(&secret.project);
+                    async move {
+                        let result = client.list_versions(&secret.name).await;
+                        (secret, result)
+                    }
+                })
+                .buffer_unordered(MAX_CONCURRENT_FETCHES)
+        );
+        while let Some((secret, result)) = fetches.next().await {
+            match result {
+                Ok(versions) => {
+                    rows.extend(check_rotation(&secret, &versions, now, &self.config));
+                }
+                Err(err) => warn!(secret = %secret.name, %err, "Could not list versions"),
+            }
+            self.progress.advance();
+        }
+
+        // Keep the output stable regardless of fetch completion order
+        rows.sort_by(|a, b| (a.finding, &a.secret.name).cmp(&(b.finding, &b.secret.name)));
+        self.tx.send(
+            StaleMsg::Completed {
+                rows,
+                accesses_checked: accesses.is_some(),
+            }
+            .into(),
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn secret(expire_time: Option<&str>) -> Secret {
+        Secret {
+            expire_time: expire_time.map(str::to_string),
+            ..Secret::test("api-key")
+        }
+    }
+
+    fn version(id: &str, created_at: &str) -> SecretVersion {
+        SecretVersion {
+            version_id: id.to_string(),
+            state: "Enabled".to_string(),
+            created_at: created_at.to_string(),
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        time::parse_moment("2025-01-01 12:00", Utc::now()).unwrap()
+    }
+
+    #[test]
+    fn test_check_expiration() {
+        let config = ReportConfig::default();
+        let finding = |expire_time| {
+            check_expiration(&secret(expire_time), now(), &config).map(|row| row.finding)
+        };
+        assert_eq!(finding(None), None);
+        assert_eq!(finding(Some("2024-12-31 12:00")), Some(Finding::Expired));
+        assert_eq!(finding(Some("2025-01-20 12:00")), Some(Finding::Expiring));
+        assert_eq!(finding(Some("2025-06-01 12:00")), None);
+
+        let row = check_expiration(&secret(Some("2025-01-11 12:00")), now(), &config).unwrap();
+        assert_eq!(row.detail, "expires 2025-01-11 12:00, in 10 days");
+    }
+
+    #[test]
+    fn test_check_rotation_uses_newest_version() {
+        let config = ReportConfig::default();
+        let secret = secret(None);
+        assert!(check_rotation(&secret, &[], now(), &config).is_none());
+
+        let recent = [
+            version("1", "2023-01-01 00:00"),
+            version("10", "2024-12-01 00:00"),
+        ];
+        assert!(check_rotation(&secret, &recent, now(), &config).is_none());
+
+        let old = [
+            version("2", "2024-06-01 00:00"),
+            version("1", "2023-01-01 00:00"),
+        ];
+        let row = check_rotation(&secret, &old, now(), &config).unwrap();
+        assert_eq!(row.finding, Finding::NotRotated);
+        assert_eq!(
+            row.detail,
+            "newest version 2 from 2024-06-01 00:00, 214 days ago"
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+
+use crate::commands::CopyToClipboardCmd;
+use crate::provider::gcp::secret_manager::secrets::{ReplicationConfig, Secret};
+use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
+use crate::provider::gcp::secret_manager::versions::SecretVersion;
+use crate::service::ServiceMsg;
+use crate::terraform::{HclValue, TerraformSnippet, TerraformTemplate, resource_name};
+use crate::ui::EventResult;
+
+// === Models ===
+
+/// A secret together with the project it lives in.
+struct SecretResource<'a> {
+    project: &'a str,
+    secret: &'a Secret,
+}
+
+impl TerraformTemplate for SecretResource<'_> {
+    fn terraform(&self) -> TerraformSnippet {
+        let name = &self.secret.name;
+        let mut snippet = TerraformSnippet::new(
+            "google_secret_manager_secret",
+            name,
+            format!("projects/{}/secrets/{name}", self.project),
+        )
+        .attr("project", HclValue::Str(self.project.to_string()))
+        .attr("secret_id", HclValue::Str(name.clone()));
+
+        if !self.secret.labels.is_empty() {
+            let labels: BTreeMap<String, String> = self.secret.labels.clone().into_iter().collect();
+            snippet = snippet.attr("labels", HclValue::Map(labels));
+        }
+
+        let replication = match &self.secret.replication {
+            ReplicationConfig::Automatic => ("auto".to_string(), HclValue::Block(Vec::new())),
+            ReplicationConfig::UserManaged { locations } => (
+                "user_managed".to_string(),
+                HclValue::Block(
+                    locations
+                        .iter()
+                        .map(|location| {
+                            (
+                                "replicas".to_string(),
+                                HclValue::Block(vec![(
+                                    "location".to_string(),
+                                    HclValue::Str(location.clone()),
+                                )]),
+                            )
+                        })
+                        .collect(),
+                ),
+            ),
+        };
+        snippet.attr("replication", HclValue::Block(vec![replication]))
+    }
+}
+
+/// A secret version. The payload is never exported; it is left as a variable.
+struct SecretVersionResource<'a> {
+    project: &'a str,
+    secret: &'a Secret,
+    version: &'a SecretVersion,
+}
+
+impl TerraformTemplate for SecretVersionResource<'_> {
+    fn terraform(&self) -> TerraformSnippet {
+        let secret_name = &self.secret.name;
+        let version_id = &self.version.version_id;
+        let local_name = format!("{secret_name}_v{version_id}");
+
+        let mut snippet = TerraformSnippet::new(
+            "google_secret_manager_secret_version",
+            &local_name,
+            format!(
+                "projects/{}/secrets/{secret_name}/versions/{version_id}",
+                self.project
+            ),
+        )
+        .attr(
+            "secret",
+            HclValue::Raw(format!(
+                "google_secret_manager_secret.{}.id",
+                resource_name(secret_name)
+            )),
+        )
+        .attr(
+            "secret_data",
+            HclValue::Raw(format!("var.{}", resource_name(&local_name))),
+        );
+
+        if self.version.state.contains("Disabled") {
+            snippet = snippet.attr("enabled", HclValue::Raw("false".to_string()));
+        }
+        snippet
+    }
+}
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum TerraformMsg {
+    CopySecret(Secret),
+    CopyVersion {
+        secret: Secret,
+        version: SecretVersion,
+    },
+}
+
+impl From<TerraformMsg> for SecretManagerMsg {
+    fn from(msg: TerraformMsg) -> Self {
+        Self::Terraform(msg)
+    }
+}
+
+impl From<TerraformMsg> for EventResult<SecretManagerMsg> {
+    fn from(msg: TerraformMsg) -> Self {
+        Self::Event(SecretManagerMsg::Terraform(msg))
+    }
+}
+
+// === Update Logic ===
+
+pub(super) fn update(msg: &TerraformMsg) -> ServiceMsg {
+    let (snippet, description) = match msg {
+        TerraformMsg::CopySecret(secret) => (
+            SecretResource {
+                project: &secret.project,
+                secret,
+            }
+            .terraform(),
+            format!("Terraform for '{}'", secret.name),
+        ),
+        TerraformMsg::CopyVersion { secret, version } => (
+            SecretVersionResource {
+                project: &secret.project,
+                secret,
+                version,
+            }
+            .terraform(),
+            format!("Terraform for '{}' v{}", secret.name, version.version_id),
+        ),
+    };
+    CopyToClipboardCmd::new(snippet.render(), description).into()
+}
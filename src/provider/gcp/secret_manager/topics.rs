@@ -0,0 +1,530 @@
+//! Notification topics: Pub/Sub topics Secret Manager publishes to when a
+//! secret or its versions change.
+//!
+//! New topics are picked from the ones in the project. Publishing also needs
+//! the Secret Manager service agent to hold the publisher role on the topic,
+//! which is not checked here.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Cell, Clear, ListItem, Paragraph};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::Theme;
+use crate::app::AppMessage;
+use crate::commands::{Command, ExportRow, TableExport};
+use crate::config::{ActionContext, GlobalAction, KeyResolver, SearchAction, SecretsAction};
+use crate::provider::gcp::secret_manager::SecretManager;
+use crate::provider::gcp::secret_manager::client::SecretManagerClient;
+use crate::provider::gcp::secret_manager::secrets::{Secret, SecretsMsg};
+use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
+use crate::provider::gcp::secret_manager::undo::UndoEntry;
+use crate::search::Matcher;
+use crate::service::ServiceMsg;
+use crate::ui::{
+    ColumnDef,
+    Component,
+    ConfirmDialog,
+    ConfirmEvent,
+    EventResult,
+    Keybinding,
+    List,
+    ListEvent,
+    ListRow,
+    Modal,
+    Result,
+    Screen,
+    Table,
+    TableRow,
+};
+
+/// Most topics a secret can notify.
+const MAX_TOPICS: usize = 10;
+
+// === Models ===
+
+/// A topic by its full name, `projects/<project>/topics/<topic>`.
+#[derive(Clone, Debug)]
+pub struct TopicEntry {
+    pub name: String,
+}
+
+impl TopicEntry {
+    /// The project and topic ID parts of the name.
+    fn parts(&self) -> (&str, &str) {
+        let mut parts = self.name.split('/');
+        match (parts.nth(1), parts.nth(1)) {
+            (Some(project), Some(topic)) => (project, topic),
+            _ => ("", &self.name),
+        }
+    }
+}
+
+impl TableRow for TopicEntry {
+    fn columns() -> &'static [ColumnDef] {
+        static COLUMNS: &[ColumnDef] = &[
+            ColumnDef::new("Topic", Constraint::Min(30)),
+            ColumnDef::new("Project", Constraint::Length(30)),
+        ];
+        COLUMNS
+    }
+
+    fn render_cells(&self, _theme: &Theme) -> Vec<Cell<'static>> {
+        let (project, topic) = self.parts();
+        vec![
+            Cell::from(topic.to_string()),
+            Cell::from(project.to_string()),
+        ]
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        Matcher::new().matches(&self.name, query)
+    }
+
+    fn details(&self) -> Vec<(&'static str, String)> {
+        vec![("Name", self.name.clone())]
+    }
+}
+
+impl ExportRow for TopicEntry {
+    fn export_values(&self) -> Vec<String> {
+        let (project, topic) = self.parts();
+        vec![topic.to_string(), project.to_string()]
+    }
+}
+
+impl ListRow for TopicEntry {
+    fn render_row(&self, theme: &Theme) -> ListItem<'static> {
+        ListItem::new(Line::styled(
+            self.name.clone(),
+            Style::default().fg(theme.text()),
+        ))
+    }
+}
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum TopicsMsg {
+    View(Secret),
+    /// Load the project's topics to pick one to add.
+    Pick(Secret),
+    Picking {
+        secret: Secret,
+        available: Vec<String>,
+    },
+    ConfirmRemove {
+        secret: Secret,
+        topic: String,
+    },
+    Update {
+        secret: Secret,
+        topics: Vec<String>,
+    },
+    Updated {
+        secret: Secret,
+        previous: Vec<String>,
+    },
+}
+
+impl From<TopicsMsg> for SecretManagerMsg {
+    fn from(msg: TopicsMsg) -> Self {
+        Self::Topics(msg)
+    }
+}
+
+impl From<TopicsMsg> for EventResult<SecretManagerMsg> {
+    fn from(msg: TopicsMsg) -> Self {
+        Self::Event(SecretManagerMsg::Topics(msg))
+    }
+}
+
+// === Screens ===
+
+pub struct TopicsScreen {
+    secret: Secret,
+    table: Table<TopicEntry>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl TopicsScreen {
+    pub fn new(secret: Secret, resolver: Arc<KeyResolver>) -> Self {
+        let topics = secret
+            .topics
+            .iter()
+            .map(|name| TopicEntry { name: name.clone() })
+            .collect();
+        let title = format!(
+            " {} - Notification Topics ({}/{MAX_TOPICS}) ",
+            secret.name,
+            secret.topics.len()
+        );
+        Self {
+            secret,
+            table: Table::new(topics, resolver.clone()).with_title(title),
+            resolver,
+        }
+    }
+}
+
+impl Screen for TopicsScreen {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        let result = self.table.handle_key(key)?;
+        if result.is_consumed() {
+            return Ok(EventResult::Consumed);
+        }
+
+        if self.resolver.matches_secrets(&key, SecretsAction::AddLabel) {
+            return Ok(TopicsMsg::Pick(self.secret.clone()).into());
+        }
+        if self.resolver.matches_secrets(&key, SecretsAction::Delete)
+            && let Some(topic) = self.table.selected_item()
+        {
+            return Ok(TopicsMsg::ConfirmRemove {
+                secret: self.secret.clone(),
+                topic: topic.name.clone(),
+            }
+            .into());
+        }
+        if self.resolver.matches_secrets(&key, SecretsAction::Export) {
+            return Ok(SecretsMsg::StartExport {
+                file_name: format!("{}-topics.json", self.secret.name),
+                export: TableExport::of(&self.table),
+            }
+            .into());
+        }
+
+        Ok(EventResult::Ignored)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.table.render(frame, area, theme);
+    }
+
+    fn breadcrumbs(&self) -> Vec<String> {
+        vec!["Topics".to_string()]
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        vec![
+            Keybinding::hint(self.resolver.display_search(SearchAction::Toggle), "Search"),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::AddLabel),
+                "Add",
+            ),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::Delete),
+                "Remove",
+            ),
+            Keybinding::new(
+                self.resolver.display_secrets(SecretsAction::Export),
+                "Export",
+            ),
+        ]
+    }
+
+    fn action_context(&self) -> ActionContext {
+        let ctx = ActionContext::screen("topics").with_var("name", &self.secret.name);
+        match self.table.selected_item() {
+            Some(topic) => ctx.with_var("topic", &topic.name),
+            None => ctx,
+        }
+    }
+}
+
+// === Dialogs ===
+
+/// Picks a project topic the secret does not notify yet.
+pub struct TopicPickerDialog {
+    secret: Secret,
+    topics: List<TopicEntry>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl TopicPickerDialog {
+    pub fn new(secret: Secret, available: Vec<String>, resolver: Arc<KeyResolver>) -> Self {
+        let topics = available
+            .into_iter()
+            .filter(|name| !secret.topics.contains(name))
+            .map(|name| TopicEntry { name })
+            .collect();
+        Self {
+            secret,
+            topics: List::new(topics, resolver.clone()),
+            resolver,
+        }
+    }
+
+    /// Why no topic can be added, if so.
+    fn blocked(&self) -> Option<String> {
+        if self.secret.topics.len() >= MAX_TOPICS {
+            return Some(format!("Secrets notify at most {MAX_TOPICS} topics"));
+        }
+        if self.topics.items().is_empty() {
+            return Some("No other topics in this project".to_string());
+        }
+        None
+    }
+}
+
+impl Modal for TopicPickerDialog {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if self.resolver.matches_global(&key, GlobalAction::Back) {
+            return Ok(SecretManagerMsg::DialogCancelled.into());
+        }
+        if let EventResult::Event(ListEvent::Activated(topic)) = self.topics.handle_key(key)?
+            && self.blocked().is_none()
+        {
+            let mut topics = self.secret.topics.clone();
+            topics.push(topic.name);
+            return Ok(TopicsMsg::Update {
+                secret: self.secret.clone(),
+                topics,
+            }
+            .into());
+        }
+        Ok(EventResult::Consumed)
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = area.centered(Constraint::Percentage(60), Constraint::Percentage(60));
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(" Add Notification Topic ")
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.lavender()))
+            .style(Style::default().bg(theme.base()));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [hint_area, list_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Min(1)]).areas(inner);
+        let (hint, color) = self.blocked().map_or_else(
+            || {
+                (
+                    "Notified when the secret or its versions change".to_string(),
+                    theme.subtext0(),
+                )
+            },
+            |reason| (reason, theme.red()),
+        );
+        frame.render_widget(
+            Paragraph::new(hint).style(Style::default().fg(color)),
+            hint_area,
+        );
+        self.topics.render(frame, list_area, theme);
+    }
+}
+
+pub struct RemoveTopicDialog {
+    secret: Secret,
+    topic: String,
+    dialog: ConfirmDialog,
+}
+
+impl RemoveTopicDialog {
+    pub fn new(secret: Secret, topic: String, resolver: Arc<KeyResolver>) -> Self {
+        let dialog = ConfirmDialog::new(
+            format!(
+                "Stop notifying \"{topic}\" of changes to \"{}\"?",
+                secret.name
+            ),
+            resolver,
+        )
+        .with_title("Remove Topic")
+        .with_confirm_text("Remove")
+        .with_cancel_text("Cancel");
+
+        Self {
+            secret,
+            topic,
+            dialog,
+        }
+    }
+}
+
+impl Modal for RemoveTopicDialog {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        Ok(match self.dialog.handle_key(key)? {
+            EventResult::Event(ConfirmEvent::Confirmed) => {
+                let mut topics = self.secret.topics.clone();
+                topics.retain(|topic| *topic != self.topic);
+                TopicsMsg::Update {
+                    secret: self.secret.clone(),
+                    topics,
+                }
+                .into()
+            }
+            EventResult::Event(ConfirmEvent::Cancelled) => SecretManagerMsg::DialogCancelled.into(),
+            _ => EventResult::Consumed,
+        })
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.dialog.render(frame, area, theme);
+    }
+}
+
+// === Update Logic ===
+
+pub(super) fn update(state: &mut SecretManager, msg: TopicsMsg) -> Result<ServiceMsg> {
+    let resolver = state.get_resolver();
+    match msg {
+        TopicsMsg::View(secret) => {
+            state.push_view(TopicsScreen::new(secret, resolver));
+            Ok(ServiceMsg::Idle)
+        }
+
+        TopicsMsg::Pick(secret) => {
+            state.display_loading_spinner("Loading topics...");
+
+            Ok(FetchTopicsCmd {
+                client: state.get_client_for(&secret)?,
+                secret,
+                tx: state.get_msg_sender(),
+            }
+            .into())
+        }
+
+        TopicsMsg::Picking { secret, available } => {
+            state.hide_loading_spinner();
+            state.display_overlay(TopicPickerDialog::new(secret, available, resolver));
+            Ok(ServiceMsg::Idle)
+        }
+
+        TopicsMsg::ConfirmRemove { secret, topic } => {
+            state.display_overlay(RemoveTopicDialog::new(secret, topic, resolver));
+            Ok(ServiceMsg::Idle)
+        }
+
+        TopicsMsg::Update { secret, topics } => {
+            state.close_overlay();
+            state.display_loading_spinner("Updating topics...");
+
+            Ok(UpdateTopicsCmd {
+                client: state.get_client_for(&secret)?,
+                secret,
+                topics,
+                tx: state.get_msg_sender(),
+            }
+            .into())
+        }
+
+        TopicsMsg::Updated { secret, previous } => {
+            state.hide_loading_spinner();
+            state.record_undo(UndoEntry::Topics {
+                secret: secret.clone(),
+                previous,
+            });
+            state.invalidate_secrets_cache();
+            state.pop_view();
+            state.push_view(TopicsScreen::new(secret, resolver));
+            Ok(ServiceMsg::Idle)
+        }
+    }
+}
+
+// === Commands ===
+
+struct FetchTopicsCmd {
+    client: SecretManagerClient,
+    secret: Secret,
+    tx: UnboundedSender<SecretManagerMsg>,
+}
+
+#[async_trait]
+impl Command for FetchTopicsCmd {
+    fn name(&self) -> String {
+        "Loading Pub/Sub topics".to_string()
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let available = self.client.list_topics().await?;
+        self.tx.send(
+            TopicsMsg::Picking {
+                secret: self.secret,
+                available,
+            }
+            .into(),
+        )?;
+        Ok(())
+    }
+}
+
+struct UpdateTopicsCmd {
+    client: SecretManagerClient,
+    secret: Secret,
+    topics: Vec<String>,
+    tx: UnboundedSender<SecretManagerMsg>,
+}
+
+#[async_trait]
+impl Command for UpdateTopicsCmd {
+    fn name(&self) -> String {
+        format!("Updating topics on '{}'", self.secret.name)
+    }
+
+    fn mutates(&self) -> bool {
+        true
+    }
+
+    fn resource(&self) -> Option<String> {
+        Some(self.secret.name.clone())
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let secret = self
+            .client
+            .update_topics(&self.secret.name, self.topics)
+            .await?;
+        self.tx.send(
+            TopicsMsg::Updated {
+                secret,
+                previous: self.secret.topics,
+            }
+            .into(),
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topic_parts() {
+        let topic = TopicEntry {
+            name: "projects/acme-dev/topics/secret-events".to_string(),
+        };
+        assert_eq!(topic.parts(), ("acme-dev", "secret-events"));
+
+        let malformed = TopicEntry {
+            name: "secret-events".to_string(),
+        };
+        assert_eq!(malformed.parts(), ("", "secret-events"));
+    }
+}
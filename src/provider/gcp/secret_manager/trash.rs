@@ -0,0 +1,600 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::Style;
+use ratatui::widgets::Cell;
+use tokio::sync::mpsc::UnboundedSender;
+use tracing::warn;
+
+use crate::Theme;
+use crate::app::AppMessage;
+use crate::commands::Command;
+use crate::config::{
+    ActionContext,
+    KeyResolver,
+    NavAction,
+    SearchAction,
+    SecretsAction,
+    TrashConfig,
+};
+use crate::provider::gcp::secret_manager::SecretManager;
+use crate::provider::gcp::secret_manager::client::SecretManagerClient;
+use crate::provider::gcp::secret_manager::payload::SecretPayload;
+use crate::provider::gcp::secret_manager::secrets::{Secret, SecretsMsg};
+use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
+use crate::provider::gcp::secret_manager::undo::UndoEntry;
+use crate::search::Matcher;
+use crate::service::ServiceMsg;
+use crate::ui::{
+    ColumnDef,
+    Component,
+    ConfirmDialog,
+    ConfirmEvent,
+    EventResult,
+    Keybinding,
+    Modal,
+    Result,
+    Screen,
+    Table,
+    TableEvent,
+    TableRow,
+};
+
+// === Models ===
+
+/// A secret waiting in the trash. It still exists in Secret Manager.
+#[derive(Debug, Clone)]
+pub struct TrashedSecret {
+    pub secret: Secret,
+    /// Latest payload at the time it was trashed, if it could be read.
+    pub payload: Option<SecretPayload>,
+    /// `None` keeps the secret until the trash is emptied.
+    pub purge_at: Option<Instant>,
+    /// A delete is running; the item stays in the trash until it succeeds.
+    pub purging: bool,
+}
+
+impl TrashedSecret {
+    fn is(&self, secret: &Secret) -> bool {
+        self.secret.resource_name() == secret.resource_name()
+    }
+
+    fn is_expired(&self, now: Instant) -> bool {
+        !self.purging && self.purge_at.is_some_and(|at| at <= now)
+    }
+}
+
+impl TableRow for TrashedSecret {
+    fn columns() -> &'static [ColumnDef] {
+        static COLUMNS: &[ColumnDef] = &[
+            ColumnDef::new("Name", Constraint::Min(20)),
+            ColumnDef::new("Payload", Constraint::Length(10)),
+            ColumnDef::new("Deleted in", Constraint::Length(16)),
+        ];
+        COLUMNS
+    }
+
+    fn render_cells(&self, theme: &Theme) -> Vec<Cell<'static>> {
+        let payload = self.payload.as_ref().map_or_else(
+            || theme.symbols.none.to_string(),
+            |payload| format!("{} B", payload.data.len()),
+        );
+        let remaining = if self.purging {
+            "deleting...".to_string()
+        } else {
+            self.purge_at.map_or_else(
+                || "when emptied".to_string(),
+                |at| format_remaining(at.saturating_duration_since(Instant::now())),
+            )
+        };
+
+        vec![
+            Cell::from(self.secret.name.clone()),
+            Cell::from(payload),
+            Cell::from(remaining).style(Style::default().fg(theme.peach())),
+        ]
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        Matcher::new().matches(&self.secret.name, query)
+    }
+}
+
+/// Secrets deleted in trash mode, oldest first.
+#[derive(Debug, Default)]
+pub struct Trash {
+    items: Vec<TrashedSecret>,
+}
+
+impl Trash {
+    pub fn items(&self) -> Vec<TrashedSecret> {
+        self.items.clone()
+    }
+
+    pub const fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Secrets are told apart by their resource name, since projects may
+    /// share secret names.
+    pub fn contains(&self, secret: &Secret) -> bool {
+        self.items.iter().any(|item| item.is(secret))
+    }
+
+    fn is_purging(&self, secret: &Secret) -> bool {
+        self.items
+            .iter()
+            .any(|item| item.is(secret) && item.purging)
+    }
+
+    fn add(&mut self, item: TrashedSecret) {
+        self.items.retain(|other| !other.is(&item.secret));
+        self.items.push(item);
+    }
+
+    fn remove(&mut self, secret: &Secret) -> Option<TrashedSecret> {
+        let index = self.items.iter().position(|item| item.is(secret))?;
+        Some(self.items.remove(index))
+    }
+
+    pub fn has_expired(&self) -> bool {
+        let now = Instant::now();
+        self.items.iter().any(|item| item.is_expired(now))
+    }
+
+    /// Mark the items whose grace period is over as purging and return them.
+    fn begin_expired(&mut self) -> Vec<TrashedSecret> {
+        let now = Instant::now();
+        self.begin_purge(|item| item.is_expired(now))
+    }
+
+    fn begin_all(&mut self) -> Vec<TrashedSecret> {
+        self.begin_purge(|_| true)
+    }
+
+    /// Items already being purged are skipped, so no secret is deleted twice.
+    fn begin_purge(&mut self, filter: impl Fn(&TrashedSecret) -> bool) -> Vec<TrashedSecret> {
+        self.items
+            .iter_mut()
+            .filter(|item| !item.purging && filter(item))
+            .map(|item| {
+                item.purging = true;
+                item.clone()
+            })
+            .collect()
+    }
+
+    /// Keep a secret whose delete failed, to be retried later.
+    fn abort_purge(&mut self, secret: &Secret) {
+        if let Some(item) = self.items.iter_mut().find(|item| item.is(secret)) {
+            item.purging = false;
+        }
+    }
+}
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum TrashMsg {
+    View,
+    /// Snapshot the latest payload and move the secret to the trash.
+    Stage(Secret),
+    Staged {
+        secret: Secret,
+        payload: Option<SecretPayload>,
+    },
+    Restore(Secret),
+    ConfirmEmpty,
+    Empty,
+    /// Delete the secrets whose grace period is over.
+    Expire,
+    Purged(TrashedSecret),
+    /// The delete failed; the secret stays in the trash.
+    PurgeFailed(Secret),
+}
+
+impl From<TrashMsg> for SecretManagerMsg {
+    fn from(msg: TrashMsg) -> Self {
+        Self::Trash(msg)
+    }
+}
+
+impl From<TrashMsg> for EventResult<SecretManagerMsg> {
+    fn from(msg: TrashMsg) -> Self {
+        Self::Event(SecretManagerMsg::Trash(msg))
+    }
+}
+
+// === Screens ===
+
+pub struct TrashScreen {
+    table: Table<TrashedSecret>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl TrashScreen {
+    pub fn new(items: Vec<TrashedSecret>, resolver: Arc<KeyResolver>) -> Self {
+        let title = format!(" Trash: {} secrets ", items.len());
+        Self {
+            table: Table::new(items, resolver.clone()).with_title(title),
+            resolver,
+        }
+    }
+}
+
+impl Screen for TrashScreen {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        let result = self.table.handle_key(key)?;
+        if let EventResult::Event(TableEvent::Activated(item)) = result {
+            return Ok(TrashMsg::Restore(item.secret).into());
+        }
+        if result.is_consumed() {
+            return Ok(EventResult::Consumed);
+        }
+
+        if self
+            .resolver
+            .matches_secrets(&key, SecretsAction::EmptyTrash)
+        {
+            return Ok(TrashMsg::ConfirmEmpty.into());
+        }
+
+        Ok(EventResult::Ignored)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.table.render(frame, area, theme);
+    }
+
+    fn breadcrumbs(&self) -> Vec<String> {
+        vec!["Trash".to_string()]
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        vec![
+            Keybinding::hint(self.resolver.display_nav(NavAction::Select), "Restore"),
+            Keybinding::hint(
+                self.resolver.display_secrets(SecretsAction::EmptyTrash),
+                "Empty trash",
+            ),
+            Keybinding::hint(self.resolver.display_search(SearchAction::Toggle), "Search"),
+        ]
+    }
+
+    fn action_context(&self) -> ActionContext {
+        let ctx = ActionContext::screen("trash");
+        match self.table.selected_item() {
+            Some(item) => ctx.with_var("name", &item.secret.name),
+            None => ctx,
+        }
+    }
+}
+
+// === Dialogs ===
+
+pub struct EmptyTrashDialog {
+    dialog: ConfirmDialog,
+}
+
+impl EmptyTrashDialog {
+    pub fn new(count: usize, resolver: Arc<KeyResolver>) -> Self {
+        let dialog = ConfirmDialog::new(
+            format!("Permanently delete the {count} secret(s) in the trash?"),
+            resolver,
+        )
+        .with_title("Empty Trash")
+        .with_confirm_text("Delete")
+        .with_cancel_text("Cancel")
+        .danger();
+
+        Self { dialog }
+    }
+}
+
+impl Modal for EmptyTrashDialog {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        Ok(match self.dialog.handle_key(key)? {
+            EventResult::Event(ConfirmEvent::Confirmed) => TrashMsg::Empty.into(),
+            EventResult::Event(ConfirmEvent::Cancelled) => SecretManagerMsg::DialogCancelled.into(),
+            _ => EventResult::Consumed,
+        })
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.dialog.render(frame, area, theme);
+    }
+}
+
+// === Update Logic ===
+
+pub(super) fn update(state: &mut SecretManager, msg: TrashMsg) -> Result<ServiceMsg> {
+    match msg {
+        TrashMsg::View => {
+            if state.get_config().trash.enabled || !state.trash().is_empty() {
+                let items = state.trash().items();
+                state.push_view(TrashScreen::new(items, state.get_resolver()));
+            }
+            Ok(ServiceMsg::Idle)
+        }
+
+        TrashMsg::Stage(secret) => {
+            state.display_loading_spinner("Moving to trash...");
+            state.close_overlay();
+
+            Ok(SnapshotPayloadCmd {
+                client: state.get_client_for(&secret)?,
+                secret,
+                tx: state.get_msg_sender(),
+            }
+            .into())
+        }
+
+        TrashMsg::Staged { secret, payload } => {
+            if let Some(payload) = &payload {
+                state.cache_payload(&secret, None, payload.clone());
+            }
+            let purge_at =
+                grace_period(&state.get_config().trash).map(|grace| Instant::now() + grace);
+            state.trash_mut().add(TrashedSecret {
+                secret,
+                payload,
+                purge_at,
+                purging: false,
+            });
+            state.invalidate_secrets_cache();
+            state.pop_to_root();
+            state.queue(SecretsMsg::Load.into());
+            Ok(ServiceMsg::Idle)
+        }
+
+        TrashMsg::Restore(secret) => {
+            // Too late once the delete is running
+            if state.trash().is_purging(&secret) {
+                return Ok(ServiceMsg::Idle);
+            }
+            state.trash_mut().remove(&secret);
+            state.invalidate_secrets_cache();
+            state.pop_to_root();
+            state.queue(SecretsMsg::Load.into());
+            Ok(ServiceMsg::Idle)
+        }
+
+        TrashMsg::ConfirmEmpty => {
+            let count = state.trash().len();
+            if count > 0 {
+                state.display_overlay(EmptyTrashDialog::new(count, state.get_resolver()));
+            }
+            Ok(ServiceMsg::Idle)
+        }
+
+        TrashMsg::Empty => {
+            state.close_overlay();
+            let items = state.trash_mut().begin_all();
+            purge(state, items)
+        }
+
+        TrashMsg::Expire => {
+            let items = state.trash_mut().begin_expired();
+            purge(state, items)
+        }
+
+        TrashMsg::Purged(item) => {
+            state.trash_mut().remove(&item.secret);
+            refresh_trash_screen(state);
+            // Older entries would act on a secret that no longer exists
+            state.forget_undo(&item.secret);
+            if let Some(payload) = item.payload {
+                state.record_undo(UndoEntry::Delete {
+                    secret: item.secret,
+                    payload,
+                });
+            }
+            Ok(ServiceMsg::Idle)
+        }
+
+        TrashMsg::PurgeFailed(secret) => {
+            state.trash_mut().abort_purge(&secret);
+            refresh_trash_screen(state);
+            Ok(ServiceMsg::Idle)
+        }
+    }
+}
+
+fn refresh_trash_screen(state: &mut SecretManager) {
+    if state.current_screen_is("trash") {
+        state.pop_view();
+        state.push_view(TrashScreen::new(
+            state.trash().items(),
+            state.get_resolver(),
+        ));
+    }
+}
+
+fn purge(state: &mut SecretManager, items: Vec<TrashedSecret>) -> Result<ServiceMsg> {
+    if items.is_empty() {
+        return Ok(ServiceMsg::Idle);
+    }
+    refresh_trash_screen(state);
+
+    let commands = items
+        .into_iter()
+        .map(|item| {
+            Ok(Box::new(PurgeSecretCmd {
+                client: state.get_client_for(&item.secret)?,
+                item,
+                tx: state.get_msg_sender(),
+            }) as Box<dyn Command>)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(ServiceMsg::Run(commands))
+}
+
+const fn grace_period(config: &TrashConfig) -> Option<Duration> {
+    match config.grace_period {
+        0 => None,
+        secs => Some(Duration::from_secs(secs)),
+    }
+}
+
+fn format_remaining(d: Duration) -> String {
+    let secs = d.as_secs();
+    if secs < 60 {
+        format!("{secs}s")
+    } else if secs < 3600 {
+        format!("{}m {}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h {}m", secs / 3600, secs % 3600 / 60)
+    }
+}
+
+// === Commands ===
+
+struct SnapshotPayloadCmd {
+    client: SecretManagerClient,
+    secret: Secret,
+    tx: UnboundedSender<SecretManagerMsg>,
+}
+
+#[async_trait]
+impl Command for SnapshotPayloadCmd {
+    fn name(&self) -> String {
+        format!("Moving '{}' to trash", self.secret.name)
+    }
+
+    fn mutates(&self) -> bool {
+        true
+    }
+
+    fn resource(&self) -> Option<String> {
+        Some(self.secret.name.clone())
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        // A secret without an enabled version can still be trashed
+        let payload = match self.client.access_latest_version(&self.secret.name).await {
+            Ok(payload) => Some(payload),
+            Err(err) => {
+                warn!(secret = %self.secret.name, %err, "No payload snapshot for trash");
+                None
+            }
+        };
+        self.tx.send(
+            TrashMsg::Staged {
+                secret: self.secret,
+                payload,
+            }
+            .into(),
+        )?;
+        Ok(())
+    }
+}
+
+struct PurgeSecretCmd {
+    client: SecretManagerClient,
+    item: TrashedSecret,
+    tx: UnboundedSender<SecretManagerMsg>,
+}
+
+#[async_trait]
+impl Command for PurgeSecretCmd {
+    fn name(&self) -> String {
+        format!("Deleting '{}'", self.item.secret.name)
+    }
+
+    fn mutates(&self) -> bool {
+        true
+    }
+
+    fn resource(&self) -> Option<String> {
+        Some(self.item.secret.name.clone())
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        if let Err(err) = self.client.delete_secret(&self.item.secret.name).await {
+            self.tx
+                .send(TrashMsg::PurgeFailed(self.item.secret).into())?;
+            return Err(err);
+        }
+        self.tx.send(TrashMsg::Purged(self.item).into())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn item(name: &str, purge_at: Option<Instant>) -> TrashedSecret {
+        TrashedSecret {
+            secret: Secret::test(name),
+            payload: None,
+            purge_at,
+            purging: false,
+        }
+    }
+
+    #[test]
+    fn test_expired_items_stay_until_purged() {
+        let now = Instant::now();
+        let mut trash = Trash::default();
+        trash.add(item("expired", Some(now)));
+        trash.add(item("pending", Some(now + Duration::from_mins(1))));
+        trash.add(item("manual", None));
+
+        assert!(trash.has_expired());
+        let expired = trash.begin_expired();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].secret.name, "expired");
+        assert!(!trash.has_expired());
+        assert_eq!(trash.len(), 3);
+
+        // Emptying the trash does not delete the expired secret a second time
+        let all = trash.begin_all();
+        assert_eq!(all.len(), 2);
+        assert!(all.iter().all(|item| item.secret.name != "expired"));
+
+        trash.abort_purge(&Secret::test("expired"));
+        assert!(trash.has_expired());
+        trash.remove(&Secret::test("expired"));
+        assert!(!trash.contains(&Secret::test("expired")));
+        assert!(
+            trash.contains(&Secret::test("pending")) && trash.contains(&Secret::test("manual"))
+        );
+    }
+
+    #[test]
+    fn test_same_name_in_other_project_is_not_trashed() {
+        let prod = Secret {
+            project: "acme-prod".to_string(),
+            ..Secret::test("db-password")
+        };
+        let mut trash = Trash::default();
+        trash.add(item("db-password", None));
+        trash.add(TrashedSecret {
+            secret: prod.clone(),
+            ..item("db-password", None)
+        });
+        assert_eq!(trash.len(), 2);
+
+        trash.remove(&Secret::test("db-password"));
+        assert!(!trash.contains(&Secret::test("db-password")));
+        assert!(trash.contains(&prod));
+    }
+}
@@ -0,0 +1,345 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::Theme;
+use crate::app::AppMessage;
+use crate::commands::Command;
+use crate::config::KeyResolver;
+use crate::provider::gcp::secret_manager::SecretManager;
+use crate::provider::gcp::secret_manager::client::SecretManagerClient;
+use crate::provider::gcp::secret_manager::payload::SecretPayload;
+use crate::provider::gcp::secret_manager::secrets::{ReplicationConfig, Secret, SecretsMsg};
+use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
+use crate::provider::gcp::secret_manager::versions::SecretVersion;
+use crate::service::ServiceMsg;
+use crate::ui::{Component, ConfirmDialog, ConfirmEvent, EventResult, Modal, Result, ToastType};
+
+/// Older operations are dropped once the stack is this deep.
+const MAX_UNDO_ENTRIES: usize = 20;
+
+// === Models ===
+
+/// A completed operation together with what is needed to revert it.
+#[derive(Debug, Clone)]
+pub enum UndoEntry {
+    /// Undone by enabling the version again.
+    Disable {
+        secret: Secret,
+        version: SecretVersion,
+    },
+    /// Undone by putting the previous labels back.
+    Labels {
+        secret: Secret,
+        previous: HashMap<String, String>,
+    },
+    /// Undone by putting the previous annotations back.
+    Annotations {
+        secret: Secret,
+        previous: HashMap<String, String>,
+    },
+    /// Undone by putting the previous topics back.
+    Topics {
+        secret: Secret,
+        previous: Vec<String>,
+    },
+    /// Undone by recreating the secret with its labels and the cached
+    /// latest payload as the first version.
+    Delete {
+        secret: Secret,
+        payload: SecretPayload,
+    },
+}
+
+impl UndoEntry {
+    pub const fn secret(&self) -> &Secret {
+        match self {
+            Self::Disable { secret, .. }
+            | Self::Labels { secret, .. }
+            | Self::Annotations { secret, .. }
+            | Self::Topics { secret, .. }
+            | Self::Delete { secret, .. } => secret,
+        }
+    }
+
+    /// What undoing will do, phrased as a question for the confirmation.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Disable { secret, version } => format!(
+                "Re-enable version {} of \"{}\"?",
+                version.version_id, secret.name
+            ),
+            Self::Labels { secret, previous } => format!(
+                "Restore the {} previous label(s) on \"{}\"?",
+                previous.len(),
+                secret.name
+            ),
+            Self::Annotations { secret, previous } => format!(
+                "Restore the {} previous annotation(s) on \"{}\"?",
+                previous.len(),
+                secret.name
+            ),
+            Self::Topics { secret, previous } => format!(
+                "Restore the {} previous notification topic(s) on \"{}\"?",
+                previous.len(),
+                secret.name
+            ),
+            Self::Delete { secret, .. } => {
+                let note = match secret.replication {
+                    ReplicationConfig::Automatic => "",
+                    ReplicationConfig::UserManaged { .. } => " with automatic replication",
+                };
+                format!(
+                    "Recreate \"{}\"{note} from its last loaded payload?",
+                    secret.name
+                )
+            }
+        }
+    }
+
+    fn done_message(&self) -> String {
+        match self {
+            Self::Disable { secret, version } => format!(
+                "Re-enabled version {} of '{}'",
+                version.version_id, secret.name
+            ),
+            Self::Labels { secret, .. } => format!("Restored labels on '{}'", secret.name),
+            Self::Annotations { secret, .. } => {
+                format!("Restored annotations on '{}'", secret.name)
+            }
+            Self::Topics { secret, .. } => format!("Restored topics on '{}'", secret.name),
+            Self::Delete { secret, .. } => format!("Recreated '{}'", secret.name),
+        }
+    }
+}
+
+/// Recent recoverable operations, newest last.
+#[derive(Debug, Default)]
+pub struct UndoStack {
+    entries: Vec<UndoEntry>,
+}
+
+impl UndoStack {
+    pub fn push(&mut self, entry: UndoEntry) {
+        if self.entries.len() == MAX_UNDO_ENTRIES {
+            self.entries.remove(0);
+        }
+        self.entries.push(entry);
+    }
+
+    pub fn last(&self) -> Option<&UndoEntry> {
+        self.entries.last()
+    }
+
+    pub fn pop(&mut self) -> Option<UndoEntry> {
+        self.entries.pop()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Drop entries for a secret that no longer exists in its old form.
+    pub fn forget(&mut self, secret: &Secret) {
+        let resource_name = secret.resource_name();
+        self.entries
+            .retain(|entry| entry.secret().resource_name() != resource_name);
+    }
+}
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum UndoMsg {
+    /// Ask before undoing the most recent operation.
+    Confirm,
+    Run,
+    Done(Box<Secret>),
+}
+
+impl From<UndoMsg> for SecretManagerMsg {
+    fn from(msg: UndoMsg) -> Self {
+        Self::Undo(msg)
+    }
+}
+
+impl From<UndoMsg> for EventResult<SecretManagerMsg> {
+    fn from(msg: UndoMsg) -> Self {
+        Self::Event(SecretManagerMsg::Undo(msg))
+    }
+}
+
+// === Dialogs ===
+
+pub struct UndoDialog {
+    dialog: ConfirmDialog,
+}
+
+impl UndoDialog {
+    pub fn new(entry: &UndoEntry, resolver: Arc<KeyResolver>) -> Self {
+        let dialog = ConfirmDialog::new(entry.describe(), resolver)
+            .with_title("Undo")
+            .with_confirm_text("Undo")
+            .with_cancel_text("Cancel");
+        Self { dialog }
+    }
+}
+
+impl Modal for UndoDialog {
+    type Output = SecretManagerMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        Ok(match self.dialog.handle_key(key)? {
+            EventResult::Event(ConfirmEvent::Confirmed) => UndoMsg::Run.into(),
+            EventResult::Event(ConfirmEvent::Cancelled) => SecretManagerMsg::DialogCancelled.into(),
+            _ => EventResult::Consumed,
+        })
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.dialog.render(frame, area, theme);
+    }
+}
+
+// === Update Logic ===
+
+pub(super) fn update(state: &mut SecretManager, msg: UndoMsg) -> Result<ServiceMsg> {
+    match msg {
+        UndoMsg::Confirm => {
+            if let Some(entry) = state.last_undo() {
+                let dialog = UndoDialog::new(entry, state.get_resolver());
+                state.display_overlay(dialog);
+            }
+            Ok(ServiceMsg::Idle)
+        }
+
+        UndoMsg::Run => {
+            state.close_overlay();
+            let Some(entry) = state.pop_undo() else {
+                return Ok(ServiceMsg::Idle);
+            };
+            state.display_loading_spinner("Undoing...");
+
+            Ok(UndoCmd {
+                client: state.get_client_for(entry.secret())?,
+                entry,
+                tx: state.get_msg_sender(),
+            }
+            .into())
+        }
+
+        UndoMsg::Done(secret) => {
+            state.invalidate_secrets_cache();
+            state.invalidate_versions_cache(&secret);
+            state.pop_to_root();
+            state.queue(SecretsMsg::Load.into());
+            Ok(ServiceMsg::Idle)
+        }
+    }
+}
+
+// === Commands ===
+
+struct UndoCmd {
+    client: SecretManagerClient,
+    entry: UndoEntry,
+    tx: UnboundedSender<SecretManagerMsg>,
+}
+
+#[async_trait]
+impl Command for UndoCmd {
+    fn name(&self) -> String {
+        format!("Undoing change to '{}'", self.entry.secret().name)
+    }
+
+    fn mutates(&self) -> bool {
+        true
+    }
+
+    fn resource(&self) -> Option<String> {
+        Some(self.entry.secret().name.clone())
+    }
+
+    async fn execute(self: Box<Self>, action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        match &self.entry {
+            UndoEntry::Disable { secret, version } => {
+                self.client
+                    .enable_version(&secret.name, &version.version_id)
+                    .await?;
+            }
+            UndoEntry::Labels { secret, previous } => {
+                self.client
+                    .update_labels(&secret.name, previous.clone())
+                    .await?;
+            }
+            UndoEntry::Annotations { secret, previous } => {
+                self.client
+                    .update_annotations(&secret.name, previous.clone())
+                    .await?;
+            }
+            UndoEntry::Topics { secret, previous } => {
+                self.client
+                    .update_topics(&secret.name, previous.clone())
+                    .await?;
+            }
+            UndoEntry::Delete { secret, payload } => {
+                self.client
+                    .create_secret_with_labels(&secret.name, secret.labels.clone())
+                    .await?;
+                self.client
+                    .add_secret_version(&secret.name, payload.data.as_bytes())
+                    .await?;
+            }
+        }
+
+        action_tx.send(AppMessage::ShowToast {
+            message: self.entry.done_message(),
+            toast_type: ToastType::Success,
+        })?;
+        self.tx
+            .send(UndoMsg::Done(Box::new(self.entry.secret().clone())).into())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels_entry(name: &str) -> UndoEntry {
+        UndoEntry::Labels {
+            secret: Secret::test(name),
+            previous: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_stack_is_bounded_and_forgets_secrets() {
+        let mut stack = UndoStack::default();
+        for i in 0..=MAX_UNDO_ENTRIES {
+            stack.push(labels_entry(&format!("secret-{i}")));
+        }
+        assert_eq!(stack.entries.len(), MAX_UNDO_ENTRIES);
+        assert_eq!(stack.entries[0].secret().name, "secret-1");
+
+        // The same name in another project is a different secret
+        let prod = Secret {
+            project: "acme-prod".to_string(),
+            ..Secret::test("secret-1")
+        };
+        stack.forget(&prod);
+        assert_eq!(stack.entries.len(), MAX_UNDO_ENTRIES);
+
+        stack.forget(&Secret::test(&format!("secret-{MAX_UNDO_ENTRIES}")));
+        let last = stack.pop().unwrap();
+        assert_eq!(
+            last.secret().name,
+            format!("secret-{}", MAX_UNDO_ENTRIES - 1)
+        );
+    }
+}
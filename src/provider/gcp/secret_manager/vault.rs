@@ -0,0 +1,167 @@
+//! Secrets kept on this machine, for the `local` context.
+//!
+//! The vault is a file holding a Secret Manager project in the demo fixture
+//! format, encrypted like saved payloads: its extension picks age or GPG and
+//! the `encryption` config section the keys. It is read once per session and
+//! written again after every change.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, PoisonError};
+
+use color_eyre::Result;
+use color_eyre::eyre::{bail, eyre};
+use tokio::io::AsyncWriteExt;
+
+use crate::encryption::{self, Scheme};
+use crate::provider::gcp::secret_manager::demo::{DemoSecretManager, ProjectFixture};
+
+/// Vaults opened in this session, by path, so every client of a vault
+/// shares its secrets.
+static VAULTS: LazyLock<Mutex<HashMap<PathBuf, LocalVault>>> = LazyLock::new(Mutex::default);
+
+#[derive(Clone, Debug)]
+pub struct LocalVault {
+    path: PathBuf,
+    store: DemoSecretManager,
+}
+
+impl LocalVault {
+    /// Open the vault at `path`, starting an empty one if there is no file.
+    pub async fn open(path: &Path) -> Result<Self> {
+        if Scheme::of(path).is_none() {
+            bail!(
+                "The vault {} must end in .age, .gpg or .asc to be encrypted",
+                path.display()
+            );
+        }
+        if let Some(vault) = VAULTS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(path)
+        {
+            return Ok(vault.clone());
+        }
+
+        let fixture = match tokio::fs::read(path).await {
+            Ok(data) => decode(path, &encryption::decrypt_from(path, data).await?)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => ProjectFixture::default(),
+            Err(err) => bail!("Cannot read the vault {}: {err}", path.display()),
+        };
+        let vault = Self {
+            path: path.to_path_buf(),
+            store: DemoSecretManager::from_fixture(fixture),
+        };
+        // Another client may have opened it meanwhile; keep the first
+        Ok(VAULTS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(vault.path.clone())
+            .or_insert(vault)
+            .clone())
+    }
+
+    pub const fn store(&self) -> &DemoSecretManager {
+        &self.store
+    }
+
+    /// Make `change` to the secrets and save them, undoing it if the vault
+    /// cannot be written, e.g. without a recipient to encrypt to.
+    pub async fn apply<T>(&self, change: impl FnOnce() -> Result<T>) -> Result<T> {
+        let before = self.store.to_fixture()?;
+        let value = change()?;
+        if let Err(err) = self.save().await {
+            self.store.restore(before);
+            return Err(err);
+        }
+        Ok(value)
+    }
+
+    /// Write the secrets back to the file, readable only by the current user.
+    pub async fn save(&self) -> Result<()> {
+        let data = encryption::encrypt_for(&self.path, encode(&self.store)?).await?;
+        if let Some(dir) = self.path.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        // Written next to the vault and renamed, so a failure keeps the old one
+        let temp = self.path.with_extension("tmp");
+        let mut options = tokio::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        let mut file = options.open(&temp).await?;
+        file.write_all(&data).await?;
+        file.flush().await?;
+        tokio::fs::rename(&temp, &self.path).await?;
+        Ok(())
+    }
+}
+
+/// The secrets of `store` as written to the vault before encryption.
+fn encode(store: &DemoSecretManager) -> Result<Vec<u8>> {
+    Ok(serde_json::to_vec(&store.to_fixture()?)?)
+}
+
+/// The secrets in the decrypted contents of the vault at `path`.
+fn decode(path: &Path, data: &[u8]) -> Result<ProjectFixture> {
+    serde_json::from_slice(data)
+        .map_err(|err| eyre!("Cannot read the vault {}: {err}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::gcp::secret_manager::secrets::NewSecret;
+
+    fn new_secret(name: &str) -> NewSecret {
+        NewSecret {
+            name: name.to_string(),
+            payload: Some("hunter2".to_string()),
+            ..NewSecret::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_contents_survive_encoding() {
+        let path = std::env::temp_dir().join(format!("lazycloud-vault-{}.gpg", std::process::id()));
+        let vault = LocalVault::open(&path).await.unwrap();
+        vault
+            .store()
+            .create_secret(&new_secret("db-password"))
+            .unwrap();
+        vault
+            .store()
+            .add_secret_version("db-password", b"hunter3")
+            .unwrap();
+
+        let reopened = DemoSecretManager::from_fixture(
+            decode(&path, &encode(vault.store()).unwrap()).unwrap(),
+        );
+        assert_eq!(
+            reopened.get_secret("db-password").unwrap().name,
+            "db-password"
+        );
+        assert_eq!(
+            reopened
+                .access_version_bytes("db-password", "latest")
+                .unwrap()
+                .as_ref(),
+            b"hunter3"
+        );
+        assert_eq!(reopened.list_versions("db-password").unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_save_undoes_change() {
+        // No age recipient is configured, so the vault cannot be written
+        let path = std::env::temp_dir().join(format!("lazycloud-vault-{}.age", std::process::id()));
+        let vault = LocalVault::open(&path).await.unwrap();
+
+        let result = vault
+            .apply(|| vault.store().create_secret(&new_secret("db-password")))
+            .await;
+        assert!(result.unwrap_err().to_string().contains("age_recipient"));
+        assert!(vault.store().list_secrets().is_empty());
+        assert!(!path.exists());
+    }
+}
@@ -2,25 +2,41 @@ use std::fmt::Display;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::Frame;
-use ratatui::layout::{Constraint, Rect};
-use ratatui::widgets::Cell;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::Style;
+use ratatui::widgets::{Cell, Paragraph};
 use tokio::sync::mpsc::UnboundedSender;
-use crate::app::AppMessage;
+
 use crate::Theme;
+use crate::app::AppMessage;
 use crate::commands::Command;
-use crate::config::{KeyResolver, SearchAction, VersionsAction};
+use crate::config::{ActionContext, KeyResolver, NavAction, SearchAction, VersionsAction};
 use crate::provider::gcp::secret_manager::SecretManager;
 use crate::provider::gcp::secret_manager::client::SecretManagerClient;
 use crate::provider::gcp::secret_manager::payload::PayloadMsg;
-use crate::provider::gcp::secret_manager::secrets::Secret;
+use crate::provider::gcp::secret_manager::secrets::{Secret, SecretsMsg};
 use crate::provider::gcp::secret_manager::service::SecretManagerMsg;
+use crate::provider::gcp::secret_manager::terraform::TerraformMsg;
+use crate::provider::gcp::secret_manager::undo::UndoEntry;
 use crate::search::Matcher;
 use crate::service::ServiceMsg;
 use crate::ui::{
-    ColumnDef, Component, ConfirmDialog, ConfirmEvent, EventResult, Keybinding, Modal, Result,
-    Screen, Table, TableEvent, TableRow, TextInput, TextInputEvent,
+    ColumnDef,
+    Component,
+    ConfirmDialog,
+    ConfirmEvent,
+    EventResult,
+    Keybinding,
+    Modal,
+    Result,
+    Screen,
+    Table,
+    TableEvent,
+    TableRow,
+    TextArea,
+    TextAreaEvent,
 };
 
 // === Models ===
@@ -62,6 +78,25 @@ impl TableRow for SecretVersion {
     }
 }
 
+/// ID of the version `target` names: a version number or `latest`, the
+/// newest version.
+fn resolve_version(versions: &[SecretVersion], target: &str) -> Option<String> {
+    let target = target.trim();
+    let number = if target.eq_ignore_ascii_case("latest") {
+        versions
+            .iter()
+            .filter_map(|v| v.version_id.parse::<u64>().ok())
+            .max()?
+    } else {
+        target.parse::<u64>().ok()?
+    };
+    versions
+        .iter()
+        .map(|v| &v.version_id)
+        .find(|id| id.parse::<u64>().ok() == Some(number))
+        .cloned()
+}
+
 // === Messages ===
 
 #[derive(Debug, Clone)]
@@ -87,6 +122,7 @@ pub enum VersionsMsg {
     },
     Disabled {
         secret: Secret,
+        version: SecretVersion,
     },
 
     Enable {
@@ -130,9 +166,15 @@ impl From<VersionsMsg> for EventResult<SecretManagerMsg> {
 
 // === Screens ===
 
+/// Labels of the version table filters, shown in its title while active.
+const ONLY_ENABLED: &str = "Enabled only";
+const HIDE_DESTROYED: &str = "No destroyed";
+
 pub struct VersionListScreen {
     secret: Secret,
     table: Table<SecretVersion>,
+    /// Version typed after the jump key, while the prompt is open.
+    jump: Option<String>,
     resolver: Arc<KeyResolver>,
 }
 
@@ -141,16 +183,61 @@ impl VersionListScreen {
         let title = format!(" {} - Versions ", secret.name);
         Self {
             secret,
-            table: Table::new(versions, resolver.clone()).with_title(title),
+            table: Table::new(versions, resolver.clone())
+                .with_title(title)
+                .with_filter(ONLY_ENABLED, |v| v.state.contains("Enabled"))
+                .with_filter(HIDE_DESTROYED, |v| !v.state.contains("Destroyed")),
+            jump: None,
             resolver,
         }
     }
+
+    /// Keys typed into the jump prompt: the selection follows the typed
+    /// version and Enter opens its payload.
+    fn handle_jump_key(&mut self, key: KeyEvent) -> EventResult<SecretManagerMsg> {
+        let Some(input) = &mut self.jump else {
+            return EventResult::Ignored;
+        };
+        if self.resolver.matches_search(&key, SearchAction::Exit) {
+            self.jump = None;
+            return EventResult::Consumed;
+        }
+        if self.resolver.matches_nav(&key, NavAction::Select) {
+            let target = resolve_version(self.table.items(), input);
+            self.jump = None;
+            return match target
+                .and_then(|id| self.table.items().iter().find(|v| v.version_id == id))
+            {
+                Some(version) => VersionsMsg::ViewPayload {
+                    secret: self.secret.clone(),
+                    version: version.clone(),
+                }
+                .into(),
+                None => EventResult::Consumed,
+            };
+        }
+        match key.code {
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => return EventResult::Consumed,
+        }
+        if let Some(id) = resolve_version(self.table.items(), input) {
+            self.table.select_where(|v| v.version_id == id);
+        }
+        EventResult::Consumed
+    }
 }
 
 impl Screen for VersionListScreen {
     type Output = SecretManagerMsg;
 
     fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if self.jump.is_some() {
+            return Ok(self.handle_jump_key(key));
+        }
+
         // Delegate to table first (handles search mode, navigation, etc.)
         let result = self.table.handle_key(key)?;
         if let EventResult::Event(TableEvent::Activated(version)) = result {
@@ -165,9 +252,27 @@ impl Screen for VersionListScreen {
         }
 
         // Handle local shortcuts only if table didn't consume the key
+        if self.resolver.matches_versions(&key, VersionsAction::Jump) {
+            self.jump = Some(String::new());
+            return Ok(EventResult::Consumed);
+        }
         if self.resolver.matches_versions(&key, VersionsAction::Reload) {
             return Ok(VersionsMsg::Load(self.secret.clone()).into());
         }
+        if self
+            .resolver
+            .matches_versions(&key, VersionsAction::OnlyEnabled)
+        {
+            self.table.toggle_filter(ONLY_ENABLED);
+            return Ok(EventResult::Consumed);
+        }
+        if self
+            .resolver
+            .matches_versions(&key, VersionsAction::HideDestroyed)
+        {
+            self.table.toggle_filter(HIDE_DESTROYED);
+            return Ok(EventResult::Consumed);
+        }
         if self.resolver.matches_versions(&key, VersionsAction::Add) {
             return Ok(VersionsMsg::StartCreation(self.secret.clone()).into());
         }
@@ -205,12 +310,57 @@ impl Screen for VersionListScreen {
             }
             .into());
         }
+        if self.resolver.matches_versions(&key, VersionsAction::Save)
+            && let Some(v) = self.table.selected_item()
+            && v.state.contains("Enabled")
+        {
+            return Ok(PayloadMsg::StartSave {
+                secret: self.secret.clone(),
+                version: Some(v.clone()),
+            }
+            .into());
+        }
+        if self
+            .resolver
+            .matches_versions(&key, VersionsAction::Terraform)
+            && let Some(v) = self.table.selected_item()
+        {
+            return Ok(TerraformMsg::CopyVersion {
+                secret: self.secret.clone(),
+                version: v.clone(),
+            }
+            .into());
+        }
+        if self
+            .resolver
+            .matches_versions(&key, VersionsAction::OpenConsole)
+        {
+            return Ok(SecretsMsg::OpenConsole(self.secret.clone()).into());
+        }
 
         Ok(EventResult::Ignored)
     }
 
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
     fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
-        self.table.render(frame, area, theme);
+        let Some(input) = &self.jump else {
+            self.table.render(frame, area, theme);
+            return;
+        };
+        let [table_area, prompt_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+        // Red while the input names no version
+        let style = if input.is_empty() || resolve_version(self.table.items(), input).is_some() {
+            Style::default().fg(theme.yellow())
+        } else {
+            Style::default().fg(theme.red())
+        };
+        let prompt = Paragraph::new(format!(":{input}_")).style(style);
+        frame.render_widget(prompt, prompt_area);
+        self.table.render(frame, table_area, theme);
     }
 
     fn keybindings(&self) -> Vec<Keybinding> {
@@ -224,6 +374,10 @@ impl Screen for VersionListScreen {
                 "Add version",
             ),
             Keybinding::hint(self.resolver.display_search(SearchAction::Toggle), "Search"),
+            Keybinding::new(
+                self.resolver.display_versions(VersionsAction::Jump),
+                "Jump to version",
+            ),
             Keybinding::new(
                 self.resolver.display_versions(VersionsAction::Disable),
                 "Disable",
@@ -236,19 +390,48 @@ impl Screen for VersionListScreen {
                 self.resolver.display_versions(VersionsAction::Destroy),
                 "Destroy",
             ),
+            Keybinding::new(
+                self.resolver.display_versions(VersionsAction::Save),
+                "Save to file",
+            ),
+            Keybinding::new(
+                self.resolver.display_versions(VersionsAction::Terraform),
+                "Copy Terraform",
+            ),
+            Keybinding::new(
+                self.resolver.display_versions(VersionsAction::OpenConsole),
+                "Open in console",
+            ),
+            Keybinding::new(
+                self.resolver.display_versions(VersionsAction::OnlyEnabled),
+                "Enabled only",
+            ),
+            Keybinding::new(
+                self.resolver
+                    .display_versions(VersionsAction::HideDestroyed),
+                "Hide destroyed",
+            ),
             Keybinding::new(
                 self.resolver.display_versions(VersionsAction::Reload),
                 "Reload",
             ),
         ]
     }
+
+    fn action_context(&self) -> ActionContext {
+        let ctx = ActionContext::screen("versions").with_var("name", &self.secret.name);
+        match self.table.selected_item() {
+            Some(version) => ctx.with_var("version", &version.version_id),
+            None => ctx,
+        }
+    }
 }
 
 // === Dialogs ===
 
 pub struct CreateVersionDialog {
     secret: Secret,
-    input: TextInput,
+    input: TextArea,
     _resolver: Arc<KeyResolver>,
 }
 
@@ -256,7 +439,7 @@ impl CreateVersionDialog {
     pub fn new(secret: Secret, resolver: Arc<KeyResolver>) -> Self {
         Self {
             secret,
-            input: TextInput::new("New Version Payload"),
+            input: TextArea::new("New Version Payload").with_generator(),
             _resolver: resolver,
         }
     }
@@ -267,14 +450,14 @@ impl Modal for CreateVersionDialog {
 
     fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
         Ok(match self.input.handle_key(key)? {
-            EventResult::Event(TextInputEvent::Submitted(payload)) if !payload.is_empty() => {
+            EventResult::Event(TextAreaEvent::Submitted(payload)) if !payload.is_empty() => {
                 VersionsMsg::Create {
                     secret: self.secret.clone(),
                     payload,
                 }
                 .into()
             }
-            EventResult::Event(TextInputEvent::Cancelled) => {
+            EventResult::Event(TextAreaEvent::Cancelled) => {
                 SecretManagerMsg::DialogCancelled.into()
             }
             // Empty submission
@@ -282,6 +465,10 @@ impl Modal for CreateVersionDialog {
         })
     }
 
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.input.handle_paste(text)?.consumed_only())
+    }
+
     fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
         self.input.render(frame, area, theme);
     }
@@ -305,7 +492,7 @@ impl DestroyVersionDialog {
         )
         .with_title("Destroy Version")
         .with_confirm_text("Destroy")
-        .danger();
+        .irreversible();
 
         Self {
             secret,
@@ -356,8 +543,8 @@ pub(super) fn update(state: &mut SecretManager, msg: VersionsMsg) -> Result<Serv
             state.display_loading_spinner("Loading versions...");
 
             Ok(FetchVersionsCmd {
+                client: state.get_client_for(&secret)?,
                 secret,
-                client: state.get_client()?,
                 tx: state.get_msg_sender(),
             }
             .into())
@@ -385,16 +572,25 @@ pub(super) fn update(state: &mut SecretManager, msg: VersionsMsg) -> Result<Serv
             state.invalidate_versions_cache(&secret);
 
             Ok(AddVersionCmd {
+                client: state.get_client_for(&secret)?,
                 secret,
                 payload,
-                client: state.get_client()?,
                 tx: state.get_msg_sender(),
             }
             .into())
         }
 
+        VersionsMsg::Disabled { secret, version } => {
+            state.record_undo(UndoEntry::Disable {
+                secret: secret.clone(),
+                version,
+            });
+            state.pop_view();
+            state.queue(VersionsMsg::Load(secret).into());
+            Ok(ServiceMsg::Idle)
+        }
+
         VersionsMsg::Created { secret }
-        | VersionsMsg::Disabled { secret }
         | VersionsMsg::Enabled { secret }
         | VersionsMsg::Destroyed { secret } => {
             state.pop_view();
@@ -407,9 +603,9 @@ pub(super) fn update(state: &mut SecretManager, msg: VersionsMsg) -> Result<Serv
             state.invalidate_versions_cache(&secret);
 
             Ok(DisableVersionCmd {
+                client: state.get_client_for(&secret)?,
                 secret,
                 version,
-                client: state.get_client()?,
                 tx: state.get_msg_sender(),
             }
             .into())
@@ -420,9 +616,9 @@ pub(super) fn update(state: &mut SecretManager, msg: VersionsMsg) -> Result<Serv
             state.invalidate_versions_cache(&secret);
 
             Ok(EnableVersionCmd {
+                client: state.get_client_for(&secret)?,
                 secret,
                 version,
-                client: state.get_client()?,
                 tx: state.get_msg_sender(),
             }
             .into())
@@ -443,9 +639,9 @@ pub(super) fn update(state: &mut SecretManager, msg: VersionsMsg) -> Result<Serv
             state.invalidate_versions_cache(&secret);
 
             Ok(DestroyVersionCmd {
+                client: state.get_client_for(&secret)?,
                 secret,
                 version,
-                client: state.get_client()?,
                 tx: state.get_msg_sender(),
             }
             .into())
@@ -466,6 +662,7 @@ pub(super) fn update(state: &mut SecretManager, msg: VersionsMsg) -> Result<Serv
 
 // === Commands ===
 
+#[derive(Clone)]
 struct FetchVersionsCmd {
     client: SecretManagerClient,
     secret: Secret,
@@ -478,6 +675,14 @@ impl Command for FetchVersionsCmd {
         format!("Loading '{}' versions", self.secret.name)
     }
 
+    fn resource(&self) -> Option<String> {
+        Some(self.secret.name.clone())
+    }
+
+    fn retry(&self) -> Option<Box<dyn Command>> {
+        Some(Box::new(self.clone()))
+    }
+
     async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
         let versions = self.client.list_versions(&self.secret.name).await?;
         self.tx.send(
@@ -504,6 +709,14 @@ impl Command for AddVersionCmd {
         format!("Adding version to '{}'", self.secret.name)
     }
 
+    fn mutates(&self) -> bool {
+        true
+    }
+
+    fn resource(&self) -> Option<String> {
+        Some(self.secret.name.clone())
+    }
+
     async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
         self.client
             .add_secret_version(&self.secret.name, self.payload.as_bytes())
@@ -534,6 +747,14 @@ impl Command for DisableVersionCmd {
         )
     }
 
+    fn mutates(&self) -> bool {
+        true
+    }
+
+    fn resource(&self) -> Option<String> {
+        Some(self.secret.name.clone())
+    }
+
     async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
         self.client
             .disable_version(&self.secret.name, &self.version.version_id)
@@ -541,6 +762,7 @@ impl Command for DisableVersionCmd {
         self.tx.send(
             VersionsMsg::Disabled {
                 secret: self.secret,
+                version: self.version,
             }
             .into(),
         )?;
@@ -564,6 +786,14 @@ impl Command for EnableVersionCmd {
         )
     }
 
+    fn mutates(&self) -> bool {
+        true
+    }
+
+    fn resource(&self) -> Option<String> {
+        Some(self.secret.name.clone())
+    }
+
     async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
         self.client
             .enable_version(&self.secret.name, &self.version.version_id)
@@ -594,6 +824,14 @@ impl Command for DestroyVersionCmd {
         )
     }
 
+    fn mutates(&self) -> bool {
+        true
+    }
+
+    fn resource(&self) -> Option<String> {
+        Some(self.secret.name.clone())
+    }
+
     async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
         self.client
             .destroy_version(&self.secret.name, &self.version.version_id)
@@ -607,3 +845,25 @@ impl Command for DestroyVersionCmd {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_version() {
+        let versions: Vec<SecretVersion> = ["3", "12", "7"]
+            .into_iter()
+            .map(|id| SecretVersion {
+                version_id: id.to_string(),
+                state: "Enabled".to_string(),
+                created_at: String::new(),
+            })
+            .collect();
+        assert_eq!(resolve_version(&versions, "7").as_deref(), Some("7"));
+        assert_eq!(resolve_version(&versions, "007").as_deref(), Some("7"));
+        assert_eq!(resolve_version(&versions, "latest").as_deref(), Some("12"));
+        assert_eq!(resolve_version(&versions, "4"), None);
+        assert_eq!(resolve_version(&versions, "1x"), None);
+    }
+}
@@ -0,0 +1,457 @@
+//! IAM service accounts, browsed with the generic resource browser.
+//!
+//! There is no generated Rust client for the IAM admin API in use, so this
+//! calls the v1 REST endpoint directly with the context's credentials.
+
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex, PoisonError};
+
+use async_trait::async_trait;
+use color_eyre::Result;
+use google_cloud_auth::credentials::Credentials;
+use ratatui::layout::Constraint;
+use ratatui::style::Style;
+use ratatui::widgets::Cell;
+use serde::{Deserialize, Serialize};
+
+use crate::Theme;
+use crate::context::{AuthMethod, CloudContext, GcpContext};
+use crate::error::Error;
+use crate::provider::Provider;
+use crate::provider::gcp::console::service_account_url;
+use crate::provider::gcp::send;
+use crate::search::Matcher;
+use crate::service::{ResourceField, ResourceKind};
+use crate::ui::{ColumnDef, TableRow};
+
+const IAM_API: &str = "https://iam.googleapis.com/v1";
+
+/// Accounts of a demo project, shared by every client opened for it.
+type DemoAccounts = Arc<Mutex<Vec<ServiceAccount>>>;
+
+/// Demo projects opened in this session, by project ID.
+static DEMO_PROJECTS: LazyLock<Mutex<HashMap<String, DemoAccounts>>> =
+    LazyLock::new(Mutex::default);
+
+// === Models ===
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceAccount {
+    pub email: String,
+    #[serde(default)]
+    pub display_name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub unique_id: String,
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+impl ServiceAccount {
+    const fn status(&self) -> &'static str {
+        if self.disabled { "Disabled" } else { "Enabled" }
+    }
+}
+
+impl TableRow for ServiceAccount {
+    fn columns() -> &'static [ColumnDef] {
+        static COLUMNS: &[ColumnDef] = &[
+            ColumnDef::new("Email", Constraint::Min(30)),
+            ColumnDef::new("Display name", Constraint::Min(20)),
+            ColumnDef::new("Status", Constraint::Length(10)),
+            ColumnDef::new("Description", Constraint::Min(20)),
+        ];
+        COLUMNS
+    }
+
+    fn render_cells(&self, theme: &Theme) -> Vec<Cell<'static>> {
+        let status_color = if self.disabled {
+            theme.overlay1()
+        } else {
+            theme.green()
+        };
+        vec![
+            Cell::from(self.email.clone()),
+            Cell::from(self.display_name.clone()),
+            Cell::from(self.status()).style(Style::default().fg(status_color)),
+            Cell::from(self.description.clone()),
+        ]
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        let matcher = Matcher::new();
+        matcher.matches(&self.email, query)
+            || matcher.matches(&self.display_name, query)
+            || matcher.matches(&self.description, query)
+    }
+}
+
+// === Resource ===
+
+#[derive(Debug)]
+pub struct ServiceAccounts;
+
+#[async_trait]
+impl ResourceKind for ServiceAccounts {
+    type Item = ServiceAccount;
+    type Client = ServiceAccountsClient;
+
+    const NAME: &'static str = "Service Accounts";
+    const NOUN: &'static str = "service account";
+    const SERVICE_KEY: &'static str = "service-accounts";
+    const DESCRIPTION: &'static str = "Manage identities for workloads and automation";
+    const PROVIDER: Provider = Provider::Gcp;
+
+    const CREATE_FIELDS: &'static [ResourceField] = &[
+        ResourceField::required("Account ID", "my-service-account"),
+        ResourceField::optional("Display name", "My service account"),
+        ResourceField::optional("Description", "What the account is used for"),
+    ];
+
+    fn id(item: &ServiceAccount) -> String {
+        item.email.clone()
+    }
+
+    async fn connect(context: &CloudContext) -> Result<ServiceAccountsClient> {
+        let CloudContext::Gcp(context) = context;
+        ServiceAccountsClient::new(context)
+    }
+
+    async fn list(client: &ServiceAccountsClient) -> Result<Vec<ServiceAccount>> {
+        client.list().await
+    }
+
+    async fn get(client: &ServiceAccountsClient, id: &str) -> Result<ServiceAccount> {
+        client.get(id).await
+    }
+
+    async fn create(client: &ServiceAccountsClient, values: Vec<String>) -> Result<ServiceAccount> {
+        let mut values = values.into_iter();
+        let account = NewServiceAccount {
+            account_id: values.next().unwrap_or_default(),
+            display_name: values.next().unwrap_or_default(),
+            description: values.next().unwrap_or_default(),
+        };
+        client.create(account).await
+    }
+
+    async fn delete(client: &ServiceAccountsClient, item: &ServiceAccount) -> Result<()> {
+        client.delete(&item.email).await
+    }
+
+    fn details(item: &ServiceAccount) -> Vec<(&'static str, String)> {
+        vec![
+            ("Email", item.email.clone()),
+            ("Display name", item.display_name.clone()),
+            ("Description", item.description.clone()),
+            ("Unique ID", item.unique_id.clone()),
+            ("Status", item.status().to_string()),
+        ]
+    }
+
+    fn console_url(context: &CloudContext, item: &ServiceAccount) -> Option<String> {
+        let CloudContext::Gcp(context) = context;
+        Some(service_account_url(&context.project_id, &item.email))
+    }
+
+    fn validate(index: usize, value: &str) -> std::result::Result<(), String> {
+        if index == 0 {
+            validate_account_id(value)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Account IDs are 6-30 lowercase letters, digits and hyphens, starting with
+/// a letter and not ending with a hyphen.
+fn validate_account_id(id: &str) -> std::result::Result<(), String> {
+    if !(6..=30).contains(&id.len()) {
+        return Err("Account ID must be 6 to 30 characters".to_string());
+    }
+    if !id
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return Err("Use lowercase letters, digits and hyphens only".to_string());
+    }
+    if !id.starts_with(|c: char| c.is_ascii_lowercase()) || id.ends_with('-') {
+        return Err("Start with a letter and do not end with a hyphen".to_string());
+    }
+    Ok(())
+}
+
+// === Client ===
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewServiceAccount {
+    account_id: String,
+    display_name: String,
+    description: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct ServiceAccountsClient {
+    backend: Backend,
+    project_id: String,
+}
+
+#[derive(Clone, Debug)]
+enum Backend {
+    Gcp {
+        http: reqwest::Client,
+        credentials: Credentials,
+    },
+    /// Seeded in-memory accounts for `--demo`.
+    Demo(DemoAccounts),
+}
+
+impl ServiceAccountsClient {
+    pub fn new(context: &GcpContext) -> Result<Self> {
+        let backend = if matches!(context.auth, AuthMethod::Demo) {
+            Backend::Demo(open_demo(&context.project_id))
+        } else {
+            Backend::Gcp {
+                http: reqwest::Client::new(),
+                credentials: context.create_credentials()?,
+            }
+        };
+        Ok(Self {
+            backend,
+            project_id: context.project_id.clone(),
+        })
+    }
+
+    fn accounts_url(&self) -> String {
+        format!("{IAM_API}/projects/{}/serviceAccounts", self.project_id)
+    }
+
+    pub async fn list(&self) -> Result<Vec<ServiceAccount>> {
+        let (http, credentials) = match &self.backend {
+            Backend::Gcp { http, credentials } => (http, credentials),
+            Backend::Demo(accounts) => return Ok(lock(accounts).clone()),
+        };
+
+        let mut accounts = Vec::new();
+        let mut page_token = String::new();
+        loop {
+            let request = http
+                .get(self.accounts_url())
+                .query(&[("pageSize", "100"), ("pageToken", &page_token)]);
+            let page: ListServiceAccountsResponse =
+                send("IAM", request, credentials).await?.json().await?;
+            accounts.extend(page.accounts);
+            match page.next_page_token {
+                Some(token) if !token.is_empty() => page_token = token,
+                _ => break,
+            }
+        }
+        Ok(accounts)
+    }
+
+    pub async fn get(&self, email: &str) -> Result<ServiceAccount> {
+        match &self.backend {
+            Backend::Gcp { http, credentials } => {
+                let request = http.get(format!("{}/{email}", self.accounts_url()));
+                Ok(send("IAM", request, credentials).await?.json().await?)
+            }
+            Backend::Demo(accounts) => lock(accounts)
+                .iter()
+                .find(|account| account.email == email)
+                .cloned()
+                .ok_or_else(|| not_found(email).into()),
+        }
+    }
+
+    pub async fn create(&self, account: NewServiceAccount) -> Result<ServiceAccount> {
+        match &self.backend {
+            Backend::Gcp { http, credentials } => {
+                let request = http
+                    .post(self.accounts_url())
+                    .json(&CreateServiceAccountRequest {
+                        account_id: &account.account_id,
+                        service_account: ServiceAccountFields {
+                            display_name: &account.display_name,
+                            description: &account.description,
+                        },
+                    });
+                Ok(send("IAM", request, credentials).await?.json().await?)
+            }
+            Backend::Demo(accounts) => {
+                let email = format!(
+                    "{}@{}.iam.gserviceaccount.com",
+                    account.account_id, self.project_id
+                );
+                let mut accounts = lock(accounts);
+                if accounts.iter().any(|existing| existing.email == email) {
+                    return Err(Error::Internal(format!(
+                        "Service account '{email}' already exists"
+                    ))
+                    .into());
+                }
+                let created = ServiceAccount {
+                    email,
+                    display_name: account.display_name,
+                    description: account.description,
+                    unique_id: demo_unique_id(accounts.len()),
+                    disabled: false,
+                };
+                accounts.push(created.clone());
+                drop(accounts);
+                Ok(created)
+            }
+        }
+    }
+
+    pub async fn delete(&self, email: &str) -> Result<()> {
+        match &self.backend {
+            Backend::Gcp { http, credentials } => {
+                let request = http.delete(format!("{}/{email}", self.accounts_url()));
+                send("IAM", request, credentials).await?;
+                Ok(())
+            }
+            Backend::Demo(accounts) => {
+                let mut accounts = lock(accounts);
+                let count = accounts.len();
+                accounts.retain(|account| account.email != email);
+                let removed = accounts.len() < count;
+                drop(accounts);
+                if !removed {
+                    return Err(not_found(email).into());
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListServiceAccountsResponse {
+    #[serde(default)]
+    accounts: Vec<ServiceAccount>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateServiceAccountRequest<'a> {
+    account_id: &'a str,
+    service_account: ServiceAccountFields<'a>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ServiceAccountFields<'a> {
+    display_name: &'a str,
+    description: &'a str,
+}
+
+// === Demo ===
+
+/// The demo accounts of a project, seeded on first use.
+fn open_demo(project_id: &str) -> DemoAccounts {
+    DEMO_PROJECTS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .entry(project_id.to_string())
+        .or_insert_with(|| Arc::new(Mutex::new(demo_accounts(project_id))))
+        .clone()
+}
+
+fn demo_accounts(project_id: &str) -> Vec<ServiceAccount> {
+    let account = |email: String, display_name: &str, description: &str, disabled| ServiceAccount {
+        email,
+        display_name: display_name.to_string(),
+        description: description.to_string(),
+        unique_id: String::new(),
+        disabled,
+    };
+    let mut accounts = vec![
+        account(
+            format!("{project_id}@appspot.gserviceaccount.com"),
+            "App Engine default service account",
+            "",
+            false,
+        ),
+        account(
+            format!("ci-deployer@{project_id}.iam.gserviceaccount.com"),
+            "CI deployer",
+            "Deploys releases from the build pipeline",
+            false,
+        ),
+        account(
+            format!("legacy-cron@{project_id}.iam.gserviceaccount.com"),
+            "Legacy cron",
+            "Replaced by Cloud Scheduler",
+            true,
+        ),
+    ];
+    for (index, account) in accounts.iter_mut().enumerate() {
+        account.unique_id = demo_unique_id(index);
+    }
+    accounts
+}
+
+fn demo_unique_id(index: usize) -> String {
+    format!("1048576{index:014}")
+}
+
+fn lock(accounts: &Mutex<Vec<ServiceAccount>>) -> std::sync::MutexGuard<'_, Vec<ServiceAccount>> {
+    accounts.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn not_found(email: &str) -> Error {
+    Error::NotFound(format!("Service account '{email}' not found"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_account_id() {
+        assert!(validate_account_id("ci-deployer").is_ok());
+        assert!(validate_account_id("short").is_err());
+        assert!(validate_account_id("Upper-case").is_err());
+        assert!(validate_account_id("1-starts-with-digit").is_err());
+        assert!(validate_account_id("ends-with-hyphen-").is_err());
+    }
+
+    #[test]
+    fn test_demo_lifecycle() {
+        let client = ServiceAccountsClient {
+            backend: Backend::Demo(Arc::new(Mutex::new(demo_accounts("demo")))),
+            project_id: "demo".to_string(),
+        };
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let values = vec![
+                "backup-job".to_string(),
+                "Backups".to_string(),
+                String::new(),
+            ];
+            let created = ServiceAccounts::create(&client, values.clone())
+                .await
+                .unwrap();
+            assert_eq!(created.email, "backup-job@demo.iam.gserviceaccount.com");
+            assert!(ServiceAccounts::create(&client, values).await.is_err());
+            assert_eq!(
+                ServiceAccounts::get(&client, &created.email).await.unwrap(),
+                created
+            );
+
+            ServiceAccounts::delete(&client, &created).await.unwrap();
+            let error = ServiceAccounts::get(&client, &created.email)
+                .await
+                .unwrap_err();
+            assert!(matches!(Error::from_report(&error), Error::NotFound(_)));
+            assert_eq!(ServiceAccounts::list(&client).await.unwrap().len(), 3);
+        });
+    }
+}
@@ -0,0 +1,191 @@
+//! Out-of-tree service plugins.
+//!
+//! Plugins live in `<config dir>/plugins/<name>/` and are described by a
+//! `plugin.toml` manifest:
+//!
+//! ```toml
+//! command = "./lazycloud-storage"   # relative to the plugin directory
+//! args = []
+//!
+//! [[services]]
+//! key = "storage"
+//! name = "Cloud Storage"
+//! description = "Browse buckets and objects"
+//! provider = "gcp"
+//!
+//! [[services.actions]]
+//! name = "open_console"
+//! description = "Open in console"
+//! key = "o"
+//! ```
+//!
+//! Each service shows up in the service selector like a built-in one. When
+//! it is used, lazycloud starts the command and talks to it with
+//! line-delimited JSON-RPC 2.0 over stdin/stdout:
+//!
+//! - `initialize` with `{ "service": key, "context": context }` once after start.
+//! - `view` with `{ "path": [ids] }`, where the path is the list of row ids
+//!   the user opened, starting empty. The result is either a table,
+//!   `{ "title", "columns": [..], "rows": [{ "id", "cells": [..] }] }`,
+//!   or a text view, `{ "title", "text" }`.
+//! - `action` with `{ "action": name, "path": [ids], "id": id }` when the key
+//!   of one of the service's actions is pressed on a table, where `id` is the
+//!   selected row or `null`. The result is `null` or a view to show on top.
+//!
+//! The keys of a service's actions can be changed under
+//! `[keybindings.services."<provider>.<key>"]`, e.g. `"gcp.storage"`.
+
+mod client;
+mod service;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use serde::Deserialize;
+use tracing::{debug, info, warn};
+
+use crate::config::config_dir;
+use crate::config::key::KeyBinding;
+use crate::provider::Provider;
+use crate::provider::plugin::service::PluginProvider;
+use crate::registry::{ActionDescriptor, ServiceRegistry};
+
+const PLUGINS_DIR: &str = "plugins";
+const MANIFEST_FILE: &str = "plugin.toml";
+
+#[derive(Debug, Deserialize)]
+struct PluginManifest {
+    command: PathBuf,
+    #[serde(default)]
+    args: Vec<String>,
+    services: Vec<ServiceManifest>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ServiceManifest {
+    key: String,
+    name: String,
+    #[serde(default)]
+    description: String,
+    provider: String,
+    #[serde(default)]
+    actions: Vec<ActionManifest>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ActionManifest {
+    name: String,
+    #[serde(default)]
+    description: String,
+    key: KeyBinding,
+}
+
+/// Register the services of every plugin found in the plugins directory.
+///
+/// Broken manifests are logged and skipped so one plugin cannot prevent
+/// lazycloud from starting.
+pub fn register(registry: &mut ServiceRegistry) {
+    let Some(dir) = config_dir().map(|dir| dir.join(PLUGINS_DIR)) else {
+        return;
+    };
+    let Ok(entries) = fs::read_dir(&dir) else {
+        debug!(path = %dir.display(), "No plugins directory");
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.join(MANIFEST_FILE).is_file() {
+            continue;
+        }
+        match load_plugin(&path) {
+            Ok(providers) => {
+                info!(path = %path.display(), count = providers.len(), "Loaded plugin");
+                for provider in providers {
+                    registry.register(provider);
+                }
+            }
+            Err(err) => warn!(path = %path.display(), %err, "Failed to load plugin"),
+        }
+    }
+}
+
+fn load_plugin(dir: &Path) -> Result<Vec<PluginProvider>> {
+    let content = fs::read_to_string(dir.join(MANIFEST_FILE))?;
+    let manifest: PluginManifest = toml::from_str(&content)?;
+    let command = dir.join(&manifest.command);
+
+    manifest
+        .services
+        .into_iter()
+        .map(|service| {
+            let provider = parse_provider(&service.provider)?;
+            let actions = service
+                .actions
+                .into_iter()
+                .map(|action| ActionDescriptor {
+                    description: if action.description.is_empty() {
+                        action.name.clone()
+                    } else {
+                        action.description
+                    },
+                    name: action.name,
+                    default: action.key,
+                })
+                .collect();
+            Ok(PluginProvider::new(
+                provider,
+                service.key,
+                service.name,
+                service.description,
+                command.clone(),
+                manifest.args.clone(),
+                actions,
+            ))
+        })
+        .collect()
+}
+
+fn parse_provider(id: &str) -> Result<Provider> {
+    [Provider::Aws, Provider::Azure, Provider::Gcp]
+        .into_iter()
+        .find(|provider| provider.id().eq_ignore_ascii_case(id))
+        .ok_or_else(|| eyre!("Unknown provider '{id}'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest: PluginManifest = toml::from_str(
+            r#"
+            command = "./bin"
+
+            [[services]]
+            key = "storage"
+            name = "Cloud Storage"
+            provider = "gcp"
+
+            [[services.actions]]
+            name = "open_console"
+            key = "o"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(manifest.command, PathBuf::from("./bin"));
+        assert!(manifest.args.is_empty());
+        assert_eq!(manifest.services[0].key, "storage");
+        assert_eq!(
+            parse_provider(&manifest.services[0].provider).unwrap(),
+            Provider::Gcp
+        );
+        assert!(parse_provider("oracle").is_err());
+        let action = &manifest.services[0].actions[0];
+        assert_eq!(action.name, "open_console");
+        assert_eq!(action.key.display(), "o");
+    }
+}
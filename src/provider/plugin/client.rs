@@ -0,0 +1,249 @@
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command as Process};
+use tokio::sync::Mutex;
+
+use crate::context::CloudContext;
+
+/// How long a plugin may take to answer a request before it is killed.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// What a plugin returns for a `view` request.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum PluginView {
+    Table {
+        title: String,
+        columns: Vec<String>,
+        rows: Vec<PluginRow>,
+    },
+    Text {
+        title: String,
+        text: String,
+    },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PluginRow {
+    pub id: String,
+    pub cells: Vec<String>,
+}
+
+struct Connection {
+    // Also killed when the last client handle is dropped
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+/// JSON-RPC connection to a running plugin process.
+///
+/// Requests are serialized: the plugin only ever sees one at a time.
+#[derive(Clone)]
+pub struct PluginClient {
+    name: String,
+    connection: Arc<Mutex<Connection>>,
+    timeout: Duration,
+}
+
+impl std::fmt::Debug for PluginClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PluginClient")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PluginClient {
+    /// Start the plugin and initialize it for a service and context.
+    pub async fn spawn(
+        command: &Path,
+        args: &[String],
+        service: &str,
+        context: &CloudContext,
+    ) -> Result<Self> {
+        let mut child = Process::new(command)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            // Anything the plugin prints to stderr would corrupt the TUI
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| eyre!("Failed to start plugin {}: {err}", command.display()))?;
+
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| eyre!("Plugin stdin unavailable"))?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| eyre!("Plugin stdout unavailable"))?;
+
+        let client = Self {
+            name: service.to_string(),
+            connection: Arc::new(Mutex::new(Connection {
+                child,
+                stdin,
+                stdout: BufReader::new(stdout),
+                next_id: 1,
+            })),
+            timeout: REQUEST_TIMEOUT,
+        };
+        client
+            .call(
+                "initialize",
+                json!({ "service": service, "context": context }),
+            )
+            .await?;
+        Ok(client)
+    }
+
+    /// Fetch the view for a path of opened row ids.
+    pub async fn view(&self, path: &[String]) -> Result<PluginView> {
+        let result = self.call("view", json!({ "path": path })).await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Run a service action on the selected row, if any, of the view at `path`.
+    ///
+    /// Returns the view to show on top of the current one, if the plugin
+    /// sent one.
+    pub async fn action(
+        &self,
+        action: &str,
+        path: &[String],
+        id: Option<&str>,
+    ) -> Result<Option<PluginView>> {
+        let result = self
+            .call(
+                "action",
+                json!({ "action": action, "path": path, "id": id }),
+            )
+            .await?;
+        Ok(serde_json::from_value(result)?)
+    }
+
+    /// Send a request and wait for its response. A plugin that does not
+    /// answer in time is killed, so it cannot hold up later requests.
+    async fn call(&self, method: &str, params: Value) -> Result<Value> {
+        let name = &self.name;
+        let mut connection = self.connection.lock().await;
+        let response =
+            tokio::time::timeout(self.timeout, connection.request(name, method, params)).await;
+        if response.is_err() {
+            let _ = connection.child.start_kill();
+        }
+        drop(connection);
+        response.unwrap_or_else(|_| {
+            Err(eyre!(
+                "Plugin '{name}' did not answer '{method}' within {}s and was stopped",
+                self.timeout.as_secs()
+            ))
+        })
+    }
+}
+
+impl Connection {
+    async fn request(&mut self, name: &str, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let request = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        let mut line = serde_json::to_string(&request)?;
+        line.push('\n');
+        self.stdin.write_all(line.as_bytes()).await?;
+        self.stdin.flush().await?;
+
+        loop {
+            let mut line = String::new();
+            if self.stdout.read_line(&mut line).await? == 0 {
+                return Err(eyre!("Plugin '{name}' exited unexpectedly"));
+            }
+            let Ok(response) = serde_json::from_str::<Value>(&line) else {
+                continue;
+            };
+            // Skip notifications and stale responses
+            if response.get("id").and_then(Value::as_u64) != Some(id) {
+                continue;
+            }
+            if let Some(error) = response.get("error") {
+                let message = error
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or("unknown error");
+                return Err(eyre!("Plugin '{name}': {message}"));
+            }
+            return Ok(response.get("result").cloned().unwrap_or(Value::Null));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::{AuthMethod, GcpContext};
+
+    /// Answers `view` with a text view, never answers `action` and
+    /// acknowledges anything else.
+    const STUB_PLUGIN: &str = r#"
+        while read -r line; do
+            id=$(echo "$line" | sed 's/.*"id":\([0-9]*\).*/\1/')
+            case "$line" in
+                *'"method":"view"'*)
+                    echo '{"jsonrpc":"2.0","method":"log","params":{}}'
+                    echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":{\"title\":\"Stub\",\"text\":\"hello\"}}" ;;
+                *'"method":"action"'*) exec sleep 60 ;;
+                *) echo "{\"jsonrpc\":\"2.0\",\"id\":$id,\"result\":null}" ;;
+            esac
+        done
+    "#;
+
+    async fn stub() -> PluginClient {
+        let context = CloudContext::Gcp(GcpContext {
+            display_name: "demo-dev".to_string(),
+            project_id: "acme-dev".to_string(),
+            account: "demo@acme.example".to_string(),
+            region: None,
+            zone: None,
+            auth: AuthMethod::Demo,
+            accounts: Vec::new(),
+            projects: Vec::new(),
+        });
+        let args = ["-c".to_string(), STUB_PLUGIN.to_string()];
+        PluginClient::spawn(Path::new("sh"), &args, "stub", &context)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_request_response() {
+        let client = stub().await;
+        let view = client.view(&["a".to_string()]).await.unwrap();
+        assert!(matches!(
+            view,
+            PluginView::Text { title, text } if title == "Stub" && text == "hello"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unanswered_request_stops_plugin() {
+        let client = PluginClient {
+            timeout: Duration::from_millis(200),
+            ..stub().await
+        };
+        let err = client.action("hang", &[], None).await.unwrap_err();
+        assert!(err.to_string().contains("did not answer 'action'"));
+        assert!(client.view(&[]).await.is_err());
+    }
+}
@@ -0,0 +1,534 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use color_eyre::Result;
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::widgets::Cell;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::Theme;
+use crate::app::AppMessage;
+use crate::commands::Command;
+use crate::config::{ActionContext, AppConfig, KeyResolver};
+use crate::context::CloudContext;
+use crate::provider::Provider;
+use crate::provider::plugin::client::{PluginClient, PluginRow, PluginView};
+use crate::registry::{ActionDescriptor, ServiceProvider, WarmClient};
+use crate::search::Matcher;
+use crate::service::{Service, ServiceMsg, ServiceShell, ServiceState};
+use crate::ui::{
+    ColumnDef,
+    Component,
+    EventResult,
+    Keybinding,
+    Screen,
+    Table,
+    TableEvent,
+    TableRow,
+    TextViewer,
+    detect_syntax,
+};
+
+// === Messages ===
+
+#[derive(Debug, Clone)]
+pub enum PluginMsg {
+    Initialize,
+    ClientInitialized(PluginClient),
+    Open(Vec<String>),
+    Loaded {
+        path: Vec<String>,
+        view: PluginView,
+    },
+    /// A service action was triggered on the table at `path`.
+    Action {
+        action: String,
+        path: Vec<String>,
+        id: Option<String>,
+    },
+    /// Starting the plugin or loading a view failed; the error is shown by the app.
+    Failed,
+}
+
+// === Provider ===
+
+/// A service backed by a plugin process.
+///
+/// There is no warm-up: the process only starts once the service is opened,
+/// not for every plugin when a context is selected.
+pub struct PluginProvider {
+    provider: Provider,
+    key: &'static str,
+    name: &'static str,
+    description: &'static str,
+    command: PathBuf,
+    args: Vec<String>,
+    actions: Vec<ActionDescriptor>,
+}
+
+impl PluginProvider {
+    pub fn new(
+        provider: Provider,
+        key: String,
+        name: String,
+        description: String,
+        command: PathBuf,
+        args: Vec<String>,
+        actions: Vec<ActionDescriptor>,
+    ) -> Self {
+        // Plugins are registered once and live for the rest of the program,
+        // so leaking their names satisfies the `&'static str` provider API.
+        Self {
+            provider,
+            key: Box::leak(key.into_boxed_str()),
+            name: Box::leak(name.into_boxed_str()),
+            description: Box::leak(description.into_boxed_str()),
+            command,
+            args,
+            actions,
+        }
+    }
+}
+
+impl ServiceProvider for PluginProvider {
+    fn provider(&self) -> Provider {
+        self.provider
+    }
+
+    fn service_key(&self) -> &'static str {
+        self.key
+    }
+
+    fn display_name(&self) -> &'static str {
+        self.name
+    }
+
+    fn description(&self) -> &'static str {
+        self.description
+    }
+
+    fn icon(&self) -> Option<&'static str> {
+        Some("⧉")
+    }
+
+    fn actions(&self) -> Vec<ActionDescriptor> {
+        self.actions.clone()
+    }
+
+    fn create_service(
+        &self,
+        ctx: &CloudContext,
+        config: Arc<AppConfig>,
+        resolver: Arc<KeyResolver>,
+        _client: Option<WarmClient>,
+    ) -> Box<dyn Service> {
+        let state = PluginState {
+            init: None,
+            name: self.name,
+            key: self.key,
+            client: None,
+            scope: self.action_scope(),
+            actions: self.actions.clone().into(),
+        };
+        let mut service = PluginService::new(state, config, resolver);
+        service.state_mut().init = Some(InitPluginCmd::new(
+            self,
+            ctx.clone(),
+            service.get_msg_sender(),
+        ));
+        Box::new(service)
+    }
+}
+
+// === Service ===
+
+type PluginService = ServiceShell<PluginState, PluginMsg>;
+
+struct PluginState {
+    init: Option<InitPluginCmd>,
+    name: &'static str,
+    key: &'static str,
+    client: Option<PluginClient>,
+    /// Scope of the service's actions in the keybindings config.
+    scope: String,
+    actions: Arc<[ActionDescriptor]>,
+}
+
+impl ServiceState<PluginMsg> for PluginState {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn service_key(&self) -> &'static str {
+        self.key
+    }
+
+    fn init(shell: &mut PluginService) {
+        shell.display_loading_spinner("Loading...");
+        shell.queue(PluginMsg::Initialize);
+    }
+
+    fn update(shell: &mut PluginService, msg: PluginMsg) -> Result<ServiceMsg> {
+        match msg {
+            PluginMsg::Initialize => Ok(shell
+                .state_mut()
+                .init
+                .take()
+                .map_or(ServiceMsg::Idle, Into::into)),
+            PluginMsg::ClientInitialized(client) => {
+                shell.state_mut().client = Some(client);
+                shell.queue(PluginMsg::Open(Vec::new()));
+                Ok(ServiceMsg::Idle)
+            }
+            PluginMsg::Open(path) => {
+                let client = shell
+                    .state()
+                    .client
+                    .clone()
+                    .ok_or_else(|| color_eyre::eyre::eyre!("Plugin not initialized"))?;
+                shell.display_loading_spinner("Loading...");
+                Ok(LoadViewCmd {
+                    client,
+                    path,
+                    tx: shell.get_msg_sender(),
+                }
+                .into())
+            }
+            PluginMsg::Loaded { path, view } => {
+                match view {
+                    PluginView::Table {
+                        title,
+                        columns,
+                        rows,
+                    } => {
+                        let actions = ServiceActions {
+                            scope: shell.state().scope.clone(),
+                            actions: shell.state().actions.clone(),
+                            resolver: shell.get_resolver(),
+                        };
+                        shell
+                            .push_view(PluginTableScreen::new(path, title, columns, rows, actions));
+                    }
+                    PluginView::Text { title, text } => {
+                        shell.push_view(PluginTextScreen::new(
+                            path,
+                            title,
+                            &text,
+                            shell.get_resolver(),
+                        ));
+                    }
+                }
+                Ok(ServiceMsg::Idle)
+            }
+            PluginMsg::Action { action, path, id } => {
+                let client = shell
+                    .state()
+                    .client
+                    .clone()
+                    .ok_or_else(|| color_eyre::eyre::eyre!("Plugin not initialized"))?;
+                Ok(RunActionCmd {
+                    client,
+                    action,
+                    path,
+                    id,
+                    tx: shell.get_msg_sender(),
+                }
+                .into())
+            }
+            PluginMsg::Failed => {
+                shell.hide_loading_spinner();
+                if !shell.has_view() {
+                    return Ok(ServiceMsg::Close);
+                }
+                Ok(ServiceMsg::Idle)
+            }
+        }
+    }
+}
+
+// === Screens ===
+
+impl TableRow for PluginRow {
+    fn columns() -> &'static [ColumnDef] {
+        // Columns come from the plugin, see `Table::with_columns`
+        &[]
+    }
+
+    fn render_cells(&self, _theme: &Theme) -> Vec<Cell<'static>> {
+        self.cells.iter().cloned().map(Cell::from).collect()
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        let matcher = Matcher::new();
+        self.cells.iter().any(|cell| matcher.matches(cell, query))
+    }
+}
+
+/// The service's own actions, available on its tables.
+struct ServiceActions {
+    scope: String,
+    actions: Arc<[ActionDescriptor]>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl ServiceActions {
+    fn find(&self, key: &KeyEvent) -> Option<&ActionDescriptor> {
+        self.actions.iter().find(|action| {
+            self.resolver
+                .matches_service(key, &self.scope, &action.name)
+        })
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        self.actions
+            .iter()
+            .map(|action| {
+                Keybinding::hint(
+                    self.resolver.display_service(&self.scope, &action.name),
+                    action.description.clone(),
+                )
+            })
+            .collect()
+    }
+}
+
+struct PluginTableScreen {
+    path: Vec<String>,
+    title: String,
+    table: Table<PluginRow>,
+    actions: ServiceActions,
+}
+
+impl PluginTableScreen {
+    fn new(
+        path: Vec<String>,
+        title: String,
+        columns: Vec<String>,
+        rows: Vec<PluginRow>,
+        actions: ServiceActions,
+    ) -> Self {
+        let columns = columns
+            .into_iter()
+            .map(|header| ColumnDef::dynamic(header, Constraint::Fill(1)))
+            .collect();
+        Self {
+            table: Table::new(rows, actions.resolver.clone())
+                .with_title(format!(" {title} "))
+                .with_columns(columns),
+            path,
+            title,
+            actions,
+        }
+    }
+}
+
+impl Screen for PluginTableScreen {
+    type Output = PluginMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> crate::ui::Result<EventResult<Self::Output>> {
+        let result = self.table.handle_key(key)?;
+        if let EventResult::Event(TableEvent::Activated(row)) = result {
+            let mut path = self.path.clone();
+            path.push(row.id);
+            return Ok(PluginMsg::Open(path).into());
+        }
+        if result.is_consumed() {
+            return Ok(EventResult::Consumed);
+        }
+        if let Some(action) = self.actions.find(&key) {
+            return Ok(PluginMsg::Action {
+                action: action.name.clone(),
+                path: self.path.clone(),
+                id: self.table.selected_item().map(|row| row.id.clone()),
+            }
+            .into());
+        }
+        Ok(EventResult::Ignored)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> crate::ui::Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.table.render(frame, area, theme);
+    }
+
+    fn breadcrumbs(&self) -> Vec<String> {
+        vec![self.title.clone()]
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        self.actions.keybindings()
+    }
+
+    fn action_context(&self) -> ActionContext {
+        let ctx = ActionContext::screen("table").with_var("path", self.path.join("/"));
+        match self.table.selected_item() {
+            Some(row) => ctx.with_var("id", row.id.clone()),
+            None => ctx,
+        }
+    }
+}
+
+struct PluginTextScreen {
+    path: Vec<String>,
+    title: String,
+    viewer: TextViewer,
+}
+
+impl PluginTextScreen {
+    fn new(path: Vec<String>, title: String, text: &str, resolver: Arc<KeyResolver>) -> Self {
+        let mut viewer = TextViewer::new(text, resolver).with_wrap(true);
+        if let Some(syntax) = detect_syntax(text) {
+            viewer = viewer.with_syntax(syntax);
+        }
+        viewer.set_title(format!(" {title} "));
+        Self {
+            path,
+            title,
+            viewer,
+        }
+    }
+}
+
+impl Screen for PluginTextScreen {
+    type Output = PluginMsg;
+
+    fn handle_key(&mut self, key: KeyEvent) -> crate::ui::Result<EventResult<Self::Output>> {
+        Ok(self.viewer.handle_key(key)?.consumed_only())
+    }
+
+    fn handle_paste(&mut self, text: &str) -> crate::ui::Result<EventResult<Self::Output>> {
+        Ok(self.viewer.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.viewer.render(frame, area, theme);
+    }
+
+    fn breadcrumbs(&self) -> Vec<String> {
+        vec![self.title.clone()]
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        self.viewer.keybindings()
+    }
+
+    fn action_context(&self) -> ActionContext {
+        ActionContext::screen("text").with_var("path", self.path.join("/"))
+    }
+}
+
+// === Commands ===
+
+struct InitPluginCmd {
+    command: PathBuf,
+    args: Vec<String>,
+    service: &'static str,
+    name: &'static str,
+    context: CloudContext,
+    tx: UnboundedSender<PluginMsg>,
+}
+
+impl InitPluginCmd {
+    fn new(
+        provider: &PluginProvider,
+        context: CloudContext,
+        tx: UnboundedSender<PluginMsg>,
+    ) -> Self {
+        Self {
+            command: provider.command.clone(),
+            args: provider.args.clone(),
+            service: provider.key,
+            name: provider.name,
+            context,
+            tx,
+        }
+    }
+}
+
+#[async_trait]
+impl Command for InitPluginCmd {
+    fn name(&self) -> String {
+        format!("Starting {}", self.name)
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        match PluginClient::spawn(&self.command, &self.args, self.service, &self.context).await {
+            Ok(client) => {
+                self.tx.send(PluginMsg::ClientInitialized(client))?;
+                Ok(())
+            }
+            Err(err) => {
+                self.tx.send(PluginMsg::Failed)?;
+                Err(err)
+            }
+        }
+    }
+}
+
+struct LoadViewCmd {
+    client: PluginClient,
+    path: Vec<String>,
+    tx: UnboundedSender<PluginMsg>,
+}
+
+#[async_trait]
+impl Command for LoadViewCmd {
+    fn name(&self) -> String {
+        "Loading plugin view".to_string()
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        match self.client.view(&self.path).await {
+            Ok(view) => {
+                self.tx.send(PluginMsg::Loaded {
+                    path: self.path,
+                    view,
+                })?;
+                Ok(())
+            }
+            Err(err) => {
+                self.tx.send(PluginMsg::Failed)?;
+                Err(err)
+            }
+        }
+    }
+}
+
+struct RunActionCmd {
+    client: PluginClient,
+    action: String,
+    path: Vec<String>,
+    id: Option<String>,
+    tx: UnboundedSender<PluginMsg>,
+}
+
+#[async_trait]
+impl Command for RunActionCmd {
+    fn name(&self) -> String {
+        format!("Running {}", self.action)
+    }
+
+    fn mutates(&self) -> bool {
+        true
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let view = self
+            .client
+            .action(&self.action, &self.path, self.id.as_deref())
+            .await?;
+        if let Some(view) = view {
+            self.tx.send(PluginMsg::Loaded {
+                path: self.path,
+                view,
+            })?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+//! Keeps secret values out of the log, error dialogs and crash reports.
+//!
+//! Services register the sensitive text of what they cache, e.g. a loaded
+//! payload, under its cache key and forget it once the entry is gone. Keys
+//! live in a scope per registrant, so a service dropping an entry leaves the
+//! same entry of another service, e.g. a parked one, masked.
+//! [`scrub`] masks every registered value in text about to leave the
+//! interface, along with its escaped form as `{:?}` would print it.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, PoisonError, RwLock};
+
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Shown in place of a masked value.
+const MASK: &str = "[redacted]";
+
+/// Values shorter than this are left alone, masking `true` or `1` would
+/// garble ordinary text.
+const MIN_LEN: usize = 4;
+
+/// Sensitive text by the scope and cache key holding it.
+#[derive(Default)]
+struct Sensitive {
+    values: HashMap<(String, String), String>,
+    /// Every value and its escaped form, longest first so a value containing
+    /// another is masked whole. Rebuilt when a value changes, as text is
+    /// scrubbed far more often, e.g. on every log line.
+    patterns: Vec<String>,
+}
+
+impl Sensitive {
+    fn rebuild(&mut self) {
+        let mut patterns: Vec<String> = self
+            .values
+            .values()
+            .flat_map(|value| [value.clone(), value.escape_debug().to_string()])
+            .collect();
+        patterns.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        patterns.dedup();
+        self.patterns = patterns;
+    }
+}
+
+static SENSITIVE: LazyLock<RwLock<Sensitive>> = LazyLock::new(RwLock::default);
+
+/// A scope of its own for a registrant, e.g. a service named `name`.
+pub fn new_scope(name: &str) -> String {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    format!("{name}#{}", NEXT.fetch_add(1, Ordering::Relaxed))
+}
+
+/// Mask `value` until the cache entry `key` of `scope` is forgotten.
+pub fn register(scope: &str, key: &str, value: &str) {
+    if value.trim().chars().count() < MIN_LEN {
+        return;
+    }
+    let entry = (scope.to_string(), key.to_string());
+    let mut sensitive = SENSITIVE.write().unwrap_or_else(PoisonError::into_inner);
+    if sensitive.values.get(&entry).map(String::as_str) != Some(value) {
+        sensitive.values.insert(entry, value.to_string());
+        sensitive.rebuild();
+    }
+}
+
+/// Stop masking the value of the cache entry `key` of `scope`.
+pub fn forget(scope: &str, key: &str) {
+    let entry = (scope.to_string(), key.to_string());
+    let mut sensitive = SENSITIVE.write().unwrap_or_else(PoisonError::into_inner);
+    if sensitive.values.remove(&entry).is_some() {
+        sensitive.rebuild();
+    }
+}
+
+/// `text` with every registered value masked.
+pub fn scrub(text: &str) -> Cow<'_, str> {
+    let sensitive = SENSITIVE.read().unwrap_or_else(PoisonError::into_inner);
+    let mut text = Cow::Borrowed(text);
+    for value in &sensitive.patterns {
+        if text.contains(value.as_str()) {
+            text = Cow::Owned(text.replace(value.as_str(), MASK));
+        }
+    }
+    drop(sensitive);
+    text
+}
+
+/// Log writer that scrubs each line before passing it on.
+pub struct Scrubbed<W>(pub W);
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for Scrubbed<M> {
+    type Writer = Scrubbed<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        Scrubbed(self.0.make_writer())
+    }
+}
+
+impl<W: io::Write> io::Write for Scrubbed<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // The formatter writes each event at once, so values aren't split
+        let text = String::from_utf8_lossy(buf);
+        self.0.write_all(scrub(&text).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCOPE: &str = "test";
+
+    #[test]
+    fn test_scrub_masks_cached_values() {
+        register(SCOPE, "payloads/db/latest", "hunter2-db-password");
+        register(SCOPE, "payloads/flag/latest", "on");
+        register(SCOPE, "payloads/json/latest", "{\n  \"token\": \"abcd\"\n}");
+
+        assert_eq!(
+            scrub("connect with hunter2-db-password failed"),
+            "connect with [redacted] failed"
+        );
+        assert_eq!(scrub("feature is on"), "feature is on");
+        let debug = format!("{:?}", "{\n  \"token\": \"abcd\"\n}");
+        assert_eq!(scrub(&debug), "\"[redacted]\"");
+
+        forget(SCOPE, "payloads/db/latest");
+        assert_eq!(scrub("hunter2-db-password"), "hunter2-db-password");
+        forget(SCOPE, "payloads/json/latest");
+    }
+
+    #[test]
+    fn test_longest_value_is_masked_whole() {
+        register(SCOPE, "payloads/short/latest", "token-abc");
+        register(SCOPE, "payloads/long/latest", "token-abc-extended");
+        register(SCOPE, "payloads/copy/latest", "token-abc");
+
+        assert_eq!(scrub("got token-abc-extended"), "got [redacted]");
+        forget(SCOPE, "payloads/short/latest");
+        assert_eq!(scrub("got token-abc"), "got [redacted]");
+
+        forget(SCOPE, "payloads/long/latest");
+        forget(SCOPE, "payloads/copy/latest");
+        assert_eq!(scrub("got token-abc"), "got token-abc");
+    }
+
+    #[test]
+    fn test_scopes_keep_their_own_values() {
+        let (active, parked) = (new_scope("secret-manager"), new_scope("secret-manager"));
+        register(&active, "payloads/shared/latest", "shared-api-token");
+        register(&parked, "payloads/shared/latest", "shared-api-token");
+
+        forget(&active, "payloads/shared/latest");
+        assert_eq!(scrub("shared-api-token"), "[redacted]");
+        forget(&parked, "payloads/shared/latest");
+        assert_eq!(scrub("shared-api-token"), "shared-api-token");
+    }
+}
@@ -1,10 +1,15 @@
+use std::any::Any;
 use std::collections::HashMap;
 use std::fmt;
+use std::str::FromStr;
 use std::sync::Arc;
 
 use color_eyre::eyre::{Result, eyre};
+use futures::future::BoxFuture;
 
-use crate::config::KeyResolver;
+use crate::config::key::KeyBinding;
+use crate::config::keybindings::KeybindingsConfig;
+use crate::config::{AppConfig, KeyResolver};
 use crate::context::CloudContext;
 use crate::provider::Provider;
 use crate::service::Service;
@@ -59,6 +64,122 @@ impl fmt::Display for ServiceId {
     }
 }
 
+/// Scheme of links that name their context, see [`ResourceLink`].
+pub const LINK_SCHEME: &str = "lazycloud://";
+
+/// A service, or a resource of it, to open from a bookmark, the command
+/// line or another service.
+///
+/// Written as `lazycloud://<provider>/<context>/<service>/<resource>`, e.g.
+/// `lazycloud://gcp/prod/secret-manager/db-password`. Links between services
+/// of the active context leave the context out, as
+/// `gcp:secret-manager/db-password`. What identifies the resource is up to
+/// the service, see [`Service::open_resource`]; it may contain slashes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceLink {
+    /// Context to open the link in, or else the active one.
+    pub context: Option<String>,
+    pub service: ServiceId,
+    /// Resource to show, or else the service's first screen.
+    pub resource: Option<String>,
+}
+
+impl ResourceLink {
+    /// Link to `resource` of `service` in the active context.
+    pub fn new(service: ServiceId, resource: impl Into<String>) -> Self {
+        Self {
+            context: None,
+            service,
+            resource: Some(resource.into()),
+        }
+    }
+
+    /// Link to the first screen of `service` in `context`.
+    pub fn service(context: impl Into<String>, service: ServiceId) -> Self {
+        Self {
+            context: Some(context.into()),
+            service,
+            resource: None,
+        }
+    }
+
+    #[must_use]
+    pub fn in_context(mut self, context: impl Into<String>) -> Self {
+        self.context = Some(context.into());
+        self
+    }
+}
+
+impl fmt::Display for ResourceLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ServiceId { provider, service } = &self.service;
+        match &self.context {
+            Some(context) => write!(f, "{LINK_SCHEME}{provider}/{context}/{service}")?,
+            None => write!(f, "{provider}:{service}")?,
+        }
+        self.resource
+            .as_ref()
+            .map_or(Ok(()), |resource| write!(f, "/{resource}"))
+    }
+}
+
+impl FromStr for ResourceLink {
+    type Err = color_eyre::Report;
+
+    fn from_str(link: &str) -> Result<Self> {
+        let invalid = || {
+            eyre!(
+                "Invalid link '{link}', expected e.g. {LINK_SCHEME}gcp/prod/secret-manager/db-password"
+            )
+        };
+        let (provider, context, path) = if let Some(uri) = link.strip_prefix(LINK_SCHEME) {
+            let mut parts = uri.splitn(3, '/');
+            let (Some(provider), Some(context), Some(path)) =
+                (parts.next(), parts.next(), parts.next())
+            else {
+                return Err(invalid());
+            };
+            (provider, Some(context), path)
+        } else {
+            let (provider, path) = link.split_once(':').ok_or_else(invalid)?;
+            (provider, None, path)
+        };
+        let provider = Provider::from_id(provider).ok_or_else(invalid)?;
+        let (service, resource) = match path.split_once('/') {
+            Some((service, resource)) => (service, Some(resource)),
+            None => (path, None),
+        };
+        if [Some(service), context, resource].contains(&Some("")) {
+            return Err(invalid());
+        }
+        Ok(Self {
+            context: context.map(str::to_string),
+            service: ServiceId::new(provider, service),
+            resource: resource.map(str::to_string),
+        })
+    }
+}
+
+/// A service client initialized ahead of time by [`ServiceProvider::warm_up`].
+///
+/// Type-erased so the registry stays agnostic of provider SDKs; each provider
+/// downcasts it back to its own client in `create_service`.
+pub type WarmClient = Arc<dyn Any + Send + Sync>;
+
+/// A key action a service handles itself, outside the built-in action sets.
+///
+/// Its key is configured under `[keybindings.services."<scope>"]`, where the
+/// scope is the service's [`ServiceProvider::action_scope`], and falls back
+/// to `default`.
+#[derive(Debug, Clone)]
+pub struct ActionDescriptor {
+    /// Name of the action within the scope, e.g. `open_console`.
+    pub name: String,
+    /// Label shown in the key hints.
+    pub description: String,
+    pub default: KeyBinding,
+}
+
 /// Trait for cloud service providers.
 ///
 /// Implement this trait to register a new cloud service with the registry.
@@ -89,8 +210,35 @@ pub trait ServiceProvider: Send + Sync {
         ServiceId::new(self.provider(), self.service_key())
     }
 
+    /// Scope of the service's actions in the keybindings config, e.g. `aws.s3`.
+    fn action_scope(&self) -> String {
+        format!("{}.{}", self.provider().id(), self.service_key())
+    }
+
+    /// Key actions the service handles besides the built-in ones.
+    fn actions(&self) -> Vec<ActionDescriptor> {
+        Vec::new()
+    }
+
+    /// Start initializing the service client for a context in the background.
+    ///
+    /// Called for every available service as soon as a context is selected, so
+    /// entering the service later does not have to wait for the connection.
+    /// Returns `None` if the service has nothing to warm up.
+    fn warm_up(&self, _ctx: &CloudContext) -> Option<BoxFuture<'static, Result<WarmClient>>> {
+        None
+    }
+
     /// Create a new service instance.
-    fn create_service(&self, ctx: &CloudContext, resolver: Arc<KeyResolver>) -> Box<dyn Service>;
+    ///
+    /// `client` is the result of a completed [`warm_up`](Self::warm_up), if any.
+    fn create_service(
+        &self,
+        ctx: &CloudContext,
+        config: Arc<AppConfig>,
+        resolver: Arc<KeyResolver>,
+        client: Option<WarmClient>,
+    ) -> Box<dyn Service>;
 
     /// Check if this service is available for the given context.
     fn is_available(&self, ctx: &CloudContext) -> bool {
@@ -164,6 +312,11 @@ impl ServiceRegistry {
             .collect()
     }
 
+    /// Whether the service `id` is registered and available for `ctx`.
+    pub fn is_available(&self, id: &ServiceId, ctx: &CloudContext) -> bool {
+        self.providers.get(id).is_some_and(|p| p.is_available(ctx))
+    }
+
     /// Get all registered service IDs.
     pub fn all_service_ids(&self) -> Vec<ServiceId> {
         self.providers.keys().cloned().collect()
@@ -201,6 +354,22 @@ impl ServiceRegistry {
             .ok_or_else(|| eyre!("Unknown service: {}", name))
     }
 
+    /// `config` with the default keys of every service action it leaves out.
+    pub fn keybindings(&self, config: &KeybindingsConfig) -> KeybindingsConfig {
+        let mut config = config.clone();
+        for provider in self.providers.values() {
+            for action in provider.actions() {
+                config
+                    .services
+                    .entry(provider.action_scope())
+                    .or_default()
+                    .entry(action.name)
+                    .or_insert(action.default);
+            }
+        }
+        config
+    }
+
     /// Get the number of registered services.
     pub fn len(&self) -> usize {
         self.providers.len()
@@ -222,13 +391,14 @@ impl Default for ServiceRegistry {
 mod tests {
     use std::sync::Arc;
 
-    use crossterm::event::KeyEvent;
+    use crossterm::event::{KeyCode, KeyEvent};
     use ratatui::Frame;
     use ratatui::layout::Rect;
 
     use super::*;
     use crate::Theme;
     use crate::config::KeyResolver;
+    use crate::config::key::Key;
     use crate::context::{AuthMethod, GcpContext};
     use crate::service::{Service, ServiceMsg};
     use crate::ui::EventResult;
@@ -246,6 +416,40 @@ mod tests {
         assert_eq!(id1, id2);
     }
 
+    #[test]
+    fn test_resource_link() {
+        let link: ResourceLink = "gcp:secret-manager/db-password".parse().unwrap();
+        assert_eq!(
+            link,
+            ResourceLink::new(ServiceId::gcp("secret-manager"), "db-password")
+        );
+        assert_eq!(link.to_string(), "gcp:secret-manager/db-password");
+
+        let uri = "lazycloud://gcp/prod/kms/projects/p/keyRings/r";
+        let link: ResourceLink = uri.parse().unwrap();
+        assert_eq!(link.context.as_deref(), Some("prod"));
+        assert_eq!(link.resource.as_deref(), Some("projects/p/keyRings/r"));
+        assert_eq!(link.to_string(), uri);
+
+        let service = ResourceLink::service("prod", ServiceId::gcp("kms"));
+        assert_eq!(service.to_string(), "lazycloud://gcp/prod/kms");
+        assert_eq!(
+            "lazycloud://gcp/prod/kms".parse::<ResourceLink>().unwrap(),
+            service
+        );
+
+        for invalid in [
+            "db-password",
+            "cloud:s3/bucket",
+            "gcp:/x",
+            "gcp:kms/",
+            "lazycloud://gcp/prod",
+            "lazycloud://gcp//kms",
+        ] {
+            assert!(invalid.parse::<ResourceLink>().is_err(), "{invalid}");
+        }
+    }
+
     struct MockProvider;
 
     impl ServiceProvider for MockProvider {
@@ -261,10 +465,23 @@ mod tests {
             "Mock Service"
         }
 
+        fn actions(&self) -> Vec<ActionDescriptor> {
+            ["open", "tail"]
+                .into_iter()
+                .map(|name| ActionDescriptor {
+                    name: name.to_string(),
+                    description: String::new(),
+                    default: Key::new(KeyCode::Char('o')).into(),
+                })
+                .collect()
+        }
+
         fn create_service(
             &self,
             _ctx: &CloudContext,
+            _config: Arc<AppConfig>,
             _resolver: Arc<KeyResolver>,
+            _client: Option<WarmClient>,
         ) -> Box<dyn Service> {
             Box::new(MockService)
         }
@@ -310,9 +527,33 @@ mod tests {
             region: Some("europe-west4".to_string()),
             zone: Some("europe-west4-a".to_string()),
             auth: AuthMethod::ApplicationDefault,
+            accounts: Vec::new(),
+            projects: Vec::new(),
         });
 
         let services = registry.available_services(&gcp_ctx);
         assert_eq!(services.len(), 1);
     }
+
+    #[test]
+    fn test_action_defaults_keep_configured_keys() {
+        let mut registry = ServiceRegistry::new();
+        registry.register(MockProvider);
+
+        let mut config = KeybindingsConfig::default();
+        config
+            .services
+            .entry("gcp.mock-service".to_string())
+            .or_default()
+            .insert("tail".to_string(), Key::new(KeyCode::Char('t')).into());
+
+        let config = registry.keybindings(&config);
+        let display = |action| {
+            config
+                .service("gcp.mock-service", action)
+                .map(KeyBinding::display)
+        };
+        assert_eq!(display("open").as_deref(), Some("o"));
+        assert_eq!(display("tail").as_deref(), Some("t"));
+    }
 }
@@ -1,16 +1,25 @@
+mod resource;
+mod shell;
+
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use color_eyre::Result;
 use crossterm::event::KeyEvent;
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Rect};
+use ratatui::style::Style;
 use ratatui::widgets::Cell;
+pub use resource::{ResourceField, ResourceKind, ResourceProvider};
+pub use shell::{ServiceShell, ServiceState};
 
 use crate::Theme;
 use crate::commands::Command;
-use crate::config::KeyResolver;
+use crate::config::{ActionContext, KeyResolver};
 use crate::context::CloudContext;
-use crate::registry::{ServiceId, ServiceProvider, ServiceRegistry};
+use crate::i18n::t;
+use crate::middleware::MiddlewareStack;
+use crate::registry::{ServiceId, ServiceProvider, ServiceRegistry, WarmClient};
 use crate::search::Matcher;
 use crate::ui::{ColumnDef, Component, EventResult, Keybinding, Table, TableEvent, TableRow};
 
@@ -38,8 +47,8 @@ impl<T: Command> From<T> for ServiceMsg {
 /// 2. `update()` - immediately after init to process startup messages
 /// 3. For each event:
 ///    - `handle_tick()` if tick event
-///    - `handle_input()` if input event, then `update()` if consumed
-/// 4. When commands completes: `update()`
+///    - `handle_input()` or `handle_paste()` if input event, then `update()` if consumed
+/// 4. When a command completes, or a streaming command emits messages: `update()`
 /// 5. `destroy()` - when service is closing
 pub trait Service {
     /// Initialize the service by queuing startup message(s).
@@ -48,12 +57,50 @@ pub trait Service {
     /// Clean up when the service is closing.
     fn destroy(&mut self) {}
 
+    /// Report handled messages and cache lookups to `middleware`.
+    fn set_middleware(&mut self, middleware: MiddlewareStack) {
+        _ = middleware;
+    }
+
+    /// Continue with `context`, e.g. after switching accounts, rebuilding
+    /// clients while keeping the views.
+    ///
+    /// Returns `false` if the service cannot, and has to be started again.
+    fn switch_context(&mut self, context: &CloudContext) -> bool {
+        _ = context;
+        false
+    }
+
+    /// Show `resource` once loaded, the target of a [`ResourceLink`] opened
+    /// from another service.
+    ///
+    /// Called after `init()`. Returns `false` if the service has no resources
+    /// to open.
+    ///
+    /// [`ResourceLink`]: crate::registry::ResourceLink
+    fn open_resource(&mut self, resource: &str) -> bool {
+        _ = resource;
+        false
+    }
+
+    /// The resource on display, as [`Self::open_resource`] takes it, to
+    /// bookmark or return to.
+    fn current_resource(&self) -> Option<String> {
+        None
+    }
+
     /// Handle a tick event for animations.
     fn handle_tick(&mut self) {}
 
     /// Handle a key event.
     fn handle_key(&mut self, key: KeyEvent) -> EventResult<()>;
 
+    /// Handle text pasted into the terminal.
+    fn handle_paste(&mut self, text: &str) -> EventResult<()> {
+        _ = text;
+        EventResult::Ignored
+    }
+
     /// Process all queued messages and return the result.
     ///
     /// # Errors
@@ -71,11 +118,32 @@ pub trait Service {
     fn keybindings(&self) -> Vec<Keybinding> {
         vec![]
     }
+
+    /// Scope and placeholder values for custom actions in the current view.
+    fn action_context(&self) -> ActionContext {
+        ActionContext::default()
+    }
+
+    /// Work held only in memory, lost when the service closes, e.g. secrets
+    /// waiting in the trash. The App warns about it before quitting or
+    /// leaving the service.
+    fn unsaved_work(&self) -> Vec<String> {
+        vec![]
+    }
+}
+
+/// Background initialization state of a service's client.
+#[derive(Clone)]
+pub enum ServiceStatus {
+    Warming,
+    Ready(WarmClient),
+    Failed(String),
 }
 
 #[derive(Clone)]
 struct ServiceItem {
     provider: Arc<dyn ServiceProvider>,
+    status: Option<ServiceStatus>,
 }
 
 impl TableRow for ServiceItem {
@@ -83,20 +151,45 @@ impl TableRow for ServiceItem {
         static COLUMNS: &[ColumnDef] = &[
             ColumnDef::new("Name", Constraint::Min(20)),
             ColumnDef::new("Provider", Constraint::Length(10)),
+            ColumnDef::new("Status", Constraint::Length(14)),
             ColumnDef::new("Description", Constraint::Min(30)),
         ];
         COLUMNS
     }
 
-    fn render_cells(&self, _theme: &Theme) -> Vec<Cell<'static>> {
-        let name = self.provider.icon().map_or_else(
+    fn render_cells(&self, theme: &Theme) -> Vec<Cell<'static>> {
+        let icon = self.provider.icon().filter(|_| theme.symbols.icons);
+        let name = icon.map_or_else(
             || self.provider.display_name().to_string(),
             |icon| format!("{icon} {}", self.provider.display_name()),
         );
+        let status = match &self.status {
+            None => Cell::from(""),
+            Some(ServiceStatus::Warming) => {
+                Cell::from(format!("{} {}", theme.symbols.pending, t("Connecting")))
+                    .style(Style::default().fg(theme.overlay1()))
+            }
+            Some(ServiceStatus::Ready(_)) => {
+                Cell::from(format!("{} {}", theme.symbols.ready, t("Ready")))
+                    .style(Style::default().fg(theme.green()))
+            }
+            Some(ServiceStatus::Failed(_)) => {
+                Cell::from(format!("{} {}", theme.symbols.failure, t("Failed")))
+                    .style(Style::default().fg(theme.red()))
+            }
+        };
+        // Why the client failed is more useful than what the service does
+        let description = match &self.status {
+            Some(ServiceStatus::Failed(err)) => {
+                Cell::from(err.clone()).style(Style::default().fg(theme.red()))
+            }
+            _ => Cell::from(self.provider.description()),
+        };
         vec![
             Cell::from(name),
-            Cell::from(format!("{}", self.provider.provider())),
-            Cell::from(self.provider.description().to_string()),
+            Cell::from(self.provider.provider().display_name()),
+            status,
+            description,
         ]
     }
 
@@ -105,29 +198,56 @@ impl TableRow for ServiceItem {
         matcher.matches(self.provider.display_name(), query)
             || matcher.matches(self.provider.service_key(), query)
             || matcher.matches(self.provider.description(), query)
+            || matcher.matches(self.provider.provider().display_name(), query)
     }
 }
 
 pub struct ServiceSelectorView {
+    items: Vec<ServiceItem>,
     table: Table<ServiceItem>,
 }
 
 impl ServiceSelectorView {
+    /// Services of `context` grouped by provider, with `last_used` selected.
     #[must_use]
     pub fn new(
         registry: &Arc<ServiceRegistry>,
         context: &CloudContext,
+        statuses: &HashMap<ServiceId, ServiceStatus>,
+        last_used: Option<&str>,
         resolver: Arc<KeyResolver>,
     ) -> Self {
-        let services: Vec<ServiceItem> = registry
+        let mut items: Vec<ServiceItem> = registry
             .available_services(context)
             .into_iter()
-            .map(|provider| ServiceItem { provider })
+            .map(|provider| ServiceItem {
+                status: statuses.get(&provider.service_id()).cloned(),
+                provider,
+            })
             .collect();
+        items.sort_by_key(|item| {
+            (
+                item.provider.provider().display_name(),
+                item.provider.display_name(),
+            )
+        });
+
+        let mut table =
+            Table::new(items.clone(), resolver).with_title(format!(" {} ", t("Services")));
+        if let Some(last_used) = last_used {
+            table.select_where(|item| item.provider.service_id().to_string() == last_used);
+        }
+        Self { items, table }
+    }
 
-        Self {
-            table: Table::new(services, resolver).with_title(" Services "),
+    /// Update the initialization status shown for a service.
+    pub fn set_status(&mut self, id: &ServiceId, status: &ServiceStatus) {
+        for item in &mut self.items {
+            if item.provider.service_id() == *id {
+                item.status = Some(status.clone());
+            }
         }
+        self.table.set_items(self.items.clone());
     }
 }
 
@@ -143,6 +263,10 @@ impl Component for ServiceSelectorView {
         })
     }
 
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
     fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
         self.table.render(frame, area, theme);
     }
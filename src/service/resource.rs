@@ -0,0 +1,809 @@
+//! Generic list/detail/create/delete service for simple resources.
+//!
+//! A service that only needs to browse a flat list of resources implements
+//! [`ResourceKind`] with its client calls and registers a
+//! [`ResourceProvider`]. The table, search, detail view and the create and
+//! delete dialogs are shared.
+
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use crossterm::event::KeyEvent;
+use futures::future::BoxFuture;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::widgets::Cell;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::Theme;
+use crate::app::AppMessage;
+use crate::commands::{Command, OpenUrlCmd};
+use crate::config::{
+    ActionContext,
+    AppConfig,
+    KeyResolver,
+    NavAction,
+    ResourceAction,
+    SearchAction,
+};
+use crate::context::CloudContext;
+use crate::provider::Provider;
+use crate::registry::{ServiceProvider, WarmClient};
+use crate::search::Matcher;
+use crate::service::{Service, ServiceMsg, ServiceShell, ServiceState};
+use crate::ui::{
+    ColumnDef,
+    Component,
+    ConfirmDialog,
+    ConfirmEvent,
+    EventResult,
+    Keybinding,
+    Modal,
+    Screen,
+    Table,
+    TableEvent,
+    TableRow,
+    TextInput,
+    TextInputEvent,
+};
+
+// === Models ===
+
+/// A kind of resource and the client calls to manage it.
+///
+/// Columns and search come from the item's [`TableRow`] implementation.
+#[async_trait]
+pub trait ResourceKind: Debug + Send + Sync + 'static {
+    type Item: TableRow + Clone + Debug + Send + 'static;
+    type Client: Clone + Debug + Send + Sync + 'static;
+
+    /// Service name and table title, e.g. `Service Accounts`.
+    const NAME: &'static str;
+    /// Singular name used in dialogs, e.g. `service account`.
+    const NOUN: &'static str;
+    const SERVICE_KEY: &'static str;
+    const DESCRIPTION: &'static str;
+    const PROVIDER: Provider;
+
+    /// Values asked for, in order, when creating a resource.
+    const CREATE_FIELDS: &'static [ResourceField];
+
+    /// Identifier shown in breadcrumbs and passed to custom actions.
+    fn id(item: &Self::Item) -> String;
+
+    async fn connect(context: &CloudContext) -> Result<Self::Client>;
+
+    async fn list(client: &Self::Client) -> Result<Vec<Self::Item>>;
+
+    async fn get(client: &Self::Client, id: &str) -> Result<Self::Item>;
+
+    /// Create a resource from the values of [`Self::CREATE_FIELDS`].
+    async fn create(client: &Self::Client, values: Vec<String>) -> Result<Self::Item>;
+
+    async fn delete(client: &Self::Client, item: &Self::Item) -> Result<()>;
+
+    /// Field/value pairs shown by the default detail screen.
+    fn details(item: &Self::Item) -> Vec<(&'static str, String)>;
+
+    /// Page of the resource in the provider's web console, if it has one.
+    fn console_url(_context: &CloudContext, _item: &Self::Item) -> Option<String> {
+        None
+    }
+
+    /// Check the value of the create field at `index` while it is typed.
+    ///
+    /// # Errors
+    /// Returns the message to show while the value is invalid.
+    fn validate(index: usize, value: &str) -> std::result::Result<(), String> {
+        let field = &Self::CREATE_FIELDS[index];
+        if field.required && value.trim().is_empty() {
+            return Err(format!("{} is required", field.label));
+        }
+        Ok(())
+    }
+
+    /// Screen opened for a resource, a field/value table by default.
+    fn detail_screen(
+        item: Self::Item,
+        resolver: Arc<KeyResolver>,
+    ) -> Box<dyn Screen<Output = ResourceMsg<Self>>>
+    where
+        Self: Sized,
+    {
+        Box::new(ResourceDetailScreen::<Self>::new(item, resolver))
+    }
+}
+
+/// An input of the create dialog.
+pub struct ResourceField {
+    pub label: &'static str,
+    pub placeholder: &'static str,
+    pub required: bool,
+}
+
+impl ResourceField {
+    #[must_use]
+    pub const fn required(label: &'static str, placeholder: &'static str) -> Self {
+        Self {
+            label,
+            placeholder,
+            required: true,
+        }
+    }
+
+    #[must_use]
+    pub const fn optional(label: &'static str, placeholder: &'static str) -> Self {
+        Self {
+            label,
+            placeholder,
+            required: false,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct DetailRow {
+    field: &'static str,
+    value: String,
+}
+
+impl TableRow for DetailRow {
+    fn columns() -> &'static [ColumnDef] {
+        static COLUMNS: &[ColumnDef] = &[
+            ColumnDef::new("Field", Constraint::Length(20)),
+            ColumnDef::new("Value", Constraint::Min(30)),
+        ];
+        COLUMNS
+    }
+
+    fn render_cells(&self, _theme: &Theme) -> Vec<Cell<'static>> {
+        vec![Cell::from(self.field), Cell::from(self.value.clone())]
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        let matcher = Matcher::new();
+        matcher.matches(self.field, query) || matcher.matches(&self.value, query)
+    }
+}
+
+// === Messages ===
+
+#[derive(Debug)]
+pub enum ResourceMsg<K: ResourceKind> {
+    Initialize,
+    ClientInitialized(K::Client),
+
+    Load,
+    Loaded(Vec<K::Item>),
+
+    View(K::Item),
+    Viewed(K::Item),
+
+    StartCreation,
+    Create(Vec<String>),
+    Created(K::Item),
+
+    ConfirmDelete(K::Item),
+    Delete(K::Item),
+    Deleted(K::Item),
+
+    /// Open the resource's page in the web console.
+    OpenConsole(K::Item),
+
+    DialogCancelled,
+    /// A request failed; the error is shown by the app.
+    Failed,
+}
+
+// === Provider ===
+
+/// Registers a [`ResourceKind`] as a service.
+pub struct ResourceProvider<K>(PhantomData<fn() -> K>);
+
+impl<K> Default for ResourceProvider<K> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<K: ResourceKind> ServiceProvider for ResourceProvider<K> {
+    fn provider(&self) -> Provider {
+        K::PROVIDER
+    }
+
+    fn service_key(&self) -> &'static str {
+        K::SERVICE_KEY
+    }
+
+    fn display_name(&self) -> &'static str {
+        K::NAME
+    }
+
+    fn description(&self) -> &'static str {
+        K::DESCRIPTION
+    }
+
+    fn warm_up(&self, ctx: &CloudContext) -> Option<BoxFuture<'static, Result<WarmClient>>> {
+        let ctx = ctx.clone();
+        Some(Box::pin(async move {
+            let client = K::connect(&ctx).await?;
+            Ok(Arc::new(client) as WarmClient)
+        }))
+    }
+
+    fn create_service(
+        &self,
+        ctx: &CloudContext,
+        config: Arc<AppConfig>,
+        resolver: Arc<KeyResolver>,
+        client: Option<WarmClient>,
+    ) -> Box<dyn Service> {
+        let client = client
+            .and_then(|client| client.downcast::<K::Client>().ok())
+            .map(|client| (*client).clone());
+        let state = ResourceService::<K> {
+            context: ctx.clone(),
+            client,
+            linked: None,
+        };
+        Box::new(ServiceShell::new(state, config, resolver))
+    }
+}
+
+// === Screens ===
+
+/// Searchable table of all resources of a kind.
+pub struct ResourceBrowser<K: ResourceKind> {
+    table: Table<K::Item>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl<K: ResourceKind> ResourceBrowser<K> {
+    pub fn new(items: Vec<K::Item>, resolver: Arc<KeyResolver>) -> Self {
+        Self {
+            table: Table::new(items, resolver.clone())
+                .with_title(format!(" {} ", K::NAME))
+                .with_preview(K::details),
+            resolver,
+        }
+    }
+}
+
+impl<K: ResourceKind> Screen for ResourceBrowser<K> {
+    type Output = ResourceMsg<K>;
+
+    fn handle_key(&mut self, key: KeyEvent) -> crate::ui::Result<EventResult<Self::Output>> {
+        let result = self.table.handle_key(key)?;
+        if let EventResult::Event(TableEvent::Activated(item)) = result {
+            return Ok(ResourceMsg::View(item).into());
+        }
+        if result.is_consumed() {
+            return Ok(EventResult::Consumed);
+        }
+
+        if self.resolver.matches_resource(&key, ResourceAction::Reload) {
+            return Ok(ResourceMsg::Load.into());
+        }
+        if self.resolver.matches_resource(&key, ResourceAction::New) {
+            return Ok(ResourceMsg::StartCreation.into());
+        }
+        if self.resolver.matches_resource(&key, ResourceAction::Delete)
+            && let Some(item) = self.table.selected_item()
+        {
+            return Ok(ResourceMsg::ConfirmDelete(item.clone()).into());
+        }
+        if self
+            .resolver
+            .matches_resource(&key, ResourceAction::OpenConsole)
+            && let Some(item) = self.table.selected_item()
+        {
+            return Ok(ResourceMsg::OpenConsole(item.clone()).into());
+        }
+
+        Ok(EventResult::Ignored)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> crate::ui::Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.table.render(frame, area, theme);
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        vec![
+            Keybinding::hint(self.resolver.display_nav(NavAction::Select), "Details"),
+            Keybinding::hint(self.resolver.display_resource(ResourceAction::New), "New"),
+            Keybinding::hint(
+                self.resolver.display_resource(ResourceAction::Delete),
+                "Delete",
+            ),
+            Keybinding::hint(self.resolver.display_search(SearchAction::Toggle), "Search"),
+            Keybinding::new(
+                self.resolver.display_resource(ResourceAction::OpenConsole),
+                "Open in console",
+            ),
+            Keybinding::new(
+                self.resolver.display_resource(ResourceAction::Reload),
+                "Reload",
+            ),
+        ]
+    }
+
+    fn action_context(&self) -> ActionContext {
+        let ctx = ActionContext::screen("list");
+        match self.table.selected_item() {
+            Some(item) => ctx.with_var("id", K::id(item)),
+            None => ctx,
+        }
+    }
+}
+
+/// Field/value table of a single resource.
+pub struct ResourceDetailScreen<K: ResourceKind> {
+    item: K::Item,
+    table: Table<DetailRow>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl<K: ResourceKind> ResourceDetailScreen<K> {
+    pub fn new(item: K::Item, resolver: Arc<KeyResolver>) -> Self {
+        let rows = K::details(&item)
+            .into_iter()
+            .map(|(field, value)| DetailRow { field, value })
+            .collect();
+        Self {
+            table: Table::new(rows, resolver.clone()).with_title(format!(" {} ", K::id(&item))),
+            item,
+            resolver,
+        }
+    }
+}
+
+impl<K: ResourceKind> Screen for ResourceDetailScreen<K> {
+    type Output = ResourceMsg<K>;
+
+    fn handle_key(&mut self, key: KeyEvent) -> crate::ui::Result<EventResult<Self::Output>> {
+        if self.table.handle_key(key)?.is_consumed() {
+            return Ok(EventResult::Consumed);
+        }
+
+        if self.resolver.matches_resource(&key, ResourceAction::Reload) {
+            return Ok(ResourceMsg::View(self.item.clone()).into());
+        }
+        if self.resolver.matches_resource(&key, ResourceAction::Delete) {
+            return Ok(ResourceMsg::ConfirmDelete(self.item.clone()).into());
+        }
+        if self
+            .resolver
+            .matches_resource(&key, ResourceAction::OpenConsole)
+        {
+            return Ok(ResourceMsg::OpenConsole(self.item.clone()).into());
+        }
+
+        Ok(EventResult::Ignored)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> crate::ui::Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.table.render(frame, area, theme);
+    }
+
+    fn breadcrumbs(&self) -> Vec<String> {
+        vec![K::id(&self.item)]
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        vec![
+            Keybinding::hint(
+                self.resolver.display_resource(ResourceAction::Delete),
+                "Delete",
+            ),
+            Keybinding::hint(self.resolver.display_search(SearchAction::Toggle), "Search"),
+            Keybinding::new(
+                self.resolver.display_resource(ResourceAction::OpenConsole),
+                "Open in console",
+            ),
+            Keybinding::new(
+                self.resolver.display_resource(ResourceAction::Reload),
+                "Reload",
+            ),
+        ]
+    }
+
+    fn action_context(&self) -> ActionContext {
+        ActionContext::screen("details").with_var("id", K::id(&self.item))
+    }
+}
+
+// === Dialogs ===
+
+/// Asks for each of [`ResourceKind::CREATE_FIELDS`] in turn.
+struct CreateResourceDialog<K: ResourceKind> {
+    input: TextInput,
+    values: Vec<String>,
+    kind: PhantomData<fn() -> K>,
+}
+
+impl<K: ResourceKind> CreateResourceDialog<K> {
+    fn new() -> Self {
+        Self {
+            input: Self::input(0),
+            values: Vec::new(),
+            kind: PhantomData,
+        }
+    }
+
+    fn input(index: usize) -> TextInput {
+        let field = &K::CREATE_FIELDS[index];
+        let label = if field.required {
+            field.label.to_string()
+        } else {
+            format!("{} (optional)", field.label)
+        };
+        TextInput::new(label)
+            .with_placeholder(field.placeholder)
+            .with_validator(move |value| K::validate(index, value))
+    }
+}
+
+impl<K: ResourceKind> Modal for CreateResourceDialog<K> {
+    type Output = ResourceMsg<K>;
+
+    fn handle_key(&mut self, key: KeyEvent) -> crate::ui::Result<EventResult<Self::Output>> {
+        Ok(match self.input.handle_key(key)? {
+            EventResult::Event(TextInputEvent::Submitted(value)) => {
+                self.values.push(value);
+                if self.values.len() == K::CREATE_FIELDS.len() {
+                    return Ok(ResourceMsg::Create(std::mem::take(&mut self.values)).into());
+                }
+                self.input = Self::input(self.values.len());
+                EventResult::Consumed
+            }
+            EventResult::Event(TextInputEvent::Cancelled) => ResourceMsg::DialogCancelled.into(),
+            _ => EventResult::Consumed,
+        })
+    }
+
+    fn handle_paste(&mut self, text: &str) -> crate::ui::Result<EventResult<Self::Output>> {
+        Ok(self.input.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.input.render(frame, area, theme);
+    }
+}
+
+struct DeleteResourceDialog<K: ResourceKind> {
+    item: K::Item,
+    dialog: ConfirmDialog,
+}
+
+impl<K: ResourceKind> DeleteResourceDialog<K> {
+    fn new(item: K::Item, resolver: Arc<KeyResolver>) -> Self {
+        let dialog = ConfirmDialog::new(
+            format!(
+                "Are you sure you want to delete the {} \"{}\"?",
+                K::NOUN,
+                K::id(&item)
+            ),
+            resolver,
+        )
+        .with_title(format!("Delete {}", K::NOUN))
+        .with_confirm_text("Delete")
+        .with_cancel_text("Cancel")
+        .danger();
+        Self { item, dialog }
+    }
+}
+
+impl<K: ResourceKind> Modal for DeleteResourceDialog<K> {
+    type Output = ResourceMsg<K>;
+
+    fn handle_key(&mut self, key: KeyEvent) -> crate::ui::Result<EventResult<Self::Output>> {
+        Ok(match self.dialog.handle_key(key)? {
+            EventResult::Event(ConfirmEvent::Confirmed) => {
+                ResourceMsg::Delete(self.item.clone()).into()
+            }
+            EventResult::Event(ConfirmEvent::Cancelled) => ResourceMsg::DialogCancelled.into(),
+            _ => EventResult::Consumed,
+        })
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.dialog.render(frame, area, theme);
+    }
+}
+
+// === Update Logic ===
+
+type ResourceShell<K> = ServiceShell<ResourceService<K>, ResourceMsg<K>>;
+
+pub struct ResourceService<K: ResourceKind> {
+    context: CloudContext,
+    client: Option<K::Client>,
+    /// Id of the resource to view once the list is loaded, see
+    /// [`Service::open_resource`].
+    linked: Option<String>,
+}
+
+impl<K: ResourceKind> ResourceService<K> {
+    fn client(&self) -> Result<K::Client> {
+        self.client
+            .clone()
+            .ok_or_else(|| color_eyre::eyre::eyre!("{} client not initialized", K::NAME))
+    }
+}
+
+impl<K: ResourceKind> ServiceState<ResourceMsg<K>> for ResourceService<K> {
+    fn name(&self) -> &'static str {
+        K::NAME
+    }
+
+    fn service_key(&self) -> &'static str {
+        K::SERVICE_KEY
+    }
+
+    fn init(shell: &mut ResourceShell<K>) {
+        // Skip connecting if the client was warmed up during service selection
+        match shell.state().client.clone() {
+            Some(client) => shell.queue(ResourceMsg::ClientInitialized(client)),
+            None => shell.queue(ResourceMsg::Initialize),
+        }
+    }
+
+    fn update(shell: &mut ResourceShell<K>, msg: ResourceMsg<K>) -> Result<ServiceMsg> {
+        let resolver = shell.get_resolver();
+        let tx = shell.get_msg_sender();
+
+        match msg {
+            ResourceMsg::Initialize => Ok(ConnectCmd::<K> {
+                context: shell.state().context.clone(),
+                tx,
+            }
+            .into()),
+
+            ResourceMsg::ClientInitialized(client) => {
+                shell.state_mut().client = Some(client);
+                shell.queue(ResourceMsg::Load);
+                Ok(ServiceMsg::Idle)
+            }
+
+            ResourceMsg::Load => {
+                shell.display_loading_spinner("Loading...");
+                Ok(ListCmd::<K> {
+                    client: shell.state().client()?,
+                    tx,
+                }
+                .into())
+            }
+
+            ResourceMsg::Loaded(items) => {
+                let mut browser = ResourceBrowser::<K>::new(items, resolver);
+                shell.pop_to_root();
+                if let Some(id) = shell.state_mut().linked.take() {
+                    if !browser.table.select_where(|item| K::id(item) == id) {
+                        shell.push_view(browser);
+                        return Err(eyre!("The {} '{id}' was not found", K::NOUN));
+                    }
+                    if let Some(item) = browser.table.selected_item() {
+                        shell.queue(ResourceMsg::View(item.clone()));
+                    }
+                }
+                shell.push_view(browser);
+                Ok(ServiceMsg::Idle)
+            }
+
+            ResourceMsg::View(item) => {
+                shell.display_loading_spinner("Loading...");
+                Ok(GetCmd::<K> {
+                    client: shell.state().client()?,
+                    id: K::id(&item),
+                    tx,
+                }
+                .into())
+            }
+
+            ResourceMsg::Viewed(item) => {
+                // Reloading the details replaces them instead of stacking up
+                if shell.current_screen_is("details") {
+                    shell.pop_view();
+                }
+                shell.push_boxed_view(K::detail_screen(item, resolver));
+                Ok(ServiceMsg::Idle)
+            }
+
+            ResourceMsg::StartCreation => {
+                shell.display_overlay(CreateResourceDialog::<K>::new());
+                Ok(ServiceMsg::Idle)
+            }
+
+            ResourceMsg::Create(values) => {
+                shell.close_overlay();
+                shell.display_loading_spinner("Creating...");
+                Ok(CreateCmd::<K> {
+                    client: shell.state().client()?,
+                    values,
+                    tx,
+                }
+                .into())
+            }
+
+            ResourceMsg::ConfirmDelete(item) => {
+                shell.display_overlay(DeleteResourceDialog::<K>::new(item, resolver));
+                Ok(ServiceMsg::Idle)
+            }
+
+            ResourceMsg::Delete(item) => {
+                shell.close_overlay();
+                shell.display_loading_spinner("Deleting...");
+                Ok(DeleteCmd::<K> {
+                    client: shell.state().client()?,
+                    item,
+                    tx,
+                }
+                .into())
+            }
+
+            // Kinds without a console page ignore the key
+            ResourceMsg::OpenConsole(item) => Ok(K::console_url(&shell.state().context, &item)
+                .map_or(ServiceMsg::Idle, |url| OpenUrlCmd::new(url).into())),
+
+            ResourceMsg::Created(_) | ResourceMsg::Deleted(_) => {
+                shell.queue(ResourceMsg::Load);
+                Ok(ServiceMsg::Idle)
+            }
+
+            ResourceMsg::DialogCancelled => {
+                shell.close_overlay();
+                Ok(ServiceMsg::Idle)
+            }
+
+            ResourceMsg::Failed => {
+                shell.hide_loading_spinner();
+                if !shell.has_view() {
+                    return Ok(ServiceMsg::Close);
+                }
+                Ok(ServiceMsg::Idle)
+            }
+        }
+    }
+
+    fn open_resource(shell: &mut ResourceShell<K>, resource: &str) -> bool {
+        shell.state_mut().linked = Some(resource.to_string());
+        true
+    }
+
+    /// The selected or viewed resource.
+    fn current_resource(shell: &ResourceShell<K>) -> Option<String> {
+        shell.action_context().vars.remove("id")
+    }
+}
+
+// === Commands ===
+
+/// Send the outcome of a request, or [`ResourceMsg::Failed`] and the error.
+///
+/// The service may have been closed in the meantime, so a send that finds no
+/// receiver is not an error.
+fn report<K: ResourceKind, T>(
+    tx: &UnboundedSender<ResourceMsg<K>>,
+    result: Result<T>,
+    msg: impl FnOnce(T) -> ResourceMsg<K>,
+) -> Result<()> {
+    match result {
+        Ok(value) => {
+            let _ = tx.send(msg(value));
+            Ok(())
+        }
+        Err(err) => {
+            let _ = tx.send(ResourceMsg::Failed);
+            Err(err)
+        }
+    }
+}
+
+struct ConnectCmd<K: ResourceKind> {
+    context: CloudContext,
+    tx: UnboundedSender<ResourceMsg<K>>,
+}
+
+#[async_trait]
+impl<K: ResourceKind> Command for ConnectCmd<K> {
+    fn name(&self) -> String {
+        format!("Connecting to {}", K::NAME)
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let result = K::connect(&self.context).await;
+        report(&self.tx, result, ResourceMsg::ClientInitialized)
+    }
+}
+
+struct ListCmd<K: ResourceKind> {
+    client: K::Client,
+    tx: UnboundedSender<ResourceMsg<K>>,
+}
+
+#[async_trait]
+impl<K: ResourceKind> Command for ListCmd<K> {
+    fn name(&self) -> String {
+        format!("Loading {}", K::NAME.to_lowercase())
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let result = K::list(&self.client).await;
+        report(&self.tx, result, ResourceMsg::Loaded)
+    }
+}
+
+struct GetCmd<K: ResourceKind> {
+    client: K::Client,
+    id: String,
+    tx: UnboundedSender<ResourceMsg<K>>,
+}
+
+#[async_trait]
+impl<K: ResourceKind> Command for GetCmd<K> {
+    fn name(&self) -> String {
+        format!("Loading '{}'", self.id)
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let result = K::get(&self.client, &self.id).await;
+        report(&self.tx, result, ResourceMsg::Viewed)
+    }
+}
+
+struct CreateCmd<K: ResourceKind> {
+    client: K::Client,
+    values: Vec<String>,
+    tx: UnboundedSender<ResourceMsg<K>>,
+}
+
+#[async_trait]
+impl<K: ResourceKind> Command for CreateCmd<K> {
+    fn name(&self) -> String {
+        format!("Creating {}", K::NOUN)
+    }
+
+    fn mutates(&self) -> bool {
+        true
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let result = K::create(&self.client, self.values).await;
+        report(&self.tx, result, ResourceMsg::Created)
+    }
+}
+
+struct DeleteCmd<K: ResourceKind> {
+    client: K::Client,
+    item: K::Item,
+    tx: UnboundedSender<ResourceMsg<K>>,
+}
+
+#[async_trait]
+impl<K: ResourceKind> Command for DeleteCmd<K> {
+    fn name(&self) -> String {
+        format!("Deleting '{}'", K::id(&self.item))
+    }
+
+    fn mutates(&self) -> bool {
+        true
+    }
+
+    async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+        let result = K::delete(&self.client, &self.item).await;
+        let Self { item, tx, .. } = *self;
+        report(&tx, result, |()| ResourceMsg::Deleted(item))
+    }
+}
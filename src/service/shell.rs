@@ -0,0 +1,594 @@
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Instant;
+
+use color_eyre::Result;
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio_util::sync::CancellationToken;
+
+use crate::commands::Command;
+use crate::config::{ActionContext, AppConfig, GlobalAction, KeyResolver};
+use crate::context::CloudContext;
+use crate::middleware::{Middleware, MiddlewareStack, message_name};
+use crate::service::{Service, ServiceMsg};
+use crate::ui::{Component, EventResult, EventResultExt, Keybinding, Modal, Screen, Spinner};
+use crate::{Theme, redact};
+
+/// The part of a service that differs between services: its data and how
+/// it reacts to messages.
+///
+/// The [`ServiceShell`] around it owns the screens, overlay, spinner, cache
+/// and message queue, and passes itself to these hooks so they can use them.
+pub trait ServiceState<M>: Sized {
+    /// First breadcrumb segment, e.g. `Secret Manager`.
+    fn name(&self) -> &'static str;
+
+    /// Service key that scopes custom actions, e.g. `secret-manager`.
+    fn service_key(&self) -> &'static str;
+
+    /// Queue the startup message(s).
+    fn init(shell: &mut ServiceShell<Self, M>);
+
+    /// Process a single message.
+    ///
+    /// # Errors
+    /// Returns an error if the message could not be processed.
+    fn update(shell: &mut ServiceShell<Self, M>, msg: M) -> Result<ServiceMsg>;
+
+    /// Handle a tick event, e.g. to expire timed state.
+    fn handle_tick(shell: &mut ServiceShell<Self, M>) {
+        _ = shell;
+    }
+
+    /// Handle a key that neither the overlay nor the current screen used.
+    fn handle_key(shell: &mut ServiceShell<Self, M>, key: KeyEvent) -> EventResult<()> {
+        _ = (shell, key);
+        EventResult::Ignored
+    }
+
+    /// Check a message from the overlay or current screen before it is
+    /// queued, e.g. to replace it with a refusal.
+    fn gate(shell: &ServiceShell<Self, M>, msg: M) -> M {
+        _ = shell;
+        msg
+    }
+
+    /// Add service-wide keybindings to those of the current screen.
+    fn keybindings(shell: &ServiceShell<Self, M>, keybindings: &mut Vec<Keybinding>) {
+        _ = (shell, keybindings);
+    }
+
+    /// Continue with `context`, see [`Service::switch_context`].
+    fn switch_context(shell: &mut ServiceShell<Self, M>, context: &CloudContext) -> bool {
+        _ = (shell, context);
+        false
+    }
+
+    /// Show a linked resource, see [`Service::open_resource`].
+    fn open_resource(shell: &mut ServiceShell<Self, M>, resource: &str) -> bool {
+        _ = (shell, resource);
+        false
+    }
+
+    /// The resource on display, see [`Service::current_resource`].
+    fn current_resource(shell: &ServiceShell<Self, M>) -> Option<String> {
+        _ = shell;
+        None
+    }
+
+    /// Work lost when the service closes, see [`Service::unsaved_work`].
+    fn unsaved_work(shell: &ServiceShell<Self, M>) -> Vec<String> {
+        _ = shell;
+        Vec::new()
+    }
+}
+
+/// Generic [`Service`] built around a [`ServiceState`].
+///
+/// Screens form a stack navigated with the back key, one modal overlay at a
+/// time captures all input above them, and a spinner replaces the screens
+/// while something loads. Messages from screens, overlays and commands are
+/// queued and handed to [`ServiceState::update`] one at a time.
+pub struct ServiceShell<S, M> {
+    state: S,
+    screen_stack: Vec<Box<dyn Screen<Output = M>>>,
+    modal: Option<Box<dyn Modal<Output = M>>>,
+    spinner: Spinner,
+    loading: Option<&'static str>,
+    /// Fetched resources by key, see [`Self::cached`].
+    cache: HashMap<String, Box<dyn Any>>,
+    /// Where sensitive cached values are registered, see [`redact`].
+    redact_scope: String,
+    /// Tokens of streams feeding a screen, by the stack depth of that screen,
+    /// see [`Self::view_token`].
+    view_tokens: Vec<(usize, CancellationToken)>,
+    /// Set when going back from the first screen.
+    closing: bool,
+    middleware: MiddlewareStack,
+    msg_tx: UnboundedSender<M>,
+    msg_rx: UnboundedReceiver<M>,
+    config: Arc<AppConfig>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl<S: ServiceState<M>, M: 'static> ServiceShell<S, M> {
+    pub fn new(state: S, config: Arc<AppConfig>, resolver: Arc<KeyResolver>) -> Self {
+        let (msg_tx, msg_rx) = mpsc::unbounded_channel();
+        let redact_scope = redact::new_scope(state.service_key());
+        Self {
+            state,
+            screen_stack: Vec::new(),
+            modal: None,
+            spinner: Spinner::new(),
+            loading: Some("Initializing..."),
+            cache: HashMap::new(),
+            redact_scope,
+            view_tokens: Vec::new(),
+            closing: false,
+            middleware: MiddlewareStack::default(),
+            msg_tx,
+            msg_rx,
+            config,
+            resolver,
+        }
+    }
+
+    pub const fn state(&self) -> &S {
+        &self.state
+    }
+
+    pub const fn state_mut(&mut self) -> &mut S {
+        &mut self.state
+    }
+
+    pub fn get_resolver(&self) -> Arc<KeyResolver> {
+        self.resolver.clone()
+    }
+
+    pub fn get_config(&self) -> &AppConfig {
+        &self.config
+    }
+
+    pub fn get_msg_sender(&self) -> UnboundedSender<M> {
+        self.msg_tx.clone()
+    }
+
+    pub fn queue(&self, msg: M) {
+        let _ = self.msg_tx.send(msg);
+    }
+
+    // === Screen stack ===
+
+    pub fn push_view<T: Screen<Output = M> + 'static>(&mut self, screen: T) {
+        self.push_boxed_view(Box::new(screen));
+    }
+
+    /// Like [`Self::push_view`], for screens built by a factory.
+    pub fn push_boxed_view(&mut self, screen: Box<dyn Screen<Output = M>>) {
+        self.hide_loading_spinner();
+        self.screen_stack.push(screen);
+    }
+
+    /// Pop the current screen unless it is the first one.
+    pub fn pop_view(&mut self) -> bool {
+        if self.screen_stack.len() > 1 {
+            self.screen_stack.pop();
+            self.cancel_streams();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn pop_to_root(&mut self) {
+        while self.screen_stack.len() > 1 {
+            self.screen_stack.pop();
+        }
+        self.screen_stack.clear();
+        self.cancel_streams();
+    }
+
+    /// A token cancelled once the current screen leaves the stack, for
+    /// commands that keep feeding it, see [`StreamCmd`].
+    ///
+    /// [`StreamCmd`]: crate::commands::StreamCmd
+    pub fn view_token(&mut self) -> CancellationToken {
+        let token = CancellationToken::new();
+        self.view_tokens
+            .push((self.screen_stack.len(), token.clone()));
+        token
+    }
+
+    /// Cancel the streams of screens no longer on the stack.
+    fn cancel_streams(&mut self) {
+        let depth = self.screen_stack.len();
+        self.view_tokens.retain(|(screen, token)| {
+            if *screen > depth {
+                token.cancel();
+            }
+            *screen <= depth
+        });
+    }
+
+    pub const fn has_view(&self) -> bool {
+        !self.screen_stack.is_empty()
+    }
+
+    fn current_screen(&self) -> Option<&dyn Screen<Output = M>> {
+        self.screen_stack.last().map(|b| &**b)
+    }
+
+    /// The current screen, if it is a `T`, e.g. to update it in place.
+    pub fn current_view_mut<T: Screen<Output = M>>(&mut self) -> Option<&mut T> {
+        let screen: &mut dyn Any = self.screen_stack.last_mut()?.as_mut();
+        screen.downcast_mut()
+    }
+
+    pub fn current_screen_is(&self, screen: &str) -> bool {
+        self.current_screen()
+            .is_some_and(|current| current.action_context().screen == Some(screen))
+    }
+
+    /// Identifier of the current screen, see [`ActionContext::screen`].
+    pub fn current_screen_name(&self) -> Option<&'static str> {
+        self.current_screen()
+            .and_then(|screen| screen.action_context().screen)
+    }
+
+    // === Modal ===
+
+    pub fn display_overlay<T: Modal<Output = M> + 'static>(&mut self, modal: T) {
+        self.modal = Some(Box::new(modal));
+    }
+
+    pub fn close_overlay(&mut self) {
+        self.modal = None;
+    }
+
+    // === Loading spinner ===
+
+    pub const fn display_loading_spinner(&mut self, label: &'static str) {
+        self.loading = Some(label);
+    }
+
+    pub const fn hide_loading_spinner(&mut self) {
+        self.loading = None;
+    }
+
+    // === Cache ===
+
+    /// A clone of the value cached under `key`, if it has type `T`.
+    pub fn cached<T: Clone + 'static>(&self, key: &str) -> Option<T> {
+        let value = self
+            .cache
+            .get(key)
+            .and_then(|value| value.downcast_ref::<T>())
+            .cloned();
+        self.middleware.on_cache(key, value.is_some());
+        value
+    }
+
+    pub fn cache<T: 'static>(&mut self, key: impl Into<String>, value: T) {
+        self.cache.insert(key.into(), Box::new(value));
+    }
+
+    /// Cache a value holding `sensitive` text, e.g. a secret payload, which
+    /// is masked in the log and error messages while it is cached.
+    pub fn cache_sensitive<T: 'static>(
+        &mut self,
+        key: impl Into<String>,
+        value: T,
+        sensitive: &str,
+    ) {
+        let key = key.into();
+        redact::register(&self.redact_scope, &key, sensitive);
+        self.cache(key, value);
+    }
+
+    pub fn invalidate(&mut self, key: &str) {
+        redact::forget(&self.redact_scope, key);
+        self.cache.remove(key);
+    }
+
+    pub fn clear_cache(&mut self) {
+        for key in self.cache.keys() {
+            redact::forget(&self.redact_scope, key);
+        }
+        self.cache.clear();
+    }
+
+    // === Message processing ===
+
+    /// Queue a message from the overlay or current screen.
+    fn queue_from_view(&self, msg: M) {
+        self.queue(S::gate(self, msg));
+    }
+}
+
+impl<S: ServiceState<M>, M: Debug + 'static> Service for ServiceShell<S, M> {
+    fn init(&mut self) {
+        S::init(self);
+    }
+
+    fn destroy(&mut self) {
+        for (_, token) in self.view_tokens.drain(..) {
+            token.cancel();
+        }
+        self.clear_cache();
+    }
+
+    fn set_middleware(&mut self, middleware: MiddlewareStack) {
+        self.middleware = middleware;
+    }
+
+    fn switch_context(&mut self, context: &CloudContext) -> bool {
+        S::switch_context(self, context)
+    }
+
+    fn open_resource(&mut self, resource: &str) -> bool {
+        S::open_resource(self, resource)
+    }
+
+    fn current_resource(&self) -> Option<String> {
+        S::current_resource(self)
+    }
+
+    fn handle_tick(&mut self) {
+        if self.loading.is_some() {
+            self.spinner.handle_tick();
+        }
+        S::handle_tick(self);
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) -> EventResult<()> {
+        if self.loading.is_some() {
+            return EventResult::Ignored;
+        }
+
+        // Handle modal first if present (captures all input)
+        if let Some(modal) = &mut self.modal {
+            let (consumed, msg) = modal.handle_key(key).process();
+            if let Some(msg) = msg {
+                self.queue_from_view(msg);
+            }
+            if consumed {
+                return EventResult::Consumed;
+            }
+        }
+
+        // Handle current screen
+        if let Some(screen) = self.screen_stack.last_mut() {
+            let (consumed, msg) = screen.handle_key(key).process();
+            if let Some(msg) = msg {
+                self.queue_from_view(msg);
+            }
+            if consumed {
+                return EventResult::Consumed;
+            }
+        }
+
+        if S::handle_key(self, key).is_consumed() {
+            return EventResult::Consumed;
+        }
+
+        // Global navigation
+        if self.resolver.matches_global(&key, GlobalAction::Back) {
+            if !self.pop_view() {
+                self.closing = true;
+            }
+            return EventResult::Consumed;
+        }
+
+        EventResult::Ignored
+    }
+
+    fn handle_paste(&mut self, text: &str) -> EventResult<()> {
+        if self.loading.is_some() {
+            return EventResult::Ignored;
+        }
+
+        // A paste never falls through a modal to the screen below
+        if let Some(modal) = &mut self.modal {
+            let (_, msg) = modal.handle_paste(text).process();
+            if let Some(msg) = msg {
+                self.queue_from_view(msg);
+            }
+            return EventResult::Consumed;
+        }
+
+        if let Some(screen) = self.screen_stack.last_mut() {
+            let (consumed, msg) = screen.handle_paste(text).process();
+            if let Some(msg) = msg {
+                self.queue_from_view(msg);
+            }
+            if consumed {
+                return EventResult::Consumed;
+            }
+        }
+
+        EventResult::Ignored
+    }
+
+    fn update(&mut self) -> Result<ServiceMsg> {
+        // Closing parks the service, which must work again when resumed
+        if std::mem::take(&mut self.closing) {
+            return Ok(ServiceMsg::Close);
+        }
+
+        let mut commands: Vec<Box<dyn Command>> = Vec::new();
+
+        while let Ok(msg) = self.msg_rx.try_recv() {
+            // Formatting every message is only worth it if someone listens
+            let name = (!self.middleware.is_empty()).then(|| message_name(&msg));
+            let started = Instant::now();
+            let result = S::update(self, msg);
+            if let Some(name) = name {
+                self.middleware
+                    .on_message(self.state.service_key(), &name, started.elapsed());
+            }
+            match result? {
+                ServiceMsg::Idle => {}
+                ServiceMsg::Run(cmds) => commands.extend(cmds),
+                ServiceMsg::Close => return Ok(ServiceMsg::Close),
+            }
+        }
+
+        if commands.is_empty() {
+            Ok(ServiceMsg::Idle)
+        } else {
+            Ok(ServiceMsg::Run(commands))
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if let Some(label) = self.loading {
+            self.spinner.set_label(label);
+            self.spinner.render(frame, area, theme);
+        } else if let Some(screen) = self.screen_stack.last_mut() {
+            screen.render(frame, area, theme);
+        }
+
+        // Render modal on top if present
+        if let Some(modal) = &mut self.modal {
+            modal.render(frame, area, theme);
+        }
+    }
+
+    fn breadcrumbs(&self) -> Vec<String> {
+        let mut bc = vec![self.state.name().to_string()];
+        for screen in &self.screen_stack {
+            bc.extend(screen.breadcrumbs());
+        }
+        bc
+    }
+
+    fn keybindings(&self) -> Vec<Keybinding> {
+        let mut keybindings = self
+            .current_screen()
+            .map(Screen::keybindings)
+            .unwrap_or_default();
+        S::keybindings(self, &mut keybindings);
+        keybindings
+    }
+
+    fn action_context(&self) -> ActionContext {
+        let mut ctx = self
+            .current_screen()
+            .map(Screen::action_context)
+            .unwrap_or_default();
+        ctx.service = Some(self.state.service_key().to_string());
+        ctx
+    }
+
+    fn unsaved_work(&self) -> Vec<String> {
+        S::unsaved_work(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    use super::*;
+    use crate::config::keybindings::KeybindingsConfig;
+
+    struct MockState;
+
+    impl ServiceState<&'static str> for MockState {
+        fn name(&self) -> &'static str {
+            "Mock"
+        }
+
+        fn service_key(&self) -> &'static str {
+            "mock"
+        }
+
+        fn init(shell: &mut ServiceShell<Self, &'static str>) {
+            shell.queue("open");
+        }
+
+        fn update(
+            shell: &mut ServiceShell<Self, &'static str>,
+            msg: &'static str,
+        ) -> Result<ServiceMsg> {
+            shell.push_view(MockScreen(msg));
+            Ok(ServiceMsg::Idle)
+        }
+    }
+
+    struct MockScreen(&'static str);
+
+    impl Screen for MockScreen {
+        type Output = &'static str;
+
+        fn handle_key(&mut self, _key: KeyEvent) -> Result<EventResult<Self::Output>> {
+            Ok(EventResult::Ignored)
+        }
+
+        fn render(&mut self, _frame: &mut Frame, _area: Rect, _theme: &Theme) {}
+
+        fn breadcrumbs(&self) -> Vec<String> {
+            vec![self.0.to_string()]
+        }
+    }
+
+    fn shell() -> ServiceShell<MockState, &'static str> {
+        let resolver = KeyResolver::new(Arc::new(KeybindingsConfig::default()));
+        ServiceShell::new(MockState, Arc::default(), Arc::new(resolver))
+    }
+
+    #[test]
+    fn test_back_pops_screens_then_closes() {
+        let mut shell = shell();
+        shell.init();
+        shell.update().unwrap();
+        shell.queue("details");
+        shell.update().unwrap();
+        assert_eq!(shell.breadcrumbs(), vec!["Mock", "open", "details"]);
+
+        let back = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        assert!(shell.handle_key(back).is_consumed());
+        assert!(matches!(shell.update().unwrap(), ServiceMsg::Idle));
+        assert_eq!(shell.breadcrumbs(), vec!["Mock", "open"]);
+
+        assert!(shell.handle_key(back).is_consumed());
+        assert!(matches!(shell.update().unwrap(), ServiceMsg::Close));
+    }
+
+    #[test]
+    fn test_streams_stop_with_their_screen() {
+        let mut shell = shell();
+        shell.init();
+        shell.update().unwrap();
+        let root = shell.view_token();
+        shell.queue("logs");
+        shell.update().unwrap();
+        let logs = shell.view_token();
+
+        shell.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(logs.is_cancelled());
+        assert!(!root.is_cancelled());
+
+        shell.destroy();
+        assert!(root.is_cancelled());
+    }
+
+    #[test]
+    fn test_cache_is_typed() {
+        let mut shell = shell();
+        shell.cache("secrets", vec!["api-key".to_string()]);
+        assert_eq!(
+            shell.cached::<Vec<String>>("secrets"),
+            Some(vec!["api-key".to_string()])
+        );
+        assert_eq!(shell.cached::<String>("secrets"), None);
+
+        shell.invalidate("secrets");
+        assert_eq!(shell.cached::<Vec<String>>("secrets"), None);
+    }
+}
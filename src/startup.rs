@@ -0,0 +1,83 @@
+//! Startup time measurement.
+//!
+//! Phases are always recorded since it is cheap; the report is only printed
+//! when lazycloud is started with `--profile-startup`.
+
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+use tracing::debug;
+
+pub struct StartupProfile {
+    enabled: bool,
+    start: Instant,
+    phases: Vec<(String, Duration)>,
+}
+
+impl StartupProfile {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            start: Instant::now(),
+            phases: Vec::new(),
+        }
+    }
+
+    pub const fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Time since the profile was created, i.e. since launch.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Run `f` and record how long it took under `phase`.
+    pub fn measure<T>(&mut self, phase: &str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.record(phase, start.elapsed());
+        result
+    }
+
+    pub fn record(&mut self, phase: impl Into<String>, duration: Duration) {
+        let phase = phase.into();
+        debug!(phase, ?duration, "Startup phase");
+        self.phases.push((phase, duration));
+    }
+
+    /// Human-readable table of all recorded phases.
+    pub fn report(&self) -> String {
+        let width = self
+            .phases
+            .iter()
+            .map(|(phase, _)| phase.len())
+            .max()
+            .unwrap_or(0);
+        let mut out = String::from("Startup profile:\n");
+        for (phase, duration) in &self.phases {
+            let millis = duration.as_secs_f64() * 1000.0;
+            let _ = writeln!(out, "  {phase:<width$}  {millis:>9.1} ms");
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_aligns_phases() {
+        let mut profile = StartupProfile::new(true);
+        profile.record("config load", Duration::from_micros(1500));
+        profile.record("first frame", Duration::from_millis(42));
+
+        let expected = "\
+Startup profile:
+  config load        1.5 ms
+  first frame       42.0 ms
+";
+        assert_eq!(profile.report(), expected);
+    }
+}
@@ -0,0 +1,185 @@
+//! Terraform snippet generation for cloud resources.
+//!
+//! Each resource kind implements [`TerraformTemplate`] to describe its
+//! resource block; this module takes care of rendering valid HCL and the
+//! matching `terraform import` command.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// A value on the right-hand side of an HCL attribute, or a nested block.
+#[derive(Debug, Clone)]
+pub enum HclValue {
+    /// A quoted string literal.
+    Str(String),
+    /// An unquoted expression (references, variables, booleans).
+    Raw(String),
+    /// A map of string literals, e.g. `labels`.
+    Map(BTreeMap<String, String>),
+    /// A nested block. Repeat the key to emit the block several times.
+    Block(Vec<(String, Self)>),
+}
+
+/// A rendered-on-demand Terraform resource block plus its import id.
+#[derive(Debug, Clone)]
+pub struct TerraformSnippet {
+    resource_type: &'static str,
+    name: String,
+    import_id: String,
+    attributes: Vec<(String, HclValue)>,
+}
+
+impl TerraformSnippet {
+    /// Create a snippet for `resource_type`. The local name is sanitized
+    /// into a valid Terraform identifier.
+    pub fn new(resource_type: &'static str, name: &str, import_id: impl Into<String>) -> Self {
+        Self {
+            resource_type,
+            name: resource_name(name),
+            import_id: import_id.into(),
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Append an attribute or nested block.
+    #[must_use]
+    pub fn attr(mut self, key: &str, value: HclValue) -> Self {
+        self.attributes.push((key.to_string(), value));
+        self
+    }
+
+    /// The resource address, e.g. `google_secret_manager_secret.db_password`.
+    pub fn address(&self) -> String {
+        format!("{}.{}", self.resource_type, self.name)
+    }
+
+    /// Render the resource block followed by the `terraform import` command.
+    pub fn render(&self) -> String {
+        let mut out = format!("resource \"{}\" \"{}\" {{\n", self.resource_type, self.name);
+        render_attributes(&mut out, &self.attributes, 1);
+        out.push_str("}\n\n");
+        let _ = writeln!(
+            out,
+            "terraform import {} {}",
+            self.address(),
+            self.import_id
+        );
+        out
+    }
+}
+
+/// A resource that can be exported as Terraform.
+pub trait TerraformTemplate {
+    fn terraform(&self) -> TerraformSnippet;
+}
+
+/// Turn an arbitrary resource name into a valid Terraform identifier.
+pub fn resource_name(name: &str) -> String {
+    let mut ident: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !ident.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        ident.insert(0, '_');
+    }
+    ident
+}
+
+fn render_attributes(out: &mut String, attributes: &[(String, HclValue)], depth: usize) {
+    let indent = "  ".repeat(depth);
+    let width = attributes
+        .iter()
+        .filter(|(_, value)| !matches!(value, HclValue::Block(_)))
+        .map(|(key, _)| key.len())
+        .max()
+        .unwrap_or(0);
+
+    for (key, value) in attributes {
+        match value {
+            HclValue::Str(s) => {
+                let _ = writeln!(out, "{indent}{key:<width$} = {}", quote(s));
+            }
+            HclValue::Raw(expr) => {
+                let _ = writeln!(out, "{indent}{key:<width$} = {expr}");
+            }
+            HclValue::Map(map) => {
+                let _ = writeln!(out, "{indent}{key:<width$} = {{");
+                for (k, v) in map {
+                    let _ = writeln!(out, "{indent}  {} = {}", quote(k), quote(v));
+                }
+                let _ = writeln!(out, "{indent}}}");
+            }
+            HclValue::Block(inner) if inner.is_empty() => {
+                let _ = writeln!(out, "{indent}{key} {{}}");
+            }
+            HclValue::Block(inner) => {
+                let _ = writeln!(out, "{indent}{key} {{");
+                render_attributes(out, inner, depth + 1);
+                let _ = writeln!(out, "{indent}}}");
+            }
+        }
+    }
+}
+
+fn quote(s: &str) -> String {
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace("${", "$${");
+    format!("\"{escaped}\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_name_sanitizes() {
+        assert_eq!(resource_name("db-password"), "db_password");
+        assert_eq!(resource_name("API.Key"), "api_key");
+        assert_eq!(resource_name("1st-secret"), "_1st_secret");
+    }
+
+    #[test]
+    fn test_render_snippet() {
+        let snippet =
+            TerraformSnippet::new("google_thing", "my-thing", "projects/p/things/my-thing")
+                .attr("name", HclValue::Str("my-thing".to_string()))
+                .attr("project", HclValue::Str("p".to_string()))
+                .attr(
+                    "labels",
+                    HclValue::Map(BTreeMap::from([("env".to_string(), "prod".to_string())])),
+                )
+                .attr(
+                    "replication",
+                    HclValue::Block(vec![("auto".to_string(), HclValue::Block(Vec::new()))]),
+                );
+
+        let expected = "\
+resource \"google_thing\" \"my_thing\" {
+  name    = \"my-thing\"
+  project = \"p\"
+  labels  = {
+    \"env\" = \"prod\"
+  }
+  replication {
+    auto {}
+  }
+}
+
+terraform import google_thing.my_thing projects/p/things/my-thing
+";
+        assert_eq!(snippet.render(), expected);
+    }
+
+    #[test]
+    fn test_quote_escapes_interpolation() {
+        assert_eq!(quote("a\"b${c}"), "\"a\\\"b$${c}\"");
+    }
+}
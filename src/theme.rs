@@ -1,5 +1,6 @@
 use catppuccin::PALETTE;
 use ratatui::style::Color;
+use ratatui::symbols::Marker;
 use ratatui::widgets::BorderType;
 
 /// Convert a catppuccin color to a ratatui color.
@@ -12,7 +13,7 @@ const fn catppuccin_to_color(c: &catppuccin::Color) -> Color {
 /// This struct holds all color values directly, making it independent of any
 /// specific color palette. Use the provided factory functions like `catppuccin_mocha()`
 /// to create pre-configured themes, or build custom themes by setting colors directly.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Theme {
     // Base colors
     pub base: Color,
@@ -51,6 +52,107 @@ pub struct Theme {
     pub lavender: Color,
 
     pub border_type: BorderType,
+    pub symbols: &'static Symbols,
+}
+
+/// Glyphs used for decoration, so they can fall back to plain ASCII.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbols {
+    /// Prefix of the selected row in lists and tables.
+    pub highlight: &'static str,
+    pub success: &'static str,
+    pub failure: &'static str,
+    pub info: &'static str,
+    pub warning: &'static str,
+    pub running: &'static str,
+    pub history: &'static str,
+    pub ready: &'static str,
+    pub pending: &'static str,
+    pub bullet: &'static str,
+    pub ellipsis: &'static str,
+    /// Vertical separator between inline sections.
+    pub separator: &'static str,
+    /// Separator within a line of details, e.g. `2s · 5m ago`.
+    pub middle_dot: &'static str,
+    /// Placeholder for an empty value.
+    pub none: &'static str,
+    pub arrow: &'static str,
+    /// Markers of open and closed branches in trees.
+    pub expanded: &'static str,
+    pub collapsed: &'static str,
+    /// Horizontal rule around section titles.
+    pub rule: &'static str,
+    /// Whether service icons, which are often emoji, are shown.
+    pub icons: bool,
+    pub spinner: &'static [&'static str],
+    /// Frames of the running command indicator.
+    pub progress: &'static [&'static str],
+    /// Bar heights from empty to full in eighths.
+    pub bars: [&'static str; 9],
+    /// Done and remaining cells of progress bars.
+    pub gauge_filled: &'static str,
+    pub gauge_empty: &'static str,
+    pub chart_marker: Marker,
+}
+
+impl Symbols {
+    pub const UNICODE: Self = Self {
+        highlight: "▶ ",
+        success: "✓",
+        failure: "✗",
+        info: "ℹ",
+        warning: "⚠",
+        running: "⚡",
+        history: "📋",
+        ready: "●",
+        pending: "◌",
+        bullet: "•",
+        ellipsis: "…",
+        separator: "│",
+        middle_dot: "·",
+        none: "—",
+        arrow: "↔",
+        expanded: "▾",
+        collapsed: "▸",
+        rule: "──",
+        icons: true,
+        spinner: &["⠷", "⠯", "⠟", "⠻", "⠽", "⠾"],
+        progress: &["▰▱▱", "▰▰▱", "▰▰▰", "▱▰▰"],
+        bars: [" ", "▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"],
+        gauge_filled: "█",
+        gauge_empty: "░",
+        chart_marker: Marker::Braille,
+    };
+
+    /// For terminals and screen readers that cannot handle box drawing,
+    /// emoji or Braille.
+    pub const ASCII: Self = Self {
+        highlight: "> ",
+        success: "+",
+        failure: "x",
+        info: "i",
+        warning: "!",
+        running: "*",
+        history: "#",
+        ready: "*",
+        pending: "o",
+        bullet: "*",
+        ellipsis: "...",
+        separator: "|",
+        middle_dot: "-",
+        none: "-",
+        arrow: "<>",
+        expanded: "-",
+        collapsed: "+",
+        rule: "--",
+        icons: false,
+        spinner: &["|", "/", "-", "\\"],
+        progress: &["*..", "**.", "***", ".**"],
+        bars: [" ", ".", ".", ":", ":", "=", "=", "#", "#"],
+        gauge_filled: "#",
+        gauge_empty: "-",
+        chart_marker: Marker::Dot,
+    };
 }
 
 impl Theme {
@@ -85,6 +187,7 @@ impl Theme {
             blue: catppuccin_to_color(&c.blue),
             lavender: catppuccin_to_color(&c.lavender),
             border_type: BorderType::Rounded,
+            symbols: &Symbols::UNICODE,
         }
     }
 
@@ -112,6 +215,104 @@ impl Theme {
         Self::from_catppuccin(&PALETTE.macchiato)
     }
 
+    /// Pure colors on black with thick borders, for low vision or washed out
+    /// displays.
+    #[must_use]
+    pub const fn high_contrast() -> Self {
+        let black = Color::Rgb(0, 0, 0);
+        let white = Color::Rgb(255, 255, 255);
+        let gray = Color::Rgb(200, 200, 200);
+        let yellow = Color::Rgb(255, 255, 0);
+        let cyan = Color::Rgb(0, 255, 255);
+        let magenta = Color::Rgb(255, 128, 255);
+        let green = Color::Rgb(0, 255, 0);
+        let red = Color::Rgb(255, 80, 80);
+        let orange = Color::Rgb(255, 170, 0);
+        let blue = Color::Rgb(100, 180, 255);
+        Self {
+            base: black,
+            mantle: black,
+            crust: black,
+            surface0: Color::Rgb(40, 40, 40),
+            // Borders and selection background, white text must stay readable
+            surface1: Color::Rgb(0, 70, 160),
+            surface2: gray,
+            overlay0: gray,
+            overlay1: gray,
+            overlay2: white,
+            text: white,
+            subtext0: gray,
+            subtext1: white,
+            rosewater: white,
+            flamingo: magenta,
+            pink: magenta,
+            mauve: magenta,
+            red,
+            maroon: red,
+            peach: orange,
+            yellow,
+            green,
+            teal: cyan,
+            sky: cyan,
+            sapphire: blue,
+            blue,
+            lavender: yellow,
+            border_type: BorderType::Thick,
+            symbols: &Symbols::UNICODE,
+        }
+    }
+
+    /// The terminal's default colors and ASCII symbols, for `NO_COLOR` and
+    /// `--no-color`.
+    #[must_use]
+    pub const fn no_color() -> Self {
+        let none = Color::Reset;
+        Self {
+            base: none,
+            mantle: none,
+            crust: none,
+            surface0: none,
+            surface1: none,
+            surface2: none,
+            overlay0: none,
+            overlay1: none,
+            overlay2: none,
+            text: none,
+            subtext0: none,
+            subtext1: none,
+            rosewater: none,
+            flamingo: none,
+            pink: none,
+            mauve: none,
+            red: none,
+            maroon: none,
+            peach: none,
+            yellow: none,
+            green: none,
+            teal: none,
+            sky: none,
+            sapphire: none,
+            blue: none,
+            lavender: none,
+            border_type: BorderType::Plain,
+            symbols: &Symbols::ASCII,
+        }
+    }
+
+    /// The same colors with plain line borders, which every terminal font
+    /// draws and which are cheap to send.
+    #[must_use]
+    pub const fn with_plain_borders(mut self) -> Self {
+        self.border_type = BorderType::Plain;
+        self
+    }
+
+    /// Whether colors are disabled, so emphasis must come from modifiers.
+    #[must_use]
+    pub const fn is_monochrome(&self) -> bool {
+        matches!(self.text, Color::Reset)
+    }
+
     // Base colors
     #[must_use]
     pub const fn base(&self) -> Color {
@@ -344,6 +545,7 @@ pub fn available_themes() -> Vec<ThemeInfo> {
         ThemeInfo::new("Catppuccin Macchiato", Theme::catppuccin_macchiato()),
         ThemeInfo::new("Catppuccin Frappé", Theme::catppuccin_frappe()),
         ThemeInfo::new("Catppuccin Latte", Theme::catppuccin_latte()),
+        ThemeInfo::new("High Contrast", Theme::high_contrast()),
     ]
 }
 
@@ -356,6 +558,12 @@ pub fn theme_from_name(name: &str) -> Theme {
         .unwrap_or_default()
 }
 
+/// Whether colors are turned off by `--no-color` or a non-empty `NO_COLOR`
+/// environment variable (<https://no-color.org>).
+pub fn no_color_requested(flag: bool) -> bool {
+    flag || std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
 /// Get the name of a theme that matches the given theme, if any.
 #[allow(dead_code)]
 pub fn theme_name(theme: &Theme) -> Option<&'static str> {
@@ -379,6 +587,7 @@ use ratatui::style::{Modifier, Style};
 use ratatui::widgets::{Block, Borders, Clear, ListItem};
 
 use crate::config::KeyResolver;
+use crate::i18n::t;
 use crate::ui::{Component, EventResult, List, ListEvent, ListRow, Result};
 
 impl ListRow for ThemeInfo {
@@ -432,14 +641,17 @@ impl Component for ThemeSelectorView {
 
         // Render block background
         let block = Block::default()
-            .title(" Select Theme (Enter to confirm, Esc to cancel) ")
+            .title(format!(
+                " {} ",
+                t("Select Theme (Enter to confirm, Esc to cancel)")
+            ))
             .title_style(
                 Style::default()
                     .fg(theme.mauve())
                     .add_modifier(Modifier::BOLD),
             )
             .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
+            .border_type(theme.border_type)
             .border_style(Style::default().fg(theme.lavender()))
             .style(Style::default().bg(theme.base()));
 
@@ -0,0 +1,487 @@
+//! Formatting of timestamps in the timezone chosen with `display.timezone`.
+//!
+//! Clients and screens format every timestamp through this module, so the
+//! whole interface agrees on one timezone, which the status bar shows. Named
+//! zones are read from the system's zoneinfo database (`TZDIR`, or
+//! `/usr/share/zoneinfo`), including the rule for years past its last
+//! transition.
+
+use std::path::PathBuf;
+use std::sync::{LazyLock, PoisonError, RwLock};
+use std::{env, fmt, fs};
+
+use chrono::{
+    DateTime,
+    Datelike,
+    FixedOffset,
+    Local,
+    NaiveDate,
+    NaiveDateTime,
+    NaiveTime,
+    Offset,
+    TimeDelta,
+    Utc,
+};
+use color_eyre::Result;
+use color_eyre::eyre::{bail, eyre};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The `display.timezone` setting: `utc`, `local` or an IANA name such as
+/// `Europe/Zurich`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum DisplayTimezone {
+    #[default]
+    Utc,
+    Local,
+    Named(String),
+}
+
+impl fmt::Display for DisplayTimezone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Utc => f.write_str("utc"),
+            Self::Local => f.write_str("local"),
+            Self::Named(name) => f.write_str(name),
+        }
+    }
+}
+
+impl From<&str> for DisplayTimezone {
+    fn from(value: &str) -> Self {
+        match value.to_ascii_lowercase().as_str() {
+            "utc" => Self::Utc,
+            "local" => Self::Local,
+            _ => Self::Named(value.to_string()),
+        }
+    }
+}
+
+impl Serialize for DisplayTimezone {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for DisplayTimezone {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        Ok(String::deserialize(deserializer)?.as_str().into())
+    }
+}
+
+/// A [`DisplayTimezone`] ready for conversions.
+#[derive(Debug, Clone)]
+enum Timezone {
+    Utc,
+    Local,
+    Named { name: String, zone: Zone },
+}
+
+static TIMEZONE: LazyLock<RwLock<Timezone>> = LazyLock::new(|| RwLock::new(Timezone::Utc));
+
+/// Use `timezone` for all timestamps formatted from now on.
+///
+/// # Errors
+///
+/// Returns an error if a named zone can't be loaded; UTC is used instead.
+pub fn set_timezone(timezone: &DisplayTimezone) -> Result<()> {
+    let (resolved, result) = match timezone {
+        DisplayTimezone::Utc => (Timezone::Utc, Ok(())),
+        DisplayTimezone::Local => (Timezone::Local, Ok(())),
+        DisplayTimezone::Named(name) => match Zone::load(name) {
+            Ok(zone) => (
+                Timezone::Named {
+                    name: name.clone(),
+                    zone,
+                },
+                Ok(()),
+            ),
+            Err(err) => (Timezone::Utc, Err(err)),
+        },
+    };
+    *TIMEZONE.write().unwrap_or_else(PoisonError::into_inner) = resolved;
+    result
+}
+
+/// Name of the active timezone for the status bar.
+pub fn timezone_label() -> String {
+    match &*TIMEZONE.read().unwrap_or_else(PoisonError::into_inner) {
+        Timezone::Utc => "UTC".to_string(),
+        Timezone::Local => Local::now().format("UTC%:z").to_string(),
+        Timezone::Named { name, .. } => name.clone(),
+    }
+}
+
+/// `at` in the active timezone.
+fn localize(at: DateTime<Utc>) -> DateTime<FixedOffset> {
+    let offset = match &*TIMEZONE.read().unwrap_or_else(PoisonError::into_inner) {
+        Timezone::Utc => Utc.fix(),
+        Timezone::Local => at.with_timezone(&Local).offset().fix(),
+        Timezone::Named { zone, .. } => zone.offset_at(at.timestamp()),
+    };
+    at.with_timezone(&offset)
+}
+
+/// Date and time to the minute, e.g. `2024-03-01 14:30`.
+pub fn format_timestamp(at: DateTime<Utc>) -> String {
+    format_with(at, "%Y-%m-%d %H:%M")
+}
+
+/// Like [`format_timestamp`], from seconds since the epoch.
+pub fn format_epoch(seconds: i64) -> String {
+    DateTime::from_timestamp(seconds, 0).map_or_else(|| "Unknown".to_string(), format_timestamp)
+}
+
+/// A duration in the largest whole unit, e.g. `90 days` or `25 hours`.
+pub fn format_period(seconds: u64) -> String {
+    let (amount, unit) = [(86_400, "day"), (3_600, "hour"), (60, "minute")]
+        .into_iter()
+        .find(|(size, _)| seconds >= *size && seconds.is_multiple_of(*size))
+        .map_or((seconds, "second"), |(size, unit)| (seconds / size, unit));
+    if amount == 1 {
+        format!("{amount} {unit}")
+    } else {
+        format!("{amount} {unit}s")
+    }
+}
+
+/// Time of day to the second, e.g. `14:30:05`.
+pub fn format_clock(at: DateTime<Utc>) -> String {
+    format_with(at, "%H:%M:%S")
+}
+
+/// `at` with a custom `chrono` format, e.g. for chart axes.
+pub fn format_with(at: DateTime<Utc>, format: &str) -> String {
+    localize(at).format(format).to_string()
+}
+
+// === Parsing ===
+
+/// Parse a point in time typed by the user: a duration from `now` such as
+/// `+30d`, `12h` or `-2w`, a date or date and time in the active timezone
+/// such as `2030-01-31` or `2030-01-31 14:30`, or RFC 3339.
+pub fn parse_moment(input: &str, now: DateTime<Utc>) -> std::result::Result<DateTime<Utc>, String> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(input) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(input, "%Y-%m-%d") {
+        return Ok(from_local(date.and_time(NaiveTime::MIN)));
+    }
+    if let Ok(at) = NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M") {
+        return Ok(from_local(at));
+    }
+    parse_duration(input)
+        .and_then(|offset| now.checked_add_signed(offset))
+        .ok_or_else(|| format!("Invalid date or duration '{input}'"))
+}
+
+/// `[+-]<amount><unit>` with the unit one of `m`, `h`, `d` or `w`.
+fn parse_duration(input: &str) -> Option<TimeDelta> {
+    let (sign, input) = match input.as_bytes().first()? {
+        b'-' => (-1, &input[1..]),
+        b'+' => (1, &input[1..]),
+        _ => (1, input),
+    };
+    let unit_at = input.char_indices().last()?.0;
+    let (amount, unit) = input.split_at(unit_at);
+    let amount = sign * amount.parse::<i64>().ok()?;
+    match unit {
+        "m" => TimeDelta::try_minutes(amount),
+        "h" => TimeDelta::try_hours(amount),
+        "d" => TimeDelta::try_days(amount),
+        "w" => TimeDelta::try_weeks(amount),
+        _ => None,
+    }
+}
+
+/// The instant `at` shows in the active timezone.
+fn from_local(at: NaiveDateTime) -> DateTime<Utc> {
+    let offset = localize(at.and_utc()).offset().local_minus_utc();
+    at.and_utc() - TimeDelta::seconds(i64::from(offset))
+}
+
+// === Zoneinfo ===
+
+/// Offsets of a named zone from its `TZif` file.
+#[derive(Debug, Clone)]
+struct Zone {
+    /// Start of each period and its offset from UTC in seconds, in order.
+    transitions: Vec<(i64, i32)>,
+    /// Offset before the first transition.
+    initial: i32,
+    /// Rule for times after the last transition, from the file's footer.
+    rule: Option<Rule>,
+}
+
+impl Zone {
+    fn load(name: &str) -> Result<Self> {
+        if name.split('/').any(|part| part.is_empty() || part == "..") {
+            bail!("Invalid timezone '{name}'");
+        }
+        let dir = env::var_os("TZDIR")
+            .map_or_else(|| PathBuf::from("/usr/share/zoneinfo"), PathBuf::from);
+        let data =
+            fs::read(dir.join(name)).map_err(|err| eyre!("Unknown timezone '{name}': {err}"))?;
+        Self::parse(&data).ok_or_else(|| eyre!("Unreadable zoneinfo file for '{name}'"))
+    }
+
+    /// Parse `TZif` data, preferring the 64-bit section of version 2 and later.
+    fn parse(data: &[u8]) -> Option<Self> {
+        let counts = tzif_counts(data)?;
+        let (data, time_size) = if *data.get(4)? >= b'2' {
+            let [isut, isstd, leap, time, types, chars] = counts;
+            let v1_len = 44 + time * 5 + types * 6 + chars + leap * 8 + isstd + isut;
+            (data.get(v1_len..)?, 8)
+        } else {
+            (data, 4)
+        };
+        let [isut, isstd, leap, time, types, chars] = tzif_counts(data)?;
+        let body = data.get(44..)?;
+
+        let times = body.get(..time * time_size)?;
+        let indices = body.get(time * time_size..time * (time_size + 1))?;
+        let infos_start = time * (time_size + 1);
+        let infos = body.get(infos_start..infos_start + types * 6)?;
+        let offset = |index: usize| {
+            let info = infos.get(index * 6..index * 6 + 4)?;
+            Some(i32::from_be_bytes(info.try_into().ok()?))
+        };
+        let transitions = times
+            .chunks_exact(time_size)
+            .zip(indices)
+            .map(|(at, &index)| {
+                let at = if time_size == 8 {
+                    i64::from_be_bytes(at.try_into().ok()?)
+                } else {
+                    i64::from(i32::from_be_bytes(at.try_into().ok()?))
+                };
+                Some((at, offset(usize::from(index))?))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        // The footer is a POSIX TZ string between newlines after the data
+        let footer_start = infos_start + types * 6 + chars + leap * (time_size + 4) + isstd + isut;
+        let rule = (time_size == 8)
+            .then(|| body.get(footer_start..))
+            .flatten()
+            .and_then(|footer| std::str::from_utf8(footer).ok())
+            .and_then(|footer| Rule::parse(footer.trim_matches('\n')));
+
+        Some(Self {
+            transitions,
+            initial: offset(0)?,
+            rule,
+        })
+    }
+
+    fn offset_at(&self, timestamp: i64) -> FixedOffset {
+        let after = self.transitions.partition_point(|(at, _)| *at <= timestamp);
+        let seconds = match (after.checked_sub(1), &self.rule) {
+            (Some(last), Some(rule)) if last + 1 == self.transitions.len() => {
+                rule.offset_at(timestamp)
+            }
+            (Some(index), _) => self.transitions[index].1,
+            (None, _) => self.initial,
+        };
+        FixedOffset::east_opt(seconds).unwrap_or_else(|| Utc.fix())
+    }
+}
+
+/// The six counts of a `TZif` header.
+fn tzif_counts(data: &[u8]) -> Option<[usize; 6]> {
+    if data.get(..4)? != b"TZif" {
+        return None;
+    }
+    let mut counts = [0; 6];
+    for (i, count) in counts.iter_mut().enumerate() {
+        let at = 20 + i * 4;
+        *count =
+            usize::try_from(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?)).ok()?;
+    }
+    Some(counts)
+}
+
+/// A POSIX TZ rule such as `CET-1CEST,M3.5.0,M10.5.0/3`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rule {
+    /// Standard offset east of UTC in seconds.
+    std: i32,
+    /// Daylight saving offset and its start and end, if observed.
+    dst: Option<(i32, Transition, Transition)>,
+}
+
+/// Day and local time a rule switches, e.g. `M3.5.0/2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Transition {
+    month: u32,
+    /// 1 to 4, or 5 for the last such weekday of the month.
+    week: u32,
+    /// Days from Sunday.
+    weekday: u32,
+    /// Seconds after local midnight.
+    time: i32,
+}
+
+impl Rule {
+    fn parse(rule: &str) -> Option<Self> {
+        let rest = skip_name(rule)?;
+        let (std, rest) = parse_offset(rest)?;
+        // POSIX offsets count west of UTC
+        let std = -std;
+        if rest.is_empty() {
+            return Some(Self { std, dst: None });
+        }
+        let rest = skip_name(rest)?;
+        // Daylight saving time is an hour ahead unless given
+        let (dst, rest) = if rest.starts_with(',') {
+            (std + 3600, rest)
+        } else {
+            let (dst, rest) = parse_offset(rest)?;
+            (-dst, rest)
+        };
+        let mut transitions = rest.strip_prefix(',')?.split(',');
+        let start = Transition::parse(transitions.next()?)?;
+        let end = Transition::parse(transitions.next()?)?;
+        Some(Self {
+            std,
+            dst: Some((dst, start, end)),
+        })
+    }
+
+    fn offset_at(&self, timestamp: i64) -> i32 {
+        let Some((dst, start, end)) = self.dst else {
+            return self.std;
+        };
+        let Some(year) =
+            DateTime::from_timestamp(timestamp + i64::from(self.std), 0).map(|at| at.year())
+        else {
+            return self.std;
+        };
+        // Each switch happens at local time before it takes effect
+        let (Some(start), Some(end)) = (start.at(year, self.std), end.at(year, dst)) else {
+            return self.std;
+        };
+        let in_dst = if start < end {
+            (start..end).contains(&timestamp)
+        } else {
+            !(end..start).contains(&timestamp)
+        };
+        if in_dst { dst } else { self.std }
+    }
+}
+
+impl Transition {
+    fn parse(spec: &str) -> Option<Self> {
+        let (date, time) = spec.split_once('/').unwrap_or((spec, "2"));
+        let mut parts = date.strip_prefix('M')?.split('.');
+        let month = parts.next()?.parse().ok()?;
+        let week = parts.next()?.parse().ok()?;
+        let weekday = parts.next()?.parse().ok()?;
+        let (time, rest) = parse_offset(time)?;
+        rest.is_empty().then_some(Self {
+            month,
+            week,
+            weekday,
+            time,
+        })
+    }
+
+    /// The switch in `year` as seconds since the epoch, given the offset in
+    /// effect before it.
+    fn at(self, year: i32, offset: i32) -> Option<i64> {
+        let first = NaiveDate::from_ymd_opt(year, self.month, 1)?;
+        let first_weekday = first.weekday().num_days_from_sunday();
+        let mut day = 1 + (self.weekday + 7 - first_weekday) % 7 + (self.week - 1) * 7;
+        while NaiveDate::from_ymd_opt(year, self.month, day).is_none() {
+            day -= 7;
+        }
+        let midnight = NaiveDate::from_ymd_opt(year, self.month, day)?
+            .and_hms_opt(0, 0, 0)?
+            .and_utc()
+            .timestamp();
+        Some(midnight + i64::from(self.time) - i64::from(offset))
+    }
+}
+
+/// Skip a zone abbreviation, either alphabetic or quoted in `<>`.
+fn skip_name(input: &str) -> Option<&str> {
+    if let Some(quoted) = input.strip_prefix('<') {
+        return quoted.split_once('>').map(|(_, rest)| rest);
+    }
+    let end = input
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(input.len());
+    (end >= 3).then(|| &input[end..])
+}
+
+/// Parse `[+-]hh[:mm[:ss]]` into seconds.
+fn parse_offset(input: &str) -> Option<(i32, &str)> {
+    let (sign, input) = match input.as_bytes().first()? {
+        b'-' => (-1, &input[1..]),
+        b'+' => (1, &input[1..]),
+        _ => (1, input),
+    };
+    let end = input
+        .find(|c: char| !c.is_ascii_digit() && c != ':')
+        .unwrap_or(input.len());
+    let mut seconds = 0;
+    for (part, scale) in input[..end].split(':').zip([3600, 60, 1]) {
+        seconds += part.parse::<i32>().ok()? * scale;
+    }
+    Some((sign * seconds, &input[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(timestamp: &str) -> i64 {
+        DateTime::parse_from_rfc3339(timestamp).unwrap().timestamp()
+    }
+
+    #[test]
+    fn test_parse_moment() {
+        let now = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let parse = |input| parse_moment(input, now).unwrap().to_rfc3339();
+
+        assert_eq!(parse("30d"), "2026-01-31T00:00:00+00:00");
+        assert_eq!(parse("+2w"), "2026-01-15T00:00:00+00:00");
+        assert_eq!(parse("12h"), "2026-01-01T12:00:00+00:00");
+        assert_eq!(parse("-45m"), "2025-12-31T23:15:00+00:00");
+        assert_eq!(parse("2026-03-01"), "2026-03-01T00:00:00+00:00");
+        assert_eq!(parse("2026-03-01 14:30"), "2026-03-01T14:30:00+00:00");
+        assert!(parse_moment("3ü", now).is_err());
+        assert!(parse_moment("soon", now).is_err());
+        assert!(parse_moment("+", now).is_err());
+    }
+
+    #[test]
+    fn test_rule_offsets() {
+        let zurich = Rule::parse("CET-1CEST,M3.5.0,M10.5.0/3").unwrap();
+        assert_eq!(zurich.offset_at(at("2024-01-15T12:00:00Z")), 3600);
+        assert_eq!(zurich.offset_at(at("2024-07-15T12:00:00Z")), 7200);
+        // Clocks go forward at 02:00 local time on the last Sunday of March
+        assert_eq!(zurich.offset_at(at("2024-03-31T00:59:59Z")), 3600);
+        assert_eq!(zurich.offset_at(at("2024-03-31T01:00:00Z")), 7200);
+
+        let sydney = Rule::parse("AEST-10AEDT,M10.1.0,M4.1.0/3").unwrap();
+        assert_eq!(sydney.offset_at(at("2024-01-15T12:00:00Z")), 39600);
+        assert_eq!(sydney.offset_at(at("2024-07-15T12:00:00Z")), 36000);
+
+        let fixed = Rule::parse("<+0530>-5:30").unwrap();
+        assert_eq!(fixed.offset_at(at("2024-07-15T12:00:00Z")), 19800);
+        assert!(Rule::parse("X").is_none());
+    }
+
+    #[test]
+    fn test_display_timezone_from_str() {
+        assert_eq!(DisplayTimezone::from("UTC"), DisplayTimezone::Utc);
+        assert_eq!(DisplayTimezone::from("local"), DisplayTimezone::Local);
+        assert_eq!(
+            DisplayTimezone::from("Europe/Zurich"),
+            DisplayTimezone::Named("Europe/Zurich".to_string())
+        );
+        assert!(Zone::load("../etc/passwd").is_err());
+    }
+}
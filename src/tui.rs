@@ -22,7 +22,8 @@ use crossterm::event::{
     KeyModifiers,
     MouseEvent,
 };
-use crossterm::terminal::{EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::style::Print;
+use crossterm::terminal::{EnterAlternateScreen, LeaveAlternateScreen, SetTitle};
 use futures::{FutureExt, StreamExt};
 use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
@@ -32,6 +33,15 @@ use tokio::task::JoinHandle;
 use tokio::time::interval;
 use tokio_util::sync::CancellationToken;
 
+/// xterm sequences to save and restore the window title on the title stack.
+const PUSH_TITLE: &str = "\x1b[22;0t";
+const POP_TITLE: &str = "\x1b[23;0t";
+
+/// Draws taking longer than this count as slow.
+const SLOW_DRAW: Duration = Duration::from_millis(50);
+/// Consecutive slow draws after which drawing is consistently slow.
+const SLOW_DRAW_STREAK: usize = 30;
+
 const GRACEFUL_SHUTDOWN_TIMEOUT_MS: u64 = 500;
 const FORCEFUL_SHUTDOWN_TIMEOUT_MS: u64 = 2000;
 
@@ -41,6 +51,8 @@ pub type Backend = CrosstermBackend<Stdout>;
 pub enum Event {
     Init,
     Quit,
+    /// Ctrl+Z, to stop the process and return to the shell.
+    Suspend,
     Error(String),
     Closed,
     Tick,
@@ -65,6 +77,8 @@ pub struct Tui {
     event_tx: UnboundedSender<Event>,
     frame_rate: f64,
     tick_rate: f64,
+    /// Window title we set; the previous one is restored on exit.
+    title: Option<String>,
 }
 
 impl Tui {
@@ -86,6 +100,7 @@ impl Tui {
             event_tx,
             frame_rate,
             tick_rate,
+            title: None,
         })
     }
 
@@ -108,6 +123,9 @@ impl Tui {
     /// Returns an error if the terminal state cannot be restored.
     pub fn exit(&mut self) -> Result<()> {
         self.stop()?;
+        if self.title.take().is_some() {
+            crossterm::execute!(std::io::stdout(), Print(POP_TITLE))?;
+        }
         if crossterm::terminal::is_raw_mode_enabled()? {
             self.flush()?;
             crossterm::execute!(std::io::stdout(), DisableBracketedPaste)?;
@@ -118,8 +136,27 @@ impl Tui {
         Ok(())
     }
 
+    /// Set the terminal window title, saving the previous one on first use.
+    ///
+    /// # Errors
+    /// Returns an error if writing to the terminal fails.
+    pub fn set_title(&mut self, title: &str) -> Result<()> {
+        if self.title.as_deref() == Some(title) {
+            return Ok(());
+        }
+        if self.title.is_none() {
+            crossterm::execute!(std::io::stdout(), Print(PUSH_TITLE))?;
+        }
+        crossterm::execute!(std::io::stdout(), SetTitle(title))?;
+        self.title = Some(title.to_string());
+        Ok(())
+    }
+
     /// Suspend the TUI (for Ctrl+Z handling).
     ///
+    /// Restores the terminal and stops the process until the shell
+    /// continues it with `fg`; returns once it has been continued.
+    ///
     /// # Errors
     /// Returns an error if the terminal state cannot be changed.
     pub fn suspend(&mut self) -> Result<()> {
@@ -129,15 +166,25 @@ impl Tui {
         Ok(())
     }
 
-    /// Resume the TUI after suspension.
+    /// Resume the TUI after suspension, redrawing everything since the
+    /// shell wrote over the screen.
     ///
     /// # Errors
     /// Returns an error if the terminal state cannot be changed.
     pub fn resume(&mut self) -> Result<()> {
         self.enter()?;
+        self.terminal.clear()?;
         Ok(())
     }
 
+    /// Render `frame_rate` frames per second from now on.
+    pub fn set_frame_rate(&mut self, frame_rate: f64) {
+        self.frame_rate = frame_rate;
+        if !self.cancellation_token.is_cancelled() {
+            self.start();
+        }
+    }
+
     /// Get the next event from the event stream.
     pub async fn next_event(&mut self) -> Option<Event> {
         self.event_rx.recv().await
@@ -185,16 +232,22 @@ impl Tui {
         let mut tick_interval = interval(Duration::from_secs_f64(1.0 / tick_rate));
         let mut frame_interval = interval(Duration::from_secs_f64(1.0 / frame_rate));
 
-        // Spawn SIGTERM handler on Unix
+        // Spawn SIGTERM handler on Unix, stopped with the loop so a resumed
+        // TUI doesn't listen twice
         #[cfg(unix)]
         {
             let event_tx_clone = event_tx.clone();
+            let cancellation_token = cancellation_token.clone();
             tokio::spawn(async move {
                 let mut sigterm =
                     tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
                         .expect("Failed to create SIGTERM handler");
-                sigterm.recv().await;
-                let _ = event_tx_clone.send(Event::Quit);
+                tokio::select! {
+                    () = cancellation_token.cancelled() => {}
+                    _ = sigterm.recv() => {
+                        let _ = event_tx_clone.send(Event::Quit);
+                    }
+                }
             });
         }
 
@@ -214,10 +267,12 @@ impl Tui {
                         Some(Ok(event)) => match event {
                             CrosstermEvent::Key(key) => {
                                 if key.kind == KeyEventKind::Press {
-                                    if key.modifiers.contains(KeyModifiers::CONTROL)
-                                        && key.code == KeyCode::Char('c')
-                                    {
+                                    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+                                    if ctrl && key.code == KeyCode::Char('c') {
                                         Event::Quit
+                                    } else if cfg!(unix) && ctrl && key.code == KeyCode::Char('z') {
+                                        // Raw mode turns Ctrl+Z into a key instead of SIGTSTP
+                                        Event::Suspend
                                     } else {
                                         Event::Key(key)
                                     }
@@ -244,6 +299,27 @@ impl Tui {
     }
 }
 
+/// Notices when drawing frames is consistently slow, e.g. over a remote
+/// connection, so rendering can be made cheaper.
+#[derive(Default)]
+pub struct DrawTimer {
+    slow_streak: usize,
+}
+
+impl DrawTimer {
+    /// Record how long a draw `took`.
+    ///
+    /// Returns `true` once enough draws in a row were slow.
+    pub fn record(&mut self, took: Duration) -> bool {
+        if took >= SLOW_DRAW {
+            self.slow_streak += 1;
+        } else {
+            self.slow_streak = 0;
+        }
+        self.slow_streak >= SLOW_DRAW_STREAK
+    }
+}
+
 impl Deref for Tui {
     type Target = Terminal<Backend>;
 
@@ -263,3 +339,21 @@ impl Drop for Tui {
         self.exit().unwrap();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_a_streak_of_slow_draws_counts() {
+        let mut timer = DrawTimer::default();
+        for _ in 1..SLOW_DRAW_STREAK {
+            assert!(!timer.record(SLOW_DRAW));
+        }
+        assert!(!timer.record(Duration::from_millis(5)));
+        for _ in 1..SLOW_DRAW_STREAK {
+            assert!(!timer.record(SLOW_DRAW));
+        }
+        assert!(timer.record(SLOW_DRAW));
+    }
+}
@@ -1,40 +1,75 @@
 pub mod components;
+pub mod text;
 pub mod widgets;
 
+mod account_selector;
+mod bookmarks;
+mod command_history;
 mod command_panel;
 mod error_dialog;
+mod focus;
 mod help;
+mod output_pane;
+mod profile_selector;
+mod quit_dialog;
+mod release_notes;
+#[cfg(test)]
+pub mod snapshot;
+mod stats_overlay;
 mod status_bar;
 mod toast;
+mod workspaces;
+
+use std::any::Any;
 
-pub use color_eyre::Result;
 // Re-export app-level UI
+pub use account_selector::{AccountEvent, AccountSelectorView};
+pub use bookmarks::{BookmarkEvent, BookmarksView};
+pub use color_eyre::Result;
+pub use command_history::{CommandHistoryView, HistoryEvent};
 pub use command_panel::{CommandId, CommandPanel};
 // Re-export components
 pub use components::{
     ColumnDef,
     ConfirmDialog,
     ConfirmEvent,
+    DateTimeInput,
+    DateTimeInputEvent,
     List,
     ListEvent,
     ListRow,
     Table,
     TableEvent,
     TableRow,
+    TextArea,
+    TextAreaEvent,
     TextInput,
     TextInputEvent,
+    TextViewer,
+    Tree,
+    TreeEvent,
+    TreeItem,
+    detect_syntax,
 };
 use crossterm::event::KeyEvent;
 pub use error_dialog::{ErrorDialog, ErrorDialogEvent};
+pub use focus::FocusManager;
 pub use help::{HelpEvent, HelpOverlay, Keybinding, KeybindingSection};
+pub use output_pane::{OutputPane, OutputPaneEvent, PANE_HEIGHT as OUTPUT_PANE_HEIGHT};
+pub use profile_selector::{ProfileEvent, ProfileSelectorView};
+pub use quit_dialog::{QuitDialog, QuitEvent};
 use ratatui::Frame;
 use ratatui::layout::Rect;
+pub use release_notes::{ReleaseNotesEvent, ReleaseNotesOverlay};
+pub use stats_overlay::{StatsEvent, StatsOverlay};
 pub use status_bar::StatusBar;
 pub use toast::{Toast, ToastManager, ToastType};
 // Re-export widgets
 pub use widgets::Spinner;
+pub use workspaces::{WorkspaceEvent, WorkspacesView};
 
 use crate::Theme;
+use crate::config::ActionContext;
 
 /// Result of handling an input event.
 ///
@@ -57,6 +92,15 @@ impl<E> EventResult<E> {
     pub const fn is_consumed(&self) -> bool {
         !matches!(self, Self::Ignored)
     }
+
+    /// Drop the event, keeping only whether the input was consumed.
+    pub fn consumed_only<F>(self) -> EventResult<F> {
+        if self.is_consumed() {
+            EventResult::Consumed
+        } else {
+            EventResult::Ignored
+        }
+    }
 }
 
 impl<E> From<E> for EventResult<E> {
@@ -111,6 +155,15 @@ pub trait Component {
         Ok(EventResult::Ignored)
     }
 
+    /// Handle text pasted into the terminal as a single event.
+    ///
+    /// Only text-accepting components override this, so a paste never
+    /// triggers keybindings.
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        _ = text;
+        Ok(EventResult::Ignored)
+    }
+
     /// Called on each tick for animations and time-based updates.
     fn handle_tick(&mut self) {}
 
@@ -136,6 +189,12 @@ pub trait Modal {
     /// Handle a key event.
     fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>>;
 
+    /// Handle pasted text.
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        _ = text;
+        Ok(EventResult::Ignored)
+    }
+
     /// Render the modal to the frame.
     fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme);
 
@@ -156,13 +215,19 @@ pub trait Modal {
 /// - `SecretListScreen` - displays secrets table, emits `SecretManagerMsg`
 /// - `VersionListScreen` - displays versions table, emits `SecretManagerMsg`
 /// - `PayloadScreen` - displays secret payload with syntax highlighting
-pub trait Screen {
+pub trait Screen: Any {
     /// The message type produced by this screen.
     type Output;
 
     /// Handle a key event.
     fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>>;
 
+    /// Handle pasted text, e.g. into a table search.
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        _ = text;
+        Ok(EventResult::Ignored)
+    }
+
     /// Render the screen to the frame.
     fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme);
 
@@ -179,4 +244,9 @@ pub trait Screen {
     fn keybindings(&self) -> Vec<Keybinding> {
         vec![]
     }
+
+    /// Screen identifier and placeholder values for custom actions.
+    fn action_context(&self) -> ActionContext {
+        ActionContext::default()
+    }
 }
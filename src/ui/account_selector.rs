@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, ListItem};
+
+use crate::Theme;
+use crate::config::{GlobalAction, KeyResolver};
+use crate::i18n::t;
+use crate::ui::{Component, EventResult, List, ListEvent, ListRow, Result};
+
+/// An account the active context can be used with.
+#[derive(Debug, Clone)]
+pub struct AccountItem {
+    pub account: String,
+    active: bool,
+}
+
+impl ListRow for AccountItem {
+    fn render_row(&self, theme: &Theme) -> ListItem<'static> {
+        let mut spans = vec![Span::styled(
+            self.account.clone(),
+            Style::default().fg(theme.text()),
+        )];
+        if self.active {
+            spans.push(Span::styled(
+                " (active)",
+                Style::default().fg(theme.green()),
+            ));
+        }
+        ListItem::new(Line::from(spans))
+    }
+}
+
+pub enum AccountEvent {
+    Cancelled,
+    Selected(String),
+}
+
+pub struct AccountSelectorView {
+    list: List<AccountItem>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl AccountSelectorView {
+    /// `accounts` starts with the active one.
+    pub fn new(accounts: Vec<String>, resolver: Arc<KeyResolver>) -> Self {
+        let items = accounts
+            .into_iter()
+            .enumerate()
+            .map(|(i, account)| AccountItem {
+                account,
+                active: i == 0,
+            })
+            .collect();
+        Self {
+            list: List::new(items, resolver.clone()),
+            resolver,
+        }
+    }
+}
+
+impl Component for AccountSelectorView {
+    type Output = AccountEvent;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if self.resolver.matches_global(&key, GlobalAction::Back)
+            || self.resolver.matches_global(&key, GlobalAction::Account)
+        {
+            return Ok(AccountEvent::Cancelled.into());
+        }
+
+        Ok(match self.list.handle_key(key)? {
+            EventResult::Event(ListEvent::Activated(item)) => {
+                AccountEvent::Selected(item.account).into()
+            }
+            EventResult::Consumed | EventResult::Event(_) => EventResult::Consumed,
+            EventResult::Ignored => EventResult::Ignored,
+        })
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = area.centered(Constraint::Percentage(40), Constraint::Percentage(50));
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(format!(
+                " {} ",
+                t("Select Account (Enter to confirm, Esc to cancel)")
+            ))
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.lavender()))
+            .style(Style::default().bg(theme.base()));
+
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        self.list.render(frame, inner, theme);
+    }
+}
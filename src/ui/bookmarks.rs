@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::widgets::{Cell, Clear};
+
+use crate::Theme;
+use crate::config::{BookmarkAction, GlobalAction, KeyResolver, NavAction};
+use crate::i18n::t;
+use crate::registry::ResourceLink;
+use crate::search::Matcher;
+use crate::ui::{
+    ColumnDef,
+    Component,
+    EventResult,
+    Keybinding,
+    Result,
+    Table,
+    TableEvent,
+    TableRow,
+};
+
+pub enum BookmarkEvent {
+    Open(ResourceLink),
+    Delete(ResourceLink),
+    Close,
+}
+
+impl TableRow for ResourceLink {
+    fn columns() -> &'static [ColumnDef] {
+        static COLUMNS: &[ColumnDef] = &[
+            ColumnDef::new("Context", Constraint::Length(20)),
+            ColumnDef::new("Service", Constraint::Length(24)),
+            ColumnDef::new("Resource", Constraint::Min(30)),
+        ];
+        COLUMNS
+    }
+
+    fn render_cells(&self, _theme: &Theme) -> Vec<Cell<'static>> {
+        vec![
+            Cell::from(self.context.clone().unwrap_or_default()),
+            Cell::from(self.service.to_string()),
+            Cell::from(self.resource.clone().unwrap_or_default()),
+        ]
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        Matcher::new().matches(&self.to_string(), query)
+    }
+
+    fn details(&self) -> Vec<(&'static str, String)> {
+        vec![("Link", self.to_string())]
+    }
+}
+
+/// Bookmarked services and resources of every context, newest first.
+pub struct BookmarksView {
+    table: Table<ResourceLink>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl BookmarksView {
+    pub fn new(links: Vec<ResourceLink>, resolver: Arc<KeyResolver>) -> Self {
+        let mut view = Self {
+            table: Table::new(links, resolver.clone()),
+            resolver,
+        };
+        view.update_title();
+        view
+    }
+
+    fn update_title(&mut self) {
+        let count = self.table.items().len();
+        self.table
+            .set_title(format!(" {} ({count}) ", t("Bookmarks")));
+    }
+
+    pub fn keybindings(&self) -> Vec<Keybinding> {
+        vec![
+            Keybinding::hint(self.resolver.display_nav(NavAction::Select), "Open"),
+            Keybinding::new(
+                self.resolver.display_bookmarks(BookmarkAction::Delete),
+                "Delete",
+            ),
+        ]
+    }
+}
+
+impl Component for BookmarksView {
+    type Output = BookmarkEvent;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        let result = self.table.handle_key(key)?;
+        if let EventResult::Event(TableEvent::Activated(link)) = result {
+            return Ok(BookmarkEvent::Open(link).into());
+        }
+        if result.is_consumed() {
+            return Ok(EventResult::Consumed);
+        }
+        let r = &self.resolver;
+        if r.matches_global(&key, GlobalAction::Bookmarks)
+            || r.matches_global(&key, GlobalAction::Back)
+            || r.matches_global(&key, GlobalAction::Quit)
+        {
+            return Ok(BookmarkEvent::Close.into());
+        }
+        if r.matches_bookmarks(&key, BookmarkAction::Delete)
+            && let Some(link) = self.table.selected_item().cloned()
+        {
+            let links = self
+                .table
+                .items()
+                .iter()
+                .filter(|item| **item != link)
+                .cloned()
+                .collect();
+            self.table.set_items(links);
+            self.update_title();
+            return Ok(BookmarkEvent::Delete(link).into());
+        }
+        Ok(EventResult::Consumed)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        frame.render_widget(Clear, area);
+        self.table.render(frame, area, theme);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use super::*;
+    use crate::config::keybindings::KeybindingsConfig;
+    use crate::registry::ServiceId;
+
+    #[test]
+    fn test_delete_bookmark() {
+        let resolver = Arc::new(KeyResolver::new(Arc::new(KeybindingsConfig::default())));
+        let secret =
+            ResourceLink::new(ServiceId::gcp("secret-manager"), "db-password").in_context("prod");
+        let kms = ResourceLink::service("dev", ServiceId::gcp("kms"));
+        let mut view = BookmarksView::new(vec![secret.clone(), kms.clone()], resolver);
+
+        let event = view.handle_key(KeyEvent::from(KeyCode::Char('d'))).unwrap();
+        assert!(matches!(event, EventResult::Event(BookmarkEvent::Delete(link)) if link == secret));
+        assert_eq!(view.table.items(), std::slice::from_ref(&kms));
+
+        let event = view.handle_key(KeyEvent::from(KeyCode::Enter)).unwrap();
+        assert!(matches!(event, EventResult::Event(BookmarkEvent::Open(link)) if link == kms));
+    }
+}
@@ -0,0 +1,244 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::Style;
+use ratatui::widgets::{Cell, Clear};
+
+use crate::Theme;
+use crate::config::{CommandRecord, GlobalAction, HistoryAction, KeyResolver};
+use crate::i18n::t;
+use crate::search::Matcher;
+use crate::time::format_epoch;
+use crate::ui::command_panel::{age_of, format_age, format_duration};
+use crate::ui::{ColumnDef, Component, EventResult, Keybinding, Result, Table, TableRow};
+
+/// Commands taking at least this long count as slow.
+const SLOW_MS: u64 = 2000;
+
+pub enum HistoryEvent {
+    /// Run the command behind the record again.
+    Retry(CommandRecord),
+    Close,
+}
+
+impl TableRow for CommandRecord {
+    fn columns() -> &'static [ColumnDef] {
+        static COLUMNS: &[ColumnDef] = &[
+            ColumnDef::new("", Constraint::Length(2)),
+            ColumnDef::new("Command", Constraint::Min(30)),
+            ColumnDef::new("Service", Constraint::Length(18)),
+            ColumnDef::new("Resource", Constraint::Length(24)),
+            ColumnDef::new("Duration", Constraint::Length(10)),
+            ColumnDef::new("Finished", Constraint::Length(10)),
+        ];
+        COLUMNS
+    }
+
+    fn render_cells(&self, theme: &Theme) -> Vec<Cell<'static>> {
+        let (icon, color) = if self.success {
+            (theme.symbols.success, theme.green())
+        } else {
+            (theme.symbols.failure, theme.red())
+        };
+        let duration_color = if self.duration_ms >= SLOW_MS {
+            theme.peach()
+        } else {
+            theme.text()
+        };
+        vec![
+            Cell::from(icon).style(Style::default().fg(color)),
+            Cell::from(self.name.clone()),
+            Cell::from(self.service.clone().unwrap_or_default()),
+            Cell::from(self.resource.clone().unwrap_or_default()),
+            Cell::from(format_duration(Duration::from_millis(self.duration_ms)))
+                .style(Style::default().fg(duration_color)),
+            Cell::from(format_age(age_of(self))),
+        ]
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        let matcher = Matcher::new();
+        matcher.matches(&self.name, query)
+            || self
+                .service
+                .iter()
+                .chain(&self.resource)
+                .any(|value| matcher.matches(value, query))
+    }
+
+    fn details(&self) -> Vec<(&'static str, String)> {
+        let mut details = vec![("Finished", format_epoch(self.completed_at))];
+        if let Some(resource) = &self.resource {
+            details.push(("Resource", resource.clone()));
+        }
+        details
+    }
+}
+
+/// Every command finished in this and earlier sessions, newest first.
+pub struct CommandHistoryView {
+    records: Vec<CommandRecord>,
+    /// Services that ran commands, stepped through to narrow the list.
+    services: Vec<String>,
+    service: Option<usize>,
+    table: Table<CommandRecord>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl CommandHistoryView {
+    pub fn new(records: Vec<CommandRecord>, resolver: Arc<KeyResolver>) -> Self {
+        let services: BTreeSet<String> = records
+            .iter()
+            .filter_map(|record| record.service.clone())
+            .collect();
+        let table = Table::new(records.clone(), resolver.clone())
+            .with_filter("Failed", |record| !record.success)
+            .with_filter("Slow", |record| record.duration_ms >= SLOW_MS);
+        let mut view = Self {
+            records,
+            services: services.into_iter().collect(),
+            service: None,
+            table,
+            resolver,
+        };
+        view.update_title();
+        view
+    }
+
+    fn cycle_service(&mut self) {
+        self.service = match self.service {
+            None if !self.services.is_empty() => Some(0),
+            Some(i) if i + 1 < self.services.len() => Some(i + 1),
+            _ => None,
+        };
+        let service = self.service.map(|i| self.services[i].as_str());
+        let records = self
+            .records
+            .iter()
+            .filter(|record| service.is_none() || record.service.as_deref() == service)
+            .cloned()
+            .collect();
+        self.table.set_items(records);
+        self.update_title();
+    }
+
+    /// Count and average duration of the listed commands.
+    fn update_title(&mut self) {
+        let items = self.table.items();
+        let count = u64::try_from(items.len()).unwrap_or(u64::MAX);
+        let total: u64 = items.iter().map(|record| record.duration_ms).sum();
+        let average = format_duration(Duration::from_millis(total.checked_div(count).unwrap_or(0)));
+        let scope = self.service.map_or_else(
+            || t("All services").to_string(),
+            |i| self.services[i].clone(),
+        );
+        self.table.set_title(format!(
+            " {} - {scope} ({count}, {} {average}) ",
+            t("Command History"),
+            t("avg"),
+        ));
+    }
+
+    pub fn keybindings(&self) -> Vec<Keybinding> {
+        vec![
+            Keybinding::new(
+                self.resolver.display_history(HistoryAction::FailedOnly),
+                "Failed only",
+            ),
+            Keybinding::new(
+                self.resolver.display_history(HistoryAction::SlowOnly),
+                "Slow only",
+            ),
+            Keybinding::new(
+                self.resolver.display_history(HistoryAction::CycleService),
+                "Next service",
+            ),
+            Keybinding::new(
+                self.resolver.display_history(HistoryAction::Retry),
+                "Re-run",
+            ),
+        ]
+    }
+}
+
+impl Component for CommandHistoryView {
+    type Output = HistoryEvent;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if self.table.handle_key(key)?.is_consumed() {
+            return Ok(EventResult::Consumed);
+        }
+        let r = &self.resolver;
+        if r.matches_global(&key, GlobalAction::History)
+            || r.matches_global(&key, GlobalAction::Back)
+            || r.matches_global(&key, GlobalAction::Quit)
+        {
+            return Ok(HistoryEvent::Close.into());
+        }
+        if r.matches_history(&key, HistoryAction::FailedOnly) {
+            self.table.toggle_filter("Failed");
+        } else if r.matches_history(&key, HistoryAction::SlowOnly) {
+            self.table.toggle_filter("Slow");
+        } else if r.matches_history(&key, HistoryAction::CycleService) {
+            self.cycle_service();
+        } else if r.matches_history(&key, HistoryAction::Retry)
+            && let Some(record) = self.table.selected_item()
+        {
+            return Ok(HistoryEvent::Retry(record.clone()).into());
+        }
+        Ok(EventResult::Consumed)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.table.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        frame.render_widget(Clear, area);
+        self.table.render(frame, area, theme);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::keybindings::KeybindingsConfig;
+
+    fn record(name: &str, service: Option<&str>, success: bool) -> CommandRecord {
+        CommandRecord {
+            name: name.to_string(),
+            service: service.map(ToString::to_string),
+            resource: None,
+            success,
+            duration_ms: 100,
+            completed_at: 0,
+            retry_id: None,
+        }
+    }
+
+    #[test]
+    fn test_cycle_service() {
+        let resolver = Arc::new(KeyResolver::new(Arc::new(KeybindingsConfig::default())));
+        let mut view = CommandHistoryView::new(
+            vec![
+                record("Loading secrets", Some("Secret Manager"), true),
+                record("Initializing", None, true),
+                record("Deleting 'db'", Some("Secret Manager"), false),
+                record("Listing accounts", Some("Service Accounts"), true),
+            ],
+            resolver,
+        );
+        assert_eq!(view.table.items().len(), 4);
+
+        view.cycle_service();
+        assert_eq!(view.table.items().len(), 2);
+        view.cycle_service();
+        assert_eq!(view.table.items()[0].name, "Listing accounts");
+        view.cycle_service();
+        assert_eq!(view.table.items().len(), 4);
+    }
+}
@@ -1,15 +1,21 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::time::{Duration, Instant};
 
+use chrono::Utc;
 use ratatui::Frame;
 use ratatui::layout::Rect;
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph};
-use throbber_widgets_tui::{BRAILLE_SIX, Throbber, ThrobberState, WhichUse};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use throbber_widgets_tui::{Throbber, ThrobberState, WhichUse};
 
 use crate::Theme;
+use crate::commands::{Command, Progress, StepTracker};
+use crate::config::CommandRecord;
+use crate::i18n::{t, t_args};
 use crate::ui::Component;
+use crate::ui::text::{display_width, padding, truncate};
+use crate::ui::widgets::{ProgressBar, TaskList, reduced_motion, throbber_set};
 
 const MIN_WIDTH: u16 = 56;
 
@@ -20,20 +26,20 @@ pub struct CommandId(u64);
 struct RunningCommand {
     id: CommandId,
     name: String,
+    service: Option<String>,
+    resource: Option<String>,
+    mutates: bool,
     started_at: Instant,
-}
-
-#[derive(Debug)]
-struct CompletedCommand {
-    name: String,
-    success: bool,
-    duration: Duration,
-    completed_at: Instant,
+    steps: Option<StepTracker>,
+    progress: Option<Progress>,
 }
 
 pub struct CommandPanel {
     running: Vec<RunningCommand>,
-    history: VecDeque<CompletedCommand>,
+    /// Finished commands, newest first.
+    history: VecDeque<CommandRecord>,
+    /// Copies of finished commands that can run again, by their run.
+    retries: HashMap<CommandId, Box<dyn Command>>,
     next_id: u64,
     max_history: usize,
     expanded: bool,
@@ -45,6 +51,7 @@ impl CommandPanel {
         Self {
             running: Vec::new(),
             history: VecDeque::new(),
+            retries: HashMap::new(),
             next_id: 0,
             max_history: 10,
             expanded: false,
@@ -52,13 +59,43 @@ impl CommandPanel {
         }
     }
 
-    pub fn start(&mut self, name: String) -> CommandId {
+    /// Continue the history of an earlier session, keeping at least the
+    /// latest `max_history` commands.
+    #[must_use]
+    pub fn with_history(mut self, records: Vec<CommandRecord>, max_history: usize) -> Self {
+        self.max_history = self.max_history.max(max_history);
+        self.history = records.into_iter().take(self.max_history).collect();
+        self
+    }
+
+    /// Finished commands, newest first.
+    pub fn history(&self) -> Vec<CommandRecord> {
+        self.history.iter().cloned().collect()
+    }
+
+    /// A copy of the command behind `record` to run again, if this session
+    /// ran it and it can be re-created.
+    pub fn retry(&self, record: &CommandRecord) -> Option<Box<dyn Command>> {
+        let id = CommandId(record.retry_id?);
+        self.retries.get(&id).and_then(|cmd| cmd.retry())
+    }
+
+    /// Track `cmd`, run on behalf of `service`, until it completes.
+    pub fn start(&mut self, cmd: &dyn Command, service: Option<String>) -> CommandId {
         let id = CommandId(self.next_id);
         self.next_id += 1;
+        if let Some(retry) = cmd.retry() {
+            self.retries.insert(id, retry);
+        }
         self.running.push(RunningCommand {
             id,
-            name,
+            name: cmd.name(),
+            service,
+            resource: cmd.resource(),
+            mutates: cmd.mutates(),
             started_at: Instant::now(),
+            steps: cmd.steps(),
+            progress: cmd.progress(),
         });
         id
     }
@@ -70,14 +107,24 @@ impl CommandPanel {
 
         let cmd = self.running.remove(pos);
         let duration = cmd.started_at.elapsed();
-        self.history.push_front(CompletedCommand {
-            name: cmd.name,
+        // A failed workflow names the step it stopped at
+        let name = match cmd.steps.as_ref().and_then(StepTracker::failed_step) {
+            Some(step) if !success => format!("{}: {step}", cmd.name),
+            _ => cmd.name,
+        };
+        self.history.push_front(CommandRecord {
+            name,
+            service: cmd.service,
+            resource: cmd.resource,
             success,
-            duration,
-            completed_at: Instant::now(),
+            duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+            completed_at: Utc::now().timestamp(),
+            retry_id: self.retries.contains_key(&id).then_some(id.0),
         });
         while self.history.len() > self.max_history {
-            self.history.pop_back();
+            if let Some(retry_id) = self.history.pop_back().and_then(|record| record.retry_id) {
+                self.retries.remove(&CommandId(retry_id));
+            }
         }
     }
 
@@ -100,6 +147,16 @@ impl CommandPanel {
         !self.running.is_empty()
     }
 
+    /// Names of the running commands that change something, see
+    /// [`Command::mutates`].
+    pub fn mutating(&self) -> Vec<String> {
+        self.running
+            .iter()
+            .filter(|cmd| cmd.mutates)
+            .map(|cmd| cmd.name.clone())
+            .collect()
+    }
+
     /// Renders a small inline status indicator showing the first running command and count.
     pub fn render_inline(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) -> u16 {
         if self.running.is_empty() {
@@ -121,8 +178,13 @@ impl CommandPanel {
             )
         };
 
-        #[allow(clippy::cast_possible_truncation)]
-        let width = status.len() as u16 + 3; // +3 for spinner and spacing
+        // +3 for spinner and spacing
+        let status = truncate(
+            &status,
+            usize::from(area.width).saturating_sub(3),
+            theme.symbols.ellipsis,
+        );
+        let width = u16::try_from(display_width(&status) + 3).unwrap_or(u16::MAX);
 
         // Position on right side of area
         let x = area.right().saturating_sub(width);
@@ -130,11 +192,17 @@ impl CommandPanel {
         let text_area = Rect::new(x + 2, area.y, width - 2, 1);
 
         // Render spinner
-        let throbber = Throbber::default()
-            .throbber_set(BRAILLE_SIX)
-            .use_type(WhichUse::Spin)
-            .throbber_style(Style::default().fg(theme.lavender()));
-        frame.render_stateful_widget(throbber, spinner_area, &mut self.throbber_state);
+        if reduced_motion() {
+            let indicator =
+                Span::styled(theme.symbols.pending, Style::default().fg(theme.lavender()));
+            frame.render_widget(Paragraph::new(Line::from(indicator)), spinner_area);
+        } else {
+            let throbber = Throbber::default()
+                .throbber_set(throbber_set(theme.symbols))
+                .use_type(WhichUse::Spin)
+                .throbber_style(Style::default().fg(theme.lavender()));
+            frame.render_stateful_widget(throbber, spinner_area, &mut self.throbber_state);
+        }
 
         // Render text
         let text = Paragraph::new(status).style(Style::default().fg(theme.subtext0()));
@@ -153,9 +221,12 @@ impl CommandPanel {
         let running_time_col = 10;
 
         lines.push(Line::from(vec![
-            Span::styled("⚡ ", Style::default().fg(theme.yellow())),
             Span::styled(
-                "RUNNING",
+                format!("{} ", theme.symbols.running),
+                Style::default().fg(theme.yellow()),
+            ),
+            Span::styled(
+                t("RUNNING"),
                 Style::default()
                     .fg(theme.yellow())
                     .add_modifier(Modifier::BOLD),
@@ -165,20 +236,23 @@ impl CommandPanel {
         let name_max_len = inner_width
             .saturating_sub(running_prefix_len)
             .saturating_sub(running_time_col);
+        // Progress and steps are indented under the command
+        let nested_width = inner_width.saturating_sub(7);
 
         for cmd in &self.running {
             let elapsed = cmd.started_at.elapsed();
             let time_str = format_duration(elapsed);
 
-            let progress_char = match elapsed.as_secs() % 4 {
-                0 => "▰▱▱",
-                1 => "▰▰▱",
-                2 => "▰▰▰",
-                _ => "▱▰▰",
+            let frames = theme.symbols.progress;
+            #[allow(clippy::cast_possible_truncation)]
+            let progress_char = if reduced_motion() {
+                frames[0]
+            } else {
+                frames[elapsed.as_secs() as usize % frames.len()]
             };
 
-            let name = truncate_with_ellipsis(&cmd.name, name_max_len);
-            let padding = name_max_len.saturating_sub(display_width(&name));
+            let name = truncate(&cmd.name, name_max_len, theme.symbols.ellipsis);
+            let padding = padding(&name, name_max_len);
             let time_display = format!("{time_str:>running_time_col$}");
 
             lines.push(Line::from(vec![
@@ -186,7 +260,7 @@ impl CommandPanel {
                 Span::styled(progress_char, Style::default().fg(theme.peach())),
                 Span::raw(" "),
                 Span::styled(name, Style::default().fg(theme.text())),
-                Span::raw(" ".repeat(padding)),
+                Span::raw(padding),
                 Span::styled(
                     time_display,
                     Style::default()
@@ -194,6 +268,18 @@ impl CommandPanel {
                         .add_modifier(Modifier::DIM),
                 ),
             ]));
+
+            let mut nested = Vec::new();
+            if let Some((done, total)) = cmd.progress.as_ref().map(Progress::get) {
+                nested.push(ProgressBar::new(done, total).line(nested_width, theme));
+            }
+            if let Some(steps) = &cmd.steps {
+                nested.extend(TaskList::new(steps.steps()).lines(nested_width, theme));
+            }
+            lines.extend(nested.into_iter().map(|mut line| {
+                line.spans.insert(0, Span::raw("      "));
+                line
+            }));
         }
         lines
     }
@@ -208,9 +294,12 @@ impl CommandPanel {
         let history_time_col = 18;
 
         lines.push(Line::from(vec![
-            Span::styled("📋 ", Style::default().fg(theme.subtext0())),
             Span::styled(
-                "RECENT",
+                format!("{} ", theme.symbols.history),
+                Style::default().fg(theme.subtext0()),
+            ),
+            Span::styled(
+                t("RECENT"),
                 Style::default()
                     .fg(theme.subtext0())
                     .add_modifier(Modifier::BOLD),
@@ -223,17 +312,17 @@ impl CommandPanel {
 
         for cmd in self.history.iter().take(5) {
             let (icon, color) = if cmd.success {
-                ("✓", theme.green())
+                (theme.symbols.success, theme.green())
             } else {
-                ("✗", theme.red())
+                (theme.symbols.failure, theme.red())
             };
 
-            let duration_str = format_duration(cmd.duration);
-            let age = format_age(cmd.completed_at.elapsed());
-            let time_info = format!("{duration_str} · {age}");
+            let duration_str = format_duration(Duration::from_millis(cmd.duration_ms));
+            let age = format_age(age_of(cmd));
+            let time_info = format!("{duration_str} {} {age}", theme.symbols.middle_dot);
 
-            let name = truncate_with_ellipsis(&cmd.name, name_max_len);
-            let padding = name_max_len.saturating_sub(display_width(&name));
+            let name = truncate(&cmd.name, name_max_len, theme.symbols.ellipsis);
+            let padding = padding(&name, name_max_len);
             let time_display = format!("{time_info:>history_time_col$}");
 
             lines.push(Line::from(vec![
@@ -241,7 +330,7 @@ impl CommandPanel {
                 Span::styled(icon, Style::default().fg(color)),
                 Span::raw(" "),
                 Span::styled(name, Style::default().fg(theme.subtext1())),
-                Span::raw(" ".repeat(padding)),
+                Span::raw(padding),
                 Span::styled(
                     time_display,
                     Style::default()
@@ -263,7 +352,15 @@ impl CommandPanel {
         let running_lines = if self.running.is_empty() {
             0
         } else {
-            self.running.len() + 1 // +1 for header
+            let nested_lines: usize = self
+                .running
+                .iter()
+                .map(|cmd| {
+                    usize::from(cmd.progress.is_some())
+                        + cmd.steps.as_ref().map_or(0, |steps| steps.steps().len())
+                })
+                .sum();
+            self.running.len() + nested_lines + 1 // +1 for header
         };
         let history_to_show = self.history.len().min(5);
         let history_lines = if history_to_show == 0 {
@@ -291,18 +388,29 @@ impl CommandPanel {
 
         // Title with stats
         let title = if self.running.is_empty() {
-            format!(" Commands ({} recent) ", self.history.len())
+            format!(
+                " {} ",
+                t_args(
+                    "Commands ({recent} recent)",
+                    &[("recent", &self.history.len().to_string())]
+                )
+            )
         } else {
             format!(
-                " Commands ({} running, {} recent) ",
-                self.running.len(),
-                self.history.len()
+                " {} ",
+                t_args(
+                    "Commands ({running} running, {recent} recent)",
+                    &[
+                        ("running", &self.running.len().to_string()),
+                        ("recent", &self.history.len().to_string()),
+                    ],
+                )
             )
         };
 
         let block = Block::default()
             .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
+            .border_type(theme.border_type)
             .border_style(Style::default().fg(theme.surface2()))
             .title(title)
             .title_style(
@@ -331,26 +439,13 @@ impl CommandPanel {
     }
 }
 
-fn truncate_with_ellipsis(s: &str, max_len: usize) -> String {
-    if max_len == 0 {
-        return String::new();
-    }
-    let chars: Vec<char> = s.chars().collect();
-    if chars.len() <= max_len {
-        s.to_string()
-    } else if max_len <= 1 {
-        "…".to_string()
-    } else {
-        let truncated: String = chars[..max_len - 1].iter().collect();
-        format!("{truncated}…")
-    }
+/// Time since `record` finished, zero if the clock went back.
+pub(super) fn age_of(record: &CommandRecord) -> Duration {
+    let secs = Utc::now().timestamp() - record.completed_at;
+    Duration::from_secs(u64::try_from(secs).unwrap_or_default())
 }
 
-fn display_width(s: &str) -> usize {
-    s.chars().count()
-}
-
-fn format_duration(d: Duration) -> String {
+pub(super) fn format_duration(d: Duration) -> String {
     let secs = d.as_secs_f64();
     if secs < 1.0 {
         format!("{:.0}ms", d.as_millis())
@@ -364,16 +459,18 @@ fn format_duration(d: Duration) -> String {
     }
 }
 
-fn format_age(d: Duration) -> String {
+pub(super) fn format_age(d: Duration) -> String {
     let secs = d.as_secs();
     if secs < 5 {
-        "just now".to_string()
+        t("just now").to_string()
     } else if secs < 60 {
         format!("{secs}s ago")
     } else if secs < 3600 {
         format!("{}m ago", secs / 60)
-    } else {
+    } else if secs < 86400 {
         format!("{}h ago", secs / 3600)
+    } else {
+        format!("{}d ago", secs / 86400)
     }
 }
 
@@ -395,3 +492,74 @@ impl Component for CommandPanel {
         self.render_expanded_panel(frame, area, theme);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use color_eyre::Result;
+    use tokio::sync::mpsc::UnboundedSender;
+
+    use super::*;
+    use crate::app::AppMessage;
+
+    struct ReloadCmd;
+
+    #[async_trait]
+    impl Command for ReloadCmd {
+        fn name(&self) -> String {
+            "Reloading".to_string()
+        }
+
+        fn retry(&self) -> Option<Box<dyn Command>> {
+            Some(Box::new(Self))
+        }
+
+        async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    struct DeleteCmd;
+
+    #[async_trait]
+    impl Command for DeleteCmd {
+        fn name(&self) -> String {
+            "Deleting".to_string()
+        }
+
+        fn mutates(&self) -> bool {
+            true
+        }
+
+        async fn execute(self: Box<Self>, _action_tx: UnboundedSender<AppMessage>) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_mutating_commands_block_quitting() {
+        let mut panel = CommandPanel::new();
+        panel.start(&ReloadCmd, None);
+        let delete = panel.start(&DeleteCmd, None);
+        assert_eq!(panel.mutating(), ["Deleting"]);
+
+        panel.complete(delete, true);
+        assert!(panel.mutating().is_empty());
+    }
+
+    #[test]
+    fn test_retry_pruned_with_history() {
+        let mut panel = CommandPanel::new();
+        let first = panel.start(&ReloadCmd, None);
+        panel.complete(first, false);
+        let record = panel.history()[0].clone();
+        assert!(panel.retry(&record).is_some());
+
+        for _ in 0..panel.max_history {
+            let id = panel.start(&ReloadCmd, None);
+            panel.complete(id, true);
+        }
+        assert!(panel.retry(&record).is_none());
+        assert_eq!(panel.retries.len(), panel.max_history);
+    }
+}
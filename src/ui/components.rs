@@ -1,9 +1,19 @@
 mod confirm_dialog;
+mod date_time_input;
 mod list;
+mod motion;
 mod table;
+mod text_area;
 mod text_input;
+mod text_viewer;
+mod tree;
+mod value_generator;
 
 pub use confirm_dialog::{ConfirmDialog, ConfirmEvent};
+pub use date_time_input::{DateTimeInput, DateTimeInputEvent};
 pub use list::{List, ListEvent, ListRow};
 pub use table::{ColumnDef, Table, TableEvent, TableRow};
+pub use text_area::{TextArea, TextAreaEvent};
 pub use text_input::{TextInput, TextInputEvent};
+pub use text_viewer::{TextViewer, detect_syntax};
+pub use tree::{Tree, TreeEvent, TreeItem};
@@ -1,6 +1,7 @@
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::Frame;
 use ratatui::layout::{Alignment, Constraint, Rect};
 use ratatui::style::{Modifier, Style};
@@ -8,39 +9,59 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph};
 
 use crate::Theme;
-use crate::config::{DialogAction, KeyResolver};
+use crate::config::{DialogAction, KeyResolver, NavAction};
+use crate::i18n::t;
 use crate::ui::{Component, EventResult, Result};
 
+/// How long Confirm stays disabled in an [`RiskLevel::Irreversible`] dialog.
+const IRREVERSIBLE_DELAY: Duration = Duration::from_secs(2);
+
 pub enum ConfirmEvent {
     Confirmed,
     Cancelled,
 }
 
-#[derive(Default, Clone, Copy)]
-pub enum ConfirmStyle {
+/// How bad it is to confirm by accident.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RiskLevel {
     #[default]
     Normal,
-    /// Shows red warning styling.
+    /// Red styling, and Enter cancels unless Confirm is focused.
     Danger,
+    /// Like `Danger`, and Confirm is disabled for a moment after opening.
+    Irreversible,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Button {
+    Confirm,
+    Cancel,
 }
 
 pub struct ConfirmDialog {
     title: String,
     message: String,
+    /// Estimated charges of confirming, see [`crate::cost`].
+    cost: Option<String>,
     confirm_text: String,
     cancel_text: String,
-    style: ConfirmStyle,
+    risk: RiskLevel,
+    focus: Button,
+    opened_at: Instant,
     resolver: Arc<KeyResolver>,
 }
 
 impl ConfirmDialog {
     pub fn new(message: impl Into<String>, resolver: Arc<KeyResolver>) -> Self {
         Self {
-            title: "Confirm".to_string(),
+            title: t("Confirm").to_string(),
             message: message.into(),
-            confirm_text: "Yes".to_string(),
-            cancel_text: "No".to_string(),
-            style: ConfirmStyle::Normal,
+            cost: None,
+            confirm_text: t("Yes").to_string(),
+            cancel_text: t("No").to_string(),
+            risk: RiskLevel::Normal,
+            focus: Button::Confirm,
+            opened_at: Instant::now(),
             resolver,
         }
     }
@@ -50,6 +71,12 @@ impl ConfirmDialog {
         self
     }
 
+    /// Show an estimate of what confirming will be billed, if any.
+    pub fn with_cost(mut self, estimate: Option<String>) -> Self {
+        self.cost = estimate;
+        self
+    }
+
     pub fn with_confirm_text(mut self, text: impl Into<String>) -> Self {
         self.confirm_text = text.into();
         self
@@ -60,18 +87,65 @@ impl ConfirmDialog {
         self
     }
 
-    pub const fn danger(mut self) -> Self {
-        self.style = ConfirmStyle::Danger;
+    pub const fn with_risk(mut self, risk: RiskLevel) -> Self {
+        self.risk = risk;
+        self.focus = match risk {
+            RiskLevel::Normal => Button::Confirm,
+            RiskLevel::Danger | RiskLevel::Irreversible => Button::Cancel,
+        };
         self
     }
+
+    pub const fn danger(self) -> Self {
+        self.with_risk(RiskLevel::Danger)
+    }
+
+    pub const fn irreversible(self) -> Self {
+        self.with_risk(RiskLevel::Irreversible)
+    }
+
+    /// Time left before Confirm can be used.
+    fn remaining_delay(&self) -> Option<Duration> {
+        if self.risk != RiskLevel::Irreversible {
+            return None;
+        }
+        IRREVERSIBLE_DELAY
+            .checked_sub(self.opened_at.elapsed())
+            .filter(|left| !left.is_zero())
+    }
+
+    fn confirm(&self) -> EventResult<ConfirmEvent> {
+        if self.remaining_delay().is_some() {
+            return EventResult::Consumed;
+        }
+        ConfirmEvent::Confirmed.into()
+    }
 }
 
 impl Component for ConfirmDialog {
     type Output = ConfirmEvent;
 
     fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if matches!(
+            key.code,
+            KeyCode::Left | KeyCode::Right | KeyCode::Tab | KeyCode::BackTab
+        ) {
+            self.focus = match self.focus {
+                Button::Confirm => Button::Cancel,
+                Button::Cancel => Button::Confirm,
+            };
+            return Ok(EventResult::Consumed);
+        }
+        // Enter activates the focused button, so it is checked before the
+        // confirm keys it is usually part of
+        if self.resolver.matches_nav(&key, NavAction::Select) {
+            return Ok(match self.focus {
+                Button::Confirm => self.confirm(),
+                Button::Cancel => ConfirmEvent::Cancelled.into(),
+            });
+        }
         if self.resolver.matches_dialog(&key, DialogAction::Confirm) {
-            return Ok(ConfirmEvent::Confirmed.into());
+            return Ok(self.confirm());
         }
         if self.resolver.matches_dialog(&key, DialogAction::Cancel) {
             return Ok(ConfirmEvent::Cancelled.into());
@@ -82,15 +156,24 @@ impl Component for ConfirmDialog {
 
     fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
         // Calculate centered popup area
-        let popup_area = area.centered(Constraint::Percentage(50), Constraint::Length(7));
+        let height = if self.cost.is_some() { 8 } else { 7 };
+        let popup_area = area.centered(Constraint::Percentage(50), Constraint::Length(height));
 
         // Clear the area behind the popup
         frame.render_widget(Clear, popup_area);
 
-        // Choose colors based on style
-        let (title_color, border_color, confirm_color) = match self.style {
-            ConfirmStyle::Normal => (theme.mauve(), theme.lavender(), theme.green()),
-            ConfirmStyle::Danger => (theme.red(), theme.red(), theme.red()),
+        // Choose colors based on risk
+        let (title_color, border_color, confirm_color, border_type) = match self.risk {
+            RiskLevel::Normal => (
+                theme.mauve(),
+                theme.lavender(),
+                theme.green(),
+                theme.border_type,
+            ),
+            RiskLevel::Danger => (theme.red(), theme.red(), theme.red(), theme.border_type),
+            RiskLevel::Irreversible => {
+                (theme.red(), theme.maroon(), theme.red(), BorderType::Double)
+            }
         };
 
         // Build the content
@@ -98,29 +181,62 @@ impl Component for ConfirmDialog {
         let key_style = Style::default()
             .fg(theme.peach())
             .add_modifier(Modifier::BOLD);
-        let confirm_style = Style::default()
-            .fg(confirm_color)
-            .add_modifier(Modifier::BOLD);
+        let delay = self.remaining_delay();
+        let confirm_style = if delay.is_some() {
+            Style::default().fg(theme.overlay0())
+        } else {
+            Style::default()
+                .fg(confirm_color)
+                .add_modifier(Modifier::BOLD)
+        };
         let cancel_style = Style::default()
             .fg(theme.overlay1())
             .add_modifier(Modifier::BOLD);
+        let focused = |style: Style, button| {
+            if self.focus == button {
+                style
+                    .bg(theme.surface1())
+                    .add_modifier(Modifier::UNDERLINED)
+            } else {
+                style
+            }
+        };
+
+        let confirm_text = match delay {
+            Some(left) => format!("{} ({})", self.confirm_text, left.as_secs() + 1),
+            None => self.confirm_text.clone(),
+        };
 
-        let lines = vec![
+        let mut lines = vec![
             Line::from(""),
             Line::from(Span::styled(self.message.clone(), message_style)),
+        ];
+        if let Some(cost) = &self.cost {
+            lines.push(Line::from(Span::styled(
+                cost.clone(),
+                Style::default().fg(theme.yellow()),
+            )));
+        }
+        lines.extend([
             Line::from(""),
             Line::from(vec![
                 Span::styled("[y]", key_style),
                 Span::raw(" "),
-                Span::styled(self.confirm_text.clone(), confirm_style),
+                Span::styled(confirm_text, focused(confirm_style, Button::Confirm)),
                 Span::raw("    "),
                 Span::styled("[n]", key_style),
                 Span::raw(" "),
-                Span::styled(self.cancel_text.clone(), cancel_style),
+                Span::styled(
+                    self.cancel_text.clone(),
+                    focused(cancel_style, Button::Cancel),
+                ),
             ]),
-        ];
+        ]);
 
-        let title = format!(" {} ", self.title);
+        let title = match self.risk {
+            RiskLevel::Irreversible => format!(" {} {} ", theme.symbols.warning, self.title),
+            RiskLevel::Normal | RiskLevel::Danger => format!(" {} ", self.title),
+        };
         let block = Block::default()
             .title(title)
             .title_style(
@@ -129,7 +245,7 @@ impl Component for ConfirmDialog {
                     .add_modifier(Modifier::BOLD),
             )
             .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
+            .border_type(border_type)
             .border_style(Style::default().fg(border_color))
             .style(Style::default().bg(theme.base()));
 
@@ -140,3 +256,47 @@ impl Component for ConfirmDialog {
         frame.render_widget(paragraph, popup_area);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+
+    use super::*;
+    use crate::config::keybindings::KeybindingsConfig;
+
+    fn dialog() -> ConfirmDialog {
+        let resolver = Arc::new(KeyResolver::new(Arc::new(KeybindingsConfig::default())));
+        ConfirmDialog::new("Destroy?", resolver)
+    }
+
+    fn press(dialog: &mut ConfirmDialog, code: KeyCode) -> Option<bool> {
+        match dialog
+            .handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+            .unwrap()
+        {
+            EventResult::Event(ConfirmEvent::Confirmed) => Some(true),
+            EventResult::Event(ConfirmEvent::Cancelled) => Some(false),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_enter_follows_focus() {
+        assert_eq!(press(&mut dialog(), KeyCode::Enter), Some(true));
+
+        let mut danger = dialog().danger();
+        assert_eq!(press(&mut danger, KeyCode::Enter), Some(false));
+        press(&mut danger, KeyCode::Left);
+        assert_eq!(press(&mut danger, KeyCode::Enter), Some(true));
+    }
+
+    #[test]
+    fn test_irreversible_delays_confirm() {
+        let mut dialog = dialog().irreversible();
+        assert_eq!(press(&mut dialog, KeyCode::Char('y')), None);
+        assert_eq!(press(&mut dialog, KeyCode::Char('n')), Some(false));
+
+        dialog.opened_at -= IRREVERSIBLE_DELAY;
+        assert_eq!(press(&mut dialog, KeyCode::Char('y')), Some(true));
+    }
+}
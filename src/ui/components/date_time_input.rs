@@ -0,0 +1,131 @@
+use chrono::{DateTime, Utc};
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+
+use super::text_input::{TextInput, TextInputEvent};
+use crate::Theme;
+use crate::time::{format_timestamp, parse_moment, timezone_label};
+use crate::ui::{Component, EventResult, Result};
+
+pub enum DateTimeInputEvent {
+    /// The resolved timestamp, `None` if the input was left empty.
+    Submitted(Option<DateTime<Utc>>),
+    Cancelled,
+}
+
+/// Input for a point in time, typed as a date (`2030-01-31`) or a duration
+/// from now (`+30d`). Shows the resolved timestamp while typing.
+pub struct DateTimeInput {
+    input: TextInput,
+    future_only: bool,
+}
+
+impl DateTimeInput {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            input: TextInput::new(label)
+                .with_placeholder("+30d, 12h or 2030-01-31")
+                .with_validator(|value| resolve(value, Utc::now(), false).map(|_| ())),
+            future_only: false,
+        }
+    }
+
+    /// Reject timestamps that already passed.
+    pub fn future_only(mut self) -> Self {
+        self.input = self
+            .input
+            .with_validator(|value| resolve(value, Utc::now(), true).map(|_| ()));
+        self.future_only = true;
+        self
+    }
+}
+
+/// The timestamp `value` stands for, `None` if it is empty.
+fn resolve(
+    value: &str,
+    now: DateTime<Utc>,
+    future_only: bool,
+) -> std::result::Result<Option<DateTime<Utc>>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    let at = parse_moment(value, now)?;
+    if future_only && at <= now {
+        return Err("Must be in the future".to_string());
+    }
+    Ok(Some(at))
+}
+
+impl Component for DateTimeInput {
+    type Output = DateTimeInputEvent;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        Ok(match self.input.handle_key(key)? {
+            EventResult::Event(TextInputEvent::Submitted(value)) => {
+                // Resolved again so a duration counts from the moment of submitting
+                resolve(&value, Utc::now(), self.future_only).map_or(EventResult::Consumed, |at| {
+                    DateTimeInputEvent::Submitted(at).into()
+                })
+            }
+            EventResult::Event(TextInputEvent::Cancelled) => DateTimeInputEvent::Cancelled.into(),
+            result => result.consumed_only(),
+        })
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        Ok(self.input.handle_paste(text)?.consumed_only())
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.input.render(frame, area, theme);
+
+        let Ok(Some(at)) = resolve(self.input.value(), Utc::now(), self.future_only) else {
+            return;
+        };
+        // Below the input line, inside the popup's border
+        let popup = TextInput::popup_area(area);
+        let preview_area = Rect {
+            x: popup.x + 1,
+            y: popup.y + 2,
+            width: popup.width.saturating_sub(2),
+            height: 1,
+        };
+        let preview = Line::styled(
+            format!(
+                "{} {} {}",
+                theme.symbols.arrow,
+                format_timestamp(at),
+                timezone_label()
+            ),
+            Style::default().fg(theme.subtext0()),
+        );
+        frame.render_widget(Paragraph::new(preview), preview_area);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let now = DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(resolve("  ", now, true), Ok(None));
+        assert_eq!(
+            resolve("+30d", now, true)
+                .unwrap()
+                .map(|at| at.to_rfc3339()),
+            Some("2026-01-31T00:00:00+00:00".to_string())
+        );
+        assert!(resolve("2025-01-01", now, true).is_err());
+        assert!(resolve("2025-01-01", now, false).is_ok());
+        assert!(resolve("soon", now, false).is_err());
+    }
+}
@@ -6,10 +6,14 @@ use ratatui::layout::Rect;
 use ratatui::prelude::{Modifier, Style};
 use ratatui::widgets::{List as RatatuiList, ListItem, ListState};
 
+use super::motion::{MotionInput, MotionStep};
 use crate::Theme;
 use crate::config::{KeyResolver, NavAction};
 use crate::ui::{Component, EventResult, Result};
 
+/// Rows moved by page up/down.
+const PAGE_STEP: usize = 5;
+
 pub enum ListEvent<T> {
     Changed(T),
     Activated(T),
@@ -22,6 +26,9 @@ pub trait ListRow {
 pub struct List<T: ListRow + Clone> {
     items: Vec<T>,
     state: ListState,
+    /// Rows that fit in the viewport at the last render.
+    height: usize,
+    motion: MotionInput,
     resolver: Arc<KeyResolver>,
 }
 
@@ -34,16 +41,20 @@ impl<T: ListRow + Clone> List<T> {
         Self {
             items,
             state,
+            height: PAGE_STEP,
+            motion: MotionInput::default(),
             resolver,
         }
     }
 
-    #[allow(dead_code)]
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
     pub fn selected(&self) -> Option<&T> {
         self.state.selected().and_then(|i| self.items.get(i))
     }
 
-    #[allow(dead_code)]
     pub fn set_items(&mut self, items: Vec<T>) {
         self.items = items;
 
@@ -74,37 +85,18 @@ impl<T: ListRow + Clone> Component for List<T> {
     fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
         let before = self.state.selected();
 
-        if self.resolver.matches_nav(&key, NavAction::Down) {
-            self.state.select_next();
-            return Ok(self.get_change_event(before));
-        }
-        if self.resolver.matches_nav(&key, NavAction::Up) {
-            self.state.select_previous();
-            return Ok(self.get_change_event(before));
-        }
-        if self.resolver.matches_nav(&key, NavAction::Home) {
-            self.state.select_first();
-            return Ok(self.get_change_event(before));
-        }
-        if self.resolver.matches_nav(&key, NavAction::End) {
-            self.state.select_last();
-            return Ok(self.get_change_event(before));
-        }
-        if self.resolver.matches_nav(&key, NavAction::PageDown) {
-            let step = 5;
-            let new_index = match self.state.selected() {
-                Some(i) => usize::min(i + step, self.items.len().saturating_sub(1)),
-                None => 0,
-            };
-            self.state.select(Some(new_index));
-            return Ok(self.get_change_event(before));
-        }
-        if self.resolver.matches_nav(&key, NavAction::PageUp) {
-            let step = 5;
-            let new_index = self.state.selected().map_or(0, |i| i.saturating_sub(step));
-            self.state.select(Some(new_index));
-            return Ok(self.get_change_event(before));
+        match self.motion.handle_key(&key, &self.resolver) {
+            MotionStep::Count => return Ok(EventResult::Consumed),
+            MotionStep::Move(motion, count) => {
+                let len = self.items.len();
+                if let Some(target) = motion.apply(count, before, len, PAGE_STEP, self.height) {
+                    self.state.select(Some(target));
+                }
+                return Ok(self.get_change_event(before));
+            }
+            MotionStep::Other => {}
         }
+
         if self.resolver.matches_nav(&key, NavAction::Select) {
             if let Some(selected) = self.state.selected() {
                 return Ok(ListEvent::Activated(self.items[selected].clone()).into());
@@ -116,6 +108,7 @@ impl<T: ListRow + Clone> Component for List<T> {
     }
 
     fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        self.height = usize::from(area.height);
         let items: Vec<ListItem> = self.items.iter().map(|i| i.render_row(theme)).collect();
 
         let list = RatatuiList::new(items)
@@ -125,7 +118,7 @@ impl<T: ListRow + Clone> Component for List<T> {
                     .fg(theme.lavender())
                     .add_modifier(Modifier::BOLD),
             )
-            .highlight_symbol("▶ ");
+            .highlight_symbol(theme.symbols.highlight);
 
         frame.render_stateful_widget(list, area, &mut self.state);
     }
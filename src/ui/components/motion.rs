@@ -0,0 +1,158 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::config::{KeyResolver, NavAction};
+
+/// Larger counts are clamped; no list is that long anyway.
+const MAX_COUNT: usize = 99_999;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Motion {
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    HalfPageUp,
+    HalfPageDown,
+    First,
+    Last,
+}
+
+impl Motion {
+    /// Position after moving from `selected` in a list of `len` rows.
+    ///
+    /// `page` is the page step and `height` the number of visible rows.
+    /// With a count, `First` and `Last` jump to that (1-based) row as in vim.
+    pub fn apply(
+        self,
+        count: Option<usize>,
+        selected: Option<usize>,
+        len: usize,
+        page: usize,
+        height: usize,
+    ) -> Option<usize> {
+        let last = len.checked_sub(1)?;
+        let current = selected.unwrap_or(0);
+        let times = count.unwrap_or(1);
+        let half = (height / 2).max(1);
+        let target = match self {
+            Self::Up => current.saturating_sub(times),
+            Self::Down => current.saturating_add(times),
+            Self::PageUp => current.saturating_sub(page * times),
+            Self::PageDown => current.saturating_add(page * times),
+            Self::HalfPageUp => current.saturating_sub(half * times),
+            Self::HalfPageDown => current.saturating_add(half * times),
+            Self::First => count.map_or(0, |row| row - 1),
+            Self::Last => count.map_or(last, |row| row - 1),
+        };
+        Some(target.min(last))
+    }
+}
+
+/// Result of feeding a key to [`MotionInput`].
+pub enum MotionStep {
+    /// The key was a digit of a count prefix.
+    Count,
+    Move(Motion, Option<usize>),
+    /// Not a motion; the caller handles the key.
+    Other,
+}
+
+/// Turns keys into motions, collecting vim-style count prefixes like `5j`.
+#[derive(Debug, Default)]
+pub struct MotionInput {
+    count: Option<usize>,
+}
+
+impl MotionInput {
+    pub fn handle_key(&mut self, key: &KeyEvent, resolver: &KeyResolver) -> MotionStep {
+        if resolver.counts_enabled()
+            && key.modifiers - KeyModifiers::SHIFT == KeyModifiers::NONE
+            && let KeyCode::Char(c @ '0'..='9') = key.code
+            && (c != '0' || self.count.is_some())
+        {
+            let digit = c as usize - '0' as usize;
+            let count = self.count.unwrap_or(0).saturating_mul(10) + digit;
+            self.count = Some(count.min(MAX_COUNT));
+            return MotionStep::Count;
+        }
+
+        let motion = [
+            (NavAction::Up, Motion::Up),
+            (NavAction::Down, Motion::Down),
+            (NavAction::PageUp, Motion::PageUp),
+            (NavAction::PageDown, Motion::PageDown),
+            (NavAction::HalfPageUp, Motion::HalfPageUp),
+            (NavAction::HalfPageDown, Motion::HalfPageDown),
+            (NavAction::Home, Motion::First),
+            (NavAction::End, Motion::Last),
+        ]
+        .into_iter()
+        .find(|(action, _)| resolver.matches_nav(key, *action))
+        .map(|(_, motion)| motion);
+
+        if let Some(motion) = motion {
+            return MotionStep::Move(motion, self.count.take());
+        }
+        // Keep the count across the first key of a sequence, as in `5gg`
+        if resolver.pending().is_some() || !resolver.starts_chord(key) {
+            self.count = None;
+        }
+        MotionStep::Other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::config::keybindings::KeybindingsConfig;
+
+    fn press(input: &mut MotionInput, resolver: &KeyResolver, keys: &str) -> MotionStep {
+        let mut step = MotionStep::Other;
+        for c in keys.chars() {
+            let key = KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+            step = input.handle_key(&key, resolver);
+        }
+        step
+    }
+
+    #[test]
+    fn test_count_prefix() {
+        let resolver = KeyResolver::new(Arc::new(KeybindingsConfig::default()));
+        let mut input = MotionInput::default();
+
+        let MotionStep::Move(motion, count) = press(&mut input, &resolver, "12j") else {
+            panic!("expected a motion");
+        };
+        assert_eq!(motion.apply(count, Some(3), 100, 10, 20), Some(15));
+        assert_eq!(motion.apply(count, Some(95), 100, 10, 20), Some(99));
+
+        // The count is used up by the motion
+        let MotionStep::Move(motion, count) = press(&mut input, &resolver, "k") else {
+            panic!("expected a motion");
+        };
+        assert_eq!(motion.apply(count, Some(3), 100, 10, 20), Some(2));
+
+        // Other keys drop a pending count
+        press(&mut input, &resolver, "5x");
+        assert!(matches!(
+            press(&mut input, &resolver, "G"),
+            MotionStep::Move(Motion::Last, None)
+        ));
+    }
+
+    #[test]
+    fn test_jump_to_row() {
+        assert_eq!(Motion::Last.apply(Some(7), Some(0), 100, 10, 20), Some(6));
+        assert_eq!(
+            Motion::First.apply(Some(500), Some(0), 100, 10, 20),
+            Some(99)
+        );
+        assert_eq!(
+            Motion::HalfPageDown.apply(None, Some(0), 100, 10, 20),
+            Some(10)
+        );
+        assert_eq!(Motion::Down.apply(None, None, 0, 10, 20), None);
+    }
+}
@@ -1,24 +1,25 @@
+use std::borrow::Cow;
 use std::sync::Arc;
 
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::Frame;
-use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::layout::{Constraint, Layout, Margin, Rect};
 use ratatui::prelude::{Modifier, Style};
-use ratatui::widgets::{
-    Block,
-    BorderType,
-    Borders,
-    Cell,
-    Paragraph,
-    Row,
-    Table as RatatuiTable,
-    TableState,
-};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table as RatatuiTable, TableState};
 
+use super::motion::{MotionInput, MotionStep};
 use crate::Theme;
 use crate::config::{KeyResolver, NavAction, SearchAction};
+use crate::i18n::{t, t_args};
+use crate::ui::text::{display_width, padding, truncate};
 use crate::ui::{Component, EventResult, Result};
 
+/// Rows moved by page up/down.
+const PAGE_STEP: usize = 10;
+/// Narrowest table that still makes room for the preview pane.
+const MIN_PREVIEW_WIDTH: u16 = 80;
+
 pub enum TableEvent<T> {
     Changed(T),
     Activated(T),
@@ -27,16 +28,33 @@ pub enum TableEvent<T> {
 }
 
 pub struct ColumnDef {
-    pub header: &'static str,
+    pub header: Cow<'static, str>,
     pub constraint: Constraint,
 }
 
 impl ColumnDef {
     pub const fn new(header: &'static str, constraint: Constraint) -> Self {
-        Self { header, constraint }
+        Self {
+            header: Cow::Borrowed(header),
+            constraint,
+        }
+    }
+
+    /// Column with a header only known at runtime.
+    pub const fn dynamic(header: String, constraint: Constraint) -> Self {
+        Self {
+            header: Cow::Owned(header),
+            constraint,
+        }
     }
 }
 
+/// A type that can be displayed as a table row.
+///
+/// Rendered rows are cached by the table and only rebuilt when the items,
+/// search query or theme change, so implementations may do some work here.
+/// Prefer borrowing `'static` text (e.g. `Cell::from("GCP")`) over
+/// allocating a `String` for it.
 pub trait TableRow {
     fn columns() -> &'static [ColumnDef];
     fn render_cells(&self, theme: &Theme) -> Vec<Cell<'static>>;
@@ -49,15 +67,49 @@ pub trait TableRow {
 
     /// Return true if this row matches the search query for local filtering.
     fn matches(&self, query: &str) -> bool;
+
+    /// Fields shown beneath the selected row when it is expanded, e.g. values
+    /// the columns cut short. An empty label continues the previous field.
+    fn details(&self) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+}
+
+/// A named condition rows must meet while it is active, toggled by the
+/// screen that owns the table.
+struct RowFilter<T> {
+    label: &'static str,
+    keep: fn(&T) -> bool,
+    active: bool,
 }
 
 pub struct Table<T: TableRow + Clone> {
     items: Vec<T>,
     filtered_indices: Vec<usize>,
     state: TableState,
+    /// First visible position in `filtered_indices`.
+    offset: usize,
+    /// Rows that fit in the viewport at the last render.
+    height: usize,
+    motion: MotionInput,
+    /// Rendered rows by item index, filled lazily for visible rows.
+    row_cache: Vec<Option<Row<'static>>>,
+    /// Theme the cached rows were rendered with.
+    cached_theme: Option<Theme>,
+    /// Overrides `T::columns()` for rows whose shape is only known at runtime.
+    columns: Option<Vec<ColumnDef>>,
     title: Option<String>,
     searching: bool,
     query: String,
+    filters: Vec<RowFilter<T>>,
+    /// Headers of the columns left out until toggled on.
+    hidden_columns: Vec<&'static str>,
+    /// Whether the selected row shows its [`TableRow::details`].
+    expanded: bool,
+    /// Whether a pane beside the table shows the selected row's preview.
+    previewing: bool,
+    /// Fields of the preview, [`TableRow::details`] unless overridden.
+    preview: fn(&T) -> Vec<(&'static str, String)>,
     resolver: Arc<KeyResolver>,
 }
 
@@ -69,12 +121,23 @@ impl<T: TableRow + Clone> Table<T> {
             state.select(Some(0));
         }
         Self {
+            row_cache: vec![None; items.len()],
             items,
             filtered_indices,
             state,
+            offset: 0,
+            height: PAGE_STEP,
+            motion: MotionInput::default(),
+            cached_theme: None,
+            columns: None,
             title: None,
             searching: false,
             query: String::new(),
+            filters: Vec::new(),
+            hidden_columns: Vec::new(),
+            expanded: false,
+            previewing: false,
+            preview: T::details,
             resolver,
         }
     }
@@ -84,6 +147,90 @@ impl<T: TableRow + Clone> Table<T> {
         self
     }
 
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.title = Some(title.into());
+    }
+
+    #[must_use]
+    pub fn with_columns(mut self, columns: Vec<ColumnDef>) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+
+    /// Preview rows with `preview` instead of their [`TableRow::details`].
+    #[must_use]
+    pub fn with_preview(mut self, preview: fn(&T) -> Vec<(&'static str, String)>) -> Self {
+        self.preview = preview;
+        self
+    }
+
+    /// Register a filter labelled `label`, inactive until toggled.
+    #[must_use]
+    pub fn with_filter(mut self, label: &'static str, keep: fn(&T) -> bool) -> Self {
+        self.filters.push(RowFilter {
+            label,
+            keep,
+            active: false,
+        });
+        self
+    }
+
+    /// Turn the filter labelled `label` on or off, combined with the search query.
+    pub fn toggle_filter(&mut self, label: &str) {
+        if let Some(filter) = self.filters.iter_mut().find(|f| f.label == label) {
+            filter.active = !filter.active;
+            self.update_filter();
+        }
+    }
+
+    /// Leave out the column headed `header` until it is toggled on.
+    #[must_use]
+    pub fn with_hidden_column(mut self, header: &'static str) -> Self {
+        self.hidden_columns.push(header);
+        self
+    }
+
+    /// Show the column headed `header` if hidden, hide it otherwise.
+    pub fn toggle_column(&mut self, header: &'static str) {
+        if let Some(pos) = self.hidden_columns.iter().position(|&h| h == header) {
+            self.hidden_columns.remove(pos);
+        } else {
+            self.hidden_columns.push(header);
+        }
+        self.row_cache.fill(None);
+    }
+
+    /// Whether the column at `idx` of [`Self::column_defs`] is shown.
+    fn column_visible(&self, idx: usize) -> bool {
+        let header = &self.column_defs()[idx].header;
+        !self.hidden_columns.iter().any(|&h| h == header)
+    }
+
+    /// The columns not hidden, in display order.
+    fn visible_columns(&self) -> Vec<&ColumnDef> {
+        self.column_defs()
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.column_visible(*i))
+            .map(|(_, c)| c)
+            .collect()
+    }
+
+    /// The title followed by the labels of the active filters.
+    fn display_title(&self) -> Option<String> {
+        let title = self.title.as_ref()?;
+        let labels: Vec<&str> = self
+            .filters
+            .iter()
+            .filter(|f| f.active)
+            .map(|f| t(f.label))
+            .collect();
+        if labels.is_empty() {
+            return Some(title.clone());
+        }
+        Some(format!("{title}[{}] ", labels.join(", ")))
+    }
+
     pub fn selected_item(&self) -> Option<&T> {
         if let Some(selected) = self.state.selected()
             && let Some(&idx) = self.filtered_indices.get(selected)
@@ -93,12 +240,81 @@ impl<T: TableRow + Clone> Table<T> {
         None
     }
 
+    /// Select the first row matching `predicate`, clearing the search and
+    /// filters if they hide it. Returns whether a row was found.
+    pub fn select_where(&mut self, predicate: impl Fn(&T) -> bool) -> bool {
+        let Some(idx) = self.items.iter().position(predicate) else {
+            return false;
+        };
+        if !self.filtered_indices.contains(&idx) {
+            self.query.clear();
+            for filter in &mut self.filters {
+                filter.active = false;
+            }
+            self.update_filter();
+        }
+        let position = self.filtered_indices.iter().position(|&i| i == idx);
+        self.state.select(position);
+        true
+    }
+
+    /// Replace the table rows, keeping the current filter and selection where possible.
+    pub fn set_items(&mut self, items: Vec<T>) {
+        self.items = items;
+        self.update_filter();
+    }
+
+    /// Change the first row matching `predicate` in place, e.g. to fill in
+    /// a value loaded later. Returns whether a row was found.
+    pub fn update_where(
+        &mut self,
+        predicate: impl Fn(&T) -> bool,
+        update: impl FnOnce(&mut T),
+    ) -> bool {
+        let Some(idx) = self.items.iter().position(predicate) else {
+            return false;
+        };
+        update(&mut self.items[idx]);
+        self.row_cache[idx] = None;
+        true
+    }
+
+    /// The rows in view, as of the last render with the selection scrolled
+    /// into it.
+    pub fn visible_items(&mut self) -> impl Iterator<Item = &T> {
+        self.scroll_into_view(self.height);
+        let end = (self.offset + self.height).min(self.filtered_indices.len());
+        self.filtered_indices[self.offset..end]
+            .iter()
+            .map(|&idx| &self.items[idx])
+    }
+
+    /// All rows, including those the search or filters hide.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// The columns shown, `T::columns()` unless overridden.
+    pub fn column_defs(&self) -> &[ColumnDef] {
+        self.columns.as_deref().unwrap_or_else(|| T::columns())
+    }
+
+    /// Iterate over the rows that pass the current search filter, in display order.
+    pub fn filtered_items(&self) -> impl Iterator<Item = &T> {
+        self.filtered_indices.iter().map(|&idx| &self.items[idx])
+    }
+
     fn update_filter(&mut self) {
+        // Rows may highlight the query, and the item list may have changed
+        self.row_cache.clear();
+        self.row_cache.resize(self.items.len(), None);
+
         self.filtered_indices = self
             .items
             .iter()
             .enumerate()
             .filter(|(_, item)| self.query.is_empty() || item.matches(&self.query))
+            .filter(|(_, item)| self.filters.iter().all(|f| !f.active || (f.keep)(item)))
             .map(|(i, _)| i)
             .collect();
 
@@ -114,44 +330,35 @@ impl<T: TableRow + Clone> Table<T> {
         }
     }
 
-    const fn select_next(&mut self) {
-        if self.filtered_indices.is_empty() {
-            return;
-        }
-        let i = match self.state.selected() {
-            Some(i) => {
-                if i >= self.filtered_indices.len() - 1 {
-                    i
-                } else {
-                    i + 1
-                }
+    /// Adjust the scroll offset so the selected row is within `visible` rows.
+    fn scroll_into_view(&mut self, visible: usize) {
+        let max_offset = self.filtered_indices.len().saturating_sub(visible);
+        if let Some(selected) = self.state.selected() {
+            if selected < self.offset {
+                self.offset = selected;
+            } else if visible > 0 && selected >= self.offset + visible {
+                self.offset = selected + 1 - visible;
             }
-            None => 0,
-        };
-        self.state.select(Some(i));
-    }
-
-    const fn select_previous(&mut self) {
-        if self.filtered_indices.is_empty() {
-            return;
-        }
-        let i = match self.state.selected() {
-            Some(i) => i.saturating_sub(1),
-            None => 0,
-        };
-        self.state.select(Some(i));
-    }
-
-    const fn select_first(&mut self) {
-        if !self.filtered_indices.is_empty() {
-            self.state.select(Some(0));
         }
+        self.offset = self.offset.min(max_offset);
     }
 
-    const fn select_last(&mut self) {
-        if !self.filtered_indices.is_empty() {
-            self.state.select(Some(self.filtered_indices.len() - 1));
+    /// Rendered row for an item, built on first use and cached until the
+    /// items, query, theme or shown columns change.
+    fn cached_row(&mut self, idx: usize, theme: &Theme) -> Row<'static> {
+        if let Some(row) = &self.row_cache[idx] {
+            return row.clone();
         }
+        let cells: Vec<Cell<'static>> = self.items[idx]
+            .render_cells_with_query(theme, &self.query)
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| self.column_visible(*i))
+            .map(|(_, cell)| cell)
+            .collect();
+        let row = Row::new(cells).style(Style::default().fg(theme.text()));
+        self.row_cache[idx] = Some(row.clone());
+        row
     }
 
     fn get_change_event(&self, before: Option<usize>) -> EventResult<TableEvent<T>> {
@@ -204,44 +411,18 @@ impl<T: TableRow + Clone> Table<T> {
     fn handle_navigation_key(&mut self, key: KeyEvent) -> EventResult<TableEvent<T>> {
         let before = self.state.selected();
 
-        // Check navigation actions using resolver
-        if self.resolver.matches_nav(&key, NavAction::Down) {
-            self.select_next();
-            return self.get_change_event(before);
-        }
-        if self.resolver.matches_nav(&key, NavAction::Up) {
-            self.select_previous();
-            return self.get_change_event(before);
-        }
-        if self.resolver.matches_nav(&key, NavAction::Home) {
-            self.select_first();
-            return self.get_change_event(before);
-        }
-        if self.resolver.matches_nav(&key, NavAction::End) {
-            self.select_last();
-            return self.get_change_event(before);
-        }
-        if self.resolver.matches_nav(&key, NavAction::PageDown) {
-            let step = 10;
-            let new_index = match self.state.selected() {
-                Some(i) if !self.filtered_indices.is_empty() => {
-                    usize::min(i + step, self.filtered_indices.len() - 1)
+        match self.motion.handle_key(&key, &self.resolver) {
+            MotionStep::Count => return EventResult::Consumed,
+            MotionStep::Move(motion, count) => {
+                let len = self.filtered_indices.len();
+                if let Some(target) = motion.apply(count, before, len, PAGE_STEP, self.height) {
+                    self.state.select(Some(target));
                 }
-                _ => 0,
-            };
-            if !self.filtered_indices.is_empty() {
-                self.state.select(Some(new_index));
-            }
-            return self.get_change_event(before);
-        }
-        if self.resolver.matches_nav(&key, NavAction::PageUp) {
-            let step = 10;
-            let new_index = self.state.selected().map_or(0, |i| i.saturating_sub(step));
-            if !self.filtered_indices.is_empty() {
-                self.state.select(Some(new_index));
+                return self.get_change_event(before);
             }
-            return self.get_change_event(before);
+            MotionStep::Other => {}
         }
+
         if self.resolver.matches_nav(&key, NavAction::Select) {
             if let Some(selected) = self.state.selected() {
                 return self
@@ -253,6 +434,14 @@ impl<T: TableRow + Clone> Table<T> {
             }
             return EventResult::Ignored;
         }
+        if self.resolver.matches_nav(&key, NavAction::Expand) {
+            self.expanded = !self.expanded;
+            return EventResult::Consumed;
+        }
+        if self.resolver.matches_nav(&key, NavAction::Preview) {
+            self.previewing = !self.previewing;
+            return EventResult::Consumed;
+        }
         if self.resolver.matches_search(&key, SearchAction::Toggle) {
             self.searching = true;
             return EventResult::Consumed;
@@ -266,6 +455,68 @@ impl<T: TableRow + Clone> Table<T> {
 
         EventResult::Ignored
     }
+
+    /// Draw `details` over the lines the expanded selected row left blank.
+    fn render_details(
+        &self,
+        frame: &mut Frame,
+        table_area: Rect,
+        details: &[(&str, String)],
+        theme: &Theme,
+    ) {
+        let Some(selected) = self.state.selected() else {
+            return;
+        };
+        let inner = if self.title.is_some() {
+            table_area.inner(Margin::new(1, 1))
+        } else {
+            table_area
+        };
+        // Below the header and the selected row's own line
+        let indent = u16::try_from(display_width(theme.symbols.highlight)).unwrap_or(0);
+        let y = inner.y + 2 + u16::try_from(selected - self.offset).unwrap_or(0);
+        let area = Rect::new(
+            inner.x + indent,
+            y,
+            inner.width.saturating_sub(indent),
+            u16::try_from(details.len()).unwrap_or(0),
+        )
+        .intersection(inner);
+        frame.render_widget(detail_block(details, area.width, theme), area);
+    }
+
+    /// Draw the preview of the selected row at the right of `area` if it is
+    /// on and fits, returning what is left for the table.
+    fn render_preview(&self, frame: &mut Frame, area: Rect, theme: &Theme) -> Rect {
+        if !self.previewing || area.width < MIN_PREVIEW_WIDTH {
+            return area;
+        }
+        let [table_area, area] =
+            Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .areas(area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.border()))
+            .title(format!(" {} ", t("Preview")))
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            );
+        let inner = block.inner(area).inner(Margin::new(1, 0));
+        frame.render_widget(block, area);
+
+        let fields = self.selected_item().map(self.preview).unwrap_or_default();
+        if fields.is_empty() {
+            let empty =
+                Paragraph::new(t("No details")).style(Style::default().fg(theme.overlay1()));
+            frame.render_widget(empty, inner);
+        } else {
+            frame.render_widget(detail_block(&fields, inner.width, theme), inner);
+        }
+        table_area
+    }
 }
 
 impl<T: TableRow + Clone> Component for Table<T> {
@@ -279,7 +530,18 @@ impl<T: TableRow + Clone> Component for Table<T> {
         }
     }
 
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        if !self.searching {
+            return Ok(EventResult::Ignored);
+        }
+        self.query.extend(text.chars().filter(|c| !c.is_control()));
+        self.update_filter();
+        Ok(TableEvent::SearchChanged(self.query.clone()).into())
+    }
+
     fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let area = self.render_preview(frame, area, theme);
+
         // If searching or has active filter, reserve space for search bar
         let has_search_bar = self.searching || !self.query.is_empty();
         let (table_area, search_area) = if has_search_bar {
@@ -289,12 +551,12 @@ impl<T: TableRow + Clone> Component for Table<T> {
             (area, None)
         };
 
-        let columns = T::columns();
+        let columns = self.visible_columns();
 
         let header_cells: Vec<Cell> = columns
             .iter()
             .map(|c| {
-                Cell::from(c.header).style(
+                Cell::from(t(&c.header).to_string()).style(
                     Style::default()
                         .fg(theme.header())
                         .add_modifier(Modifier::BOLD),
@@ -304,17 +566,40 @@ impl<T: TableRow + Clone> Component for Table<T> {
         let header = Row::new(header_cells)
             .height(1)
             .style(Style::default().bg(theme.surface0()));
+        let widths: Vec<Constraint> = columns.iter().map(|c| c.constraint).collect();
 
-        let rows: Vec<Row> = self
-            .filtered_indices
-            .iter()
-            .map(|&idx| {
-                Row::new(self.items[idx].render_cells_with_query(theme, &self.query))
-                    .style(Style::default().fg(theme.text()))
+        if self.cached_theme.as_ref() != Some(theme) {
+            self.cached_theme = Some(*theme);
+            self.row_cache.fill(None);
+        }
+
+        // Only build rows that fit in the viewport: borders and header take 3 lines
+        let chrome = if self.title.is_some() { 3 } else { 1 };
+        let visible = usize::from(table_area.height.saturating_sub(chrome));
+        // The expanded row grows by its details, leaving room for fewer rows
+        let details = self
+            .selected_item()
+            .filter(|_| self.expanded)
+            .map(TableRow::details)
+            .unwrap_or_default();
+        let detail_lines = details.len().min(visible.saturating_sub(1));
+        let visible = visible - detail_lines;
+        self.height = visible;
+        self.scroll_into_view(visible);
+        let end = (self.offset + visible).min(self.filtered_indices.len());
+        let selected = self.state.selected();
+        let rows: Vec<Row> = (self.offset..end)
+            .map(|pos| {
+                let row = self.cached_row(self.filtered_indices[pos], theme);
+                if Some(pos) == selected {
+                    row.height(u16::try_from(detail_lines + 1).unwrap_or(u16::MAX))
+                } else {
+                    row
+                }
             })
             .collect();
-
-        let widths: Vec<Constraint> = columns.iter().map(|c| c.constraint).collect();
+        let mut view_state = TableState::default()
+            .with_selected(self.state.selected().map(|selected| selected - self.offset));
 
         let mut table = RatatuiTable::new(rows, widths)
             .header(header)
@@ -324,14 +609,14 @@ impl<T: TableRow + Clone> Component for Table<T> {
                     .fg(theme.lavender())
                     .add_modifier(Modifier::BOLD),
             )
-            .highlight_symbol("▶ ");
+            .highlight_symbol(theme.symbols.highlight);
 
-        if let Some(title) = &self.title {
+        if let Some(title) = self.display_title() {
             let block = Block::default()
                 .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
+                .border_type(theme.border_type)
                 .border_style(Style::default().fg(theme.border()))
-                .title(title.as_str())
+                .title(title)
                 .title_style(
                     Style::default()
                         .fg(theme.mauve())
@@ -340,14 +625,25 @@ impl<T: TableRow + Clone> Component for Table<T> {
             table = table.block(block);
         }
 
-        frame.render_stateful_widget(table, table_area, &mut self.state);
+        frame.render_stateful_widget(table, table_area, &mut view_state);
+
+        if detail_lines > 0 {
+            self.render_details(frame, table_area, &details[..detail_lines], theme);
+        }
 
         // Render search bar if needed
         if let Some(search_area) = search_area {
             let search_text = if self.searching {
                 format!("/{}_", self.query)
             } else {
-                format!("/{} ({} matches)", self.query, self.filtered_indices.len())
+                format!(
+                    "/{} ({})",
+                    self.query,
+                    t_args(
+                        "{count} matches",
+                        &[("count", &self.filtered_indices.len().to_string())]
+                    )
+                )
             };
 
             let search_style = if self.searching {
@@ -361,3 +657,238 @@ impl<T: TableRow + Clone> Component for Table<T> {
         }
     }
 }
+
+/// Aligned `label  value` lines for an expanded row.
+fn detail_block(details: &[(&str, String)], width: u16, theme: &Theme) -> Paragraph<'static> {
+    let labels: Vec<String> = details
+        .iter()
+        .map(|(label, _)| t(label).to_string())
+        .collect();
+    let label_width = labels
+        .iter()
+        .map(|label| display_width(label))
+        .max()
+        .unwrap_or(0)
+        + 2;
+    let value_width = usize::from(width).saturating_sub(label_width);
+    let lines: Vec<Line> = labels
+        .into_iter()
+        .zip(details)
+        .map(|(label, (_, value))| {
+            let padding = padding(&label, label_width);
+            Line::from(vec![
+                Span::styled(label, Style::default().fg(theme.subtext0())),
+                Span::raw(padding),
+                Span::styled(
+                    truncate(value, value_width, theme.symbols.ellipsis),
+                    Style::default().fg(theme.text()),
+                ),
+            ])
+        })
+        .collect();
+    Paragraph::new(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use crossterm::event::KeyModifiers;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    use super::*;
+    use crate::config::keybindings::KeybindingsConfig;
+
+    #[derive(Clone)]
+    struct Item(String);
+
+    impl TableRow for Item {
+        fn columns() -> &'static [ColumnDef] {
+            static COLUMNS: &[ColumnDef] = &[ColumnDef::new("Name", Constraint::Min(10))];
+            COLUMNS
+        }
+
+        fn render_cells(&self, _theme: &Theme) -> Vec<Cell<'static>> {
+            vec![Cell::from(self.0.clone())]
+        }
+
+        fn matches(&self, query: &str) -> bool {
+            self.0.contains(query)
+        }
+
+        fn details(&self) -> Vec<(&'static str, String)> {
+            vec![
+                ("Full", format!("{}-details", self.0)),
+                ("", "more".to_string()),
+            ]
+        }
+    }
+
+    fn table(rows: usize) -> Table<Item> {
+        let items = (0..rows).map(|i| Item(format!("item-{i}"))).collect();
+        let resolver = Arc::new(KeyResolver::new(Arc::new(KeybindingsConfig::default())));
+        Table::new(items, resolver).with_title(" Items ")
+    }
+
+    #[test]
+    fn test_selection_stays_visible() {
+        let mut table = table(100);
+        let theme = Theme::catppuccin_mocha();
+        let mut terminal = Terminal::new(TestBackend::new(40, 13)).unwrap();
+
+        for _ in 0..50 {
+            let _ = table.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        }
+        terminal
+            .draw(|frame| table.render(frame, frame.area(), &theme))
+            .unwrap();
+
+        // 13 lines minus borders and header leave 10 rows, ending at the selection
+        assert_eq!(table.offset, 41);
+        let buffer = terminal.backend().buffer();
+        let last_row: String = (0..40).map(|x| buffer[(x, 11)].symbol()).collect();
+        assert!(last_row.contains("item-50"));
+    }
+
+    #[test]
+    fn test_expanded_row_shows_details() {
+        let mut table = table(20);
+        let theme = Theme::catppuccin_mocha();
+        let mut terminal = Terminal::new(TestBackend::new(40, 8)).unwrap();
+        let row = |terminal: &Terminal<TestBackend>, y| -> String {
+            let buffer = terminal.backend().buffer();
+            (0..40).map(|x| buffer[(x, y)].symbol()).collect()
+        };
+
+        for _ in 0..4 {
+            let _ = table.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        }
+        let _ = table.handle_key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::NONE));
+        terminal
+            .draw(|frame| table.render(frame, frame.area(), &theme))
+            .unwrap();
+
+        // 5 row lines, 2 of them taken by the details of the selection
+        assert_eq!(table.height, 3);
+        assert!(row(&terminal, 4).contains("item-4"));
+        assert!(row(&terminal, 5).contains("Full  item-4-details"));
+        assert!(row(&terminal, 6).contains("more"));
+    }
+
+    #[test]
+    fn test_preview_follows_selection() {
+        let mut table = table(20);
+        let theme = Theme::catppuccin_mocha();
+        let mut terminal = Terminal::new(TestBackend::new(100, 8)).unwrap();
+        let preview = |terminal: &Terminal<TestBackend>| -> String {
+            let buffer = terminal.backend().buffer();
+            (0..8)
+                .flat_map(|y| (60..100).map(move |x| (x, y)))
+                .map(|pos| buffer[pos].symbol())
+                .collect()
+        };
+
+        let _ = table.handle_key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL));
+        terminal
+            .draw(|frame| table.render(frame, frame.area(), &theme))
+            .unwrap();
+        assert!(preview(&terminal).contains("Full  item-0-details"));
+
+        let _ = table.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        terminal
+            .draw(|frame| table.render(frame, frame.area(), &theme))
+            .unwrap();
+        assert!(preview(&terminal).contains("Full  item-1-details"));
+
+        // Too narrow to share, the table keeps the whole width
+        let mut terminal = Terminal::new(TestBackend::new(60, 8)).unwrap();
+        terminal
+            .draw(|frame| table.render(frame, frame.area(), &theme))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let screen: String = buffer
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect();
+        assert!(!screen.contains("Preview"));
+    }
+
+    #[test]
+    fn test_filters_combine_with_search() {
+        let mut table =
+            table(20).with_filter("Even", |item| item.0.ends_with(['0', '2', '4', '6', '8']));
+        table.toggle_filter("Even");
+        assert_eq!(table.filtered_items().count(), 10);
+        assert_eq!(table.display_title().as_deref(), Some(" Items [Even] "));
+
+        table.query = "1".to_string();
+        table.update_filter();
+        let names: Vec<&str> = table.filtered_items().map(|item| item.0.as_str()).collect();
+        assert_eq!(
+            names,
+            ["item-10", "item-12", "item-14", "item-16", "item-18"]
+        );
+
+        table.toggle_filter("Even");
+        assert_eq!(table.filtered_items().count(), 11);
+        assert_eq!(table.display_title().as_deref(), Some(" Items "));
+    }
+
+    #[test]
+    fn test_hidden_column_toggles() {
+        let mut table = table(3).with_hidden_column("Name");
+        let theme = Theme::catppuccin_mocha();
+        let mut terminal = Terminal::new(TestBackend::new(40, 6)).unwrap();
+        let header = |terminal: &Terminal<TestBackend>| -> String {
+            let buffer = terminal.backend().buffer();
+            (0..40).map(|x| buffer[(x, 1)].symbol()).collect()
+        };
+
+        terminal
+            .draw(|frame| table.render(frame, frame.area(), &theme))
+            .unwrap();
+        assert!(!header(&terminal).contains("Name"));
+
+        table.toggle_column("Name");
+        terminal
+            .draw(|frame| table.render(frame, frame.area(), &theme))
+            .unwrap();
+        assert!(header(&terminal).contains("Name"));
+        let buffer = terminal.backend().buffer();
+        let first_row: String = (0..40).map(|x| buffer[(x, 2)].symbol()).collect();
+        assert!(first_row.contains("item-0"));
+    }
+
+    #[test]
+    fn test_paste_only_goes_to_search() {
+        let mut table = table(20);
+        assert!(!table.handle_paste("item-1").unwrap().is_consumed());
+        assert!(table.query.is_empty());
+
+        let _ = table.handle_key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE));
+        let _ = table.handle_paste("item-1\n");
+        assert_eq!(table.query, "item-1");
+        assert_eq!(table.filtered_indices.len(), 11);
+    }
+
+    /// Render cost for a large table; run with `cargo test -- --ignored`.
+    #[test]
+    #[ignore = "benchmark"]
+    fn bench_render_10k_rows() {
+        const FRAMES: u32 = 1000;
+        let mut table = table(10_000);
+        let theme = Theme::catppuccin_mocha();
+        let mut terminal = Terminal::new(TestBackend::new(120, 50)).unwrap();
+
+        let start = Instant::now();
+        for _ in 0..FRAMES {
+            let _ = table.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+            terminal
+                .draw(|frame| table.render(frame, frame.area(), &theme))
+                .unwrap();
+        }
+        println!("10k rows: {:?} per frame", start.elapsed() / FRAMES);
+    }
+}
@@ -0,0 +1,435 @@
+use std::fs;
+use std::path::Path;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use tokio::runtime::Handle;
+
+use crate::Theme;
+use crate::encryption::{self, Scheme};
+use crate::i18n::t;
+use crate::ui::components::value_generator::{GeneratorEvent, ValueGenerator};
+use crate::ui::{Component, EventResult, Result, TextInput, TextInputEvent};
+
+pub enum TextAreaEvent {
+    Submitted(String),
+    Cancelled,
+}
+
+/// Multi-line text editor for payloads like JSON documents or PEM keys.
+///
+/// Enter inserts a newline; Ctrl+S submits and Ctrl+O loads a file, decrypted
+/// if it is an `.age` or `.gpg` export. With [`TextArea::with_generator`],
+/// Ctrl+G replaces the text with a random value.
+pub struct TextArea {
+    label: String,
+    lines: Vec<String>,
+    /// Cursor position as line index and char offset.
+    row: usize,
+    col: usize,
+    scroll: usize,
+    hscroll: usize,
+    /// Path prompt shown while loading the content from a file.
+    file_input: Option<TextInput>,
+    /// Whether Ctrl+G opens the value generator.
+    generates: bool,
+    generator: Option<ValueGenerator>,
+    error: Option<String>,
+}
+
+impl TextArea {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            lines: vec![String::new()],
+            row: 0,
+            col: 0,
+            scroll: 0,
+            hscroll: 0,
+            file_input: None,
+            generates: false,
+            generator: None,
+            error: None,
+        }
+    }
+
+    /// Offer generating a random value, for secret payloads.
+    #[must_use]
+    pub const fn with_generator(mut self) -> Self {
+        self.generates = true;
+        self
+    }
+
+    pub fn value(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    pub fn set_value(&mut self, value: &str) {
+        self.lines = vec![String::new()];
+        self.row = 0;
+        self.col = 0;
+        self.insert_str(value);
+    }
+
+    /// Show `error` in the status line until the next edit, e.g. when a
+    /// submitted value is rejected.
+    pub fn set_error(&mut self, error: impl Into<String>) {
+        self.error = Some(error.into());
+    }
+
+    /// Insert text at the cursor, e.g. from a paste.
+    pub fn insert_str(&mut self, text: &str) {
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        for (i, part) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.insert_newline();
+            }
+            for c in part.chars() {
+                self.insert_char(c);
+            }
+        }
+    }
+
+    fn line_len(&self, row: usize) -> usize {
+        self.lines[row].chars().count()
+    }
+
+    /// Byte index of the cursor in the current line.
+    fn cursor_byte(&self) -> usize {
+        let line = &self.lines[self.row];
+        line.char_indices()
+            .nth(self.col)
+            .map_or(line.len(), |(i, _)| i)
+    }
+
+    fn insert_char(&mut self, c: char) {
+        let at = self.cursor_byte();
+        self.lines[self.row].insert(at, c);
+        self.col += 1;
+    }
+
+    fn insert_newline(&mut self) {
+        let at = self.cursor_byte();
+        let rest = self.lines[self.row].split_off(at);
+        self.row += 1;
+        self.col = 0;
+        self.lines.insert(self.row, rest);
+    }
+
+    fn delete_before_cursor(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+            let at = self.cursor_byte();
+            self.lines[self.row].remove(at);
+        } else if self.row > 0 {
+            let line = self.lines.remove(self.row);
+            self.row -= 1;
+            self.col = self.line_len(self.row);
+            self.lines[self.row].push_str(&line);
+        }
+    }
+
+    fn delete_at_cursor(&mut self) {
+        if self.col < self.line_len(self.row) {
+            let at = self.cursor_byte();
+            self.lines[self.row].remove(at);
+        } else if self.row + 1 < self.lines.len() {
+            let next = self.lines.remove(self.row + 1);
+            self.lines[self.row].push_str(&next);
+        }
+    }
+
+    fn move_left(&mut self) {
+        if self.col > 0 {
+            self.col -= 1;
+        } else if self.row > 0 {
+            self.row -= 1;
+            self.col = self.line_len(self.row);
+        }
+    }
+
+    fn move_right(&mut self) {
+        if self.col < self.line_len(self.row) {
+            self.col += 1;
+        } else if self.row + 1 < self.lines.len() {
+            self.row += 1;
+            self.col = 0;
+        }
+    }
+
+    fn move_to_row(&mut self, row: usize) {
+        self.row = row.min(self.lines.len() - 1);
+        self.col = self.col.min(self.line_len(self.row));
+    }
+
+    fn load_file(&mut self, path: &str) {
+        match read_file(path) {
+            Ok(bytes) => match String::from_utf8(bytes) {
+                Ok(content) => {
+                    self.set_value(&content);
+                    self.file_input = None;
+                    self.error = None;
+                }
+                Err(_) => self.error = Some(format!("{path} is not valid UTF-8")),
+            },
+            Err(err) => self.error = Some(format!("Cannot read {path}: {err}")),
+        }
+    }
+
+    fn handle_file_key(&mut self, key: KeyEvent) -> Result<EventResult<TextAreaEvent>> {
+        let Some(input) = &mut self.file_input else {
+            return Ok(EventResult::Ignored);
+        };
+        match input.handle_key(key)? {
+            EventResult::Event(TextInputEvent::Submitted(path)) if !path.trim().is_empty() => {
+                self.load_file(path.trim());
+            }
+            EventResult::Event(TextInputEvent::Cancelled) => {
+                self.file_input = None;
+                self.error = None;
+            }
+            _ => {}
+        }
+        Ok(EventResult::Consumed)
+    }
+
+    fn handle_generator_key(&mut self, key: KeyEvent) -> Result<EventResult<TextAreaEvent>> {
+        let Some(generator) = &mut self.generator else {
+            return Ok(EventResult::Ignored);
+        };
+        match generator.handle_key(key)? {
+            EventResult::Event(GeneratorEvent::Generated(value)) => {
+                self.set_value(&value);
+                self.generator = None;
+            }
+            EventResult::Event(GeneratorEvent::Cancelled) => self.generator = None,
+            _ => {}
+        }
+        Ok(EventResult::Consumed)
+    }
+
+    /// Keep the cursor inside a viewport of `width` x `height`.
+    const fn scroll_to_cursor(&mut self, width: usize, height: usize) {
+        if self.row < self.scroll {
+            self.scroll = self.row;
+        } else if self.row >= self.scroll + height {
+            self.scroll = self.row + 1 - height;
+        }
+        if self.col < self.hscroll {
+            self.hscroll = self.col;
+        } else if self.col >= self.hscroll + width {
+            self.hscroll = self.col + 1 - width;
+        }
+    }
+
+    fn render_line(&self, row: usize, width: usize, theme: &Theme) -> Line<'static> {
+        let text_style = Style::default().fg(theme.text());
+        let visible: Vec<char> = self.lines[row]
+            .chars()
+            .skip(self.hscroll)
+            .take(width)
+            .collect();
+        if row != self.row {
+            return Line::styled(visible.into_iter().collect::<String>(), text_style);
+        }
+
+        let cursor_style = Style::default()
+            .fg(theme.base())
+            .bg(theme.text())
+            .add_modifier(Modifier::BOLD);
+        let at = self.col - self.hscroll;
+        let before: String = visible.iter().take(at).collect();
+        let cursor = visible.get(at).copied().unwrap_or(' ');
+        let after: String = visible.iter().skip(at + 1).collect();
+        Line::from(vec![
+            Span::styled(before, text_style),
+            Span::styled(cursor.to_string(), cursor_style),
+            Span::styled(after, text_style),
+        ])
+    }
+}
+
+impl Component for TextArea {
+    type Output = TextAreaEvent;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if self.file_input.is_some() {
+            return self.handle_file_key(key);
+        }
+        if self.generator.is_some() {
+            return self.handle_generator_key(key);
+        }
+        self.error = None;
+
+        let page = 10;
+        match (key.code, key.modifiers) {
+            (KeyCode::Char('s'), KeyModifiers::CONTROL) => {
+                return Ok(TextAreaEvent::Submitted(self.value()).into());
+            }
+            (KeyCode::Esc, _) => return Ok(TextAreaEvent::Cancelled.into()),
+            (KeyCode::Char('o'), KeyModifiers::CONTROL) => {
+                self.file_input = Some(TextInput::new(t("Load From File")));
+            }
+            (KeyCode::Char('g'), KeyModifiers::CONTROL) if self.generates => {
+                self.generator = Some(ValueGenerator::new());
+            }
+
+            (KeyCode::Enter, _) => self.insert_newline(),
+            (KeyCode::Backspace, _) => self.delete_before_cursor(),
+            (KeyCode::Delete, _) => self.delete_at_cursor(),
+
+            (KeyCode::Left, _) => self.move_left(),
+            (KeyCode::Right, _) => self.move_right(),
+            (KeyCode::Up, _) => self.move_to_row(self.row.saturating_sub(1)),
+            (KeyCode::Down, _) => self.move_to_row(self.row + 1),
+            (KeyCode::PageUp, _) => self.move_to_row(self.row.saturating_sub(page)),
+            (KeyCode::PageDown, _) => self.move_to_row(self.row + page),
+            (KeyCode::Home, KeyModifiers::CONTROL) => {
+                self.row = 0;
+                self.col = 0;
+            }
+            (KeyCode::End, KeyModifiers::CONTROL) => {
+                self.row = self.lines.len() - 1;
+                self.col = self.line_len(self.row);
+            }
+            (KeyCode::Home, _) | (KeyCode::Char('a'), KeyModifiers::CONTROL) => self.col = 0,
+            (KeyCode::End, _) | (KeyCode::Char('e'), KeyModifiers::CONTROL) => {
+                self.col = self.line_len(self.row);
+            }
+
+            (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => self.insert_char(c),
+
+            _ => {} // Consume all keys to prevent propagation
+        }
+        Ok(EventResult::Consumed)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        if let Some(input) = &mut self.file_input {
+            return input.handle_paste(text).map(|_| EventResult::Consumed);
+        }
+        self.error = None;
+        self.insert_str(text);
+        Ok(EventResult::Consumed)
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = area.centered(Constraint::Percentage(70), Constraint::Percentage(60));
+        frame.render_widget(Clear, popup_area);
+
+        let dot = theme.symbols.middle_dot;
+        let generate = if self.generates {
+            format!(" {dot} Ctrl+G generate")
+        } else {
+            String::new()
+        };
+        let status = match &self.error {
+            Some(error) => Line::styled(format!(" {error} "), Style::default().fg(theme.red())),
+            None => Line::styled(
+                format!(
+                    " Ln {}/{}, Col {} {dot} Ctrl+S save {dot} Ctrl+O load file{generate} {dot} Esc cancel ",
+                    self.row + 1,
+                    self.lines.len(),
+                    self.col + 1,
+                ),
+                Style::default().fg(theme.overlay1()),
+            ),
+        };
+        let block = Block::default()
+            .title(format!(" {} ", self.label))
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .title_bottom(status)
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.lavender()))
+            .style(Style::default().bg(theme.base()));
+
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let width = usize::from(inner.width).max(1);
+        let height = usize::from(inner.height).max(1);
+        self.scroll_to_cursor(width, height);
+        let lines: Vec<Line> = (self.scroll..self.lines.len().min(self.scroll + height))
+            .map(|row| self.render_line(row, width, theme))
+            .collect();
+        frame.render_widget(Paragraph::new(lines), inner);
+
+        if let Some(input) = &mut self.file_input {
+            input.render(frame, area, theme);
+        }
+        if let Some(generator) = &mut self.generator {
+            generator.render(frame, area, theme);
+        }
+    }
+}
+
+/// The contents of the file at `path`, decrypted as its extension says.
+fn read_file(path: &str) -> color_eyre::Result<Vec<u8>> {
+    let data = fs::read(path)?;
+    let path = Path::new(path);
+    if Scheme::of(path).is_none() {
+        return Ok(data);
+    }
+    // age and gpg run as programs; the editor waits for them like for the read
+    tokio::task::block_in_place(|| Handle::current().block_on(encryption::decrypt_from(path, data)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(area: &mut TextArea, code: KeyCode) {
+        area.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_edit_across_lines() {
+        let mut area = TextArea::new("Payload");
+        area.insert_str("{\r\n  \"kéy\": 1\n}");
+        assert_eq!(area.value(), "{\n  \"kéy\": 1\n}");
+
+        // Joining lines with backspace at the start of a line
+        press(&mut area, KeyCode::Up);
+        press(&mut area, KeyCode::Home);
+        press(&mut area, KeyCode::Backspace);
+        assert_eq!(area.value(), "{  \"kéy\": 1\n}");
+
+        // Multi-byte characters are edited as a whole
+        for _ in 0..4 {
+            press(&mut area, KeyCode::Right);
+        }
+        press(&mut area, KeyCode::Delete);
+        press(&mut area, KeyCode::Enter);
+        assert_eq!(area.value(), "{  \"k\ny\": 1\n}");
+    }
+
+    fn load(area: &mut TextArea, name: &str, content: &str) {
+        let path = std::env::temp_dir().join(format!("lazycloud-{}-{name}", std::process::id()));
+        fs::write(&path, content).unwrap();
+        area.load_file(&path.to_string_lossy());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_load_file_decrypts_exports() {
+        let mut area = TextArea::new("Payload");
+        load(&mut area, "payload.txt", "hunter2\n");
+        assert_eq!(area.value(), "hunter2\n");
+        assert_eq!(area.error, None);
+
+        // Without an identity the export is not read as plain text
+        load(&mut area, "payload.age", "age-encryption.org/v1");
+        assert_eq!(area.value(), "hunter2\n");
+        assert!(area.error.as_deref().unwrap().contains("age_identity"));
+    }
+}
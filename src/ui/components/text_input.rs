@@ -3,7 +3,7 @@ use ratatui::Frame;
 use ratatui::layout::{Constraint, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
 use crate::Theme;
 use crate::ui::{Component, EventResult, Result};
@@ -13,12 +13,17 @@ pub enum TextInputEvent {
     Cancelled,
 }
 
+/// Checks the current value, returning the message to show when invalid.
+type Validator = Box<dyn Fn(&str) -> std::result::Result<(), String>>;
+
 pub struct TextInput {
     label: String,
     value: String,
     cursor: usize,
     placeholder: Option<String>,
     masked: bool,
+    validator: Option<Validator>,
+    error: Option<String>,
 }
 
 impl TextInput {
@@ -29,6 +34,8 @@ impl TextInput {
             cursor: 0,
             placeholder: None,
             masked: false,
+            validator: None,
+            error: None,
         }
     }
 
@@ -50,10 +57,32 @@ impl TextInput {
         self
     }
 
+    /// Validate the value while typing. Errors are shown in the input and
+    /// Enter does nothing until the value is valid.
+    pub fn with_validator(
+        mut self,
+        validator: impl Fn(&str) -> std::result::Result<(), String> + 'static,
+    ) -> Self {
+        self.validator = Some(Box::new(validator));
+        self
+    }
+
+    fn validate(&mut self) {
+        self.error = self
+            .validator
+            .as_ref()
+            .and_then(|validate| validate(&self.value).err());
+    }
+
     pub fn value(&self) -> &str {
         &self.value
     }
 
+    /// Where the input is drawn within `area`.
+    pub(super) fn popup_area(area: Rect) -> Rect {
+        area.centered(Constraint::Percentage(50), Constraint::Length(5))
+    }
+
     fn insert_char(&mut self, c: char) {
         self.value.insert(self.cursor, c);
         self.cursor += 1;
@@ -110,15 +139,29 @@ impl TextInput {
         self.value.clear();
         self.cursor = 0;
     }
+
+    /// Insert pasted text at the cursor. Line breaks are dropped since the
+    /// input holds a single line.
+    fn insert_str(&mut self, text: &str) {
+        let text: String = text.chars().filter(|c| !matches!(c, '\r' | '\n')).collect();
+        self.value.insert_str(self.cursor, &text);
+        self.cursor += text.len();
+    }
 }
 
 impl Component for TextInput {
     type Output = TextInputEvent;
 
     fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
-        Ok(match (key.code, key.modifiers) {
+        let result = match (key.code, key.modifiers) {
             // Submit
-            (KeyCode::Enter, _) => TextInputEvent::Submitted(self.value.clone()).into(),
+            (KeyCode::Enter, _) => {
+                self.validate();
+                if self.error.is_some() {
+                    return Ok(EventResult::Consumed);
+                }
+                TextInputEvent::Submitted(self.value.clone()).into()
+            }
 
             // Cancel
             (KeyCode::Esc, _) => TextInputEvent::Cancelled.into(),
@@ -168,12 +211,22 @@ impl Component for TextInput {
             }
 
             _ => EventResult::Consumed, // Consume all keys to prevent propagation
-        })
+        };
+        if matches!(result, EventResult::Consumed) {
+            self.validate();
+        }
+        Ok(result)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        self.insert_str(text);
+        self.validate();
+        Ok(EventResult::Consumed)
     }
 
     fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
         // Calculate centered popup area - smaller for single input
-        let popup_area = area.centered(Constraint::Percentage(50), Constraint::Length(5));
+        let popup_area = Self::popup_area(area);
 
         // Clear the area behind the popup
         frame.render_widget(Clear, popup_area);
@@ -202,14 +255,13 @@ impl Component for TextInput {
             .add_modifier(Modifier::BOLD);
         let placeholder_style = Style::default().fg(theme.overlay0());
 
-        let line = if self.value.is_empty() && self.placeholder.is_some() {
+        let line = if self.value.is_empty()
+            && let Some(placeholder) = &self.placeholder
+        {
             // Show placeholder with cursor at start
             Line::from(vec![
                 Span::styled(" ", cursor_style),
-                Span::styled(
-                    self.placeholder.as_ref().unwrap().clone(),
-                    placeholder_style,
-                ),
+                Span::styled(placeholder.clone(), placeholder_style),
             ])
         } else {
             Line::from(vec![
@@ -220,7 +272,12 @@ impl Component for TextInput {
         };
 
         let title = format!(" {} (Enter to confirm, Esc to cancel) ", self.label);
-        let block = Block::default()
+        let border_color = if self.error.is_some() {
+            theme.red()
+        } else {
+            theme.lavender()
+        };
+        let mut block = Block::default()
             .title(title)
             .title_style(
                 Style::default()
@@ -228,9 +285,15 @@ impl Component for TextInput {
                     .add_modifier(Modifier::BOLD),
             )
             .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(theme.lavender()))
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(border_color))
             .style(Style::default().bg(theme.base()));
+        if let Some(error) = &self.error {
+            block = block.title_bottom(Line::styled(
+                format!(" {error} "),
+                Style::default().fg(theme.red()),
+            ));
+        }
 
         let paragraph = Paragraph::new(line).block(block);
 
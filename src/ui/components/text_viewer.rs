@@ -0,0 +1,640 @@
+use std::str::FromStr;
+use std::sync::{Arc, LazyLock};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::prelude::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{
+    Color as SyntectColor,
+    FontStyle,
+    ScopeSelectors,
+    StyleModifier,
+    Theme as SyntectTheme,
+    ThemeItem,
+    ThemeSettings,
+};
+use syntect::parsing::SyntaxSet;
+
+use super::motion::{MotionInput, MotionStep};
+use crate::Theme;
+use crate::config::{KeyResolver, NavAction, SearchAction, ViewerAction};
+use crate::i18n::t_args;
+use crate::ui::{Component, EventResult, Keybinding, Result};
+
+/// Lines moved by page up/down.
+const PAGE_STEP: usize = 10;
+
+static SYNTAXES: LazyLock<SyntaxSet> = LazyLock::new(SyntaxSet::load_defaults_nonewlines);
+
+/// syntect theme whose colors are [`Slot`] indices, mapped to the app theme
+/// when a line is drawn so every theme colors the text alike.
+static SLOT_THEME: LazyLock<SyntectTheme> = LazyLock::new(slot_theme);
+
+/// Theme color of a highlighted token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Slot {
+    Text,
+    Comment,
+    Keyword,
+    String,
+    Number,
+    Key,
+    Inserted,
+    Deleted,
+    Header,
+    Range,
+    /// Unchanged lines of a diff.
+    Context,
+    Invalid,
+}
+
+impl Slot {
+    const ALL: [Self; 12] = [
+        Self::Text,
+        Self::Comment,
+        Self::Keyword,
+        Self::String,
+        Self::Number,
+        Self::Key,
+        Self::Inserted,
+        Self::Deleted,
+        Self::Header,
+        Self::Range,
+        Self::Context,
+        Self::Invalid,
+    ];
+
+    /// Scopes colored with this slot.
+    const fn scopes(self) -> &'static str {
+        match self {
+            Self::Text => "",
+            Self::Comment => "comment",
+            Self::Keyword => "keyword, storage",
+            Self::String => "string",
+            Self::Number => "constant",
+            Self::Key => {
+                "meta.structure.dictionary.key string, meta.mapping.key string, entity.name, \
+                 support.type.property-name"
+            }
+            Self::Inserted => "markup.inserted",
+            Self::Deleted => "markup.deleted",
+            Self::Header => "meta.diff.header",
+            Self::Range => "meta.diff.range",
+            Self::Context => "source.diff",
+            Self::Invalid => "invalid",
+        }
+    }
+
+    fn style(self, theme: &Theme) -> Style {
+        let color = match self {
+            Self::Text | Self::Header => theme.text(),
+            Self::Comment | Self::Context => theme.overlay1(),
+            Self::Keyword => theme.mauve(),
+            Self::String | Self::Inserted => theme.green(),
+            Self::Number => theme.peach(),
+            Self::Key | Self::Range => theme.blue(),
+            Self::Deleted | Self::Invalid => theme.red(),
+        };
+        let style = Style::default().fg(color);
+        if self == Self::Header {
+            style.add_modifier(Modifier::BOLD)
+        } else {
+            style
+        }
+    }
+
+    const fn color(self) -> SyntectColor {
+        SyntectColor {
+            r: self as u8,
+            g: 0,
+            b: 0,
+            a: 0xFF,
+        }
+    }
+
+    fn from_color(color: SyntectColor) -> Self {
+        Self::ALL
+            .get(usize::from(color.r))
+            .copied()
+            .unwrap_or(Self::Text)
+    }
+}
+
+fn slot_theme() -> SyntectTheme {
+    let scopes = Slot::ALL
+        .into_iter()
+        .filter(|slot| !slot.scopes().is_empty())
+        .filter_map(|slot| {
+            Some(ThemeItem {
+                scope: ScopeSelectors::from_str(slot.scopes()).ok()?,
+                style: StyleModifier {
+                    foreground: Some(slot.color()),
+                    background: None,
+                    font_style: Some(FontStyle::empty()),
+                },
+            })
+        })
+        .collect();
+    SyntectTheme {
+        settings: ThemeSettings {
+            foreground: Some(Slot::Text.color()),
+            ..ThemeSettings::default()
+        },
+        scopes,
+        ..SyntectTheme::default()
+    }
+}
+
+/// Syntax of `text` when it can be told from the content, e.g. `json`.
+pub fn detect_syntax(text: &str) -> Option<&'static str> {
+    let trimmed = text.trim_start();
+    let json = (trimmed.starts_with('{') || trimmed.starts_with('['))
+        && serde_json::from_str::<serde_json::Value>(text).is_ok();
+    json.then_some("json")
+}
+
+/// Read-only view of text with scrolling, wrapping, line numbers, search and
+/// syntax highlighting, e.g. for payloads, logs and diffs.
+pub struct TextViewer {
+    lines: Vec<String>,
+    title: String,
+    /// Token of the syntax such as `json` or `diff`, plain text if `None`.
+    syntax: Option<String>,
+    /// Highlighted lines, rebuilt when the text or theme changes.
+    highlighted: Option<(Theme, Vec<Line<'static>>)>,
+    /// First visible line.
+    scroll: usize,
+    wrap: bool,
+    line_numbers: bool,
+    query: String,
+    searching: bool,
+    /// Lines that fit in the viewport at the last render.
+    height: usize,
+    motion: MotionInput,
+    resolver: Arc<KeyResolver>,
+}
+
+impl TextViewer {
+    pub fn new(text: &str, resolver: Arc<KeyResolver>) -> Self {
+        Self {
+            lines: text.lines().map(ToString::to_string).collect(),
+            title: String::new(),
+            syntax: None,
+            highlighted: None,
+            scroll: 0,
+            wrap: false,
+            line_numbers: false,
+            query: String::new(),
+            searching: false,
+            height: PAGE_STEP,
+            motion: MotionInput::default(),
+            resolver,
+        }
+    }
+
+    /// Highlight the text as `syntax`, a name or file extension known to
+    /// syntect such as `json`, `yaml` or `diff`.
+    #[must_use]
+    pub fn with_syntax(mut self, syntax: impl Into<String>) -> Self {
+        self.syntax = Some(syntax.into());
+        self.highlighted = None;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.title = title.into();
+    }
+
+    pub fn keybindings(&self) -> Vec<Keybinding> {
+        let r = &self.resolver;
+        let mut bindings = vec![
+            Keybinding::hint(r.display_search(SearchAction::Toggle), "Search"),
+            Keybinding::new(r.display_viewer(ViewerAction::Wrap), "Wrap lines"),
+            Keybinding::new(r.display_viewer(ViewerAction::LineNumbers), "Line numbers"),
+        ];
+        if !self.query.is_empty() {
+            bindings.push(Keybinding::new(
+                r.display_viewer(ViewerAction::NextMatch),
+                "Next match",
+            ));
+            bindings.push(Keybinding::new(
+                r.display_viewer(ViewerAction::PrevMatch),
+                "Previous match",
+            ));
+        }
+        bindings
+    }
+
+    const fn max_scroll(&self) -> usize {
+        // Wrapped lines take more rows, so let the last line reach the top
+        if self.wrap {
+            self.lines.len().saturating_sub(1)
+        } else {
+            self.lines.len().saturating_sub(self.height)
+        }
+    }
+
+    fn is_match(&self, line: &str) -> bool {
+        !match_ranges(line, &self.query).is_empty()
+    }
+
+    fn match_count(&self) -> usize {
+        self.lines.iter().filter(|line| self.is_match(line)).count()
+    }
+
+    /// Scroll to the next matching line after the top one, or the previous
+    /// one before it. Stays put without a match.
+    fn jump_to_match(&mut self, forward: bool, include_current: bool) {
+        if self.query.is_empty() {
+            return;
+        }
+        let found = if forward {
+            let start = if include_current {
+                self.scroll
+            } else {
+                self.scroll + 1
+            };
+            (start..self.lines.len()).find(|&i| self.is_match(&self.lines[i]))
+        } else {
+            (0..self.scroll)
+                .rev()
+                .find(|&i| self.is_match(&self.lines[i]))
+        };
+        if let Some(line) = found {
+            self.scroll = line.min(self.max_scroll());
+        }
+    }
+
+    fn handle_search_key(&mut self, key: KeyEvent) -> EventResult<()> {
+        if self.resolver.matches_search(&key, SearchAction::Exit) {
+            self.searching = false;
+            self.query.clear();
+            return EventResult::Consumed;
+        }
+        if self.resolver.matches_nav(&key, NavAction::Select) {
+            self.searching = false;
+            return EventResult::Consumed;
+        }
+        match key.code {
+            KeyCode::Backspace => {
+                self.query.pop();
+            }
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.jump_to_match(true, true);
+            }
+            _ => {}
+        }
+        EventResult::Consumed
+    }
+
+    fn highlighted(&mut self, theme: &Theme) -> &[Line<'static>] {
+        if self
+            .highlighted
+            .as_ref()
+            .is_none_or(|(cached, _)| cached != theme)
+        {
+            let lines = highlight(&self.lines, self.syntax.as_deref(), theme);
+            self.highlighted = Some((*theme, lines));
+        }
+        self.highlighted
+            .as_ref()
+            .map_or(&[], |(_, lines)| lines.as_slice())
+    }
+}
+
+/// Style every line, as plain text if the syntax is unknown.
+fn highlight(lines: &[String], syntax: Option<&str>, theme: &Theme) -> Vec<Line<'static>> {
+    let plain = Style::default().fg(theme.text());
+    let Some(syntax) = syntax.and_then(|token| SYNTAXES.find_syntax_by_token(token)) else {
+        return lines
+            .iter()
+            .map(|line| Line::styled(line.clone(), plain))
+            .collect();
+    };
+    let mut highlighter = HighlightLines::new(syntax, &SLOT_THEME);
+    lines
+        .iter()
+        .map(|line| {
+            highlighter.highlight_line(line, &SYNTAXES).map_or_else(
+                |_| Line::styled(line.clone(), plain),
+                |ranges| {
+                    Line::from(
+                        ranges
+                            .into_iter()
+                            .map(|(style, text)| {
+                                Span::styled(
+                                    text.to_string(),
+                                    Slot::from_color(style.foreground).style(theme),
+                                )
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                },
+            )
+        })
+        .collect()
+}
+
+/// `line` with every case-insensitive occurrence of `query` marked.
+/// Byte ranges of `text` matching `query`, ignoring case.
+///
+/// Characters are compared one by one instead of lowercasing the whole text,
+/// whose length may change, so the ranges stay valid offsets into `text`.
+fn match_ranges(text: &str, query: &str) -> Vec<(usize, usize)> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let mut ranges = Vec::new();
+    let mut next = 0;
+    for (start, _) in text.char_indices() {
+        if start < next {
+            continue;
+        }
+        if let Some(len) = match_len(&text[start..], query) {
+            ranges.push((start, start + len));
+            next = start + len;
+        }
+    }
+    ranges
+}
+
+/// Length in bytes of the match of `query` at the start of `text`.
+fn match_len(text: &str, query: &str) -> Option<usize> {
+    let mut chars = text.char_indices();
+    for expected in query.chars() {
+        let (_, found) = chars.next()?;
+        if !found.to_lowercase().eq(expected.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(chars.next().map_or(text.len(), |(end, _)| end))
+}
+
+fn mark_matches(line: &Line<'static>, query: &str, style: Style) -> Line<'static> {
+    let text: String = line
+        .spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect();
+    let ranges = match_ranges(&text, query);
+    if ranges.is_empty() {
+        return line.clone();
+    }
+
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    for span in &line.spans {
+        let content = span.content.as_ref();
+        let end = offset + content.len();
+        let mut cut = offset;
+        for &(start, stop) in &ranges {
+            let (start, stop) = (start.max(offset), stop.min(end));
+            if start >= stop {
+                continue;
+            }
+            if start > cut {
+                spans.push(Span::styled(text[cut..start].to_string(), span.style));
+            }
+            spans.push(Span::styled(
+                text[start..stop].to_string(),
+                span.style.patch(style),
+            ));
+            cut = stop;
+        }
+        if cut < end {
+            spans.push(Span::styled(text[cut..end].to_string(), span.style));
+        }
+        offset = end;
+    }
+    Line::from(spans)
+}
+
+impl Component for TextViewer {
+    type Output = ();
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if self.searching {
+            return Ok(self.handle_search_key(key));
+        }
+
+        match self.motion.handle_key(&key, &self.resolver) {
+            MotionStep::Count => return Ok(EventResult::Consumed),
+            MotionStep::Move(motion, count) => {
+                let len = self.max_scroll() + 1;
+                if let Some(target) =
+                    motion.apply(count, Some(self.scroll), len, PAGE_STEP, self.height)
+                {
+                    self.scroll = target;
+                }
+                return Ok(EventResult::Consumed);
+            }
+            MotionStep::Other => {}
+        }
+
+        let r = &self.resolver;
+        if r.matches_search(&key, SearchAction::Toggle) {
+            self.searching = true;
+            self.query.clear();
+        } else if r.matches_search(&key, SearchAction::Exit) && !self.query.is_empty() {
+            self.query.clear();
+        } else if r.matches_viewer(&key, ViewerAction::Wrap) {
+            self.wrap = !self.wrap;
+            self.scroll = self.scroll.min(self.max_scroll());
+        } else if r.matches_viewer(&key, ViewerAction::LineNumbers) {
+            self.line_numbers = !self.line_numbers;
+        } else if r.matches_viewer(&key, ViewerAction::NextMatch) && !self.query.is_empty() {
+            self.jump_to_match(true, false);
+        } else if r.matches_viewer(&key, ViewerAction::PrevMatch) && !self.query.is_empty() {
+            self.jump_to_match(false, false);
+        } else {
+            return Ok(EventResult::Ignored);
+        }
+        Ok(EventResult::Consumed)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        if !self.searching {
+            return Ok(EventResult::Ignored);
+        }
+        self.query.push_str(text.lines().next().unwrap_or_default());
+        self.jump_to_match(true, true);
+        Ok(EventResult::Consumed)
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.border()))
+            .title(self.title.clone())
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            );
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let show_search = self.searching || !self.query.is_empty();
+        let [text_area, search_area] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(u16::from(show_search)),
+        ])
+        .areas(inner);
+        self.height = usize::from(text_area.height).max(1);
+        self.scroll = self.scroll.min(self.max_scroll());
+
+        let scroll = self.scroll;
+        let query = self.query.clone();
+        let number_width = self.lines.len().to_string().len();
+        let line_numbers = self.line_numbers;
+        let mark = Style::default()
+            .fg(theme.base())
+            .bg(theme.yellow())
+            .add_modifier(Modifier::BOLD);
+        let number_style = Style::default().fg(theme.overlay0());
+        let lines: Vec<Line> = self
+            .highlighted(theme)
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(usize::from(text_area.height))
+            .map(|(i, line)| {
+                let mut line = if query.is_empty() {
+                    line.clone()
+                } else {
+                    mark_matches(line, &query, mark)
+                };
+                if line_numbers {
+                    line.spans.insert(
+                        0,
+                        Span::styled(format!("{:>number_width$} ", i + 1), number_style),
+                    );
+                }
+                line
+            })
+            .collect();
+        let mut paragraph = Paragraph::new(lines);
+        if self.wrap {
+            paragraph = paragraph.wrap(Wrap { trim: false });
+        }
+        frame.render_widget(paragraph, text_area);
+
+        if show_search {
+            let (text, color) = if self.searching {
+                (format!("/{}_", self.query), theme.yellow())
+            } else {
+                let matches = self.match_count().to_string();
+                (
+                    format!(
+                        "/{} ({})",
+                        self.query,
+                        t_args("{count} matches", &[("count", &matches)])
+                    ),
+                    theme.subtext0(),
+                )
+            };
+            frame.render_widget(
+                Paragraph::new(text).style(Style::default().fg(color)),
+                search_area,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+    use ratatui::style::Color;
+
+    use super::*;
+    use crate::config::keybindings::KeybindingsConfig;
+
+    fn press(viewer: &mut TextViewer, code: KeyCode) {
+        viewer
+            .handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_search_jumps_between_matches() {
+        let resolver = Arc::new(KeyResolver::new(Arc::new(KeybindingsConfig::default())));
+        let text = (0..30)
+            .map(|i| {
+                if i % 10 == 5 {
+                    format!("TOKEN_{i}")
+                } else {
+                    format!("line {i}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut viewer = TextViewer::new(&text, resolver);
+        viewer.height = 5;
+
+        press(&mut viewer, KeyCode::Char('/'));
+        for c in "token".chars() {
+            press(&mut viewer, KeyCode::Char(c));
+        }
+        press(&mut viewer, KeyCode::Enter);
+        assert_eq!(viewer.scroll, 5);
+        assert_eq!(viewer.match_count(), 3);
+
+        press(&mut viewer, KeyCode::Char('n'));
+        assert_eq!(viewer.scroll, 15);
+        // The last match is below the last full page
+        press(&mut viewer, KeyCode::Char('n'));
+        assert_eq!(viewer.scroll, 25);
+        press(&mut viewer, KeyCode::Char('p'));
+        assert_eq!(viewer.scroll, 15);
+    }
+
+    #[test]
+    fn test_mark_matches_splits_spans() {
+        let line = Line::from(vec![Span::raw("\"api_"), Span::raw("key\": 1")]);
+        let marked = mark_matches(&line, "API_K", Style::default().bg(Color::Yellow));
+        let parts: Vec<&str> = marked
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(parts, ["\"", "api_", "k", "ey\": 1"]);
+        assert_eq!(marked.spans[2].style.bg, Some(Color::Yellow));
+
+        // Matched lines are also marked outside of ASCII
+        let line = Line::from("Größe: ÄPFEL");
+        let marked = mark_matches(&line, "äpfel", Style::default());
+        assert_eq!(marked.spans.last().unwrap().content, "ÄPFEL");
+    }
+
+    #[test]
+    fn test_json_keys_and_strings_differ() {
+        let theme = Theme::catppuccin_mocha();
+        let lines = highlight(
+            &[r#"{"key": "value", "n": 1}"#.to_string()],
+            Some("json"),
+            &theme,
+        );
+        let color_of = |needle: &str| {
+            lines[0]
+                .spans
+                .iter()
+                .find(|span| span.content.contains(needle))
+                .and_then(|span| span.style.fg)
+        };
+        assert_eq!(color_of("value"), Some(theme.green()));
+        assert_eq!(color_of("key"), Some(theme.blue()));
+        assert_eq!(color_of("1"), Some(theme.peach()));
+    }
+}
@@ -0,0 +1,402 @@
+use std::sync::Arc;
+
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::prelude::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List as RatatuiList, ListItem, ListState};
+
+use super::motion::{MotionInput, MotionStep};
+use crate::Theme;
+use crate::config::{GlobalAction, KeyResolver, NavAction};
+use crate::i18n::t;
+use crate::ui::{Component, EventResult, Result};
+
+/// Rows moved by page up/down.
+const PAGE_STEP: usize = 5;
+
+pub enum TreeEvent<T> {
+    /// The children of a branch are needed, see [`Tree::set_children`].
+    Load(T),
+    /// A leaf was chosen.
+    Activated(T),
+}
+
+pub trait TreeItem {
+    /// Identifies the node, unique within the tree.
+    fn key(&self) -> &str;
+
+    /// Whether the node has children, loaded when it is first expanded.
+    fn is_branch(&self) -> bool;
+
+    /// Name of the node, also used in the path.
+    fn label(&self) -> String;
+
+    /// Extra spans after the label, e.g. a type or ID.
+    fn render_extra(&self, theme: &Theme) -> Vec<Span<'static>> {
+        _ = theme;
+        Vec::new()
+    }
+}
+
+struct Node<T> {
+    item: T,
+    expanded: bool,
+    /// `None` until the children were loaded.
+    children: Option<Vec<Self>>,
+}
+
+impl<T> Node<T> {
+    const fn new(item: T) -> Self {
+        Self {
+            item,
+            expanded: false,
+            children: None,
+        }
+    }
+}
+
+/// A visible node, found by its indices from the top level down.
+struct Row {
+    indices: Vec<usize>,
+}
+
+impl Row {
+    const fn depth(&self) -> usize {
+        self.indices.len() - 1
+    }
+}
+
+/// Expandable tree of nodes whose children load lazily, e.g. folders or
+/// path prefixes. Navigates like [`super::Table`].
+pub struct Tree<T: TreeItem + Clone> {
+    /// `None` while the top level is loading.
+    roots: Option<Vec<Node<T>>>,
+    /// Key of the selected node.
+    selected: Option<String>,
+    title: String,
+    state: ListState,
+    /// Rows that fit in the viewport at the last render.
+    height: usize,
+    motion: MotionInput,
+    resolver: Arc<KeyResolver>,
+}
+
+impl<T: TreeItem + Clone> Tree<T> {
+    /// An empty tree whose top level is still loading.
+    pub fn new(resolver: Arc<KeyResolver>) -> Self {
+        Self {
+            roots: None,
+            selected: None,
+            title: String::new(),
+            state: ListState::default(),
+            height: PAGE_STEP,
+            motion: MotionInput::default(),
+            resolver,
+        }
+    }
+
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.title = title.into();
+    }
+
+    /// Fill in the children of `parent`, the top level for `None`.
+    ///
+    /// Children of a node that is no longer in the tree are dropped.
+    pub fn set_children(&mut self, parent: Option<&str>, items: Vec<T>) {
+        let nodes = items.into_iter().map(Node::new).collect();
+        match parent {
+            None => self.roots = Some(nodes),
+            Some(key) => {
+                if let Some(node) = self.roots.as_mut().and_then(|roots| find_mut(roots, key)) {
+                    node.children = Some(nodes);
+                }
+            }
+        }
+        if self.selected.is_none() {
+            self.selected = self.node_at(0).map(|node| node.item.key().to_string());
+        }
+    }
+
+    pub fn selected_item(&self) -> Option<&T> {
+        let index = self.selected_index()?;
+        self.node_at(index).map(|node| &node.item)
+    }
+
+    /// Labels from the top level down to the selected node.
+    pub fn path(&self) -> Vec<String> {
+        let Some(row) = self
+            .selected_index()
+            .and_then(|index| self.rows().into_iter().nth(index))
+        else {
+            return Vec::new();
+        };
+        let mut nodes = self.roots.as_deref().unwrap_or_default();
+        let mut path = Vec::new();
+        for &i in &row.indices {
+            let node = &nodes[i];
+            path.push(node.item.label());
+            nodes = node.children.as_deref().unwrap_or_default();
+        }
+        path
+    }
+
+    /// Visible rows: the top level and the children of expanded nodes.
+    fn rows(&self) -> Vec<Row> {
+        fn walk<T>(nodes: &[Node<T>], prefix: &[usize], rows: &mut Vec<Row>) {
+            for (i, node) in nodes.iter().enumerate() {
+                let mut indices = prefix.to_vec();
+                indices.push(i);
+                rows.push(Row {
+                    indices: indices.clone(),
+                });
+                if node.expanded
+                    && let Some(children) = &node.children
+                {
+                    walk(children, &indices, rows);
+                }
+            }
+        }
+        let mut rows = Vec::new();
+        walk(self.roots.as_deref().unwrap_or_default(), &[], &mut rows);
+        rows
+    }
+
+    fn node(&self, indices: &[usize]) -> Option<&Node<T>> {
+        let (&first, rest) = indices.split_first()?;
+        let mut node = self.roots.as_ref()?.get(first)?;
+        for &i in rest {
+            node = node.children.as_ref()?.get(i)?;
+        }
+        Some(node)
+    }
+
+    fn node_at(&self, index: usize) -> Option<&Node<T>> {
+        let row = self.rows().into_iter().nth(index)?;
+        self.node(&row.indices)
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        let key = self.selected.as_deref()?;
+        self.rows().iter().position(|row| {
+            self.node(&row.indices)
+                .is_some_and(|node| node.item.key() == key)
+        })
+    }
+
+    fn select_index(&mut self, index: usize) {
+        self.selected = self.node_at(index).map(|node| node.item.key().to_string());
+    }
+
+    /// Open or close the selected branch, asking for its children the
+    /// first time it opens.
+    fn toggle_selected(&mut self) -> EventResult<TreeEvent<T>> {
+        let Some(key) = self.selected.clone() else {
+            return EventResult::Ignored;
+        };
+        let Some(node) = self.roots.as_mut().and_then(|roots| find_mut(roots, &key)) else {
+            return EventResult::Ignored;
+        };
+        if !node.item.is_branch() {
+            return EventResult::Consumed;
+        }
+        node.expanded = !node.expanded;
+        if node.expanded && node.children.is_none() {
+            return TreeEvent::Load(node.item.clone()).into();
+        }
+        EventResult::Consumed
+    }
+
+    /// Close the parent of the selected node and select it instead.
+    fn select_parent(&mut self) -> bool {
+        let Some(row) = self
+            .selected_index()
+            .and_then(|index| self.rows().into_iter().nth(index))
+        else {
+            return false;
+        };
+        let Some((_, parent)) = row.indices.split_last() else {
+            return false;
+        };
+        let Some(key) = self.node(parent).map(|node| node.item.key().to_string()) else {
+            return false;
+        };
+        if let Some(node) = self.roots.as_mut().and_then(|roots| find_mut(roots, &key)) {
+            node.expanded = false;
+        }
+        self.selected = Some(key);
+        true
+    }
+
+    fn render_row(&self, row: &Row, theme: &Theme) -> ListItem<'static> {
+        let Some(node) = self.node(&row.indices) else {
+            return ListItem::new("");
+        };
+        let marker = if !node.item.is_branch() {
+            " "
+        } else if !node.expanded {
+            theme.symbols.collapsed
+        } else if node.children.is_none() {
+            theme.symbols.pending
+        } else {
+            theme.symbols.expanded
+        };
+        let mut spans = vec![
+            Span::raw("  ".repeat(row.depth())),
+            Span::styled(format!("{marker} "), Style::default().fg(theme.overlay1())),
+            Span::styled(node.item.label(), Style::default().fg(theme.text())),
+        ];
+        spans.extend(node.item.render_extra(theme));
+        ListItem::new(Line::from(spans))
+    }
+}
+
+fn find_mut<'a, T: TreeItem>(nodes: &'a mut [Node<T>], key: &str) -> Option<&'a mut Node<T>> {
+    for node in nodes {
+        if node.item.key() == key {
+            return Some(node);
+        }
+        if let Some(found) = node
+            .children
+            .as_deref_mut()
+            .and_then(|children| find_mut(children, key))
+        {
+            return Some(found);
+        }
+    }
+    None
+}
+
+impl<T: TreeItem + Clone> Component for Tree<T> {
+    type Output = TreeEvent<T>;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        match self.motion.handle_key(&key, &self.resolver) {
+            MotionStep::Count => return Ok(EventResult::Consumed),
+            MotionStep::Move(motion, count) => {
+                let len = self.rows().len();
+                let before = self.selected_index();
+                if let Some(target) = motion.apply(count, before, len, PAGE_STEP, self.height) {
+                    self.select_index(target);
+                }
+                return Ok(EventResult::Consumed);
+            }
+            MotionStep::Other => {}
+        }
+
+        if self.resolver.matches_nav(&key, NavAction::Select) {
+            return Ok(match self.selected_item() {
+                Some(item) if !item.is_branch() => TreeEvent::Activated(item.clone()).into(),
+                Some(_) => self.toggle_selected(),
+                None => EventResult::Ignored,
+            });
+        }
+        if self.resolver.matches_nav(&key, NavAction::Expand) {
+            return Ok(self.toggle_selected());
+        }
+        // Back climbs to the parent before it falls through to the caller
+        if self.resolver.matches_global(&key, GlobalAction::Back) && self.select_parent() {
+            return Ok(EventResult::Consumed);
+        }
+
+        Ok(EventResult::Ignored)
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        // Borders take 2 lines
+        self.height = usize::from(area.height.saturating_sub(2)).max(1);
+        let items: Vec<ListItem> = self
+            .rows()
+            .iter()
+            .map(|row| self.render_row(row, theme))
+            .collect();
+        self.state.select(self.selected_index());
+
+        let title = if self.roots.is_none() {
+            format!("{} ({}) ", self.title.trim_end(), t("loading"))
+        } else {
+            self.title.clone()
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.border()))
+            .title(title)
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            );
+        let list = RatatuiList::new(items)
+            .block(block)
+            .highlight_style(
+                Style::default()
+                    .bg(theme.selection_bg())
+                    .fg(theme.lavender())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol(theme.symbols.highlight);
+
+        frame.render_stateful_widget(list, area, &mut self.state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    use super::*;
+    use crate::config::keybindings::KeybindingsConfig;
+
+    #[derive(Clone)]
+    struct Prefix(&'static str);
+
+    impl TreeItem for Prefix {
+        fn key(&self) -> &str {
+            self.0
+        }
+
+        fn is_branch(&self) -> bool {
+            self.0.ends_with('/')
+        }
+
+        fn label(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    fn press(tree: &mut Tree<Prefix>, code: KeyCode) -> EventResult<TreeEvent<Prefix>> {
+        tree.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_lazy_expand_and_path() {
+        let resolver = Arc::new(KeyResolver::new(Arc::new(KeybindingsConfig::default())));
+        let mut tree = Tree::new(resolver);
+        tree.set_children(None, vec![Prefix("app/"), Prefix("readme")]);
+        assert_eq!(tree.path(), ["app/"]);
+
+        let EventResult::Event(TreeEvent::Load(parent)) = press(&mut tree, KeyCode::Enter) else {
+            panic!("expected a load");
+        };
+        tree.set_children(Some(parent.0), vec![Prefix("app/db"), Prefix("app/api")]);
+        press(&mut tree, KeyCode::Down);
+        assert_eq!(tree.path(), ["app/", "app/db"]);
+        assert!(matches!(
+            press(&mut tree, KeyCode::Enter),
+            EventResult::Event(TreeEvent::Activated(Prefix("app/db")))
+        ));
+
+        // Back closes the branch and selects it, loaded children are kept
+        press(&mut tree, KeyCode::Esc);
+        assert_eq!(tree.path(), ["app/"]);
+        assert_eq!(tree.rows().len(), 2);
+        assert!(matches!(
+            press(&mut tree, KeyCode::Char(' ')),
+            EventResult::Consumed
+        ));
+        assert_eq!(tree.rows().len(), 4);
+    }
+}
@@ -0,0 +1,160 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Margin, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::Theme;
+use crate::generate::{Charset, MAX_LENGTH};
+use crate::ui::{Component, EventResult, Result};
+
+pub enum GeneratorEvent {
+    Generated(String),
+    Cancelled,
+}
+
+/// Picks the charset and length of a random value.
+///
+/// Left and Right switch the charset, Up and Down change the length and
+/// Enter generates the value.
+pub struct ValueGenerator {
+    charset: Charset,
+    length: usize,
+}
+
+impl ValueGenerator {
+    pub const fn new() -> Self {
+        let charset = Charset::Hex;
+        Self {
+            charset,
+            length: charset.default_length(),
+        }
+    }
+
+    fn cycle_charset(&mut self, forward: bool) {
+        let count = Charset::ALL.len();
+        let index = Charset::ALL
+            .iter()
+            .position(|&charset| charset == self.charset)
+            .unwrap_or(0);
+        let index = if forward {
+            (index + 1) % count
+        } else {
+            (index + count - 1) % count
+        };
+        self.charset = Charset::ALL[index];
+        self.length = self.charset.default_length();
+    }
+
+    fn change_length(&mut self, by: isize) {
+        self.length = self.length.saturating_add_signed(by).clamp(1, MAX_LENGTH);
+    }
+}
+
+impl Default for ValueGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Component for ValueGenerator {
+    type Output = GeneratorEvent;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        match key.code {
+            KeyCode::Enter => {
+                return Ok(GeneratorEvent::Generated(self.charset.generate(self.length)).into());
+            }
+            KeyCode::Esc => return Ok(GeneratorEvent::Cancelled.into()),
+            KeyCode::Right | KeyCode::Tab => self.cycle_charset(true),
+            KeyCode::Left | KeyCode::BackTab => self.cycle_charset(false),
+            KeyCode::Up => self.change_length(1),
+            KeyCode::Down => self.change_length(-1),
+            KeyCode::PageUp => self.change_length(8),
+            KeyCode::PageDown => self.change_length(-8),
+            _ => {}
+        }
+        Ok(EventResult::Consumed)
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = area.centered(Constraint::Length(60), Constraint::Length(6));
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(" Generate Value ")
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .title_bottom(Line::styled(
+                format!(
+                    " ←/→ charset {dot} ↑/↓ length {dot} Enter insert {dot} Esc cancel ",
+                    dot = theme.symbols.middle_dot,
+                ),
+                Style::default().fg(theme.overlay1()),
+            ))
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.lavender()))
+            .style(Style::default().bg(theme.base()));
+        let inner = block.inner(popup_area).inner(Margin::new(1, 1));
+        frame.render_widget(block, popup_area);
+
+        let charsets: Vec<Span> = Charset::ALL
+            .iter()
+            .flat_map(|&charset| {
+                let style = if charset == self.charset {
+                    Style::default()
+                        .fg(theme.base())
+                        .bg(theme.mauve())
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.subtext0())
+                };
+                [
+                    Span::styled(format!(" {} ", charset.label()), style),
+                    Span::raw(" "),
+                ]
+            })
+            .collect();
+        let length = Line::from(vec![
+            Span::styled("Length  ", Style::default().fg(theme.subtext0())),
+            Span::styled(
+                format!("{} {}", self.length, self.charset.unit()),
+                Style::default().fg(theme.text()),
+            ),
+        ]);
+        frame.render_widget(Paragraph::new(vec![Line::from(charsets), length]), inner);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+
+    use super::*;
+
+    fn press(generator: &mut ValueGenerator, code: KeyCode) -> Option<GeneratorEvent> {
+        match generator
+            .handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+            .unwrap()
+        {
+            EventResult::Event(event) => Some(event),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_generate_passphrase() {
+        let mut generator = ValueGenerator::new();
+        press(&mut generator, KeyCode::Left);
+        press(&mut generator, KeyCode::Down);
+        let Some(GeneratorEvent::Generated(value)) = press(&mut generator, KeyCode::Enter) else {
+            panic!("expected a value");
+        };
+        assert_eq!(value.split('-').count(), 5);
+    }
+}
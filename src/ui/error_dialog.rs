@@ -5,25 +5,47 @@ use ratatui::Frame;
 use ratatui::layout::{Alignment, Constraint, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
 
-use crate::Theme;
 use crate::config::{DialogAction, KeyResolver};
+use crate::error::Error;
+use crate::i18n::t;
 use crate::ui::{Component, EventResult, Result};
+use crate::{Theme, redact};
 
 pub enum ErrorDialogEvent {
     Dismissed,
 }
 
 pub struct ErrorDialog {
+    title: &'static str,
     message: String,
+    hint: Option<&'static str>,
     resolver: Arc<KeyResolver>,
 }
 
 impl ErrorDialog {
     pub fn new(message: impl Into<String>, resolver: Arc<KeyResolver>) -> Self {
         Self {
-            message: message.into(),
+            title: "Error",
+            message: redact::scrub(&message.into()).into_owned(),
+            hint: None,
+            resolver,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_title(mut self, title: &'static str) -> Self {
+        self.title = title;
+        self
+    }
+
+    /// Dialog titled after the kind of `error`, with what to do about it.
+    pub fn from_error(error: &Error, resolver: Arc<KeyResolver>) -> Self {
+        Self {
+            title: error.title(),
+            message: redact::scrub(error.message()).into_owned(),
+            hint: error.hint(),
             resolver,
         }
     }
@@ -50,18 +72,27 @@ impl Component for ErrorDialog {
         let message_style = Style::default().fg(theme.text());
         let hint_style = Style::default().fg(theme.overlay1());
 
-        let lines = vec![
-            Line::from(""),
-            Line::from(Span::styled(&self.message, message_style)),
-            Line::from(""),
-            Line::from(Span::styled("Press Enter or Esc to dismiss", hint_style)),
-        ];
+        let mut lines = vec![Line::from("")];
+        lines.extend(
+            self.message
+                .lines()
+                .map(|line| Line::from(Span::styled(line, message_style))),
+        );
+        lines.push(Line::from(""));
+        if let Some(hint) = self.hint {
+            lines.push(Line::from(Span::styled(t(hint), message_style)));
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::from(Span::styled(
+            t("Press Enter or Esc to dismiss"),
+            hint_style,
+        )));
 
         let block = Block::default()
-            .title(" Error ")
+            .title(format!(" {} ", t(self.title)))
             .title_style(title_style)
             .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
+            .border_type(theme.border_type)
             .border_style(Style::default().fg(theme.red()))
             .style(Style::default().bg(theme.base()));
 
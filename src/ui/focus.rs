@@ -0,0 +1,99 @@
+use crossterm::event::KeyEvent;
+
+use crate::config::{GlobalAction, KeyResolver};
+
+/// Tracks which of several components receives keys, e.g. the panes of a
+/// split view or the fields of a form. Focus moves through them in tab order
+/// with the focus keys and wraps around at either end. The focused component
+/// draws its border in `theme.border_focused()`.
+pub struct FocusManager<F> {
+    /// Focusable components in tab order, never empty.
+    order: Vec<F>,
+    index: usize,
+}
+
+impl<F: Copy + PartialEq> FocusManager<F> {
+    /// Focus on the first of `order`.
+    ///
+    /// # Panics
+    ///
+    /// If `order` is empty.
+    pub fn new(order: Vec<F>) -> Self {
+        assert!(!order.is_empty(), "nothing to focus");
+        Self { order, index: 0 }
+    }
+
+    pub fn focused(&self) -> F {
+        self.order[self.index]
+    }
+
+    pub fn is_focused(&self, target: F) -> bool {
+        self.focused() == target
+    }
+
+    /// Move focus to `target`, if it is in the tab order.
+    pub fn focus(&mut self, target: F) {
+        if let Some(index) = self.order.iter().position(|&f| f == target) {
+            self.index = index;
+        }
+    }
+
+    pub const fn focus_next(&mut self) {
+        self.index = (self.index + 1) % self.order.len();
+    }
+
+    pub const fn focus_prev(&mut self) {
+        self.index = (self.index + self.order.len() - 1) % self.order.len();
+    }
+
+    /// Move focus if `key` is a focus key.
+    ///
+    /// Returns `true` if the focus moved.
+    pub fn handle_key(&mut self, key: &KeyEvent, resolver: &KeyResolver) -> bool {
+        if resolver.matches_global(key, GlobalAction::FocusNext) {
+            self.focus_next();
+        } else if resolver.matches_global(key, GlobalAction::FocusPrev) {
+            self.focus_prev();
+        } else {
+            return false;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    use super::*;
+    use crate::config::keybindings::KeybindingsConfig;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Pane {
+        List,
+        Preview,
+        Filter,
+    }
+
+    #[test]
+    fn test_tab_order_wraps() {
+        let resolver = KeyResolver::new(Arc::new(KeybindingsConfig::default()));
+        let mut focus = FocusManager::new(vec![Pane::List, Pane::Preview, Pane::Filter]);
+        let tab = KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE);
+        let back_tab = KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT);
+
+        assert!(focus.handle_key(&back_tab, &resolver));
+        assert_eq!(focus.focused(), Pane::Filter);
+        assert!(focus.handle_key(&tab, &resolver));
+        assert_eq!(focus.focused(), Pane::List);
+        assert!(!focus.handle_key(
+            &KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+            &resolver
+        ));
+
+        focus.focus(Pane::Preview);
+        assert!(focus.is_focused(Pane::Preview));
+    }
+}
@@ -1,11 +1,16 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use std::sync::Arc;
+
+use crossterm::event::KeyEvent;
 use ratatui::Frame;
-use ratatui::layout::{Constraint, Rect};
+use ratatui::layout::{Constraint, Layout, Margin, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
 use crate::Theme;
+use crate::config::{GlobalAction, KeyResolver, NavAction};
+use crate::i18n::{t, t_args};
+use crate::ui::text::{display_width, padding};
 use crate::ui::{Component, EventResult, Result};
 
 pub struct Keybinding {
@@ -13,6 +18,9 @@ pub struct Keybinding {
     pub description: String,
     /// Whether this keybinding should be shown in the hints line at the bottom.
     pub hint: bool,
+    /// Shown greyed out because the action is not available, e.g. for lack
+    /// of a permission.
+    pub disabled: bool,
 }
 
 impl Keybinding {
@@ -21,6 +29,7 @@ impl Keybinding {
             key: key.into(),
             description: description.into(),
             hint: false,
+            disabled: false,
         }
     }
 
@@ -30,6 +39,24 @@ impl Keybinding {
             key: key.into(),
             description: description.into(),
             hint: true,
+            disabled: false,
+        }
+    }
+
+    #[must_use]
+    pub const fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// `style`, or the greyed out style if the keybinding is disabled.
+    pub fn style(&self, style: Style, theme: &Theme) -> Style {
+        if self.disabled {
+            Style::default()
+                .fg(theme.overlay0())
+                .add_modifier(Modifier::CROSSED_OUT)
+        } else {
+            style
         }
     }
 }
@@ -47,26 +74,89 @@ impl KeybindingSection {
             keybindings,
         }
     }
+
+    fn lines(&self, key_width: usize, theme: &Theme) -> Vec<Line<'static>> {
+        let key_style = Style::default()
+            .fg(theme.peach())
+            .add_modifier(Modifier::BOLD);
+        let desc_style = Style::default().fg(theme.text());
+        let section_style = Style::default()
+            .fg(theme.subtext0())
+            .add_modifier(Modifier::BOLD);
+
+        let mut lines = vec![Line::from(Span::styled(
+            format!(
+                "{rule} {} {rule}",
+                t(&self.title),
+                rule = theme.symbols.rule
+            ),
+            section_style,
+        ))];
+        lines.extend(self.keybindings.iter().map(|kb| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{}{}", padding(&kb.key, key_width), kb.key),
+                    kb.style(key_style, theme),
+                ),
+                Span::raw("  "),
+                Span::styled(t(&kb.description).to_string(), kb.style(desc_style, theme)),
+            ])
+        }));
+        lines
+    }
 }
 
 pub enum HelpEvent {
     Close,
 }
 
+/// Cheatsheet of the keybindings available in the current view.
+///
+/// Sections are laid out in as many columns as fit and are never split
+/// across columns; what does not fit scrolls.
 pub struct HelpOverlay {
     sections: Vec<KeybindingSection>,
+    scroll: u16,
+    resolver: Arc<KeyResolver>,
 }
 
 impl HelpOverlay {
-    #[allow(dead_code)]
-    pub fn new(keybindings: Vec<Keybinding>) -> Self {
+    /// Empty sections are left out.
+    pub fn with_sections(sections: Vec<KeybindingSection>, resolver: Arc<KeyResolver>) -> Self {
         Self {
-            sections: vec![KeybindingSection::new("Keybindings", keybindings)],
+            sections: sections
+                .into_iter()
+                .filter(|section| !section.keybindings.is_empty())
+                .collect(),
+            scroll: 0,
+            resolver,
         }
     }
 
-    pub const fn with_sections(sections: Vec<KeybindingSection>) -> Self {
-        Self { sections }
+    /// Distribute sections over `columns` columns of `height` lines.
+    fn layout(
+        &self,
+        columns: usize,
+        height: usize,
+        key_width: usize,
+        theme: &Theme,
+    ) -> Vec<Vec<Line<'static>>> {
+        let mut layout: Vec<Vec<Line>> = vec![Vec::new()];
+        for section in &self.sections {
+            let lines = section.lines(key_width, theme);
+            let has_room = layout.len() < columns;
+            let column = layout.last_mut().expect("layout has a column");
+            let needed = lines.len() + usize::from(!column.is_empty());
+            if !column.is_empty() && column.len() + needed > height && has_room {
+                layout.push(lines);
+            } else {
+                if !column.is_empty() {
+                    column.push(Line::from(""));
+                }
+                column.extend(lines);
+            }
+        }
+        layout
     }
 }
 
@@ -74,64 +164,86 @@ impl Component for HelpOverlay {
     type Output = HelpEvent;
 
     fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
-        Ok(match key.code {
-            KeyCode::Esc | KeyCode::Char('?' | 'q') => HelpEvent::Close.into(),
-            _ => EventResult::Ignored,
-        })
+        let r = &self.resolver;
+        if r.matches_global(&key, GlobalAction::Help)
+            || r.matches_global(&key, GlobalAction::Back)
+            || r.matches_global(&key, GlobalAction::Quit)
+        {
+            return Ok(HelpEvent::Close.into());
+        }
+        if r.matches_nav(&key, NavAction::Down) {
+            self.scroll = self.scroll.saturating_add(1);
+        } else if r.matches_nav(&key, NavAction::Up) {
+            self.scroll = self.scroll.saturating_sub(1);
+        } else if r.matches_nav(&key, NavAction::Home) {
+            self.scroll = 0;
+        }
+        // Everything else is swallowed while the help is open
+        Ok(EventResult::Consumed)
     }
 
     fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
-        // Calculate centered popup area
-        let popup_area = area.centered(Constraint::Percentage(60), Constraint::Percentage(70));
-
-        // Clear the area behind the popup
+        let popup_area = area.centered(Constraint::Percentage(80), Constraint::Percentage(80));
         frame.render_widget(Clear, popup_area);
 
-        // Build keybinding lines with sections
-        let key_style = Style::default()
-            .fg(theme.peach())
-            .add_modifier(Modifier::BOLD);
-        let desc_style = Style::default().fg(theme.text());
-        let section_style = Style::default()
-            .fg(theme.subtext0())
-            .add_modifier(Modifier::BOLD);
-
-        let mut lines: Vec<Line> = Vec::new();
-
-        for (i, section) in self.sections.iter().enumerate() {
-            // Add blank line between sections (but not before first)
-            if i > 0 {
-                lines.push(Line::from(""));
-            }
-
-            // Section header
-            let header = format!("── {} ──", section.title);
-            lines.push(Line::from(Span::styled(header, section_style)));
-
-            // Keybindings in this section
-            for kb in &section.keybindings {
-                lines.push(Line::from(vec![
-                    Span::styled(format!("{:>12}", kb.key), key_style),
-                    Span::raw("  "),
-                    Span::styled(kb.description.clone(), desc_style),
-                ]));
-            }
-        }
-
         let block = Block::default()
-            .title(" Help (press ? or Esc to close) ")
+            .title(format!(
+                " {} ",
+                t_args(
+                    "Help ({help} or {back} to close)",
+                    &[
+                        ("help", &self.resolver.display_global(GlobalAction::Help)),
+                        ("back", &self.resolver.display_global(GlobalAction::Back)),
+                    ],
+                )
+            ))
             .title_style(
                 Style::default()
                     .fg(theme.mauve())
                     .add_modifier(Modifier::BOLD),
             )
             .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
+            .border_type(theme.border_type)
             .border_style(Style::default().fg(theme.lavender()))
             .style(Style::default().bg(theme.base()));
-
-        let paragraph = Paragraph::new(lines).block(block);
-
-        frame.render_widget(paragraph, popup_area);
+        let inner = block.inner(popup_area).inner(Margin::new(1, 0));
+
+        let keybindings = self.sections.iter().flat_map(|s| &s.keybindings);
+        let key_width = keybindings
+            .clone()
+            .map(|kb| display_width(&kb.key))
+            .max()
+            .unwrap_or(1);
+        let desc_width = keybindings
+            .map(|kb| display_width(t(&kb.description)))
+            .max()
+            .unwrap_or(1);
+        // key + gap + description + gutter between columns
+        let column_width = u16::try_from(key_width + 2 + desc_width + 3).unwrap_or(u16::MAX);
+        let columns = usize::from((inner.width / column_width).max(1));
+
+        let layout = self.layout(columns, usize::from(inner.height), key_width, theme);
+        let tallest = layout.iter().map(Vec::len).max().unwrap_or(0);
+        let max_scroll =
+            u16::try_from(tallest.saturating_sub(usize::from(inner.height))).unwrap_or(u16::MAX);
+        self.scroll = self.scroll.min(max_scroll);
+
+        let block = if max_scroll > 0 {
+            block.title_bottom(format!(
+                " {}/{} to scroll ",
+                self.resolver.display_nav(NavAction::Down),
+                self.resolver.display_nav(NavAction::Up)
+            ))
+        } else {
+            block
+        };
+        frame.render_widget(block, popup_area);
+
+        let areas =
+            Layout::horizontal(vec![Constraint::Length(column_width); layout.len()]).split(inner);
+        for (lines, column_area) in layout.into_iter().zip(areas.iter()) {
+            let paragraph = Paragraph::new(lines).scroll((self.scroll, 0));
+            frame.render_widget(paragraph, *column_area);
+        }
     }
 }
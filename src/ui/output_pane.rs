@@ -0,0 +1,305 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::Theme;
+use crate::config::{GlobalAction, KeyResolver, NavAction, OutputAction};
+use crate::i18n::{t, t_args};
+use crate::time::format_clock;
+use crate::ui::{Component, EventResult, Keybinding, Result};
+
+/// Maximum number of lines kept in the scrollback buffer.
+const MAX_LINES: usize = 2000;
+
+/// Lines scrolled by page up and page down.
+const PAGE_STEP: isize = 10;
+
+/// Height of the pane when expanded, including borders.
+pub const PANE_HEIGHT: u16 = 12;
+
+pub enum OutputPaneEvent {
+    /// Copy the whole scrollback buffer.
+    Copy(String),
+    Close,
+}
+
+enum OutputLine {
+    Header { title: String, success: bool },
+    Body(String),
+}
+
+/// Collapsible bottom pane collecting the output of executed commands.
+///
+/// Unlike the command panel, which only tracks command status, this keeps
+/// what commands printed so it can be scrolled back through and copied.
+pub struct OutputPane {
+    lines: VecDeque<OutputLine>,
+    expanded: bool,
+    /// Whether keys go to the pane before the view beside it.
+    focused: bool,
+    /// Distance from the bottom of the buffer, in lines.
+    scroll: usize,
+    resolver: Arc<KeyResolver>,
+}
+
+impl OutputPane {
+    pub const fn new(resolver: Arc<KeyResolver>) -> Self {
+        Self {
+            lines: VecDeque::new(),
+            expanded: false,
+            focused: false,
+            scroll: 0,
+            resolver,
+        }
+    }
+
+    /// Swap keybindings after a profile switch without losing the output.
+    pub fn set_resolver(&mut self, resolver: Arc<KeyResolver>) {
+        self.resolver = resolver;
+    }
+
+    /// Append a command's output, scrolled to the bottom. A collapsed pane
+    /// stays collapsed.
+    pub fn push(&mut self, title: &str, output: &str, success: bool) {
+        let time = format_clock(chrono::Utc::now());
+        self.lines.push_back(OutputLine::Header {
+            title: format!("{title} · {time}"),
+            success,
+        });
+        self.lines.extend(
+            output
+                .lines()
+                .map(|line| OutputLine::Body(line.to_string())),
+        );
+        while self.lines.len() > MAX_LINES {
+            self.lines.pop_front();
+        }
+        self.scroll = 0;
+    }
+
+    pub fn clear(&mut self) {
+        self.lines.clear();
+        self.scroll = 0;
+    }
+
+    pub const fn toggle_expanded(&mut self) {
+        self.expanded = !self.expanded;
+    }
+
+    pub const fn is_expanded(&self) -> bool {
+        self.expanded
+    }
+
+    pub const fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    /// Plain-text contents of the scrollback buffer.
+    pub fn text(&self) -> String {
+        self.lines
+            .iter()
+            .map(|line| match line {
+                OutputLine::Header { title, .. } => format!("# {title}"),
+                OutputLine::Body(text) => text.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn keybindings(&self) -> Vec<Keybinding> {
+        vec![
+            Keybinding::hint(
+                self.resolver.display_output(OutputAction::Copy),
+                "Copy output",
+            ),
+            Keybinding::hint(
+                self.resolver.display_output(OutputAction::Clear),
+                "Clear output",
+            ),
+            Keybinding::hint(
+                self.resolver.display_global(GlobalAction::OutputToggle),
+                "Hide output",
+            ),
+        ]
+    }
+
+    /// Scroll by `delta` lines, positive towards the top. Returns whether
+    /// the view moved, so keys at either end reach the view beside the pane.
+    fn scroll_by(&mut self, delta: isize) -> bool {
+        let max = self.lines.len().saturating_sub(1);
+        let scroll = self.scroll.saturating_add_signed(delta).min(max);
+        let moved = scroll != self.scroll;
+        self.scroll = scroll;
+        moved
+    }
+
+    fn render_line(line: &OutputLine, theme: &Theme) -> Line<'static> {
+        match line {
+            OutputLine::Header { title, success } => {
+                let (icon, color) = if *success {
+                    (theme.symbols.success, theme.green())
+                } else {
+                    (theme.symbols.failure, theme.red())
+                };
+                Line::from(vec![
+                    Span::styled(format!("{icon} "), Style::default().fg(color)),
+                    Span::styled(
+                        title.clone(),
+                        Style::default()
+                            .fg(theme.mauve())
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                ])
+            }
+            OutputLine::Body(text) => Line::from(Span::styled(
+                text.clone(),
+                Style::default().fg(theme.text()),
+            )),
+        }
+    }
+}
+
+impl Component for OutputPane {
+    type Output = OutputPaneEvent;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if self
+            .resolver
+            .matches_global(&key, GlobalAction::OutputToggle)
+            || self.resolver.matches_global(&key, GlobalAction::Back)
+        {
+            return Ok(OutputPaneEvent::Close.into());
+        }
+        if self.resolver.matches_output(&key, OutputAction::Copy) {
+            return Ok(OutputPaneEvent::Copy(self.text()).into());
+        }
+        if self.resolver.matches_output(&key, OutputAction::Clear) {
+            self.clear();
+            return Ok(EventResult::Consumed);
+        }
+
+        let all = isize::try_from(self.lines.len()).unwrap_or(isize::MAX);
+        let delta = if self.resolver.matches_nav(&key, NavAction::Up) {
+            1
+        } else if self.resolver.matches_nav(&key, NavAction::Down) {
+            -1
+        } else if self.resolver.matches_nav(&key, NavAction::PageUp) {
+            PAGE_STEP
+        } else if self.resolver.matches_nav(&key, NavAction::PageDown) {
+            -PAGE_STEP
+        } else if self.resolver.matches_nav(&key, NavAction::Home) {
+            all
+        } else if self.resolver.matches_nav(&key, NavAction::End) {
+            -all
+        } else {
+            return Ok(EventResult::Ignored);
+        };
+        Ok(if self.scroll_by(delta) {
+            EventResult::Consumed
+        } else {
+            EventResult::Ignored
+        })
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        frame.render_widget(Clear, area);
+
+        let border = if self.focused {
+            theme.border_focused()
+        } else {
+            theme.surface2()
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(border))
+            .title(format!(
+                " {} ",
+                t_args(
+                    "Output ({count} lines)",
+                    &[("count", &self.lines.len().to_string())]
+                )
+            ))
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .style(Style::default().bg(theme.mantle()));
+
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        if self.lines.is_empty() {
+            let empty =
+                Paragraph::new(t("No output yet")).style(Style::default().fg(theme.overlay0()));
+            frame.render_widget(empty, inner);
+            return;
+        }
+
+        // Show the window ending `scroll` lines above the bottom
+        let visible = usize::from(inner.height);
+        let end = self.lines.len().saturating_sub(self.scroll);
+        let start = end.saturating_sub(visible);
+        let lines: Vec<Line> = self
+            .lines
+            .range(start..end)
+            .map(|line| Self::render_line(line, theme))
+            .collect();
+
+        frame.render_widget(Paragraph::new(lines), inner);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    use super::*;
+    use crate::config::keybindings::KeybindingsConfig;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn pane() -> OutputPane {
+        OutputPane::new(Arc::new(KeyResolver::new(Arc::new(
+            KeybindingsConfig::default(),
+        ))))
+    }
+
+    #[test]
+    fn test_push_keeps_pane_collapsed() {
+        let mut pane = pane();
+        pane.push("gcloud", "one\ntwo", true);
+        assert!(!pane.is_expanded());
+        assert!(pane.text().ends_with("one\ntwo"));
+    }
+
+    #[test]
+    fn test_navigation_at_the_ends_passes_through() {
+        let mut pane = pane();
+        assert!(matches!(
+            pane.handle_key(key(KeyCode::Up)).unwrap(),
+            EventResult::Ignored
+        ));
+
+        pane.push("gcloud", "one\ntwo", true);
+        assert!(matches!(
+            pane.handle_key(key(KeyCode::Down)).unwrap(),
+            EventResult::Ignored
+        ));
+        assert!(pane.handle_key(key(KeyCode::Up)).unwrap().is_consumed());
+        assert!(pane.handle_key(key(KeyCode::Home)).unwrap().is_consumed());
+        assert!(matches!(
+            pane.handle_key(key(KeyCode::Up)).unwrap(),
+            EventResult::Ignored
+        ));
+    }
+}
@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, ListItem};
+
+use crate::Theme;
+use crate::config::{GlobalAction, KeyResolver};
+use crate::i18n::t;
+use crate::ui::{Component, EventResult, List, ListEvent, ListRow, Result};
+
+/// A selectable profile; `None` is the base config without any overlay.
+#[derive(Debug, Clone)]
+pub struct ProfileItem {
+    pub name: Option<String>,
+    active: bool,
+}
+
+impl ListRow for ProfileItem {
+    fn render_row(&self, theme: &Theme) -> ListItem<'static> {
+        let name = self.name.clone().unwrap_or_else(|| "default".to_string());
+        let mut spans = vec![Span::styled(name, Style::default().fg(theme.text()))];
+        if self.active {
+            spans.push(Span::styled(
+                " (active)",
+                Style::default().fg(theme.green()),
+            ));
+        }
+        ListItem::new(Line::from(spans))
+    }
+}
+
+pub enum ProfileEvent {
+    Cancelled,
+    Selected(Option<String>),
+}
+
+pub struct ProfileSelectorView {
+    list: List<ProfileItem>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl ProfileSelectorView {
+    pub fn new(profiles: Vec<String>, active: Option<&str>, resolver: Arc<KeyResolver>) -> Self {
+        let items = std::iter::once(None)
+            .chain(profiles.into_iter().map(Some))
+            .map(|name| ProfileItem {
+                active: name.as_deref() == active,
+                name,
+            })
+            .collect();
+        Self {
+            list: List::new(items, resolver.clone()),
+            resolver,
+        }
+    }
+}
+
+impl Component for ProfileSelectorView {
+    type Output = ProfileEvent;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if self.resolver.matches_global(&key, GlobalAction::Back)
+            || self.resolver.matches_global(&key, GlobalAction::Profile)
+        {
+            return Ok(ProfileEvent::Cancelled.into());
+        }
+
+        Ok(match self.list.handle_key(key)? {
+            EventResult::Event(ListEvent::Activated(item)) => {
+                ProfileEvent::Selected(item.name).into()
+            }
+            EventResult::Consumed | EventResult::Event(_) => EventResult::Consumed,
+            EventResult::Ignored => EventResult::Ignored,
+        })
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = area.centered(Constraint::Percentage(40), Constraint::Percentage(50));
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(format!(
+                " {} ",
+                t("Select Profile (Enter to confirm, Esc to cancel)")
+            ))
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.lavender()))
+            .style(Style::default().bg(theme.base()));
+
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        self.list.render(frame, inner, theme);
+    }
+}
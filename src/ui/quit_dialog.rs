@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+
+use crate::Theme;
+use crate::config::{DialogAction, KeyResolver};
+use crate::i18n::{t, t_args};
+use crate::ui::{Component, EventResult, Result};
+
+pub enum QuitEvent {
+    /// Quit without waiting, abandoning the operations.
+    QuitNow,
+    Cancelled,
+}
+
+/// Shown when quitting while operations that change something still run,
+/// or while services hold work that only lives in memory. Without such work
+/// the app quits on its own once the operations finished.
+pub struct QuitDialog {
+    /// Names of the operations still running.
+    pending: Vec<String>,
+    /// What the services lose on quitting, e.g. secrets in the trash.
+    losses: Vec<String>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl QuitDialog {
+    pub const fn new(
+        pending: Vec<String>,
+        losses: Vec<String>,
+        resolver: Arc<KeyResolver>,
+    ) -> Self {
+        Self {
+            pending,
+            losses,
+            resolver,
+        }
+    }
+
+    pub fn set_pending(&mut self, pending: Vec<String>) {
+        self.pending = pending;
+    }
+
+    /// Whether quitting has to wait for the user, not only the operations.
+    pub const fn has_losses(&self) -> bool {
+        !self.losses.is_empty()
+    }
+}
+
+impl Component for QuitDialog {
+    type Output = QuitEvent;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if self.resolver.matches_dialog(&key, DialogAction::Confirm) {
+            return Ok(QuitEvent::QuitNow.into());
+        }
+        if self.resolver.matches_dialog(&key, DialogAction::Cancel) {
+            return Ok(QuitEvent::Cancelled.into());
+        }
+        Ok(EventResult::Consumed)
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = area.centered(Constraint::Percentage(60), Constraint::Percentage(40));
+        frame.render_widget(Clear, popup_area);
+
+        let mut lines = Vec::new();
+        if !self.pending.is_empty() {
+            lines.push(Line::from(Span::styled(
+                t_args(
+                    "Waiting for {count} operations to finish...",
+                    &[("count", &self.pending.len().to_string())],
+                ),
+                Style::default().fg(theme.text()),
+            )));
+            lines.push(Line::from(""));
+            lines.extend(self.pending.iter().map(|name| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{} ", theme.symbols.running),
+                        Style::default().fg(theme.peach()),
+                    ),
+                    Span::styled(name.clone(), Style::default().fg(theme.subtext0())),
+                ])
+            }));
+            lines.push(Line::from(""));
+        }
+        if self.has_losses() {
+            lines.push(Line::from(Span::styled(
+                t("Quitting loses what is only kept in memory:"),
+                Style::default().fg(theme.text()),
+            )));
+            lines.push(Line::from(""));
+            lines.extend(self.losses.iter().map(|loss| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{} ", theme.symbols.warning),
+                        Style::default().fg(theme.yellow()),
+                    ),
+                    Span::styled(loss.clone(), Style::default().fg(theme.subtext0())),
+                ])
+            }));
+            lines.push(Line::from(""));
+        }
+        let hint = if self.pending.is_empty() {
+            "{quit} to quit anyway, {cancel} to keep working"
+        } else {
+            "{quit} to quit now and abandon them, {cancel} to keep working"
+        };
+        lines.push(Line::from(Span::styled(
+            t_args(
+                hint,
+                &[
+                    ("quit", &self.resolver.display_dialog(DialogAction::Confirm)),
+                    (
+                        "cancel",
+                        &self.resolver.display_dialog(DialogAction::Cancel),
+                    ),
+                ],
+            ),
+            Style::default().fg(theme.overlay1()),
+        )));
+
+        let block = Block::default()
+            .title(format!(" {} ", t("Quitting")))
+            .title_style(
+                Style::default()
+                    .fg(theme.yellow())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.yellow()))
+            .style(Style::default().bg(theme.base()));
+
+        frame.render_widget(
+            Paragraph::new(lines).block(block).wrap(Wrap { trim: true }),
+            popup_area,
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Margin, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+
+use crate::Theme;
+use crate::config::{GlobalAction, KeyResolver, NavAction};
+use crate::ui::{Component, EventResult, Result};
+
+pub enum ReleaseNotesEvent {
+    Close,
+}
+
+/// Release notes written in the changelog's markdown, scrolled with the
+/// navigation keys.
+pub struct ReleaseNotesOverlay {
+    title: String,
+    notes: String,
+    scroll: u16,
+    resolver: Arc<KeyResolver>,
+}
+
+impl ReleaseNotesOverlay {
+    pub fn new(
+        title: impl Into<String>,
+        notes: impl Into<String>,
+        resolver: Arc<KeyResolver>,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            notes: notes.into(),
+            scroll: 0,
+            resolver,
+        }
+    }
+
+    fn lines(&self, theme: &Theme) -> Vec<Line<'static>> {
+        self.notes
+            .lines()
+            .map(|line| note_line(line, theme))
+            .collect()
+    }
+}
+
+/// A heading or list item styled, anything else as plain text.
+fn note_line(line: &str, theme: &Theme) -> Line<'static> {
+    let text_style = Style::default().fg(theme.text());
+    if let Some(release) = line.strip_prefix("## ") {
+        return Line::from(Span::styled(
+            release.to_string(),
+            Style::default()
+                .fg(theme.mauve())
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    if let Some(section) = line.strip_prefix("### ") {
+        return Line::from(Span::styled(
+            section.to_string(),
+            Style::default()
+                .fg(theme.subtext0())
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    if let Some(item) = line.strip_prefix("- ") {
+        return Line::from(vec![
+            Span::styled(
+                format!("{} ", theme.symbols.bullet),
+                Style::default().fg(theme.blue()),
+            ),
+            Span::styled(item.replace('`', ""), text_style),
+        ]);
+    }
+    Line::from(Span::styled(line.to_string(), text_style))
+}
+
+impl Component for ReleaseNotesOverlay {
+    type Output = ReleaseNotesEvent;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        let r = &self.resolver;
+        if r.matches_global(&key, GlobalAction::Back)
+            || r.matches_global(&key, GlobalAction::Quit)
+            || r.matches_nav(&key, NavAction::Select)
+        {
+            return Ok(ReleaseNotesEvent::Close.into());
+        }
+        if r.matches_nav(&key, NavAction::Down) {
+            self.scroll = self.scroll.saturating_add(1);
+        } else if r.matches_nav(&key, NavAction::Up) {
+            self.scroll = self.scroll.saturating_sub(1);
+        } else if r.matches_nav(&key, NavAction::Home) {
+            self.scroll = 0;
+        }
+        Ok(EventResult::Consumed)
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = area.centered(Constraint::Percentage(70), Constraint::Percentage(70));
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(format!(" {} ", self.title))
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.lavender()))
+            .style(Style::default().bg(theme.base()));
+        let inner = block.inner(popup_area).inner(Margin::new(1, 0));
+        frame.render_widget(block, popup_area);
+
+        let lines = self.lines(theme);
+        let max_scroll = u16::try_from(lines.len().saturating_sub(usize::from(inner.height)))
+            .unwrap_or(u16::MAX);
+        self.scroll = self.scroll.min(max_scroll);
+        frame.render_widget(
+            Paragraph::new(lines)
+                .wrap(Wrap { trim: false })
+                .scroll((self.scroll, 0)),
+            inner,
+        );
+    }
+}
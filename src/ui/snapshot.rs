@@ -0,0 +1,86 @@
+//! Snapshot assertions for rendered screens.
+//!
+//! A snapshot is the debug dump of a [`TestBackend`] buffer, including the
+//! style of every cell, stored in a `snapshots/` directory next to the test
+//! module. After an intended UI change, rewrite them with
+//! `UPDATE_SNAPSHOTS=1 cargo test` and review the diff.
+
+use std::fs;
+use std::path::Path;
+
+use ratatui::backend::TestBackend;
+use ratatui::{Frame, Terminal};
+use similar::TextDiff;
+
+use crate::Theme;
+
+/// Environment variable that rewrites snapshots instead of comparing them.
+///
+/// An environment variable rather than a cargo feature, so that
+/// `--all-features` in CI still compares.
+const UPDATE_VAR: &str = "UPDATE_SNAPSHOTS";
+
+/// A dark and a light theme, so contrast issues show up in either.
+const THEMES: [(&str, Theme); 2] = [
+    ("mocha", Theme::catppuccin_mocha()),
+    ("latte", Theme::catppuccin_latte()),
+];
+
+/// A small terminal and a roomy one.
+pub const SIZES: [(u16, u16); 2] = [(80, 24), (120, 40)];
+
+/// Assert a snapshot per theme and terminal size, named
+/// `<name>@<theme>_<width>x<height>`.
+macro_rules! assert_snapshots {
+    ($name:expr, $sizes:expr, $draw:expr) => {
+        $crate::ui::snapshot::assert_variants(file!(), $name, $sizes, $draw)
+    };
+}
+
+pub(crate) use assert_snapshots;
+
+pub fn assert_variants(
+    test_file: &str,
+    name: &str,
+    sizes: &[(u16, u16)],
+    mut draw: impl FnMut(&mut Frame, &Theme),
+) {
+    for (theme_name, theme) in &THEMES {
+        for &(width, height) in sizes {
+            let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+            terminal.draw(|frame| draw(frame, theme)).unwrap();
+            let actual = format!("{:#?}\n", terminal.backend().buffer());
+            assert_snapshot(
+                test_file,
+                &format!("{name}@{theme_name}_{width}x{height}"),
+                &actual,
+            );
+        }
+    }
+}
+
+fn assert_snapshot(test_file: &str, name: &str, actual: &str) {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join(test_file)
+        .with_file_name("snapshots");
+    let path = dir.join(format!("{name}.snap"));
+
+    if std::env::var_os(UPDATE_VAR).is_some_and(|value| value == "1") {
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+    let Ok(expected) = fs::read_to_string(&path) else {
+        panic!(
+            "Missing snapshot {}, create it with `{UPDATE_VAR}=1 cargo test`",
+            path.display()
+        );
+    };
+    if expected != actual {
+        let diff = TextDiff::from_lines(expected.as_str(), actual)
+            .unified_diff()
+            .header("snapshot", "rendered")
+            .to_string();
+        panic!("Snapshot {name} does not match:\n{diff}");
+    }
+}
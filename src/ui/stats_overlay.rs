@@ -0,0 +1,180 @@
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Margin, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::Theme;
+use crate::config::{GlobalAction, KeyResolver, NavAction};
+use crate::i18n::t;
+use crate::middleware::{MetricsSnapshot, Timing};
+use crate::ui::text::{padding, truncate};
+use crate::ui::{Component, EventResult, Result};
+
+/// Widest name column before names are truncated.
+const NAME_WIDTH: usize = 40;
+
+pub enum StatsEvent {
+    Close,
+}
+
+/// Metrics collected in this session, for performance work.
+pub struct StatsOverlay {
+    snapshot: MetricsSnapshot,
+    scroll: u16,
+    resolver: Arc<KeyResolver>,
+}
+
+impl StatsOverlay {
+    pub const fn new(snapshot: MetricsSnapshot, resolver: Arc<KeyResolver>) -> Self {
+        Self {
+            snapshot,
+            scroll: 0,
+            resolver,
+        }
+    }
+
+    fn lines(&self, theme: &Theme) -> Vec<Line<'static>> {
+        let section_style = Style::default()
+            .fg(theme.subtext0())
+            .add_modifier(Modifier::BOLD);
+        let header_style = Style::default().fg(theme.overlay1());
+        let text_style = Style::default().fg(theme.text());
+        let section = |title: &str| {
+            Line::from(Span::styled(
+                format!("{rule} {} {rule}", t(title), rule = theme.symbols.rule),
+                section_style,
+            ))
+        };
+        let row = |name: &str, columns: [String; 4], style: Style| {
+            let name = truncate(name, NAME_WIDTH, theme.symbols.ellipsis);
+            let columns = columns.iter().fold(String::new(), |mut line, column| {
+                let _ = write!(line, "{column:>10}");
+                line
+            });
+            Line::from(Span::styled(
+                format!("{name}{}{columns}", padding(&name, NAME_WIDTH)),
+                style,
+            ))
+        };
+        let timing_header = || {
+            let header = ["Count", "Failed", "Average", "Max"].map(|h| t(h).to_string());
+            row("", header, header_style)
+        };
+        let timing_row = |name: &str, timing: &Timing| {
+            let columns = [
+                timing.count.to_string(),
+                timing.failures.to_string(),
+                format_duration(timing.average()),
+                format_duration(timing.max),
+            ];
+            row(name, columns, text_style)
+        };
+
+        let mut lines = vec![section("Commands"), timing_header()];
+        lines.extend(
+            self.snapshot
+                .commands
+                .iter()
+                .map(|(name, timing)| timing_row(name, timing)),
+        );
+
+        lines.extend([Line::from(""), section("Messages"), timing_header()]);
+        lines.extend(
+            self.snapshot
+                .messages
+                .iter()
+                .map(|(name, timing)| timing_row(name, timing)),
+        );
+
+        let header = ["Hits", "Misses", "Hit rate", ""].map(|h| t(h).to_string());
+        lines.extend([
+            Line::from(""),
+            section("Cache"),
+            row("", header, header_style),
+        ]);
+        lines.extend(self.snapshot.cache.iter().map(|(kind, stats)| {
+            let columns = [
+                stats.hits.to_string(),
+                stats.misses.to_string(),
+                format!("{:.0}%", stats.hit_rate() * 100.0),
+                String::new(),
+            ];
+            row(kind, columns, text_style)
+        }));
+        lines
+    }
+}
+
+/// Milliseconds, with a fraction below ten.
+fn format_duration(duration: Duration) -> String {
+    let millis = duration.as_secs_f64() * 1000.0;
+    if millis < 10.0 {
+        format!("{millis:.1}ms")
+    } else {
+        format!("{millis:.0}ms")
+    }
+}
+
+impl Component for StatsOverlay {
+    type Output = StatsEvent;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        let r = &self.resolver;
+        if r.matches_global(&key, GlobalAction::Stats)
+            || r.matches_global(&key, GlobalAction::Back)
+            || r.matches_global(&key, GlobalAction::Quit)
+        {
+            return Ok(StatsEvent::Close.into());
+        }
+        if r.matches_nav(&key, NavAction::Down) {
+            self.scroll = self.scroll.saturating_add(1);
+        } else if r.matches_nav(&key, NavAction::Up) {
+            self.scroll = self.scroll.saturating_sub(1);
+        } else if r.matches_nav(&key, NavAction::Home) {
+            self.scroll = 0;
+        }
+        Ok(EventResult::Consumed)
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let popup_area = area.centered(Constraint::Percentage(80), Constraint::Percentage(80));
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(format!(" {} ", t("Stats")))
+            .title_style(
+                Style::default()
+                    .fg(theme.mauve())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_type(theme.border_type)
+            .border_style(Style::default().fg(theme.lavender()))
+            .style(Style::default().bg(theme.base()));
+        let inner = block.inner(popup_area).inner(Margin::new(1, 0));
+        frame.render_widget(block, popup_area);
+
+        let lines = self.lines(theme);
+        let max_scroll = u16::try_from(lines.len().saturating_sub(usize::from(inner.height)))
+            .unwrap_or(u16::MAX);
+        self.scroll = self.scroll.min(max_scroll);
+        frame.render_widget(Paragraph::new(lines).scroll((self.scroll, 0)), inner);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_micros(2500)), "2.5ms");
+        assert_eq!(format_duration(Duration::from_millis(1234)), "1234ms");
+    }
+}
@@ -4,12 +4,25 @@ use ratatui::Frame;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, BorderType, Borders, Paragraph};
+use ratatui::widgets::{Block, Borders, Paragraph};
 
 use crate::Theme;
-use crate::config::{GlobalAction, KeyResolver, NavAction};
+use crate::config::key::Key;
+use crate::config::{
+    GlobalAction,
+    KeyResolver,
+    NavAction,
+    SearchAction,
+    StatusBarConfig,
+    StatusBarMode,
+    StatusBarSection,
+};
 use crate::context::CloudContext;
+use crate::cost::{format_cost, session_cost};
+use crate::i18n::t;
+use crate::time::timezone_label;
 use crate::ui::Keybinding;
+use crate::ui::text::{display_width, padding, truncate};
 
 /// ASCII art logo for the status bar.
 const LOGO: &[&str] = &[
@@ -22,19 +35,46 @@ const LOGO: &[&str] = &[
     r"    `--'              ",
 ];
 
+/// Height of the full status bar: borders around the seven logo lines.
+const FULL_HEIGHT: u16 = 9;
+
 pub struct StatusBar {
     active_context: Option<CloudContext>,
+    profile: Option<String>,
+    config: StatusBarConfig,
     resolver: Arc<KeyResolver>,
 }
 
 impl StatusBar {
-    pub const fn new(resolver: Arc<KeyResolver>) -> Self {
+    pub const fn new(config: StatusBarConfig, resolver: Arc<KeyResolver>) -> Self {
         Self {
             active_context: None,
+            profile: None,
+            config,
             resolver,
         }
     }
 
+    /// Rows the status bar takes on a terminal `terminal_height` rows tall.
+    pub const fn height(&self, terminal_height: u16) -> u16 {
+        let compact = match self.config.mode {
+            StatusBarMode::Full => false,
+            StatusBarMode::Compact => true,
+            StatusBarMode::Auto => terminal_height < self.config.compact_below,
+        };
+        if self.config.sections.is_empty() {
+            0
+        } else if compact {
+            1
+        } else {
+            FULL_HEIGHT
+        }
+    }
+
+    fn shows(&self, section: StatusBarSection) -> bool {
+        self.config.sections.contains(&section)
+    }
+
     pub fn set_active_context(&mut self, context: CloudContext) {
         self.active_context = Some(context);
     }
@@ -43,6 +83,10 @@ impl StatusBar {
         self.active_context = None;
     }
 
+    pub fn set_profile(&mut self, profile: Option<String>) {
+        self.profile = profile;
+    }
+
     pub fn render_with_keybindings(
         &self,
         frame: &mut Frame,
@@ -50,33 +94,135 @@ impl StatusBar {
         theme: &Theme,
         local_keybindings: &[Keybinding],
     ) {
+        if area.height == 0 {
+            return;
+        }
+        if area.height < FULL_HEIGHT {
+            self.render_compact(frame, area, theme, local_keybindings);
+            return;
+        }
+
         // Draw outer block
         let block = Block::default()
             .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
+            .border_type(theme.border_type)
             .border_style(Style::default().fg(theme.surface1()));
 
         let inner_area = block.inner(area);
         frame.render_widget(block, area);
 
-        // Split into three columns: status (left), keybindings (middle), logo (right)
+        // Sections side by side in the configured order; only keybindings stretch
+        let constraints = self.config.sections.iter().map(|section| match section {
+            StatusBarSection::Context => Constraint::Length(42),
+            StatusBarSection::Keybindings => Constraint::Min(20),
+            StatusBarSection::Logo => Constraint::Length(25),
+        });
         let chunks = Layout::default()
             .direction(Direction::Horizontal)
-            .constraints([
-                Constraint::Length(42), // Left: status info
-                Constraint::Min(20),    // Middle: keybindings (flexible)
-                Constraint::Length(25), // Right: logo
-            ])
+            .constraints(constraints)
             .split(inner_area);
 
-        // === Left: Status Info ===
-        self.render_status_info(frame, chunks[0], theme);
+        for (section, chunk) in self.config.sections.iter().zip(chunks.iter()) {
+            match section {
+                StatusBarSection::Context => self.render_status_info(frame, *chunk, theme),
+                StatusBarSection::Keybindings => {
+                    self.render_keybindings(frame, *chunk, theme, local_keybindings);
+                }
+                StatusBarSection::Logo => Self::render_logo(frame, *chunk, theme),
+            }
+        }
+    }
 
-        // === Middle: Keybindings in columns ===
-        self.render_keybindings(frame, chunks[1], theme, local_keybindings);
+    /// Single line: context, then as many keybinding hints as fit.
+    fn render_compact(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        theme: &Theme,
+        local_keybindings: &[Keybinding],
+    ) {
+        let separator = || {
+            Span::styled(
+                format!(" {} ", theme.symbols.separator),
+                Style::default().fg(theme.surface2()),
+            )
+        };
+        let mut spans = vec![Span::raw(" ")];
+
+        if self.shows(StatusBarSection::Context) {
+            match &self.active_context {
+                Some(CloudContext::Gcp(gcp)) => {
+                    spans.push(Span::styled(
+                        gcp.display_name.clone(),
+                        Style::default()
+                            .fg(theme.lavender())
+                            .add_modifier(Modifier::BOLD),
+                    ));
+                    spans.push(Span::styled(
+                        format!(" {}", gcp.project_id),
+                        Style::default().fg(theme.subtext0()),
+                    ));
+                }
+                None => spans.push(Span::styled(
+                    t("No context"),
+                    Style::default().fg(theme.overlay0()),
+                )),
+            }
+            if let Some(profile) = &self.profile {
+                spans.push(separator());
+                spans.push(Span::styled(
+                    profile.clone(),
+                    Style::default().fg(theme.green()),
+                ));
+            }
+            if let Some(spent) = session_cost() {
+                spans.push(separator());
+                spans.push(Span::styled(
+                    format!("~{}", format_cost(spent)),
+                    Style::default().fg(theme.yellow()),
+                ));
+            }
+            spans.push(separator());
+            spans.push(Span::styled(
+                timezone_label(),
+                Style::default().fg(theme.overlay1()),
+            ));
+        }
+        if let Some(pending) = self.resolver.pending() {
+            spans.push(separator());
+            spans.push(Span::styled(
+                format!(
+                    "{} {}",
+                    Key::from_event(&pending).display(),
+                    theme.symbols.ellipsis
+                ),
+                Style::default()
+                    .fg(theme.peach())
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        if self.shows(StatusBarSection::Keybindings) {
+            let global_keybindings = self.global_keybindings();
+            let hints = local_keybindings
+                .iter()
+                .chain(global_keybindings.iter())
+                .filter(|kb| kb.hint);
+            for (i, kb) in hints.enumerate() {
+                spans.push(if i == 0 { separator() } else { Span::raw("  ") });
+                spans.push(Span::styled(
+                    kb.key.clone(),
+                    kb.style(Style::default().fg(theme.peach()), theme),
+                ));
+                spans.push(Span::styled(
+                    format!(" {}", t(&kb.description)),
+                    kb.style(Style::default().fg(theme.subtext0()), theme),
+                ));
+            }
+        }
 
-        // === Right: Logo ===
-        Self::render_logo(frame, chunks[2], theme);
+        let line = Paragraph::new(Line::from(spans)).style(Style::default().bg(theme.surface0()));
+        frame.render_widget(line, area);
     }
 
     fn render_status_info(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
@@ -84,40 +230,89 @@ impl StatusBar {
         let label_style = Style::default().fg(theme.overlay1());
         let value_style = Style::default().fg(theme.text());
 
-        let lines = match &self.active_context {
+        // The timezone of displayed timestamps sits right of the title
+        let timezone = timezone_label();
+        let title = |text: &str, style: Style| {
+            let width = w.saturating_sub(display_width(&timezone) + 1);
+            let text = truncate(text, width, "...");
+            Line::from(vec![
+                Span::styled(text.clone(), style),
+                Span::raw(padding(&text, width + 1)),
+                Span::styled(timezone.clone(), label_style),
+            ])
+        };
+
+        let mut lines = match &self.active_context {
             Some(CloudContext::Gcp(gcp)) => {
-                let region = gcp.region.as_deref().or(gcp.zone.as_deref()).unwrap_or("—");
+                let region = gcp
+                    .region
+                    .as_deref()
+                    .or(gcp.zone.as_deref())
+                    .unwrap_or(theme.symbols.none);
 
                 vec![
-                    Line::from(Span::styled(
-                        truncate_str(&gcp.display_name, w),
+                    title(
+                        &gcp.display_name,
                         Style::default()
                             .fg(theme.lavender())
                             .add_modifier(Modifier::BOLD),
-                    )),
+                    ),
                     Line::from(""),
                     status_line(
-                        "provider",
+                        t("provider"),
                         "GCP",
                         w,
                         label_style,
                         Style::default().fg(theme.blue()),
                     ),
-                    status_line("project", &gcp.project_id, w, label_style, value_style),
-                    status_line("account", &gcp.account, w, label_style, value_style),
-                    status_line("region", region, w, label_style, value_style),
+                    status_line(t("project"), &gcp.project_id, w, label_style, value_style),
+                    status_line(t("account"), &gcp.account, w, label_style, value_style),
+                    status_line(t("region"), region, w, label_style, value_style),
                 ]
             }
             None => {
-                vec![Line::from(Span::styled(
-                    "No context",
+                vec![title(
+                    t("No context"),
                     Style::default()
                         .fg(theme.overlay0())
                         .add_modifier(Modifier::BOLD),
-                ))]
+                )]
             }
         };
 
+        if let Some(pending) = self.resolver.pending() {
+            let key = Key::from_event(&pending).display();
+            lines.push(status_line(
+                t("keys"),
+                &format!("{key} {}", theme.symbols.ellipsis),
+                w,
+                label_style,
+                Style::default()
+                    .fg(theme.peach())
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        if let Some(profile) = &self.profile {
+            lines.push(status_line(
+                t("profile"),
+                profile,
+                w,
+                label_style,
+                Style::default().fg(theme.green()),
+            ));
+        }
+
+        if let Some(spent) = session_cost() {
+            lines.push(status_line(
+                t("cost"),
+                &format!("~{}", format_cost(spent)),
+                w,
+                label_style,
+                Style::default().fg(theme.yellow()),
+            ));
+        }
+
         let paragraph = Paragraph::new(lines);
         frame.render_widget(paragraph, area);
     }
@@ -145,10 +340,14 @@ impl StatusBar {
 
         // Compute alignment widths from actual content so the separator
         // forms a straight vertical line regardless of key length.
-        let max_key_w = hints.iter().map(|kb| kb.key.len()).max().unwrap_or(1);
+        let max_key_w = hints
+            .iter()
+            .map(|kb| display_width(&kb.key))
+            .max()
+            .unwrap_or(1);
         let max_desc_w = hints
             .iter()
-            .map(|kb| kb.description.len())
+            .map(|kb| display_width(t(&kb.description)))
             .max()
             .unwrap_or(1);
         // key(right-aligned) + " │ " (3) + desc + gap(2)
@@ -167,13 +366,16 @@ impl StatusBar {
 
             let line = Line::from(vec![
                 Span::styled(
-                    format!("{:>width$}", kb.key, width = max_key_w),
-                    Style::default().fg(theme.peach()),
+                    format!("{}{}", padding(&kb.key, max_key_w), kb.key),
+                    kb.style(Style::default().fg(theme.peach()), theme),
+                ),
+                Span::styled(
+                    format!(" {} ", theme.symbols.separator),
+                    Style::default().fg(theme.surface2()),
                 ),
-                Span::styled(" │ ", Style::default().fg(theme.surface2())),
                 Span::styled(
-                    kb.description.clone(),
-                    Style::default().fg(theme.subtext0()),
+                    t(&kb.description).to_string(),
+                    kb.style(Style::default().fg(theme.subtext0()), theme),
                 ),
             ]);
             columns[col_idx].push(line);
@@ -218,22 +420,97 @@ impl StatusBar {
             Keybinding::hint(self.resolver.display_global(GlobalAction::Help), "Help"),
             Keybinding::hint(self.resolver.display_global(GlobalAction::Back), "Back"),
             Keybinding::new(self.resolver.display_global(GlobalAction::Theme), "Theme"),
+            Keybinding::new(
+                self.resolver.display_global(GlobalAction::Profile),
+                "Profile",
+            ),
+            Keybinding::new(
+                self.resolver.display_global(GlobalAction::Account),
+                "Account",
+            ),
             Keybinding::new(self.resolver.display_global(GlobalAction::Quit), "Quit"),
             Keybinding::new(
                 self.resolver.display_global(GlobalAction::CommandsToggle),
                 "Commands",
             ),
-            Keybinding::new(self.resolver.display_nav(NavAction::Select), "Select"),
+            Keybinding::new(
+                self.resolver.display_global(GlobalAction::OutputToggle),
+                "Output",
+            ),
+            Keybinding::new(self.resolver.display_global(GlobalAction::Stats), "Stats"),
+            Keybinding::new(
+                self.resolver.display_global(GlobalAction::History),
+                "History",
+            ),
+            Keybinding::new(
+                self.resolver.display_global(GlobalAction::Bookmark),
+                "Bookmark",
+            ),
+            Keybinding::new(
+                self.resolver.display_global(GlobalAction::Bookmarks),
+                "Bookmarks",
+            ),
+            Keybinding::new(
+                self.resolver.display_global(GlobalAction::Workspaces),
+                "Workspaces",
+            ),
+            Keybinding::new(
+                self.resolver.display_global(GlobalAction::SwitchPrevious),
+                "Previous service",
+            ),
+            Keybinding::new(
+                self.resolver.display_global(GlobalAction::ReleaseNotes),
+                "What's new",
+            ),
             Keybinding::new(
                 format!(
                     "{}/{}",
-                    self.resolver.display_nav(NavAction::Up),
-                    self.resolver.display_nav(NavAction::Down)
+                    self.resolver.display_global(GlobalAction::FocusNext),
+                    self.resolver.display_global(GlobalAction::FocusPrev)
                 ),
-                "Navigate",
+                "Next/previous pane",
             ),
         ]
     }
+
+    /// Keys shared by every table and list, for the help overlay.
+    pub fn navigation_keybindings(&self) -> Vec<Keybinding> {
+        let nav = |action| self.resolver.display_nav(action);
+        let mut keybindings = vec![
+            Keybinding::new(
+                format!("{}/{}", nav(NavAction::Up), nav(NavAction::Down)),
+                "Move up/down",
+            ),
+            Keybinding::new(
+                format!("{}/{}", nav(NavAction::PageUp), nav(NavAction::PageDown)),
+                "Page up/down",
+            ),
+            Keybinding::new(
+                format!(
+                    "{}/{}",
+                    nav(NavAction::HalfPageUp),
+                    nav(NavAction::HalfPageDown)
+                ),
+                "Half page up/down",
+            ),
+            Keybinding::new(
+                format!("{}/{}", nav(NavAction::Home), nav(NavAction::End)),
+                "First/last row",
+            ),
+            Keybinding::new(nav(NavAction::Select), "Select"),
+            Keybinding::new(nav(NavAction::Expand), "Expand row"),
+            Keybinding::new(nav(NavAction::Preview), "Toggle preview"),
+            Keybinding::new(self.resolver.display_search(SearchAction::Toggle), "Search"),
+            Keybinding::new(
+                self.resolver.display_search(SearchAction::Exit),
+                "Clear search",
+            ),
+        ];
+        if self.resolver.counts_enabled() {
+            keybindings.push(Keybinding::new("<n>", "Repeat next motion n times"));
+        }
+        keybindings
+    }
 }
 
 /// Render a labelled status line: `  label  value` (right-aligned label, then value).
@@ -247,19 +524,42 @@ fn status_line<'a>(
     const LABEL_W: usize = 10;
     let available = max_width.saturating_sub(LABEL_W + 1);
     Line::from(vec![
-        Span::styled(format!("{label:>LABEL_W$}"), label_style),
+        Span::styled(format!("{}{label}", padding(label, LABEL_W)), label_style),
         Span::raw(" "),
-        Span::styled(truncate_str(value, available), value_style),
+        Span::styled(truncate(value, available, "..."), value_style),
     ])
 }
 
-/// Truncate a string to fit within a given width, adding "..." if truncated.
-fn truncate_str(s: &str, max_width: usize) -> String {
-    if s.len() <= max_width {
-        s.to_string()
-    } else if max_width > 3 {
-        format!("{}...", &s[..max_width - 3])
-    } else {
-        s[..max_width].to_string()
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::keybindings::KeybindingsConfig;
+    use crate::context::{AuthMethod, GcpContext};
+    use crate::ui::snapshot::{SIZES, assert_snapshots};
+
+    #[test]
+    fn test_status_bar() {
+        let resolver = Arc::new(KeyResolver::new(Arc::new(KeybindingsConfig::default())));
+        let mut status_bar = StatusBar::new(StatusBarConfig::default(), resolver);
+        status_bar.set_active_context(CloudContext::Gcp(GcpContext {
+            display_name: "demo-dev".to_string(),
+            project_id: "acme-dev".to_string(),
+            account: "demo@acme.example".to_string(),
+            region: Some("europe-west4".to_string()),
+            zone: None,
+            auth: AuthMethod::Demo,
+            accounts: Vec::new(),
+            projects: Vec::new(),
+        }));
+        status_bar.set_profile(Some("work".to_string()));
+        let hints = [Keybinding::new("d", "Delete"), Keybinding::new("n", "New")];
+
+        assert_snapshots!("status_bar", &SIZES, |frame, theme| {
+            let area = Rect {
+                height: status_bar.height(frame.area().height),
+                ..frame.area()
+            };
+            status_bar.render_with_keybindings(frame, area, theme, &hints);
+        });
     }
 }
@@ -0,0 +1,81 @@
+//! Width-aware string helpers for layout.
+//!
+//! Terminal columns are not bytes or chars: CJK characters take two cells,
+//! combining marks and zero-width joiners take none, and an emoji sequence
+//! may span several chars. These helpers measure with `unicode-width` and
+//! only cut between grapheme clusters, so truncation never splits a
+//! character or misaligns the columns after it.
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Number of terminal cells `s` occupies.
+pub fn display_width(s: &str) -> usize {
+    s.width()
+}
+
+/// Longest prefix of `s` that fits in `max_width` cells.
+pub fn take_width(s: &str, max_width: usize) -> &str {
+    let mut width = 0;
+    for (offset, grapheme) in s.grapheme_indices(true) {
+        width += grapheme.width();
+        if width > max_width {
+            return &s[..offset];
+        }
+    }
+    s
+}
+
+/// Fit `s` into `max_width` cells, ending with `ellipsis` when it is cut.
+pub fn truncate(s: &str, max_width: usize, ellipsis: &str) -> String {
+    if display_width(s) <= max_width {
+        return s.to_string();
+    }
+    let ellipsis_width = display_width(ellipsis);
+    if max_width <= ellipsis_width {
+        return take_width(ellipsis, max_width).to_string();
+    }
+    format!("{}{ellipsis}", take_width(s, max_width - ellipsis_width))
+}
+
+/// Spaces needed after `s` to fill `width` cells.
+pub fn padding(s: &str, width: usize) -> String {
+    " ".repeat(width.saturating_sub(display_width(s)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_width() {
+        assert_eq!(display_width("secret"), 6);
+        assert_eq!(display_width("密钥"), 4);
+        assert_eq!(display_width("e\u{301}"), 1);
+        assert_eq!(display_width("🔑"), 2);
+    }
+
+    #[test]
+    fn test_take_width_keeps_graphemes_whole() {
+        assert_eq!(take_width("密钥管理", 5), "密钥");
+        assert_eq!(take_width("cafe\u{301}s", 4), "cafe\u{301}");
+        assert_eq!(take_width("🔑key", 1), "");
+        assert_eq!(take_width("key", 10), "key");
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("api-key", 7, "..."), "api-key");
+        assert_eq!(truncate("stripe-api-key", 9, "..."), "stripe...");
+        assert_eq!(truncate("数据库密码", 7, "…"), "数据库…");
+        assert_eq!(truncate("数据库密码", 8, "…"), "数据库…");
+        assert_eq!(truncate("secret", 2, "..."), "..");
+        assert_eq!(truncate("secret", 0, "..."), "");
+    }
+
+    #[test]
+    fn test_padding() {
+        assert_eq!(padding("密钥", 6), "  ");
+        assert_eq!(padding("too long", 3), "");
+    }
+}
@@ -8,17 +8,35 @@ use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
 use super::{Component, EventResult, Result};
 use crate::Theme;
+use crate::i18n::t_args;
+
+/// Toasts kept waiting behind the visible ones, the oldest are dropped.
+const MAX_QUEUED: usize = 20;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ToastType {
     Success,
     Info,
+    Warning,
+    Error,
+}
+
+impl ToastType {
+    /// How long a toast stays up, longer for the ones that need reading.
+    const fn duration(self) -> Duration {
+        match self {
+            Self::Success | Self::Info => Duration::from_secs(3),
+            Self::Warning => Duration::from_secs(5),
+            Self::Error => Duration::from_secs(8),
+        }
+    }
 }
 
 pub struct Toast {
     message: String,
     kind: ToastType,
-    created_at: Instant,
+    /// When the toast came into view, its timer only runs from then.
+    shown_at: Option<Instant>,
     duration: Duration,
 }
 
@@ -27,8 +45,8 @@ impl Toast {
         Self {
             message: message.into(),
             kind,
-            created_at: Instant::now(),
-            duration: Duration::from_secs(3),
+            shown_at: None,
+            duration: kind.duration(),
         }
     }
 
@@ -40,11 +58,29 @@ impl Toast {
         Self::new(message, ToastType::Info)
     }
 
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(message, ToastType::Warning)
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(message, ToastType::Error)
+    }
+
+    /// Keep the toast up for `duration` instead of the default of its type.
+    #[must_use]
+    pub const fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
     fn is_expired(&self) -> bool {
-        self.created_at.elapsed() >= self.duration
+        self.shown_at
+            .is_some_and(|shown_at| shown_at.elapsed() >= self.duration)
     }
 }
 
+/// Toasts stacked in the bottom right corner, oldest at the bottom. Toasts
+/// beyond `max_visible` wait their turn behind a `+N more` indicator.
 pub struct ToastManager {
     toasts: VecDeque<Toast>,
     max_visible: usize,
@@ -66,10 +102,23 @@ impl ToastManager {
 
     pub fn show(&mut self, toast: Toast) {
         self.toasts.push_back(toast);
-        // Keep only max_visible toasts
-        while self.toasts.len() > self.max_visible {
+        while self.toasts.len() > self.max_visible + MAX_QUEUED {
             self.toasts.pop_front();
         }
+        self.start_timers();
+    }
+
+    /// Start the timers of toasts that just came into view.
+    fn start_timers(&mut self) {
+        let now = Instant::now();
+        for toast in self.toasts.iter_mut().take(self.max_visible) {
+            toast.shown_at.get_or_insert(now);
+        }
+    }
+
+    /// Dismiss every toast still showing.
+    pub fn clear(&mut self) {
+        self.toasts.clear();
     }
 }
 
@@ -91,25 +140,28 @@ impl Component for ToastManager {
         let toast_height = 3u16;
         let toast_width = 50u16.min(area.width.saturating_sub(4));
         let spacing = 1u16;
+        let x = area.x + area.width.saturating_sub(toast_width + 2);
+        // Top of the highest toast drawn, for the overflow indicator
+        let mut top = area.bottom();
 
         // Stack toasts from bottom-right, going upward
-        let visible_toasts: Vec<_> = self.toasts.iter().collect();
-
-        for (i, toast) in visible_toasts.iter().enumerate() {
+        for (i, toast) in self.toasts.iter().take(self.max_visible).enumerate() {
             #[allow(clippy::cast_possible_truncation)]
             let y_offset = (i as u16) * (toast_height + spacing);
             let y = area.y + area.height.saturating_sub(toast_height + y_offset + 1);
-            let x = area.x + area.width.saturating_sub(toast_width + 2);
 
-            if y < area.y {
+            if y < area.y || y >= top {
                 break; // No more room
             }
+            top = y;
 
             let toast_area = Rect::new(x, y, toast_width, toast_height);
 
             let (border_color, icon) = match toast.kind {
-                ToastType::Success => (theme.green(), "✓"),
-                ToastType::Info => (theme.blue(), "ℹ"),
+                ToastType::Success => (theme.green(), theme.symbols.success),
+                ToastType::Info => (theme.blue(), theme.symbols.info),
+                ToastType::Warning => (theme.yellow(), theme.symbols.warning),
+                ToastType::Error => (theme.red(), theme.symbols.failure),
             };
 
             frame.render_widget(Clear, toast_area);
@@ -138,9 +190,43 @@ impl Component for ToastManager {
 
             frame.render_widget(paragraph, content_area);
         }
+
+        let hidden = self.toasts.len().saturating_sub(self.max_visible);
+        if hidden > 0 && top > area.y {
+            let more = t_args("+{count} more", &[("count", &hidden.to_string())]);
+            frame.render_widget(
+                Paragraph::new(more)
+                    .style(Style::default().fg(theme.subtext0()))
+                    .alignment(Alignment::Right),
+                Rect::new(x, top - 1, toast_width, 1),
+            );
+        }
     }
 
     fn handle_tick(&mut self) {
         self.toasts.retain(|t| !t.is_expired());
+        self.start_timers();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queued_toasts_wait_to_be_shown() {
+        let mut manager = ToastManager::new();
+        for i in 0..5 {
+            manager.show(Toast::error(format!("toast {i}")).with_duration(Duration::ZERO));
+        }
+        assert_eq!(manager.toasts.len(), 5);
+        assert!(manager.toasts[2].shown_at.is_some());
+        assert!(manager.toasts[3].shown_at.is_none());
+
+        // Only the visible ones expire, the queued ones move up
+        manager.handle_tick();
+        assert_eq!(manager.toasts.len(), 2);
+        assert_eq!(manager.toasts[0].message, "toast 3");
+        assert!(manager.toasts.iter().all(|toast| toast.shown_at.is_some()));
     }
 }
@@ -1,3 +1,8 @@
+pub mod charts;
+mod progress;
+mod qr_code;
 mod spinner;
 
-pub use spinner::Spinner;
+pub use progress::{ProgressBar, TaskList};
+pub use qr_code::QrCode;
+pub use spinner::{Spinner, reduced_motion, set_reduced_motion, throbber_set};
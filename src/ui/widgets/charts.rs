@@ -0,0 +1,258 @@
+//! Themed charts for metrics views.
+//!
+//! Thin wrappers around the ratatui chart widgets that pick colors from the
+//! [`Theme`] and derive axis bounds and labels from the data, so monitoring
+//! screens only provide the values.
+
+use chrono::{DateTime, Utc};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::symbols::bar;
+use ratatui::text::Line;
+use ratatui::widgets::{
+    Axis,
+    Bar,
+    BarChart as RatatuiBarChart,
+    BarGroup,
+    Chart,
+    Dataset,
+    GraphType,
+    Sparkline as RatatuiSparkline,
+};
+
+use crate::Theme;
+use crate::theme::Symbols;
+use crate::time::format_with;
+
+/// Gap between bars in a [`BarChart`].
+const BAR_GAP: u16 = 1;
+
+/// A metric value at a point in time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimePoint {
+    pub at: DateTime<Utc>,
+    pub value: f64,
+}
+
+/// Single-row trend line without axes, e.g. next to a summary value.
+pub struct Sparkline<'a> {
+    data: &'a [u64],
+}
+
+impl<'a> Sparkline<'a> {
+    pub const fn new(data: &'a [u64]) -> Self {
+        Self { data }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let sparkline = RatatuiSparkline::default()
+            .data(self.data)
+            .bar_set(bar_set(theme.symbols))
+            .style(Style::default().fg(theme.blue()));
+        frame.render_widget(sparkline, area);
+    }
+}
+
+/// Labelled vertical bars that share the available width.
+pub struct BarChart<'a> {
+    bars: &'a [(String, u64)],
+    show_values: bool,
+}
+
+impl<'a> BarChart<'a> {
+    pub const fn new(bars: &'a [(String, u64)]) -> Self {
+        Self {
+            bars,
+            show_values: false,
+        }
+    }
+
+    /// Print each value on top of its bar.
+    #[allow(dead_code)]
+    pub const fn with_values(mut self) -> Self {
+        self.show_values = true;
+        self
+    }
+
+    /// Widest bars that still fit `width` columns.
+    fn bar_width(&self, width: u16) -> u16 {
+        let count = u16::try_from(self.bars.len()).unwrap_or(u16::MAX).max(1);
+        (width / count).saturating_sub(BAR_GAP).max(1)
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let bars: Vec<Bar> = self
+            .bars
+            .iter()
+            .map(|(label, value)| {
+                let bar = Bar::default()
+                    .value(*value)
+                    .label(Line::from(label.as_str()));
+                if self.show_values {
+                    bar.text_value(format_value(as_f64(*value)))
+                } else {
+                    bar.text_value(String::new())
+                }
+            })
+            .collect();
+        let chart = RatatuiBarChart::default()
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(self.bar_width(area.width))
+            .bar_gap(BAR_GAP)
+            .bar_set(bar_set(theme.symbols))
+            .bar_style(Style::default().fg(theme.blue()))
+            .value_style(Style::default().fg(theme.base()).bg(theme.blue()))
+            .label_style(Style::default().fg(theme.overlay1()));
+        frame.render_widget(chart, area);
+    }
+}
+
+/// Line chart over time drawn with Braille dots, with labelled axes.
+#[allow(dead_code)]
+pub struct TimeSeriesChart<'a> {
+    points: &'a [TimePoint],
+    name: Option<&'a str>,
+    time_format: &'a str,
+}
+
+#[allow(dead_code)]
+impl<'a> TimeSeriesChart<'a> {
+    pub const fn new(points: &'a [TimePoint]) -> Self {
+        Self {
+            points,
+            name: None,
+            time_format: "%m-%d %H:%M",
+        }
+    }
+
+    /// Legend entry for the series.
+    pub const fn with_name(mut self, name: &'a str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// `chrono` format for the time axis labels.
+    pub const fn with_time_format(mut self, format: &'a str) -> Self {
+        self.time_format = format;
+        self
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let (Some(first), Some(last)) = (self.points.first(), self.points.last()) else {
+            return;
+        };
+        let data: Vec<(f64, f64)> = self
+            .points
+            .iter()
+            .map(|point| (point.at.timestamp() as f64, point.value))
+            .collect();
+        let x_bounds = [first.at.timestamp() as f64, last.at.timestamp() as f64];
+        let y_max = self
+            .points
+            .iter()
+            .map(|point| point.value)
+            .fold(0.0, f64::max);
+        let y_bounds = [0.0, if y_max > 0.0 { y_max } else { 1.0 }];
+
+        let label_style = Style::default().fg(theme.overlay1());
+        let middle = first.at + (last.at - first.at) / 2;
+        let x_labels = [first.at, middle, last.at]
+            .map(|at| Line::styled(format_with(at, self.time_format), label_style));
+        let y_labels = [0.0, y_bounds[1] / 2.0, y_bounds[1]]
+            .map(|value| Line::styled(format_value(value), label_style));
+
+        let mut dataset = Dataset::default()
+            .marker(theme.symbols.chart_marker)
+            .graph_type(GraphType::Line)
+            .style(Style::default().fg(theme.blue()))
+            .data(&data);
+        if let Some(name) = self.name {
+            dataset = dataset.name(name);
+        }
+        let axis_style = Style::default().fg(theme.surface2());
+        let chart = Chart::new(vec![dataset])
+            .x_axis(
+                Axis::default()
+                    .style(axis_style)
+                    .bounds(x_bounds)
+                    .labels(x_labels),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(axis_style)
+                    .bounds(y_bounds)
+                    .labels(y_labels),
+            );
+        frame.render_widget(chart, area);
+    }
+}
+
+const fn bar_set(symbols: &Symbols) -> bar::Set<'static> {
+    let [
+        empty,
+        one_eighth,
+        one_quarter,
+        three_eighths,
+        half,
+        five_eighths,
+        three_quarters,
+        seven_eighths,
+        full,
+    ] = symbols.bars;
+    bar::Set {
+        full,
+        seven_eighths,
+        three_quarters,
+        five_eighths,
+        half,
+        three_eighths,
+        one_quarter,
+        one_eighth,
+        empty,
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+const fn as_f64(value: u64) -> f64 {
+    value as f64
+}
+
+/// Compact axis label, e.g. `950`, `1.2k` or `3.4M`.
+pub fn format_value(value: f64) -> String {
+    let abs = value.abs();
+    if abs >= 1_000_000_000.0 {
+        format!("{:.1}G", value / 1_000_000_000.0)
+    } else if abs >= 1_000_000.0 {
+        format!("{:.1}M", value / 1_000_000.0)
+    } else if abs >= 1_000.0 {
+        format!("{:.1}k", value / 1_000.0)
+    } else if value.fract() == 0.0 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.1}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_value() {
+        assert_eq!(format_value(0.0), "0");
+        assert_eq!(format_value(950.0), "950");
+        assert_eq!(format_value(2.5), "2.5");
+        assert_eq!(format_value(1_240.0), "1.2k");
+        assert_eq!(format_value(3_400_000.0), "3.4M");
+    }
+
+    #[test]
+    fn test_bars_share_width() {
+        let bars: Vec<(String, u64)> = (0..10).map(|i| (i.to_string(), i)).collect();
+        let chart = BarChart::new(&bars);
+        assert_eq!(chart.bar_width(60), 5);
+        assert_eq!(chart.bar_width(5), 1);
+    }
+}
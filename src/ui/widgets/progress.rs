@@ -0,0 +1,139 @@
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+
+use crate::Theme;
+use crate::commands::{StepStatus, TrackedStep};
+use crate::ui::Component;
+use crate::ui::command_panel::format_duration;
+use crate::ui::text::{display_width, padding, truncate};
+
+/// Width of the duration column of a [`TaskList`].
+const DURATION_WIDTH: usize = 8;
+
+/// Bar of finished items, e.g. `████░░░░ 12/40`.
+pub struct ProgressBar {
+    done: usize,
+    total: usize,
+}
+
+impl ProgressBar {
+    pub const fn new(done: usize, total: usize) -> Self {
+        Self { done, total }
+    }
+
+    /// The bar and its counts in `width` cells.
+    pub fn line(&self, width: usize, theme: &Theme) -> Line<'static> {
+        let done = self.done.min(self.total);
+        let counts = format!(" {done}/{}", self.total);
+        let bar_width = width.saturating_sub(display_width(&counts));
+        let filled = (bar_width * done)
+            .checked_div(self.total)
+            .unwrap_or(bar_width);
+        let color = if done == self.total {
+            theme.green()
+        } else {
+            theme.peach()
+        };
+
+        Line::from(vec![
+            Span::styled(
+                theme.symbols.gauge_filled.repeat(filled),
+                Style::default().fg(color),
+            ),
+            Span::styled(
+                theme.symbols.gauge_empty.repeat(bar_width - filled),
+                Style::default().fg(theme.surface2()),
+            ),
+            Span::styled(counts, Style::default().fg(theme.subtext0())),
+        ])
+    }
+}
+
+impl Component for ProgressBar {
+    type Output = ();
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let line = self.line(usize::from(area.width), theme);
+        frame.render_widget(Paragraph::new(line), area);
+    }
+}
+
+/// Steps of a batch operation or workflow, each with its state and how long
+/// it ran.
+pub struct TaskList {
+    steps: Vec<TrackedStep>,
+}
+
+impl TaskList {
+    pub const fn new(steps: Vec<TrackedStep>) -> Self {
+        Self { steps }
+    }
+
+    /// One line per step in `width` cells, the durations aligned right.
+    pub fn lines(&self, width: usize, theme: &Theme) -> Vec<Line<'static>> {
+        // Icon and the space after it
+        let name_width = width.saturating_sub(2 + DURATION_WIDTH);
+        self.steps
+            .iter()
+            .map(|step| {
+                let (icon, color) = status_icon(step.status, theme);
+                let name = truncate(&step.name, name_width, theme.symbols.ellipsis);
+                let duration = step.elapsed().map(format_duration).unwrap_or_default();
+                Line::from(vec![
+                    Span::styled(icon, Style::default().fg(color)),
+                    Span::raw(" "),
+                    Span::styled(name.clone(), Style::default().fg(theme.subtext0())),
+                    Span::raw(padding(&name, name_width)),
+                    Span::styled(
+                        format!("{duration:>DURATION_WIDTH$}"),
+                        Style::default().fg(theme.overlay0()),
+                    ),
+                ])
+            })
+            .collect()
+    }
+}
+
+impl Component for TaskList {
+    type Output = ();
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let lines = self.lines(usize::from(area.width), theme);
+        frame.render_widget(Paragraph::new(lines), area);
+    }
+}
+
+/// Icon and color of a step in `status`.
+const fn status_icon(status: StepStatus, theme: &Theme) -> (&'static str, Color) {
+    match status {
+        StepStatus::Pending => (theme.symbols.pending, theme.overlay1()),
+        StepStatus::Running => (theme.symbols.ready, theme.peach()),
+        StepStatus::Done => (theme.symbols.success, theme.green()),
+        StepStatus::Failed | StepStatus::RolledBack => (theme.symbols.failure, theme.red()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(line: &Line) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn test_progress_bar_fills_width() {
+        let theme = Theme::no_color();
+        let line = ProgressBar::new(1, 4).line(16, &theme);
+        assert_eq!(text(&line), "###--------- 1/4");
+
+        let line = ProgressBar::new(0, 0).line(8, &theme);
+        assert_eq!(text(&line), "#### 0/0");
+    }
+}
@@ -0,0 +1,101 @@
+use qrcode::{Color as Module, EcLevel};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+
+use crate::Theme;
+use crate::i18n::t;
+use crate::ui::Component;
+
+/// Light modules kept around the code so scanners can find its edges.
+const QUIET_ZONE: usize = 2;
+
+/// A QR code drawn with half blocks, two modules to a cell.
+///
+/// The blocks draw the light modules, so the code stays scannable on dark
+/// terminals even when colors are not shown.
+pub struct QrCode {
+    lines: Vec<String>,
+}
+
+impl QrCode {
+    /// Encode `data`, or `None` when it is too long for a QR code.
+    pub fn new(data: &[u8]) -> Option<Self> {
+        let code = qrcode::QrCode::with_error_correction_level(data, EcLevel::L).ok()?;
+        let width = code.width();
+        let modules = code.to_colors();
+        let dark = |x: usize, y: usize| {
+            let (Some(x), Some(y)) = (x.checked_sub(QUIET_ZONE), y.checked_sub(QUIET_ZONE)) else {
+                return false;
+            };
+            x < width && y < width && modules[y * width + x] == Module::Dark
+        };
+
+        let size = width + 2 * QUIET_ZONE;
+        let lines = (0..size)
+            .step_by(2)
+            .map(|y| {
+                (0..size)
+                    .map(|x| match (dark(x, y), dark(x, y + 1)) {
+                        (false, false) => '█',
+                        (false, true) => '▀',
+                        (true, false) => '▄',
+                        (true, true) => ' ',
+                    })
+                    .collect()
+            })
+            .collect();
+        Some(Self { lines })
+    }
+
+    /// Cells needed to show the whole code.
+    pub fn size(&self) -> (u16, u16) {
+        let width = self.lines.first().map_or(0, |line| line.chars().count());
+        (
+            u16::try_from(width).unwrap_or(u16::MAX),
+            u16::try_from(self.lines.len()).unwrap_or(u16::MAX),
+        )
+    }
+}
+
+impl Component for QrCode {
+    type Output = ();
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let (width, height) = self.size();
+        // A cropped code does not scan, ask for room instead
+        if width > area.width || height > area.height {
+            let message = Paragraph::new(t("Enlarge the terminal to show the QR code"))
+                .style(Style::default().fg(theme.yellow()))
+                .centered();
+            frame.render_widget(message, area);
+            return;
+        }
+
+        let lines: Vec<Line> = self
+            .lines
+            .iter()
+            .map(|line| Line::raw(line.as_str()))
+            .collect();
+        let code = Paragraph::new(lines).style(Style::default().fg(Color::White).bg(Color::Black));
+        frame.render_widget(
+            code,
+            area.centered(Constraint::Length(width), Constraint::Length(height)),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_qr_code_size() {
+        // Version 1 is 21 modules wide
+        let code = QrCode::new(b"123456").unwrap();
+        assert_eq!(code.size(), (25, 13));
+        assert!(QrCode::new(&[b'a'; 4000]).is_none());
+    }
+}
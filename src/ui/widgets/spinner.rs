@@ -1,11 +1,39 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Rect};
 use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
 use throbber_widgets_tui::WhichUse::Spin;
-use throbber_widgets_tui::{BRAILLE_SIX, Throbber, ThrobberState};
+use throbber_widgets_tui::{Set, Throbber, ThrobberState};
 
 use crate::Theme;
+use crate::i18n::t;
+use crate::theme::Symbols;
 use crate::ui::Component;
+use crate::ui::text::display_width;
+
+static REDUCED_MOTION: AtomicBool = AtomicBool::new(false);
+
+/// Show static indicators instead of animated ones from now on.
+pub fn set_reduced_motion(enabled: bool) {
+    REDUCED_MOTION.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether spinners and progress indicators stand still.
+pub fn reduced_motion() -> bool {
+    REDUCED_MOTION.load(Ordering::Relaxed)
+}
+
+/// Throbber frames for the theme's symbols.
+pub const fn throbber_set(symbols: &Symbols) -> Set {
+    Set {
+        full: symbols.spinner[0],
+        empty: " ",
+        symbols: symbols.spinner,
+    }
+}
 
 pub struct Spinner {
     throbber_state: ThrobberState,
@@ -39,8 +67,21 @@ impl Component for Spinner {
     }
 
     fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        if reduced_motion() {
+            let label = self.label.unwrap_or_else(|| t("Loading..."));
+            let line = Line::from(vec![
+                Span::styled(theme.symbols.pending, Style::default().fg(theme.lavender())),
+                Span::raw(" "),
+                Span::styled(label, Style::default().fg(theme.subtext1())),
+            ]);
+            let width = u16::try_from(line.width()).unwrap_or(u16::MAX);
+            let area = area.centered(Constraint::Length(width), Constraint::Length(1));
+            frame.render_widget(Paragraph::new(line), area);
+            return;
+        }
+
         let mut throbber = Throbber::default()
-            .throbber_set(BRAILLE_SIX)
+            .throbber_set(throbber_set(theme.symbols))
             .use_type(Spin)
             .throbber_style(Style::default().fg(theme.lavender()))
             .style(Style::default().fg(theme.subtext1()));
@@ -50,10 +91,8 @@ impl Component for Spinner {
 
         if let Some(label) = self.label {
             throbber = throbber.label(label);
-            #[allow(clippy::cast_possible_truncation)]
-            {
-                width += label.len() as u16 + 1;
-            } // +1 for space between throbber and label
+            // +1 for space between throbber and label
+            width += u16::try_from(display_width(label) + 1).unwrap_or(u16::MAX);
         }
 
         let area = area.centered(Constraint::Length(width), Constraint::Length(1));
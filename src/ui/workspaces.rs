@@ -0,0 +1,229 @@
+use std::sync::Arc;
+
+use crossterm::event::KeyEvent;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Rect};
+use ratatui::widgets::{Cell, Clear};
+
+use crate::Theme;
+use crate::config::{GlobalAction, KeyResolver, NavAction, Workspace, WorkspaceAction};
+use crate::i18n::t;
+use crate::search::Matcher;
+use crate::ui::{
+    ColumnDef,
+    Component,
+    EventResult,
+    Keybinding,
+    Result,
+    Table,
+    TableEvent,
+    TableRow,
+    TextInput,
+    TextInputEvent,
+};
+
+pub enum WorkspaceEvent {
+    Restore(Workspace),
+    /// Save the open services under the name.
+    Save(String),
+    Delete(String),
+    Close,
+}
+
+impl TableRow for Workspace {
+    fn columns() -> &'static [ColumnDef] {
+        static COLUMNS: &[ColumnDef] = &[
+            ColumnDef::new("Name", Constraint::Length(20)),
+            ColumnDef::new("Open", Constraint::Min(30)),
+            ColumnDef::new("Previous", Constraint::Min(30)),
+        ];
+        COLUMNS
+    }
+
+    fn render_cells(&self, _theme: &Theme) -> Vec<Cell<'static>> {
+        vec![
+            Cell::from(self.name.clone()),
+            Cell::from(self.location.clone()),
+            Cell::from(self.previous.clone().unwrap_or_default()),
+        ]
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        let matcher = Matcher::new();
+        matcher.matches(&self.name, query) || matcher.matches(&self.location, query)
+    }
+
+    fn details(&self) -> Vec<(&'static str, String)> {
+        vec![(
+            "Output pane",
+            if self.output { "open" } else { "closed" }.to_string(),
+        )]
+    }
+}
+
+/// Saved workspaces, with a name prompt to save the open services as one.
+pub struct WorkspacesView {
+    table: Table<Workspace>,
+    /// The name prompt while saving.
+    name: Option<TextInput>,
+    resolver: Arc<KeyResolver>,
+}
+
+impl WorkspacesView {
+    pub fn new(workspaces: Vec<Workspace>, resolver: Arc<KeyResolver>) -> Self {
+        let mut view = Self {
+            table: Table::new(workspaces, resolver.clone()),
+            name: None,
+            resolver,
+        };
+        view.update_title();
+        view
+    }
+
+    fn update_title(&mut self) {
+        let count = self.table.items().len();
+        self.table
+            .set_title(format!(" {} ({count}) ", t("Workspaces")));
+    }
+
+    fn name_input(&self) -> TextInput {
+        let input = TextInput::new(t("Save workspace as"))
+            .with_placeholder("oncall")
+            .with_validator(|value| {
+                if value.trim().is_empty() {
+                    Err("Enter a name".to_string())
+                } else {
+                    Ok(())
+                }
+            });
+        // Start from the selected name to overwrite it
+        match self.table.selected_item() {
+            Some(workspace) => input.with_value(&workspace.name),
+            None => input,
+        }
+    }
+
+    pub fn keybindings(&self) -> Vec<Keybinding> {
+        if self.name.is_some() {
+            return Vec::new();
+        }
+        vec![
+            Keybinding::hint(self.resolver.display_nav(NavAction::Select), "Restore"),
+            Keybinding::new(
+                self.resolver.display_workspaces(WorkspaceAction::Save),
+                "Save current",
+            ),
+            Keybinding::new(
+                self.resolver.display_workspaces(WorkspaceAction::Delete),
+                "Delete",
+            ),
+        ]
+    }
+}
+
+impl Component for WorkspacesView {
+    type Output = WorkspaceEvent;
+
+    fn handle_key(&mut self, key: KeyEvent) -> Result<EventResult<Self::Output>> {
+        if let Some(input) = &mut self.name {
+            return Ok(match input.handle_key(key)? {
+                EventResult::Event(TextInputEvent::Submitted(name)) => {
+                    self.name = None;
+                    WorkspaceEvent::Save(name.trim().to_string()).into()
+                }
+                EventResult::Event(TextInputEvent::Cancelled) => {
+                    self.name = None;
+                    EventResult::Consumed
+                }
+                _ => EventResult::Consumed,
+            });
+        }
+
+        let result = self.table.handle_key(key)?;
+        if let EventResult::Event(TableEvent::Activated(workspace)) = result {
+            return Ok(WorkspaceEvent::Restore(workspace).into());
+        }
+        if result.is_consumed() {
+            return Ok(EventResult::Consumed);
+        }
+        let r = &self.resolver;
+        if r.matches_global(&key, GlobalAction::Workspaces)
+            || r.matches_global(&key, GlobalAction::Back)
+            || r.matches_global(&key, GlobalAction::Quit)
+        {
+            return Ok(WorkspaceEvent::Close.into());
+        }
+        if r.matches_workspaces(&key, WorkspaceAction::Save) {
+            self.name = Some(self.name_input());
+        } else if r.matches_workspaces(&key, WorkspaceAction::Delete)
+            && let Some(name) = self.table.selected_item().map(|w| w.name.clone())
+        {
+            let workspaces = self
+                .table
+                .items()
+                .iter()
+                .filter(|workspace| workspace.name != name)
+                .cloned()
+                .collect();
+            self.table.set_items(workspaces);
+            self.update_title();
+            return Ok(WorkspaceEvent::Delete(name).into());
+        }
+        Ok(EventResult::Consumed)
+    }
+
+    fn handle_paste(&mut self, text: &str) -> Result<EventResult<Self::Output>> {
+        match &mut self.name {
+            Some(input) => Ok(input.handle_paste(text)?.consumed_only()),
+            None => Ok(self.table.handle_paste(text)?.consumed_only()),
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        frame.render_widget(Clear, area);
+        self.table.render(frame, area, theme);
+        if let Some(input) = &mut self.name {
+            input.render(frame, area, theme);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use super::*;
+    use crate::config::keybindings::KeybindingsConfig;
+
+    fn workspace(name: &str) -> Workspace {
+        Workspace {
+            name: name.to_string(),
+            location: "lazycloud://gcp/prod/secret-manager/db-password".to_string(),
+            previous: None,
+            output: false,
+        }
+    }
+
+    #[test]
+    fn test_save_and_delete() {
+        let resolver = Arc::new(KeyResolver::new(Arc::new(KeybindingsConfig::default())));
+        let mut view = WorkspacesView::new(
+            vec![workspace("oncall"), workspace("release-day")],
+            resolver,
+        );
+
+        // Saving starts from the selected name, to overwrite it
+        view.handle_key(KeyEvent::from(KeyCode::Char('s'))).unwrap();
+        view.handle_key(KeyEvent::from(KeyCode::Char('2'))).unwrap();
+        let event = view.handle_key(KeyEvent::from(KeyCode::Enter)).unwrap();
+        assert!(
+            matches!(event, EventResult::Event(WorkspaceEvent::Save(name)) if name == "oncall2")
+        );
+
+        let event = view.handle_key(KeyEvent::from(KeyCode::Char('d'))).unwrap();
+        assert!(
+            matches!(event, EventResult::Event(WorkspaceEvent::Delete(name)) if name == "oncall")
+        );
+        assert_eq!(view.table.items().len(), 1);
+    }
+}